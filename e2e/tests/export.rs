@@ -0,0 +1,162 @@
+// E2E tests that run against a real Postgres instance.
+
+#![cfg(test)]
+
+use anyhow::{Context, Result};
+use bookshelf_e2e::*;
+use reqwest::{
+    Client, StatusCode,
+    multipart::{Form, Part},
+};
+use serial_test::serial;
+
+const MAPPING: &str = r#"{
+    "title": "Title",
+    "authors": "Authors",
+    "isbn": "ISBN",
+    "read": "Read",
+    "owned": "Owned",
+    "priority": "Priority",
+    "format": "Format",
+    "store": "Store"
+}"#;
+
+const CSV: &str = "Title,Authors,ISBN,Read,Owned,Priority,Format,Store\n\
+                   Export Book One,\"Export, Author\"; Export Other Author,9784061596931,yes,no,70,eBook,Kindle\n\
+                   Export Book Two,Export Other Author,,,x,,Printed,\n\
+                   Export Book Three,\"\"\"Export; Semicolon\"\"\",,,,,,\n";
+
+async fn export(path: &str, token: Option<&str>) -> Result<(StatusCode, String)> {
+    let mut request = Client::new().get(format!("{}/export/{}", get_server_url()?, path));
+    if let Some(token) = token {
+        request = request.header("Authorization", format!("Bearer {}", token));
+    }
+    let res = request.send().await.context("request failed")?;
+    let status = res.status();
+    let body = res.text().await.context("invalid body")?;
+    Ok((status, body))
+}
+
+async fn import_csv(token: &str, csv: &str) -> Result<serde_json::Value> {
+    let form = Form::new()
+        .part(
+            "file",
+            Part::bytes(csv.as_bytes().to_vec()).file_name("books.csv"),
+        )
+        .text("mapping", MAPPING);
+    let res = Client::new()
+        .post(format!("{}/import/csv", get_server_url()?))
+        .header("Authorization", format!("Bearer {}", token))
+        .multipart(form)
+        .send()
+        .await
+        .context("request failed")?;
+    assert_eq!(res.status(), StatusCode::OK);
+    res.json().await.context("invalid JSON")
+}
+
+// The entries of an exported library, by title. Authors are sorted by name,
+// as books do not keep the order they were given in.
+fn entries(library: &str) -> Result<Vec<serde_json::Value>> {
+    let library: serde_json::Value = serde_json::from_str(library)?;
+    let mut entries: Vec<serde_json::Value> = library["books"]
+        .as_array()
+        .context("books should be an array")?
+        .iter()
+        .map(|book| {
+            let mut entry = book["entry"].clone();
+            if let Some(author_names) = entry["authorNames"].as_array_mut() {
+                author_names.sort_by_key(|name| name.to_string());
+            }
+            entry
+        })
+        .collect();
+    entries.sort_by_key(|entry| entry["title"].to_string());
+    Ok(entries)
+}
+
+#[tokio::test]
+async fn e2e_export_without_auth_returns_401() -> Result<()> {
+    let (status, _) = export("books.csv", None).await?;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+
+    let (status, _) = export("library.json", None).await?;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+    Ok(())
+}
+
+#[tokio::test]
+#[serial]
+async fn e2e_csv_export_round_trips_through_the_csv_importer() -> Result<()> {
+    let (_user_id, token) = create_test_user().await?;
+    import_csv(&token, CSV).await?;
+
+    let (status, exported) = export("books.csv", Some(&token)).await?;
+    assert_eq!(status, StatusCode::OK);
+    assert!(exported.starts_with(
+        "ID,Title,Authors,ISBN,Read,Owned,Priority,Format,Store,Created At,Updated At\n"
+    ));
+    assert_eq!(exported.lines().count(), 4);
+
+    // Importing the export for another user recreates the same books
+    let (_other_user_id, other_token) = create_test_user().await?;
+    let imported = import_csv(&other_token, &exported).await?;
+    assert_eq!(imported["created"].as_array().map(Vec::len), Some(3));
+    let mut new_author_names: Vec<&str> = imported["newAuthorNames"]
+        .as_array()
+        .context("newAuthorNames should be an array")?
+        .iter()
+        .filter_map(serde_json::Value::as_str)
+        .collect();
+    new_author_names.sort();
+    assert_eq!(
+        new_author_names,
+        ["Export Other Author", "Export, Author", "Export; Semicolon"]
+    );
+
+    let (_, library) = export("library.json", Some(&token)).await?;
+    let (_, other_library) = export("library.json", Some(&other_token)).await?;
+    assert_eq!(entries(&other_library)?, entries(&library)?);
+    Ok(())
+}
+
+#[tokio::test]
+#[serial]
+async fn e2e_json_export_round_trips_through_import_books() -> Result<()> {
+    let (_user_id, token) = create_test_user().await?;
+    import_csv(&token, CSV).await?;
+
+    let (status, exported) = export("library.json", Some(&token)).await?;
+    assert_eq!(status, StatusCode::OK);
+    let library: serde_json::Value = serde_json::from_str(&exported)?;
+    let book = &library["books"][0];
+    assert!(book["id"].is_string());
+    assert!(book["createdAt"].is_i64());
+    assert!(book["updatedAt"].is_i64());
+
+    // The entries are importBooks inputs as they are
+    let (_other_user_id, other_token) = create_test_user().await?;
+    let res = Client::new()
+        .post(get_graphql_url()?)
+        .header("Authorization", format!("Bearer {}", other_token))
+        .json(&serde_json::json!({
+            "query": "mutation ($books: [ImportBookInput!]!) { \
+                      importBooks(books: $books) { created { id } } }",
+            "variables": { "books": entries(&exported)? },
+        }))
+        .send()
+        .await
+        .context("request failed")?;
+    let response: serde_json::Value = res.json().await.context("invalid JSON")?;
+    assert_no_graphql_errors(&response, "importBooks");
+    assert_eq!(
+        response["data"]["importBooks"]["created"]
+            .as_array()
+            .map(Vec::len),
+        Some(3)
+    );
+
+    let (_, reexported) = export("library.json", Some(&other_token)).await?;
+    assert_eq!(entries(&reexported)?, entries(&exported)?);
+    Ok(())
+}
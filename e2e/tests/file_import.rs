@@ -0,0 +1,226 @@
+// E2E tests that run against a real Postgres instance.
+
+#![cfg(test)]
+
+use anyhow::{Context, Result};
+use bookshelf_e2e::*;
+use reqwest::{
+    Client, StatusCode,
+    multipart::{Form, Part},
+};
+use serial_test::serial;
+
+const MAPPING: &str = r#"{
+    "title": "Title",
+    "authors": "Authors",
+    "isbn": "ISBN",
+    "read": "Read",
+    "owned": "Owned",
+    "priority": "Priority",
+    "format": "Format",
+    "store": "Store"
+}"#;
+
+async fn import_csv(
+    token: Option<&str>,
+    csv: &str,
+    mapping: &str,
+    options: &[(&str, &str)],
+) -> Result<(StatusCode, serde_json::Value)> {
+    let mut fields = vec![("mapping", mapping)];
+    fields.extend_from_slice(options);
+    import_file("csv", token, csv, &fields).await
+}
+
+async fn import_file(
+    importer: &str,
+    token: Option<&str>,
+    file: &str,
+    fields: &[(&str, &str)],
+) -> Result<(StatusCode, serde_json::Value)> {
+    let mut form = Form::new().part(
+        "file",
+        Part::bytes(file.as_bytes().to_vec()).file_name("books.csv"),
+    );
+    for (name, value) in fields {
+        form = form.text(name.to_string(), value.to_string());
+    }
+
+    let mut request = Client::new()
+        .post(format!("{}/import/{}", get_server_url()?, importer))
+        .multipart(form);
+    if let Some(token) = token {
+        request = request.header("Authorization", format!("Bearer {}", token));
+    }
+    let res = request.send().await.context("request failed")?;
+    let status = res.status();
+    let body = res.json().await.context("invalid JSON")?;
+    Ok((status, body))
+}
+
+#[tokio::test]
+async fn e2e_csv_import_without_auth_returns_401() -> Result<()> {
+    let (status, _) = import_csv(None, "Title\nDune\n", MAPPING, &[]).await?;
+
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+    Ok(())
+}
+
+#[tokio::test]
+#[serial]
+async fn e2e_csv_import_creates_books_from_mapped_columns() -> Result<()> {
+    let (_user_id, token) = create_test_user().await?;
+    let existing_author_id = create_test_author("CSV Existing Author", &token).await?;
+
+    let csv = "Title,Authors,ISBN,Read,Owned,Priority,Format,Store\n\
+               CSV Book One,CSV Existing Author; CSV New Author,,yes,no,70,eBook,Kindle\n\
+               CSV Book Two,CSV New Author,,,x,,Printed,\n";
+    let (status, body) = import_csv(Some(&token), csv, MAPPING, &[]).await?;
+
+    assert_eq!(status, StatusCode::OK, "unexpected response: {body}");
+    let created = body["created"]
+        .as_array()
+        .context("created should be an array")?;
+    assert_eq!(created.len(), 2);
+    assert_eq!(created[0]["title"], "CSV Book One");
+    assert_eq!(created[0]["read"], true);
+    assert_eq!(created[0]["owned"], false);
+    assert_eq!(created[0]["priority"], 70);
+    assert_eq!(created[0]["format"], "eBook");
+    assert_eq!(created[0]["store"], "Kindle");
+    assert_eq!(created[0]["authorIds"][0], existing_author_id.as_str());
+    assert_eq!(created[1]["owned"], true);
+    assert_eq!(created[1]["priority"], 0);
+    assert_eq!(
+        body["newAuthorNames"],
+        serde_json::json!(["CSV New Author"])
+    );
+    assert!(body["eventSetId"].is_string());
+
+    // The books are visible through GraphQL
+    let book_id = created[1]["id"].as_str().context("id should be a string")?;
+    let query = format!(r#"query {{ book(id: "{book_id}") {{ title authors {{ name }} }} }}"#);
+    let (_, response) = graphql_request(&query, Some(&token)).await?;
+    assert_no_graphql_errors(&response, "book");
+    assert_eq!(response["data"]["book"]["title"], "CSV Book Two");
+    assert_eq!(
+        response["data"]["book"]["authors"][0]["name"],
+        "CSV New Author"
+    );
+    Ok(())
+}
+
+#[tokio::test]
+#[serial]
+async fn e2e_csv_import_partial_reports_invalid_rows_by_line() -> Result<()> {
+    let (_user_id, token) = create_test_user().await?;
+
+    let csv = "Title,Authors,ISBN,Read,Owned,Priority,Format,Store\n\
+               CSV Valid Book,,,,,,,\n\
+               CSV Invalid Book,,,,,500,,\n";
+    let (status, body) = import_csv(
+        Some(&token),
+        csv,
+        MAPPING,
+        &[("partial", "true"), ("mode", "SKIP_EXISTING")],
+    )
+    .await?;
+
+    assert_eq!(status, StatusCode::OK, "unexpected response: {body}");
+    assert_eq!(body["created"].as_array().map(Vec::len), Some(1));
+    assert_eq!(body["errors"][0]["index"], 1);
+    assert_eq!(body["errors"][0]["line"], 3);
+    assert_eq!(body["errors"][0]["field"], "PRIORITY");
+    Ok(())
+}
+
+#[tokio::test]
+async fn e2e_csv_import_rejects_unreadable_cells() -> Result<()> {
+    let (_user_id, token) = create_test_user().await?;
+
+    let csv = "Title,Authors,ISBN,Read,Owned,Priority,Format,Store\n\
+               CSV Book,,,maybe,,,,\n";
+    let (status, body) = import_csv(Some(&token), csv, MAPPING, &[]).await?;
+
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    let message = body["message"]
+        .as_str()
+        .context("message should be a string")?;
+    assert!(
+        message.contains("line 2") && message.contains("Read"),
+        "unexpected message: {message}"
+    );
+    Ok(())
+}
+
+#[tokio::test]
+#[serial]
+async fn e2e_goodreads_import_maps_shelves_and_reuses_authors() -> Result<()> {
+    let (_user_id, token) = create_test_user().await?;
+    let existing_author_id = create_test_author("Goodreads Author", &token).await?;
+
+    let export = "Book Id,Title,Author,Additional Authors,ISBN,ISBN13,Binding,Date Read,Bookshelves,Exclusive Shelf,Owned Copies\n\
+                  1,Goodreads Read Book,Goodreads Author,,\"=\"\"0441013597\"\"\",\"=\"\"\"\"\",Kindle Edition,2023/05/14,,read,0\n\
+                  2,Goodreads Wanted Book,Goodreads  Author,,\"=\"\"\"\"\",\"=\"\"\"\"\",Paperback,,owned,to-read,0\n";
+    let (status, body) = import_file("goodreads", Some(&token), export, &[]).await?;
+
+    assert_eq!(status, StatusCode::OK, "unexpected response: {body}");
+    let created = body["created"]
+        .as_array()
+        .context("created should be an array")?;
+    assert_eq!(created.len(), 2);
+    assert_eq!(created[0]["isbn"], "9780441013593");
+    assert_eq!(created[0]["read"], true);
+    assert_eq!(created[0]["format"], "eBook");
+    assert_eq!(created[0]["store"], "Kindle");
+    assert_eq!(created[1]["read"], false);
+    assert_eq!(created[1]["owned"], true);
+    assert_eq!(created[1]["priority"], 50);
+    for book in created {
+        assert_eq!(book["authorIds"][0], existing_author_id.as_str());
+    }
+    assert_eq!(body["newAuthorNames"], serde_json::json!([]));
+    Ok(())
+}
+
+#[tokio::test]
+#[serial]
+async fn e2e_bookmeter_import_maps_shelves() -> Result<()> {
+    let (_user_id, token) = create_test_user().await?;
+
+    let export = "タイトル,著者,ISBN/ASIN,読了日,本棚\n\
+                  読書メーターの本,読書メーター著者,4062748681,2023/05/14,読んだ本\n\
+                  積んでいる本,読書メーター著者,B01J4JW3KM,,積読本\n";
+    let (status, body) = import_file("bookmeter", Some(&token), export, &[]).await?;
+
+    assert_eq!(status, StatusCode::OK, "unexpected response: {body}");
+    let created = body["created"]
+        .as_array()
+        .context("created should be an array")?;
+    assert_eq!(created.len(), 2);
+    assert_eq!(created[0]["isbn"], "9784062748681");
+    assert_eq!(created[0]["read"], true);
+    assert_eq!(created[1]["owned"], true);
+    assert_eq!(created[1]["store"], "Kindle");
+    assert_eq!(
+        body["newAuthorNames"],
+        serde_json::json!(["読書メーター著者"])
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn e2e_goodreads_import_rejects_a_mapping() -> Result<()> {
+    let (_user_id, token) = create_test_user().await?;
+
+    let (status, _) = import_file(
+        "goodreads",
+        Some(&token),
+        "Title,Author\nDune,Frank Herbert\n",
+        &[("mapping", MAPPING)],
+    )
+    .await?;
+
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    Ok(())
+}
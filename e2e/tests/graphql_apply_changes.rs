@@ -0,0 +1,138 @@
+// E2E tests that run against a real Postgres instance.
+
+#![cfg(test)]
+
+use anyhow::{Context, Result};
+use bookshelf_e2e::*;
+use serial_test::serial;
+
+#[tokio::test]
+#[serial]
+async fn e2e_apply_changes_creates_a_book_for_an_author_from_the_same_batch() -> Result<()> {
+    let (_user_id, token) = create_test_user().await?;
+    let author_id =
+        create_test_author(&format!("Batch Author {}", uuid::Uuid::new_v4()), &token).await?;
+    let old_book_id = create_test_book("Replaced Book", &author_id, &token).await?;
+
+    let query = format!(
+        r#"
+        mutation {{
+            applyChanges(operations: [
+                {{ createAuthor: {{ tempId: "new-author", name: "Batch New Author" }} }}
+                {{ createBook: {{
+                    tempId: "new-book"
+                    title: "Batch Book"
+                    authorIds: ["new-author", "{author_id}"]
+                    isbn: ""
+                    read: false
+                    owned: true
+                    priority: 50
+                    format: E_BOOK
+                    store: KINDLE
+                }} }}
+                {{ deleteBook: "{old_book_id}" }}
+            ]) {{
+                results {{ id tempId book {{ title authors {{ name }} }} author {{ name }} }}
+                eventSetId
+            }}
+        }}
+        "#
+    );
+    let (_, response) = graphql_request(&query, Some(&token)).await?;
+    assert_no_graphql_errors(&response, "applyChanges");
+    let payload = &response["data"]["applyChanges"];
+    let results = payload["results"]
+        .as_array()
+        .context("results should be an array")?;
+    assert_eq!(results.len(), 3);
+    assert_eq!(results[0]["tempId"].as_str(), Some("new-author"));
+    assert_eq!(
+        results[0]["author"]["name"].as_str(),
+        Some("Batch New Author")
+    );
+    assert_eq!(results[1]["tempId"].as_str(), Some("new-book"));
+    let author_names: Vec<&str> = results[1]["book"]["authors"]
+        .as_array()
+        .context("authors should be an array")?
+        .iter()
+        .filter_map(|author| author["name"].as_str())
+        .collect();
+    assert!(author_names.contains(&"Batch New Author"));
+    assert_eq!(results[2]["id"].as_str(), Some(old_book_id.as_str()));
+    assert!(results[2]["book"].is_null());
+
+    let event_set_id = payload["eventSetId"]
+        .as_str()
+        .context("eventSetId should be a string")?;
+    let event_set_query = format!(
+        r#"{{ eventSet(id: "{event_set_id}") {{ operation bookEvents {{ operation }} authorEvents {{ operation }} }} }}"#
+    );
+    let (_, response) = graphql_request(&event_set_query, Some(&token)).await?;
+    let event_set = &response["data"]["eventSet"];
+    assert_eq!(event_set["operation"].as_str(), Some("apply_changes"));
+    assert_eq!(event_set["bookEvents"].as_array().map(Vec::len), Some(2));
+    assert_eq!(event_set["authorEvents"].as_array().map(Vec::len), Some(1));
+    Ok(())
+}
+
+#[tokio::test]
+#[serial]
+async fn e2e_apply_changes_applies_nothing_when_an_operation_fails() -> Result<()> {
+    let (_user_id, token) = create_test_user().await?;
+    let author_name = format!("Kept Author {}", uuid::Uuid::new_v4());
+    let author_id = create_test_author(&author_name, &token).await?;
+    let missing_book_id = uuid::Uuid::new_v4();
+
+    let query = format!(
+        r#"
+        mutation {{
+            applyChanges(operations: [
+                {{ updateAuthor: {{ id: "{author_id}", name: "Renamed Author" }} }}
+                {{ createAuthor: {{ name: "Never Created" }} }}
+                {{ deleteBook: "{missing_book_id}" }}
+            ]) {{ eventSetId }}
+        }}
+        "#
+    );
+    let (_, response) = graphql_request(&query, Some(&token)).await?;
+    assert_graphql_errors(&response, "applyChanges with a missing book");
+    let message = response["errors"][0]["message"]
+        .as_str()
+        .context("message should be a string")?;
+    assert!(message.starts_with("operation 2:"), "{message}");
+
+    let author_query = format!(r#"{{ author(id: "{author_id}") {{ name }} }}"#);
+    let (_, response) = graphql_request(&author_query, Some(&token)).await?;
+    assert_eq!(
+        response["data"]["author"]["name"].as_str(),
+        Some(author_name.as_str())
+    );
+    let (_, response) =
+        graphql_request(r#"{ authorsConnection { totalCount } }"#, Some(&token)).await?;
+    assert_eq!(
+        response["data"]["authorsConnection"]["totalCount"].as_i64(),
+        Some(1)
+    );
+    Ok(())
+}
+
+#[tokio::test]
+#[serial]
+async fn e2e_apply_changes_rejects_a_temp_id_used_before_its_create() -> Result<()> {
+    let (_user_id, token) = create_test_user().await?;
+
+    let query = r#"
+        mutation {
+            applyChanges(operations: [
+                { deleteAuthor: "later" }
+                { createAuthor: { tempId: "later", name: "Later Author" } }
+            ]) { eventSetId }
+        }
+    "#;
+    let (_, response) = graphql_request(query, Some(&token)).await?;
+    let message = response["errors"][0]["message"]
+        .as_str()
+        .context("message should be a string")?;
+    assert!(message.contains("operation 0"), "{message}");
+    Ok(())
+}
@@ -52,6 +52,68 @@ async fn e2e_graphql_authors() -> Result<()> {
     Ok(())
 }
 
+#[tokio::test]
+#[serial]
+async fn e2e_graphql_authors_order_by_yomi() -> Result<()> {
+    let (_user_id, token) = create_test_user().await?;
+
+    let mut author_ids = Vec::new();
+    for (name, yomi) in [
+        ("森鴎外", "もりおうがい"),
+        ("カフカ", ""),
+        ("芥川龍之介", "あくたがわりゅうのすけ"),
+    ] {
+        let query = format!(
+            r#"mutation {{ createAuthor(authorData: {{ name: "{name}", yomi: "{yomi}" }}) {{ author {{ id }} }} }}"#
+        );
+        let (_, response) = graphql_request(&query, Some(&token)).await?;
+        assert_no_graphql_errors(&response, "createAuthor with yomi");
+        let id = response["data"]["createAuthor"]["author"]["id"]
+            .as_str()
+            .context("createAuthor id should be a string")?;
+        author_ids.push(id.to_owned());
+    }
+
+    let query = r#"{
+        authors(orderBy: { field: YOMI }) { name }
+        authorsConnection(orderBy: { field: YOMI, direction: DESC }, first: 2) {
+            edges { node { name } }
+            pageInfo { hasNextPage }
+        }
+    }"#;
+    let (_, response) = graphql_request(query, Some(&token)).await?;
+    assert_no_graphql_errors(&response, "authors ordered by yomi");
+    let names = |value: &serde_json::Value, field: &str| -> Result<Vec<String>> {
+        value
+            .as_array()
+            .with_context(|| format!("{field} should be an array"))?
+            .iter()
+            .map(|item| {
+                item.pointer("/node/name")
+                    .or_else(|| item.get("name"))
+                    .and_then(|name| name.as_str())
+                    .map(str::to_owned)
+                    .with_context(|| format!("{field} entries should have a name"))
+            })
+            .collect()
+    };
+    let data = &response["data"];
+    assert_eq!(
+        names(&data["authors"], "authors")?,
+        vec!["芥川龍之介", "カフカ", "森鴎外"]
+    );
+    assert_eq!(
+        names(&data["authorsConnection"]["edges"], "edges")?,
+        vec!["森鴎外", "カフカ"]
+    );
+    assert_eq!(data["authorsConnection"]["pageInfo"]["hasNextPage"], true);
+
+    for author_id in &author_ids {
+        delete_test_author(author_id, &token).await?;
+    }
+    Ok(())
+}
+
 #[tokio::test]
 #[serial]
 async fn e2e_graphql_authors_resolve_shared_and_empty_books() -> Result<()> {
@@ -290,6 +352,80 @@ async fn e2e_graphql_delete_author_with_associated_books_fails() -> Result<()> {
     Ok(())
 }
 
+#[tokio::test]
+#[serial]
+async fn e2e_graphql_delete_authors_records_one_event_set() -> Result<()> {
+    let (_user_id, token) = create_test_user().await?;
+    let first_id =
+        create_test_author(&format!("Author A {}", uuid::Uuid::new_v4()), &token).await?;
+    let second_id =
+        create_test_author(&format!("Author B {}", uuid::Uuid::new_v4()), &token).await?;
+
+    let query = format!(
+        r#"mutation {{ deleteAuthors(authorIds: ["{first_id}", "{second_id}"]) {{ authorIds eventSetId }} }}"#
+    );
+    let (_, response) = graphql_request(&query, Some(&token)).await?;
+    assert_no_graphql_errors(&response, "deleteAuthors");
+    let event_set_id = response["data"]["deleteAuthors"]["eventSetId"]
+        .as_str()
+        .context("eventSetId should be a string")?;
+
+    let event_set_query = format!(
+        r#"{{ eventSet(id: "{event_set_id}") {{ operation authorEvents {{ authorId operation }} }} }}"#
+    );
+    let (_, response) = graphql_request(&event_set_query, Some(&token)).await?;
+    let event_set = &response["data"]["eventSet"];
+    assert_eq!(event_set["operation"].as_str(), Some("delete_authors"));
+    let author_events = event_set["authorEvents"]
+        .as_array()
+        .context("authorEvents should be an array")?;
+    assert_eq!(author_events.len(), 2);
+    assert!(
+        author_events
+            .iter()
+            .all(|event| event["operation"].as_str() == Some("delete"))
+    );
+    Ok(())
+}
+
+#[tokio::test]
+#[serial]
+async fn e2e_graphql_delete_authors_reports_every_blocking_author() -> Result<()> {
+    let (_user_id, token) = create_test_user().await?;
+    let free_id =
+        create_test_author(&format!("Free Author {}", uuid::Uuid::new_v4()), &token).await?;
+    let first_id =
+        create_test_author(&format!("Blocking A {}", uuid::Uuid::new_v4()), &token).await?;
+    let second_id =
+        create_test_author(&format!("Blocking B {}", uuid::Uuid::new_v4()), &token).await?;
+    let first_book_id = create_test_book("Blocking Book A", &first_id, &token).await?;
+    let second_book_id = create_test_book("Blocking Book B", &second_id, &token).await?;
+
+    let query = format!(
+        r#"mutation {{ deleteAuthors(authorIds: ["{free_id}", "{first_id}", "{second_id}"]) {{ eventSetId }} }}"#
+    );
+    let (_, response) = graphql_request(&query, Some(&token)).await?;
+    let extensions = &response["errors"][0]["extensions"];
+    assert_eq!(extensions["code"].as_str(), Some("CONFLICT"));
+    let blocking = extensions["blockingAuthors"]
+        .as_array()
+        .context("blockingAuthors should be an array")?;
+    assert_eq!(blocking.len(), 2);
+    for (author_id, book_id) in [(&first_id, &first_book_id), (&second_id, &second_book_id)] {
+        let entry = blocking
+            .iter()
+            .find(|entry| entry["authorId"].as_str() == Some(author_id.as_str()))
+            .context("each blocking author should be listed")?;
+        assert_eq!(entry["bookIds"], serde_json::json!([book_id]));
+    }
+
+    // The author without books is not deleted either.
+    let query = format!(r#"{{ author(id: "{free_id}") {{ id }} }}"#);
+    let (_, response) = graphql_request(&query, Some(&token)).await?;
+    assert!(!response["data"]["author"].is_null());
+    Ok(())
+}
+
 #[tokio::test]
 #[serial]
 async fn e2e_graphql_update_author() -> Result<()> {
@@ -25,6 +25,292 @@ async fn e2e_graphql_books_empty() -> Result<()> {
     Ok(())
 }
 
+#[tokio::test]
+#[serial]
+async fn e2e_graphql_books_connection_pages_forward_and_backward() -> Result<()> {
+    let (_user_id, token) = create_test_user().await?;
+    let author_id = create_test_author("Connection Author", &token).await?;
+    let mut book_ids = Vec::new();
+    for title in [
+        "Connection Book 1",
+        "Connection Book 2",
+        "Connection Book 3",
+    ] {
+        book_ids.push(create_test_book(title, &author_id, &token).await?);
+    }
+
+    let query = r#"{ booksConnection(first: 2) {
+        totalCount
+        pageInfo { hasNextPage endCursor }
+        nodes { id }
+    } }"#;
+    let (_, response) = graphql_request(query, Some(&token)).await?;
+    assert_no_graphql_errors(&response, "booksConnection first page");
+    let connection = &response["data"]["booksConnection"];
+    assert_eq!(connection["totalCount"], 3);
+    assert_eq!(connection["pageInfo"]["hasNextPage"], true);
+    assert_eq!(
+        connection["nodes"][0]["id"].as_str(),
+        Some(book_ids[0].as_str())
+    );
+    assert_eq!(
+        connection["nodes"][1]["id"].as_str(),
+        Some(book_ids[1].as_str())
+    );
+    let end_cursor = connection["pageInfo"]["endCursor"]
+        .as_str()
+        .context("endCursor should be a string")?;
+
+    let query = format!(
+        r#"{{ booksConnection(first: 2, after: "{end_cursor}") {{
+            pageInfo {{ hasNextPage hasPreviousPage }}
+            nodes {{ id }}
+        }} }}"#
+    );
+    let (_, response) = graphql_request(&query, Some(&token)).await?;
+    assert_no_graphql_errors(&response, "booksConnection second page");
+    let connection = &response["data"]["booksConnection"];
+    assert_eq!(connection["pageInfo"]["hasNextPage"], false);
+    assert_eq!(connection["pageInfo"]["hasPreviousPage"], true);
+    let nodes = connection["nodes"]
+        .as_array()
+        .context("nodes should be an array")?;
+    assert_eq!(nodes.len(), 1);
+    assert_eq!(nodes[0]["id"].as_str(), Some(book_ids[2].as_str()));
+
+    let query = r#"{ authorsConnection(first: 1) {
+        nodes { id booksConnection(last: 1) { totalCount nodes { id } } }
+    } }"#;
+    let (_, response) = graphql_request(query, Some(&token)).await?;
+    assert_no_graphql_errors(&response, "authorsConnection with booksConnection");
+    let author = &response["data"]["authorsConnection"]["nodes"][0];
+    assert_eq!(author["id"].as_str(), Some(author_id.as_str()));
+    assert_eq!(author["booksConnection"]["totalCount"], 3);
+    assert_eq!(
+        author["booksConnection"]["nodes"][0]["id"].as_str(),
+        Some(book_ids[2].as_str())
+    );
+
+    let (_, response) = graphql_request(
+        r#"{ booksConnection(first: 1, last: 1) { totalCount } }"#,
+        Some(&token),
+    )
+    .await?;
+    assert!(
+        response.get("errors").is_some(),
+        "first and last together should be rejected"
+    );
+
+    for book_id in &book_ids {
+        delete_test_book(book_id, &token).await?;
+    }
+    delete_test_author(&author_id, &token).await?;
+    Ok(())
+}
+
+#[tokio::test]
+#[serial]
+async fn e2e_graphql_books_order_by() -> Result<()> {
+    let (_user_id, token) = create_test_user().await?;
+    let author = create_test_author("Order Author", &token).await?;
+    let mut book_ids = Vec::new();
+    for title in ["Order B", "Order C", "Order A"] {
+        book_ids.push(create_test_book(title, &author, &token).await?);
+    }
+
+    let query = r#"{
+        books(orderBy: { field: TITLE, direction: DESC }) { title }
+        booksConnection(orderBy: { field: TITLE }, last: 1) {
+            edges { node { title } }
+            pageInfo { hasPreviousPage }
+        }
+    }"#;
+    let (_, response) = graphql_request(query, Some(&token)).await?;
+    assert_no_graphql_errors(&response, "books with orderBy");
+    let data = &response["data"];
+    let titles = data["books"]
+        .as_array()
+        .context("books should be an array")?
+        .iter()
+        .map(|book| book["title"].as_str())
+        .collect::<Vec<_>>();
+    assert_eq!(
+        titles,
+        vec![Some("Order C"), Some("Order B"), Some("Order A")]
+    );
+    assert_eq!(
+        data["booksConnection"]["edges"][0]["node"]["title"].as_str(),
+        Some("Order C")
+    );
+    assert_eq!(data["booksConnection"]["pageInfo"]["hasPreviousPage"], true);
+
+    for book_id in &book_ids {
+        delete_test_book(book_id, &token).await?;
+    }
+    delete_test_author(&author, &token).await?;
+    Ok(())
+}
+
+#[tokio::test]
+#[serial]
+async fn e2e_graphql_search() -> Result<()> {
+    let (_user_id, token) = create_test_user().await?;
+    let (_, response) = graphql_request(
+        r#"mutation { createAuthor(authorData: { name: "夏目漱石", yomi: "なつめそうせき" }) { author { id } } }"#,
+        Some(&token),
+    )
+    .await?;
+    assert_no_graphql_errors(&response, "create author for search");
+    let author = response["data"]["createAuthor"]["author"]["id"]
+        .as_str()
+        .context("createAuthor id should be a string")?
+        .to_owned();
+    let kokoro = create_test_book("こころ", &author, &token).await?;
+    let rust = create_test_book("ＲＵＳＴ入門", &author, &token).await?;
+
+    let query = r#"{
+        byKana: search(query: "ｺｺﾛ") { books { id } authors { id } }
+        byWidth: search(query: "rust") { books { id } }
+        byYomi: search(query: "ナツメ", limit: 1) { books { id } authors { id } }
+    }"#;
+    let (_, response) = graphql_request(query, Some(&token)).await?;
+    assert_no_graphql_errors(&response, "search");
+    let data = &response["data"];
+    assert_eq!(
+        data["byKana"]["books"][0]["id"].as_str(),
+        Some(kokoro.as_str())
+    );
+    assert_eq!(data["byKana"]["books"].as_array().map(Vec::len), Some(1));
+    assert_eq!(data["byKana"]["authors"].as_array().map(Vec::len), Some(0));
+    assert_eq!(
+        data["byWidth"]["books"][0]["id"].as_str(),
+        Some(rust.as_str())
+    );
+    assert_eq!(data["byYomi"]["books"].as_array().map(Vec::len), Some(1));
+    assert_eq!(
+        data["byYomi"]["authors"][0]["id"].as_str(),
+        Some(author.as_str())
+    );
+
+    let (_, response) =
+        graphql_request(r#"{ search(query: " ") { books { id } } }"#, Some(&token)).await?;
+    assert_graphql_errors(&response, "blank search query");
+
+    delete_test_book(&kokoro, &token).await?;
+    delete_test_book(&rust, &token).await?;
+    delete_test_author(&author, &token).await?;
+    Ok(())
+}
+
+#[tokio::test]
+#[serial]
+async fn e2e_graphql_library_stats() -> Result<()> {
+    let (_user_id, token) = create_test_user().await?;
+    let author1 = create_test_author("Stats Author 1", &token).await?;
+    let author2 = create_test_author("Stats Author 2", &token).await?;
+    let book1 = create_test_book("Stats Book 1", &author1, &token).await?;
+    let book2 = create_test_book("Stats Book 2", &author1, &token).await?;
+    let book3 = create_test_book("Stats Book 3", &author2, &token).await?;
+
+    let query = r#"{
+        libraryStats(topAuthors: 1) {
+            totalBooks readCount unreadCount ownedCount notOwnedCount unreadOwnedCount
+            formats { format count }
+            stores { store count }
+            priorityHistogram { min max count }
+            topAuthors { author { id } bookCount }
+        }
+    }"#;
+    let (_, response) = graphql_request(query, Some(&token)).await?;
+    assert_no_graphql_errors(&response, "libraryStats");
+    let stats = &response["data"]["libraryStats"];
+    assert_eq!(stats["totalBooks"], 3);
+    assert_eq!(stats["readCount"], 0);
+    assert_eq!(stats["unreadCount"], 3);
+    assert_eq!(stats["ownedCount"], 0);
+    assert_eq!(stats["notOwnedCount"], 3);
+    assert_eq!(stats["unreadOwnedCount"], 0);
+    let formats = stats["formats"]
+        .as_array()
+        .context("formats should be an array")?;
+    assert!(formats.contains(&serde_json::json!({ "format": "E_BOOK", "count": 3 })));
+    assert!(formats.contains(&serde_json::json!({ "format": "PRINTED", "count": 0 })));
+    let stores = stats["stores"]
+        .as_array()
+        .context("stores should be an array")?;
+    assert!(stores.contains(&serde_json::json!({ "store": "KINDLE", "count": 3 })));
+    let histogram = stats["priorityHistogram"]
+        .as_array()
+        .context("priorityHistogram should be an array")?;
+    assert_eq!(histogram.len(), 10);
+    assert_eq!(
+        histogram[5],
+        serde_json::json!({ "min": 50, "max": 59, "count": 3 })
+    );
+    assert_eq!(
+        stats["topAuthors"],
+        serde_json::json!([{ "author": { "id": author1 }, "bookCount": 2 }])
+    );
+
+    let (_, response) = graphql_request(
+        r#"{ libraryStats(topAuthors: -1) { totalBooks } }"#,
+        Some(&token),
+    )
+    .await?;
+    assert_graphql_errors(&response, "negative topAuthors");
+
+    for book in [&book1, &book2, &book3] {
+        delete_test_book(book, &token).await?;
+    }
+    delete_test_author(&author1, &token).await?;
+    delete_test_author(&author2, &token).await?;
+    Ok(())
+}
+
+#[tokio::test]
+#[serial]
+async fn e2e_graphql_books_filter() -> Result<()> {
+    let (_user_id, token) = create_test_user().await?;
+    let author1 = create_test_author("Filter Author 1", &token).await?;
+    let author2 = create_test_author("Filter Author 2", &token).await?;
+    let book1 = create_test_book("Filter Book 1", &author1, &token).await?;
+    let book2 = create_test_book("Filter Book 2", &author2, &token).await?;
+
+    let query = format!(
+        r#"{{
+            byAuthor: books(filter: {{ authorIds: ["{author2}"], read: false }}) {{ id }}
+            read: books(filter: {{ read: true }}) {{ id }}
+            connection: booksConnection(filter: {{ hasIsbn: false }}) {{ totalCount }}
+        }}"#
+    );
+    let (_, response) = graphql_request(&query, Some(&token)).await?;
+    assert_no_graphql_errors(&response, "books with filter");
+    let data = &response["data"];
+    let by_author = data["byAuthor"]
+        .as_array()
+        .context("byAuthor should be an array")?;
+    assert_eq!(by_author.len(), 1);
+    assert_eq!(by_author[0]["id"].as_str(), Some(book2.as_str()));
+    assert_eq!(data["read"].as_array().map(Vec::len), Some(0));
+    assert_eq!(data["connection"]["totalCount"], 2);
+
+    let (_, response) = graphql_request(
+        r#"{ books(filter: { priority: { min: 80, max: 20 } }) { id } }"#,
+        Some(&token),
+    )
+    .await?;
+    assert!(
+        response.get("errors").is_some(),
+        "inverted priority range should be rejected"
+    );
+
+    delete_test_book(&book1, &token).await?;
+    delete_test_book(&book2, &token).await?;
+    delete_test_author(&author1, &token).await?;
+    delete_test_author(&author2, &token).await?;
+    Ok(())
+}
+
 #[tokio::test]
 #[serial]
 async fn e2e_graphql_crud_book() -> Result<()> {
@@ -353,6 +639,283 @@ async fn e2e_graphql_create_book_without_auth() -> Result<()> {
     Ok(())
 }
 
+#[tokio::test]
+#[serial]
+async fn e2e_graphql_update_book_with_stale_version_returns_conflict() -> Result<()> {
+    let (_user_id, token) = create_test_user().await?;
+    let author_id = create_test_author(
+        &format!("Concurrent Author {}", uuid::Uuid::new_v4()),
+        &token,
+    )
+    .await?;
+    let (book_id, created_event_id, _) =
+        create_test_book_with_event("Shared Book", &author_id, &token).await?;
+    let update = |title: &str, expected_event_id: &str| {
+        format!(
+            r#"
+            mutation {{
+                updateBook(bookData: {{
+                    id: "{book_id}"
+                    title: "{title}"
+                    authorIds: ["{author_id}"]
+                    isbn: ""
+                    read: false
+                    owned: false
+                    priority: 50
+                    format: E_BOOK
+                    store: KINDLE
+                    expectedEventId: "{expected_event_id}"
+                }}) {{ book {{ title }} eventId }}
+            }}
+            "#
+        )
+    };
+
+    // The first device edits from the created version.
+    let (_, response) =
+        graphql_request(&update("First Edit", &created_event_id), Some(&token)).await?;
+    assert_no_graphql_errors(&response, "updateBook from the latest version");
+    let first_event_id = response["data"]["updateBook"]["eventId"]
+        .as_str()
+        .context("eventId should be a string")?
+        .to_owned();
+
+    // The second device still holds the created version.
+    let (_, response) =
+        graphql_request(&update("Second Edit", &created_event_id), Some(&token)).await?;
+    assert_graphql_errors(&response, "updateBook from a stale version");
+    let extensions = &response["errors"][0]["extensions"];
+    assert_eq!(extensions["code"].as_str(), Some("CONFLICT"));
+    assert_eq!(extensions["current"]["title"].as_str(), Some("First Edit"));
+    assert_eq!(
+        extensions["currentEventId"].as_str(),
+        Some(first_event_id.as_str())
+    );
+
+    let book_query = format!(r#"{{ book(id: "{book_id}") {{ title }} }}"#);
+    let (_, response) = graphql_request(&book_query, Some(&token)).await?;
+    assert_eq!(
+        response["data"]["book"]["title"].as_str(),
+        Some("First Edit"),
+        "the stale update should not be applied"
+    );
+    Ok(())
+}
+
+#[tokio::test]
+#[serial]
+async fn e2e_graphql_patch_book_keeps_omitted_fields() -> Result<()> {
+    let (_user_id, token) = create_test_user().await?;
+    let first_author_id =
+        create_test_author(&format!("Patch Author A {}", uuid::Uuid::new_v4()), &token).await?;
+    let second_author_id =
+        create_test_author(&format!("Patch Author B {}", uuid::Uuid::new_v4()), &token).await?;
+    let (book_id, created_event_id, _) =
+        create_test_book_with_event("Patched Book", &first_author_id, &token).await?;
+
+    // Mark as read and swap authors without re-sending the rest of the book.
+    let query = format!(
+        r#"
+        mutation {{
+            patchBook(bookData: {{
+                id: "{book_id}"
+                read: true
+                authorIds: {{ edit: {{ add: ["{second_author_id}"], remove: ["{first_author_id}"] }} }}
+                expectedEventId: "{created_event_id}"
+            }}) {{ book {{ title read owned priority authors {{ id }} }} eventSetId }}
+        }}
+        "#
+    );
+    let (_, response) = graphql_request(&query, Some(&token)).await?;
+    assert_no_graphql_errors(&response, "patchBook");
+    let book = &response["data"]["patchBook"]["book"];
+    assert_eq!(book["title"].as_str(), Some("Patched Book"));
+    assert_eq!(book["read"].as_bool(), Some(true));
+    assert_eq!(book["priority"].as_i64(), Some(50));
+    assert_eq!(
+        book["authors"][0]["id"].as_str(),
+        Some(second_author_id.as_str())
+    );
+    assert_eq!(book["authors"].as_array().map(Vec::len), Some(1));
+
+    let event_set_id = response["data"]["patchBook"]["eventSetId"]
+        .as_str()
+        .context("eventSetId should be a string")?;
+    let event_set_query = format!(
+        r#"{{ eventSet(id: "{event_set_id}") {{ operation bookEvents {{ operation read }} }} }}"#
+    );
+    let (_, response) = graphql_request(&event_set_query, Some(&token)).await?;
+    let event_set = &response["data"]["eventSet"];
+    assert_eq!(event_set["operation"].as_str(), Some("update_book"));
+    assert_eq!(
+        event_set["bookEvents"][0]["operation"].as_str(),
+        Some("update")
+    );
+    assert_eq!(event_set["bookEvents"][0]["read"].as_bool(), Some(true));
+    Ok(())
+}
+
+#[tokio::test]
+#[serial]
+async fn e2e_graphql_patch_book_that_changes_nothing_records_no_event() -> Result<()> {
+    let (_user_id, token) = create_test_user().await?;
+    let author_id =
+        create_test_author(&format!("Patch Author {}", uuid::Uuid::new_v4()), &token).await?;
+    let book_id = create_test_book("Unchanged Book", &author_id, &token).await?;
+
+    let query = format!(
+        r#"
+        mutation {{
+            patchBook(bookData: {{ id: "{book_id}", read: true }}) {{
+                book {{ read }} eventSetId eventId
+            }}
+        }}
+        "#
+    );
+    let (_, response) = graphql_request(&query, Some(&token)).await?;
+    assert_no_graphql_errors(&response, "patchBook");
+    let patched = response["data"]["patchBook"].clone();
+
+    // Patching it again to what it already is returns the same event
+    let (_, response) = graphql_request(&query, Some(&token)).await?;
+    assert_no_graphql_errors(&response, "repeated patchBook");
+    assert_eq!(response["data"]["patchBook"], patched);
+
+    let history_query =
+        format!(r#"{{ bookEventsConnection(bookId: "{book_id}") {{ totalCount }} }}"#);
+    let (_, response) = graphql_request(&history_query, Some(&token)).await?;
+    assert_no_graphql_errors(&response, "bookEventsConnection");
+    assert_eq!(
+        response["data"]["bookEventsConnection"]["totalCount"].as_i64(),
+        Some(2)
+    );
+    Ok(())
+}
+
+#[tokio::test]
+#[serial]
+async fn e2e_graphql_bulk_update_books_records_one_event_set() -> Result<()> {
+    let (_user_id, token) = create_test_user().await?;
+    let author_id =
+        create_test_author(&format!("Bulk Author {}", uuid::Uuid::new_v4()), &token).await?;
+    let first_book_id = create_test_book("Bulk Book 1", &author_id, &token).await?;
+    let second_book_id = create_test_book("Bulk Book 2", &author_id, &token).await?;
+    let untouched_book_id = create_test_book("Bulk Book 3", &author_id, &token).await?;
+
+    let bulk_update = |priority: i32| {
+        format!(
+            r#"
+            mutation {{
+                bulkUpdateBooks(
+                    selection: {{ ids: ["{first_book_id}", "{second_book_id}"] }}
+                    patch: {{ owned: true, priority: {priority} }}
+                ) {{ updatedCount eventSetId }}
+            }}
+            "#
+        )
+    };
+    let (_, response) = graphql_request(&bulk_update(90), Some(&token)).await?;
+    assert_no_graphql_errors(&response, "bulkUpdateBooks");
+    let payload = &response["data"]["bulkUpdateBooks"];
+    assert_eq!(payload["updatedCount"].as_i64(), Some(2));
+    let event_set_id = payload["eventSetId"]
+        .as_str()
+        .context("eventSetId should be a string")?;
+
+    let event_set_query = format!(
+        r#"{{ eventSet(id: "{event_set_id}") {{ operation bookEvents {{ bookId operation owned priority }} }} }}"#
+    );
+    let (_, response) = graphql_request(&event_set_query, Some(&token)).await?;
+    let event_set = &response["data"]["eventSet"];
+    assert_eq!(event_set["operation"].as_str(), Some("bulk_update_books"));
+    let book_events = event_set["bookEvents"]
+        .as_array()
+        .context("bookEvents should be an array")?;
+    assert_eq!(book_events.len(), 2);
+    for event in book_events {
+        assert_eq!(event["operation"].as_str(), Some("update"));
+        assert_eq!(event["owned"].as_bool(), Some(true));
+        assert_eq!(event["priority"].as_i64(), Some(90));
+    }
+
+    let book_query = format!(r#"{{ book(id: "{untouched_book_id}") {{ owned priority }} }}"#);
+    let (_, response) = graphql_request(&book_query, Some(&token)).await?;
+    assert_eq!(response["data"]["book"]["priority"].as_i64(), Some(50));
+
+    // Repeating the same patch changes nothing and records nothing.
+    let (_, response) = graphql_request(&bulk_update(90), Some(&token)).await?;
+    assert_no_graphql_errors(&response, "bulkUpdateBooks without changes");
+    let payload = &response["data"]["bulkUpdateBooks"];
+    assert_eq!(payload["updatedCount"].as_i64(), Some(0));
+    assert!(payload["eventSetId"].is_null());
+    Ok(())
+}
+
+#[tokio::test]
+#[serial]
+async fn e2e_graphql_delete_books_records_one_event_set() -> Result<()> {
+    let (_user_id, token) = create_test_user().await?;
+    let author_id =
+        create_test_author(&format!("Delete Author {}", uuid::Uuid::new_v4()), &token).await?;
+    let first_book_id = create_test_book("Delete Book 1", &author_id, &token).await?;
+    let second_book_id = create_test_book("Delete Book 2", &author_id, &token).await?;
+
+    let query = format!(
+        r#"mutation {{ deleteBooks(bookIds: ["{first_book_id}", "{second_book_id}"]) {{ bookIds eventSetId }} }}"#
+    );
+    let (_, response) = graphql_request(&query, Some(&token)).await?;
+    assert_no_graphql_errors(&response, "deleteBooks");
+    let payload = &response["data"]["deleteBooks"];
+    assert_eq!(payload["bookIds"].as_array().map(Vec::len), Some(2));
+    let event_set_id = payload["eventSetId"]
+        .as_str()
+        .context("eventSetId should be a string")?;
+
+    let event_set_query = format!(
+        r#"{{ eventSet(id: "{event_set_id}") {{ operation bookEvents {{ bookId operation }} }} }}"#
+    );
+    let (_, response) = graphql_request(&event_set_query, Some(&token)).await?;
+    let event_set = &response["data"]["eventSet"];
+    assert_eq!(event_set["operation"].as_str(), Some("delete_books"));
+    let book_events = event_set["bookEvents"]
+        .as_array()
+        .context("bookEvents should be an array")?;
+    assert_eq!(book_events.len(), 2);
+    assert!(
+        book_events
+            .iter()
+            .all(|event| event["operation"].as_str() == Some("delete"))
+    );
+
+    for book_id in [&first_book_id, &second_book_id] {
+        let book_query = format!(r#"{{ book(id: "{book_id}") {{ id }} }}"#);
+        let (_, response) = graphql_request(&book_query, Some(&token)).await?;
+        assert!(response["data"]["book"].is_null());
+    }
+    Ok(())
+}
+
+#[tokio::test]
+#[serial]
+async fn e2e_graphql_delete_books_with_a_missing_id_deletes_nothing() -> Result<()> {
+    let (_user_id, token) = create_test_user().await?;
+    let author_id =
+        create_test_author(&format!("Delete Author {}", uuid::Uuid::new_v4()), &token).await?;
+    let book_id = create_test_book("Kept Book", &author_id, &token).await?;
+    let missing_id = uuid::Uuid::new_v4();
+
+    let query = format!(
+        r#"mutation {{ deleteBooks(bookIds: ["{book_id}", "{missing_id}"]) {{ eventSetId }} }}"#
+    );
+    let (_, response) = graphql_request(&query, Some(&token)).await?;
+    assert!(response.get("errors").is_some());
+
+    let book_query = format!(r#"{{ book(id: "{book_id}") {{ id }} }}"#);
+    let (_, response) = graphql_request(&book_query, Some(&token)).await?;
+    assert!(!response["data"]["book"].is_null());
+    Ok(())
+}
+
 #[tokio::test]
 #[serial]
 async fn e2e_graphql_update_nonexistent_book_returns_error() -> Result<()> {
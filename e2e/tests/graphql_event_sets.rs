@@ -147,3 +147,115 @@ async fn e2e_event_sets() -> Result<()> {
 
     Ok(())
 }
+
+#[tokio::test]
+#[serial]
+async fn e2e_event_sets_and_histories_page_with_filters() -> Result<()> {
+    let (_user_id, token) = create_test_user().await?;
+
+    let author_id = create_test_author(
+        &format!("Event Set Author {}", uuid::Uuid::new_v4()),
+        &token,
+    )
+    .await?;
+    let first_book = create_test_book("Paged Book 1", &author_id, &token).await?;
+    let second_book = create_test_book("Paged Book 2", &author_id, &token).await?;
+
+    // Only the two create_book sets match, newest first, one per page.
+    let page_query = |after: Option<&str>| {
+        format!(
+            r#"{{ eventSetsConnection(first: 1, filter: {{ operations: ["create_book"] }}{}) {{
+                totalCount
+                pageInfo {{ hasNextPage endCursor }}
+                edges {{ node {{ id operation }} }}
+            }} }}"#,
+            after
+                .map(|cursor| format!(r#", after: "{cursor}""#))
+                .unwrap_or_default()
+        )
+    };
+    let (_, response) = graphql_request(&page_query(None), Some(&token)).await?;
+    assert_no_graphql_errors(&response, "eventSetsConnection first page");
+    let connection = &response["data"]["eventSetsConnection"];
+    assert_eq!(connection["totalCount"].as_i64(), Some(2));
+    assert_eq!(connection["pageInfo"]["hasNextPage"].as_bool(), Some(true));
+    let newest_set = connection["edges"][0]["node"]["id"]
+        .as_str()
+        .context("event set id should be a string")?
+        .to_owned();
+    let end_cursor = connection["pageInfo"]["endCursor"]
+        .as_str()
+        .context("endCursor should be a string")?
+        .to_owned();
+
+    let (_, response) = graphql_request(&page_query(Some(&end_cursor)), Some(&token)).await?;
+    assert_no_graphql_errors(&response, "eventSetsConnection second page");
+    let connection = &response["data"]["eventSetsConnection"];
+    assert_eq!(connection["pageInfo"]["hasNextPage"].as_bool(), Some(false));
+    let older_set = connection["edges"][0]["node"]["id"]
+        .as_str()
+        .context("event set id should be a string")?;
+    assert_ne!(older_set, newest_set);
+
+    // A window ending in the past matches nothing.
+    let (_, response) = graphql_request(
+        r#"{ eventSetsConnection(filter: { createdAt: { to: 0 } }) { totalCount } }"#,
+        Some(&token),
+    )
+    .await?;
+    assert_no_graphql_errors(&response, "eventSetsConnection with createdAt");
+    assert_eq!(
+        response["data"]["eventSetsConnection"]["totalCount"].as_i64(),
+        Some(0)
+    );
+
+    // Histories filter by operation and still report field changes.
+    delete_test_book(&second_book, &token).await?;
+    let query = format!(
+        r#"{{ bookEventsConnection(bookId: "{}", filter: {{ operations: ["delete"] }}) {{
+            totalCount
+            edges {{ node {{ operation changes {{ field oldValue newValue }} }} }}
+        }} }}"#,
+        second_book
+    );
+    let (_, response) = graphql_request(&query, Some(&token)).await?;
+    assert_no_graphql_errors(&response, "bookEventsConnection");
+    let connection = &response["data"]["bookEventsConnection"];
+    assert_eq!(connection["totalCount"].as_i64(), Some(1));
+    let node = &connection["edges"][0]["node"];
+    assert_eq!(node["operation"].as_str(), Some("delete"));
+    let title_change = node["changes"]
+        .as_array()
+        .and_then(|changes| changes.iter().find(|c| c["field"] == "title"))
+        .context("delete should report the removed title")?;
+    assert_eq!(title_change["oldValue"].as_str(), Some("Paged Book 2"));
+    assert!(title_change["newValue"].is_null());
+
+    let query = format!(
+        r#"{{ authorEventsConnection(authorId: "{}", last: 1) {{
+            totalCount
+            pageInfo {{ hasPreviousPage }}
+            edges {{ node {{ operation }} }}
+        }} }}"#,
+        author_id
+    );
+    let (_, response) = graphql_request(&query, Some(&token)).await?;
+    assert_no_graphql_errors(&response, "authorEventsConnection");
+    let connection = &response["data"]["authorEventsConnection"];
+    assert_eq!(connection["totalCount"].as_i64(), Some(1));
+    assert_eq!(
+        connection["edges"][0]["node"]["operation"].as_str(),
+        Some("create")
+    );
+
+    let (_, response) = graphql_request(
+        r#"{ eventSetsConnection(filter: { operations: ["rename"] }) { totalCount } }"#,
+        Some(&token),
+    )
+    .await?;
+    assert_graphql_errors(&response, "unknown event set operation");
+
+    delete_test_book(&first_book, &token).await?;
+    delete_test_author(&author_id, &token).await?;
+    Ok(())
+}
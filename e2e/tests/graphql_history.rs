@@ -278,6 +278,249 @@ async fn e2e_book_events_records_delete_operation() -> Result<()> {
     Ok(())
 }
 
+#[tokio::test]
+#[serial]
+async fn e2e_book_events_report_field_changes() -> Result<()> {
+    let (_user_id, token) = create_test_user().await?;
+
+    let old_author =
+        create_test_author(&format!("History Author {}", uuid::Uuid::new_v4()), &token).await?;
+    let new_author =
+        create_test_author(&format!("History Author {}", uuid::Uuid::new_v4()), &token).await?;
+    let book_id = create_test_book("Changes Book", &old_author, &token).await?;
+    let update_query = format!(
+        r#"
+        mutation {{
+            updateBook(bookData: {{
+                id: "{}"
+                title: "Changes Book"
+                authorIds: ["{}"]
+                isbn: ""
+                read: true
+                owned: false
+                priority: 80
+                format: E_BOOK
+                store: KINDLE
+            }}) {{ book {{ id }} }}
+        }}
+        "#,
+        book_id, new_author
+    );
+    let (_, response) = graphql_request(&update_query, Some(&token)).await?;
+    assert_no_graphql_errors(&response, "updateBook");
+
+    let query = format!(
+        r#"{{ bookEvents(bookId: "{}") {{ operation changes {{ field oldValue newValue }} }} }}"#,
+        book_id
+    );
+    let (_, response) = graphql_request(&query, Some(&token)).await?;
+    assert_no_graphql_errors(&response, "bookEvents changes");
+    let entries = response["data"]["bookEvents"]
+        .as_array()
+        .context("bookEvents should be an array")?;
+    let changes = |entry: &serde_json::Value| -> Vec<(String, Option<String>, Option<String>)> {
+        entry["changes"]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .map(|change| {
+                (
+                    change["field"].as_str().unwrap_or_default().to_owned(),
+                    change["oldValue"].as_str().map(str::to_owned),
+                    change["newValue"].as_str().map(str::to_owned),
+                )
+            })
+            .collect()
+    };
+    let value = |s: &str| Some(s.to_owned());
+
+    assert_eq!(entries[0]["operation"].as_str(), Some("update"));
+    assert_eq!(
+        changes(&entries[0]),
+        vec![
+            ("read".to_owned(), value("false"), value("true")),
+            ("priority".to_owned(), value("50"), value("80")),
+            ("authorIds".to_owned(), value(&old_author), None),
+            ("authorIds".to_owned(), None, value(&new_author)),
+        ]
+    );
+    // The create event is diffed against nothing, so every field is new.
+    assert!(
+        changes(&entries[1])
+            .iter()
+            .all(|(_, old_value, new_value)| old_value.is_none() && new_value.is_some())
+    );
+
+    delete_test_book(&book_id, &token).await?;
+    delete_test_author(&old_author, &token).await?;
+    delete_test_author(&new_author, &token).await?;
+    Ok(())
+}
+
+#[tokio::test]
+#[serial]
+async fn e2e_reading_activity_counts_book_transitions() -> Result<()> {
+    let (_user_id, token) = create_test_user().await?;
+
+    let author_id =
+        create_test_author(&format!("History Author {}", uuid::Uuid::new_v4()), &token).await?;
+    let read_book = create_test_book("Activity Read Book", &author_id, &token).await?;
+    let deleted_book = create_test_book("Activity Deleted Book", &author_id, &token).await?;
+    let update_query = format!(
+        r#"
+        mutation {{
+            updateBook(bookData: {{
+                id: "{}"
+                title: "Activity Read Book"
+                authorIds: ["{}"]
+                isbn: ""
+                read: true
+                owned: false
+                priority: 50
+                format: E_BOOK
+                store: KINDLE
+            }}) {{ book {{ id }} }}
+        }}
+        "#,
+        read_book, author_id
+    );
+    let (_, response) = graphql_request(&update_query, Some(&token)).await?;
+    assert_no_graphql_errors(&response, "mark book as read");
+    delete_test_book(&deleted_book, &token).await?;
+    // A book created as read was finished before it was added
+    let create_read_query = format!(
+        r#"
+        mutation {{
+            createBook(bookData: {{
+                title: "Activity Already Read Book"
+                authorIds: ["{author_id}"]
+                isbn: ""
+                read: true
+                owned: false
+                priority: 50
+                format: E_BOOK
+                store: KINDLE
+            }}) {{ book {{ id }} }}
+        }}
+        "#
+    );
+    let (_, response) = graphql_request(&create_read_query, Some(&token)).await?;
+    assert_no_graphql_errors(&response, "create a read book");
+    let already_read_book = response["data"]["createBook"]["book"]["id"]
+        .as_str()
+        .context("book id should be a string")?
+        .to_string();
+
+    // The range may straddle a month boundary, so sum over its periods.
+    let now = time::OffsetDateTime::now_utc().unix_timestamp();
+    let activity_query = format!(
+        r#"{{ readingActivity(from: {}, to: {}, granularity: MONTH) {{
+            periodStart finishedCount addedCount deletedCount
+        }} }}"#,
+        now - 3600,
+        now + 3600
+    );
+    let (_, response) = graphql_request(&activity_query, Some(&token)).await?;
+    assert_no_graphql_errors(&response, "readingActivity");
+    let periods = response["data"]["readingActivity"]
+        .as_array()
+        .context("readingActivity should be an array")?;
+    let total = |field: &str| {
+        periods
+            .iter()
+            .map(|period| period[field].as_i64().unwrap_or_default())
+            .sum::<i64>()
+    };
+    assert_eq!(total("finishedCount"), 1);
+    assert_eq!(total("addedCount"), 3);
+    assert_eq!(total("deletedCount"), 1);
+
+    let (_, response) = graphql_request(
+        &format!(r#"{{ readingActivity(from: {now}, to: {now}) {{ periodStart }} }}"#),
+        Some(&token),
+    )
+    .await?;
+    assert_graphql_errors(&response, "empty activity range");
+
+    delete_test_book(&read_book, &token).await?;
+    delete_test_book(&already_read_book, &token).await?;
+    delete_test_author(&author_id, &token).await?;
+    Ok(())
+}
+
+#[tokio::test]
+#[serial]
+async fn e2e_library_as_of_and_diff_replay_the_history() -> Result<()> {
+    let (_user_id, token) = create_test_user().await?;
+
+    let author_id =
+        create_test_author(&format!("History Author {}", uuid::Uuid::new_v4()), &token).await?;
+    let (book_id, _, created_set) =
+        create_test_book_with_event("As Of Original", &author_id, &token).await?;
+    let update_query = format!(
+        r#"
+        mutation {{
+            updateBook(bookData: {{
+                id: "{}"
+                title: "As Of Renamed"
+                authorIds: ["{}"]
+                isbn: ""
+                read: false
+                owned: false
+                priority: 50
+                format: E_BOOK
+                store: KINDLE
+            }}) {{ eventSetId }}
+        }}
+        "#,
+        book_id, author_id
+    );
+    let (_, response) = graphql_request(&update_query, Some(&token)).await?;
+    assert_no_graphql_errors(&response, "rename book");
+    let renamed_set = response["data"]["updateBook"]["eventSetId"]
+        .as_str()
+        .context("updateBook eventSetId should be a string")?
+        .to_owned();
+    delete_test_book(&book_id, &token).await?;
+
+    let query = format!(
+        r#"{{
+            created: booksAsOf(eventSetId: "{created_set}") {{ id title authorIds }}
+            authors: authorsAsOf(eventSetId: "{created_set}") {{ id }}
+            diff: libraryDiff(from: {{ eventSetId: "{created_set}" }}, to: {{ eventSetId: "{renamed_set}" }}) {{
+                books {{ added {{ id }} removed {{ id }} changed {{ before {{ title }} after {{ title }} }} }}
+            }}
+        }}"#
+    );
+    let (_, response) = graphql_request(&query, Some(&token)).await?;
+    assert_no_graphql_errors(&response, "as-of queries");
+    let data = &response["data"];
+    assert_eq!(
+        data["created"],
+        serde_json::json!([{ "id": book_id, "title": "As Of Original", "authorIds": [author_id] }])
+    );
+    assert_eq!(data["authors"], serde_json::json!([{ "id": author_id }]));
+    assert_eq!(
+        data["diff"]["books"],
+        serde_json::json!({
+            "added": [],
+            "removed": [],
+            "changed": [{ "before": { "title": "As Of Original" }, "after": { "title": "As Of Renamed" } }],
+        })
+    );
+
+    let (_, response) = graphql_request(
+        r#"{ booksAsOf(at: "2999-01-01T00:00:00Z") { id } }"#,
+        Some(&token),
+    )
+    .await?;
+    assert_no_graphql_errors(&response, "booksAsOf after deletion");
+    assert_eq!(response["data"]["booksAsOf"], serde_json::json!([]));
+
+    delete_test_author(&author_id, &token).await?;
+    Ok(())
+}
+
 #[tokio::test]
 #[serial]
 async fn e2e_create_author_returns_event_matching_history_and_usable_for_restore() -> Result<()> {
@@ -676,3 +676,252 @@ async fn e2e_import_books_empty_returns_error() -> Result<()> {
 
     Ok(())
 }
+
+fn import_entry(title: &str, author_name: &str, isbn: &str, priority: i32) -> String {
+    format!(
+        r#"{{
+            title: "{title}"
+            authorNames: ["{author_name}"]
+            isbn: "{isbn}"
+            read: false
+            owned: true
+            priority: {priority}
+            format: E_BOOK
+            store: KINDLE
+        }}"#
+    )
+}
+
+#[tokio::test]
+#[serial]
+async fn e2e_import_books_upsert_by_isbn_updates_matching_books() -> Result<()> {
+    let (_user_id, token) = create_test_user().await?;
+    let author_name = format!("Upsert Import Author {}", uuid::Uuid::new_v4());
+
+    let seed = import_entry("Upsert Seed", &author_name, "9784062758574", 50);
+    let (_, response) = graphql_request(
+        &format!(r#"mutation {{ importBooks(books: [{seed}]) {{ created {{ id }} }} }}"#),
+        Some(&token),
+    )
+    .await?;
+    assert_no_graphql_errors(&response, "seeding importBooks");
+    let seed_id = response["data"]["importBooks"]["created"][0]["id"]
+        .as_str()
+        .context("seeded book id should be a string")?
+        .to_owned();
+
+    let entries = [
+        import_entry("Upsert Seed", &author_name, "978-4-06-275857-4", 80),
+        import_entry("Upsert Fresh", &author_name, "", 50),
+    ]
+    .join(",\n");
+    let query = format!(
+        r#"
+        mutation {{
+            importBooks(books: [{entries}], mode: UPSERT_BY_ISBN) {{
+                created {{ title }}
+                updated {{ id priority }}
+                skipped {{ id }}
+                eventSetId
+            }}
+        }}
+        "#
+    );
+    let (_, response) = graphql_request(&query, Some(&token)).await?;
+    assert_no_graphql_errors(&response, "importBooks in UPSERT_BY_ISBN mode");
+    let payload = &response["data"]["importBooks"];
+    assert_eq!(
+        payload["created"][0]["title"].as_str(),
+        Some("Upsert Fresh")
+    );
+    assert_eq!(payload["updated"][0]["id"].as_str(), Some(seed_id.as_str()));
+    assert_eq!(payload["updated"][0]["priority"].as_i64(), Some(80));
+    assert_eq!(payload["skipped"].as_array().map(Vec::len), Some(0));
+
+    let event_set_id = payload["eventSetId"]
+        .as_str()
+        .context("eventSetId should be a string")?;
+    let event_set_query = format!(
+        r#"{{ eventSet(id: "{event_set_id}") {{ bookEvents {{ bookId operation }} authorEvents {{ name }} }} }}"#
+    );
+    let (_, response) = graphql_request(&event_set_query, Some(&token)).await?;
+    let book_events = response["data"]["eventSet"]["bookEvents"]
+        .as_array()
+        .context("bookEvents should be an array")?;
+    assert_eq!(book_events.len(), 2);
+    assert!(book_events.iter().any(|event| {
+        event["bookId"].as_str() == Some(seed_id.as_str())
+            && event["operation"].as_str() == Some("update")
+    }));
+    assert_eq!(
+        response["data"]["eventSet"]["authorEvents"]
+            .as_array()
+            .map(Vec::len),
+        Some(0),
+        "the existing author should be reused"
+    );
+    Ok(())
+}
+
+#[tokio::test]
+#[serial]
+async fn e2e_import_books_skip_existing_records_nothing_for_known_books() -> Result<()> {
+    let (_user_id, token) = create_test_user().await?;
+    let author_name = format!("Skip Import Author {}", uuid::Uuid::new_v4());
+
+    let seed = import_entry("Skip Seed", &author_name, "", 50);
+    let (_, response) = graphql_request(
+        &format!(r#"mutation {{ importBooks(books: [{seed}]) {{ eventSetId }} }}"#),
+        Some(&token),
+    )
+    .await?;
+    assert_no_graphql_errors(&response, "seeding importBooks");
+
+    let entry = import_entry("Skip Seed", &author_name, "", 90);
+    let query = format!(
+        r#"
+        mutation {{
+            importBooks(books: [{entry}], mode: SKIP_EXISTING) {{
+                created {{ id }}
+                skipped {{ title priority }}
+                eventSetId
+            }}
+        }}
+        "#
+    );
+    let (_, response) = graphql_request(&query, Some(&token)).await?;
+    assert_no_graphql_errors(&response, "importBooks in SKIP_EXISTING mode");
+    let payload = &response["data"]["importBooks"];
+    assert_eq!(payload["created"].as_array().map(Vec::len), Some(0));
+    assert_eq!(payload["skipped"][0]["title"].as_str(), Some("Skip Seed"));
+    assert_eq!(payload["skipped"][0]["priority"].as_i64(), Some(50));
+    assert!(payload["eventSetId"].is_null());
+
+    let (_, response) =
+        graphql_request(r#"{ eventSetsConnection { totalCount } }"#, Some(&token)).await?;
+    assert_eq!(
+        response["data"]["eventSetsConnection"]["totalCount"].as_i64(),
+        Some(1)
+    );
+    Ok(())
+}
+
+#[tokio::test]
+#[serial]
+async fn e2e_import_books_dry_run_reports_without_writing() -> Result<()> {
+    let (_user_id, token) = create_test_user().await?;
+    let run_id = uuid::Uuid::new_v4();
+    let existing_author = format!("Dry Run Existing Author {run_id}");
+    let new_author = format!("Dry Run New Author {run_id}");
+    create_test_author(&existing_author, &token).await?;
+
+    let entries = [
+        import_entry("Dry Run Book", &existing_author, "", 50),
+        import_entry("Dry Run Invalid", &new_author, "1", 50),
+        import_entry("Dry Run Other", &new_author, "", 50),
+    ]
+    .join(",\n");
+    let query = format!(
+        r#"
+        mutation {{
+            importBooks(books: [{entries}], dryRun: true) {{
+                created {{ title }}
+                newAuthorNames
+                reusedAuthorNames
+                errors {{ index message }}
+                eventSetId
+            }}
+        }}
+        "#
+    );
+    let (_, response) = graphql_request(&query, Some(&token)).await?;
+    assert_no_graphql_errors(&response, "importBooks dry run");
+    let payload = &response["data"]["importBooks"];
+    assert_eq!(payload["created"].as_array().map(Vec::len), Some(2));
+    assert_eq!(
+        payload["newAuthorNames"],
+        serde_json::json!([new_author.as_str()])
+    );
+    assert_eq!(
+        payload["reusedAuthorNames"],
+        serde_json::json!([existing_author.as_str()])
+    );
+    assert_eq!(payload["errors"][0]["index"].as_i64(), Some(1));
+    assert!(payload["eventSetId"].is_null());
+
+    let (_, response) = graphql_request(
+        r#"{ booksConnection { totalCount } authorsConnection { totalCount } }"#,
+        Some(&token),
+    )
+    .await?;
+    assert_eq!(
+        response["data"]["booksConnection"]["totalCount"].as_i64(),
+        Some(0)
+    );
+    assert_eq!(
+        response["data"]["authorsConnection"]["totalCount"].as_i64(),
+        Some(1)
+    );
+    Ok(())
+}
+
+#[tokio::test]
+#[serial]
+async fn e2e_import_books_error_names_the_invalid_entry() -> Result<()> {
+    let (_user_id, token) = create_test_user().await?;
+
+    let entries = [
+        import_entry("Valid Entry", "Some Author", "", 50),
+        import_entry("Invalid Entry", "Some Author", "1", 50),
+    ]
+    .join(",\n");
+    let query = format!(r#"mutation {{ importBooks(books: [{entries}]) {{ eventSetId }} }}"#);
+    let (_, response) = graphql_request(&query, Some(&token)).await?;
+    let message = response["errors"][0]["message"]
+        .as_str()
+        .context("message should be a string")?;
+    assert!(message.contains("book 1"), "{message}");
+    Ok(())
+}
+
+#[tokio::test]
+#[serial]
+async fn e2e_import_books_partial_imports_the_valid_entries() -> Result<()> {
+    let (_user_id, token) = create_test_user().await?;
+    let author_name = format!("Partial Import Author {}", uuid::Uuid::new_v4());
+
+    let entries = [
+        import_entry("Partial Kept", &author_name, "", 50),
+        import_entry("Partial Dropped", &author_name, "", 500),
+    ]
+    .join(",\n");
+    let query = format!(
+        r#"
+        mutation {{
+            importBooks(books: [{entries}], partial: true) {{
+                created {{ title }}
+                errors {{ index field message }}
+                eventSetId
+            }}
+        }}
+        "#
+    );
+    let (_, response) = graphql_request(&query, Some(&token)).await?;
+    assert_no_graphql_errors(&response, "partial importBooks");
+    let payload = &response["data"]["importBooks"];
+    assert_eq!(
+        payload["created"][0]["title"].as_str(),
+        Some("Partial Kept")
+    );
+    assert_eq!(payload["errors"][0]["index"].as_i64(), Some(1));
+    assert_eq!(payload["errors"][0]["field"].as_str(), Some("PRIORITY"));
+    assert!(payload["eventSetId"].is_string());
+
+    let (_, response) =
+        graphql_request(r#"{ booksConnection { totalCount } }"#, Some(&token)).await?;
+    assert_eq!(
+        response["data"]["booksConnection"]["totalCount"].as_i64(),
+        Some(1)
+    );
+    Ok(())
+}
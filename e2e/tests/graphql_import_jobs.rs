@@ -0,0 +1,173 @@
+// E2E tests that run against a real Postgres instance.
+
+#![cfg(test)]
+
+use std::time::Duration;
+
+use anyhow::{Context, Result, bail};
+use bookshelf_e2e::*;
+use serial_test::serial;
+
+fn import_entry(title: &str, author_name: &str) -> String {
+    format!(
+        r#"{{
+            title: "{title}"
+            authorNames: ["{author_name}"]
+            isbn: ""
+            read: false
+            owned: true
+            priority: 50
+            format: E_BOOK
+            store: KINDLE
+        }}"#
+    )
+}
+
+// Polls `importJob` until the job has finished and returns it.
+async fn wait_for_import_job(job_id: &str, token: &str) -> Result<serde_json::Value> {
+    let query = format!(
+        r#"{{ importJob(id: "{job_id}") {{
+            status totalCount processedCount failedCount
+            failures {{ index field message }}
+            eventSetIds error
+        }} }}"#
+    );
+    for _ in 0..50 {
+        let (_, response) = graphql_request(&query, Some(token)).await?;
+        assert_no_graphql_errors(&response, "importJob");
+        let job = &response["data"]["importJob"];
+        match job["status"].as_str() {
+            Some("PENDING") | Some("RUNNING") => {
+                tokio::time::sleep(Duration::from_millis(100)).await;
+            }
+            _ => return Ok(job.clone()),
+        }
+    }
+    bail!("import job {job_id} did not finish")
+}
+
+#[tokio::test]
+#[serial]
+async fn e2e_start_import_runs_the_job_in_the_background() -> Result<()> {
+    let (_user_id, token) = create_test_user().await?;
+    let author_name = format!("Job Author {}", uuid::Uuid::new_v4());
+    let books = [
+        import_entry("Job Book One", &author_name),
+        import_entry("Job Book Two", &author_name),
+    ]
+    .join(", ");
+
+    let query = format!(
+        r#"mutation {{ startImport(books: [{books}]) {{ id status totalCount processedCount }} }}"#
+    );
+    let (_, response) = graphql_request(&query, Some(&token)).await?;
+    assert_no_graphql_errors(&response, "startImport");
+    let started = &response["data"]["startImport"];
+    assert_eq!(started["totalCount"].as_i64(), Some(2));
+    let job_id = started["id"]
+        .as_str()
+        .context("id should be a string")?
+        .to_string();
+
+    let job = wait_for_import_job(&job_id, &token).await?;
+    assert_eq!(job["status"].as_str(), Some("SUCCEEDED"));
+    assert_eq!(job["processedCount"].as_i64(), Some(2));
+    assert_eq!(job["failedCount"].as_i64(), Some(0));
+    let event_set_ids = job["eventSetIds"]
+        .as_array()
+        .context("eventSetIds should be an array")?;
+    assert_eq!(event_set_ids.len(), 1);
+
+    let event_set_id = event_set_ids[0].as_str().context("id should be a string")?;
+    let event_set_query = format!(
+        r#"{{ eventSet(id: "{event_set_id}") {{ operation bookEvents {{ operation }} }} }}"#
+    );
+    let (_, response) = graphql_request(&event_set_query, Some(&token)).await?;
+    let event_set = &response["data"]["eventSet"];
+    assert_eq!(event_set["operation"].as_str(), Some("import_books"));
+    assert_eq!(event_set["bookEvents"].as_array().map(Vec::len), Some(2));
+
+    // Cancelling a finished job leaves it as it is.
+    let cancel_query =
+        format!(r#"mutation {{ cancelImport(id: "{job_id}") {{ status processedCount }} }}"#);
+    let (_, response) = graphql_request(&cancel_query, Some(&token)).await?;
+    assert_no_graphql_errors(&response, "cancelImport");
+    assert_eq!(
+        response["data"]["cancelImport"]["status"].as_str(),
+        Some("SUCCEEDED")
+    );
+    Ok(())
+}
+
+#[tokio::test]
+#[serial]
+async fn e2e_start_import_partial_reports_invalid_entries() -> Result<()> {
+    let (_user_id, token) = create_test_user().await?;
+    let author_name = format!("Partial Job Author {}", uuid::Uuid::new_v4());
+    let books = [
+        import_entry("", &author_name),
+        import_entry("Partial Job Book", &author_name),
+    ]
+    .join(", ");
+
+    let query = format!(r#"mutation {{ startImport(books: [{books}], partial: true) {{ id }} }}"#);
+    let (_, response) = graphql_request(&query, Some(&token)).await?;
+    assert_no_graphql_errors(&response, "startImport");
+    let job_id = response["data"]["startImport"]["id"]
+        .as_str()
+        .context("id should be a string")?
+        .to_string();
+
+    let job = wait_for_import_job(&job_id, &token).await?;
+    assert_eq!(job["status"].as_str(), Some("SUCCEEDED"));
+    assert_eq!(job["processedCount"].as_i64(), Some(2));
+    assert_eq!(job["failedCount"].as_i64(), Some(1));
+    assert_eq!(job["failures"][0]["index"].as_i64(), Some(0));
+    assert_eq!(job["failures"][0]["field"].as_str(), Some("TITLE"));
+    Ok(())
+}
+
+#[tokio::test]
+#[serial]
+async fn e2e_start_import_rejects_an_invalid_entry_unless_partial() -> Result<()> {
+    let (_user_id, token) = create_test_user().await?;
+    let books = [
+        import_entry("Valid Book", "Author"),
+        import_entry("", "Author"),
+    ]
+    .join(", ");
+
+    let query = format!(r#"mutation {{ startImport(books: [{books}]) {{ id }} }}"#);
+    let (_, response) = graphql_request(&query, Some(&token)).await?;
+    let message = response["errors"][0]["message"]
+        .as_str()
+        .context("message should be a string")?;
+    assert!(message.contains("book 1"), "{message}");
+    Ok(())
+}
+
+#[tokio::test]
+#[serial]
+async fn e2e_import_job_is_not_visible_to_other_users() -> Result<()> {
+    let (_user_id, token) = create_test_user().await?;
+    let query = format!(
+        r#"mutation {{ startImport(books: [{}]) {{ id }} }}"#,
+        import_entry("Private Job Book", "Private Author")
+    );
+    let (_, response) = graphql_request(&query, Some(&token)).await?;
+    assert_no_graphql_errors(&response, "startImport");
+    let job_id = response["data"]["startImport"]["id"]
+        .as_str()
+        .context("id should be a string")?
+        .to_string();
+
+    let (_other_user_id, other_token) = create_test_user().await?;
+    let (_, response) = graphql_request(
+        &format!(r#"{{ importJob(id: "{job_id}") {{ id }} }}"#),
+        Some(&other_token),
+    )
+    .await?;
+    assert_no_graphql_errors(&response, "importJob");
+    assert!(response["data"]["importJob"].is_null());
+    Ok(())
+}
@@ -397,3 +397,348 @@ async fn e2e_restore_mutations_are_user_isolated() -> Result<()> {
     delete_test_author(&author_id, &owner_token).await?;
     Ok(())
 }
+
+const REVERT_FIELDS: &str = r#"
+    applied eventSetId
+    changes { entityType entityId action changes { field oldValue newValue } }
+    conflicts { entityType entityId reason laterEventId laterEventSetId relatedIds }
+"#;
+
+#[tokio::test]
+#[serial]
+async fn e2e_revert_event_set_undoes_an_import() -> Result<()> {
+    let (_user_id, token) = create_test_user().await?;
+
+    let author_name = format!("Revert Author {}", uuid::Uuid::new_v4());
+    let import_query = format!(
+        r#"
+        mutation {{
+            importBooks(books: [
+                {{ title: "Revert One" authorNames: ["{author_name}"] isbn: "" read: false
+                   owned: false priority: 50 format: E_BOOK store: KINDLE }},
+                {{ title: "Revert Two" authorNames: ["{author_name}"] isbn: "" read: false
+                   owned: false priority: 50 format: E_BOOK store: KINDLE }}
+            ]) {{ eventSetId books {{ id authors {{ id }} }} }}
+        }}
+        "#
+    );
+    let (_, response) = graphql_request(&import_query, Some(&token)).await?;
+    assert_no_graphql_errors(&response, "importBooks");
+    let payload = &response["data"]["importBooks"];
+    let import_set_id = payload["eventSetId"]
+        .as_str()
+        .context("eventSetId should be a string")?;
+    let book_id = payload["books"][0]["id"]
+        .as_str()
+        .context("book id should be a string")?;
+    let author_id = payload["books"][0]["authors"][0]["id"]
+        .as_str()
+        .context("author id should be a string")?;
+
+    // A dry run plans removing both books and the new author but changes nothing.
+    let dry_run_query = format!(
+        r#"mutation {{ revertEventSet(id: "{import_set_id}", dryRun: true) {{ {REVERT_FIELDS} }} }}"#
+    );
+    let (_, response) = graphql_request(&dry_run_query, Some(&token)).await?;
+    assert_no_graphql_errors(&response, "revertEventSet dry run");
+    let plan = &response["data"]["revertEventSet"];
+    assert_eq!(plan["applied"].as_bool(), Some(false));
+    assert!(plan["eventSetId"].is_null());
+    assert_eq!(plan["conflicts"].as_array().map(Vec::len), Some(0));
+    let changes = plan["changes"]
+        .as_array()
+        .context("changes should be an array")?;
+    assert_eq!(changes.len(), 3, "two books and one author: {changes:?}");
+    assert!(changes.iter().all(|c| c["action"] == "REMOVE"));
+    let author_change = changes
+        .iter()
+        .find(|c| c["entityType"] == "AUTHOR")
+        .context("the author should be removed")?;
+    assert_eq!(author_change["entityId"].as_str(), Some(author_id));
+    assert_eq!(
+        author_change["changes"][0]["oldValue"].as_str(),
+        Some(author_name.as_str())
+    );
+
+    let book_query = format!(r#"{{ book(id: "{book_id}") {{ id }} }}"#);
+    let (_, response) = graphql_request(&book_query, Some(&token)).await?;
+    assert!(
+        response["data"]["book"].is_object(),
+        "a dry run must not remove the book"
+    );
+
+    // Reverting for real removes everything in one revert_event_set set.
+    let revert_query =
+        format!(r#"mutation {{ revertEventSet(id: "{import_set_id}") {{ {REVERT_FIELDS} }} }}"#);
+    let (_, response) = graphql_request(&revert_query, Some(&token)).await?;
+    assert_no_graphql_errors(&response, "revertEventSet");
+    let result = &response["data"]["revertEventSet"];
+    assert_eq!(result["applied"].as_bool(), Some(true));
+    let revert_set_id = result["eventSetId"]
+        .as_str()
+        .context("eventSetId should be set once applied")?;
+
+    let (_, response) = graphql_request(&book_query, Some(&token)).await?;
+    assert!(response["data"]["book"].is_null(), "book should be removed");
+    let author_query = format!(r#"{{ author(id: "{author_id}") {{ id }} }}"#);
+    let (_, response) = graphql_request(&author_query, Some(&token)).await?;
+    assert!(
+        response["data"]["author"].is_null(),
+        "author should be removed"
+    );
+
+    let set_query = format!(
+        r#"{{ eventSet(id: "{revert_set_id}") {{ operation bookEvents {{ operation }} authorEvents {{ operation }} }} }}"#
+    );
+    let (_, response) = graphql_request(&set_query, Some(&token)).await?;
+    let set = &response["data"]["eventSet"];
+    assert_eq!(set["operation"].as_str(), Some("revert_event_set"));
+    assert_eq!(set["bookEvents"].as_array().map(Vec::len), Some(2));
+    assert_eq!(set["authorEvents"].as_array().map(Vec::len), Some(1));
+
+    // Reverting the revert brings the import back.
+    let (_, response) = graphql_request(
+        &format!(r#"mutation {{ revertEventSet(id: "{revert_set_id}") {{ {REVERT_FIELDS} }} }}"#),
+        Some(&token),
+    )
+    .await?;
+    assert_no_graphql_errors(&response, "revertEventSet of a revert");
+    assert_eq!(
+        response["data"]["revertEventSet"]["applied"].as_bool(),
+        Some(true)
+    );
+    let (_, response) = graphql_request(&book_query, Some(&token)).await?;
+    assert_eq!(response["data"]["book"]["id"].as_str(), Some(book_id));
+
+    Ok(())
+}
+
+#[tokio::test]
+#[serial]
+async fn e2e_revert_event_set_reports_later_changes() -> Result<()> {
+    let (_user_id, token) = create_test_user().await?;
+
+    let author_id =
+        create_test_author(&format!("Conflict Author {}", uuid::Uuid::new_v4()), &token).await?;
+    let (book_id, _, create_set_id) =
+        create_test_book_with_event("Before Conflict", &author_id, &token).await?;
+    let update_query = format!(
+        r#"
+        mutation {{
+            updateBook(bookData: {{
+                id: "{book_id}" title: "After Conflict" authorIds: ["{author_id}"] isbn: ""
+                read: false owned: false priority: 50 format: E_BOOK store: KINDLE
+            }}) {{ eventId eventSetId }}
+        }}
+        "#
+    );
+    let (_, response) = graphql_request(&update_query, Some(&token)).await?;
+    assert_no_graphql_errors(&response, "updateBook");
+    let update = &response["data"]["updateBook"];
+
+    let revert_query =
+        format!(r#"mutation {{ revertEventSet(id: "{create_set_id}") {{ {REVERT_FIELDS} }} }}"#);
+    let (_, response) = graphql_request(&revert_query, Some(&token)).await?;
+    assert_no_graphql_errors(&response, "revertEventSet");
+    let result = &response["data"]["revertEventSet"];
+    assert_eq!(result["applied"].as_bool(), Some(false));
+    let conflicts = result["conflicts"]
+        .as_array()
+        .context("conflicts should be an array")?;
+    assert_eq!(conflicts.len(), 1, "{conflicts:?}");
+    assert_eq!(conflicts[0]["entityType"], "BOOK");
+    assert_eq!(conflicts[0]["entityId"].as_str(), Some(book_id.as_str()));
+    assert_eq!(conflicts[0]["reason"], "CHANGED_LATER");
+    assert_eq!(conflicts[0]["laterEventId"], update["eventId"]);
+    assert_eq!(conflicts[0]["laterEventSetId"], update["eventSetId"]);
+
+    let book_query = format!(r#"{{ book(id: "{book_id}") {{ title }} }}"#);
+    let (_, response) = graphql_request(&book_query, Some(&token)).await?;
+    assert_eq!(
+        response["data"]["book"]["title"].as_str(),
+        Some("After Conflict"),
+        "a conflicting revert must not change the book"
+    );
+
+    // Unknown event sets are reported as errors.
+    let (_, response) = graphql_request(
+        &format!(
+            r#"mutation {{ revertEventSet(id: "{}") {{ applied }} }}"#,
+            uuid::Uuid::new_v4()
+        ),
+        Some(&token),
+    )
+    .await?;
+    assert_graphql_errors(&response, "revertEventSet of an unknown set");
+
+    Ok(())
+}
+
+#[tokio::test]
+#[serial]
+async fn e2e_restore_book_cascade_brings_back_authors() -> Result<()> {
+    let (_user_id, token) = create_test_user().await?;
+
+    let suffix = uuid::Uuid::new_v4();
+    let deleted_name = format!("Cascade Deleted {suffix}");
+    let deleted_id = create_test_author(&deleted_name, &token).await?;
+    let merged_id = create_test_author(&format!("Cascade Merged {suffix}"), &token).await?;
+    let destination_id =
+        create_test_author(&format!("Cascade Destination {suffix}"), &token).await?;
+
+    let create_query = format!(
+        r#"
+        mutation {{
+            createBook(bookData: {{
+                title: "Cascade Book" authorIds: ["{deleted_id}", "{merged_id}"] isbn: ""
+                read: false owned: false priority: 50 format: E_BOOK store: KINDLE
+            }}) {{ book {{ id }} eventId }}
+        }}
+        "#
+    );
+    let (_, response) = graphql_request(&create_query, Some(&token)).await?;
+    assert_no_graphql_errors(&response, "createBook");
+    let book_id = response["data"]["createBook"]["book"]["id"]
+        .as_str()
+        .context("book id should be a string")?
+        .to_owned();
+    let create_event_id = response["data"]["createBook"]["eventId"]
+        .as_str()
+        .context("eventId should be a string")?
+        .to_owned();
+
+    // Remove the book, then both of its authors: one deleted, one merged.
+    delete_test_book(&book_id, &token).await?;
+    delete_test_author(&deleted_id, &token).await?;
+    let merge_query = format!(
+        r#"mutation {{ mergeAuthor(sourceAuthorId: "{merged_id}", destinationAuthorId: "{destination_id}") {{ eventSetId }} }}"#
+    );
+    let (_, response) = graphql_request(&merge_query, Some(&token)).await?;
+    assert_no_graphql_errors(&response, "mergeAuthor");
+
+    // Without cascading, the missing authors make the restore fail.
+    let restore_fields = "book { id authors { id } } eventSetId restoredAuthors { id name } \
+                          remappedAuthors { mergedAuthorId author { id } }";
+    let (_, response) = graphql_request(
+        &format!(
+            r#"mutation {{ restoreBook(eventId: "{create_event_id}") {{ {restore_fields} }} }}"#
+        ),
+        Some(&token),
+    )
+    .await?;
+    assert_graphql_errors(&response, "restoreBook without cascade");
+
+    let (_, response) = graphql_request(
+        &format!(
+            r#"mutation {{ restoreBook(eventId: "{create_event_id}", cascade: true) {{ {restore_fields} }} }}"#
+        ),
+        Some(&token),
+    )
+    .await?;
+    assert_no_graphql_errors(&response, "restoreBook with cascade");
+    let payload = &response["data"]["restoreBook"];
+    let author_ids: Vec<&str> = payload["book"]["authors"]
+        .as_array()
+        .context("authors should be an array")?
+        .iter()
+        .filter_map(|a| a["id"].as_str())
+        .collect();
+    assert_eq!(author_ids.len(), 2, "{author_ids:?}");
+    assert!(author_ids.contains(&deleted_id.as_str()));
+    assert!(author_ids.contains(&destination_id.as_str()));
+    assert_eq!(
+        payload["restoredAuthors"][0]["name"].as_str(),
+        Some(deleted_name.as_str())
+    );
+    assert_eq!(payload["restoredAuthors"].as_array().map(Vec::len), Some(1));
+    assert_eq!(
+        payload["remappedAuthors"][0]["mergedAuthorId"].as_str(),
+        Some(merged_id.as_str())
+    );
+    assert_eq!(
+        payload["remappedAuthors"][0]["author"]["id"].as_str(),
+        Some(destination_id.as_str())
+    );
+
+    // The author came back in the same event set as the book.
+    let event_set_id = payload["eventSetId"]
+        .as_str()
+        .context("eventSetId should be a string")?;
+    let (_, response) = graphql_request(
+        &format!(
+            r#"{{ eventSet(id: "{event_set_id}") {{ bookEvents {{ operation }} authorEvents {{ authorId operation }} }} }}"#
+        ),
+        Some(&token),
+    )
+    .await?;
+    let author_events = response["data"]["eventSet"]["authorEvents"]
+        .as_array()
+        .context("authorEvents should be an array")?;
+    assert_eq!(author_events.len(), 1);
+    assert_eq!(
+        author_events[0]["authorId"].as_str(),
+        Some(deleted_id.as_str())
+    );
+    assert_eq!(author_events[0]["operation"].as_str(), Some("restore"));
+
+    Ok(())
+}
+
+#[tokio::test]
+#[serial]
+async fn e2e_restore_book_cascade_reports_a_name_taken_since() -> Result<()> {
+    let (_user_id, token) = create_test_user().await?;
+
+    let name = format!("Cascade Clash {}", uuid::Uuid::new_v4());
+    let deleted_id = create_test_author(&name, &token).await?;
+    let create_query = format!(
+        r#"
+        mutation {{
+            createBook(bookData: {{
+                title: "Clash Book" authorIds: ["{deleted_id}"] isbn: ""
+                read: false owned: false priority: 50 format: E_BOOK store: KINDLE
+            }}) {{ book {{ id }} eventId }}
+        }}
+        "#
+    );
+    let (_, response) = graphql_request(&create_query, Some(&token)).await?;
+    assert_no_graphql_errors(&response, "createBook");
+    let book_id = response["data"]["createBook"]["book"]["id"]
+        .as_str()
+        .context("book id should be a string")?
+        .to_owned();
+    let create_event_id = response["data"]["createBook"]["eventId"]
+        .as_str()
+        .context("eventId should be a string")?
+        .to_owned();
+
+    // The author is deleted and another one takes its name.
+    delete_test_book(&book_id, &token).await?;
+    delete_test_author(&deleted_id, &token).await?;
+    let holder_id = create_test_author(&name, &token).await?;
+
+    let (_, response) = graphql_request(
+        &format!(
+            r#"mutation {{ restoreBook(eventId: "{create_event_id}", cascade: true) {{ eventSetId }} }}"#
+        ),
+        Some(&token),
+    )
+    .await?;
+    assert_graphql_errors(&response, "restoreBook onto a taken name");
+    let message = response["errors"][0]["message"]
+        .as_str()
+        .context("error should have a message")?;
+    assert!(message.contains(&deleted_id), "{message}");
+    assert!(message.contains(&holder_id), "{message}");
+
+    // Nothing was restored.
+    let (_, response) = graphql_request(
+        &format!(r#"{{ book(id: "{book_id}") {{ id }} }}"#),
+        Some(&token),
+    )
+    .await?;
+    assert_no_graphql_errors(&response, "book after the failed restore");
+    assert!(response["data"]["book"].is_null());
+
+    Ok(())
+}
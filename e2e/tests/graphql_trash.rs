@@ -0,0 +1,168 @@
+// E2E tests that run against a real Postgres instance.
+
+#![cfg(test)]
+
+use anyhow::{Context, Result};
+use bookshelf_e2e::*;
+use serial_test::serial;
+
+#[tokio::test]
+#[serial]
+async fn e2e_deleted_books_lists_restorable_snapshots() -> Result<()> {
+    let (_user_id, token) = create_test_user().await?;
+    let author_id =
+        create_test_author(&format!("Trash Author {}", uuid::Uuid::new_v4()), &token).await?;
+    let book_id = create_test_book("Trashed Book", &author_id, &token).await?;
+    delete_test_book(&book_id, &token).await?;
+
+    let trash_query = r#"{
+        deletedBooks {
+            lastKnown { eventId bookId title authorIds }
+            deletion { eventId operation }
+        }
+    }"#;
+    let (_, response) = graphql_request(trash_query, Some(&token)).await?;
+    assert_no_graphql_errors(&response, "deletedBooks");
+    let deleted = response["data"]["deletedBooks"]
+        .as_array()
+        .context("deletedBooks should be an array")?;
+    assert_eq!(deleted.len(), 1);
+    let last_known = &deleted[0]["lastKnown"];
+    assert_eq!(last_known["bookId"].as_str(), Some(book_id.as_str()));
+    assert_eq!(last_known["title"].as_str(), Some("Trashed Book"));
+    assert_eq!(
+        last_known["authorIds"][0].as_str(),
+        Some(author_id.as_str())
+    );
+    assert_eq!(deleted[0]["deletion"]["operation"].as_str(), Some("delete"));
+
+    // The last known event restores the book and takes it out of the trash.
+    let restore_query = format!(
+        r#"mutation {{ restoreBook(eventId: "{}") {{ book {{ id title }} }} }}"#,
+        last_known["eventId"]
+            .as_str()
+            .context("eventId should be a string")?
+    );
+    let (_, response) = graphql_request(&restore_query, Some(&token)).await?;
+    assert_no_graphql_errors(&response, "restoreBook");
+    assert_eq!(
+        response["data"]["restoreBook"]["book"]["title"].as_str(),
+        Some("Trashed Book")
+    );
+
+    let (_, response) = graphql_request(trash_query, Some(&token)).await?;
+    assert_no_graphql_errors(&response, "deletedBooks after restore");
+    assert_eq!(
+        response["data"]["deletedBooks"].as_array().map(Vec::len),
+        Some(0)
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+#[serial]
+async fn e2e_deleted_authors_lists_deleted_authors() -> Result<()> {
+    let (_user_id, token) = create_test_user().await?;
+    let name = format!("Trash Author {}", uuid::Uuid::new_v4());
+    let author_id = create_test_author(&name, &token).await?;
+    delete_test_author(&author_id, &token).await?;
+
+    let (_, response) = graphql_request(
+        r#"{
+            deletedAuthors {
+                lastKnown { authorId name }
+                deletion { operation }
+                mergedIntoAuthorId
+            }
+        }"#,
+        Some(&token),
+    )
+    .await?;
+    assert_no_graphql_errors(&response, "deletedAuthors");
+    let deleted = response["data"]["deletedAuthors"]
+        .as_array()
+        .context("deletedAuthors should be an array")?;
+    assert_eq!(deleted.len(), 1);
+    assert_eq!(
+        deleted[0]["lastKnown"]["authorId"].as_str(),
+        Some(author_id.as_str())
+    );
+    assert_eq!(
+        deleted[0]["lastKnown"]["name"].as_str(),
+        Some(name.as_str())
+    );
+    assert!(deleted[0]["mergedIntoAuthorId"].is_null());
+
+    Ok(())
+}
+
+#[tokio::test]
+#[serial]
+async fn e2e_purge_trash_drops_deleted_history() -> Result<()> {
+    let (_user_id, token) = create_test_user().await?;
+    let author_id =
+        create_test_author(&format!("Trash Author {}", uuid::Uuid::new_v4()), &token).await?;
+    let book_id = create_test_book("Purged Book", &author_id, &token).await?;
+    delete_test_book(&book_id, &token).await?;
+
+    let (_, response) = graphql_request(
+        "mutation { purgeTrash(retentionDays: -1) { purgedBookCount } }",
+        Some(&token),
+    )
+    .await?;
+    assert_graphql_errors(&response, "purgeTrash with a negative retention");
+
+    // A generous window keeps the fresh deletion.
+    let purge = |days: i32| {
+        format!(
+            "mutation {{ purgeTrash(retentionDays: {days}) {{
+                purgedBookCount purgedAuthorCount purgedEventSetCount eventSetId
+            }} }}"
+        )
+    };
+    let (_, response) = graphql_request(&purge(30), Some(&token)).await?;
+    assert_no_graphql_errors(&response, "purgeTrash(30)");
+    assert_eq!(
+        response["data"]["purgeTrash"]["purgedBookCount"].as_i64(),
+        Some(0)
+    );
+    assert!(response["data"]["purgeTrash"]["eventSetId"].is_null());
+
+    let (_, response) = graphql_request(&purge(0), Some(&token)).await?;
+    assert_no_graphql_errors(&response, "purgeTrash(0)");
+    let purged = &response["data"]["purgeTrash"];
+    assert_eq!(purged["purgedBookCount"].as_i64(), Some(1));
+    assert_eq!(purged["purgedAuthorCount"].as_i64(), Some(0));
+    // The book's create and delete event sets.
+    assert_eq!(purged["purgedEventSetCount"].as_i64(), Some(2));
+    // The purge itself is recorded in a purge_trash event set.
+    let event_set_id = purged["eventSetId"]
+        .as_str()
+        .context("eventSetId should be set")?;
+    let event_set_query = format!(r#"{{ eventSet(id: "{event_set_id}") {{ operation }} }}"#);
+    let (_, response) = graphql_request(&event_set_query, Some(&token)).await?;
+    assert_no_graphql_errors(&response, "eventSet of the purge");
+    assert_eq!(
+        response["data"]["eventSet"]["operation"].as_str(),
+        Some("purge_trash")
+    );
+
+    let (_, response) =
+        graphql_request("{ deletedBooks { lastKnown { bookId } } }", Some(&token)).await?;
+    assert_no_graphql_errors(&response, "deletedBooks after purge");
+    assert_eq!(
+        response["data"]["deletedBooks"].as_array().map(Vec::len),
+        Some(0)
+    );
+
+    let history_query = format!(r#"{{ bookEvents(bookId: "{book_id}") {{ eventId }} }}"#);
+    let (_, response) = graphql_request(&history_query, Some(&token)).await?;
+    assert_no_graphql_errors(&response, "bookEvents after purge");
+    assert_eq!(
+        response["data"]["bookEvents"].as_array().map(Vec::len),
+        Some(0)
+    );
+
+    Ok(())
+}
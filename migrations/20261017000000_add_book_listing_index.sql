@@ -0,0 +1,2 @@
+-- Supports keyset pagination over a user's books in (created_at, id) order.
+CREATE INDEX book_user_id_created_at_id_idx ON book (user_id, created_at, id);
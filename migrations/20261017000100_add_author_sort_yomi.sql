@@ -0,0 +1,12 @@
+-- Reading used to list authors in gojūon order: yomi with katakana folded to
+-- hiragana, or name when yomi is empty. The "C" collation compares code
+-- points, which follow gojūon order within the hiragana block.
+ALTER TABLE author ADD COLUMN sort_yomi TEXT COLLATE "C" NOT NULL GENERATED ALWAYS AS (
+    translate(
+        CASE WHEN yomi = '' THEN name ELSE yomi END,
+        'ァアィイゥウェエォオカガキギクグケゲコゴサザシジスズセゼソゾタダチヂッツヅテデトドナニヌネノハバパヒビピフブプヘベペホボポマミムメモャヤュユョヨラリルレロヮワヰヱヲンヴヵヶ',
+        'ぁあぃいぅうぇえぉおかがきぎくぐけげこごさざしじすずせぜそぞただちぢっつづてでとどなにぬねのはばぱひびぴふぶぷへべぺほぼぽまみむめもゃやゅゆょよらりるれろゎわゐゑをんゔゕゖ'
+    )
+) STORED;
+
+CREATE INDEX author_user_id_sort_yomi_id_idx ON author (user_id, sort_yomi, id);
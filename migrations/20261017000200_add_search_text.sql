@@ -0,0 +1,24 @@
+-- Normalised text for the search query: NFKC folds full-width and half-width
+-- forms, and katakana is folded to hiragana. Must stay in sync with
+-- normalize_search_text in src/domain/repository/search.rs.
+CREATE EXTENSION IF NOT EXISTS pg_trgm;
+
+ALTER TABLE book ADD COLUMN search_text TEXT NOT NULL GENERATED ALWAYS AS (
+    translate(
+        normalize(title, NFKC),
+        'ァアィイゥウェエォオカガキギクグケゲコゴサザシジスズセゼソゾタダチヂッツヅテデトドナニヌネノハバパヒビピフブプヘベペホボポマミムメモャヤュユョヨラリルレロヮワヰヱヲンヴヵヶ',
+        'ぁあぃいぅうぇえぉおかがきぎくぐけげこごさざしじすずせぜそぞただちぢっつづてでとどなにぬねのはばぱひびぴふぶぷへべぺほぼぽまみむめもゃやゅゆょよらりるれろゎわゐゑをんゔゕゖ'
+    )
+) STORED;
+
+ALTER TABLE author ADD COLUMN search_text TEXT NOT NULL GENERATED ALWAYS AS (
+    translate(
+        normalize(name || ' ' || yomi, NFKC),
+        'ァアィイゥウェエォオカガキギクグケゲコゴサザシジスズセゼソゾタダチヂッツヅテデトドナニヌネノハバパヒビピフブプヘベペホボポマミムメモャヤュユョヨラリルレロヮワヰヱヲンヴヵヶ',
+        'ぁあぃいぅうぇえぉおかがきぎくぐけげこごさざしじすずせぜそぞただちぢっつづてでとどなにぬねのはばぱひびぴふぶぷへべぺほぼぽまみむめもゃやゅゆょよらりるれろゎわゐゑをんゔゕゖ'
+    )
+) STORED;
+
+-- Trigram indexes serve the substring (ILIKE '%term%') matches.
+CREATE INDEX book_search_text_trgm_idx ON book USING gin (search_text gin_trgm_ops);
+CREATE INDEX author_search_text_trgm_idx ON author USING gin (search_text gin_trgm_ops);
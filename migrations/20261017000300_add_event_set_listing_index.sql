@@ -0,0 +1,2 @@
+-- Supports keyset pagination over a user's event sets, newest first.
+CREATE INDEX event_set_user_id_created_at_id_idx ON event_set (user_id, created_at DESC, id DESC);
@@ -0,0 +1,3 @@
+INSERT INTO event_set_operation (operation)
+VALUES ('revert_event_set')
+ON CONFLICT DO NOTHING;
@@ -0,0 +1,3 @@
+INSERT INTO event_set_operation (operation)
+VALUES ('bulk_update_books')
+ON CONFLICT DO NOTHING;
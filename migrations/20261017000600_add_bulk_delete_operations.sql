@@ -0,0 +1,3 @@
+INSERT INTO event_set_operation (operation)
+VALUES ('delete_books'), ('delete_authors')
+ON CONFLICT DO NOTHING;
@@ -0,0 +1,3 @@
+INSERT INTO event_set_operation (operation)
+VALUES ('apply_changes')
+ON CONFLICT DO NOTHING;
@@ -0,0 +1,39 @@
+CREATE TABLE import_job_status (
+  status text NOT NULL PRIMARY KEY
+);
+
+INSERT INTO import_job_status VALUES
+  ('pending'),
+  ('running'),
+  ('succeeded'),
+  ('failed'),
+  ('cancelled');
+
+CREATE TABLE import_mode (
+  mode text NOT NULL PRIMARY KEY
+);
+
+INSERT INTO import_mode VALUES
+  ('create_only'),
+  ('upsert_by_isbn'),
+  ('upsert_by_title_and_authors'),
+  ('skip_existing');
+
+CREATE TABLE import_job (
+  id              uuid        NOT NULL PRIMARY KEY,
+  user_id         text        NOT NULL REFERENCES bookshelf_user(id),
+  status          text        NOT NULL REFERENCES import_job_status(status),
+  mode            text        NOT NULL REFERENCES import_mode(mode),
+  partial         boolean     NOT NULL,
+  entries         jsonb       NOT NULL,
+  total_count     integer     NOT NULL,
+  processed_count integer     NOT NULL DEFAULT 0,
+  event_set_ids   uuid[]      NOT NULL DEFAULT '{}',
+  failures        jsonb       NOT NULL DEFAULT '[]',
+  error           text,
+  created_at      timestamptz NOT NULL DEFAULT current_timestamp,
+  updated_at      timestamptz NOT NULL DEFAULT current_timestamp
+);
+
+-- Jobs the worker resumes on startup
+CREATE INDEX ON import_job (created_at) WHERE status IN ('pending', 'running');
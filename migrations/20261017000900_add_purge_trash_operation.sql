@@ -0,0 +1,3 @@
+INSERT INTO event_set_operation (operation)
+VALUES ('purge_trash')
+ON CONFLICT DO NOTHING;
@@ -0,0 +1,3 @@
+-- The worker running a job. Another worker only takes a running job over
+-- once its progress has not been saved for a while.
+ALTER TABLE import_job ADD COLUMN claimed_by uuid;
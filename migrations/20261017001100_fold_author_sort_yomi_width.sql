@@ -0,0 +1,17 @@
+-- Fold sort_yomi with NFKC before folding katakana to hiragana, as search
+-- does, so that half-width kana and full-width letters sort with their usual
+-- forms. The "C" collation then compares code points: gojūon order of the
+-- plain kana, with voiced, semi-voiced and small kana as letters of their own
+-- next to them (ぁ < あ, か < が < き). So がくせい sorts after かわ, not before
+-- it as in a dictionary.
+ALTER TABLE author DROP COLUMN sort_yomi;
+
+ALTER TABLE author ADD COLUMN sort_yomi TEXT COLLATE "C" NOT NULL GENERATED ALWAYS AS (
+    translate(
+        normalize(CASE WHEN yomi = '' THEN name ELSE yomi END, NFKC),
+        'ァアィイゥウェエォオカガキギクグケゲコゴサザシジスズセゼソゾタダチヂッツヅテデトドナニヌネノハバパヒビピフブプヘベペホボポマミムメモャヤュユョヨラリルレロヮワヰヱヲンヴヵヶ',
+        'ぁあぃいぅうぇえぉおかがきぎくぐけげこごさざしじすずせぜそぞただちぢっつづてでとどなにぬねのはばぱひびぴふぶぷへべぺほぼぽまみむめもゃやゅゆょよらりるれろゎわゐゑをんゔゕゖ'
+    )
+) STORED;
+
+CREATE INDEX author_user_id_sort_yomi_id_idx ON author (user_id, sort_yomi, id);
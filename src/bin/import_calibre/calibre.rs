@@ -0,0 +1,448 @@
+//! Reads a Calibre library, from its metadata.db or from the metadata.opf
+//! files Calibre keeps next to the files of each book.
+
+use std::{
+    collections::HashMap,
+    fs,
+    path::{Path, PathBuf},
+};
+
+use sqlx::{
+    Connection,
+    sqlite::{SqliteConnectOptions, SqliteConnection},
+};
+use thiserror::Error;
+use unicode_normalization::UnicodeNormalization;
+
+use bookshelf_api::{
+    common::types::{BookFormat, BookStore},
+    presentation::import::isbn::normalize_isbn,
+    use_case::dto::book::ImportBookEntryDto,
+};
+
+const DC_NAMESPACE: &str = "http://purl.org/dc/elements/1.1/";
+const OPF_NAMESPACE: &str = "http://www.idpf.org/2007/opf";
+const EBOOK_FORMATS: &[&str] = &[
+    "EPUB", "KEPUB", "PDF", "MOBI", "AZW", "AZW3", "AZW4", "KFX", "FB2", "CBZ", "CBR", "DJVU",
+];
+const KINDLE_FORMATS: &[&str] = &["AZW", "AZW3", "AZW4", "KFX"];
+
+/// The books of a Calibre library, ready to import.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CalibreLibrary {
+    pub books: Vec<ImportBookEntryDto>,
+    /// Yomi for author names, from Calibre's author sort where it is
+    /// written in kana.
+    pub author_yomi: HashMap<String, String>,
+}
+
+#[derive(Debug, Error)]
+pub enum CalibreImportError {
+    #[error("cannot read {path}: {source}")]
+    Io {
+        path: PathBuf,
+        source: std::io::Error,
+    },
+    #[error("invalid {path}: {message}")]
+    Opf { path: PathBuf, message: String },
+    #[error("cannot read metadata.db: {0}")]
+    Database(#[from] sqlx::Error),
+}
+
+// A book as Calibre describes it: authors with their sort names, and the
+// formats of the files it has.
+#[derive(Debug, Default)]
+struct CalibreBook {
+    title: String,
+    authors: Vec<(String, Option<String>)>,
+    isbn: String,
+    formats: Vec<String>,
+}
+
+#[derive(sqlx::FromRow)]
+struct BookRow {
+    id: i64,
+    title: String,
+}
+
+#[derive(sqlx::FromRow)]
+struct AuthorRow {
+    book: i64,
+    name: String,
+    sort: Option<String>,
+}
+
+#[derive(sqlx::FromRow)]
+struct ValueRow {
+    book: i64,
+    value: String,
+}
+
+/// Reads the books of the metadata.db at `path`, which is opened read-only.
+pub async fn read_metadata_db(path: &Path) -> Result<CalibreLibrary, CalibreImportError> {
+    let options = SqliteConnectOptions::new().filename(path).read_only(true);
+    let mut conn = SqliteConnection::connect_with(&options).await?;
+
+    let rows: Vec<BookRow> = sqlx::query_as("SELECT id, title FROM books ORDER BY id")
+        .fetch_all(&mut conn)
+        .await?;
+    let authors: Vec<AuthorRow> = sqlx::query_as(
+        "SELECT link.book, author.name, author.sort
+         FROM books_authors_link AS link
+         JOIN authors AS author ON author.id = link.author
+         ORDER BY link.book, link.id",
+    )
+    .fetch_all(&mut conn)
+    .await?;
+    let isbns: Vec<ValueRow> = sqlx::query_as(
+        "SELECT book, val AS value FROM identifiers WHERE lower(type) = 'isbn' ORDER BY id",
+    )
+    .fetch_all(&mut conn)
+    .await?;
+    let formats: Vec<ValueRow> =
+        sqlx::query_as("SELECT book, format AS value FROM data ORDER BY id")
+            .fetch_all(&mut conn)
+            .await?;
+    conn.close().await?;
+
+    let mut books: HashMap<i64, CalibreBook> = rows
+        .iter()
+        .map(|row| {
+            let book = CalibreBook {
+                title: row.title.clone(),
+                ..Default::default()
+            };
+            (row.id, book)
+        })
+        .collect();
+    for author in authors {
+        if let Some(book) = books.get_mut(&author.book) {
+            book.authors.push((author.name, author.sort));
+        }
+    }
+    for isbn in isbns {
+        if let Some(book) = books
+            .get_mut(&isbn.book)
+            .filter(|book| book.isbn.is_empty())
+        {
+            book.isbn = isbn.value;
+        }
+    }
+    for format in formats {
+        if let Some(book) = books.get_mut(&format.book) {
+            book.formats.push(format.value);
+        }
+    }
+
+    Ok(to_library(
+        rows.iter().filter_map(|row| books.remove(&row.id)),
+    ))
+}
+
+/// Reads the metadata.opf files under the folder at `path`, a Calibre
+/// library or any folder of book folders. The extensions of the other files
+/// next to each metadata.opf give the book's formats.
+pub fn read_opf_folder(path: &Path) -> Result<CalibreLibrary, CalibreImportError> {
+    let mut opf_paths = Vec::new();
+    find_opf_files(path, &mut opf_paths)?;
+    opf_paths.sort();
+
+    let books = opf_paths
+        .iter()
+        .map(|opf_path| {
+            let xml = fs::read_to_string(opf_path).map_err(|source| CalibreImportError::Io {
+                path: opf_path.clone(),
+                source,
+            })?;
+            let mut book = parse_opf(&xml).map_err(|message| CalibreImportError::Opf {
+                path: opf_path.clone(),
+                message,
+            })?;
+            book.formats = sibling_formats(opf_path)?;
+            Ok(book)
+        })
+        .collect::<Result<Vec<_>, CalibreImportError>>()?;
+    Ok(to_library(books))
+}
+
+fn find_opf_files(dir: &Path, found: &mut Vec<PathBuf>) -> Result<(), CalibreImportError> {
+    let io_error = |source| CalibreImportError::Io {
+        path: dir.to_path_buf(),
+        source,
+    };
+    for entry in fs::read_dir(dir).map_err(io_error)? {
+        let path = entry.map_err(io_error)?.path();
+        if path.is_dir() {
+            find_opf_files(&path, found)?;
+        } else if path.file_name().is_some_and(|name| name == "metadata.opf") {
+            found.push(path);
+        }
+    }
+    Ok(())
+}
+
+fn sibling_formats(opf_path: &Path) -> Result<Vec<String>, CalibreImportError> {
+    let Some(dir) = opf_path.parent() else {
+        return Ok(Vec::new());
+    };
+    let io_error = |source| CalibreImportError::Io {
+        path: dir.to_path_buf(),
+        source,
+    };
+    let mut formats = Vec::new();
+    for entry in fs::read_dir(dir).map_err(io_error)? {
+        let path = entry.map_err(io_error)?.path();
+        if let Some(extension) = path.extension().and_then(|extension| extension.to_str()) {
+            formats.push(extension.to_uppercase());
+        }
+    }
+    formats.sort();
+    Ok(formats)
+}
+
+// Reads the title, authors and ISBN of an OPF package. Creators with a role
+// other than author, such as editors or translators, are left out.
+fn parse_opf(xml: &str) -> Result<CalibreBook, String> {
+    let document = roxmltree::Document::parse(xml).map_err(|e| e.to_string())?;
+    let metadata = document
+        .descendants()
+        .find(|node| node.tag_name().name() == "metadata")
+        .ok_or("no metadata element")?;
+    let dc = |node: &roxmltree::Node, name: &str| {
+        node.is_element()
+            && node.tag_name().namespace() == Some(DC_NAMESPACE)
+            && node.tag_name().name() == name
+    };
+    let text = |node: roxmltree::Node| node.text().unwrap_or_default().trim().to_string();
+
+    let mut book = CalibreBook::default();
+    for node in metadata.children() {
+        if dc(&node, "title") && book.title.is_empty() {
+            book.title = text(node);
+        } else if dc(&node, "creator") {
+            let role = node.attribute((OPF_NAMESPACE, "role")).unwrap_or("aut");
+            if role == "aut" {
+                let sort = node
+                    .attribute((OPF_NAMESPACE, "file-as"))
+                    .map(str::to_string);
+                book.authors.push((text(node), sort));
+            }
+        } else if dc(&node, "identifier") && book.isbn.is_empty() {
+            let value = text(node);
+            let scheme = node
+                .attribute((OPF_NAMESPACE, "scheme"))
+                .unwrap_or_default();
+            if scheme.eq_ignore_ascii_case("isbn") {
+                book.isbn = value;
+            } else if let Some(isbn) = value.strip_prefix("urn:isbn:") {
+                book.isbn = isbn.to_string();
+            }
+        }
+    }
+    Ok(book)
+}
+
+// Books with a file in an eBook format are owned eBooks, from Kindle when a
+// file is in one of Kindle's own formats. Other files, like the cover, do
+// not count.
+fn to_library(books: impl IntoIterator<Item = CalibreBook>) -> CalibreLibrary {
+    let mut library = CalibreLibrary::default();
+    for book in books {
+        let formats: Vec<String> = book
+            .formats
+            .iter()
+            .map(|format| format.to_uppercase())
+            .collect();
+        let has = |known: &[&str]| formats.iter().any(|format| known.contains(&&**format));
+        let is_ebook = has(EBOOK_FORMATS);
+        let store = if has(KINDLE_FORMATS) {
+            BookStore::Kindle
+        } else {
+            BookStore::Unknown
+        };
+
+        let mut author_names = Vec::with_capacity(book.authors.len());
+        for (name, sort) in book.authors {
+            if let Some(yomi) = sort.as_deref().and_then(kana_yomi) {
+                library.author_yomi.entry(name.clone()).or_insert(yomi);
+            }
+            author_names.push(name);
+        }
+
+        library.books.push(ImportBookEntryDto {
+            title: book.title,
+            author_names,
+            isbn: normalize_isbn(&book.isbn),
+            read: false,
+            owned: is_ebook,
+            priority: 0,
+            format: if is_ebook {
+                BookFormat::EBook
+            } else {
+                BookFormat::Unknown
+            },
+            store,
+        });
+    }
+    library
+}
+
+// Turns an author sort written in kana, like "ムラカミ, ハルキ", into a yomi
+// ("むらかみ はるき"). Sorts in any other script, which is what Calibre
+// makes up by default, give none.
+fn kana_yomi(sort: &str) -> Option<String> {
+    let yomi = sort
+        .nfkc()
+        .map(|c| match c {
+            'ァ'..='ヶ' => char::from_u32(c as u32 - 0x60).unwrap_or(c),
+            ',' | '、' => ' ',
+            _ => c,
+        })
+        .collect::<String>()
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ");
+    let is_kana = yomi
+        .chars()
+        .all(|c| matches!(c, 'ぁ'..='ゖ' | 'ー' | '・' | ' '));
+    (is_kana && !yomi.is_empty()).then_some(yomi)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use bookshelf_api::common::types::{BookFormat, BookStore};
+
+    use sqlx::{
+        Connection,
+        sqlite::{SqliteConnectOptions, SqliteConnection},
+    };
+
+    use super::{kana_yomi, parse_opf, read_metadata_db, read_opf_folder};
+
+    const OPF: &str = r#"<?xml version='1.0' encoding='utf-8'?>
+<package xmlns="http://www.idpf.org/2007/opf" unique-identifier="uuid_id" version="2.0">
+  <metadata xmlns:dc="http://purl.org/dc/elements/1.1/" xmlns:opf="http://www.idpf.org/2007/opf">
+    <dc:identifier opf:scheme="calibre" id="calibre_id">12</dc:identifier>
+    <dc:identifier opf:scheme="ISBN">4-06-275857-X</dc:identifier>
+    <dc:title>ノルウェイの森</dc:title>
+    <dc:creator opf:file-as="ムラカミ, ハルキ" opf:role="aut">村上春樹</dc:creator>
+    <dc:creator opf:file-as="Doe, Jane" opf:role="trl">Jane Doe</dc:creator>
+  </metadata>
+</package>"#;
+
+    #[test]
+    fn parse_opf_reads_title_authors_and_isbn() {
+        let book = parse_opf(OPF).unwrap();
+
+        assert_eq!(book.title, "ノルウェイの森");
+        assert_eq!(
+            book.authors,
+            vec![("村上春樹".to_string(), Some("ムラカミ, ハルキ".to_string()))]
+        );
+        assert_eq!(book.isbn, "4-06-275857-X");
+    }
+
+    #[test]
+    fn kana_yomi_reads_only_kana_sorts() {
+        assert_eq!(
+            kana_yomi("ムラカミ, ハルキ").as_deref(),
+            Some("むらかみ はるき")
+        );
+        assert_eq!(
+            kana_yomi("なつめ そうせき").as_deref(),
+            Some("なつめ そうせき")
+        );
+        assert_eq!(kana_yomi("村上, 春樹"), None);
+        assert_eq!(kana_yomi("Herbert, Frank"), None);
+    }
+
+    #[test]
+    fn read_opf_folder_reads_books_with_their_formats() {
+        // Given: A library with an EPUB and AZW3 book and a book without files
+        let library_dir = std::env::temp_dir().join(format!("calibre-{}", uuid::Uuid::new_v4()));
+        let norwegian_wood = library_dir.join("村上春樹/ノルウェイの森 (12)");
+        let no_files = library_dir.join("Frank Herbert/Dune (13)");
+        fs::create_dir_all(&norwegian_wood).unwrap();
+        fs::create_dir_all(&no_files).unwrap();
+        fs::write(norwegian_wood.join("metadata.opf"), OPF).unwrap();
+        fs::write(norwegian_wood.join("book.epub"), "").unwrap();
+        fs::write(norwegian_wood.join("book.azw3"), "").unwrap();
+        fs::write(
+            no_files.join("metadata.opf"),
+            OPF.replace("ノルウェイの森", "Dune")
+                .replace("4-06-275857-X", ""),
+        )
+        .unwrap();
+
+        // When: Reading the folder
+        let library = read_opf_folder(&library_dir);
+        fs::remove_dir_all(&library_dir).unwrap();
+        let library = library.unwrap();
+
+        // Then: Books with files are owned eBooks, and kana sorts give yomi
+        assert_eq!(library.books.len(), 2);
+        let dune = &library.books[0];
+        assert_eq!(dune.title, "Dune");
+        assert!(!dune.owned);
+        assert_eq!(dune.format, BookFormat::Unknown);
+        let norwegian_wood = &library.books[1];
+        assert_eq!(norwegian_wood.isbn, "9784062758574");
+        assert_eq!(norwegian_wood.author_names, vec!["村上春樹"]);
+        assert!(norwegian_wood.owned);
+        assert_eq!(norwegian_wood.format, BookFormat::EBook);
+        assert_eq!(norwegian_wood.store, BookStore::Kindle);
+        assert_eq!(
+            library.author_yomi.get("村上春樹").map(String::as_str),
+            Some("むらかみ はるき")
+        );
+    }
+
+    #[tokio::test]
+    async fn read_metadata_db_reads_books_authors_isbns_and_formats() {
+        // Given: A metadata.db with the tables Calibre keeps books in
+        let path = std::env::temp_dir().join(format!("calibre-{}.db", uuid::Uuid::new_v4()));
+        let options = SqliteConnectOptions::new()
+            .filename(&path)
+            .create_if_missing(true);
+        let mut conn = SqliteConnection::connect_with(&options).await.unwrap();
+        sqlx::raw_sql(
+            "CREATE TABLE books (id INTEGER PRIMARY KEY, title TEXT NOT NULL);
+             CREATE TABLE authors (id INTEGER PRIMARY KEY, name TEXT NOT NULL, sort TEXT);
+             CREATE TABLE books_authors_link (id INTEGER PRIMARY KEY, book INTEGER, author INTEGER);
+             CREATE TABLE identifiers (id INTEGER PRIMARY KEY, book INTEGER, type TEXT, val TEXT);
+             CREATE TABLE data (id INTEGER PRIMARY KEY, book INTEGER, format TEXT, name TEXT);
+             INSERT INTO books VALUES (1, 'Dune'), (2, 'ノルウェイの森');
+             INSERT INTO authors VALUES
+               (1, 'Frank Herbert', 'Herbert, Frank'),
+               (2, '村上春樹', 'むらかみ, はるき'),
+               (3, 'Brian Herbert', NULL);
+             INSERT INTO books_authors_link VALUES (1, 1, 1), (2, 1, 3), (3, 2, 2);
+             INSERT INTO identifiers VALUES (1, 1, 'isbn', '0441013597'), (2, 2, 'amazon', 'B0');
+             INSERT INTO data VALUES (1, 1, 'PDF', 'Dune');",
+        )
+        .execute(&mut conn)
+        .await
+        .unwrap();
+        conn.close().await.unwrap();
+
+        // When: Reading it
+        let library = read_metadata_db(&path).await;
+        fs::remove_file(&path).unwrap();
+        let library = library.unwrap();
+
+        // Then: Books keep Calibre's order and their authors' order
+        assert_eq!(library.books.len(), 2);
+        let dune = &library.books[0];
+        assert_eq!(dune.author_names, vec!["Frank Herbert", "Brian Herbert"]);
+        assert_eq!(dune.isbn, "9780441013593");
+        assert_eq!(dune.format, BookFormat::EBook);
+        assert_eq!(dune.store, BookStore::Unknown);
+        let norwegian_wood = &library.books[1];
+        assert_eq!(norwegian_wood.isbn, "");
+        assert_eq!(norwegian_wood.format, BookFormat::Unknown);
+        assert_eq!(library.author_yomi.len(), 1);
+        assert_eq!(library.author_yomi["村上春樹"], "むらかみ はるき");
+    }
+}
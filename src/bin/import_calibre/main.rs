@@ -0,0 +1,114 @@
+// Imports a Calibre library for one user, in a single import_books event set.
+//
+// Usage: import_calibre <user id> <metadata.db or library folder> [--dry-run]
+//        [--partial] [--mode CREATE_ONLY|UPSERT_BY_ISBN|UPSERT_BY_TITLE_AND_AUTHORS|SKIP_EXISTING]
+//
+// Given a file, it reads Calibre's metadata.db; given a folder, the
+// metadata.opf files under it. The user must be registered already. With
+// --partial, books that cannot be imported are reported and the others are
+// imported anyway. Build it with the calibre feature:
+// cargo run --features calibre --bin import_calibre -- ...
+mod calibre;
+
+use std::path::PathBuf;
+
+use anyhow::{Context as _, bail};
+use bookshelf_api::{
+    infrastructure::{
+        author_repository::PgAuthorRepository, book_repository::PgBookRepository,
+        transaction::PgTransactionManager,
+    },
+    use_case::{
+        dto::book::{ImportModeDto, ImportOptionsDto},
+        interactor::book::ImportBooksInteractor,
+        traits::book::ImportBooksUseCase,
+    },
+};
+use calibre::{read_metadata_db, read_opf_folder};
+use sqlx::postgres::PgPoolOptions;
+
+#[tokio::main]
+async fn main() -> Result<(), anyhow::Error> {
+    dotenvy::dotenv().ok();
+
+    let mut args = std::env::args().skip(1);
+    let (Some(user_id), Some(path)) = (args.next(), args.next().map(PathBuf::from)) else {
+        bail!(
+            "usage: import_calibre <user id> <metadata.db or library folder> [--dry-run] [--partial] [--mode MODE]"
+        );
+    };
+    let mut options = ImportOptionsDto::default();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--dry-run" => options.dry_run = true,
+            "--partial" => options.partial = true,
+            "--mode" => options.mode = parse_mode(&args.next().context("--mode needs a value")?)?,
+            other => bail!("unknown argument: {other}"),
+        }
+    }
+
+    let library = if path.is_dir() {
+        read_opf_folder(&path)?
+    } else {
+        read_metadata_db(&path).await?
+    };
+    if library.books.is_empty() {
+        bail!("no books found in {}", path.display());
+    }
+
+    let db_url =
+        std::env::var("DATABASE_URL").context("environment variable DATABASE_URL is required")?;
+    let pool = PgPoolOptions::new()
+        .max_connections(1)
+        .connect(&db_url)
+        .await?;
+    // A migration imports the whole library at once, however large.
+    let import_books_use_case = ImportBooksInteractor::new(
+        PgBookRepository::new(pool.clone()),
+        PgAuthorRepository::new(pool.clone()),
+        PgTransactionManager::new(pool),
+    )
+    .with_max_books(usize::MAX);
+
+    let titles: Vec<String> = library
+        .books
+        .iter()
+        .map(|book| book.title.clone())
+        .collect();
+    let imported = import_books_use_case
+        .import_with_author_yomi(&user_id, library.books, library.author_yomi, options)
+        .await?;
+    for error in &imported.errors {
+        println!(
+            "not imported: \"{}\": {}",
+            titles[error.index], error.message
+        );
+    }
+    println!(
+        "created {}, updated {}, skipped {} books",
+        imported.created.len(),
+        imported.updated.len(),
+        imported.skipped.len()
+    );
+    println!(
+        "new authors: {}, existing authors: {}",
+        imported.new_author_names.len(),
+        imported.reused_author_names.len()
+    );
+    match imported.event_set_id {
+        Some(event_set_id) => println!("event set: {event_set_id}"),
+        None if options.dry_run => println!("dry run: nothing was kept"),
+        None => println!("nothing was imported"),
+    }
+    Ok(())
+}
+
+fn parse_mode(value: &str) -> Result<ImportModeDto, anyhow::Error> {
+    match value {
+        "CREATE_ONLY" => Ok(ImportModeDto::CreateOnly),
+        "UPSERT_BY_ISBN" => Ok(ImportModeDto::UpsertByIsbn),
+        "UPSERT_BY_TITLE_AND_AUTHORS" => Ok(ImportModeDto::UpsertByTitleAndAuthors),
+        "SKIP_EXISTING" => Ok(ImportModeDto::SkipExisting),
+        other => bail!("unknown import mode: {other}"),
+    }
+}
@@ -1,3 +1,5 @@
+pub mod activity;
 pub mod http;
+pub mod sort;
 pub mod time;
 pub mod types;
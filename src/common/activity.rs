@@ -0,0 +1,19 @@
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum ActivityGranularity {
+    #[default]
+    Day,
+    // Weeks start on Monday.
+    Week,
+    Month,
+}
+
+impl ActivityGranularity {
+    // The field name accepted by PostgreSQL's `date_trunc`.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            ActivityGranularity::Day => "day",
+            ActivityGranularity::Week => "week",
+            ActivityGranularity::Month => "month",
+        }
+    }
+}
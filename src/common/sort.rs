@@ -0,0 +1,83 @@
+use time::OffsetDateTime;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum SortDirection {
+    #[default]
+    Asc,
+    Desc,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum BookSortKey {
+    Title,
+    Priority,
+    #[default]
+    CreatedAt,
+    UpdatedAt,
+    // The smallest reading among the book's authors; books without authors
+    // have an empty reading.
+    AuthorYomi,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct BookOrder {
+    pub key: BookSortKey,
+    pub direction: SortDirection,
+}
+
+// The value of a book's sort key, as stored in a cursor.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BookSortValue {
+    Title(String),
+    Priority(i32),
+    CreatedAt(OffsetDateTime),
+    UpdatedAt(OffsetDateTime),
+    AuthorYomi(String),
+}
+
+impl BookSortValue {
+    pub fn key(&self) -> BookSortKey {
+        match self {
+            BookSortValue::Title(_) => BookSortKey::Title,
+            BookSortValue::Priority(_) => BookSortKey::Priority,
+            BookSortValue::CreatedAt(_) => BookSortKey::CreatedAt,
+            BookSortValue::UpdatedAt(_) => BookSortKey::UpdatedAt,
+            BookSortValue::AuthorYomi(_) => BookSortKey::AuthorYomi,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum AuthorSortKey {
+    #[default]
+    Name,
+    // yomi in hiragana order, or name when yomi is empty.
+    Yomi,
+    CreatedAt,
+    UpdatedAt,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct AuthorOrder {
+    pub key: AuthorSortKey,
+    pub direction: SortDirection,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AuthorSortValue {
+    Name(String),
+    Yomi(String),
+    CreatedAt(OffsetDateTime),
+    UpdatedAt(OffsetDateTime),
+}
+
+impl AuthorSortValue {
+    pub fn key(&self) -> AuthorSortKey {
+        match self {
+            AuthorSortValue::Name(_) => AuthorSortKey::Name,
+            AuthorSortValue::Yomi(_) => AuthorSortKey::Yomi,
+            AuthorSortValue::CreatedAt(_) => AuthorSortKey::CreatedAt,
+            AuthorSortValue::UpdatedAt(_) => AuthorSortKey::UpdatedAt,
+        }
+    }
+}
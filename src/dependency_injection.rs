@@ -1,11 +1,15 @@
+use std::sync::Arc;
+
 use async_graphql::{EmptySubscription, Schema};
 use sqlx::{Pool, Postgres};
+use tokio::sync::mpsc;
 
 use crate::{
     infrastructure::{
         author_event_repository::PgAuthorEventRepository, author_repository::PgAuthorRepository,
         book_event_repository::PgBookEventRepository, book_repository::PgBookRepository,
-        event_set_repository::PgEventSetRepository, transaction::PgTransactionManager,
+        event_set_repository::PgEventSetRepository, import_job_repository::PgImportJobRepository,
+        transaction::PgTransactionManager, trash_repository::PgTrashRepository,
         user_repository::PgUserRepository,
     },
     presentation::graphql::{mutation::Mutation, query::Query, schema::build_schema},
@@ -15,9 +19,15 @@ use crate::{
             UpdateAuthorInteractor,
         },
         book::{
-            CreateBookInteractor, DeleteBookInteractor, ImportBooksInteractor, UpdateBookInteractor,
+            BulkUpdateBooksInteractor, CreateBookInteractor, DeleteBookInteractor,
+            ImportBooksInteractor, UpdateBookInteractor,
+        },
+        change::ApplyChangesInteractor,
+        event::{
+            PurgeTrashInteractor, RestoreAuthorInteractor, RestoreBookInteractor,
+            RevertEventSetInteractor,
         },
-        event::{RestoreAuthorInteractor, RestoreBookInteractor},
+        import_job::{ImportJobInteractor, ImportJobWorker},
         mutation::MutationInteractor,
         query::QueryInteractor,
         user::RegisterUserInteractor,
@@ -31,6 +41,7 @@ pub type QI = QueryInteractor<
     PgBookEventRepository,
     PgAuthorEventRepository,
     PgEventSetRepository,
+    PgImportJobRepository,
 >;
 
 pub type MI = MutationInteractor<
@@ -47,20 +58,49 @@ pub type MI = MutationInteractor<
         PgAuthorEventRepository,
         PgTransactionManager,
     >,
-    RestoreBookInteractor<PgBookRepository, PgBookEventRepository, PgTransactionManager>,
+    RestoreBookInteractor<
+        PgBookRepository,
+        PgAuthorRepository,
+        PgBookEventRepository,
+        PgAuthorEventRepository,
+        PgTransactionManager,
+    >,
     RestoreAuthorInteractor<PgAuthorRepository, PgAuthorEventRepository, PgTransactionManager>,
-    ImportBooksInteractor<PgBookRepository, PgAuthorRepository, PgTransactionManager>,
+    RevertEventSetInteractor<
+        PgBookRepository,
+        PgAuthorRepository,
+        PgBookEventRepository,
+        PgAuthorEventRepository,
+        PgEventSetRepository,
+        PgTransactionManager,
+    >,
+    PurgeTrashInteractor<PgTrashRepository, PgTransactionManager>,
+    IBI,
+    BulkUpdateBooksInteractor<PgBookRepository, PgTransactionManager>,
+    ApplyChangesInteractor<PgBookRepository, PgAuthorRepository, PgTransactionManager>,
+    ImportJobInteractor<PgImportJobRepository>,
 >;
 
-pub fn dependency_injection(
-    pool: Pool<Postgres>,
-) -> (QI, Schema<Query<QI>, Mutation<MI>, EmptySubscription>) {
+pub type AppSchema = Schema<Query<QI>, Mutation<MI>, EmptySubscription>;
+
+pub type IBI = ImportBooksInteractor<PgBookRepository, PgAuthorRepository, PgTransactionManager>;
+
+pub type IJW = ImportJobWorker<
+    PgImportJobRepository,
+    PgBookRepository,
+    PgAuthorRepository,
+    PgTransactionManager,
+>;
+
+pub fn dependency_injection(pool: Pool<Postgres>) -> (QI, AppSchema, Arc<IBI>, IJW) {
     let user_repository = PgUserRepository::new(pool.clone());
     let book_repository = PgBookRepository::new(pool.clone());
     let author_repository = PgAuthorRepository::new(pool.clone());
     let book_event_repository = PgBookEventRepository::new(pool.clone());
     let author_event_repository = PgAuthorEventRepository::new(pool.clone());
     let event_set_repository = PgEventSetRepository::new(pool.clone());
+    let trash_repository = PgTrashRepository::new();
+    let import_job_repository = PgImportJobRepository::new(pool.clone());
     let transaction_manager = PgTransactionManager::new(pool);
 
     let query_use_case = QueryInteractor {
@@ -69,7 +109,8 @@ pub fn dependency_injection(
         author_repository: author_repository.clone(),
         book_event_repository: book_event_repository.clone(),
         author_event_repository: author_event_repository.clone(),
-        event_set_repository,
+        event_set_repository: event_set_repository.clone(),
+        import_job_repository: import_job_repository.clone(),
     };
     let register_user_use_case = RegisterUserInteractor::new(user_repository);
     let create_book_use_case =
@@ -95,16 +136,50 @@ pub fn dependency_injection(
         author_repository.clone(),
         transaction_manager.clone(),
     );
+    // The file import endpoints import through an interactor of their own.
+    let file_import_books_use_case = Arc::new(ImportBooksInteractor::new(
+        book_repository.clone(),
+        author_repository.clone(),
+        transaction_manager.clone(),
+    ));
+    let bulk_update_books_use_case =
+        BulkUpdateBooksInteractor::new(book_repository.clone(), transaction_manager.clone());
+    let apply_changes_use_case = ApplyChangesInteractor::new(
+        book_repository.clone(),
+        author_repository.clone(),
+        transaction_manager.clone(),
+    );
+    let (import_job_queue, queued_import_jobs) = mpsc::unbounded_channel();
+    let import_job_use_case =
+        ImportJobInteractor::new(import_job_repository.clone(), import_job_queue);
+    let import_job_worker = ImportJobWorker::new(
+        import_job_repository,
+        book_repository.clone(),
+        author_repository.clone(),
+        transaction_manager.clone(),
+        queued_import_jobs,
+    );
     let restore_book_use_case = RestoreBookInteractor::new(
-        book_repository,
-        book_event_repository,
+        book_repository.clone(),
+        author_repository.clone(),
+        book_event_repository.clone(),
+        author_event_repository.clone(),
         transaction_manager.clone(),
     );
     let restore_author_use_case = RestoreAuthorInteractor::new(
+        author_repository.clone(),
+        author_event_repository.clone(),
+        transaction_manager.clone(),
+    );
+    let revert_event_set_use_case = RevertEventSetInteractor::new(
+        book_repository,
         author_repository,
+        book_event_repository,
         author_event_repository,
-        transaction_manager,
+        event_set_repository,
+        transaction_manager.clone(),
     );
+    let purge_trash_use_case = PurgeTrashInteractor::new(trash_repository, transaction_manager);
 
     let mutation_use_case = MutationInteractor::new(
         register_user_use_case,
@@ -117,7 +192,12 @@ pub fn dependency_injection(
         merge_author_use_case,
         restore_book_use_case,
         restore_author_use_case,
+        revert_event_set_use_case,
+        purge_trash_use_case,
         import_books_use_case,
+        bulk_update_books_use_case,
+        apply_changes_use_case,
+        import_job_use_case,
     );
 
     let query = Query::new(query_use_case.clone());
@@ -125,5 +205,10 @@ pub fn dependency_injection(
 
     let schema = build_schema(query, mutation);
 
-    (query_use_case, schema)
+    (
+        query_use_case,
+        schema,
+        file_import_books_use_case,
+        import_job_worker,
+    )
 }
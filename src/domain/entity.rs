@@ -3,4 +3,5 @@ pub mod book;
 pub mod common;
 pub mod event;
 pub mod event_set;
+pub mod import_job;
 pub mod user;
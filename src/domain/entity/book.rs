@@ -1,6 +1,6 @@
 use getset::Getters;
 use regex::Regex;
-use std::sync::LazyLock;
+use std::{collections::HashSet, sync::LazyLock};
 use time::OffsetDateTime;
 use uuid::Uuid;
 use validator::Validate;
@@ -72,6 +72,13 @@ pub struct Isbn {
 
 impl_string_value_object!(Isbn);
 
+impl Isbn {
+    // The ISBN without hyphens, which sources place differently.
+    pub fn digits(&self) -> String {
+        self.value.replace('-', "")
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct ReadFlag {
     value: bool,
@@ -158,6 +165,51 @@ pub struct BookUpdate {
     pub store: BookStore,
 }
 
+// Changes to some of a book's fields; the omitted ones keep their values.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct BookPatch {
+    pub title: Option<BookTitle>,
+    pub author_ids: Option<AuthorIdsPatch>,
+    pub isbn: Option<Isbn>,
+    pub read: Option<ReadFlag>,
+    pub owned: Option<OwnedFlag>,
+    pub priority: Option<Priority>,
+    pub format: Option<BookFormat>,
+    pub store: Option<BookStore>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AuthorIdsPatch {
+    Replace(Vec<AuthorId>),
+    // Removals apply first; added authors go after the remaining ones unless
+    // the book already has them.
+    Edit {
+        add: Vec<AuthorId>,
+        remove: Vec<AuthorId>,
+    },
+}
+
+impl AuthorIdsPatch {
+    pub fn apply(self, current: &[AuthorId]) -> Vec<AuthorId> {
+        match self {
+            AuthorIdsPatch::Replace(author_ids) => author_ids,
+            AuthorIdsPatch::Edit { add, remove } => {
+                let mut author_ids: Vec<AuthorId> = current
+                    .iter()
+                    .filter(|author_id| !remove.contains(author_id))
+                    .cloned()
+                    .collect();
+                for author_id in add {
+                    if !author_ids.contains(&author_id) {
+                        author_ids.push(author_id);
+                    }
+                }
+                author_ids
+            }
+        }
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct DestructureBook {
     pub id: BookId,
@@ -215,6 +267,43 @@ impl Book {
         self.updated_at = normalize_timestamp_for_persistence(updated_at);
     }
 
+    pub fn patch(&mut self, patch: BookPatch, updated_at: OffsetDateTime) {
+        let update = self.update_from_patch(patch);
+        self.update(update, updated_at);
+    }
+
+    // The full update that applying `patch` amounts to.
+    pub fn update_from_patch(&self, patch: BookPatch) -> BookUpdate {
+        BookUpdate {
+            title: patch.title.unwrap_or_else(|| self.title.clone()),
+            author_ids: match patch.author_ids {
+                Some(author_ids) => author_ids.apply(&self.author_ids),
+                None => self.author_ids.clone(),
+            },
+            isbn: patch.isbn.unwrap_or_else(|| self.isbn.clone()),
+            read: patch.read.unwrap_or_else(|| self.read.clone()),
+            owned: patch.owned.unwrap_or_else(|| self.owned.clone()),
+            priority: patch.priority.unwrap_or_else(|| self.priority.clone()),
+            format: patch.format.unwrap_or_else(|| self.format.clone()),
+            store: patch.store.unwrap_or_else(|| self.store.clone()),
+        }
+    }
+
+    // Whether `update` would change any field. Authors are compared as a set,
+    // since their order is not stored.
+    pub fn is_changed_by(&self, update: &BookUpdate) -> bool {
+        let author_ids: HashSet<&AuthorId> = self.author_ids.iter().collect();
+        let updated_author_ids: HashSet<&AuthorId> = update.author_ids.iter().collect();
+        self.title != update.title
+            || author_ids != updated_author_ids
+            || self.isbn != update.isbn
+            || self.read != update.read
+            || self.owned != update.owned
+            || self.priority != update.priority
+            || self.format != update.format
+            || self.store != update.store
+    }
+
     pub fn destructure(self) -> DestructureBook {
         DestructureBook {
             id: self.id,
@@ -239,7 +328,10 @@ mod test {
 
     use crate::common::types::{BookFormat, BookStore};
 
-    use super::{Book, BookId, BookTitle, BookUpdate, Isbn, OwnedFlag, Priority, ReadFlag};
+    use super::{
+        AuthorIdsPatch, Book, BookId, BookPatch, BookTitle, BookUpdate, Isbn, OwnedFlag, Priority,
+        ReadFlag,
+    };
     use crate::domain::entity::author::AuthorId;
 
     #[test]
@@ -293,6 +385,86 @@ mod test {
         assert_eq!(book.created_at(), &created_at);
     }
 
+    #[test]
+    fn patch_keeps_omitted_fields_and_edits_author_ids() {
+        let created_at = OffsetDateTime::from_unix_timestamp(1_700_000_000).expect("valid time");
+        let updated_at = OffsetDateTime::from_unix_timestamp(1_700_000_200).expect("valid time");
+        let kept_author_id = AuthorId::new(Uuid::new_v4());
+        let removed_author_id = AuthorId::new(Uuid::new_v4());
+        let added_author_id = AuthorId::new(Uuid::new_v4());
+
+        let mut book = Book::new(
+            BookId::new(Uuid::new_v4()).expect("valid book id"),
+            BookTitle::new("Original title".to_owned()).expect("valid title"),
+            vec![removed_author_id.clone(), kept_author_id.clone()],
+            Isbn::new("9784062758574".to_owned()).expect("valid isbn"),
+            ReadFlag::new(false),
+            OwnedFlag::new(true),
+            Priority::new(10).expect("valid priority"),
+            BookFormat::Printed,
+            BookStore::Unknown,
+            created_at,
+            created_at,
+        )
+        .expect("valid book");
+
+        let patch = BookPatch {
+            read: Some(ReadFlag::new(true)),
+            author_ids: Some(AuthorIdsPatch::Edit {
+                add: vec![kept_author_id.clone(), added_author_id.clone()],
+                remove: vec![removed_author_id],
+            }),
+            ..Default::default()
+        };
+
+        book.patch(patch, updated_at);
+
+        assert!(book.read().to_bool());
+        assert_eq!(book.author_ids(), &vec![kept_author_id, added_author_id]);
+        assert_eq!(book.title().as_str(), "Original title");
+        assert_eq!(book.isbn().as_str(), "9784062758574");
+        assert!(book.owned().to_bool());
+        assert_eq!(book.priority().to_i32(), 10);
+        assert_eq!(book.format(), &BookFormat::Printed);
+        assert_eq!(book.updated_at(), &updated_at);
+    }
+
+    #[test]
+    fn is_changed_by_ignores_the_order_of_authors() {
+        let first_author_id = AuthorId::new(Uuid::new_v4());
+        let second_author_id = AuthorId::new(Uuid::new_v4());
+        let created_at = OffsetDateTime::from_unix_timestamp(1_700_000_000).expect("valid time");
+        let book = Book::new(
+            BookId::new(Uuid::new_v4()).expect("valid book id"),
+            BookTitle::new("Title".to_owned()).expect("valid title"),
+            vec![first_author_id.clone(), second_author_id.clone()],
+            Isbn::new("".to_owned()).expect("valid isbn"),
+            ReadFlag::new(false),
+            OwnedFlag::new(false),
+            Priority::new(50).expect("valid priority"),
+            BookFormat::Unknown,
+            BookStore::Unknown,
+            created_at,
+            created_at,
+        )
+        .expect("valid book");
+
+        let reordered = book.update_from_patch(BookPatch {
+            author_ids: Some(AuthorIdsPatch::Replace(vec![
+                second_author_id,
+                first_author_id,
+            ])),
+            ..Default::default()
+        });
+        let owned = book.update_from_patch(BookPatch {
+            owned: Some(OwnedFlag::new(true)),
+            ..Default::default()
+        });
+
+        assert!(!book.is_changed_by(&reordered));
+        assert!(book.is_changed_by(&owned));
+    }
+
     #[test]
     fn valid_isbn_with_hyphen() {
         let isbn = Isbn::new("978-4062758574".to_owned());
@@ -311,6 +483,13 @@ mod test {
         assert!(isbn.is_ok());
     }
 
+    #[test]
+    fn isbn_digits_ignore_hyphens() {
+        let hyphenated = Isbn::new("978-4-06-275857-4".to_owned()).unwrap();
+        let plain = Isbn::new("9784062758574".to_owned()).unwrap();
+        assert_eq!(hyphenated.digits(), plain.digits());
+    }
+
     #[test]
     fn isbn_too_short() {
         let isbn = Isbn::new("1".to_owned());
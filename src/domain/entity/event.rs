@@ -54,6 +54,12 @@ pub enum EventSetOperation {
     ImportBooks,
     SnapshotAll,
     MergeAuthor,
+    RevertEventSet,
+    BulkUpdateBooks,
+    DeleteBooks,
+    DeleteAuthors,
+    ApplyChanges,
+    PurgeTrash,
 }
 
 impl EventSetOperation {
@@ -70,6 +76,12 @@ impl EventSetOperation {
             EventSetOperation::ImportBooks => "import_books",
             EventSetOperation::SnapshotAll => "snapshot_all",
             EventSetOperation::MergeAuthor => "merge_author",
+            EventSetOperation::RevertEventSet => "revert_event_set",
+            EventSetOperation::BulkUpdateBooks => "bulk_update_books",
+            EventSetOperation::DeleteBooks => "delete_books",
+            EventSetOperation::DeleteAuthors => "delete_authors",
+            EventSetOperation::ApplyChanges => "apply_changes",
+            EventSetOperation::PurgeTrash => "purge_trash",
         }
     }
 }
@@ -90,6 +102,12 @@ impl TryFrom<&str> for EventSetOperation {
             "import_books" => Ok(EventSetOperation::ImportBooks),
             "snapshot_all" => Ok(EventSetOperation::SnapshotAll),
             "merge_author" => Ok(EventSetOperation::MergeAuthor),
+            "revert_event_set" => Ok(EventSetOperation::RevertEventSet),
+            "bulk_update_books" => Ok(EventSetOperation::BulkUpdateBooks),
+            "delete_books" => Ok(EventSetOperation::DeleteBooks),
+            "delete_authors" => Ok(EventSetOperation::DeleteAuthors),
+            "apply_changes" => Ok(EventSetOperation::ApplyChanges),
+            "purge_trash" => Ok(EventSetOperation::PurgeTrash),
             _ => Err(format!("Unknown event set operation: {}", value)),
         }
     }
@@ -126,7 +144,166 @@ impl TryFrom<&str> for EventOperation {
 
 #[cfg(test)]
 mod tests {
-    use super::{EventId, EventOperation, EventSetOperation};
+    use time::OffsetDateTime;
+    use uuid::Uuid;
+
+    use super::{AuthorEvent, BookEvent, EventId, EventOperation, EventSetOperation, FieldChange};
+    use crate::{
+        common::types::{BookFormat, BookStore},
+        domain::entity::{
+            author::AuthorId,
+            book::{BookId, BookTitle, Isbn, OwnedFlag, Priority, ReadFlag},
+            event_set::EventSetId,
+        },
+    };
+
+    const AUTHOR1: &str = "278935cf-ed83-4346-9b35-b84bbdb630c0";
+    const AUTHOR2: &str = "e324be11-2d3b-4f4e-a1a6-e6bcd0e6b4a1";
+
+    fn book_event(read: bool, priority: i32, author_ids: &[&str]) -> BookEvent {
+        BookEvent {
+            event_id: 1,
+            event_set_id: EventSetId::from(Uuid::nil()),
+            operation: EventOperation::Update,
+            book_id: BookId::new(Uuid::nil()).unwrap(),
+            title: Some(BookTitle::new("title".to_string()).unwrap()),
+            author_ids: author_ids
+                .iter()
+                .map(|id| AuthorId::try_from(*id).unwrap())
+                .collect(),
+            isbn: Some(Isbn::new(String::new()).unwrap()),
+            read: Some(ReadFlag::new(read)),
+            owned: Some(OwnedFlag::new(false)),
+            priority: Some(Priority::new(priority).unwrap()),
+            format: Some(BookFormat::EBook),
+            store: Some(BookStore::Kindle),
+            book_created_at: Some(OffsetDateTime::UNIX_EPOCH),
+            book_updated_at: Some(OffsetDateTime::UNIX_EPOCH),
+            changed_at: OffsetDateTime::UNIX_EPOCH,
+            extra: None,
+        }
+    }
+
+    fn change(field: &'static str, old: Option<&str>, new: Option<&str>) -> FieldChange {
+        FieldChange {
+            field,
+            old_value: old.map(str::to_owned),
+            new_value: new.map(str::to_owned),
+        }
+    }
+
+    #[test]
+    fn book_changes_list_differing_fields_and_author_ids() {
+        let previous = book_event(false, 30, &[AUTHOR1]);
+        let mut current = book_event(true, 80, &[AUTHOR2]);
+        // Timestamps are not reported.
+        current.book_updated_at = Some(OffsetDateTime::now_utc());
+
+        assert_eq!(
+            current.changes_since(Some(&previous)),
+            vec![
+                change("read", Some("false"), Some("true")),
+                change("priority", Some("30"), Some("80")),
+                change("authorIds", Some(AUTHOR1), None),
+                change("authorIds", None, Some(AUTHOR2)),
+            ]
+        );
+    }
+
+    #[test]
+    fn book_changes_of_first_and_delete_events_cover_every_field() {
+        let created = book_event(false, 50, &[AUTHOR1]);
+        let deleted = BookEvent {
+            operation: EventOperation::Delete,
+            title: None,
+            author_ids: vec![],
+            isbn: None,
+            read: None,
+            owned: None,
+            priority: None,
+            format: None,
+            store: None,
+            ..created.clone()
+        };
+
+        let creation = created.changes_since(None);
+        assert_eq!(creation.len(), 8);
+        assert!(creation.iter().all(|c| c.old_value.is_none()));
+        assert_eq!(creation[0], change("title", None, Some("title")));
+        assert_eq!(creation[5], change("format", None, Some("eBook")));
+        let deletion = deleted.changes_since(Some(&created));
+        assert_eq!(deletion.len(), 8);
+        assert!(deletion.iter().all(|c| c.new_value.is_none()));
+        // Empty ISBNs are reported too, as an empty string.
+        assert_eq!(deletion[1], change("isbn", Some(""), None));
+    }
+
+    #[test]
+    fn author_merge_markers_have_no_changes() {
+        let author_id = AuthorId::try_from(AUTHOR1).unwrap();
+        let created = AuthorEvent {
+            event_id: 1,
+            event_set_id: EventSetId::from(Uuid::nil()),
+            operation: EventOperation::Create,
+            author_id: author_id.clone(),
+            name: Some("name".to_string()),
+            yomi: Some(String::new()),
+            author_created_at: None,
+            author_updated_at: None,
+            changed_at: OffsetDateTime::UNIX_EPOCH,
+            extra: None,
+        };
+        let renamed = AuthorEvent {
+            name: Some("renamed".to_string()),
+            ..created.clone()
+        };
+        let marker = AuthorEvent {
+            operation: EventOperation::MergeAsDestination,
+            name: None,
+            yomi: None,
+            ..created.clone()
+        };
+
+        assert_eq!(
+            renamed.changes_since(Some(&created)),
+            vec![change("name", Some("name"), Some("renamed"))]
+        );
+        assert!(!marker.has_state());
+        assert!(marker.changes_since(Some(&renamed)).is_empty());
+    }
+
+    #[test]
+    fn author_merge_destination_is_read_from_merge_deletions_only() {
+        let destination = AuthorId::try_from(AUTHOR2).unwrap();
+        let deleted = AuthorEvent {
+            event_id: 1,
+            event_set_id: EventSetId::from(Uuid::nil()),
+            operation: EventOperation::Delete,
+            author_id: AuthorId::try_from(AUTHOR1).unwrap(),
+            name: None,
+            yomi: None,
+            author_created_at: None,
+            author_updated_at: None,
+            changed_at: OffsetDateTime::UNIX_EPOCH,
+            extra: None,
+        };
+        let merged = AuthorEvent {
+            extra: Some(serde_json::json!({
+                "type": "merge",
+                "version": 1,
+                "destination_author_id": AUTHOR2,
+            })),
+            ..deleted.clone()
+        };
+        let marker = AuthorEvent {
+            operation: EventOperation::MergeAsDestination,
+            ..merged.clone()
+        };
+
+        assert_eq!(merged.merge_destination(), Some(destination));
+        assert_eq!(deleted.merge_destination(), None);
+        assert_eq!(marker.merge_destination(), None);
+    }
 
     #[test]
     fn event_id_round_trips_database_value() {
@@ -171,6 +348,18 @@ mod tests {
         assert_eq!(EventSetOperation::ImportBooks.as_str(), "import_books");
         assert_eq!(EventSetOperation::SnapshotAll.as_str(), "snapshot_all");
         assert_eq!(EventSetOperation::MergeAuthor.as_str(), "merge_author");
+        assert_eq!(
+            EventSetOperation::RevertEventSet.as_str(),
+            "revert_event_set"
+        );
+        assert_eq!(
+            EventSetOperation::BulkUpdateBooks.as_str(),
+            "bulk_update_books"
+        );
+        assert_eq!(EventSetOperation::DeleteBooks.as_str(), "delete_books");
+        assert_eq!(EventSetOperation::DeleteAuthors.as_str(), "delete_authors");
+        assert_eq!(EventSetOperation::ApplyChanges.as_str(), "apply_changes");
+        assert_eq!(EventSetOperation::PurgeTrash.as_str(), "purge_trash");
     }
 
     #[test]
@@ -187,6 +376,12 @@ mod tests {
             EventSetOperation::ImportBooks,
             EventSetOperation::SnapshotAll,
             EventSetOperation::MergeAuthor,
+            EventSetOperation::RevertEventSet,
+            EventSetOperation::BulkUpdateBooks,
+            EventSetOperation::DeleteBooks,
+            EventSetOperation::DeleteAuthors,
+            EventSetOperation::ApplyChanges,
+            EventSetOperation::PurgeTrash,
         ];
         for variant in &variants {
             let s = variant.as_str();
@@ -239,6 +434,139 @@ pub struct AuthorEvent {
     pub extra: Option<Value>,
 }
 
+// One field whose value differs between an event and the previous event of
+// the same entity. A value is None when the entity did not exist; each added
+// or removed author id is a change of its own.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FieldChange {
+    pub field: &'static str,
+    pub old_value: Option<String>,
+    pub new_value: Option<String>,
+}
+
+impl FieldChange {
+    // The same change applied backwards.
+    pub fn reversed(self) -> Self {
+        Self {
+            field: self.field,
+            old_value: self.new_value,
+            new_value: self.old_value,
+        }
+    }
+}
+
+fn push_change(
+    changes: &mut Vec<FieldChange>,
+    field: &'static str,
+    old_value: Option<String>,
+    new_value: Option<String>,
+) {
+    if old_value != new_value {
+        changes.push(FieldChange {
+            field,
+            old_value,
+            new_value,
+        });
+    }
+}
+
+type BookFieldValue = fn(&BookEvent) -> Option<String>;
+
+impl BookEvent {
+    // Whether the book exists after this event. Deletions, and restores of a
+    // deletion, leave the state empty.
+    pub fn is_present(&self) -> bool {
+        self.title.is_some()
+    }
+
+    // Differences from `previous`, the preceding event of the same book, or
+    // from nothing for the first one. Timestamps are left out.
+    pub fn changes_since(&self, previous: Option<&BookEvent>) -> Vec<FieldChange> {
+        let old = |f: BookFieldValue| previous.and_then(f);
+        let mut changes = Vec::new();
+        let fields: [(&'static str, BookFieldValue); 7] = [
+            ("title", |e| e.title.as_ref().map(|t| t.as_str().to_owned())),
+            ("isbn", |e| e.isbn.as_ref().map(|i| i.as_str().to_owned())),
+            ("read", |e| e.read.as_ref().map(|r| r.to_bool().to_string())),
+            ("owned", |e| {
+                e.owned.as_ref().map(|o| o.to_bool().to_string())
+            }),
+            ("priority", |e| {
+                e.priority.as_ref().map(|p| p.to_i32().to_string())
+            }),
+            ("format", |e| e.format.as_ref().map(ToString::to_string)),
+            ("store", |e| e.store.as_ref().map(ToString::to_string)),
+        ];
+        for (field, value) in fields {
+            push_change(&mut changes, field, old(value), value(self));
+        }
+
+        let old_author_ids: &[AuthorId] = previous.map_or(&[], |p| &p.author_ids);
+        for removed in old_author_ids
+            .iter()
+            .filter(|id| !self.author_ids.contains(id))
+        {
+            push_change(&mut changes, "authorIds", Some(removed.to_string()), None);
+        }
+        for added in self
+            .author_ids
+            .iter()
+            .filter(|id| !old_author_ids.contains(id))
+        {
+            push_change(&mut changes, "authorIds", None, Some(added.to_string()));
+        }
+
+        changes
+    }
+}
+
+impl AuthorEvent {
+    // Merge markers only point at the merged author and hold no state.
+    pub fn has_state(&self) -> bool {
+        self.operation != EventOperation::MergeAsDestination
+    }
+
+    // Whether the author exists after this stateful event.
+    pub fn is_present(&self) -> bool {
+        self.name.is_some()
+    }
+
+    // The author this one was merged into, when the event is the deletion
+    // recorded by a merge.
+    pub fn merge_destination(&self) -> Option<AuthorId> {
+        if self.operation != EventOperation::Delete {
+            return None;
+        }
+        let extra = self.extra.as_ref()?;
+        if extra.get("type")?.as_str()? != "merge" {
+            return None;
+        }
+        AuthorId::try_from(extra.get("destination_author_id")?.as_str()?).ok()
+    }
+
+    // Differences from `previous`, the preceding stateful event of the same
+    // author, or from nothing for the first one. Timestamps are left out.
+    pub fn changes_since(&self, previous: Option<&AuthorEvent>) -> Vec<FieldChange> {
+        let mut changes = Vec::new();
+        if !self.has_state() {
+            return changes;
+        }
+        push_change(
+            &mut changes,
+            "name",
+            previous.and_then(|p| p.name.clone()),
+            self.name.clone(),
+        );
+        push_change(
+            &mut changes,
+            "yomi",
+            previous.and_then(|p| p.yomi.clone()),
+            self.yomi.clone(),
+        );
+        changes
+    }
+}
+
 #[derive(Debug, Clone, PartialEq)]
 pub struct NewAuthorEvent {
     pub operation: EventOperation,
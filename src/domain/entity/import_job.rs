@@ -0,0 +1,304 @@
+use time::OffsetDateTime;
+use uuid::Uuid;
+
+use crate::{
+    common::types::{BookFormat, BookStore},
+    domain::entity::{event_set::EventSetId, user::UserId},
+};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ImportJobId(Uuid);
+
+impl ImportJobId {
+    pub fn new() -> Self {
+        Self(Uuid::new_v4())
+    }
+
+    pub fn to_uuid(&self) -> Uuid {
+        self.0
+    }
+}
+
+impl Default for ImportJobId {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl std::fmt::Display for ImportJobId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0.hyphenated())
+    }
+}
+
+impl TryFrom<&str> for ImportJobId {
+    type Error = String;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        Uuid::parse_str(value)
+            .map(ImportJobId)
+            .map_err(|e| e.to_string())
+    }
+}
+
+impl From<Uuid> for ImportJobId {
+    fn from(uuid: Uuid) -> Self {
+        ImportJobId(uuid)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImportJobStatus {
+    Pending,
+    Running,
+    Succeeded,
+    Failed,
+    Cancelled,
+}
+
+impl ImportJobStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ImportJobStatus::Pending => "pending",
+            ImportJobStatus::Running => "running",
+            ImportJobStatus::Succeeded => "succeeded",
+            ImportJobStatus::Failed => "failed",
+            ImportJobStatus::Cancelled => "cancelled",
+        }
+    }
+
+    // Whether the worker still has to run the job.
+    pub fn is_unfinished(&self) -> bool {
+        matches!(self, ImportJobStatus::Pending | ImportJobStatus::Running)
+    }
+}
+
+impl TryFrom<&str> for ImportJobStatus {
+    type Error = String;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        match value {
+            "pending" => Ok(ImportJobStatus::Pending),
+            "running" => Ok(ImportJobStatus::Running),
+            "succeeded" => Ok(ImportJobStatus::Succeeded),
+            "failed" => Ok(ImportJobStatus::Failed),
+            "cancelled" => Ok(ImportJobStatus::Cancelled),
+            _ => Err(format!("Unknown import job status: {}", value)),
+        }
+    }
+}
+
+// How a job treats entries that match a book the user already has.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImportMode {
+    CreateOnly,
+    UpsertByIsbn,
+    UpsertByTitleAndAuthors,
+    SkipExisting,
+}
+
+impl ImportMode {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ImportMode::CreateOnly => "create_only",
+            ImportMode::UpsertByIsbn => "upsert_by_isbn",
+            ImportMode::UpsertByTitleAndAuthors => "upsert_by_title_and_authors",
+            ImportMode::SkipExisting => "skip_existing",
+        }
+    }
+}
+
+impl TryFrom<&str> for ImportMode {
+    type Error = String;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        match value {
+            "create_only" => Ok(ImportMode::CreateOnly),
+            "upsert_by_isbn" => Ok(ImportMode::UpsertByIsbn),
+            "upsert_by_title_and_authors" => Ok(ImportMode::UpsertByTitleAndAuthors),
+            "skip_existing" => Ok(ImportMode::SkipExisting),
+            _ => Err(format!("Unknown import mode: {}", value)),
+        }
+    }
+}
+
+// A book to import, as it was submitted. A job validates its entries when
+// the chunk holding them runs.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ImportEntry {
+    pub title: String,
+    pub author_names: Vec<String>,
+    pub isbn: String,
+    pub read: bool,
+    pub owned: bool,
+    pub priority: i32,
+    pub format: BookFormat,
+    pub store: BookStore,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImportField {
+    Title,
+    AuthorNames,
+    Isbn,
+    Priority,
+}
+
+impl ImportField {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ImportField::Title => "title",
+            ImportField::AuthorNames => "author_names",
+            ImportField::Isbn => "isbn",
+            ImportField::Priority => "priority",
+        }
+    }
+}
+
+impl TryFrom<&str> for ImportField {
+    type Error = String;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        match value {
+            "title" => Ok(ImportField::Title),
+            "author_names" => Ok(ImportField::AuthorNames),
+            "isbn" => Ok(ImportField::Isbn),
+            "priority" => Ok(ImportField::Priority),
+            _ => Err(format!("Unknown import field: {}", value)),
+        }
+    }
+}
+
+// An entry a job could not import, by its position among the job's entries.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ImportJobFailure {
+    pub index: usize,
+    pub field: ImportField,
+    pub message: String,
+}
+
+// An import run in the background, chunk by chunk. Each chunk that writes
+// anything is recorded in an event set of its own.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ImportJob {
+    pub id: ImportJobId,
+    pub user_id: UserId,
+    pub status: ImportJobStatus,
+    pub mode: ImportMode,
+    pub partial: bool,
+    pub total_count: usize,
+    // Entries handled so far, failed ones included. The job resumes after them.
+    pub processed_count: usize,
+    pub event_set_ids: Vec<EventSetId>,
+    pub failures: Vec<ImportJobFailure>,
+    // Why the job failed, for failed jobs.
+    pub error: Option<String>,
+    // The worker that claimed the job to run it. Only that worker saves its
+    // progress.
+    pub claimed_by: Option<Uuid>,
+    pub created_at: OffsetDateTime,
+    pub updated_at: OffsetDateTime,
+}
+
+impl ImportJob {
+    pub fn new(
+        user_id: UserId,
+        mode: ImportMode,
+        partial: bool,
+        total_count: usize,
+        created_at: OffsetDateTime,
+    ) -> Self {
+        Self {
+            id: ImportJobId::new(),
+            user_id,
+            status: ImportJobStatus::Pending,
+            mode,
+            partial,
+            total_count,
+            processed_count: 0,
+            event_set_ids: Vec::new(),
+            failures: Vec::new(),
+            error: None,
+            claimed_by: None,
+            created_at,
+            updated_at: created_at,
+        }
+    }
+
+    // Records a chunk of `processed` entries, the entries of it that failed
+    // and the event set it was recorded in, if it wrote anything.
+    pub fn record_chunk(
+        &mut self,
+        processed: usize,
+        failures: Vec<ImportJobFailure>,
+        event_set_id: Option<EventSetId>,
+        updated_at: OffsetDateTime,
+    ) {
+        self.processed_count += processed;
+        self.failures.extend(failures);
+        self.event_set_ids.extend(event_set_id);
+        self.updated_at = updated_at;
+    }
+
+    pub fn set_status(&mut self, status: ImportJobStatus, updated_at: OffsetDateTime) {
+        self.status = status;
+        self.updated_at = updated_at;
+    }
+
+    pub fn fail(&mut self, error: String, updated_at: OffsetDateTime) {
+        self.error = Some(error);
+        self.set_status(ImportJobStatus::Failed, updated_at);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use time::OffsetDateTime;
+
+    use crate::domain::entity::{event_set::EventSetId, user::UserId};
+
+    use super::{ImportField, ImportJob, ImportJobFailure, ImportJobStatus, ImportMode};
+
+    #[test]
+    fn import_job_status_round_trips_through_str() {
+        for status in [
+            ImportJobStatus::Pending,
+            ImportJobStatus::Running,
+            ImportJobStatus::Succeeded,
+            ImportJobStatus::Failed,
+            ImportJobStatus::Cancelled,
+        ] {
+            assert_eq!(ImportJobStatus::try_from(status.as_str()), Ok(status));
+        }
+    }
+
+    #[test]
+    fn record_chunk_accumulates_progress() {
+        let now = OffsetDateTime::now_utc();
+        let mut job = ImportJob::new(
+            UserId::new("user1".to_string()).unwrap(),
+            ImportMode::CreateOnly,
+            true,
+            3,
+            now,
+        );
+        let event_set_id = EventSetId::new();
+
+        job.record_chunk(2, vec![], Some(event_set_id.clone()), now);
+        job.record_chunk(
+            1,
+            vec![ImportJobFailure {
+                index: 2,
+                field: ImportField::Isbn,
+                message: "invalid".to_string(),
+            }],
+            None,
+            now,
+        );
+
+        assert_eq!(job.processed_count, 3);
+        assert_eq!(job.failures.len(), 1);
+        assert_eq!(job.event_set_ids, vec![event_set_id]);
+        assert_eq!(job.status, ImportJobStatus::Pending);
+    }
+}
@@ -1,7 +1,13 @@
+pub mod activity;
 pub mod author_event_repository;
 pub mod author_repository;
 pub mod book_event_repository;
 pub mod book_repository;
 pub mod event_set_repository;
+pub mod history;
+pub mod import_job_repository;
+pub mod pagination;
+pub mod search;
 pub mod transaction;
+pub mod trash_repository;
 pub mod user_repository;
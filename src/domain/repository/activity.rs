@@ -0,0 +1,137 @@
+use time::{OffsetDateTime, UtcOffset};
+
+use crate::{common::activity::ActivityGranularity, domain::error::DomainError};
+
+pub const MAX_ACTIVITY_PERIODS: i64 = 1000;
+
+// A time range split into calendar periods. `from` is inclusive and `to` is
+// exclusive; periods are aligned to calendar boundaries at `utc_offset`, so
+// the first and last ones may extend beyond the range.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ActivityRange {
+    from: OffsetDateTime,
+    to: OffsetDateTime,
+    granularity: ActivityGranularity,
+    utc_offset: UtcOffset,
+}
+
+impl ActivityRange {
+    pub fn new(
+        from: OffsetDateTime,
+        to: OffsetDateTime,
+        granularity: ActivityGranularity,
+        utc_offset_minutes: i32,
+    ) -> Result<Self, DomainError> {
+        if from >= to {
+            return Err(DomainError::Validation(
+                "activity range must end after it starts.".to_string(),
+            ));
+        }
+        let utc_offset = UtcOffset::from_whole_seconds(utc_offset_minutes.saturating_mul(60))
+            .map_err(|_| {
+                DomainError::Validation(format!("Invalid UTC offset: {utc_offset_minutes}."))
+            })?;
+        // Months are counted as 28 days so that the bound is never too lax.
+        let period_days = match granularity {
+            ActivityGranularity::Day => 1,
+            ActivityGranularity::Week => 7,
+            ActivityGranularity::Month => 28,
+        };
+        let periods = (to - from).whole_days() / period_days + 1;
+        if periods > MAX_ACTIVITY_PERIODS {
+            return Err(DomainError::Validation(format!(
+                "activity range must span at most {MAX_ACTIVITY_PERIODS} periods."
+            )));
+        }
+
+        Ok(Self {
+            from,
+            to,
+            granularity,
+            utc_offset,
+        })
+    }
+
+    pub fn from(&self) -> OffsetDateTime {
+        self.from
+    }
+
+    pub fn to(&self) -> OffsetDateTime {
+        self.to
+    }
+
+    pub fn granularity(&self) -> ActivityGranularity {
+        self.granularity
+    }
+
+    pub fn utc_offset(&self) -> UtcOffset {
+        self.utc_offset
+    }
+
+    pub fn utc_offset_minutes(&self) -> i32 {
+        self.utc_offset.whole_minutes().into()
+    }
+}
+
+// Book events within one period, counted from state transitions:
+// - finished: a book was updated from unread to read. Books created as read
+//   were finished before they were added, so they do not count.
+// - added: a book was created
+// - deleted: a book was deleted
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ActivityPeriod {
+    pub start: OffsetDateTime,
+    pub finished_count: i64,
+    pub added_count: i64,
+    pub deleted_count: i64,
+}
+
+#[cfg(test)]
+mod tests {
+    use time::macros::datetime;
+
+    use super::*;
+
+    #[test]
+    fn accepts_a_year_of_days_with_an_offset() {
+        let range = ActivityRange::new(
+            datetime!(2026-01-01 00:00 UTC),
+            datetime!(2027-01-01 00:00 UTC),
+            ActivityGranularity::Day,
+            540,
+        )
+        .unwrap();
+
+        assert_eq!(range.utc_offset_minutes(), 540);
+    }
+
+    #[test]
+    fn rejects_empty_ranges_bad_offsets_and_too_many_periods() {
+        let from = datetime!(2026-01-01 00:00 UTC);
+        for (to, granularity, offset) in [
+            (from, ActivityGranularity::Day, 0),
+            (
+                datetime!(2026-01-02 00:00 UTC),
+                ActivityGranularity::Day,
+                26 * 60,
+            ),
+            (datetime!(2030-01-01 00:00 UTC), ActivityGranularity::Day, 0),
+        ] {
+            let result = ActivityRange::new(from, to, granularity, offset);
+
+            assert!(
+                matches!(result, Err(DomainError::Validation(_))),
+                "{to} {granularity:?} {offset}"
+            );
+        }
+        assert!(
+            ActivityRange::new(
+                from,
+                datetime!(2030-01-01 00:00 UTC),
+                ActivityGranularity::Week,
+                0
+            )
+            .is_ok()
+        );
+    }
+}
@@ -1,14 +1,21 @@
+use std::collections::HashMap;
+
 use async_trait::async_trait;
 use mockall::automock;
+use time::OffsetDateTime;
 
 use crate::domain::{
     entity::{
-        author::AuthorId,
-        event::{AuthorEvent, EventId, NewAuthorEvent},
+        author::{Author, AuthorId},
+        event::{AuthorEvent, EventId, EventOperation, NewAuthorEvent},
         event_set::EventSetId,
         user::UserId,
     },
     error::DomainError,
+    repository::{
+        history::{DeletedEntity, EventCursor, HistoryFilter},
+        pagination::{Page, PageRequest},
+    },
 };
 
 #[automock(type Transaction = ();)]
@@ -27,6 +34,14 @@ pub trait AuthorEventRepository: Send + Sync + 'static {
         author_id: &AuthorId,
     ) -> Result<Vec<AuthorEvent>, DomainError>;
 
+    async fn find_page_by_author(
+        &self,
+        user_id: &UserId,
+        author_id: &AuthorId,
+        filter: &HistoryFilter<EventOperation>,
+        page: &PageRequest<EventCursor>,
+    ) -> Result<Page<AuthorEvent, EventCursor>, DomainError>;
+
     async fn find_by_event_id(
         &self,
         user_id: &UserId,
@@ -38,4 +53,35 @@ pub trait AuthorEventRepository: Send + Sync + 'static {
         user_id: &UserId,
         event_set_id: &EventSetId,
     ) -> Result<Vec<AuthorEvent>, DomainError>;
+
+    // The stateful event preceding each of `event_ids` for the same author,
+    // keyed by the id of the event it precedes. First events have no entry.
+    async fn find_previous(
+        &self,
+        user_id: &UserId,
+        event_ids: &[i64],
+    ) -> Result<HashMap<i64, AuthorEvent>, DomainError>;
+
+    // The stateful event following each of `event_ids` for the same author,
+    // keyed by the id of the event it follows. Latest events have no entry.
+    async fn find_next(
+        &self,
+        user_id: &UserId,
+        event_ids: &[i64],
+    ) -> Result<HashMap<i64, AuthorEvent>, DomainError>;
+
+    // The user's authors as of `at`, rebuilt from the latest event of each
+    // author at or before it. Merge markers carry no state and are skipped.
+    async fn find_authors_as_of(
+        &self,
+        user_id: &UserId,
+        at: OffsetDateTime,
+    ) -> Result<Vec<Author>, DomainError>;
+
+    // Authors whose latest stateful event left them deleted, most recently
+    // deleted first. This includes authors merged into another one.
+    async fn find_deleted(
+        &self,
+        user_id: &UserId,
+    ) -> Result<Vec<DeletedEntity<AuthorEvent>>, DomainError>;
 }
@@ -4,15 +4,37 @@ use async_trait::async_trait;
 use mockall::automock;
 use time::OffsetDateTime;
 
-use crate::domain::{
-    entity::{
-        author::{Author, AuthorId, AuthorName},
-        event::EventId,
-        user::UserId,
+use crate::{
+    common::sort::{AuthorOrder, AuthorSortValue},
+    domain::{
+        entity::{
+            author::{Author, AuthorId, AuthorName},
+            book::BookId,
+            event::EventId,
+            user::UserId,
+        },
+        error::DomainError,
+        repository::{
+            pagination::{Page, PageRequest},
+            search::SearchQuery,
+        },
     },
-    error::DomainError,
 };
 
+// Keyset position of an author in a listing: the value of the sort key, then id.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AuthorCursor {
+    pub value: AuthorSortValue,
+    pub id: AuthorId,
+}
+
+// An author resolved by name, and whether resolving it created the author.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ResolvedAuthor {
+    pub id: AuthorId,
+    pub created: bool,
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum DeleteAuthorEventExtra {
     Merge { destination_author_id: AuthorId },
@@ -39,25 +61,74 @@ pub trait AuthorRepository: Send + Sync + 'static {
         user_id: &UserId,
         author_id: &AuthorId,
     ) -> Result<Option<Author>, DomainError>;
-    async fn find_all(&self, user_id: &UserId) -> Result<Vec<Author>, DomainError>;
+    // The author with `name`, locked until `tx` ends.
+    async fn find_by_name_with_tx(
+        &self,
+        tx: &mut Self::Transaction,
+        user_id: &UserId,
+        name: &AuthorName,
+    ) -> Result<Option<Author>, DomainError>;
+    // Id of the author's latest stateful event, read within `tx`. Merge
+    // markers are skipped as they leave the author unchanged.
+    async fn find_latest_event_id_with_tx(
+        &self,
+        tx: &mut Self::Transaction,
+        user_id: &UserId,
+        author_id: &AuthorId,
+    ) -> Result<Option<i64>, DomainError>;
+    async fn find_all(
+        &self,
+        user_id: &UserId,
+        order: &AuthorOrder,
+    ) -> Result<Vec<Author>, DomainError>;
+    async fn find_page(
+        &self,
+        user_id: &UserId,
+        order: &AuthorOrder,
+        page: &PageRequest<AuthorCursor>,
+    ) -> Result<Page<Author, AuthorCursor>, DomainError>;
     async fn find_by_ids_as_hash_map(
         &self,
         user_id: &UserId,
         author_ids: &[AuthorId],
     ) -> Result<HashMap<AuthorId, Author>, DomainError>;
-    // Resolves an author by name within the transaction, creating it if absent.
+    // Like `find_by_ids_as_hash_map`, read within `tx`.
+    async fn find_by_ids_as_hash_map_with_tx(
+        &self,
+        tx: &mut Self::Transaction,
+        user_id: &UserId,
+        author_ids: &[AuthorId],
+    ) -> Result<HashMap<AuthorId, Author>, DomainError>;
+    // Authors whose name or yomi matches every term, best match first.
+    async fn search(
+        &self,
+        user_id: &UserId,
+        query: &SearchQuery,
+        limit: usize,
+    ) -> Result<Vec<Author>, DomainError>;
+    // Resolves an author by name within the transaction, creating it with
+    // `yomi` if absent; an existing author keeps its own yomi.
     // A newly inserted author records one author_event; an existing one records none.
     async fn find_or_create_by_name(
         &self,
         tx: &mut Self::Transaction,
         name: &AuthorName,
+        yomi: &str,
         created_at: OffsetDateTime,
-    ) -> Result<AuthorId, DomainError>;
+    ) -> Result<ResolvedAuthor, DomainError>;
     async fn update(
         &self,
         tx: &mut Self::Transaction,
         author: &Author,
     ) -> Result<EventId, DomainError>;
+    // Locks the listed authors that exist until `tx` ends and returns each,
+    // in id order, with the ids of its books.
+    async fn lock_with_book_ids(
+        &self,
+        tx: &mut Self::Transaction,
+        user_id: &UserId,
+        author_ids: &[AuthorId],
+    ) -> Result<Vec<(AuthorId, Vec<BookId>)>, DomainError>;
     async fn delete(
         &self,
         tx: &mut Self::Transaction,
@@ -1,9 +1,22 @@
+use std::collections::HashMap;
+
 use async_trait::async_trait;
 use mockall::automock;
+use time::OffsetDateTime;
 
 use crate::domain::{
-    entity::{book::BookId, event::BookEvent, event_set::EventSetId, user::UserId},
+    entity::{
+        book::{Book, BookId},
+        event::{BookEvent, EventOperation},
+        event_set::EventSetId,
+        user::UserId,
+    },
     error::DomainError,
+    repository::{
+        activity::{ActivityPeriod, ActivityRange},
+        history::{DeletedEntity, EventCursor, HistoryFilter},
+        pagination::{Page, PageRequest},
+    },
 };
 
 #[automock]
@@ -15,6 +28,14 @@ pub trait BookEventRepository: Send + Sync + 'static {
         book_id: &BookId,
     ) -> Result<Vec<BookEvent>, DomainError>;
 
+    async fn find_page_by_book(
+        &self,
+        user_id: &UserId,
+        book_id: &BookId,
+        filter: &HistoryFilter<EventOperation>,
+        page: &PageRequest<EventCursor>,
+    ) -> Result<Page<BookEvent, EventCursor>, DomainError>;
+
     async fn find_by_event_id(
         &self,
         user_id: &UserId,
@@ -26,4 +47,42 @@ pub trait BookEventRepository: Send + Sync + 'static {
         user_id: &UserId,
         event_set_id: &EventSetId,
     ) -> Result<Vec<BookEvent>, DomainError>;
+
+    // The event preceding each of `event_ids` for the same book, keyed by the
+    // id of the event it precedes. First events have no entry.
+    async fn find_previous(
+        &self,
+        user_id: &UserId,
+        event_ids: &[i64],
+    ) -> Result<HashMap<i64, BookEvent>, DomainError>;
+
+    // The event following each of `event_ids` for the same book, keyed by the
+    // id of the event it follows. Latest events have no entry.
+    async fn find_next(
+        &self,
+        user_id: &UserId,
+        event_ids: &[i64],
+    ) -> Result<HashMap<i64, BookEvent>, DomainError>;
+
+    // One entry per period of `range`, oldest first, including empty ones.
+    async fn reading_activity(
+        &self,
+        user_id: &UserId,
+        range: &ActivityRange,
+    ) -> Result<Vec<ActivityPeriod>, DomainError>;
+
+    // The user's books as of `at`, rebuilt from the latest event of each book
+    // at or before it. Books whose latest event is a deletion are left out.
+    async fn find_books_as_of(
+        &self,
+        user_id: &UserId,
+        at: OffsetDateTime,
+    ) -> Result<Vec<Book>, DomainError>;
+
+    // Books whose latest event left them deleted, most recently deleted
+    // first. Books that never held state are left out.
+    async fn find_deleted(
+        &self,
+        user_id: &UserId,
+    ) -> Result<Vec<DeletedEntity<BookEvent>>, DomainError>;
 }
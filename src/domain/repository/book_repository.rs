@@ -2,17 +2,86 @@ use std::collections::HashMap;
 
 use async_trait::async_trait;
 use mockall::automock;
+use time::OffsetDateTime;
 
-use crate::domain::{
-    entity::{
-        author::AuthorId,
-        book::{Book, BookId},
-        event::EventId,
-        user::UserId,
+use crate::{
+    common::{
+        sort::{BookOrder, BookSortValue},
+        types::{BookFormat, BookStore},
+    },
+    domain::{
+        entity::{
+            author::{Author, AuthorId},
+            book::{Book, BookId},
+            event::EventId,
+            event_set::EventSetId,
+            user::UserId,
+        },
+        error::DomainError,
+        repository::{
+            pagination::{Page, PageRequest},
+            search::SearchQuery,
+        },
     },
-    error::DomainError,
 };
 
+// Keyset position of a book in a listing: the value of the sort key, then id.
+// A cursor can only be used with the order whose key produced it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BookCursor {
+    pub value: BookSortValue,
+    pub id: BookId,
+}
+
+// Conditions for narrowing down a user's books. Every Some field must match;
+// lower bounds are inclusive and the `*_to` timestamps are exclusive.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct BookFilter {
+    pub read: Option<bool>,
+    pub owned: Option<bool>,
+    pub format: Option<BookFormat>,
+    pub store: Option<BookStore>,
+    pub priority_min: Option<i32>,
+    pub priority_max: Option<i32>,
+    // Matches books written by any of the listed authors.
+    pub author_ids: Option<Vec<AuthorId>>,
+    pub created_from: Option<OffsetDateTime>,
+    pub created_to: Option<OffsetDateTime>,
+    pub updated_from: Option<OffsetDateTime>,
+    pub updated_to: Option<OffsetDateTime>,
+    pub has_isbn: Option<bool>,
+}
+
+// Books picked for a change to many of them at once.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BookSelection {
+    Ids(Vec<BookId>),
+    Filter(BookFilter),
+}
+
+// Aggregates over all of a user's books.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LibraryStats {
+    pub total_count: i64,
+    pub read_count: i64,
+    pub owned_count: i64,
+    pub unread_owned_count: i64,
+    // Every format and store, including those without books.
+    pub format_counts: Vec<(BookFormat, i64)>,
+    pub store_counts: Vec<(BookStore, i64)>,
+    pub priority_histogram: Vec<PriorityBucket>,
+    // Authors with the most books, with their book counts.
+    pub top_authors: Vec<(Author, i64)>,
+}
+
+// Number of books whose priority lies in `min..=max`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PriorityBucket {
+    pub min: i32,
+    pub max: i32,
+    pub count: i64,
+}
+
 #[automock(type Transaction = ();)]
 #[async_trait]
 pub trait BookRepository: Send + Sync + 'static {
@@ -31,18 +100,73 @@ pub trait BookRepository: Send + Sync + 'static {
         user_id: &UserId,
         book_id: &BookId,
     ) -> Result<Option<Book>, DomainError>;
-    async fn find_all(&self, user_id: &UserId) -> Result<Vec<Book>, DomainError>;
+    // Id of the book's latest event, read within `tx`.
+    async fn find_latest_event_id_with_tx(
+        &self,
+        tx: &mut Self::Transaction,
+        user_id: &UserId,
+        book_id: &BookId,
+    ) -> Result<Option<i64>, DomainError>;
+    // The book's latest event and the event set it belongs to, read within
+    // `tx`.
+    async fn find_latest_event_with_tx(
+        &self,
+        tx: &mut Self::Transaction,
+        user_id: &UserId,
+        book_id: &BookId,
+    ) -> Result<Option<(EventId, EventSetId)>, DomainError>;
+    async fn find_all(
+        &self,
+        user_id: &UserId,
+        filter: &BookFilter,
+        order: &BookOrder,
+    ) -> Result<Vec<Book>, DomainError>;
+    async fn find_page(
+        &self,
+        user_id: &UserId,
+        filter: &BookFilter,
+        order: &BookOrder,
+        page: &PageRequest<BookCursor>,
+    ) -> Result<Page<Book, BookCursor>, DomainError>;
+    async fn find_page_by_author_id(
+        &self,
+        user_id: &UserId,
+        author_id: &AuthorId,
+        order: &BookOrder,
+        page: &PageRequest<BookCursor>,
+    ) -> Result<Page<Book, BookCursor>, DomainError>;
     async fn find_by_author_ids_as_hash_map(
         &self,
         user_id: &UserId,
         author_ids: &[AuthorId],
+        order: &BookOrder,
     ) -> Result<HashMap<AuthorId, Vec<Book>>, DomainError>;
+    // Books whose title or any author matches every term, best match first.
+    async fn search(
+        &self,
+        user_id: &UserId,
+        query: &SearchQuery,
+        limit: usize,
+    ) -> Result<Vec<Book>, DomainError>;
+    async fn library_stats(
+        &self,
+        user_id: &UserId,
+        top_authors: usize,
+    ) -> Result<LibraryStats, DomainError>;
     async fn find_by_author_id_with_tx(
         &self,
         tx: &mut Self::Transaction,
         user_id: &UserId,
         author_id: &AuthorId,
     ) -> Result<Vec<Book>, DomainError>;
+    // The selected books in id order, locked until `tx` ends. Unknown ids are
+    // skipped.
+    async fn find_selection_with_tx(
+        &self,
+        tx: &mut Self::Transaction,
+        user_id: &UserId,
+        selection: &BookSelection,
+    ) -> Result<Vec<Book>, DomainError>;
     async fn update(&self, tx: &mut Self::Transaction, book: &Book)
     -> Result<EventId, DomainError>;
     async fn delete(&self, tx: &mut Self::Transaction, book_id: &BookId)
@@ -3,16 +3,27 @@ use mockall::automock;
 
 use crate::domain::{
     entity::{
+        event::EventSetOperation,
         event_set::{EventSet, EventSetId},
         user::UserId,
     },
     error::DomainError,
+    repository::{
+        history::{EventSetCursor, HistoryFilter},
+        pagination::{Page, PageRequest},
+    },
 };
 
 #[automock]
 #[async_trait]
 pub trait EventSetRepository: Send + Sync + 'static {
     async fn find_all(&self, user_id: &UserId) -> Result<Vec<EventSet>, DomainError>;
+    async fn find_page(
+        &self,
+        user_id: &UserId,
+        filter: &HistoryFilter<EventSetOperation>,
+        page: &PageRequest<EventSetCursor>,
+    ) -> Result<Page<EventSet, EventSetCursor>, DomainError>;
     async fn find_by_id(
         &self,
         user_id: &UserId,
@@ -0,0 +1,43 @@
+use time::OffsetDateTime;
+
+use crate::domain::entity::event_set::EventSetId;
+
+// Narrows a history listing to some operations and a time window. The window
+// applies to when the event or event set was recorded; `from` is inclusive
+// and `to` is exclusive.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HistoryFilter<O> {
+    // Matches entries with any of the listed operations.
+    pub operations: Option<Vec<O>>,
+    pub from: Option<OffsetDateTime>,
+    pub to: Option<OffsetDateTime>,
+}
+
+impl<O> Default for HistoryFilter<O> {
+    fn default() -> Self {
+        Self {
+            operations: None,
+            from: None,
+            to: None,
+        }
+    }
+}
+
+// Position in a history listing, which runs newest first by the time an
+// entry was recorded and then by id.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HistoryCursor<I> {
+    pub at: OffsetDateTime,
+    pub id: I,
+}
+
+pub type EventCursor = HistoryCursor<i64>;
+pub type EventSetCursor = HistoryCursor<EventSetId>;
+
+// An entity whose latest event left it deleted. `last_known` is the latest
+// event that still held its state, from which it can be restored.
+#[derive(Debug, Clone)]
+pub struct DeletedEntity<E> {
+    pub deletion: E,
+    pub last_known: E,
+}
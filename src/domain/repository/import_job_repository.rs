@@ -0,0 +1,60 @@
+use async_trait::async_trait;
+use mockall::automock;
+use time::OffsetDateTime;
+use uuid::Uuid;
+
+use crate::domain::{
+    entity::{
+        import_job::{ImportEntry, ImportJob, ImportJobId},
+        user::UserId,
+    },
+    error::DomainError,
+};
+
+#[automock(type Transaction = ();)]
+#[async_trait]
+pub trait ImportJobRepository: Send + Sync + 'static {
+    type Transaction: Send;
+
+    async fn create(&self, job: &ImportJob, entries: &[ImportEntry]) -> Result<(), DomainError>;
+    async fn find_by_id(
+        &self,
+        user_id: &UserId,
+        job_id: &ImportJobId,
+    ) -> Result<Option<ImportJob>, DomainError>;
+    // Claims the oldest job `worker_id` can run, whoever owns it, and returns
+    // it with its entries, marked running. A job can be claimed while it is
+    // pending, or running without its progress saved since `stale_before`,
+    // as left by a stopped server. Jobs of `excluded_user_ids` are left for
+    // later. Two workers never claim the same job at once.
+    async fn claim(
+        &self,
+        worker_id: Uuid,
+        excluded_user_ids: &[UserId],
+        stale_before: OffsetDateTime,
+        now: OffsetDateTime,
+    ) -> Result<Option<(ImportJob, Vec<ImportEntry>)>, DomainError>;
+    // Keeps the claim on a running job that `job`'s worker still holds from
+    // going stale while a chunk is written, as of `now`. Returns false when the
+    // job has finished meanwhile or another worker took it over.
+    async fn renew(&self, job: &ImportJob, now: OffsetDateTime) -> Result<bool, DomainError>;
+    // Saves the status, progress and error of a job that is still unfinished
+    // and claimed by the worker that claimed `job`. Returns false when the job
+    // has finished meanwhile, e.g. was cancelled, or another worker took it
+    // over.
+    async fn save(&self, job: &ImportJob) -> Result<bool, DomainError>;
+    // Like `save`, in the transaction that wrote the chunk the progress is for.
+    async fn save_with_tx(
+        &self,
+        tx: &mut Self::Transaction,
+        job: &ImportJob,
+    ) -> Result<bool, DomainError>;
+    // Cancels an unfinished job. Returns the job, cancelled or already
+    // finished, or None when the user has no such job.
+    async fn cancel(
+        &self,
+        user_id: &UserId,
+        job_id: &ImportJobId,
+        now: OffsetDateTime,
+    ) -> Result<Option<ImportJob>, DomainError>;
+}
@@ -0,0 +1,140 @@
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PageDirection {
+    Forward,
+    Backward,
+}
+
+// A keyset window over an ordered listing. `after` and `before` are exclusive
+// bounds; `limit` rows are taken from the start (Forward) or end (Backward).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PageRequest<C> {
+    pub after: Option<C>,
+    pub before: Option<C>,
+    pub limit: usize,
+    pub direction: PageDirection,
+}
+
+impl<C> PageRequest<C> {
+    pub fn forward(limit: usize) -> Self {
+        Self {
+            after: None,
+            before: None,
+            limit,
+            direction: PageDirection::Forward,
+        }
+    }
+
+    // Repositories fetch one extra row to learn whether the window continues.
+    pub fn fetch_limit(&self) -> i64 {
+        self.limit as i64 + 1
+    }
+}
+
+// A listed item together with the cursor that points at it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Edge<T, C> {
+    pub node: T,
+    pub cursor: C,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Page<T, C> {
+    pub edges: Vec<Edge<T, C>>,
+    pub has_previous_page: bool,
+    pub has_next_page: bool,
+    pub total_count: i64,
+}
+
+impl<T, C> Page<T, C> {
+    // Builds a page from rows fetched with `fetch_limit()` in the request's
+    // direction, i.e. reversed listing order for Backward requests.
+    pub fn from_rows(
+        mut rows: Vec<Edge<T, C>>,
+        request: &PageRequest<C>,
+        total_count: i64,
+    ) -> Self {
+        let has_more = rows.len() > request.limit;
+        rows.truncate(request.limit);
+
+        match request.direction {
+            PageDirection::Forward => Self {
+                edges: rows,
+                has_previous_page: request.after.is_some(),
+                has_next_page: has_more,
+                total_count,
+            },
+            PageDirection::Backward => {
+                rows.reverse();
+                Self {
+                    edges: rows,
+                    has_previous_page: has_more,
+                    has_next_page: request.before.is_some(),
+                    total_count,
+                }
+            }
+        }
+    }
+
+    pub fn nodes(&self) -> impl Iterator<Item = &T> {
+        self.edges.iter().map(|edge| &edge.node)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Edge, Page, PageDirection, PageRequest};
+
+    fn edges(cursors: &[i32]) -> Vec<Edge<i32, i32>> {
+        cursors
+            .iter()
+            .map(|&cursor| Edge {
+                node: cursor * 10,
+                cursor,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn forward_page_trims_extra_row_and_reports_next_page() {
+        let request = PageRequest::forward(2);
+
+        let page = Page::from_rows(edges(&[1, 2, 3]), &request, 5);
+
+        assert_eq!(page.nodes().copied().collect::<Vec<_>>(), vec![10, 20]);
+        assert!(page.has_next_page);
+        assert!(!page.has_previous_page);
+        assert_eq!(page.total_count, 5);
+    }
+
+    #[test]
+    fn forward_page_after_cursor_reports_previous_page() {
+        let request = PageRequest {
+            after: Some(1),
+            before: None,
+            limit: 2,
+            direction: PageDirection::Forward,
+        };
+
+        let page = Page::from_rows(edges(&[2, 3]), &request, 3);
+
+        assert_eq!(page.edges, edges(&[2, 3]));
+        assert!(!page.has_next_page);
+        assert!(page.has_previous_page);
+    }
+
+    #[test]
+    fn backward_page_restores_ascending_order() {
+        let request = PageRequest {
+            after: None,
+            before: Some(5),
+            limit: 2,
+            direction: PageDirection::Backward,
+        };
+
+        let page = Page::from_rows(edges(&[4, 3, 2]), &request, 5);
+
+        assert_eq!(page.edges, edges(&[3, 4]));
+        assert!(page.has_previous_page);
+        assert!(page.has_next_page);
+    }
+}
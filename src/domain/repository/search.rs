@@ -0,0 +1,89 @@
+use unicode_normalization::UnicodeNormalization;
+
+use crate::domain::error::DomainError;
+
+pub const MAX_SEARCH_QUERY_CHARS: usize = 200;
+
+// A free-text query, normalised the same way as the `search_text` columns
+// (see the add_search_text migration) so that both sides compare equal
+// regardless of full-width/half-width forms or katakana/hiragana.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SearchQuery {
+    text: String,
+    terms: Vec<String>,
+}
+
+impl SearchQuery {
+    pub fn new(raw: &str) -> Result<Self, DomainError> {
+        if raw.chars().count() > MAX_SEARCH_QUERY_CHARS {
+            return Err(DomainError::Validation(format!(
+                "search query must be at most {MAX_SEARCH_QUERY_CHARS} characters."
+            )));
+        }
+        let normalized = normalize_search_text(raw);
+        let terms: Vec<String> = normalized.split_whitespace().map(str::to_owned).collect();
+        if terms.is_empty() {
+            return Err(DomainError::Validation(
+                "search query must not be empty.".to_string(),
+            ));
+        }
+
+        Ok(Self {
+            text: terms.join(" "),
+            terms,
+        })
+    }
+
+    // The whole normalised query, used for ranking.
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    // Whitespace-separated terms; a match must contain every one of them.
+    pub fn terms(&self) -> &[String] {
+        &self.terms
+    }
+}
+
+// NFKC folds full-width ASCII and half-width katakana; katakana is then
+// shifted onto the hiragana block. Case is left to ILIKE.
+pub fn normalize_search_text(text: &str) -> String {
+    text.nfkc().map(katakana_to_hiragana).collect()
+}
+
+fn katakana_to_hiragana(c: char) -> char {
+    match c {
+        'ァ'..='ヶ' => char::from_u32(c as u32 - 0x60).unwrap_or(c),
+        _ => c,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::domain::error::DomainError;
+
+    use super::{SearchQuery, normalize_search_text};
+
+    #[test]
+    fn normalize_folds_width_and_katakana() {
+        assert_eq!(normalize_search_text("ＲＵＳＴ入門"), "RUST入門");
+        assert_eq!(normalize_search_text("ﾅﾂﾒ ソウセキ"), "なつめ そうせき");
+        assert_eq!(normalize_search_text("ヴァイオリン"), "ゔぁいおりん");
+    }
+
+    #[test]
+    fn query_splits_terms_on_any_whitespace() {
+        let query = SearchQuery::new("  夏目\u{3000}ｺｺﾛ ").unwrap();
+
+        assert_eq!(query.terms(), ["夏目", "こころ"]);
+        assert_eq!(query.text(), "夏目 こころ");
+    }
+
+    #[test]
+    fn query_rejects_blank_and_overlong_input() {
+        for raw in ["", " \u{3000}", &"あ".repeat(201)] {
+            let result = SearchQuery::new(raw);
+            assert!(matches!(result, Err(DomainError::Validation(_))), "{raw:?}");
+        }
+    }
+}
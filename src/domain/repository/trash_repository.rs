@@ -0,0 +1,30 @@
+use async_trait::async_trait;
+use mockall::automock;
+use time::OffsetDateTime;
+
+use crate::domain::error::DomainError;
+
+// What a purge removed: the books and authors whose history was dropped and
+// the event sets left without any event.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PurgedTrash {
+    pub book_count: i64,
+    pub author_count: i64,
+    pub event_set_count: i64,
+}
+
+#[automock(type Transaction = ();)]
+#[async_trait]
+pub trait TrashRepository: Send + Sync + 'static {
+    type Transaction: Send;
+
+    // Drops the whole history of the transaction user's books and authors
+    // whose latest event deleted them before `deleted_before`, along with
+    // merge markers naming a purged author. They can no longer be restored
+    // afterwards.
+    async fn purge(
+        &self,
+        tx: &mut Self::Transaction,
+        deleted_before: OffsetDateTime,
+    ) -> Result<PurgedTrash, DomainError>;
+}
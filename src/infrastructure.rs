@@ -4,5 +4,9 @@ pub mod book_event_repository;
 pub mod book_repository;
 pub mod error;
 pub mod event_set_repository;
+pub mod history;
+pub mod import_job_repository;
+pub mod search;
 pub mod transaction;
+pub mod trash_repository;
 pub mod user_repository;
@@ -1,20 +1,30 @@
+use std::collections::HashMap;
+
 use async_trait::async_trait;
 use serde_json::Value;
-use sqlx::PgPool;
+use sqlx::{PgPool, QueryBuilder};
 use time::OffsetDateTime;
 use uuid::Uuid;
 
 use crate::domain::{
     entity::{
-        author::AuthorId,
+        author::{Author, AuthorId},
         event::{AuthorEvent, EventId, EventOperation, NewAuthorEvent},
         event_set::EventSetId,
         user::UserId,
     },
     error::DomainError,
-    repository::author_event_repository::AuthorEventRepository,
+    repository::{
+        author_event_repository::AuthorEventRepository,
+        history::{DeletedEntity, EventCursor, HistoryFilter},
+        pagination::{Edge, Page, PageRequest},
+    },
+};
+use crate::infrastructure::{
+    author_repository::{AuthorRow, author_from_row},
+    history::{push_history_filter, push_history_window},
+    transaction::PgTransaction,
 };
-use crate::infrastructure::transaction::PgTransaction;
 
 #[derive(sqlx::FromRow)]
 struct AuthorEventRow {
@@ -30,6 +40,51 @@ struct AuthorEventRow {
     extra: Option<Value>,
 }
 
+#[derive(sqlx::FromRow)]
+struct AdjacentAuthorEventRow {
+    anchor_event_id: i64,
+    #[sqlx(flatten)]
+    event: AuthorEventRow,
+}
+
+// An author's latest stateful event alongside the event that deleted it. The
+// deleting event carries no state, so only its metadata is selected.
+#[derive(sqlx::FromRow)]
+struct DeletedAuthorEventRow {
+    deletion_event_id: i64,
+    deletion_event_set_id: Uuid,
+    deletion_operation: String,
+    deletion_changed_at: OffsetDateTime,
+    deletion_extra: Option<Value>,
+    #[sqlx(flatten)]
+    event: AuthorEventRow,
+}
+
+fn row_to_deleted_author(
+    row: DeletedAuthorEventRow,
+) -> Result<DeletedEntity<AuthorEvent>, DomainError> {
+    let operation = EventOperation::try_from(row.deletion_operation.as_str())
+        .map_err(DomainError::Unexpected)?;
+    let last_known = row_to_author_event(row.event)?;
+    let deletion = AuthorEvent {
+        event_id: row.deletion_event_id,
+        event_set_id: EventSetId::from(row.deletion_event_set_id),
+        operation,
+        author_id: last_known.author_id.clone(),
+        name: None,
+        yomi: None,
+        author_created_at: None,
+        author_updated_at: None,
+        changed_at: row.deletion_changed_at,
+        extra: row.deletion_extra,
+    };
+
+    Ok(DeletedEntity {
+        deletion,
+        last_known,
+    })
+}
+
 fn row_to_author_event(row: AuthorEventRow) -> Result<AuthorEvent, DomainError> {
     let operation =
         EventOperation::try_from(row.operation.as_str()).map_err(DomainError::Unexpected)?;
@@ -101,7 +156,7 @@ impl AuthorEventRepository for PgAuthorEventRepository {
                     author_created_at, author_updated_at, changed_at, extra
              FROM author_event
              WHERE user_id = $1 AND author_id = $2
-             ORDER BY changed_at DESC",
+             ORDER BY changed_at DESC, event_id DESC",
         )
         .bind(user_id.as_str())
         .bind(author_id.to_uuid())
@@ -111,6 +166,51 @@ impl AuthorEventRepository for PgAuthorEventRepository {
         rows.into_iter().map(row_to_author_event).collect()
     }
 
+    async fn find_page_by_author(
+        &self,
+        user_id: &UserId,
+        author_id: &AuthorId,
+        filter: &HistoryFilter<EventOperation>,
+        page: &PageRequest<EventCursor>,
+    ) -> Result<Page<AuthorEvent, EventCursor>, DomainError> {
+        let mut count = QueryBuilder::new("SELECT count(*) FROM author_event WHERE user_id = ");
+        count
+            .push_bind(user_id.as_str().to_owned())
+            .push(" AND author_id = ")
+            .push_bind(author_id.to_uuid());
+        push_history_filter(&mut count, "changed_at", filter, EventOperation::as_str);
+        let (total_count,): (i64,) = count.build_query_as().fetch_one(&self.pool).await?;
+
+        let mut query = QueryBuilder::new(
+            "SELECT event_id, event_set_id, operation, author_id, name, yomi,
+                    author_created_at, author_updated_at, changed_at, extra
+             FROM author_event
+             WHERE user_id = ",
+        );
+        query
+            .push_bind(user_id.as_str().to_owned())
+            .push(" AND author_id = ")
+            .push_bind(author_id.to_uuid());
+        push_history_filter(&mut query, "changed_at", filter, EventOperation::as_str);
+        push_history_window(&mut query, "changed_at", "event_id", page, |id| *id);
+        let rows: Vec<AuthorEventRow> = query.build_query_as().fetch_all(&self.pool).await?;
+        let edges = rows
+            .into_iter()
+            .map(|row| {
+                let event = row_to_author_event(row)?;
+                Ok(Edge {
+                    cursor: EventCursor {
+                        at: event.changed_at,
+                        id: event.event_id,
+                    },
+                    node: event,
+                })
+            })
+            .collect::<Result<Vec<_>, DomainError>>()?;
+
+        Ok(Page::from_rows(edges, page, total_count))
+    }
+
     async fn find_by_event_id(
         &self,
         user_id: &UserId,
@@ -149,6 +249,139 @@ impl AuthorEventRepository for PgAuthorEventRepository {
 
         rows.into_iter().map(row_to_author_event).collect()
     }
+
+    async fn find_previous(
+        &self,
+        user_id: &UserId,
+        event_ids: &[i64],
+    ) -> Result<HashMap<i64, AuthorEvent>, DomainError> {
+        let rows: Vec<AdjacentAuthorEventRow> = sqlx::query_as(
+            "SELECT
+                cur.event_id AS anchor_event_id,
+                ae.event_id, ae.event_set_id, ae.operation, ae.author_id, ae.name, ae.yomi,
+                ae.author_created_at, ae.author_updated_at, ae.changed_at, ae.extra
+             FROM author_event cur
+             CROSS JOIN LATERAL (
+                 SELECT * FROM author_event prev
+                 WHERE prev.user_id = cur.user_id
+                   AND prev.author_id = cur.author_id
+                   AND prev.operation <> 'merge_as_destination'
+                   AND (prev.changed_at, prev.event_id) < (cur.changed_at, cur.event_id)
+                 ORDER BY prev.changed_at DESC, prev.event_id DESC
+                 LIMIT 1
+             ) ae
+             WHERE cur.user_id = $1 AND cur.event_id = ANY($2)",
+        )
+        .bind(user_id.as_str())
+        .bind(event_ids)
+        .fetch_all(&self.pool)
+        .await?;
+
+        rows.into_iter()
+            .map(|row| Ok((row.anchor_event_id, row_to_author_event(row.event)?)))
+            .collect()
+    }
+
+    async fn find_next(
+        &self,
+        user_id: &UserId,
+        event_ids: &[i64],
+    ) -> Result<HashMap<i64, AuthorEvent>, DomainError> {
+        let rows: Vec<AdjacentAuthorEventRow> = sqlx::query_as(
+            "SELECT
+                cur.event_id AS anchor_event_id,
+                ae.event_id, ae.event_set_id, ae.operation, ae.author_id, ae.name, ae.yomi,
+                ae.author_created_at, ae.author_updated_at, ae.changed_at, ae.extra
+             FROM author_event cur
+             CROSS JOIN LATERAL (
+                 SELECT * FROM author_event later
+                 WHERE later.user_id = cur.user_id
+                   AND later.author_id = cur.author_id
+                   AND later.operation <> 'merge_as_destination'
+                   AND (later.changed_at, later.event_id) > (cur.changed_at, cur.event_id)
+                 ORDER BY later.changed_at, later.event_id
+                 LIMIT 1
+             ) ae
+             WHERE cur.user_id = $1 AND cur.event_id = ANY($2)",
+        )
+        .bind(user_id.as_str())
+        .bind(event_ids)
+        .fetch_all(&self.pool)
+        .await?;
+
+        rows.into_iter()
+            .map(|row| Ok((row.anchor_event_id, row_to_author_event(row.event)?)))
+            .collect()
+    }
+
+    async fn find_authors_as_of(
+        &self,
+        user_id: &UserId,
+        at: OffsetDateTime,
+    ) -> Result<Vec<Author>, DomainError> {
+        // Delete events, and restores of a deletion, leave the name null.
+        let rows: Vec<AuthorRow> = sqlx::query_as(
+            "WITH latest AS (
+                SELECT DISTINCT ON (author_id) *
+                FROM author_event
+                WHERE user_id = $1 AND changed_at <= $2 AND operation <> 'merge_as_destination'
+                ORDER BY author_id, changed_at DESC, event_id DESC
+            )
+            SELECT
+                author_id AS id,
+                name,
+                yomi,
+                author_created_at AS created_at,
+                author_updated_at AS updated_at
+            FROM latest
+            WHERE name IS NOT NULL
+            ORDER BY name, author_id",
+        )
+        .bind(user_id.as_str())
+        .bind(at)
+        .fetch_all(&self.pool)
+        .await?;
+
+        rows.into_iter().map(author_from_row).collect()
+    }
+
+    async fn find_deleted(
+        &self,
+        user_id: &UserId,
+    ) -> Result<Vec<DeletedEntity<AuthorEvent>>, DomainError> {
+        let rows: Vec<DeletedAuthorEventRow> = sqlx::query_as(
+            "WITH latest AS (
+                SELECT DISTINCT ON (author_id) *
+                FROM author_event
+                WHERE user_id = $1 AND operation <> 'merge_as_destination'
+                ORDER BY author_id, changed_at DESC, event_id DESC
+            )
+            SELECT
+                d.event_id AS deletion_event_id,
+                d.event_set_id AS deletion_event_set_id,
+                d.operation AS deletion_operation,
+                d.changed_at AS deletion_changed_at,
+                d.extra AS deletion_extra,
+                ae.event_id, ae.event_set_id, ae.operation, ae.author_id, ae.name, ae.yomi,
+                ae.author_created_at, ae.author_updated_at, ae.changed_at, ae.extra
+            FROM latest d
+            CROSS JOIN LATERAL (
+                SELECT * FROM author_event known
+                WHERE known.user_id = d.user_id
+                  AND known.author_id = d.author_id
+                  AND known.name IS NOT NULL
+                ORDER BY known.changed_at DESC, known.event_id DESC
+                LIMIT 1
+            ) ae
+            WHERE d.name IS NULL
+            ORDER BY d.changed_at DESC, d.event_id DESC",
+        )
+        .bind(user_id.as_str())
+        .fetch_all(&self.pool)
+        .await?;
+
+        rows.into_iter().map(row_to_deleted_author).collect()
+    }
 }
 
 #[cfg(feature = "test-with-database")]
@@ -156,12 +389,13 @@ impl AuthorEventRepository for PgAuthorEventRepository {
 mod tests {
     use crate::domain::{
         entity::{
-            author::{Author, AuthorName},
-            event::{EventOperation, EventSetOperation},
+            author::{Author, AuthorName, AuthorUpdate},
+            event::{EventOperation, EventSetOperation, NewAuthorEvent},
             user::User,
         },
         repository::{
-            author_repository::AuthorRepository, transaction::TransactionManager,
+            author_repository::{AuthorRepository, DeleteAuthorEventExtra},
+            transaction::TransactionManager,
             user_repository::UserRepository,
         },
     };
@@ -278,4 +512,176 @@ mod tests {
 
         Ok(())
     }
+
+    #[sqlx::test]
+    async fn find_previous_and_next_skip_merge_markers(pool: PgPool) -> anyhow::Result<()> {
+        let user_repo = PgUserRepository::new(pool.clone());
+        let author_repo = PgAuthorRepository::new(pool.clone());
+        let event_repo = PgAuthorEventRepository::new(pool.clone());
+
+        let user_id = prepare_user(&user_repo, "user1").await?;
+        let author_id = AuthorId::try_from("278935cf-ed83-4346-9b35-b84bbdb630c0")?;
+        let mut author = Author::new(
+            author_id.clone(),
+            AuthorName::new("before".to_owned())?,
+            OffsetDateTime::UNIX_EPOCH,
+        )?;
+        create_author(&pool, &author_repo, &user_id, &author).await?;
+
+        let tm = PgTransactionManager::new(pool.clone());
+        let mut tx = tm.begin(&user_id, EventSetOperation::MergeAuthor).await?;
+        event_repo
+            .append(
+                &mut tx,
+                &NewAuthorEvent::merge_as_destination(
+                    author_id.clone(),
+                    &AuthorId::try_from("925aaf96-64c7-44be-85f8-767a20b2c20c")?,
+                ),
+            )
+            .await?;
+        tm.commit(tx).await?;
+
+        author.update(
+            AuthorUpdate {
+                name: AuthorName::new("after".to_owned())?,
+                yomi: None,
+            },
+            OffsetDateTime::now_utc(),
+        );
+        let tm = PgTransactionManager::new(pool.clone());
+        let mut tx = tm.begin(&user_id, EventSetOperation::UpdateAuthor).await?;
+        author_repo.update(&mut tx, &author).await?;
+        tm.commit(tx).await?;
+
+        let entries = event_repo.find_by_author(&user_id, &author_id).await?;
+        let ids: Vec<i64> = entries.iter().map(|e| e.event_id).collect();
+        assert_eq!(entries.len(), 3);
+
+        let previous = event_repo.find_previous(&user_id, &ids).await?;
+        assert_eq!(previous.len(), 2);
+        // Both the update and the marker look past the marker to the create.
+        assert_eq!(previous[&ids[0]].event_id, ids[2]);
+        assert_eq!(previous[&ids[0]].name.as_deref(), Some("before"));
+        assert_eq!(previous[&ids[1]].event_id, ids[2]);
+
+        let next = event_repo.find_next(&user_id, &ids).await?;
+        assert_eq!(next.len(), 2);
+        assert_eq!(next[&ids[2]].event_id, ids[0]);
+        assert_eq!(next[&ids[2]].name.as_deref(), Some("after"));
+        assert_eq!(next[&ids[1]].event_id, ids[0]);
+
+        Ok(())
+    }
+
+    async fn latest_changed_at(pool: &PgPool) -> Result<OffsetDateTime, sqlx::Error> {
+        let (changed_at,): (OffsetDateTime,) =
+            sqlx::query_as("SELECT max(changed_at) FROM author_event")
+                .fetch_one(pool)
+                .await?;
+        Ok(changed_at)
+    }
+
+    #[sqlx::test]
+    async fn find_authors_as_of_replays_events_up_to_the_given_time(
+        pool: PgPool,
+    ) -> anyhow::Result<()> {
+        let user_repo = PgUserRepository::new(pool.clone());
+        let author_repo = PgAuthorRepository::new(pool.clone());
+        let event_repo = PgAuthorEventRepository::new(pool.clone());
+        let tm = PgTransactionManager::new(pool.clone());
+
+        let user_id = prepare_user(&user_repo, "user1").await?;
+        let renamed_id = AuthorId::try_from("278935cf-ed83-4346-9b35-b84bbdb630c0")?;
+        let deleted_id = AuthorId::try_from("e324be11-2d3b-4f4e-a1a6-e6bcd0e6b4a1")?;
+        let original = Author::new(
+            renamed_id.clone(),
+            AuthorName::new("original".to_owned())?,
+            OffsetDateTime::UNIX_EPOCH,
+        )?;
+        create_author(&pool, &author_repo, &user_id, &original).await?;
+        let deleted = Author::new(
+            deleted_id.clone(),
+            AuthorName::new("deleted".to_owned())?,
+            OffsetDateTime::UNIX_EPOCH,
+        )?;
+        create_author(&pool, &author_repo, &user_id, &deleted).await?;
+        let before_changes = latest_changed_at(&pool).await?;
+
+        let renamed = Author::new(
+            renamed_id.clone(),
+            AuthorName::new("renamed".to_owned())?,
+            OffsetDateTime::UNIX_EPOCH,
+        )?;
+        let mut tx = tm.begin(&user_id, EventSetOperation::MergeAuthor).await?;
+        author_repo.update(&mut tx, &renamed).await?;
+        author_repo.delete(&mut tx, &deleted_id, None).await?;
+        event_repo
+            .append(
+                &mut tx,
+                &NewAuthorEvent::merge_as_destination(renamed_id, &deleted_id),
+            )
+            .await?;
+        tm.commit(tx).await?;
+        let after_changes = latest_changed_at(&pool).await?;
+
+        let authors = event_repo
+            .find_authors_as_of(&user_id, before_changes)
+            .await?;
+        assert_eq!(authors, vec![deleted, original]);
+        let authors = event_repo
+            .find_authors_as_of(&user_id, after_changes)
+            .await?;
+        assert_eq!(authors, vec![renamed]);
+        let authors = event_repo
+            .find_authors_as_of(&user_id, OffsetDateTime::UNIX_EPOCH)
+            .await?;
+        assert!(authors.is_empty());
+
+        Ok(())
+    }
+
+    #[sqlx::test]
+    async fn find_deleted_keeps_the_merge_destination(pool: PgPool) -> anyhow::Result<()> {
+        let user_repo = PgUserRepository::new(pool.clone());
+        let author_repo = PgAuthorRepository::new(pool.clone());
+        let event_repo = PgAuthorEventRepository::new(pool.clone());
+        let user_id = prepare_user(&user_repo, "user1").await?;
+
+        let source_id = AuthorId::try_from("278935cf-ed83-4346-9b35-b84bbdb630c0")?;
+        let destination_id = AuthorId::try_from("e324be11-2d3b-4f4e-a1a6-e6bcd0e6b4a1")?;
+        for (author_id, name) in [(&source_id, "source"), (&destination_id, "destination")] {
+            let author = Author::new(
+                author_id.clone(),
+                AuthorName::new(name.to_owned())?,
+                OffsetDateTime::UNIX_EPOCH,
+            )?;
+            create_author(&pool, &author_repo, &user_id, &author).await?;
+        }
+        let tm = PgTransactionManager::new(pool.clone());
+        let mut tx = tm.begin(&user_id, EventSetOperation::MergeAuthor).await?;
+        author_repo
+            .delete(
+                &mut tx,
+                &source_id,
+                Some(DeleteAuthorEventExtra::Merge {
+                    destination_author_id: destination_id.clone(),
+                }),
+            )
+            .await?;
+        tm.commit(tx).await?;
+
+        // Given an author merged into another, when listing deleted authors
+        let deleted = event_repo.find_deleted(&user_id).await?;
+
+        // Then only the source is listed, pointing at its destination
+        assert_eq!(deleted.len(), 1);
+        assert_eq!(deleted[0].last_known.author_id, source_id);
+        assert_eq!(deleted[0].last_known.name.as_deref(), Some("source"));
+        assert_eq!(
+            deleted[0].deletion.merge_destination(),
+            Some(destination_id)
+        );
+
+        Ok(())
+    }
 }
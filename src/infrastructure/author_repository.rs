@@ -3,23 +3,34 @@ use std::collections::HashMap;
 use async_trait::async_trait;
 use futures_util::{StreamExt, TryStreamExt};
 use serde_json::json;
-use sqlx::PgPool;
+use sqlx::{PgPool, Postgres, QueryBuilder};
 use time::OffsetDateTime;
 use uuid::Uuid;
 
+use crate::common::sort::{AuthorOrder, AuthorSortKey, AuthorSortValue, SortDirection};
 use crate::domain::{
     entity::{
         author::{Author, AuthorId, AuthorName},
+        book::BookId,
         event::{EventId, EventOperation},
         user::UserId,
     },
     error::DomainError,
-    repository::author_repository::{AuthorRepository, DeleteAuthorEventExtra},
+    repository::{
+        author_repository::{
+            AuthorCursor, AuthorRepository, DeleteAuthorEventExtra, ResolvedAuthor,
+        },
+        pagination::{Edge, Page, PageDirection, PageRequest},
+        search::SearchQuery,
+    },
+};
+use crate::infrastructure::{
+    search::{contains_pattern, push_search_rank},
+    transaction::PgTransaction,
 };
-use crate::infrastructure::transaction::PgTransaction;
 
 #[derive(sqlx::FromRow)]
-struct AuthorRow {
+pub(super) struct AuthorRow {
     id: Uuid,
     name: String,
     yomi: String,
@@ -27,6 +38,13 @@ struct AuthorRow {
     updated_at: OffsetDateTime,
 }
 
+#[derive(sqlx::FromRow)]
+struct AuthorPageRow {
+    #[sqlx(flatten)]
+    author: AuthorRow,
+    sort_yomi: String,
+}
+
 #[derive(sqlx::FromRow)]
 struct AuthorSnapshotRow {
     name: String,
@@ -112,20 +130,22 @@ impl AuthorRepository for PgAuthorRepository {
         &self,
         tx: &mut Self::Transaction,
         name: &AuthorName,
+        yomi: &str,
         created_at: OffsetDateTime,
-    ) -> Result<AuthorId, DomainError> {
+    ) -> Result<ResolvedAuthor, DomainError> {
         let user_id = tx.user_id().clone();
         let name = name.as_str();
         let candidate_id = Uuid::new_v4();
 
         let result = sqlx::query(
-            "INSERT INTO author (id, user_id, name, created_at, updated_at)
-             VALUES ($1, $2, $3, $4, $4)
+            "INSERT INTO author (id, user_id, name, yomi, created_at, updated_at)
+             VALUES ($1, $2, $3, $4, $5, $5)
              ON CONFLICT (user_id, name) DO NOTHING",
         )
         .bind(candidate_id)
         .bind(user_id.as_str())
         .bind(name)
+        .bind(yomi)
         .bind(created_at)
         .execute(tx.as_mut())
         .await?;
@@ -143,8 +163,9 @@ impl AuthorRepository for PgAuthorRepository {
         .await?;
 
         let author_id = AuthorId::new(snap.id);
+        let created = rows_affected == 1;
 
-        if rows_affected == 1 {
+        if created {
             sqlx::query(
                 "INSERT INTO author_event
                    (event_set_id, operation, author_id, user_id,
@@ -163,7 +184,10 @@ impl AuthorRepository for PgAuthorRepository {
             .await?;
         }
 
-        Ok(author_id)
+        Ok(ResolvedAuthor {
+            id: author_id,
+            created,
+        })
     }
 
     async fn find_by_id(
@@ -190,32 +214,104 @@ impl AuthorRepository for PgAuthorRepository {
         author_from_optional_row(row)
     }
 
-    async fn find_all(&self, user_id: &UserId) -> Result<Vec<Author>, DomainError> {
-        let authors: Result<Vec<Author>, DomainError> =
-            sqlx::query_as("SELECT * FROM author WHERE user_id = $1 ORDER BY name ASC")
+    async fn find_by_name_with_tx(
+        &self,
+        tx: &mut Self::Transaction,
+        user_id: &UserId,
+        name: &AuthorName,
+    ) -> Result<Option<Author>, DomainError> {
+        let row: Option<AuthorRow> =
+            sqlx::query_as("SELECT * FROM author WHERE user_id = $1 AND name = $2 FOR UPDATE")
                 .bind(user_id.as_str())
-                .fetch(&self.pool)
-                .map(
-                    |row: Result<AuthorRow, sqlx::Error>| -> Result<Author, DomainError> {
-                        let row = row?;
-                        let author_id = AuthorId::new(row.id);
-                        let author_name = AuthorName::new(row.name)?;
-                        let author = Author::new_with_timestamps(
-                            author_id,
-                            author_name,
-                            row.yomi,
-                            row.created_at,
-                            row.updated_at,
-                        )?;
-                        Ok(author)
-                    },
-                )
-                .try_collect()
-                .await;
+                .bind(name.as_str())
+                .fetch_optional(tx.as_mut())
+                .await?;
+
+        author_from_optional_row(row)
+    }
+
+    async fn find_latest_event_id_with_tx(
+        &self,
+        tx: &mut Self::Transaction,
+        user_id: &UserId,
+        author_id: &AuthorId,
+    ) -> Result<Option<i64>, DomainError> {
+        let row: Option<(i64,)> = sqlx::query_as(
+            "SELECT event_id FROM author_event
+             WHERE user_id = $1 AND author_id = $2 AND operation <> 'merge_as_destination'
+             ORDER BY changed_at DESC, event_id DESC
+             LIMIT 1",
+        )
+        .bind(user_id.as_str())
+        .bind(author_id.to_uuid())
+        .fetch_optional(tx.as_mut())
+        .await?;
+
+        Ok(row.map(|(event_id,)| event_id))
+    }
+
+    async fn find_all(
+        &self,
+        user_id: &UserId,
+        order: &AuthorOrder,
+    ) -> Result<Vec<Author>, DomainError> {
+        let mut query = QueryBuilder::new("SELECT * FROM author WHERE user_id = ");
+        query.push_bind(user_id.as_str().to_owned());
+        push_order_by(&mut query, order.key, order.direction);
+        let authors: Result<Vec<Author>, DomainError> = query
+            .build_query_as()
+            .fetch(&self.pool)
+            .map(
+                |row: Result<AuthorRow, sqlx::Error>| -> Result<Author, DomainError> {
+                    let row = row?;
+                    let author_id = AuthorId::new(row.id);
+                    let author_name = AuthorName::new(row.name)?;
+                    let author = Author::new_with_timestamps(
+                        author_id,
+                        author_name,
+                        row.yomi,
+                        row.created_at,
+                        row.updated_at,
+                    )?;
+                    Ok(author)
+                },
+            )
+            .try_collect()
+            .await;
 
         authors
     }
 
+    async fn find_page(
+        &self,
+        user_id: &UserId,
+        order: &AuthorOrder,
+        page: &PageRequest<AuthorCursor>,
+    ) -> Result<Page<Author, AuthorCursor>, DomainError> {
+        let (total_count,): (i64,) =
+            sqlx::query_as("SELECT count(*) FROM author WHERE user_id = $1")
+                .bind(user_id.as_str())
+                .fetch_one(&self.pool)
+                .await?;
+
+        let mut query = QueryBuilder::new("SELECT * FROM author WHERE user_id = ");
+        query.push_bind(user_id.as_str().to_owned());
+        push_page_window(&mut query, order, page)?;
+        let rows: Vec<AuthorPageRow> = query.build_query_as().fetch_all(&self.pool).await?;
+        let edges = rows
+            .into_iter()
+            .map(|row| {
+                let author = author_from_row(row.author)?;
+                Ok(Edge {
+                    cursor: author_cursor(&author, order.key, row.sort_yomi),
+                    node: author,
+                })
+            })
+            .collect::<Result<Vec<_>, DomainError>>()?;
+
+        Ok(Page::from_rows(edges, page, total_count))
+    }
+
     async fn update(
         &self,
         tx: &mut Self::Transaction,
@@ -279,6 +375,52 @@ impl AuthorRepository for PgAuthorRepository {
         Ok(EventId::from(event_id))
     }
 
+    async fn lock_with_book_ids(
+        &self,
+        tx: &mut Self::Transaction,
+        user_id: &UserId,
+        author_ids: &[AuthorId],
+    ) -> Result<Vec<(AuthorId, Vec<BookId>)>, DomainError> {
+        let author_ids: Vec<Uuid> = author_ids.iter().map(AuthorId::to_uuid).collect();
+        // Locking the authors keeps books from being linked to them until
+        // the transaction ends.
+        let locked_author_ids: Vec<Uuid> = sqlx::query_scalar(
+            "SELECT id FROM author
+             WHERE user_id = $1 AND id = ANY($2)
+             ORDER BY id
+             FOR UPDATE",
+        )
+        .bind(user_id.as_str())
+        .bind(&author_ids)
+        .fetch_all(tx.as_mut())
+        .await?;
+
+        let links: Vec<(Uuid, Uuid)> = sqlx::query_as(
+            "SELECT author_id, book_id FROM book_author
+             WHERE user_id = $1 AND author_id = ANY($2)
+             ORDER BY author_id, book_id",
+        )
+        .bind(user_id.as_str())
+        .bind(&locked_author_ids)
+        .fetch_all(tx.as_mut())
+        .await?;
+
+        let mut book_ids_by_author: HashMap<Uuid, Vec<BookId>> = HashMap::new();
+        for (author_id, book_id) in links {
+            book_ids_by_author
+                .entry(author_id)
+                .or_default()
+                .push(BookId::new(book_id)?);
+        }
+        Ok(locked_author_ids
+            .into_iter()
+            .map(|author_id| {
+                let book_ids = book_ids_by_author.remove(&author_id).unwrap_or_default();
+                (AuthorId::new(author_id), book_ids)
+            })
+            .collect())
+    }
+
     async fn delete(
         &self,
         tx: &mut Self::Transaction,
@@ -465,36 +607,44 @@ impl AuthorRepository for PgAuthorRepository {
         user_id: &UserId,
         author_ids: &[AuthorId],
     ) -> Result<HashMap<AuthorId, Author>, DomainError> {
-        let author_ids: Vec<Uuid> = author_ids
-            .iter()
-            .map(|author_id| author_id.to_uuid())
-            .collect();
+        find_authors_by_ids_with_executor(&self.pool, user_id, author_ids).await
+    }
 
-        let authors_map: HashMap<AuthorId, Author> = sqlx::query_as(
-            "SELECT * FROM author WHERE user_id = $1 AND id = ANY($2) ORDER BY name ASC",
-        )
-        .bind(user_id.as_str())
-        .bind(author_ids)
-        .fetch(&self.pool)
-        .map(
-            |row: Result<AuthorRow, sqlx::Error>| -> Result<(AuthorId, Author), DomainError> {
-                let row = row?;
-                let author_id = AuthorId::new(row.id);
-                let author_name = AuthorName::new(row.name)?;
-                let author = Author::new_with_timestamps(
-                    author_id.clone(),
-                    author_name,
-                    row.yomi,
-                    row.created_at,
-                    row.updated_at,
-                )?;
-                Ok((author_id, author))
-            },
-        )
-        .try_collect()
-        .await?;
+    async fn find_by_ids_as_hash_map_with_tx(
+        &self,
+        tx: &mut Self::Transaction,
+        user_id: &UserId,
+        author_ids: &[AuthorId],
+    ) -> Result<HashMap<AuthorId, Author>, DomainError> {
+        find_authors_by_ids_with_executor(tx.as_mut(), user_id, author_ids).await
+    }
 
-        Ok(authors_map)
+    async fn search(
+        &self,
+        user_id: &UserId,
+        search: &SearchQuery,
+        limit: usize,
+    ) -> Result<Vec<Author>, DomainError> {
+        let mut query = QueryBuilder::new("SELECT * FROM author WHERE user_id = ");
+        query.push_bind(user_id.as_str().to_owned());
+        for term in search.terms() {
+            query
+                .push(" AND search_text ILIKE ")
+                .push_bind(contains_pattern(term));
+        }
+        query.push(" ORDER BY ");
+        push_search_rank(&mut query, "search_text", search);
+        query.push(" DESC, sort_yomi ASC, id ASC LIMIT ");
+        query.push_bind(limit as i64);
+
+        let authors: Result<Vec<Author>, DomainError> = query
+            .build_query_as()
+            .fetch(&self.pool)
+            .map(|row: Result<AuthorRow, sqlx::Error>| author_from_row(row?))
+            .try_collect()
+            .await;
+
+        authors
     }
 }
 
@@ -516,19 +666,136 @@ where
     author_from_optional_row(row)
 }
 
+async fn find_authors_by_ids_with_executor<'e, E>(
+    executor: E,
+    user_id: &UserId,
+    author_ids: &[AuthorId],
+) -> Result<HashMap<AuthorId, Author>, DomainError>
+where
+    E: sqlx::Executor<'e, Database = sqlx::Postgres>,
+{
+    let author_ids: Vec<Uuid> = author_ids
+        .iter()
+        .map(|author_id| author_id.to_uuid())
+        .collect();
+
+    let rows: Vec<AuthorRow> = sqlx::query_as(
+        "SELECT * FROM author WHERE user_id = $1 AND id = ANY($2) ORDER BY name ASC",
+    )
+    .bind(user_id.as_str())
+    .bind(author_ids)
+    .fetch_all(executor)
+    .await?;
+
+    rows.into_iter()
+        .map(|row| {
+            let author = author_from_row(row)?;
+            Ok((author.id().clone(), author))
+        })
+        .collect()
+}
+
+pub(super) fn author_from_row(row: AuthorRow) -> Result<Author, DomainError> {
+    let author_id: AuthorId = row.id.into();
+    let author_name = AuthorName::new(row.name)?;
+    Author::new_with_timestamps(
+        author_id,
+        author_name,
+        row.yomi,
+        row.created_at,
+        row.updated_at,
+    )
+}
+
 fn author_from_optional_row(row: Option<AuthorRow>) -> Result<Option<Author>, DomainError> {
-    row.map(|row| -> Result<Author, DomainError> {
-        let author_id: AuthorId = row.id.into();
-        let author_name = AuthorName::new(row.name)?;
-        Author::new_with_timestamps(
-            author_id,
-            author_name,
-            row.yomi,
-            row.created_at,
-            row.updated_at,
-        )
-    })
-    .transpose()
+    row.map(author_from_row).transpose()
+}
+
+fn sort_column(key: AuthorSortKey) -> &'static str {
+    match key {
+        AuthorSortKey::Name => "name",
+        AuthorSortKey::Yomi => "sort_yomi",
+        AuthorSortKey::CreatedAt => "created_at",
+        AuthorSortKey::UpdatedAt => "updated_at",
+    }
+}
+
+fn push_order_by(query: &mut QueryBuilder<Postgres>, key: AuthorSortKey, direction: SortDirection) {
+    let direction = match direction {
+        SortDirection::Asc => "ASC",
+        SortDirection::Desc => "DESC",
+    };
+    query.push(format!(
+        " ORDER BY {} {direction}, id {direction}",
+        sort_column(key)
+    ));
+}
+
+fn push_cursor_bound(
+    query: &mut QueryBuilder<Postgres>,
+    order: &AuthorOrder,
+    operator: &str,
+    cursor: &AuthorCursor,
+) -> Result<(), DomainError> {
+    if cursor.value.key() != order.key {
+        return Err(DomainError::Validation(
+            "cursor does not match the requested order.".to_string(),
+        ));
+    }
+    query.push(format!(
+        " AND ({}, id) {operator} (",
+        sort_column(order.key)
+    ));
+    match &cursor.value {
+        AuthorSortValue::Name(value) | AuthorSortValue::Yomi(value) => {
+            query.push_bind(value.clone())
+        }
+        AuthorSortValue::CreatedAt(value) | AuthorSortValue::UpdatedAt(value) => {
+            query.push_bind(*value)
+        }
+    };
+    query.push(", ").push_bind(cursor.id.to_uuid()).push(")");
+    Ok(())
+}
+
+// Appends the keyset bounds, ordering and limit of a page. Rows are compared
+// as (sort key, id), matching the listing order of find_all.
+fn push_page_window(
+    query: &mut QueryBuilder<Postgres>,
+    order: &AuthorOrder,
+    page: &PageRequest<AuthorCursor>,
+) -> Result<(), DomainError> {
+    let (after, before) = match order.direction {
+        SortDirection::Asc => (">", "<"),
+        SortDirection::Desc => ("<", ">"),
+    };
+    if let Some(cursor) = &page.after {
+        push_cursor_bound(query, order, after, cursor)?;
+    }
+    if let Some(cursor) = &page.before {
+        push_cursor_bound(query, order, before, cursor)?;
+    }
+    let direction = match (order.direction, page.direction) {
+        (SortDirection::Asc, PageDirection::Forward)
+        | (SortDirection::Desc, PageDirection::Backward) => SortDirection::Asc,
+        _ => SortDirection::Desc,
+    };
+    push_order_by(query, order.key, direction);
+    query.push(" LIMIT ").push_bind(page.fetch_limit());
+    Ok(())
+}
+
+fn author_cursor(author: &Author, key: AuthorSortKey, sort_yomi: String) -> AuthorCursor {
+    let value = match key {
+        AuthorSortKey::Name => AuthorSortValue::Name(author.name().as_str().to_owned()),
+        AuthorSortKey::Yomi => AuthorSortValue::Yomi(sort_yomi),
+        AuthorSortKey::CreatedAt => AuthorSortValue::CreatedAt(*author.created_at()),
+        AuthorSortKey::UpdatedAt => AuthorSortValue::UpdatedAt(*author.updated_at()),
+    };
+    AuthorCursor {
+        value,
+        id: author.id().clone(),
+    }
 }
 
 #[cfg(feature = "test-with-database")]
@@ -676,6 +943,35 @@ mod tests {
         Ok(())
     }
 
+    #[sqlx::test]
+    async fn find_by_ids_as_hash_map_with_tx_sees_authors_created_in_the_transaction(
+        pool: PgPool,
+    ) -> anyhow::Result<()> {
+        let user_repository = PgUserRepository::new(pool.clone());
+        let author_repository = PgAuthorRepository::new(pool.clone());
+
+        let user_id = prepare_user(&user_repository, "user1").await?;
+        let author_id = AuthorId::try_from("e324be11-5b77-4ba6-8423-9f27e2d228f1")?;
+        let author = new_author(author_id.clone(), AuthorName::new(String::from("author1"))?)?;
+
+        let tm = PgTransactionManager::new(pool.clone());
+        let mut tx = tm.begin(&user_id, EventSetOperation::CreateAuthor).await?;
+        author_repository.create(&mut tx, &author).await?;
+        let found = author_repository
+            .find_by_ids_as_hash_map_with_tx(&mut tx, &user_id, std::slice::from_ref(&author_id))
+            .await?;
+        assert_eq!(found.get(&author_id), Some(&author));
+        assert!(
+            author_repository
+                .find_by_ids_as_hash_map(&user_id, std::slice::from_ref(&author_id))
+                .await?
+                .is_empty()
+        );
+        tm.commit(tx).await?;
+
+        Ok(())
+    }
+
     #[sqlx::test]
     async fn find_by_id_with_tx_uses_explicit_user_scope(pool: PgPool) -> anyhow::Result<()> {
         let user_repository = PgUserRepository::new(pool.clone());
@@ -716,7 +1012,9 @@ mod tests {
         create_author(&pool, &author_repository, &user_id, &author1).await?;
         create_author(&pool, &author_repository, &user_id, &author2).await?;
 
-        let all_authors = author_repository.find_all(&user_id).await?;
+        let all_authors = author_repository
+            .find_all(&user_id, &AuthorOrder::default())
+            .await?;
         assert_eq!(all_authors.len(), 2);
         assert_eq!(all_authors, vec![author1, author2]);
 
@@ -772,6 +1070,299 @@ mod tests {
         Ok(())
     }
 
+    #[sqlx::test]
+    async fn find_page_walks_authors_in_name_order(pool: PgPool) -> anyhow::Result<()> {
+        let user_repository = PgUserRepository::new(pool.clone());
+        let author_repository = PgAuthorRepository::new(pool.clone());
+
+        let user1_id = prepare_user(&user_repository, "user1").await?;
+        let user2_id = prepare_user(&user_repository, "user2").await?;
+        let authors = [
+            ("93090e87-b7a1-403c-974c-d74d881e83b9", "author2"),
+            ("e324be11-5b77-4ba6-8423-9f27e2d228f1", "author1"),
+            ("278935cf-ed83-4346-9b35-b84bbdb630c0", "author3"),
+        ]
+        .into_iter()
+        .map(|(id, name)| new_author(AuthorId::try_from(id)?, AuthorName::new(name.to_string())?))
+        .collect::<Result<Vec<_>, DomainError>>()?;
+        for author in &authors {
+            create_author(&pool, &author_repository, &user1_id, author).await?;
+        }
+        let other_author = new_author(
+            AuthorId::try_from("925aaf96-64c7-44be-85f8-767a20b2c20c")?,
+            AuthorName::new("author0".to_string())?,
+        )?;
+        create_author(&pool, &author_repository, &user2_id, &other_author).await?;
+
+        let page = author_repository
+            .find_page(&user1_id, &AuthorOrder::default(), &PageRequest::forward(2))
+            .await?;
+        assert_eq!(
+            page.nodes().cloned().collect::<Vec<_>>(),
+            vec![authors[1].clone(), authors[0].clone()]
+        );
+        assert!(page.has_next_page);
+        assert_eq!(page.total_count, 3);
+
+        let after = AuthorCursor {
+            value: AuthorSortValue::Name(authors[0].name().as_str().to_string()),
+            id: authors[0].id().clone(),
+        };
+        let page = author_repository
+            .find_page(
+                &user1_id,
+                &AuthorOrder::default(),
+                &PageRequest {
+                    after: Some(after.clone()),
+                    ..PageRequest::forward(2)
+                },
+            )
+            .await?;
+        assert_eq!(
+            page.nodes().cloned().collect::<Vec<_>>(),
+            vec![authors[2].clone()]
+        );
+        assert!(!page.has_next_page);
+        assert!(page.has_previous_page);
+
+        let page = author_repository
+            .find_page(
+                &user1_id,
+                &AuthorOrder::default(),
+                &PageRequest {
+                    after: None,
+                    before: Some(after),
+                    limit: 1,
+                    direction: PageDirection::Backward,
+                },
+            )
+            .await?;
+        assert_eq!(
+            page.nodes().cloned().collect::<Vec<_>>(),
+            vec![authors[1].clone()]
+        );
+        assert!(!page.has_previous_page);
+        assert!(page.has_next_page);
+
+        Ok(())
+    }
+
+    #[sqlx::test]
+    async fn yomi_order_folds_katakana_and_falls_back_to_name(pool: PgPool) -> anyhow::Result<()> {
+        let user_repository = PgUserRepository::new(pool.clone());
+        let author_repository = PgAuthorRepository::new(pool.clone());
+
+        let user_id = prepare_user(&user_repository, "user1").await?;
+        // Sorted by reading: あくたがわ, いのうえ and ナツメ (no yomi, so their
+        // names, with katakana read as hiragana), もり.
+        let authors = [
+            (
+                "93090e87-b7a1-403c-974c-d74d881e83b9",
+                "森鴎外",
+                "もりおうがい",
+            ),
+            ("e324be11-5b77-4ba6-8423-9f27e2d228f1", "ナツメ", ""),
+            (
+                "278935cf-ed83-4346-9b35-b84bbdb630c0",
+                "芥川龍之介",
+                "あくたがわりゅうのすけ",
+            ),
+            ("925aaf96-64c7-44be-85f8-767a20b2c20c", "いのうえやすし", ""),
+        ]
+        .into_iter()
+        .map(|(id, name, yomi)| {
+            Author::new_with_yomi(
+                AuthorId::try_from(id)?,
+                AuthorName::new(name.to_string())?,
+                yomi.to_string(),
+                OffsetDateTime::UNIX_EPOCH,
+            )
+        })
+        .collect::<Result<Vec<_>, DomainError>>()?;
+        for author in &authors {
+            create_author(&pool, &author_repository, &user_id, author).await?;
+        }
+        let expected = vec![
+            authors[2].clone(),
+            authors[3].clone(),
+            authors[1].clone(),
+            authors[0].clone(),
+        ];
+        let by_yomi = AuthorOrder {
+            key: AuthorSortKey::Yomi,
+            direction: SortDirection::Asc,
+        };
+
+        let all = author_repository.find_all(&user_id, &by_yomi).await?;
+        assert_eq!(all, expected);
+
+        let page = author_repository
+            .find_page(&user_id, &by_yomi, &PageRequest::forward(2))
+            .await?;
+        assert_eq!(page.nodes().cloned().collect::<Vec<_>>(), expected[..2]);
+        let after = page.edges[1].cursor.clone();
+        assert_eq!(
+            after.value,
+            AuthorSortValue::Yomi("いのうえやすし".to_string())
+        );
+
+        let page = author_repository
+            .find_page(
+                &user_id,
+                &by_yomi,
+                &PageRequest {
+                    after: Some(after),
+                    ..PageRequest::forward(2)
+                },
+            )
+            .await?;
+        assert_eq!(page.nodes().cloned().collect::<Vec<_>>(), expected[2..]);
+        assert!(!page.has_next_page);
+
+        let descending = author_repository
+            .find_all(
+                &user_id,
+                &AuthorOrder {
+                    direction: SortDirection::Desc,
+                    ..by_yomi
+                },
+            )
+            .await?;
+        assert_eq!(descending, expected.into_iter().rev().collect::<Vec<_>>());
+
+        Ok(())
+    }
+
+    #[sqlx::test]
+    async fn yomi_order_compares_voiced_and_half_width_kana_by_code_point(
+        pool: PgPool,
+    ) -> anyhow::Result<()> {
+        let user_repository = PgUserRepository::new(pool.clone());
+        let author_repository = PgAuthorRepository::new(pool.clone());
+
+        let user_id = prepare_user(&user_repository, "user1").await?;
+        // Sorted as かわ, がく (read from half-width ｶﾞｸ), がくせい, きく: a
+        // voiced kana sorts right after its plain one rather than with it.
+        let authors = [
+            ("b4c5e0a6-2f51-4c1e-9d7a-3e8f1a2b4c01", "菊", "きく"),
+            ("b4c5e0a6-2f51-4c1e-9d7a-3e8f1a2b4c02", "学生", "がくせい"),
+            ("b4c5e0a6-2f51-4c1e-9d7a-3e8f1a2b4c03", "川", "かわ"),
+            ("b4c5e0a6-2f51-4c1e-9d7a-3e8f1a2b4c04", "学", "ｶﾞｸ"),
+        ]
+        .into_iter()
+        .map(|(id, name, yomi)| {
+            Author::new_with_yomi(
+                AuthorId::try_from(id)?,
+                AuthorName::new(name.to_string())?,
+                yomi.to_string(),
+                OffsetDateTime::UNIX_EPOCH,
+            )
+        })
+        .collect::<Result<Vec<_>, DomainError>>()?;
+        for author in &authors {
+            create_author(&pool, &author_repository, &user_id, author).await?;
+        }
+
+        let all = author_repository
+            .find_all(
+                &user_id,
+                &AuthorOrder {
+                    key: AuthorSortKey::Yomi,
+                    direction: SortDirection::Asc,
+                },
+            )
+            .await?;
+
+        let names: Vec<&str> = all.iter().map(|author| author.name().as_str()).collect();
+        assert_eq!(names, ["川", "学", "学生", "菊"]);
+        Ok(())
+    }
+
+    #[sqlx::test]
+    async fn search_matches_name_and_yomi(pool: PgPool) -> anyhow::Result<()> {
+        let user_repository = PgUserRepository::new(pool.clone());
+        let author_repository = PgAuthorRepository::new(pool.clone());
+
+        let user_id = prepare_user(&user_repository, "user1").await?;
+        let other_user_id = prepare_user(&user_repository, "user2").await?;
+        let authors = [
+            (
+                "93090e87-b7a1-403c-974c-d74d881e83b9",
+                "夏目漱石",
+                "なつめそうせき",
+            ),
+            ("e324be11-5b77-4ba6-8423-9f27e2d228f1", "ナツメ", ""),
+            (
+                "278935cf-ed83-4346-9b35-b84bbdb630c0",
+                "Ｊ．Ｋ．ローリング",
+                "",
+            ),
+        ]
+        .into_iter()
+        .map(|(id, name, yomi)| {
+            Author::new_with_yomi(
+                AuthorId::try_from(id)?,
+                AuthorName::new(name.to_string())?,
+                yomi.to_string(),
+                OffsetDateTime::UNIX_EPOCH,
+            )
+        })
+        .collect::<Result<Vec<_>, DomainError>>()?;
+        for author in &authors {
+            create_author(&pool, &author_repository, &user_id, author).await?;
+        }
+        let other_author = new_author(
+            AuthorId::try_from("925aaf96-64c7-44be-85f8-767a20b2c20c")?,
+            AuthorName::new("夏目".to_string())?,
+        )?;
+        create_author(&pool, &author_repository, &other_user_id, &other_author).await?;
+
+        let cases = vec![
+            // Matches yomi and a katakana name alike; the closer match ranks first.
+            ("なつめ", vec![&authors[1], &authors[0]]),
+            ("夏目", vec![&authors[0]]),
+            ("j.k.", vec![&authors[2]]),
+            ("ﾛｰﾘﾝｸﾞ", vec![&authors[2]]),
+            ("なつめ 漱石", vec![&authors[0]]),
+        ];
+        for (raw, expected) in cases {
+            let actual = author_repository
+                .search(&user_id, &SearchQuery::new(raw)?, 10)
+                .await?;
+            let expected = expected.into_iter().cloned().collect::<Vec<_>>();
+            assert_eq!(actual, expected, "search for {raw:?}");
+        }
+
+        Ok(())
+    }
+
+    #[sqlx::test]
+    async fn find_page_rejects_cursor_of_another_order(pool: PgPool) -> anyhow::Result<()> {
+        let user_repository = PgUserRepository::new(pool.clone());
+        let author_repository = PgAuthorRepository::new(pool.clone());
+        let user_id = prepare_user(&user_repository, "user1").await?;
+
+        let result = author_repository
+            .find_page(
+                &user_id,
+                &AuthorOrder {
+                    key: AuthorSortKey::Yomi,
+                    direction: SortDirection::Asc,
+                },
+                &PageRequest {
+                    after: Some(AuthorCursor {
+                        value: AuthorSortValue::Name("author1".to_string()),
+                        id: AuthorId::try_from("e324be11-5b77-4ba6-8423-9f27e2d228f1")?,
+                    }),
+                    ..PageRequest::forward(2)
+                },
+            )
+            .await;
+        assert!(matches!(result, Err(DomainError::Validation(_))));
+
+        Ok(())
+    }
+
     #[sqlx::test]
     async fn find_all_does_not_return_other_users_authors(pool: PgPool) -> anyhow::Result<()> {
         let user_repository = PgUserRepository::new(pool.clone());
@@ -784,7 +1375,9 @@ mod tests {
         let author = new_author(author_id, AuthorName::new("author1".to_string())?)?;
         create_author(&pool, &author_repository, &user1_id, &author).await?;
 
-        let result = author_repository.find_all(&user2_id).await?;
+        let result = author_repository
+            .find_all(&user2_id, &AuthorOrder::default())
+            .await?;
         assert_eq!(result.len(), 0);
 
         Ok(())
@@ -870,6 +1463,56 @@ mod tests {
         Ok(())
     }
 
+    #[sqlx::test]
+    async fn lock_with_book_ids_lists_the_books_of_existing_authors(
+        pool: PgPool,
+    ) -> anyhow::Result<()> {
+        let user_repository = PgUserRepository::new(pool.clone());
+        let author_repository = PgAuthorRepository::new(pool.clone());
+        let book_repository = PgBookRepository::new(pool.clone());
+
+        let user_id = prepare_user(&user_repository, "user1").await?;
+        let author_with_book_id = AuthorId::try_from("3a4bf9a4-1f4b-4f0d-9d2c-4f6f4f3b1a01")?;
+        let author_without_book_id = AuthorId::try_from("7c1d2e3f-5a6b-4c7d-8e9f-0a1b2c3d4e5f")?;
+        let missing_author_id = AuthorId::try_from("e324be11-5b77-4ba6-8423-9f27e2d228f1")?;
+        for (author_id, name) in [
+            (&author_with_book_id, "author1"),
+            (&author_without_book_id, "author2"),
+        ] {
+            let author = new_author(author_id.clone(), AuthorName::new(name.to_string())?)?;
+            create_author(&pool, &author_repository, &user_id, &author).await?;
+        }
+        let book = make_book(
+            "675bc8d9-3155-42fb-87b0-0a82cb162848",
+            std::slice::from_ref(&author_with_book_id),
+        )?;
+        create_book(&pool, &book_repository, &user_id, &book).await?;
+
+        let tm = PgTransactionManager::new(pool.clone());
+        let mut tx = tm.begin(&user_id, EventSetOperation::DeleteAuthors).await?;
+        let locked = author_repository
+            .lock_with_book_ids(
+                &mut tx,
+                &user_id,
+                &[
+                    author_without_book_id.clone(),
+                    missing_author_id,
+                    author_with_book_id.clone(),
+                ],
+            )
+            .await?;
+
+        assert_eq!(
+            locked,
+            vec![
+                (author_with_book_id, vec![book.id().clone()]),
+                (author_without_book_id, vec![]),
+            ]
+        );
+
+        Ok(())
+    }
+
     #[sqlx::test]
     async fn delete_fails_when_author_has_associated_books(pool: PgPool) -> anyhow::Result<()> {
         let user_repository = PgUserRepository::new(pool.clone());
@@ -1203,15 +1846,18 @@ mod tests {
         let mut tx = tm.begin(&user_id, EventSetOperation::ImportBooks).await?;
         let name = AuthorName::new("New Author".to_owned())?;
         let created_at = OffsetDateTime::from_unix_timestamp(1_700_000_000)?;
-        let author_id = author_repository
-            .find_or_create_by_name(&mut tx, &name, created_at)
+        let resolved = author_repository
+            .find_or_create_by_name(&mut tx, &name, "にゅー", created_at)
             .await?;
         tm.commit(tx).await?;
+        assert!(resolved.created);
+        let author_id = resolved.id;
 
-        // The author row exists with the returned id and name
+        // The author row exists with the returned id, name and yomi
         let found = author_repository.find_by_id(&user_id, &author_id).await?;
         let found = found.expect("new author exists");
         assert_eq!(found.name().as_str(), "New Author");
+        assert_eq!(found.yomi(), "にゅー");
         assert_eq!(found.created_at(), &created_at);
         assert_eq!(found.updated_at(), &created_at);
 
@@ -1255,11 +1901,17 @@ mod tests {
         let name = AuthorName::new("Existing".to_owned())?;
         let attempted_created_at = OffsetDateTime::from_unix_timestamp(1_700_000_000)?;
         let resolved = author_repository
-            .find_or_create_by_name(&mut tx, &name, attempted_created_at)
+            .find_or_create_by_name(&mut tx, &name, "べつ", attempted_created_at)
             .await?;
         tm.commit(tx).await?;
 
-        assert_eq!(resolved, existing_id);
+        assert_eq!(
+            resolved,
+            ResolvedAuthor {
+                id: existing_id.clone(),
+                created: false,
+            }
+        );
 
         let (events_after,): (i64,) =
             sqlx::query_as("SELECT COUNT(*) FROM author_event WHERE user_id = $1")
@@ -1272,6 +1924,7 @@ mod tests {
             .find_by_id(&user_id, &existing_id)
             .await?
             .expect("existing author remains");
+        assert_eq!(found.yomi(), "");
         assert_eq!(found.created_at(), &OffsetDateTime::UNIX_EPOCH);
         assert_eq!(found.updated_at(), &OffsetDateTime::UNIX_EPOCH);
 
@@ -1,6 +1,8 @@
+use std::collections::HashMap;
+
 use async_trait::async_trait;
 use serde_json::Value;
-use sqlx::PgPool;
+use sqlx::{PgPool, QueryBuilder};
 use time::OffsetDateTime;
 use uuid::Uuid;
 
@@ -9,13 +11,22 @@ use crate::{
     domain::{
         entity::{
             author::AuthorId,
-            book::{BookId, BookTitle, Isbn, OwnedFlag, Priority, ReadFlag},
+            book::{Book, BookId, BookTitle, Isbn, OwnedFlag, Priority, ReadFlag},
             event::{BookEvent, EventOperation},
             event_set::EventSetId,
             user::UserId,
         },
         error::DomainError,
-        repository::book_event_repository::BookEventRepository,
+        repository::{
+            activity::{ActivityPeriod, ActivityRange},
+            book_event_repository::BookEventRepository,
+            history::{DeletedEntity, EventCursor, HistoryFilter},
+            pagination::{Edge, Page, PageRequest},
+        },
+    },
+    infrastructure::{
+        book_repository::{BookRow, book_from_row},
+        history::{push_history_filter, push_history_window},
     },
 };
 
@@ -82,6 +93,63 @@ fn row_to_book_event(row: BookEventRow) -> Result<BookEvent, DomainError> {
     })
 }
 
+#[derive(sqlx::FromRow)]
+struct AdjacentBookEventRow {
+    anchor_event_id: i64,
+    #[sqlx(flatten)]
+    event: BookEventRow,
+}
+
+// A book's latest stateful event alongside the event that deleted it. The
+// deleting event carries no state, so only its metadata is selected.
+#[derive(sqlx::FromRow)]
+struct DeletedBookEventRow {
+    deletion_event_id: i64,
+    deletion_event_set_id: Uuid,
+    deletion_operation: String,
+    deletion_changed_at: OffsetDateTime,
+    deletion_extra: Option<Value>,
+    #[sqlx(flatten)]
+    event: BookEventRow,
+}
+
+fn row_to_deleted_book(row: DeletedBookEventRow) -> Result<DeletedEntity<BookEvent>, DomainError> {
+    let operation = EventOperation::try_from(row.deletion_operation.as_str())
+        .map_err(DomainError::Unexpected)?;
+    let last_known = row_to_book_event(row.event)?;
+    let deletion = BookEvent {
+        event_id: row.deletion_event_id,
+        event_set_id: EventSetId::from(row.deletion_event_set_id),
+        operation,
+        book_id: last_known.book_id.clone(),
+        title: None,
+        author_ids: Vec::new(),
+        isbn: None,
+        read: None,
+        owned: None,
+        priority: None,
+        format: None,
+        store: None,
+        book_created_at: None,
+        book_updated_at: None,
+        changed_at: row.deletion_changed_at,
+        extra: row.deletion_extra,
+    };
+
+    Ok(DeletedEntity {
+        deletion,
+        last_known,
+    })
+}
+
+#[derive(sqlx::FromRow)]
+struct ActivityPeriodRow {
+    start: OffsetDateTime,
+    finished_count: i64,
+    added_count: i64,
+    deleted_count: i64,
+}
+
 #[derive(Debug, Clone)]
 pub struct PgBookEventRepository {
     pool: PgPool,
@@ -122,7 +190,7 @@ impl BookEventRepository for PgBookEventRepository {
             LEFT JOIN book_event_author bea ON be.event_id = bea.event_id
             WHERE be.user_id = $1 AND be.book_id = $2
             GROUP BY be.event_id
-            ORDER BY be.changed_at DESC",
+            ORDER BY be.changed_at DESC, be.event_id DESC",
         )
         .bind(user_id.as_str())
         .bind(book_id.to_uuid())
@@ -132,6 +200,70 @@ impl BookEventRepository for PgBookEventRepository {
         rows.into_iter().map(row_to_book_event).collect()
     }
 
+    async fn find_page_by_book(
+        &self,
+        user_id: &UserId,
+        book_id: &BookId,
+        filter: &HistoryFilter<EventOperation>,
+        page: &PageRequest<EventCursor>,
+    ) -> Result<Page<BookEvent, EventCursor>, DomainError> {
+        let mut count = QueryBuilder::new("SELECT count(*) FROM book_event WHERE user_id = ");
+        count
+            .push_bind(user_id.as_str().to_owned())
+            .push(" AND book_id = ")
+            .push_bind(book_id.to_uuid());
+        push_history_filter(&mut count, "changed_at", filter, EventOperation::as_str);
+        let (total_count,): (i64,) = count.build_query_as().fetch_one(&self.pool).await?;
+
+        let mut query = QueryBuilder::new(
+            "SELECT
+                event_id,
+                event_set_id,
+                operation,
+                book_id,
+                title,
+                isbn,
+                read,
+                owned,
+                priority,
+                format,
+                store,
+                book_created_at,
+                book_updated_at,
+                changed_at,
+                ARRAY(
+                    SELECT bea.author_id FROM book_event_author bea
+                    WHERE bea.event_id = book_event.event_id
+                    ORDER BY bea.author_id
+                ) AS author_ids,
+                extra
+            FROM book_event
+            WHERE user_id = ",
+        );
+        query
+            .push_bind(user_id.as_str().to_owned())
+            .push(" AND book_id = ")
+            .push_bind(book_id.to_uuid());
+        push_history_filter(&mut query, "changed_at", filter, EventOperation::as_str);
+        push_history_window(&mut query, "changed_at", "event_id", page, |id| *id);
+        let rows: Vec<BookEventRow> = query.build_query_as().fetch_all(&self.pool).await?;
+        let edges = rows
+            .into_iter()
+            .map(|row| {
+                let event = row_to_book_event(row)?;
+                Ok(Edge {
+                    cursor: EventCursor {
+                        at: event.changed_at,
+                        id: event.event_id,
+                    },
+                    node: event,
+                })
+            })
+            .collect::<Result<Vec<_>, DomainError>>()?;
+
+        Ok(Page::from_rows(edges, page, total_count))
+    }
+
     async fn find_by_event_id(
         &self,
         user_id: &UserId,
@@ -204,6 +336,270 @@ impl BookEventRepository for PgBookEventRepository {
 
         rows.into_iter().map(row_to_book_event).collect()
     }
+
+    async fn find_previous(
+        &self,
+        user_id: &UserId,
+        event_ids: &[i64],
+    ) -> Result<HashMap<i64, BookEvent>, DomainError> {
+        let rows: Vec<AdjacentBookEventRow> = sqlx::query_as(
+            "SELECT
+                cur.event_id AS anchor_event_id,
+                be.event_id,
+                be.event_set_id,
+                be.operation,
+                be.book_id,
+                be.title,
+                be.isbn,
+                be.read,
+                be.owned,
+                be.priority,
+                be.format,
+                be.store,
+                be.book_created_at,
+                be.book_updated_at,
+                be.changed_at,
+                ARRAY(
+                    SELECT author_id FROM book_event_author
+                    WHERE event_id = be.event_id
+                    ORDER BY author_id
+                ) AS author_ids,
+                be.extra
+            FROM book_event cur
+            CROSS JOIN LATERAL (
+                SELECT * FROM book_event prev
+                WHERE prev.user_id = cur.user_id
+                  AND prev.book_id = cur.book_id
+                  AND (prev.changed_at, prev.event_id) < (cur.changed_at, cur.event_id)
+                ORDER BY prev.changed_at DESC, prev.event_id DESC
+                LIMIT 1
+            ) be
+            WHERE cur.user_id = $1 AND cur.event_id = ANY($2)",
+        )
+        .bind(user_id.as_str())
+        .bind(event_ids)
+        .fetch_all(&self.pool)
+        .await?;
+
+        rows.into_iter()
+            .map(|row| Ok((row.anchor_event_id, row_to_book_event(row.event)?)))
+            .collect()
+    }
+
+    async fn find_next(
+        &self,
+        user_id: &UserId,
+        event_ids: &[i64],
+    ) -> Result<HashMap<i64, BookEvent>, DomainError> {
+        let rows: Vec<AdjacentBookEventRow> = sqlx::query_as(
+            "SELECT
+                cur.event_id AS anchor_event_id,
+                be.event_id,
+                be.event_set_id,
+                be.operation,
+                be.book_id,
+                be.title,
+                be.isbn,
+                be.read,
+                be.owned,
+                be.priority,
+                be.format,
+                be.store,
+                be.book_created_at,
+                be.book_updated_at,
+                be.changed_at,
+                ARRAY(
+                    SELECT author_id FROM book_event_author
+                    WHERE event_id = be.event_id
+                    ORDER BY author_id
+                ) AS author_ids,
+                be.extra
+            FROM book_event cur
+            CROSS JOIN LATERAL (
+                SELECT * FROM book_event later
+                WHERE later.user_id = cur.user_id
+                  AND later.book_id = cur.book_id
+                  AND (later.changed_at, later.event_id) > (cur.changed_at, cur.event_id)
+                ORDER BY later.changed_at, later.event_id
+                LIMIT 1
+            ) be
+            WHERE cur.user_id = $1 AND cur.event_id = ANY($2)",
+        )
+        .bind(user_id.as_str())
+        .bind(event_ids)
+        .fetch_all(&self.pool)
+        .await?;
+
+        rows.into_iter()
+            .map(|row| Ok((row.anchor_event_id, row_to_book_event(row.event)?)))
+            .collect()
+    }
+
+    async fn reading_activity(
+        &self,
+        user_id: &UserId,
+        range: &ActivityRange,
+    ) -> Result<Vec<ActivityPeriod>, DomainError> {
+        // Periods are computed on local wall-clock time (`changed_at` shifted
+        // by the offset) and shifted back for the result. A book is finished
+        // by an update that marks it read: the read state before it comes
+        // from the book's previous event, which may lie before the range.
+        let rows: Vec<ActivityPeriodRow> = sqlx::query_as(
+            "WITH periods AS (
+                SELECT generate_series(
+                    date_trunc($4, ($2 AT TIME ZONE 'UTC') + make_interval(mins => $5)),
+                    ($3 AT TIME ZONE 'UTC') + make_interval(mins => $5)
+                        - interval '1 microsecond',
+                    ('1 ' || $4)::interval
+                ) AS local_start
+            ),
+            transitions AS (
+                SELECT
+                    changed_at,
+                    operation,
+                    read,
+                    lag(read) OVER (PARTITION BY book_id ORDER BY changed_at, event_id)
+                        AS previous_read
+                FROM book_event
+                WHERE user_id = $1 AND changed_at < $3
+            ),
+            counted AS (
+                SELECT
+                    date_trunc($4, (changed_at AT TIME ZONE 'UTC') + make_interval(mins => $5))
+                        AS local_start,
+                    operation = 'update'
+                        AND read
+                        AND previous_read IS DISTINCT FROM true AS finished,
+                    operation = 'create' AS added,
+                    operation = 'delete' AS deleted
+                FROM transitions
+                WHERE changed_at >= $2
+            )
+            SELECT
+                (p.local_start - make_interval(mins => $5)) AT TIME ZONE 'UTC' AS start,
+                count(*) FILTER (WHERE c.finished) AS finished_count,
+                count(*) FILTER (WHERE c.added) AS added_count,
+                count(*) FILTER (WHERE c.deleted) AS deleted_count
+            FROM periods p
+            LEFT JOIN counted c ON c.local_start = p.local_start
+            GROUP BY p.local_start
+            ORDER BY p.local_start",
+        )
+        .bind(user_id.as_str())
+        .bind(range.from())
+        .bind(range.to())
+        .bind(range.granularity().as_str())
+        .bind(range.utc_offset_minutes())
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| ActivityPeriod {
+                start: row.start,
+                finished_count: row.finished_count,
+                added_count: row.added_count,
+                deleted_count: row.deleted_count,
+            })
+            .collect())
+    }
+
+    async fn find_books_as_of(
+        &self,
+        user_id: &UserId,
+        at: OffsetDateTime,
+    ) -> Result<Vec<Book>, DomainError> {
+        // Delete events, and restores of a deletion, leave the title null.
+        let rows: Vec<BookRow> = sqlx::query_as(
+            "WITH latest AS (
+                SELECT DISTINCT ON (book_id) *
+                FROM book_event
+                WHERE user_id = $1 AND changed_at <= $2
+                ORDER BY book_id, changed_at DESC, event_id DESC
+            )
+            SELECT
+                l.book_id AS id,
+                l.title,
+                array_agg(bea.author_id ORDER BY bea.author_id)
+                    FILTER (WHERE bea.author_id IS NOT NULL) AS author_ids,
+                l.isbn,
+                l.read,
+                l.owned,
+                l.priority,
+                l.format,
+                l.store,
+                l.book_created_at AS created_at,
+                l.book_updated_at AS updated_at
+            FROM latest l
+            LEFT JOIN book_event_author bea ON bea.event_id = l.event_id
+            WHERE l.title IS NOT NULL
+            GROUP BY l.event_id, l.book_id, l.title, l.isbn, l.read, l.owned, l.priority,
+                     l.format, l.store, l.book_created_at, l.book_updated_at
+            ORDER BY l.book_created_at, l.book_id",
+        )
+        .bind(user_id.as_str())
+        .bind(at)
+        .fetch_all(&self.pool)
+        .await?;
+
+        rows.into_iter().map(book_from_row).collect()
+    }
+
+    async fn find_deleted(
+        &self,
+        user_id: &UserId,
+    ) -> Result<Vec<DeletedEntity<BookEvent>>, DomainError> {
+        let rows: Vec<DeletedBookEventRow> = sqlx::query_as(
+            "WITH latest AS (
+                SELECT DISTINCT ON (book_id) *
+                FROM book_event
+                WHERE user_id = $1
+                ORDER BY book_id, changed_at DESC, event_id DESC
+            )
+            SELECT
+                d.event_id AS deletion_event_id,
+                d.event_set_id AS deletion_event_set_id,
+                d.operation AS deletion_operation,
+                d.changed_at AS deletion_changed_at,
+                d.extra AS deletion_extra,
+                be.event_id,
+                be.event_set_id,
+                be.operation,
+                be.book_id,
+                be.title,
+                be.isbn,
+                be.read,
+                be.owned,
+                be.priority,
+                be.format,
+                be.store,
+                be.book_created_at,
+                be.book_updated_at,
+                be.changed_at,
+                ARRAY(
+                    SELECT author_id FROM book_event_author
+                    WHERE event_id = be.event_id
+                    ORDER BY author_id
+                ) AS author_ids,
+                be.extra
+            FROM latest d
+            CROSS JOIN LATERAL (
+                SELECT * FROM book_event known
+                WHERE known.user_id = d.user_id
+                  AND known.book_id = d.book_id
+                  AND known.title IS NOT NULL
+                ORDER BY known.changed_at DESC, known.event_id DESC
+                LIMIT 1
+            ) be
+            WHERE d.title IS NULL
+            ORDER BY d.changed_at DESC, d.event_id DESC",
+        )
+        .bind(user_id.as_str())
+        .fetch_all(&self.pool)
+        .await?;
+
+        rows.into_iter().map(row_to_deleted_book).collect()
+    }
 }
 
 #[cfg(feature = "test-with-database")]
@@ -222,8 +618,8 @@ mod tests {
             error::DomainError,
             repository::{
                 author_repository::AuthorRepository, book_event_repository::BookEventRepository,
-                book_repository::BookRepository, transaction::TransactionManager,
-                user_repository::UserRepository,
+                book_repository::BookRepository, pagination::PageDirection,
+                transaction::TransactionManager, user_repository::UserRepository,
             },
         },
         infrastructure::{
@@ -287,6 +683,15 @@ mod tests {
         book_id_str: &str,
         title: &str,
         author_ids: &[AuthorId],
+    ) -> Result<Book, DomainError> {
+        make_book_with_read(book_id_str, title, author_ids, false)
+    }
+
+    fn make_book_with_read(
+        book_id_str: &str,
+        title: &str,
+        author_ids: &[AuthorId],
+        read: bool,
     ) -> Result<Book, DomainError> {
         let created_at = PrimitiveDateTime::new(date!(2022 - 05 - 05), time!(0:00)).assume_utc();
         Book::new(
@@ -294,7 +699,7 @@ mod tests {
             BookTitle::new(title.to_owned())?,
             author_ids.to_vec(),
             Isbn::new("1111111111116".to_owned())?,
-            ReadFlag::new(false),
+            ReadFlag::new(read),
             OwnedFlag::new(false),
             Priority::new(50)?,
             BookFormat::EBook,
@@ -350,6 +755,81 @@ mod tests {
         Ok(())
     }
 
+    #[sqlx::test]
+    async fn find_page_by_book_filters_and_pages_backward(pool: PgPool) -> anyhow::Result<()> {
+        let user_repo = PgUserRepository::new(pool.clone());
+        let author_repo = PgAuthorRepository::new(pool.clone());
+        let book_repo = PgBookRepository::new(pool.clone());
+        let event_repo = PgBookEventRepository::new(pool.clone());
+
+        let user_id = prepare_user(&user_repo, "user1").await?;
+        let author_id = AuthorId::try_from("278935cf-ed83-4346-9b35-b84bbdb630c0")?;
+        create_author(
+            &pool,
+            &author_repo,
+            &user_id,
+            &Author::new(
+                author_id.clone(),
+                AuthorName::new("author1".to_owned())?,
+                OffsetDateTime::UNIX_EPOCH,
+            )?,
+        )
+        .await?;
+        let book_id = "675bc8d9-3155-42fb-87b0-0a82cb162848";
+        let authors = std::slice::from_ref(&author_id);
+        create_book(
+            &pool,
+            &book_repo,
+            &user_id,
+            &make_book(book_id, "v1", authors)?,
+        )
+        .await?;
+        for title in ["v2", "v3"] {
+            let book = make_book(book_id, title, authors)?;
+            update_book(&pool, &book_repo, &user_id, &book).await?;
+        }
+        let book_id = BookId::try_from(book_id)?;
+        let updates = HistoryFilter {
+            operations: Some(vec![EventOperation::Update]),
+            ..HistoryFilter::default()
+        };
+
+        let page = event_repo
+            .find_page_by_book(&user_id, &book_id, &updates, &PageRequest::forward(10))
+            .await?;
+        assert_eq!(page.total_count, 2);
+        let titles: Vec<_> = page
+            .nodes()
+            .map(|e| e.title.as_ref().unwrap().as_str())
+            .collect();
+        assert_eq!(titles, vec!["v3", "v2"]);
+        assert_eq!(page.edges[0].node.author_ids, vec![author_id]);
+
+        // Paging backward from the newest event returns the one before it.
+        let backward = event_repo
+            .find_page_by_book(
+                &user_id,
+                &book_id,
+                &HistoryFilter::default(),
+                &PageRequest {
+                    after: None,
+                    before: Some(page.edges[1].cursor.clone()),
+                    limit: 1,
+                    direction: PageDirection::Backward,
+                },
+            )
+            .await?;
+        assert_eq!(backward.total_count, 3);
+        assert!(backward.has_next_page);
+        assert!(!backward.has_previous_page);
+        assert_eq!(
+            backward.edges[0].node.title.as_ref().unwrap().as_str(),
+            "v3"
+        );
+
+        Ok(())
+    }
+
     #[sqlx::test]
     async fn find_by_book_aggregates_multiple_author_ids(pool: PgPool) -> anyhow::Result<()> {
         let user_repo = PgUserRepository::new(pool.clone());
@@ -557,4 +1037,371 @@ mod tests {
 
         Ok(())
     }
+
+    #[sqlx::test]
+    async fn find_previous_and_next_link_neighbouring_events(pool: PgPool) -> anyhow::Result<()> {
+        let user_repo = PgUserRepository::new(pool.clone());
+        let author_repo = PgAuthorRepository::new(pool.clone());
+        let book_repo = PgBookRepository::new(pool.clone());
+        let event_repo = PgBookEventRepository::new(pool.clone());
+
+        let user_id = prepare_user(&user_repo, "user1").await?;
+        let author_id1 = AuthorId::try_from("278935cf-ed83-4346-9b35-b84bbdb630c0")?;
+        let author_id2 = AuthorId::try_from("925aaf96-64c7-44be-85f8-767a20b2c20c")?;
+        for (author_id, name) in [(&author_id1, "a1"), (&author_id2, "a2")] {
+            create_author(
+                &pool,
+                &author_repo,
+                &user_id,
+                &Author::new(
+                    author_id.clone(),
+                    AuthorName::new(name.to_owned())?,
+                    OffsetDateTime::UNIX_EPOCH,
+                )?,
+            )
+            .await?;
+        }
+
+        let book_id = "675bc8d9-3155-42fb-87b0-0a82cb162848";
+        let book = make_book(book_id, "original", std::slice::from_ref(&author_id1))?;
+        create_book(&pool, &book_repo, &user_id, &book).await?;
+        let updated = make_book(book_id, "updated", std::slice::from_ref(&author_id2))?;
+        update_book(&pool, &book_repo, &user_id, &updated).await?;
+
+        let entries = event_repo.find_by_book(&user_id, book.id()).await?;
+        let (update_id, create_id) = (entries[0].event_id, entries[1].event_id);
+
+        let previous = event_repo
+            .find_previous(&user_id, &[update_id, create_id])
+            .await?;
+        assert_eq!(previous.len(), 1);
+        let before_update = &previous[&update_id];
+        assert_eq!(before_update.event_id, create_id);
+        assert_eq!(before_update.title.as_ref().unwrap().as_str(), "original");
+        assert_eq!(before_update.author_ids, vec![author_id1]);
+
+        let next = event_repo
+            .find_next(&user_id, &[update_id, create_id])
+            .await?;
+        assert_eq!(next.len(), 1);
+        let after_create = &next[&create_id];
+        assert_eq!(after_create.event_id, update_id);
+        assert_eq!(after_create.title.as_ref().unwrap().as_str(), "updated");
+        assert_eq!(after_create.author_ids, vec![author_id2]);
+
+        let other_user = prepare_user(&user_repo, "user2").await?;
+        assert!(
+            event_repo
+                .find_previous(&other_user, &[update_id])
+                .await?
+                .is_empty()
+        );
+        assert!(
+            event_repo
+                .find_next(&other_user, &[create_id])
+                .await?
+                .is_empty()
+        );
+
+        Ok(())
+    }
+
+    #[sqlx::test]
+    async fn reading_activity_counts_transitions_per_local_period(
+        pool: PgPool,
+    ) -> anyhow::Result<()> {
+        use time::macros::datetime;
+
+        use crate::{
+            common::activity::ActivityGranularity, domain::repository::activity::ActivityRange,
+        };
+
+        let user_repo = PgUserRepository::new(pool.clone());
+        let book_repo = PgBookRepository::new(pool.clone());
+        let event_repo = PgBookEventRepository::new(pool.clone());
+        let user_id = prepare_user(&user_repo, "user1").await?;
+
+        // Created unread in February, finished and then renamed in March.
+        let finished_id = "675bc8d9-3155-42fb-87b0-0a82cb162848";
+        create_book(
+            &pool,
+            &book_repo,
+            &user_id,
+            &make_book(finished_id, "finished", &[])?,
+        )
+        .await?;
+        sqlx::query("UPDATE book_event SET changed_at = '2026-02-01 00:00+00' WHERE book_id = $1")
+            .bind(Uuid::parse_str(finished_id)?)
+            .execute(&pool)
+            .await?;
+        let finished = make_book_with_read(finished_id, "finished", &[], true)?;
+        update_book(&pool, &book_repo, &user_id, &finished).await?;
+        let renamed = make_book_with_read(finished_id, "renamed", &[], true)?;
+        update_book(&pool, &book_repo, &user_id, &renamed).await?;
+        // Created as already read, which finishes nothing.
+        let read = make_book_with_read("c5a81e57-bc91-40ff-8b57-18cfa7cc7ae8", "read", &[], true)?;
+        create_book(&pool, &book_repo, &user_id, &read).await?;
+        // Created and deleted.
+        let deleted_id = BookId::try_from("99a1b2c3-d4e5-4f60-8a7b-1c2d3e4f5a6b")?;
+        create_book(
+            &pool,
+            &book_repo,
+            &user_id,
+            &make_book("99a1b2c3-d4e5-4f60-8a7b-1c2d3e4f5a6b", "deleted", &[])?,
+        )
+        .await?;
+        let tm = PgTransactionManager::new(pool.clone());
+        let mut tx = tm.begin(&user_id, EventSetOperation::DeleteBook).await?;
+        book_repo.delete(&mut tx, &deleted_id).await?;
+        tm.commit(tx).await?;
+        sqlx::query(
+            "UPDATE book_event SET changed_at = '2026-03-01 20:00+00'
+             WHERE changed_at > '2026-02-01 00:00+00'",
+        )
+        .execute(&pool)
+        .await?;
+
+        // 20:00 UTC on March 1st is 05:00 on March 2nd at UTC+9.
+        let range = ActivityRange::new(
+            datetime!(2026-03-01 00:00 UTC),
+            datetime!(2026-03-03 00:00 UTC),
+            ActivityGranularity::Day,
+            540,
+        )?;
+        let periods = event_repo.reading_activity(&user_id, &range).await?;
+        let summary: Vec<_> = periods
+            .iter()
+            .map(|p| (p.start, p.finished_count, p.added_count, p.deleted_count))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                (datetime!(2026-02-28 15:00 UTC), 0, 0, 0),
+                (datetime!(2026-03-01 15:00 UTC), 1, 2, 1),
+                (datetime!(2026-03-02 15:00 UTC), 0, 0, 0),
+            ]
+        );
+
+        let range = ActivityRange::new(
+            datetime!(2026-01-15 00:00 UTC),
+            datetime!(2026-03-15 00:00 UTC),
+            ActivityGranularity::Month,
+            0,
+        )?;
+        let periods = event_repo.reading_activity(&user_id, &range).await?;
+        let summary: Vec<_> = periods
+            .iter()
+            .map(|p| (p.start, p.finished_count, p.added_count, p.deleted_count))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                (datetime!(2026-01-01 00:00 UTC), 0, 0, 0),
+                (datetime!(2026-02-01 00:00 UTC), 0, 1, 0),
+                (datetime!(2026-03-01 00:00 UTC), 1, 2, 1),
+            ]
+        );
+
+        Ok(())
+    }
+
+    #[sqlx::test]
+    async fn reading_activity_leaves_out_restores_and_imports_of_read_books(
+        pool: PgPool,
+    ) -> anyhow::Result<()> {
+        use time::macros::datetime;
+
+        use crate::{
+            common::activity::ActivityGranularity, domain::repository::activity::ActivityRange,
+        };
+
+        let user_repo = PgUserRepository::new(pool.clone());
+        let book_repo = PgBookRepository::new(pool.clone());
+        let event_repo = PgBookEventRepository::new(pool.clone());
+        let tm = PgTransactionManager::new(pool.clone());
+        let user_id = prepare_user(&user_repo, "user1").await?;
+
+        // Finished in February, then deleted, restored and updated by an
+        // import in March, read all along.
+        let book_id = "675bc8d9-3155-42fb-87b0-0a82cb162848";
+        create_book(
+            &pool,
+            &book_repo,
+            &user_id,
+            &make_book(book_id, "book", &[])?,
+        )
+        .await?;
+        let finished = make_book_with_read(book_id, "book", &[], true)?;
+        update_book(&pool, &book_repo, &user_id, &finished).await?;
+        sqlx::query("UPDATE book_event SET changed_at = '2026-02-01 00:00+00'")
+            .execute(&pool)
+            .await?;
+        let (finished_event_id,): (i64,) =
+            sqlx::query_as("SELECT event_id FROM book_event WHERE operation = 'update'")
+                .fetch_one(&pool)
+                .await?;
+
+        let mut tx = tm.begin(&user_id, EventSetOperation::DeleteBook).await?;
+        book_repo
+            .delete(&mut tx, &BookId::try_from(book_id)?)
+            .await?;
+        tm.commit(tx).await?;
+        let mut tx = tm.begin(&user_id, EventSetOperation::RestoreBook).await?;
+        book_repo
+            .restore(&mut tx, finished_event_id, Some(finished))
+            .await?;
+        tm.commit(tx).await?;
+        let mut tx = tm.begin(&user_id, EventSetOperation::ImportBooks).await?;
+        book_repo
+            .update(
+                &mut tx,
+                &make_book_with_read(book_id, "imported", &[], true)?,
+            )
+            .await?;
+        tm.commit(tx).await?;
+        sqlx::query(
+            "UPDATE book_event SET changed_at = '2026-03-01 00:00+00'
+             WHERE changed_at > '2026-02-01 00:00+00'",
+        )
+        .execute(&pool)
+        .await?;
+
+        let range = ActivityRange::new(
+            datetime!(2026-02-01 00:00 UTC),
+            datetime!(2026-04-01 00:00 UTC),
+            ActivityGranularity::Month,
+            0,
+        )?;
+        let periods = event_repo.reading_activity(&user_id, &range).await?;
+        let summary: Vec<_> = periods
+            .iter()
+            .map(|p| (p.start, p.finished_count, p.added_count, p.deleted_count))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                (datetime!(2026-02-01 00:00 UTC), 1, 1, 0),
+                (datetime!(2026-03-01 00:00 UTC), 0, 0, 1),
+            ]
+        );
+
+        Ok(())
+    }
+
+    async fn latest_changed_at(pool: &PgPool) -> Result<OffsetDateTime, sqlx::Error> {
+        let (changed_at,): (OffsetDateTime,) =
+            sqlx::query_as("SELECT max(changed_at) FROM book_event")
+                .fetch_one(pool)
+                .await?;
+        Ok(changed_at)
+    }
+
+    #[sqlx::test]
+    async fn find_books_as_of_replays_events_up_to_the_given_time(
+        pool: PgPool,
+    ) -> anyhow::Result<()> {
+        let user_repo = PgUserRepository::new(pool.clone());
+        let book_repo = PgBookRepository::new(pool.clone());
+        let event_repo = PgBookEventRepository::new(pool.clone());
+        let author_repo = PgAuthorRepository::new(pool.clone());
+        let user_id = prepare_user(&user_repo, "user1").await?;
+        let author_ids = [
+            AuthorId::try_from("278935cf-ed83-4346-9b35-b84bbdb630c0")?,
+            AuthorId::try_from("e324be11-2d3b-4f4e-a1a6-e6bcd0e6b4a1")?,
+        ];
+        for (author_id, name) in author_ids.iter().zip(["author1", "author2"]) {
+            let author = Author::new(
+                author_id.clone(),
+                AuthorName::new(name.to_owned())?,
+                OffsetDateTime::UNIX_EPOCH,
+            )?;
+            create_author(&pool, &author_repo, &user_id, &author).await?;
+        }
+
+        let original = make_book(
+            "675bc8d9-3155-42fb-87b0-0a82cb162848",
+            "original",
+            &author_ids,
+        )?;
+        create_book(&pool, &book_repo, &user_id, &original).await?;
+        let first = latest_changed_at(&pool).await?;
+
+        let renamed = make_book("675bc8d9-3155-42fb-87b0-0a82cb162848", "renamed", &[])?;
+        update_book(&pool, &book_repo, &user_id, &renamed).await?;
+        let added = make_book("c5a81e57-bc91-40ff-8b57-18cfa7cc7ae8", "added", &[])?;
+        create_book(&pool, &book_repo, &user_id, &added).await?;
+        let second = latest_changed_at(&pool).await?;
+
+        let tm = PgTransactionManager::new(pool.clone());
+        let mut tx = tm.begin(&user_id, EventSetOperation::DeleteBook).await?;
+        book_repo.delete(&mut tx, renamed.id()).await?;
+        tm.commit(tx).await?;
+        let third = latest_changed_at(&pool).await?;
+
+        // Both books share created_at, so they come back in id order.
+        assert_eq!(
+            event_repo.find_books_as_of(&user_id, first).await?,
+            vec![original]
+        );
+        assert_eq!(
+            event_repo.find_books_as_of(&user_id, second).await?,
+            vec![renamed, added.clone()]
+        );
+        assert_eq!(
+            event_repo.find_books_as_of(&user_id, third).await?,
+            vec![added]
+        );
+        assert!(
+            event_repo
+                .find_books_as_of(&user_id, OffsetDateTime::UNIX_EPOCH)
+                .await?
+                .is_empty()
+        );
+
+        Ok(())
+    }
+
+    #[sqlx::test]
+    async fn find_deleted_pairs_deletions_with_the_last_known_state(
+        pool: PgPool,
+    ) -> anyhow::Result<()> {
+        let user_repo = PgUserRepository::new(pool.clone());
+        let book_repo = PgBookRepository::new(pool.clone());
+        let event_repo = PgBookEventRepository::new(pool.clone());
+        let user_id = prepare_user(&user_repo, "user1").await?;
+
+        let kept = make_book("c5a81e57-bc91-40ff-8b57-18cfa7cc7ae8", "kept", &[])?;
+        create_book(&pool, &book_repo, &user_id, &kept).await?;
+        let first = make_book("675bc8d9-3155-42fb-87b0-0a82cb162848", "first", &[])?;
+        create_book(&pool, &book_repo, &user_id, &first).await?;
+        let renamed = make_book("675bc8d9-3155-42fb-87b0-0a82cb162848", "renamed", &[])?;
+        update_book(&pool, &book_repo, &user_id, &renamed).await?;
+        let second = make_book("9d5f2ae6-3b8e-4d7c-a0c1-3f0d2b7d8e11", "second", &[])?;
+        create_book(&pool, &book_repo, &user_id, &second).await?;
+
+        let tm = PgTransactionManager::new(pool.clone());
+        for book in [&renamed, &second] {
+            let mut tx = tm.begin(&user_id, EventSetOperation::DeleteBook).await?;
+            book_repo.delete(&mut tx, book.id()).await?;
+            tm.commit(tx).await?;
+        }
+
+        // Given two deleted books and a live one, when listing deleted books
+        let deleted = event_repo.find_deleted(&user_id).await?;
+
+        // Then the latest deletion comes first, each with its last state
+        assert_eq!(deleted.len(), 2);
+        assert_eq!(&deleted[0].deletion.book_id, second.id());
+        assert_eq!(deleted[0].deletion.operation, EventOperation::Delete);
+        assert_eq!(deleted[0].last_known.operation, EventOperation::Create);
+        assert_eq!(&deleted[1].last_known.book_id, renamed.id());
+        assert_eq!(deleted[1].last_known.operation, EventOperation::Update);
+        assert_eq!(
+            deleted[1].last_known.title.as_ref().unwrap().as_str(),
+            "renamed"
+        );
+        assert!(deleted[1].deletion.event_id > deleted[1].last_known.event_id);
+
+        Ok(())
+    }
 }
@@ -3,27 +3,40 @@ use std::collections::HashMap;
 use async_trait::async_trait;
 use futures_util::{StreamExt, TryStreamExt};
 use serde_json::json;
-use sqlx::{Executor, PgPool, Postgres};
+use sqlx::{Executor, PgPool, Postgres, QueryBuilder};
 use time::OffsetDateTime;
 use uuid::Uuid;
 
 use crate::{
-    common::types::{BookFormat, BookStore},
+    common::{
+        sort::{BookOrder, BookSortKey, BookSortValue, SortDirection},
+        types::{BookFormat, BookStore},
+    },
     domain::{
         entity::{
-            author::AuthorId,
+            author::{Author, AuthorId, AuthorName},
             book::{Book, BookId, BookTitle, Isbn, OwnedFlag, Priority, ReadFlag},
             event::EventId,
+            event_set::EventSetId,
             user::UserId,
         },
         error::DomainError,
-        repository::book_repository::BookRepository,
+        repository::{
+            book_repository::{
+                BookCursor, BookFilter, BookRepository, BookSelection, LibraryStats, PriorityBucket,
+            },
+            pagination::{Edge, Page, PageDirection, PageRequest},
+            search::SearchQuery,
+        },
+    },
+    infrastructure::{
+        search::{contains_pattern, push_search_rank},
+        transaction::PgTransaction,
     },
-    infrastructure::transaction::PgTransaction,
 };
 
 #[derive(sqlx::FromRow)]
-struct BookRow {
+pub(super) struct BookRow {
     id: Uuid,
     title: String,
     author_ids: Option<Vec<Uuid>>,
@@ -37,6 +50,13 @@ struct BookRow {
     updated_at: OffsetDateTime,
 }
 
+#[derive(sqlx::FromRow)]
+struct BookPageRow {
+    #[sqlx(flatten)]
+    book: BookRow,
+    author_yomi: String,
+}
+
 #[derive(sqlx::FromRow)]
 struct AuthorBookRow {
     author_id: Uuid,
@@ -44,7 +64,29 @@ struct AuthorBookRow {
     book: BookRow,
 }
 
-fn book_from_row(row: BookRow) -> Result<Book, DomainError> {
+#[derive(sqlx::FromRow)]
+struct BookCountsRow {
+    total_count: i64,
+    read_count: i64,
+    owned_count: i64,
+    unread_owned_count: i64,
+}
+
+#[derive(sqlx::FromRow)]
+struct AuthorBookCountRow {
+    id: Uuid,
+    name: String,
+    yomi: String,
+    created_at: OffsetDateTime,
+    updated_at: OffsetDateTime,
+    book_count: i64,
+}
+
+// Width of the priority histogram buckets; the last bucket also holds 100.
+const PRIORITY_BUCKET_WIDTH: i32 = 10;
+const PRIORITY_BUCKET_COUNT: i32 = 10;
+
+pub(super) fn book_from_row(row: BookRow) -> Result<Book, DomainError> {
     let book_id = BookId::new(row.id)?;
     let title = BookTitle::new(row.title)?;
     let author_ids: Vec<AuthorId> = row
@@ -118,6 +160,256 @@ where
     book_row.map(book_from_row).transpose()
 }
 
+// Restricts a `WHERE book.user_id = ...` query to the books of one author.
+fn push_author_scope(query: &mut QueryBuilder<Postgres>, author_id: Option<&AuthorId>) {
+    if let Some(author_id) = author_id {
+        query
+            .push(
+                " AND EXISTS (
+                    SELECT 1 FROM book_author requested
+                    WHERE requested.user_id = book.user_id
+                      AND requested.book_id = book.id
+                      AND requested.author_id = ",
+            )
+            .push_bind(author_id.to_uuid())
+            .push(")");
+    }
+}
+
+// Appends one condition per filter field to a `WHERE book.user_id = ...` query.
+fn push_book_filter(query: &mut QueryBuilder<Postgres>, filter: &BookFilter) {
+    if let Some(read) = filter.read {
+        query.push(" AND book.read = ").push_bind(read);
+    }
+    if let Some(owned) = filter.owned {
+        query.push(" AND book.owned = ").push_bind(owned);
+    }
+    if let Some(format) = &filter.format {
+        query
+            .push(" AND book.format = ")
+            .push_bind(format.to_string());
+    }
+    if let Some(store) = &filter.store {
+        query
+            .push(" AND book.store = ")
+            .push_bind(store.to_string());
+    }
+    if let Some(priority_min) = filter.priority_min {
+        query.push(" AND book.priority >= ").push_bind(priority_min);
+    }
+    if let Some(priority_max) = filter.priority_max {
+        query.push(" AND book.priority <= ").push_bind(priority_max);
+    }
+    if let Some(author_ids) = &filter.author_ids {
+        let author_ids: Vec<Uuid> = author_ids.iter().map(AuthorId::to_uuid).collect();
+        query
+            .push(
+                " AND EXISTS (
+                    SELECT 1 FROM book_author filtered
+                    WHERE filtered.user_id = book.user_id
+                      AND filtered.book_id = book.id
+                      AND filtered.author_id = ANY(",
+            )
+            .push_bind(author_ids)
+            .push("))");
+    }
+    if let Some(created_from) = filter.created_from {
+        query
+            .push(" AND book.created_at >= ")
+            .push_bind(created_from);
+    }
+    if let Some(created_to) = filter.created_to {
+        query.push(" AND book.created_at < ").push_bind(created_to);
+    }
+    if let Some(updated_from) = filter.updated_from {
+        query
+            .push(" AND book.updated_at >= ")
+            .push_bind(updated_from);
+    }
+    if let Some(updated_to) = filter.updated_to {
+        query.push(" AND book.updated_at < ").push_bind(updated_to);
+    }
+    match filter.has_isbn {
+        Some(true) => {
+            query.push(" AND book.isbn <> ''");
+        }
+        Some(false) => {
+            query.push(" AND book.isbn = ''");
+        }
+        None => {}
+    }
+}
+
+fn push_book_selection(query: &mut QueryBuilder<Postgres>, selection: &BookSelection) {
+    match selection {
+        BookSelection::Ids(book_ids) => {
+            let book_ids: Vec<Uuid> = book_ids.iter().map(BookId::to_uuid).collect();
+            query
+                .push(" AND book.id = ANY(")
+                .push_bind(book_ids)
+                .push(")");
+        }
+        BookSelection::Filter(filter) => push_book_filter(query, filter),
+    }
+}
+
+// The smallest reading among a book's authors, compared in the "C" collation
+// of author.sort_yomi. Books without authors sort as an empty reading.
+const AUTHOR_YOMI_OF_BOOK: &str = "COALESCE((
+        SELECT min(author.sort_yomi)
+        FROM book_author
+        INNER JOIN author
+            ON author.user_id = book_author.user_id
+            AND author.id = book_author.author_id
+        WHERE book_author.user_id = book.user_id
+          AND book_author.book_id = book.id
+    ), '') COLLATE \"C\"";
+
+fn sort_column(key: BookSortKey) -> &'static str {
+    match key {
+        BookSortKey::Title => "book.title",
+        BookSortKey::Priority => "book.priority",
+        BookSortKey::CreatedAt => "book.created_at",
+        BookSortKey::UpdatedAt => "book.updated_at",
+        BookSortKey::AuthorYomi => AUTHOR_YOMI_OF_BOOK,
+    }
+}
+
+fn push_order_by(query: &mut QueryBuilder<Postgres>, key: BookSortKey, direction: SortDirection) {
+    let direction = match direction {
+        SortDirection::Asc => "ASC",
+        SortDirection::Desc => "DESC",
+    };
+    query.push(format!(
+        " ORDER BY {} {direction}, book.id {direction}",
+        sort_column(key)
+    ));
+}
+
+fn push_cursor_bound(
+    query: &mut QueryBuilder<Postgres>,
+    order: &BookOrder,
+    operator: &str,
+    cursor: &BookCursor,
+) -> Result<(), DomainError> {
+    if cursor.value.key() != order.key {
+        return Err(DomainError::Validation(
+            "cursor does not match the requested order.".to_string(),
+        ));
+    }
+    query.push(format!(
+        " AND ({}, book.id) {operator} (",
+        sort_column(order.key)
+    ));
+    match &cursor.value {
+        BookSortValue::Title(value) | BookSortValue::AuthorYomi(value) => {
+            query.push_bind(value.clone())
+        }
+        BookSortValue::Priority(value) => query.push_bind(*value),
+        BookSortValue::CreatedAt(value) | BookSortValue::UpdatedAt(value) => {
+            query.push_bind(*value)
+        }
+    };
+    query.push(", ").push_bind(cursor.id.to_uuid()).push(")");
+    Ok(())
+}
+
+// Appends the keyset bounds, ordering and limit of a page. Rows are compared
+// as (sort key, id) so that books sharing a sort key keep a stable order.
+fn push_page_window(
+    query: &mut QueryBuilder<Postgres>,
+    order: &BookOrder,
+    page: &PageRequest<BookCursor>,
+) -> Result<(), DomainError> {
+    let (after, before) = match order.direction {
+        SortDirection::Asc => (">", "<"),
+        SortDirection::Desc => ("<", ">"),
+    };
+    if let Some(cursor) = &page.after {
+        push_cursor_bound(query, order, after, cursor)?;
+    }
+    if let Some(cursor) = &page.before {
+        push_cursor_bound(query, order, before, cursor)?;
+    }
+    // Backward pages are read from the end of the listing.
+    let direction = match (order.direction, page.direction) {
+        (SortDirection::Asc, PageDirection::Forward)
+        | (SortDirection::Desc, PageDirection::Backward) => SortDirection::Asc,
+        _ => SortDirection::Desc,
+    };
+    push_order_by(query, order.key, direction);
+    query.push(" LIMIT ").push_bind(page.fetch_limit());
+    Ok(())
+}
+
+fn book_cursor(book: &Book, key: BookSortKey, author_yomi: String) -> BookCursor {
+    let value = match key {
+        BookSortKey::Title => BookSortValue::Title(book.title().as_str().to_owned()),
+        BookSortKey::Priority => BookSortValue::Priority(book.priority().to_i32()),
+        BookSortKey::CreatedAt => BookSortValue::CreatedAt(*book.created_at()),
+        BookSortKey::UpdatedAt => BookSortValue::UpdatedAt(*book.updated_at()),
+        BookSortKey::AuthorYomi => BookSortValue::AuthorYomi(author_yomi),
+    };
+    BookCursor {
+        value,
+        id: book.id().clone(),
+    }
+}
+
+// Joins a book to its authors; used in correlated subqueries over `book`.
+const AUTHORS_OF_BOOK: &str = "FROM book_author
+        INNER JOIN author
+            ON author.user_id = book_author.user_id
+            AND author.id = book_author.author_id
+        WHERE book_author.user_id = book.user_id
+          AND book_author.book_id = book.id";
+
+const BOOK_COLUMNS: &str = "book.id, book.title,
+        (SELECT array_agg(book_author.author_id)
+         FROM book_author
+         WHERE book_author.user_id = book.user_id
+           AND book_author.book_id = book.id) AS author_ids,
+        book.isbn, book.read, book.owned, book.priority, book.format,
+        book.store, book.created_at, book.updated_at";
+
+async fn find_book_page(
+    pool: &PgPool,
+    user_id: &UserId,
+    author_id: Option<&AuthorId>,
+    filter: &BookFilter,
+    order: &BookOrder,
+    page: &PageRequest<BookCursor>,
+) -> Result<Page<Book, BookCursor>, DomainError> {
+    let mut count_query = QueryBuilder::new("SELECT count(*) FROM book WHERE book.user_id = ");
+    count_query.push_bind(user_id.as_str().to_owned());
+    push_author_scope(&mut count_query, author_id);
+    push_book_filter(&mut count_query, filter);
+    let total_count: i64 = count_query.build_query_scalar().fetch_one(pool).await?;
+
+    let mut query = QueryBuilder::new(format!(
+        "SELECT {BOOK_COLUMNS}, {AUTHOR_YOMI_OF_BOOK} AS author_yomi
+         FROM book
+         WHERE book.user_id = "
+    ));
+    query.push_bind(user_id.as_str().to_owned());
+    push_author_scope(&mut query, author_id);
+    push_book_filter(&mut query, filter);
+    push_page_window(&mut query, order, page)?;
+    let rows: Vec<BookPageRow> = query.build_query_as().fetch_all(pool).await?;
+    let edges = rows
+        .into_iter()
+        .map(|row| {
+            let book = book_from_row(row.book)?;
+            Ok(Edge {
+                cursor: book_cursor(&book, order.key, row.author_yomi),
+                node: book,
+            })
+        })
+        .collect::<Result<Vec<_>, DomainError>>()?;
+
+    Ok(Page::from_rows(edges, page, total_count))
+}
+
 #[derive(Debug, Clone)]
 pub struct PgBookRepository {
     pool: PgPool,
@@ -256,52 +548,108 @@ impl BookRepository for PgBookRepository {
         row.map(book_from_row).transpose()
     }
 
-    async fn find_all(&self, user_id: &UserId) -> Result<Vec<Book>, DomainError> {
-        let books: Result<Vec<Book>, DomainError> = sqlx::query_as(
-            "WITH book_of_user AS(
-                SELECT
-                    *
-                FROM
-                    book
-                WHERE
-                    book.user_id = $1
-            ),
-            authors_of_book_and_user AS(
-                SELECT
-                    book_id,
-                    array_agg(author_id) AS author_ids
-                FROM
-                    book_author
-                WHERE
-                    book_author.user_id = $1
-                GROUP BY
-                    book_author.book_id
-            )
-            SELECT
-                *
-            FROM
-                book_of_user
-                LEFT OUTER JOIN
-                    authors_of_book_and_user
-                ON  book_of_user.id = authors_of_book_and_user.book_id",
+    async fn find_latest_event_id_with_tx(
+        &self,
+        tx: &mut Self::Transaction,
+        user_id: &UserId,
+        book_id: &BookId,
+    ) -> Result<Option<i64>, DomainError> {
+        let row: Option<(i64,)> = sqlx::query_as(
+            "SELECT event_id FROM book_event
+             WHERE user_id = $1 AND book_id = $2
+             ORDER BY changed_at DESC, event_id DESC
+             LIMIT 1",
         )
         .bind(user_id.as_str())
-        .fetch(&self.pool)
-        .map(
-            |row: Result<BookRow, sqlx::Error>| -> Result<Book, DomainError> {
-                book_from_row(row?)
-            },
+        .bind(book_id.to_uuid())
+        .fetch_optional(tx.as_mut())
+        .await?;
+
+        Ok(row.map(|(event_id,)| event_id))
+    }
+
+    async fn find_latest_event_with_tx(
+        &self,
+        tx: &mut Self::Transaction,
+        user_id: &UserId,
+        book_id: &BookId,
+    ) -> Result<Option<(EventId, EventSetId)>, DomainError> {
+        let row: Option<(i64, Uuid)> = sqlx::query_as(
+            "SELECT event_id, event_set_id FROM book_event
+             WHERE user_id = $1 AND book_id = $2
+             ORDER BY changed_at DESC, event_id DESC
+             LIMIT 1",
         )
-        .try_collect()
-        .await;
+        .bind(user_id.as_str())
+        .bind(book_id.to_uuid())
+        .fetch_optional(tx.as_mut())
+        .await?;
+
+        Ok(row.map(|(event_id, event_set_id)| {
+            (EventId::from(event_id), EventSetId::from(event_set_id))
+        }))
+    }
+
+    async fn find_all(
+        &self,
+        user_id: &UserId,
+        filter: &BookFilter,
+        order: &BookOrder,
+    ) -> Result<Vec<Book>, DomainError> {
+        let mut query = QueryBuilder::new(format!(
+            "SELECT {BOOK_COLUMNS} FROM book WHERE book.user_id = "
+        ));
+        query.push_bind(user_id.as_str().to_owned());
+        push_book_filter(&mut query, filter);
+        push_order_by(&mut query, order.key, order.direction);
+
+        let books: Result<Vec<Book>, DomainError> = query
+            .build_query_as()
+            .fetch(&self.pool)
+            .map(
+                |row: Result<BookRow, sqlx::Error>| -> Result<Book, DomainError> {
+                    book_from_row(row?)
+                },
+            )
+            .try_collect()
+            .await;
 
         books
     }
 
+    async fn find_page(
+        &self,
+        user_id: &UserId,
+        filter: &BookFilter,
+        order: &BookOrder,
+        page: &PageRequest<BookCursor>,
+    ) -> Result<Page<Book, BookCursor>, DomainError> {
+        find_book_page(&self.pool, user_id, None, filter, order, page).await
+    }
+
+    async fn find_page_by_author_id(
+        &self,
+        user_id: &UserId,
+        author_id: &AuthorId,
+        order: &BookOrder,
+        page: &PageRequest<BookCursor>,
+    ) -> Result<Page<Book, BookCursor>, DomainError> {
+        find_book_page(
+            &self.pool,
+            user_id,
+            Some(author_id),
+            &BookFilter::default(),
+            order,
+            page,
+        )
+        .await
+    }
+
     async fn find_by_author_ids_as_hash_map(
         &self,
         user_id: &UserId,
         author_ids: &[AuthorId],
+        order: &BookOrder,
     ) -> Result<HashMap<AuthorId, Vec<Book>>, DomainError> {
         let mut books_by_author: HashMap<AuthorId, Vec<Book>> = author_ids
             .iter()
@@ -310,43 +658,21 @@ impl BookRepository for PgBookRepository {
             .collect();
         let author_uuids: Vec<Uuid> = author_ids.iter().map(AuthorId::to_uuid).collect();
 
-        let rows: Vec<AuthorBookRow> = sqlx::query_as(
-            "WITH authors_of_book_and_user AS (
-                SELECT
-                    user_id,
-                    book_id,
-                    array_agg(author_id) AS author_ids
-                FROM book_author
-                WHERE user_id = $1
-                GROUP BY user_id, book_id
-            )
-            SELECT
-                requested.author_id,
-                book.id,
-                book.title,
-                authors.author_ids,
-                book.isbn,
-                book.read,
-                book.owned,
-                book.priority,
-                book.format,
-                book.store,
-                book.created_at,
-                book.updated_at
-            FROM book_author AS requested
-            INNER JOIN book
-                ON book.user_id = requested.user_id
-                AND book.id = requested.book_id
-            LEFT OUTER JOIN authors_of_book_and_user AS authors
-                ON authors.user_id = book.user_id
-                AND authors.book_id = book.id
-            WHERE requested.user_id = $1
-                AND requested.author_id = ANY($2)",
-        )
-        .bind(user_id.as_str())
-        .bind(author_uuids)
-        .fetch_all(&self.pool)
-        .await?;
+        let mut query = QueryBuilder::new(format!(
+            "SELECT requested.author_id, {BOOK_COLUMNS}
+             FROM book_author AS requested
+             INNER JOIN book
+                 ON book.user_id = requested.user_id
+                 AND book.id = requested.book_id
+             WHERE requested.user_id = "
+        ));
+        query
+            .push_bind(user_id.as_str().to_owned())
+            .push(" AND requested.author_id = ANY(")
+            .push_bind(author_uuids)
+            .push(")");
+        push_order_by(&mut query, order.key, order.direction);
+        let rows: Vec<AuthorBookRow> = query.build_query_as().fetch_all(&self.pool).await?;
 
         for row in rows {
             books_by_author
@@ -358,6 +684,179 @@ impl BookRepository for PgBookRepository {
         Ok(books_by_author)
     }
 
+    async fn search(
+        &self,
+        user_id: &UserId,
+        search: &SearchQuery,
+        limit: usize,
+    ) -> Result<Vec<Book>, DomainError> {
+        let mut query = QueryBuilder::new(format!(
+            "SELECT {BOOK_COLUMNS} FROM book WHERE book.user_id = "
+        ));
+        query.push_bind(user_id.as_str().to_owned());
+        for term in search.terms() {
+            let pattern = contains_pattern(term);
+            query
+                .push(" AND (book.search_text ILIKE ")
+                .push_bind(pattern.clone())
+                .push(format!(
+                    " OR EXISTS (SELECT 1 {AUTHORS_OF_BOOK} AND author.search_text ILIKE "
+                ))
+                .push_bind(pattern)
+                .push("))");
+        }
+        // A book ranks by its best match, be it the title or one of its authors.
+        query.push(" ORDER BY GREATEST(");
+        push_search_rank(&mut query, "book.search_text", search);
+        query.push(", COALESCE((SELECT max(");
+        push_search_rank(&mut query, "author.search_text", search);
+        query.push(format!(
+            ") {AUTHORS_OF_BOOK}), 0)) DESC, book.title ASC, book.id ASC LIMIT "
+        ));
+        query.push_bind(limit as i64);
+
+        let books: Result<Vec<Book>, DomainError> = query
+            .build_query_as()
+            .fetch(&self.pool)
+            .map(
+                |row: Result<BookRow, sqlx::Error>| -> Result<Book, DomainError> {
+                    book_from_row(row?)
+                },
+            )
+            .try_collect()
+            .await;
+
+        books
+    }
+
+    async fn library_stats(
+        &self,
+        user_id: &UserId,
+        top_authors: usize,
+    ) -> Result<LibraryStats, DomainError> {
+        // One snapshot for all aggregates, so the numbers add up.
+        let mut tx = self.pool.begin().await?;
+        sqlx::query("SET TRANSACTION ISOLATION LEVEL REPEATABLE READ, READ ONLY")
+            .execute(&mut *tx)
+            .await?;
+
+        let counts: BookCountsRow = sqlx::query_as(
+            "SELECT count(*) AS total_count,
+                    count(*) FILTER (WHERE read) AS read_count,
+                    count(*) FILTER (WHERE owned) AS owned_count,
+                    count(*) FILTER (WHERE owned AND NOT read) AS unread_owned_count
+             FROM book
+             WHERE user_id = $1",
+        )
+        .bind(user_id.as_str())
+        .fetch_one(&mut *tx)
+        .await?;
+
+        let format_counts: Vec<(String, i64)> = sqlx::query_as(
+            "SELECT book_format.format, count(book.id)
+             FROM book_format
+             LEFT JOIN book
+                ON book.format = book_format.format
+                AND book.user_id = $1
+             GROUP BY book_format.format
+             ORDER BY book_format.format",
+        )
+        .bind(user_id.as_str())
+        .fetch_all(&mut *tx)
+        .await?;
+        let format_counts = format_counts
+            .into_iter()
+            .map(|(format, count)| Ok((BookFormat::try_from(format.as_str())?, count)))
+            .collect::<Result<Vec<_>, DomainError>>()?;
+
+        let store_counts: Vec<(String, i64)> = sqlx::query_as(
+            "SELECT book_store.store, count(book.id)
+             FROM book_store
+             LEFT JOIN book
+                ON book.store = book_store.store
+                AND book.user_id = $1
+             GROUP BY book_store.store
+             ORDER BY book_store.store",
+        )
+        .bind(user_id.as_str())
+        .fetch_all(&mut *tx)
+        .await?;
+        let store_counts = store_counts
+            .into_iter()
+            .map(|(store, count)| Ok((BookStore::try_from(store.as_str())?, count)))
+            .collect::<Result<Vec<_>, DomainError>>()?;
+
+        let histogram: Vec<(i32, i64)> = sqlx::query_as(
+            "SELECT bucket, count(book.id)
+             FROM generate_series(0, $2 - 1) AS bucket
+             LEFT JOIN book
+                ON book.user_id = $1
+                AND LEAST(book.priority / $3, $2 - 1) = bucket
+             GROUP BY bucket
+             ORDER BY bucket",
+        )
+        .bind(user_id.as_str())
+        .bind(PRIORITY_BUCKET_COUNT)
+        .bind(PRIORITY_BUCKET_WIDTH)
+        .fetch_all(&mut *tx)
+        .await?;
+        let priority_histogram = histogram
+            .into_iter()
+            .map(|(bucket, count)| {
+                let min = bucket * PRIORITY_BUCKET_WIDTH;
+                let max = if bucket == PRIORITY_BUCKET_COUNT - 1 {
+                    100
+                } else {
+                    min + PRIORITY_BUCKET_WIDTH - 1
+                };
+                PriorityBucket { min, max, count }
+            })
+            .collect();
+
+        let top_authors: Vec<AuthorBookCountRow> = sqlx::query_as(
+            "SELECT author.id, author.name, author.yomi, author.created_at, author.updated_at,
+                    count(*) AS book_count
+             FROM book_author
+             INNER JOIN author
+                ON author.user_id = book_author.user_id
+                AND author.id = book_author.author_id
+             WHERE book_author.user_id = $1
+             GROUP BY author.user_id, author.id
+             ORDER BY book_count DESC, author.sort_yomi ASC, author.id ASC
+             LIMIT $2",
+        )
+        .bind(user_id.as_str())
+        .bind(top_authors as i64)
+        .fetch_all(&mut *tx)
+        .await?;
+        let top_authors = top_authors
+            .into_iter()
+            .map(|row| {
+                let author = Author::new_with_timestamps(
+                    AuthorId::new(row.id),
+                    AuthorName::new(row.name)?,
+                    row.yomi,
+                    row.created_at,
+                    row.updated_at,
+                )?;
+                Ok((author, row.book_count))
+            })
+            .collect::<Result<Vec<_>, DomainError>>()?;
+
+        tx.commit().await?;
+
+        Ok(LibraryStats {
+            total_count: counts.total_count,
+            read_count: counts.read_count,
+            owned_count: counts.owned_count,
+            unread_owned_count: counts.unread_owned_count,
+            format_counts,
+            store_counts,
+            priority_histogram,
+            top_authors,
+        })
+    }
+
     async fn find_by_author_id_with_tx(
         &self,
         tx: &mut Self::Transaction,
@@ -416,6 +915,39 @@ impl BookRepository for PgBookRepository {
         rows.into_iter().map(book_from_row).collect()
     }
 
+    async fn find_selection_with_tx(
+        &self,
+        tx: &mut Self::Transaction,
+        user_id: &UserId,
+        selection: &BookSelection,
+    ) -> Result<Vec<Book>, DomainError> {
+        // Lock first, then read the books in a later statement so that a
+        // filter is checked against their state after any lock wait.
+        let mut query = QueryBuilder::new("SELECT book.id FROM book WHERE book.user_id = ");
+        query.push_bind(user_id.as_str().to_owned());
+        push_book_selection(&mut query, selection);
+        query.push(" ORDER BY book.id FOR UPDATE OF book");
+        let locked_book_ids: Vec<Uuid> = query.build_query_scalar().fetch_all(tx.as_mut()).await?;
+
+        if locked_book_ids.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let mut query = QueryBuilder::new(format!(
+            "SELECT {BOOK_COLUMNS} FROM book WHERE book.user_id = "
+        ));
+        query.push_bind(user_id.as_str().to_owned());
+        query
+            .push(" AND book.id = ANY(")
+            .push_bind(locked_book_ids)
+            .push(")");
+        push_book_selection(&mut query, selection);
+        query.push(" ORDER BY book.id");
+        let rows: Vec<BookRow> = query.build_query_as().fetch_all(tx.as_mut()).await?;
+
+        rows.into_iter().map(book_from_row).collect()
+    }
+
     async fn update(
         &self,
         tx: &mut Self::Transaction,
@@ -817,7 +1349,9 @@ mod tests {
         let user_id = prepare_user(&user_repository, "user1").await?;
         let author_ids = prepare_authors1(&pool, &user_id, &author_repository).await?;
 
-        let all_books = book_repository.find_all(&user_id).await?;
+        let all_books = book_repository
+            .find_all(&user_id, &BookFilter::default(), &BookOrder::default())
+            .await?;
         assert_eq!(all_books.len(), 0);
 
         let book = book_entity1(&author_ids)?;
@@ -876,6 +1410,116 @@ mod tests {
         Ok(())
     }
 
+    #[sqlx::test]
+    async fn test_find_latest_event_id_with_tx_follows_updates(pool: PgPool) -> anyhow::Result<()> {
+        let user_repository = PgUserRepository::new(pool.clone());
+        let author_repository = PgAuthorRepository::new(pool.clone());
+        let book_repository = PgBookRepository::new(pool.clone());
+
+        let user_id = prepare_user(&user_repository, "user1").await?;
+        let author_ids = prepare_authors1(&pool, &user_id, &author_repository).await?;
+        let book = book_entity1(&author_ids)?;
+        let tm = PgTransactionManager::new(pool.clone());
+        let mut tx = tm.begin(&user_id, EventSetOperation::CreateBook).await?;
+        let created_event_id = book_repository.create(&mut tx, &book).await?;
+        tm.commit(tx).await?;
+        let mut tx = tm.begin(&user_id, EventSetOperation::UpdateBook).await?;
+        assert_eq!(
+            book_repository
+                .find_latest_event_id_with_tx(&mut tx, &user_id, book.id())
+                .await?,
+            Some(created_event_id.value())
+        );
+        let updated_event_id = book_repository.update(&mut tx, &book).await?;
+        assert_eq!(
+            book_repository
+                .find_latest_event_id_with_tx(&mut tx, &user_id, book.id())
+                .await?,
+            Some(updated_event_id.value())
+        );
+        tm.commit(tx).await?;
+
+        Ok(())
+    }
+
+    #[sqlx::test]
+    async fn find_latest_event_with_tx_gives_the_event_and_its_event_set(
+        pool: PgPool,
+    ) -> anyhow::Result<()> {
+        let user_repository = PgUserRepository::new(pool.clone());
+        let author_repository = PgAuthorRepository::new(pool.clone());
+        let book_repository = PgBookRepository::new(pool.clone());
+
+        let user_id = prepare_user(&user_repository, "user1").await?;
+        let author_ids = prepare_authors1(&pool, &user_id, &author_repository).await?;
+        let book = book_entity1(&author_ids)?;
+        let tm = PgTransactionManager::new(pool.clone());
+        let mut tx = tm.begin(&user_id, EventSetOperation::CreateBook).await?;
+        let created_event_id = book_repository.create(&mut tx, &book).await?;
+        let created_event_set_id = EventSetId::from(tx.event_set_id());
+        tm.commit(tx).await?;
+
+        let mut tx = tm.begin(&user_id, EventSetOperation::UpdateBook).await?;
+        assert_eq!(
+            book_repository
+                .find_latest_event_with_tx(&mut tx, &user_id, book.id())
+                .await?,
+            Some((created_event_id, created_event_set_id))
+        );
+        tm.commit(tx).await?;
+
+        Ok(())
+    }
+
+    #[sqlx::test]
+    async fn find_selection_with_tx_picks_ids_or_filtered_books(
+        pool: PgPool,
+    ) -> anyhow::Result<()> {
+        let user_repository = PgUserRepository::new(pool.clone());
+        let author_repository = PgAuthorRepository::new(pool.clone());
+        let book_repository = PgBookRepository::new(pool.clone());
+
+        let user_id = prepare_user(&user_repository, "user1").await?;
+        let author_ids1 = prepare_authors1(&pool, &user_id, &author_repository).await?;
+        let author_ids2 = prepare_authors2(&pool, &user_id, &author_repository).await?;
+        let book1 = book_entity1(&author_ids1)?;
+        let book2 = book_entity2(&author_ids2)?;
+        create_book(&pool, &book_repository, &user_id, &book1).await?;
+        create_book(&pool, &book_repository, &user_id, &book2).await?;
+
+        let cases = vec![
+            (
+                BookSelection::Ids(vec![book1.id().clone(), BookId::new(Uuid::new_v4())?]),
+                vec![&book1],
+            ),
+            (
+                BookSelection::Filter(BookFilter {
+                    author_ids: Some(vec![author_ids2[0].clone()]),
+                    ..Default::default()
+                }),
+                vec![&book2],
+            ),
+            (
+                BookSelection::Filter(BookFilter::default()),
+                vec![&book1, &book2],
+            ),
+        ];
+        let tm = PgTransactionManager::new(pool.clone());
+        for (selection, expected) in cases {
+            let mut tx = tm
+                .begin(&user_id, EventSetOperation::BulkUpdateBooks)
+                .await?;
+            let books = book_repository
+                .find_selection_with_tx(&mut tx, &user_id, &selection)
+                .await?;
+            let titles: Vec<&str> = books.iter().map(|book| book.title().as_str()).collect();
+            let expected: Vec<&str> = expected.iter().map(|book| book.title().as_str()).collect();
+            assert_eq!(titles, expected, "{selection:?}");
+        }
+
+        Ok(())
+    }
+
     #[sqlx::test]
     async fn test_create_and_find_all(pool: PgPool) -> anyhow::Result<()> {
         let user_repository = PgUserRepository::new(pool.clone());
@@ -887,7 +1531,9 @@ mod tests {
         let author_ids1 = prepare_authors1(&pool, &user_id, &author_repository).await?;
         let author_ids2 = prepare_authors2(&pool, &user_id, &author_repository).await?;
 
-        let all_books = book_repository.find_all(&user_id).await?;
+        let all_books = book_repository
+            .find_all(&user_id, &BookFilter::default(), &BookOrder::default())
+            .await?;
         assert_eq!(all_books.len(), 0);
 
         let book1 = book_entity1(&author_ids1)?;
@@ -895,7 +1541,9 @@ mod tests {
         create_book(&pool, &book_repository, &user_id, &book1).await?;
         create_book(&pool, &book_repository, &user_id, &book2).await?;
 
-        let all_books = book_repository.find_all(&user_id).await?;
+        let all_books = book_repository
+            .find_all(&user_id, &BookFilter::default(), &BookOrder::default())
+            .await?;
         assert_eq!(all_books.len(), 2);
         if all_books[0] == book1 {
             assert_eq!(all_books[0], book1);
@@ -908,6 +1556,727 @@ mod tests {
         Ok(())
     }
 
+    #[sqlx::test]
+    async fn find_page_walks_books_in_created_at_and_id_order(pool: PgPool) -> anyhow::Result<()> {
+        let user_repository = PgUserRepository::new(pool.clone());
+        let author_repository = PgAuthorRepository::new(pool.clone());
+        let book_repository = PgBookRepository::new(pool.clone());
+
+        let user_id = prepare_user(&user_repository, "user1").await?;
+        let other_user_id = prepare_user(&user_repository, "user2").await?;
+        let author_ids = prepare_authors1(&pool, &user_id, &author_repository).await?;
+
+        // book1 and book2 share created_at, so their ids break the tie.
+        let book1 = book_entity1(&author_ids)?;
+        let book2 = book_entity2(&author_ids[..1])?;
+        let earlier = PrimitiveDateTime::new(date!(2022 - 05 - 04), time!(0:00)).assume_utc();
+        let book0 = Book::new(
+            BookId::try_from("f1c1d7a4-5e3b-4c8e-9a57-2a3f4e5d6c7b")?,
+            BookTitle::new("title0".to_owned())?,
+            vec![],
+            Isbn::new(String::new())?,
+            ReadFlag::new(false),
+            OwnedFlag::new(false),
+            Priority::new(50)?,
+            BookFormat::Printed,
+            BookStore::Unknown,
+            earlier,
+            earlier,
+        )?;
+        for book in [&book2, &book0, &book1] {
+            create_book(&pool, &book_repository, &user_id, book).await?;
+        }
+        let other_author_ids = prepare_authors1(&pool, &other_user_id, &author_repository).await?;
+        create_book(
+            &pool,
+            &book_repository,
+            &other_user_id,
+            &book_entity1(&other_author_ids)?,
+        )
+        .await?;
+        let cursor_of = |book: &Book| BookCursor {
+            value: BookSortValue::CreatedAt(*book.created_at()),
+            id: book.id().clone(),
+        };
+
+        let page = book_repository
+            .find_page(
+                &user_id,
+                &BookFilter::default(),
+                &BookOrder::default(),
+                &PageRequest::forward(2),
+            )
+            .await?;
+        assert_eq!(
+            page.nodes().cloned().collect::<Vec<_>>(),
+            vec![book0.clone(), book1.clone()]
+        );
+        assert!(page.has_next_page);
+        assert!(!page.has_previous_page);
+        assert_eq!(page.total_count, 3);
+
+        let page = book_repository
+            .find_page(
+                &user_id,
+                &BookFilter::default(),
+                &BookOrder::default(),
+                &PageRequest {
+                    after: Some(cursor_of(&book1)),
+                    ..PageRequest::forward(2)
+                },
+            )
+            .await?;
+        assert_eq!(
+            page.nodes().cloned().collect::<Vec<_>>(),
+            vec![book2.clone()]
+        );
+        assert!(!page.has_next_page);
+        assert!(page.has_previous_page);
+
+        let page = book_repository
+            .find_page(
+                &user_id,
+                &BookFilter::default(),
+                &BookOrder::default(),
+                &PageRequest {
+                    after: None,
+                    before: None,
+                    limit: 2,
+                    direction: PageDirection::Backward,
+                },
+            )
+            .await?;
+        assert_eq!(
+            page.nodes().cloned().collect::<Vec<_>>(),
+            vec![book1.clone(), book2.clone()]
+        );
+        assert!(page.has_previous_page);
+
+        let page = book_repository
+            .find_page(
+                &user_id,
+                &BookFilter::default(),
+                &BookOrder::default(),
+                &PageRequest {
+                    after: None,
+                    before: Some(cursor_of(&book1)),
+                    limit: 5,
+                    direction: PageDirection::Backward,
+                },
+            )
+            .await?;
+        assert_eq!(page.nodes().cloned().collect::<Vec<_>>(), vec![book0]);
+        assert!(!page.has_previous_page);
+        assert!(page.has_next_page);
+
+        let page = book_repository
+            .find_page_by_author_id(
+                &user_id,
+                &author_ids[1],
+                &BookOrder::default(),
+                &PageRequest::forward(10),
+            )
+            .await?;
+        assert_eq!(page.nodes().cloned().collect::<Vec<_>>(), vec![book1]);
+        assert_eq!(page.total_count, 1);
+
+        Ok(())
+    }
+
+    #[sqlx::test]
+    async fn find_all_and_find_page_follow_requested_order(pool: PgPool) -> anyhow::Result<()> {
+        let user_repository = PgUserRepository::new(pool.clone());
+        let author_repository = PgAuthorRepository::new(pool.clone());
+        let book_repository = PgBookRepository::new(pool.clone());
+
+        let user_id = prepare_user(&user_repository, "user1").await?;
+        let natsume = Author::new_with_yomi(
+            AuthorId::try_from("278935cf-ed83-4346-9b35-b84bbdb630c0")?,
+            AuthorName::new("ナツメ".to_owned())?,
+            String::new(),
+            OffsetDateTime::UNIX_EPOCH,
+        )?;
+        let akutagawa = Author::new_with_yomi(
+            AuthorId::try_from("925aaf96-64c7-44be-85f8-767a20b2c20c")?,
+            AuthorName::new("芥川龍之介".to_owned())?,
+            "あくたがわりゅうのすけ".to_owned(),
+            OffsetDateTime::UNIX_EPOCH,
+        )?;
+        let mori = Author::new_with_yomi(
+            AuthorId::try_from("93090e87-b7a1-403c-974c-d74d881e83b9")?,
+            AuthorName::new("森鴎外".to_owned())?,
+            "もりおうがい".to_owned(),
+            OffsetDateTime::UNIX_EPOCH,
+        )?;
+        for author in [&natsume, &akutagawa, &mori] {
+            create_author(&pool, &author_repository, &user_id, author).await?;
+        }
+
+        let at = PrimitiveDateTime::new(date!(2022 - 05 - 05), time!(0:00)).assume_utc();
+        let new_book = |id: &str, title: &str, author_ids: Vec<AuthorId>, priority: i32| {
+            Book::new(
+                BookId::try_from(id)?,
+                BookTitle::new(title.to_owned())?,
+                author_ids,
+                Isbn::new(String::new())?,
+                ReadFlag::new(false),
+                OwnedFlag::new(false),
+                Priority::new(priority)?,
+                BookFormat::Printed,
+                BookStore::Unknown,
+                at,
+                at,
+            )
+        };
+        // The co-authored book sorts by its smallest reading (あくたがわ).
+        let kokoro = new_book(
+            "675bc8d9-3155-42fb-87b0-0a82cb162848",
+            "Kokoro",
+            vec![natsume.id().clone()],
+            30,
+        )?;
+        let anthology = new_book(
+            "c5a81e57-bc91-40ff-8b57-18cfa7cc7ae8",
+            "Anthology",
+            vec![akutagawa.id().clone(), mori.id().clone()],
+            90,
+        )?;
+        let maihime = new_book(
+            "f1c1d7a4-5e3b-4c8e-9a57-2a3f4e5d6c7b",
+            "Maihime",
+            vec![mori.id().clone()],
+            60,
+        )?;
+        let untitled = new_book(
+            "0b7ab1f1-1d4a-4b9c-8f3e-3c2d1e0f9a8b",
+            "Untitled",
+            vec![],
+            60,
+        )?;
+        for book in [&kokoro, &anthology, &maihime, &untitled] {
+            create_book(&pool, &book_repository, &user_id, book).await?;
+        }
+
+        let cases = vec![
+            (
+                BookOrder {
+                    key: BookSortKey::Title,
+                    direction: SortDirection::Desc,
+                },
+                vec![&untitled, &maihime, &kokoro, &anthology],
+            ),
+            // Ties on priority fall back to ids in the same direction.
+            (
+                BookOrder {
+                    key: BookSortKey::Priority,
+                    direction: SortDirection::Desc,
+                },
+                vec![&anthology, &maihime, &untitled, &kokoro],
+            ),
+            // Books without authors have an empty reading and come first.
+            (
+                BookOrder {
+                    key: BookSortKey::AuthorYomi,
+                    direction: SortDirection::Asc,
+                },
+                vec![&untitled, &anthology, &kokoro, &maihime],
+            ),
+        ];
+        for (order, expected) in cases {
+            let expected = expected.into_iter().cloned().collect::<Vec<_>>();
+
+            let all = book_repository
+                .find_all(&user_id, &BookFilter::default(), &order)
+                .await?;
+            assert_eq!(all, expected, "find_all with {order:?}");
+
+            // Walk the listing two at a time in both directions.
+            let first = book_repository
+                .find_page(
+                    &user_id,
+                    &BookFilter::default(),
+                    &order,
+                    &PageRequest::forward(2),
+                )
+                .await?;
+            let second = book_repository
+                .find_page(
+                    &user_id,
+                    &BookFilter::default(),
+                    &order,
+                    &PageRequest {
+                        after: Some(first.edges[1].cursor.clone()),
+                        ..PageRequest::forward(2)
+                    },
+                )
+                .await?;
+            let walked = first
+                .nodes()
+                .chain(second.nodes())
+                .cloned()
+                .collect::<Vec<_>>();
+            assert_eq!(walked, expected, "forward pages with {order:?}");
+            assert!(!second.has_next_page);
+
+            let previous = book_repository
+                .find_page(
+                    &user_id,
+                    &BookFilter::default(),
+                    &order,
+                    &PageRequest {
+                        after: None,
+                        before: Some(second.edges[0].cursor.clone()),
+                        limit: 2,
+                        direction: PageDirection::Backward,
+                    },
+                )
+                .await?;
+            assert_eq!(
+                previous.nodes().cloned().collect::<Vec<_>>(),
+                expected[..2],
+                "backward page with {order:?}"
+            );
+            assert!(!previous.has_previous_page);
+        }
+
+        let by_author = book_repository
+            .find_by_author_ids_as_hash_map(
+                &user_id,
+                &[mori.id().clone()],
+                &BookOrder {
+                    key: BookSortKey::Title,
+                    direction: SortDirection::Asc,
+                },
+            )
+            .await?;
+        assert_eq!(by_author[mori.id()], vec![anthology, maihime]);
+
+        Ok(())
+    }
+
+    #[sqlx::test]
+    async fn find_page_rejects_cursor_of_another_order(pool: PgPool) -> anyhow::Result<()> {
+        let user_repository = PgUserRepository::new(pool.clone());
+        let book_repository = PgBookRepository::new(pool.clone());
+        let user_id = prepare_user(&user_repository, "user1").await?;
+
+        let result = book_repository
+            .find_page(
+                &user_id,
+                &BookFilter::default(),
+                &BookOrder {
+                    key: BookSortKey::Title,
+                    direction: SortDirection::Asc,
+                },
+                &PageRequest {
+                    after: Some(BookCursor {
+                        value: BookSortValue::Priority(50),
+                        id: BookId::try_from("675bc8d9-3155-42fb-87b0-0a82cb162848")?,
+                    }),
+                    ..PageRequest::forward(2)
+                },
+            )
+            .await;
+        assert!(matches!(result, Err(DomainError::Validation(_))));
+
+        Ok(())
+    }
+
+    #[sqlx::test]
+    async fn search_matches_titles_and_authors_across_kana_and_width(
+        pool: PgPool,
+    ) -> anyhow::Result<()> {
+        let user_repository = PgUserRepository::new(pool.clone());
+        let author_repository = PgAuthorRepository::new(pool.clone());
+        let book_repository = PgBookRepository::new(pool.clone());
+
+        let user_id = prepare_user(&user_repository, "user1").await?;
+        let other_user_id = prepare_user(&user_repository, "user2").await?;
+        let natsume = Author::new_with_yomi(
+            AuthorId::try_from("278935cf-ed83-4346-9b35-b84bbdb630c0")?,
+            AuthorName::new("夏目漱石".to_owned())?,
+            "なつめそうせき".to_owned(),
+            OffsetDateTime::UNIX_EPOCH,
+        )?;
+        create_author(&pool, &author_repository, &user_id, &natsume).await?;
+
+        let at = PrimitiveDateTime::new(date!(2022 - 05 - 05), time!(0:00)).assume_utc();
+        let new_book = |id: &str, title: &str, author_ids: Vec<AuthorId>| {
+            Book::new(
+                BookId::try_from(id)?,
+                BookTitle::new(title.to_owned())?,
+                author_ids,
+                Isbn::new(String::new())?,
+                ReadFlag::new(false),
+                OwnedFlag::new(false),
+                Priority::new(50)?,
+                BookFormat::Printed,
+                BookStore::Unknown,
+                at,
+                at,
+            )
+        };
+        let kokoro = new_book(
+            "675bc8d9-3155-42fb-87b0-0a82cb162848",
+            "こころ",
+            vec![natsume.id().clone()],
+        )?;
+        let neko = new_book(
+            "c5a81e57-bc91-40ff-8b57-18cfa7cc7ae8",
+            "吾輩は猫である",
+            vec![natsume.id().clone()],
+        )?;
+        let rust_in_action = new_book(
+            "f1c1d7a4-5e3b-4c8e-9a57-2a3f4e5d6c7b",
+            "Rust in Action",
+            vec![],
+        )?;
+        let programming_rust = new_book(
+            "0b7ab1f1-1d4a-4b9c-8f3e-3c2d1e0f9a8b",
+            "ＰＲＯＧＲＡＭＭＩＮＧ ＲＵＳＴ",
+            vec![],
+        )?;
+        for book in [&kokoro, &neko, &rust_in_action, &programming_rust] {
+            create_book(&pool, &book_repository, &user_id, book).await?;
+        }
+        let other_book = new_book("9a0f5b3e-2c1d-4e6f-8a7b-6c5d4e3f2a1b", "こころ", vec![])?;
+        create_book(&pool, &book_repository, &other_user_id, &other_book).await?;
+
+        let cases = vec![
+            // Half-width katakana finds a hiragana title.
+            ("ｺｺﾛ", vec![&kokoro]),
+            // Full-width titles match case-insensitively; the prefix match ranks first.
+            ("rust", vec![&rust_in_action, &programming_rust]),
+            // Author yomi, written in katakana.
+            ("ナツメ", vec![&kokoro, &neko]),
+            // Every term must match, in the title or an author.
+            ("夏目　猫", vec![&neko]),
+            ("100%", vec![]),
+        ];
+        for (raw, expected) in cases {
+            let actual = book_repository
+                .search(&user_id, &SearchQuery::new(raw)?, 10)
+                .await?;
+            let expected = expected.into_iter().cloned().collect::<Vec<_>>();
+            assert_eq!(actual, expected, "search for {raw:?}");
+        }
+
+        let limited = book_repository
+            .search(&user_id, &SearchQuery::new("なつめ")?, 1)
+            .await?;
+        assert_eq!(limited.len(), 1);
+
+        Ok(())
+    }
+
+    #[sqlx::test]
+    async fn library_stats_aggregates_the_users_books(pool: PgPool) -> anyhow::Result<()> {
+        let user_repository = PgUserRepository::new(pool.clone());
+        let author_repository = PgAuthorRepository::new(pool.clone());
+        let book_repository = PgBookRepository::new(pool.clone());
+
+        let user_id = prepare_user(&user_repository, "user1").await?;
+        let other_user_id = prepare_user(&user_repository, "user2").await?;
+        let author_ids = prepare_authors1(&pool, &user_id, &author_repository).await?;
+        prepare_authors2(&pool, &user_id, &author_repository).await?;
+
+        let at = PrimitiveDateTime::new(date!(2022 - 05 - 05), time!(0:00)).assume_utc();
+        let new_book = |id: &str,
+                        author_ids: &[AuthorId],
+                        read: bool,
+                        owned: bool,
+                        priority: i32,
+                        format: BookFormat,
+                        store: BookStore| {
+            Book::new(
+                BookId::try_from(id)?,
+                BookTitle::new("title".to_owned())?,
+                author_ids.to_vec(),
+                Isbn::new(String::new())?,
+                ReadFlag::new(read),
+                OwnedFlag::new(owned),
+                Priority::new(priority)?,
+                format,
+                store,
+                at,
+                at,
+            )
+        };
+        let books = [
+            new_book(
+                "675bc8d9-3155-42fb-87b0-0a82cb162848",
+                &author_ids,
+                true,
+                true,
+                100,
+                BookFormat::Printed,
+                BookStore::Unknown,
+            )?,
+            new_book(
+                "c5a81e57-bc91-40ff-8b57-18cfa7cc7ae8",
+                &author_ids[..1],
+                false,
+                true,
+                5,
+                BookFormat::EBook,
+                BookStore::Kindle,
+            )?,
+            new_book(
+                "f1c1d7a4-5e3b-4c8e-9a57-2a3f4e5d6c7b",
+                &[],
+                false,
+                false,
+                95,
+                BookFormat::EBook,
+                BookStore::Kindle,
+            )?,
+        ];
+        for book in &books {
+            create_book(&pool, &book_repository, &user_id, book).await?;
+        }
+        let other_author_ids = prepare_authors1(&pool, &other_user_id, &author_repository).await?;
+        create_book(
+            &pool,
+            &book_repository,
+            &other_user_id,
+            &book_entity1(&other_author_ids)?,
+        )
+        .await?;
+
+        let stats = book_repository.library_stats(&user_id, 10).await?;
+
+        assert_eq!(stats.total_count, 3);
+        assert_eq!(stats.read_count, 1);
+        assert_eq!(stats.owned_count, 2);
+        assert_eq!(stats.unread_owned_count, 1);
+        let format_count = |format: BookFormat| {
+            stats
+                .format_counts
+                .iter()
+                .find(|(f, _)| *f == format)
+                .map(|(_, count)| *count)
+        };
+        assert_eq!(stats.format_counts.len(), 3);
+        assert_eq!(format_count(BookFormat::EBook), Some(2));
+        assert_eq!(format_count(BookFormat::Printed), Some(1));
+        assert_eq!(format_count(BookFormat::Unknown), Some(0));
+        assert_eq!(stats.store_counts.len(), 2);
+        assert!(stats.store_counts.contains(&(BookStore::Kindle, 2)));
+        assert!(stats.store_counts.contains(&(BookStore::Unknown, 1)));
+
+        assert_eq!(stats.priority_histogram.len(), 10);
+        assert_eq!(
+            stats.priority_histogram[0],
+            PriorityBucket {
+                min: 0,
+                max: 9,
+                count: 1
+            }
+        );
+        assert_eq!(
+            stats.priority_histogram[9],
+            PriorityBucket {
+                min: 90,
+                max: 100,
+                count: 2
+            }
+        );
+        assert_eq!(
+            stats
+                .priority_histogram
+                .iter()
+                .map(|bucket| bucket.count)
+                .sum::<i64>(),
+            3
+        );
+
+        // author3 has no books and is left out.
+        let top_authors = stats
+            .top_authors
+            .iter()
+            .map(|(author, count)| (author.id().clone(), *count))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            top_authors,
+            vec![(author_ids[0].clone(), 2), (author_ids[1].clone(), 1)]
+        );
+
+        let stats = book_repository.library_stats(&user_id, 1).await?;
+        assert_eq!(stats.top_authors.len(), 1);
+
+        Ok(())
+    }
+
+    #[sqlx::test]
+    async fn find_all_and_find_page_apply_filter(pool: PgPool) -> anyhow::Result<()> {
+        let user_repository = PgUserRepository::new(pool.clone());
+        let author_repository = PgAuthorRepository::new(pool.clone());
+        let book_repository = PgBookRepository::new(pool.clone());
+
+        let user_id = prepare_user(&user_repository, "user1").await?;
+        let author_ids1 = prepare_authors1(&pool, &user_id, &author_repository).await?;
+        let author_ids2 = prepare_authors2(&pool, &user_id, &author_repository).await?;
+
+        let book1 = book_entity1(&author_ids1[..1])?;
+        let june = PrimitiveDateTime::new(date!(2022 - 06 - 01), time!(0:00)).assume_utc();
+        let july = PrimitiveDateTime::new(date!(2022 - 07 - 01), time!(0:00)).assume_utc();
+        let book2 = Book::new(
+            BookId::try_from("c5a81e57-bc91-40ff-8b57-18cfa7cc7ae8")?,
+            BookTitle::new("title2".to_owned())?,
+            vec![author_ids1[1].clone(), author_ids2[0].clone()],
+            Isbn::new(String::new())?,
+            ReadFlag::new(true),
+            OwnedFlag::new(true),
+            Priority::new(80)?,
+            BookFormat::Printed,
+            BookStore::Unknown,
+            june,
+            july,
+        )?;
+        create_book(&pool, &book_repository, &user_id, &book1).await?;
+        create_book(&pool, &book_repository, &user_id, &book2).await?;
+
+        let cases = vec![
+            (
+                BookFilter {
+                    read: Some(true),
+                    ..Default::default()
+                },
+                vec![&book2],
+            ),
+            (
+                BookFilter {
+                    owned: Some(false),
+                    ..Default::default()
+                },
+                vec![&book1],
+            ),
+            (
+                BookFilter {
+                    format: Some(BookFormat::Printed),
+                    ..Default::default()
+                },
+                vec![&book2],
+            ),
+            (
+                BookFilter {
+                    store: Some(BookStore::Kindle),
+                    ..Default::default()
+                },
+                vec![&book1],
+            ),
+            (
+                BookFilter {
+                    priority_min: Some(60),
+                    ..Default::default()
+                },
+                vec![&book2],
+            ),
+            (
+                BookFilter {
+                    priority_min: Some(50),
+                    priority_max: Some(80),
+                    ..Default::default()
+                },
+                vec![&book1, &book2],
+            ),
+            (
+                BookFilter {
+                    priority_max: Some(50),
+                    ..Default::default()
+                },
+                vec![&book1],
+            ),
+            (
+                BookFilter {
+                    author_ids: Some(vec![author_ids2[0].clone()]),
+                    ..Default::default()
+                },
+                vec![&book2],
+            ),
+            (
+                BookFilter {
+                    author_ids: Some(vec![author_ids1[0].clone(), author_ids2[0].clone()]),
+                    ..Default::default()
+                },
+                vec![&book1, &book2],
+            ),
+            (
+                BookFilter {
+                    author_ids: Some(vec![]),
+                    ..Default::default()
+                },
+                vec![],
+            ),
+            (
+                BookFilter {
+                    created_from: Some(june),
+                    ..Default::default()
+                },
+                vec![&book2],
+            ),
+            (
+                BookFilter {
+                    created_to: Some(june),
+                    ..Default::default()
+                },
+                vec![&book1],
+            ),
+            (
+                BookFilter {
+                    updated_from: Some(july),
+                    ..Default::default()
+                },
+                vec![&book2],
+            ),
+            (
+                BookFilter {
+                    updated_to: Some(july),
+                    ..Default::default()
+                },
+                vec![&book1],
+            ),
+            (
+                BookFilter {
+                    has_isbn: Some(true),
+                    ..Default::default()
+                },
+                vec![&book1],
+            ),
+            (
+                BookFilter {
+                    has_isbn: Some(false),
+                    read: Some(false),
+                    ..Default::default()
+                },
+                vec![],
+            ),
+        ];
+        for (filter, expected) in cases {
+            let expected: Vec<Book> = expected.into_iter().cloned().collect();
+            let actual = book_repository
+                .find_all(&user_id, &filter, &BookOrder::default())
+                .await?;
+            assert_eq!(actual, expected, "find_all with {filter:?}");
+
+            let page = book_repository
+                .find_page(
+                    &user_id,
+                    &filter,
+                    &BookOrder::default(),
+                    &PageRequest::forward(1),
+                )
+                .await?;
+            assert_eq!(page.total_count, expected.len() as i64, "{filter:?}");
+            assert_eq!(
+                page.nodes().cloned().collect::<Vec<_>>(),
+                expected.into_iter().take(1).collect::<Vec<_>>()
+            );
+        }
+
+        Ok(())
+    }
+
     #[sqlx::test]
     async fn find_by_author_ids_groups_books_and_preserves_scope(
         pool: PgPool,
@@ -936,7 +2305,7 @@ mod tests {
             empty_author_id.clone(),
         ];
         let result = book_repository
-            .find_by_author_ids_as_hash_map(&user1_id, &requested_author_ids)
+            .find_by_author_ids_as_hash_map(&user1_id, &requested_author_ids, &BookOrder::default())
             .await?;
 
         assert_eq!(result[&user1_author_ids[0]].len(), 2);
@@ -1154,7 +2523,9 @@ mod tests {
         create_book(&pool, &book_repository, &user2_id, &book2).await?;
 
         // user1's find_all must contain only user1's authors
-        let user1_books = book_repository.find_all(&user1_id).await?;
+        let user1_books = book_repository
+            .find_all(&user1_id, &BookFilter::default(), &BookOrder::default())
+            .await?;
         assert_eq!(user1_books.len(), 1);
         assert!(
             user1_author_ids
@@ -1169,7 +2540,9 @@ mod tests {
         );
 
         // user2's find_all must contain only user2's authors
-        let user2_books = book_repository.find_all(&user2_id).await?;
+        let user2_books = book_repository
+            .find_all(&user2_id, &BookFilter::default(), &BookOrder::default())
+            .await?;
         assert_eq!(user2_books.len(), 1);
         assert!(
             user2_author_ids
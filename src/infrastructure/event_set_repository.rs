@@ -1,5 +1,5 @@
 use async_trait::async_trait;
-use sqlx::PgPool;
+use sqlx::{PgPool, QueryBuilder};
 use time::OffsetDateTime;
 use uuid::Uuid;
 
@@ -10,8 +10,13 @@ use crate::domain::{
         user::UserId,
     },
     error::DomainError,
-    repository::event_set_repository::EventSetRepository,
+    repository::{
+        event_set_repository::EventSetRepository,
+        history::{EventSetCursor, HistoryFilter},
+        pagination::{Edge, Page, PageRequest},
+    },
 };
+use crate::infrastructure::history::{push_history_filter, push_history_window};
 
 #[derive(sqlx::FromRow)]
 struct EventSetRow {
@@ -58,6 +63,41 @@ impl EventSetRepository for PgEventSetRepository {
         rows.into_iter().map(row_to_event_set).collect()
     }
 
+    async fn find_page(
+        &self,
+        user_id: &UserId,
+        filter: &HistoryFilter<EventSetOperation>,
+        page: &PageRequest<EventSetCursor>,
+    ) -> Result<Page<EventSet, EventSetCursor>, DomainError> {
+        let mut count = QueryBuilder::new("SELECT count(*) FROM event_set WHERE user_id = ");
+        count.push_bind(user_id.as_str().to_owned());
+        push_history_filter(&mut count, "created_at", filter, EventSetOperation::as_str);
+        let (total_count,): (i64,) = count.build_query_as().fetch_one(&self.pool).await?;
+
+        let mut query = QueryBuilder::new(
+            "SELECT id, user_id, operation, created_at FROM event_set WHERE user_id = ",
+        );
+        query.push_bind(user_id.as_str().to_owned());
+        push_history_filter(&mut query, "created_at", filter, EventSetOperation::as_str);
+        push_history_window(&mut query, "created_at", "id", page, EventSetId::to_uuid);
+        let rows: Vec<EventSetRow> = query.build_query_as().fetch_all(&self.pool).await?;
+        let edges = rows
+            .into_iter()
+            .map(|row| {
+                let event_set = row_to_event_set(row)?;
+                Ok(Edge {
+                    cursor: EventSetCursor {
+                        at: event_set.created_at,
+                        id: event_set.id.clone(),
+                    },
+                    node: event_set,
+                })
+            })
+            .collect::<Result<Vec<_>, DomainError>>()?;
+
+        Ok(Page::from_rows(edges, page, total_count))
+    }
+
     async fn find_by_id(
         &self,
         user_id: &UserId,
@@ -198,6 +238,76 @@ mod tests {
         Ok(())
     }
 
+    #[sqlx::test]
+    async fn find_page_filters_by_operation_and_walks_newest_first(
+        pool: PgPool,
+    ) -> anyhow::Result<()> {
+        let user_repo = PgUserRepository::new(pool.clone());
+        let author_repo = PgAuthorRepository::new(pool.clone());
+        let book_repo = PgBookRepository::new(pool.clone());
+        let event_set_repo = PgEventSetRepository::new(pool.clone());
+
+        let user_id = prepare_user(&user_repo, "user1").await?;
+        let author_id = AuthorId::try_from("278935cf-ed83-4346-9b35-b84bbdb630c0")?;
+        create_author(
+            &pool,
+            &author_repo,
+            &user_id,
+            &Author::new(
+                author_id.clone(),
+                AuthorName::new("author1".to_owned())?,
+                OffsetDateTime::UNIX_EPOCH,
+            )?,
+        )
+        .await?;
+        for (id, title) in [
+            ("675bc8d9-3155-42fb-87b0-0a82cb162848", "title1"),
+            ("cf6e4e5d-ff7a-4d2f-b2f4-0e0fc5f1e1c4", "title2"),
+        ] {
+            let book = make_book(id, title, std::slice::from_ref(&author_id))?;
+            create_book(&pool, &book_repo, &user_id, &book).await?;
+        }
+        let all = event_set_repo.find_all(&user_id).await?;
+        let filter = HistoryFilter {
+            operations: Some(vec![EventSetOperation::CreateBook]),
+            ..HistoryFilter::default()
+        };
+
+        let first = event_set_repo
+            .find_page(&user_id, &filter, &PageRequest::forward(1))
+            .await?;
+        assert_eq!(first.total_count, 2);
+        assert!(first.has_next_page);
+        assert_eq!(first.edges[0].node.id, all[0].id);
+
+        let second = event_set_repo
+            .find_page(
+                &user_id,
+                &filter,
+                &PageRequest {
+                    after: Some(first.edges[0].cursor.clone()),
+                    ..PageRequest::forward(1)
+                },
+            )
+            .await?;
+        assert!(!second.has_next_page);
+        assert!(second.has_previous_page);
+        assert_eq!(second.edges[0].node.id, all[1].id);
+
+        // The window ends before the author's event set was created.
+        let before_author = HistoryFilter {
+            to: Some(all[2].created_at),
+            ..HistoryFilter::default()
+        };
+        let empty = event_set_repo
+            .find_page(&user_id, &before_author, &PageRequest::forward(10))
+            .await?;
+        assert_eq!(empty.total_count, 0);
+        assert!(empty.edges.is_empty());
+
+        Ok(())
+    }
+
     #[sqlx::test]
     async fn find_all_returns_empty_for_user_without_events(pool: PgPool) -> anyhow::Result<()> {
         let user_repo = PgUserRepository::new(pool.clone());
@@ -0,0 +1,62 @@
+use sqlx::{Encode, Postgres, QueryBuilder, Type};
+
+use crate::domain::repository::{
+    history::{HistoryCursor, HistoryFilter},
+    pagination::{PageDirection, PageRequest},
+};
+
+// Appends the conditions of `filter` to a query over a table with an
+// `operation` column and a `time_column` recording when each row was written.
+pub fn push_history_filter<O>(
+    query: &mut QueryBuilder<Postgres>,
+    time_column: &str,
+    filter: &HistoryFilter<O>,
+    operation_name: fn(&O) -> &'static str,
+) {
+    if let Some(operations) = &filter.operations {
+        let operations: Vec<&str> = operations.iter().map(operation_name).collect();
+        query
+            .push(" AND operation = ANY(")
+            .push_bind(operations)
+            .push(")");
+    }
+    if let Some(from) = filter.from {
+        query
+            .push(format!(" AND {time_column} >= "))
+            .push_bind(from);
+    }
+    if let Some(to) = filter.to {
+        query.push(format!(" AND {time_column} < ")).push_bind(to);
+    }
+}
+
+// Appends the keyset bounds, ordering and limit of a page over a listing
+// ordered newest first by (time_column, id_column).
+pub fn push_history_window<I, V>(
+    query: &mut QueryBuilder<Postgres>,
+    time_column: &str,
+    id_column: &str,
+    page: &PageRequest<HistoryCursor<I>>,
+    id_value: fn(&I) -> V,
+) where
+    V: for<'q> Encode<'q, Postgres> + Type<Postgres> + Send + 'static,
+{
+    for (cursor, operator) in [(&page.after, "<"), (&page.before, ">")] {
+        if let Some(cursor) = cursor {
+            query
+                .push(format!(" AND ({time_column}, {id_column}) {operator} ("))
+                .push_bind(cursor.at)
+                .push(", ")
+                .push_bind(id_value(&cursor.id))
+                .push(")");
+        }
+    }
+    let direction = match page.direction {
+        PageDirection::Forward => "DESC",
+        PageDirection::Backward => "ASC",
+    };
+    query.push(format!(
+        " ORDER BY {time_column} {direction}, {id_column} {direction} LIMIT "
+    ));
+    query.push_bind(page.fetch_limit());
+}
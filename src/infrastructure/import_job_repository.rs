@@ -0,0 +1,558 @@
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use sqlx::{PgConnection, PgPool, types::Json};
+use time::OffsetDateTime;
+use uuid::Uuid;
+
+use crate::{
+    common::types::{BookFormat, BookStore},
+    domain::{
+        entity::{
+            event_set::EventSetId,
+            import_job::{
+                ImportEntry, ImportField, ImportJob, ImportJobFailure, ImportJobId,
+                ImportJobStatus, ImportMode,
+            },
+            user::UserId,
+        },
+        error::DomainError,
+        repository::import_job_repository::ImportJobRepository,
+    },
+    infrastructure::transaction::PgTransaction,
+};
+
+// The shape an entry is stored in, inside the job's `entries` array.
+#[derive(Serialize, Deserialize)]
+struct EntryRecord {
+    title: String,
+    author_names: Vec<String>,
+    isbn: String,
+    read: bool,
+    owned: bool,
+    priority: i32,
+    format: String,
+    store: String,
+}
+
+#[derive(Serialize, Deserialize)]
+struct FailureRecord {
+    index: usize,
+    field: String,
+    message: String,
+}
+
+#[derive(sqlx::FromRow)]
+struct ImportJobRow {
+    id: Uuid,
+    user_id: String,
+    status: String,
+    mode: String,
+    partial: bool,
+    total_count: i32,
+    processed_count: i32,
+    event_set_ids: Vec<Uuid>,
+    failures: Json<Vec<FailureRecord>>,
+    error: Option<String>,
+    claimed_by: Option<Uuid>,
+    created_at: OffsetDateTime,
+    updated_at: OffsetDateTime,
+}
+
+#[derive(sqlx::FromRow)]
+struct ImportJobRunRow {
+    #[sqlx(flatten)]
+    job: ImportJobRow,
+    entries: Json<Vec<EntryRecord>>,
+}
+
+fn row_to_import_job(row: ImportJobRow) -> Result<ImportJob, DomainError> {
+    let failures = row
+        .failures
+        .0
+        .into_iter()
+        .map(|failure| {
+            Ok(ImportJobFailure {
+                index: failure.index,
+                field: ImportField::try_from(failure.field.as_str())
+                    .map_err(DomainError::Unexpected)?,
+                message: failure.message,
+            })
+        })
+        .collect::<Result<Vec<_>, DomainError>>()?;
+
+    Ok(ImportJob {
+        id: ImportJobId::from(row.id),
+        user_id: UserId::new(row.user_id)?,
+        status: ImportJobStatus::try_from(row.status.as_str()).map_err(DomainError::Unexpected)?,
+        mode: ImportMode::try_from(row.mode.as_str()).map_err(DomainError::Unexpected)?,
+        partial: row.partial,
+        total_count: row.total_count as usize,
+        processed_count: row.processed_count as usize,
+        event_set_ids: row
+            .event_set_ids
+            .into_iter()
+            .map(EventSetId::from)
+            .collect(),
+        failures,
+        error: row.error,
+        claimed_by: row.claimed_by,
+        created_at: row.created_at,
+        updated_at: row.updated_at,
+    })
+}
+
+fn record_to_entry(record: EntryRecord) -> Result<ImportEntry, DomainError> {
+    Ok(ImportEntry {
+        title: record.title,
+        author_names: record.author_names,
+        isbn: record.isbn,
+        read: record.read,
+        owned: record.owned,
+        priority: record.priority,
+        format: BookFormat::try_from(record.format.as_str())?,
+        store: BookStore::try_from(record.store.as_str())?,
+    })
+}
+
+fn entry_to_record(entry: &ImportEntry) -> EntryRecord {
+    EntryRecord {
+        title: entry.title.clone(),
+        author_names: entry.author_names.clone(),
+        isbn: entry.isbn.clone(),
+        read: entry.read,
+        owned: entry.owned,
+        priority: entry.priority,
+        format: entry.format.to_string(),
+        store: entry.store.to_string(),
+    }
+}
+
+fn failure_records(job: &ImportJob) -> Vec<FailureRecord> {
+    job.failures
+        .iter()
+        .map(|failure| FailureRecord {
+            index: failure.index,
+            field: failure.field.as_str().to_owned(),
+            message: failure.message.clone(),
+        })
+        .collect()
+}
+
+#[derive(Debug, Clone)]
+pub struct PgImportJobRepository {
+    pool: PgPool,
+}
+
+impl PgImportJobRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    async fn save_on(&self, conn: &mut PgConnection, job: &ImportJob) -> Result<bool, DomainError> {
+        let event_set_ids: Vec<Uuid> = job.event_set_ids.iter().map(EventSetId::to_uuid).collect();
+        let result = sqlx::query(
+            "UPDATE import_job
+             SET status = $1, processed_count = $2, event_set_ids = $3, failures = $4,
+                 error = $5, updated_at = $6
+             WHERE id = $7 AND claimed_by = $8 AND status IN ('pending', 'running')",
+        )
+        .bind(job.status.as_str())
+        .bind(job.processed_count as i32)
+        .bind(event_set_ids)
+        .bind(Json(failure_records(job)))
+        .bind(&job.error)
+        .bind(job.updated_at)
+        .bind(job.id.to_uuid())
+        .bind(job.claimed_by)
+        .execute(conn)
+        .await?;
+
+        Ok(result.rows_affected() == 1)
+    }
+}
+
+#[async_trait]
+impl ImportJobRepository for PgImportJobRepository {
+    type Transaction = PgTransaction;
+
+    async fn create(&self, job: &ImportJob, entries: &[ImportEntry]) -> Result<(), DomainError> {
+        let entries: Vec<EntryRecord> = entries.iter().map(entry_to_record).collect();
+        sqlx::query(
+            "INSERT INTO import_job
+               (id, user_id, status, mode, partial, entries, total_count, created_at, updated_at)
+             VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)",
+        )
+        .bind(job.id.to_uuid())
+        .bind(job.user_id.as_str())
+        .bind(job.status.as_str())
+        .bind(job.mode.as_str())
+        .bind(job.partial)
+        .bind(Json(entries))
+        .bind(job.total_count as i32)
+        .bind(job.created_at)
+        .bind(job.updated_at)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn find_by_id(
+        &self,
+        user_id: &UserId,
+        job_id: &ImportJobId,
+    ) -> Result<Option<ImportJob>, DomainError> {
+        let row: Option<ImportJobRow> = sqlx::query_as(
+            "SELECT id, user_id, status, mode, partial, total_count, processed_count,
+                    event_set_ids, failures, error, claimed_by, created_at, updated_at
+             FROM import_job
+             WHERE user_id = $1 AND id = $2",
+        )
+        .bind(user_id.as_str())
+        .bind(job_id.to_uuid())
+        .fetch_optional(&self.pool)
+        .await?;
+
+        row.map(row_to_import_job).transpose()
+    }
+
+    async fn claim(
+        &self,
+        worker_id: Uuid,
+        excluded_user_ids: &[UserId],
+        stale_before: OffsetDateTime,
+        now: OffsetDateTime,
+    ) -> Result<Option<(ImportJob, Vec<ImportEntry>)>, DomainError> {
+        let excluded_user_ids: Vec<&str> = excluded_user_ids.iter().map(UserId::as_str).collect();
+        // SKIP LOCKED lets workers claiming at the same time take different
+        // jobs instead of waiting for each other.
+        let row: Option<ImportJobRunRow> = sqlx::query_as(
+            "UPDATE import_job
+             SET status = 'running', claimed_by = $1, updated_at = $2
+             WHERE id = (
+                 SELECT id FROM import_job
+                 WHERE (status = 'pending' OR (status = 'running' AND updated_at < $3))
+                   AND user_id <> ALL($4)
+                 ORDER BY created_at, id
+                 LIMIT 1
+                 FOR UPDATE SKIP LOCKED
+             )
+             RETURNING id, user_id, status, mode, partial, total_count, processed_count,
+                       event_set_ids, failures, error, claimed_by, created_at, updated_at,
+                       entries",
+        )
+        .bind(worker_id)
+        .bind(now)
+        .bind(stale_before)
+        .bind(&excluded_user_ids)
+        .fetch_optional(&self.pool)
+        .await?;
+        let Some(row) = row else {
+            return Ok(None);
+        };
+
+        let entries = row
+            .entries
+            .0
+            .into_iter()
+            .map(record_to_entry)
+            .collect::<Result<Vec<_>, DomainError>>()?;
+
+        Ok(Some((row_to_import_job(row.job)?, entries)))
+    }
+
+    async fn renew(&self, job: &ImportJob, now: OffsetDateTime) -> Result<bool, DomainError> {
+        let result = sqlx::query(
+            "UPDATE import_job SET updated_at = $1
+             WHERE id = $2 AND claimed_by = $3 AND status = 'running'",
+        )
+        .bind(now)
+        .bind(job.id.to_uuid())
+        .bind(job.claimed_by)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.rows_affected() == 1)
+    }
+
+    async fn save(&self, job: &ImportJob) -> Result<bool, DomainError> {
+        let mut conn = self.pool.acquire().await?;
+        self.save_on(&mut conn, job).await
+    }
+
+    async fn save_with_tx(
+        &self,
+        tx: &mut Self::Transaction,
+        job: &ImportJob,
+    ) -> Result<bool, DomainError> {
+        self.save_on(tx.as_mut(), job).await
+    }
+
+    async fn cancel(
+        &self,
+        user_id: &UserId,
+        job_id: &ImportJobId,
+        now: OffsetDateTime,
+    ) -> Result<Option<ImportJob>, DomainError> {
+        sqlx::query(
+            "UPDATE import_job SET status = 'cancelled', updated_at = $1
+             WHERE user_id = $2 AND id = $3 AND status IN ('pending', 'running')",
+        )
+        .bind(now)
+        .bind(user_id.as_str())
+        .bind(job_id.to_uuid())
+        .execute(&self.pool)
+        .await?;
+
+        self.find_by_id(user_id, job_id).await
+    }
+}
+
+#[cfg(feature = "test-with-database")]
+#[cfg(test)]
+mod tests {
+    use time::Duration;
+
+    use crate::domain::entity::event::EventSetOperation;
+    use crate::{
+        domain::{
+            entity::user::User,
+            repository::{transaction::TransactionManager, user_repository::UserRepository},
+        },
+        infrastructure::{transaction::PgTransactionManager, user_repository::PgUserRepository},
+    };
+
+    use super::*;
+
+    async fn prepare_user(repository: &PgUserRepository, id: &str) -> Result<UserId, DomainError> {
+        let user_id = UserId::new(id.to_string())?;
+        let user = User::new(user_id.clone());
+        repository.create(&user).await?;
+        Ok(user_id)
+    }
+
+    fn make_entry(title: &str) -> ImportEntry {
+        ImportEntry {
+            title: title.to_owned(),
+            author_names: vec!["author1".to_owned()],
+            isbn: "".to_owned(),
+            read: false,
+            owned: true,
+            priority: 50,
+            format: BookFormat::Printed,
+            store: BookStore::Unknown,
+        }
+    }
+
+    #[sqlx::test]
+    async fn create_and_claim_round_trips_entries(pool: PgPool) -> anyhow::Result<()> {
+        let user_repo = PgUserRepository::new(pool.clone());
+        let job_repo = PgImportJobRepository::new(pool.clone());
+        let user_id = prepare_user(&user_repo, "user1").await?;
+        let entries = vec![make_entry("title1"), make_entry("title2")];
+        let job = ImportJob::new(
+            user_id.clone(),
+            ImportMode::SkipExisting,
+            true,
+            entries.len(),
+            OffsetDateTime::UNIX_EPOCH,
+        );
+
+        job_repo.create(&job, &entries).await?;
+        assert_eq!(
+            job_repo.find_by_id(&user_id, &job.id).await?,
+            Some(job.clone())
+        );
+
+        let worker_id = Uuid::new_v4();
+        let now = OffsetDateTime::UNIX_EPOCH + Duration::hours(1);
+        let (claimed, claimed_entries) = job_repo
+            .claim(worker_id, &[], now - Duration::minutes(10), now)
+            .await?
+            .unwrap();
+        let mut expected = job.clone();
+        expected.set_status(ImportJobStatus::Running, now);
+        expected.claimed_by = Some(worker_id);
+        assert_eq!(claimed, expected);
+        assert_eq!(claimed_entries, entries);
+        assert_eq!(
+            job_repo.find_by_id(&user_id, &job.id).await?,
+            Some(expected)
+        );
+        let other_user_id = prepare_user(&user_repo, "user2").await?;
+        assert_eq!(job_repo.find_by_id(&other_user_id, &job.id).await?, None);
+        Ok(())
+    }
+
+    #[sqlx::test]
+    async fn claim_takes_over_only_running_jobs_left_unsaved(pool: PgPool) -> anyhow::Result<()> {
+        let user_repo = PgUserRepository::new(pool.clone());
+        let job_repo = PgImportJobRepository::new(pool.clone());
+        let user_id = prepare_user(&user_repo, "user1").await?;
+        let other_user_id = prepare_user(&user_repo, "user2").await?;
+        let job = ImportJob::new(
+            user_id.clone(),
+            ImportMode::CreateOnly,
+            true,
+            1,
+            OffsetDateTime::UNIX_EPOCH,
+        );
+        job_repo.create(&job, &[make_entry("title1")]).await?;
+        let other_job = ImportJob::new(
+            other_user_id.clone(),
+            ImportMode::CreateOnly,
+            true,
+            1,
+            OffsetDateTime::UNIX_EPOCH + Duration::seconds(1),
+        );
+        job_repo.create(&other_job, &[make_entry("title1")]).await?;
+
+        // Jobs of excluded users are left for later
+        let now = OffsetDateTime::UNIX_EPOCH + Duration::hours(1);
+        let stale_before = now - Duration::minutes(10);
+        let (claimed, _) = job_repo
+            .claim(
+                Uuid::new_v4(),
+                std::slice::from_ref(&user_id),
+                stale_before,
+                now,
+            )
+            .await?
+            .unwrap();
+        assert_eq!(claimed.id, other_job.id);
+
+        // A job another worker keeps saving is not claimed again
+        let first_worker_id = Uuid::new_v4();
+        let (mut claimed, _) = job_repo
+            .claim(first_worker_id, &[], stale_before, now)
+            .await?
+            .unwrap();
+        assert_eq!(claimed.id, job.id);
+        assert!(
+            job_repo
+                .claim(Uuid::new_v4(), &[], stale_before, now)
+                .await?
+                .is_none()
+        );
+
+        // Until it stops saving, and then the worker that took over saves it
+        let later = now + Duration::hours(1);
+        let second_worker_id = Uuid::new_v4();
+        let (mut taken_over, _) = job_repo
+            .claim(second_worker_id, &[], later - Duration::minutes(10), later)
+            .await?
+            .unwrap();
+        assert_eq!(taken_over.id, job.id);
+        assert_eq!(taken_over.claimed_by, Some(second_worker_id));
+        claimed.record_chunk(1, vec![], None, later);
+        assert!(!job_repo.save(&claimed).await?);
+        taken_over.record_chunk(1, vec![], None, later);
+        assert!(job_repo.save(&taken_over).await?);
+        Ok(())
+    }
+
+    #[sqlx::test]
+    async fn renew_keeps_a_claim_from_going_stale(pool: PgPool) -> anyhow::Result<()> {
+        let user_repo = PgUserRepository::new(pool.clone());
+        let job_repo = PgImportJobRepository::new(pool.clone());
+        let user_id = prepare_user(&user_repo, "user1").await?;
+        let job = ImportJob::new(
+            user_id.clone(),
+            ImportMode::CreateOnly,
+            true,
+            1,
+            OffsetDateTime::UNIX_EPOCH,
+        );
+        job_repo.create(&job, &[make_entry("title1")]).await?;
+        let now = OffsetDateTime::UNIX_EPOCH;
+        let (claimed, _) = job_repo
+            .claim(Uuid::new_v4(), &[], now - Duration::minutes(1), now)
+            .await?
+            .unwrap();
+
+        // A claim renewed since the lease began is not taken over
+        let later = now + Duration::minutes(2);
+        assert!(job_repo.renew(&claimed, later).await?);
+        assert!(
+            job_repo
+                .claim(Uuid::new_v4(), &[], later - Duration::minutes(1), later)
+                .await?
+                .is_none()
+        );
+
+        // Once it is taken over, the first worker can no longer renew it
+        let latest = later + Duration::minutes(2);
+        let (taken_over, _) = job_repo
+            .claim(Uuid::new_v4(), &[], latest - Duration::minutes(1), latest)
+            .await?
+            .unwrap();
+        assert!(!job_repo.renew(&claimed, latest).await?);
+        assert!(job_repo.renew(&taken_over, latest).await?);
+        Ok(())
+    }
+
+    #[sqlx::test]
+    async fn save_with_tx_records_progress_until_the_job_is_cancelled(
+        pool: PgPool,
+    ) -> anyhow::Result<()> {
+        let user_repo = PgUserRepository::new(pool.clone());
+        let job_repo = PgImportJobRepository::new(pool.clone());
+        let tm = PgTransactionManager::new(pool.clone());
+        let user_id = prepare_user(&user_repo, "user1").await?;
+        let job = ImportJob::new(
+            user_id.clone(),
+            ImportMode::CreateOnly,
+            true,
+            2,
+            OffsetDateTime::UNIX_EPOCH,
+        );
+        job_repo
+            .create(&job, &[make_entry("title1"), make_entry("title2")])
+            .await?;
+        let now = OffsetDateTime::UNIX_EPOCH;
+        let (mut job, _) = job_repo
+            .claim(Uuid::new_v4(), &[], now - Duration::minutes(10), now)
+            .await?
+            .unwrap();
+
+        let mut tx = tm.begin(&user_id, EventSetOperation::ImportBooks).await?;
+        let event_set_id = EventSetId::from(tx.event_set_id());
+        job.record_chunk(
+            1,
+            vec![ImportJobFailure {
+                index: 0,
+                field: ImportField::Title,
+                message: "empty".to_owned(),
+            }],
+            Some(event_set_id),
+            OffsetDateTime::UNIX_EPOCH,
+        );
+        assert!(job_repo.save_with_tx(&mut tx, &job).await?);
+        tm.commit(tx).await?;
+        assert_eq!(
+            job_repo.find_by_id(&user_id, &job.id).await?,
+            Some(job.clone())
+        );
+
+        let cancelled = job_repo
+            .cancel(&user_id, &job.id, OffsetDateTime::UNIX_EPOCH)
+            .await?
+            .unwrap();
+        assert_eq!(cancelled.status, ImportJobStatus::Cancelled);
+        assert_eq!(cancelled.processed_count, 1);
+        let later = OffsetDateTime::UNIX_EPOCH + Duration::hours(1);
+        assert!(
+            job_repo
+                .claim(Uuid::new_v4(), &[], later, later)
+                .await?
+                .is_none()
+        );
+
+        job.record_chunk(1, vec![], None, OffsetDateTime::UNIX_EPOCH);
+        assert!(!job_repo.save(&job).await?);
+        let found = job_repo.find_by_id(&user_id, &job.id).await?.unwrap();
+        assert_eq!(found.status, ImportJobStatus::Cancelled);
+        assert_eq!(found.processed_count, 1);
+        Ok(())
+    }
+}
@@ -0,0 +1,44 @@
+use sqlx::{Postgres, QueryBuilder};
+
+use crate::domain::repository::search::SearchQuery;
+
+// Ranks a row by trigram similarity, putting prefix matches of the whole
+// query first. Pushes a numeric expression over `column`.
+pub fn push_search_rank(query: &mut QueryBuilder<Postgres>, column: &str, search: &SearchQuery) {
+    query
+        .push(format!("(({column} ILIKE "))
+        .push_bind(prefix_pattern(search.text()))
+        .push(format!(")::int + similarity({column}, "))
+        .push_bind(search.text().to_owned())
+        .push("))");
+}
+
+pub fn contains_pattern(term: &str) -> String {
+    format!("%{}%", escape_like(term))
+}
+
+pub fn prefix_pattern(text: &str) -> String {
+    format!("{}%", escape_like(text))
+}
+
+fn escape_like(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        if matches!(c, '\\' | '%' | '_') {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{contains_pattern, prefix_pattern};
+
+    #[test]
+    fn patterns_escape_like_wildcards() {
+        assert_eq!(contains_pattern("100%_\\"), "%100\\%\\_\\\\%");
+        assert_eq!(prefix_pattern("なつめ"), "なつめ%");
+    }
+}
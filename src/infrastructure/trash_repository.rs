@@ -0,0 +1,297 @@
+use std::collections::HashSet;
+
+use async_trait::async_trait;
+use time::OffsetDateTime;
+use uuid::Uuid;
+
+use crate::{
+    domain::{
+        error::DomainError,
+        repository::trash_repository::{PurgedTrash, TrashRepository},
+    },
+    infrastructure::transaction::PgTransaction,
+};
+
+// Purging only runs inside the use case's transaction, so the repository
+// needs no pool of its own.
+#[derive(Debug, Clone, Default)]
+pub struct PgTrashRepository;
+
+impl PgTrashRepository {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+#[async_trait]
+impl TrashRepository for PgTrashRepository {
+    type Transaction = PgTransaction;
+
+    async fn purge(
+        &self,
+        tx: &mut PgTransaction,
+        deleted_before: OffsetDateTime,
+    ) -> Result<PurgedTrash, DomainError> {
+        let user_id = tx.user_id().as_str().to_owned();
+
+        // book_event_author rows go along with their events.
+        let book_rows: Vec<(Uuid, Uuid)> = sqlx::query_as(
+            "WITH latest AS (
+                SELECT DISTINCT ON (book_id) book_id, title, changed_at
+                FROM book_event
+                WHERE user_id = $1
+                ORDER BY book_id, changed_at DESC, event_id DESC
+            )
+            DELETE FROM book_event
+            WHERE user_id = $1
+              AND book_id IN (
+                  SELECT book_id FROM latest WHERE title IS NULL AND changed_at < $2
+              )
+            RETURNING book_id, event_set_id",
+        )
+        .bind(&user_id)
+        .bind(deleted_before)
+        .fetch_all(tx.as_mut())
+        .await?;
+
+        // Merge markers carry no state, so they neither keep an author alive
+        // nor survive it.
+        let author_rows: Vec<(Uuid, Uuid)> = sqlx::query_as(
+            "WITH latest AS (
+                SELECT DISTINCT ON (author_id) author_id, name, changed_at
+                FROM author_event
+                WHERE user_id = $1 AND operation <> 'merge_as_destination'
+                ORDER BY author_id, changed_at DESC, event_id DESC
+            )
+            DELETE FROM author_event
+            WHERE user_id = $1
+              AND author_id IN (
+                  SELECT author_id FROM latest WHERE name IS NULL AND changed_at < $2
+              )
+            RETURNING author_id, event_set_id",
+        )
+        .bind(&user_id)
+        .bind(deleted_before)
+        .fetch_all(tx.as_mut())
+        .await?;
+
+        // A destination that outlives a purged merge source would otherwise
+        // keep a marker naming an author with no history.
+        let purged_author_ids: Vec<String> = author_rows
+            .iter()
+            .map(|(author_id, _)| author_id.hyphenated().to_string())
+            .collect::<HashSet<_>>()
+            .into_iter()
+            .collect();
+        let marker_event_set_ids: Vec<Uuid> = sqlx::query_scalar(
+            "DELETE FROM author_event
+            WHERE user_id = $1
+              AND operation = 'merge_as_destination'
+              AND extra->>'source_author_id' = ANY($2)
+            RETURNING event_set_id",
+        )
+        .bind(&user_id)
+        .bind(&purged_author_ids)
+        .fetch_all(tx.as_mut())
+        .await?;
+
+        // Only event sets the purge emptied are removed. The purge's own set
+        // stays as the record that it happened.
+        let event_set_ids: Vec<Uuid> = book_rows
+            .iter()
+            .chain(&author_rows)
+            .map(|(_, event_set_id)| *event_set_id)
+            .chain(marker_event_set_ids)
+            .collect::<HashSet<_>>()
+            .into_iter()
+            .collect();
+        let event_set_count = sqlx::query(
+            "DELETE FROM event_set es
+             WHERE es.user_id = $1
+               AND es.id = ANY($2)
+               AND es.id <> $3
+               AND NOT EXISTS (SELECT 1 FROM book_event WHERE event_set_id = es.id)
+               AND NOT EXISTS (SELECT 1 FROM author_event WHERE event_set_id = es.id)",
+        )
+        .bind(&user_id)
+        .bind(&event_set_ids)
+        .bind(tx.event_set_id())
+        .execute(tx.as_mut())
+        .await?
+        .rows_affected() as i64;
+
+        let distinct = |rows: &[(Uuid, Uuid)]| {
+            rows.iter().map(|(id, _)| id).collect::<HashSet<_>>().len() as i64
+        };
+        Ok(PurgedTrash {
+            book_count: distinct(&book_rows),
+            author_count: purged_author_ids.len() as i64,
+            event_set_count,
+        })
+    }
+}
+
+#[cfg(feature = "test-with-database")]
+#[cfg(test)]
+mod tests {
+    use sqlx::PgPool;
+    use time::Duration;
+
+    use crate::{
+        common::types::{BookFormat, BookStore},
+        domain::{
+            entity::{
+                author::{Author, AuthorId, AuthorName},
+                book::{Book, BookId, BookTitle, Isbn, OwnedFlag, Priority, ReadFlag},
+                event::{EventOperation, EventSetOperation, NewAuthorEvent},
+                user::{User, UserId},
+            },
+            repository::{
+                author_event_repository::AuthorEventRepository,
+                author_repository::{AuthorRepository, DeleteAuthorEventExtra},
+                book_event_repository::BookEventRepository,
+                book_repository::BookRepository,
+                transaction::TransactionManager,
+                user_repository::UserRepository,
+            },
+        },
+        infrastructure::{
+            author_event_repository::PgAuthorEventRepository,
+            author_repository::PgAuthorRepository, book_event_repository::PgBookEventRepository,
+            book_repository::PgBookRepository, transaction::PgTransactionManager,
+            user_repository::PgUserRepository,
+        },
+    };
+
+    use super::*;
+
+    fn make_book(book_id: &str, title: &str) -> Result<Book, DomainError> {
+        Book::new(
+            BookId::try_from(book_id)?,
+            BookTitle::new(title.to_owned())?,
+            vec![],
+            Isbn::new("1111111111116".to_owned())?,
+            ReadFlag::new(false),
+            OwnedFlag::new(false),
+            Priority::new(50)?,
+            BookFormat::EBook,
+            BookStore::Kindle,
+            OffsetDateTime::UNIX_EPOCH,
+            OffsetDateTime::UNIX_EPOCH,
+        )
+    }
+
+    // Creates and then deletes the book, each in its own event set.
+    async fn create_and_delete_book(
+        tm: &PgTransactionManager,
+        book_repo: &PgBookRepository,
+        user_id: &UserId,
+        book: &Book,
+    ) -> Result<(), DomainError> {
+        let mut tx = tm.begin(user_id, EventSetOperation::CreateBook).await?;
+        book_repo.create(&mut tx, book).await?;
+        tm.commit(tx).await?;
+        let mut tx = tm.begin(user_id, EventSetOperation::DeleteBook).await?;
+        book_repo.delete(&mut tx, book.id()).await?;
+        tm.commit(tx).await
+    }
+
+    #[sqlx::test]
+    async fn purge_drops_history_deleted_before_the_cutoff(pool: PgPool) -> anyhow::Result<()> {
+        let user_repo = PgUserRepository::new(pool.clone());
+        let book_repo = PgBookRepository::new(pool.clone());
+        let author_repo = PgAuthorRepository::new(pool.clone());
+        let book_event_repo = PgBookEventRepository::new(pool.clone());
+        let author_event_repo = PgAuthorEventRepository::new(pool.clone());
+        let trash_repo = PgTrashRepository::new();
+        let tm = PgTransactionManager::new(pool.clone());
+        let user_id = UserId::new("user1".to_owned())?;
+        user_repo.create(&User::new(user_id.clone())).await?;
+
+        let old = make_book("675bc8d9-3155-42fb-87b0-0a82cb162848", "old")?;
+        create_and_delete_book(&tm, &book_repo, &user_id, &old).await?;
+        let source_id = AuthorId::try_from("278935cf-ed83-4346-9b35-b84bbdb630c0")?;
+        let destination_id = AuthorId::try_from("925aaf96-64c7-44be-85f8-767a20b2c20c")?;
+        for (author_id, name) in [(&source_id, "author1"), (&destination_id, "author2")] {
+            let author = Author::new(
+                author_id.clone(),
+                AuthorName::new(name.to_owned())?,
+                OffsetDateTime::UNIX_EPOCH,
+            )?;
+            let mut tx = tm.begin(&user_id, EventSetOperation::CreateAuthor).await?;
+            author_repo.create(&mut tx, &author).await?;
+            tm.commit(tx).await?;
+        }
+        let mut tx = tm.begin(&user_id, EventSetOperation::MergeAuthor).await?;
+        author_repo
+            .delete(
+                &mut tx,
+                &source_id,
+                Some(DeleteAuthorEventExtra::Merge {
+                    destination_author_id: destination_id.clone(),
+                }),
+            )
+            .await?;
+        author_event_repo
+            .append(
+                &mut tx,
+                &NewAuthorEvent::merge_as_destination(destination_id.clone(), &source_id),
+            )
+            .await?;
+        tm.commit(tx).await?;
+        let week_ago = OffsetDateTime::now_utc() - Duration::days(7);
+        for sql in [
+            "UPDATE book_event SET changed_at = $1",
+            "UPDATE author_event SET changed_at = $1",
+        ] {
+            sqlx::query(sql).bind(week_ago).execute(&pool).await?;
+        }
+
+        let recent = make_book("c5a81e57-bc91-40ff-8b57-18cfa7cc7ae8", "recent")?;
+        create_and_delete_book(&tm, &book_repo, &user_id, &recent).await?;
+
+        // Given a book deleted a week ago, an author merged away a week ago
+        // and a book deleted now, when purging what was deleted more than a
+        // day ago
+        let mut tx = tm.begin(&user_id, EventSetOperation::PurgeTrash).await?;
+        let purged = trash_repo
+            .purge(&mut tx, OffsetDateTime::now_utc() - Duration::days(1))
+            .await?;
+        tm.commit(tx).await?;
+
+        // Then the old entries, the merge marker naming the source and their
+        // four event sets are gone
+        assert_eq!(
+            purged,
+            PurgedTrash {
+                book_count: 1,
+                author_count: 1,
+                event_set_count: 4,
+            }
+        );
+        let deleted = book_event_repo.find_deleted(&user_id).await?;
+        assert_eq!(deleted.len(), 1);
+        assert_eq!(&deleted[0].last_known.book_id, recent.id());
+        assert!(author_event_repo.find_deleted(&user_id).await?.is_empty());
+        assert!(
+            book_event_repo
+                .find_by_book(&user_id, old.id())
+                .await?
+                .is_empty()
+        );
+        let destination_events = author_event_repo
+            .find_by_author(&user_id, &destination_id)
+            .await?;
+        assert_eq!(destination_events.len(), 1);
+        assert_eq!(destination_events[0].operation, EventOperation::Create);
+
+        // The destination's creation, the recent book's two sets and the
+        // purge's own set remain
+        let (event_set_count,): (i64,) = sqlx::query_as("SELECT count(*) FROM event_set")
+            .fetch_one(&pool)
+            .await?;
+        assert_eq!(event_set_count, 4);
+
+        Ok(())
+    }
+}
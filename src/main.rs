@@ -5,8 +5,12 @@ use axum::{
     routing::{get, post},
 };
 use bookshelf_api::{
-    dependency_injection::{MI, QI, dependency_injection},
+    dependency_injection::{IBI, MI, QI, dependency_injection},
+    presentation::handler::export::{export_books_csv_handler, export_library_json_handler},
     presentation::handler::graphql::{graphql_handler, graphql_playground_handler},
+    presentation::handler::import::{
+        import_bookmeter_handler, import_csv_handler, import_goodreads_handler,
+    },
     presentation::handler::user::me_handler,
     presentation::{app_state::AppState, extractor::claims::JwtConfig},
 };
@@ -37,7 +41,9 @@ async fn main() -> Result<(), anyhow::Error> {
 
     sqlx::migrate!().run(&pool).await?;
 
-    let (query_use_case, schema) = dependency_injection(pool);
+    let (query_use_case, schema, import_books_use_case, import_job_worker) =
+        dependency_injection(pool);
+    tokio::spawn(import_job_worker.run());
 
     let jwt_config = JwtConfig::from_env()?;
     let jwks_cache = moka::future::Cache::builder()
@@ -67,6 +73,14 @@ async fn main() -> Result<(), anyhow::Error> {
         .route("/", get(|| async { "OK" }))
         .route("/me", get(me_handler))
         .route("/graphql", post(graphql_handler::<QI, MI>))
+        .route("/import/csv", post(import_csv_handler::<IBI>))
+        .route("/import/goodreads", post(import_goodreads_handler::<IBI>))
+        .route("/import/bookmeter", post(import_bookmeter_handler::<IBI>))
+        .route("/export/books.csv", get(export_books_csv_handler::<QI>))
+        .route(
+            "/export/library.json",
+            get(export_library_json_handler::<QI>),
+        )
         .route("/graphql/playground", get(graphql_playground_handler))
         .route("/health", get(|| async { "OK" }))
         .with_state(state)
@@ -74,6 +88,7 @@ async fn main() -> Result<(), anyhow::Error> {
             ServiceBuilder::new()
                 .layer(Extension(query_use_case))
                 .layer(Extension(schema))
+                .layer(Extension(import_books_use_case))
                 .layer(
                     TraceLayer::new_for_http()
                         .on_request(DefaultOnRequest::new().level(Level::INFO))
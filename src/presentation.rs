@@ -3,3 +3,4 @@ pub mod error;
 pub mod extractor;
 pub mod graphql;
 pub mod handler;
+pub mod import;
@@ -1,8 +1,12 @@
 use std::sync::Arc;
 
+use async_graphql::{ErrorExtensions, InputType, Value, value};
 use thiserror::Error;
 
-use crate::use_case::error::UseCaseError;
+use crate::{
+    presentation::graphql::object::{BookFormat, BookStore},
+    use_case::{dto::mutation::CurrentStateDto, error::UseCaseError},
+};
 
 #[derive(Debug, Clone, Error)]
 pub enum PresentationalError {
@@ -10,8 +14,12 @@ pub enum PresentationalError {
     NotFound(String),
     #[error("{0}")]
     Validation(String),
-    #[error("{0}")]
-    Conflict(String),
+    #[error("{message}")]
+    Conflict {
+        message: String,
+        // Extra fields for the error's extensions.
+        extensions: Option<Value>,
+    },
     #[error(transparent)]
     OtherError(Arc<anyhow::Error>),
     #[error("{0}")]
@@ -23,7 +31,10 @@ impl From<UseCaseError> for PresentationalError {
         match err {
             UseCaseError::NotFound { .. } => PresentationalError::NotFound(err.to_string()),
             UseCaseError::Validation(_) => PresentationalError::Validation(err.to_string()),
-            UseCaseError::Conflict(_) => PresentationalError::Conflict(err.to_string()),
+            UseCaseError::Conflict { message, current } => PresentationalError::Conflict {
+                message,
+                extensions: current.map(|current| conflict_extensions(*current)),
+            },
             UseCaseError::Other(_) => {
                 PresentationalError::OtherError(Arc::new(anyhow::Error::new(err)))
             }
@@ -31,3 +42,72 @@ impl From<UseCaseError> for PresentationalError {
         }
     }
 }
+
+// Conflicts with a concurrent change report the stored state under the
+// `current` extension, shaped like the entity's GraphQL object, and its
+// latest event under `currentEventId`. Authors that block a bulk deletion are
+// listed under `blockingAuthors`.
+impl ErrorExtensions for PresentationalError {
+    fn extend(&self) -> async_graphql::Error {
+        async_graphql::Error::new(self.to_string()).extend_with(|_, extensions| {
+            if let PresentationalError::Conflict {
+                extensions: extra, ..
+            } = self
+            {
+                extensions.set("code", "CONFLICT");
+                if let Some(Value::Object(extra)) = extra {
+                    for (key, value) in extra {
+                        extensions.set(key.as_str(), value.clone());
+                    }
+                }
+            }
+        })
+    }
+}
+
+fn conflict_extensions(current: CurrentStateDto) -> Value {
+    let (state, event_id) = match current {
+        CurrentStateDto::Book { book, event_id } => (
+            value!({
+                "id": book.id,
+                "title": book.title,
+                "authorIds": book.author_ids,
+                "isbn": book.isbn,
+                "read": book.read,
+                "owned": book.owned,
+                "priority": book.priority,
+                "format": BookFormat::from(book.format).to_value(),
+                "store": BookStore::from(book.store).to_value(),
+                "createdAt": book.created_at.unix_timestamp(),
+                "updatedAt": book.updated_at.unix_timestamp(),
+            }),
+            event_id,
+        ),
+        CurrentStateDto::Author { author, event_id } => (
+            value!({
+                "id": author.id,
+                "name": author.name,
+                "yomi": author.yomi,
+                "createdAt": author.created_at.unix_timestamp(),
+                "updatedAt": author.updated_at.unix_timestamp(),
+            }),
+            event_id,
+        ),
+        CurrentStateDto::AuthorsWithBooks(authors) => {
+            let authors: Vec<Value> = authors
+                .into_iter()
+                .map(|author| {
+                    value!({
+                        "authorId": author.author_id,
+                        "bookIds": author.book_ids,
+                    })
+                })
+                .collect();
+            return value!({ "blockingAuthors": authors });
+        }
+    };
+    value!({
+        "current": state,
+        "currentEventId": event_id.map(|id| id.to_string()),
+    })
+}
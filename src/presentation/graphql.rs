@@ -1,3 +1,4 @@
+pub mod connection;
 pub mod loader;
 pub mod mutation;
 pub mod object;
@@ -0,0 +1,359 @@
+use async_graphql::{
+    SimpleObject,
+    connection::{Connection, CursorType, Edge, OpaqueCursor},
+};
+use serde::{Deserialize, Serialize, de::DeserializeOwned};
+use time::OffsetDateTime;
+
+use crate::{
+    common::sort::{AuthorSortValue, BookSortValue},
+    presentation::error::PresentationalError,
+    use_case::dto::{
+        author::{AuthorCursorDto, AuthorDto},
+        book::{BookCursorDto, BookDto},
+        event::{AuthorEventDto, BookEventDto},
+        event_set::EventSetDto,
+        history::HistoryCursorDto,
+        pagination::{PageDto, PageRequestDto},
+    },
+};
+
+use super::object::{Author, AuthorEventEntry, Book, BookEventEntry, EventSetEntry};
+
+#[derive(SimpleObject)]
+pub struct ConnectionFields {
+    /// Number of items across all pages.
+    pub total_count: i64,
+}
+
+pub type BookConnection = Connection<OpaqueCursor<BookCursor>, Book, ConnectionFields>;
+pub type AuthorConnection = Connection<OpaqueCursor<AuthorCursor>, Author, ConnectionFields>;
+pub type BookEventConnection =
+    Connection<OpaqueCursor<HistoryCursor>, BookEventEntry, ConnectionFields>;
+pub type AuthorEventConnection =
+    Connection<OpaqueCursor<HistoryCursor>, AuthorEventEntry, ConnectionFields>;
+pub type EventSetConnection =
+    Connection<OpaqueCursor<HistoryCursor>, EventSetEntry, ConnectionFields>;
+
+// Cursor payloads are serialized to JSON and base64-encoded by OpaqueCursor.
+// Each carries the value of the sort key it was produced under, so a cursor
+// only continues a listing with the same order.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BookCursor {
+    value: BookCursorValue,
+    id: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+enum BookCursorValue {
+    Title(String),
+    Priority(i32),
+    CreatedAtMicros(i64),
+    UpdatedAtMicros(i64),
+    AuthorYomi(String),
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AuthorCursor {
+    value: AuthorCursorValue,
+    id: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+enum AuthorCursorValue {
+    Name(String),
+    Yomi(String),
+    CreatedAtMicros(i64),
+    UpdatedAtMicros(i64),
+}
+
+// Points into a newest-first history listing of events or event sets.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HistoryCursor {
+    at_micros: i64,
+    id: String,
+}
+
+impl From<BookCursorDto> for BookCursor {
+    fn from(cursor: BookCursorDto) -> Self {
+        let value = match cursor.value {
+            BookSortValue::Title(title) => BookCursorValue::Title(title),
+            BookSortValue::Priority(priority) => BookCursorValue::Priority(priority),
+            BookSortValue::CreatedAt(at) => BookCursorValue::CreatedAtMicros(to_micros(at)),
+            BookSortValue::UpdatedAt(at) => BookCursorValue::UpdatedAtMicros(to_micros(at)),
+            BookSortValue::AuthorYomi(yomi) => BookCursorValue::AuthorYomi(yomi),
+        };
+        Self {
+            value,
+            id: cursor.id,
+        }
+    }
+}
+
+impl TryFrom<BookCursor> for BookCursorDto {
+    type Error = PresentationalError;
+
+    fn try_from(cursor: BookCursor) -> Result<Self, Self::Error> {
+        let value = match cursor.value {
+            BookCursorValue::Title(title) => BookSortValue::Title(title),
+            BookCursorValue::Priority(priority) => BookSortValue::Priority(priority),
+            BookCursorValue::CreatedAtMicros(micros) => {
+                BookSortValue::CreatedAt(from_micros(micros)?)
+            }
+            BookCursorValue::UpdatedAtMicros(micros) => {
+                BookSortValue::UpdatedAt(from_micros(micros)?)
+            }
+            BookCursorValue::AuthorYomi(yomi) => BookSortValue::AuthorYomi(yomi),
+        };
+        Ok(BookCursorDto {
+            value,
+            id: cursor.id,
+        })
+    }
+}
+
+impl From<AuthorCursorDto> for AuthorCursor {
+    fn from(cursor: AuthorCursorDto) -> Self {
+        let value = match cursor.value {
+            AuthorSortValue::Name(name) => AuthorCursorValue::Name(name),
+            AuthorSortValue::Yomi(yomi) => AuthorCursorValue::Yomi(yomi),
+            AuthorSortValue::CreatedAt(at) => AuthorCursorValue::CreatedAtMicros(to_micros(at)),
+            AuthorSortValue::UpdatedAt(at) => AuthorCursorValue::UpdatedAtMicros(to_micros(at)),
+        };
+        Self {
+            value,
+            id: cursor.id,
+        }
+    }
+}
+
+impl TryFrom<AuthorCursor> for AuthorCursorDto {
+    type Error = PresentationalError;
+
+    fn try_from(cursor: AuthorCursor) -> Result<Self, Self::Error> {
+        let value = match cursor.value {
+            AuthorCursorValue::Name(name) => AuthorSortValue::Name(name),
+            AuthorCursorValue::Yomi(yomi) => AuthorSortValue::Yomi(yomi),
+            AuthorCursorValue::CreatedAtMicros(micros) => {
+                AuthorSortValue::CreatedAt(from_micros(micros)?)
+            }
+            AuthorCursorValue::UpdatedAtMicros(micros) => {
+                AuthorSortValue::UpdatedAt(from_micros(micros)?)
+            }
+        };
+        Ok(AuthorCursorDto {
+            value,
+            id: cursor.id,
+        })
+    }
+}
+
+impl From<HistoryCursorDto> for HistoryCursor {
+    fn from(cursor: HistoryCursorDto) -> Self {
+        Self {
+            at_micros: to_micros(cursor.at),
+            id: cursor.id,
+        }
+    }
+}
+
+impl TryFrom<HistoryCursor> for HistoryCursorDto {
+    type Error = PresentationalError;
+
+    fn try_from(cursor: HistoryCursor) -> Result<Self, Self::Error> {
+        Ok(HistoryCursorDto {
+            at: from_micros(cursor.at_micros)?,
+            id: cursor.id,
+        })
+    }
+}
+
+// Timestamps are stored with microsecond precision, so cursors keep micros.
+fn to_micros(at: OffsetDateTime) -> i64 {
+    (at.unix_timestamp_nanos() / 1_000) as i64
+}
+
+fn from_micros(micros: i64) -> Result<OffsetDateTime, PresentationalError> {
+    OffsetDateTime::from_unix_timestamp_nanos(i128::from(micros) * 1_000)
+        .map_err(|_| invalid_cursor())
+}
+
+pub fn book_page_request(
+    after: Option<String>,
+    before: Option<String>,
+    first: Option<i32>,
+    last: Option<i32>,
+) -> Result<PageRequestDto<BookCursorDto>, PresentationalError> {
+    Ok(PageRequestDto {
+        first,
+        after: decode_cursor::<BookCursor>(after)?
+            .map(BookCursorDto::try_from)
+            .transpose()?,
+        last,
+        before: decode_cursor::<BookCursor>(before)?
+            .map(BookCursorDto::try_from)
+            .transpose()?,
+    })
+}
+
+pub fn author_page_request(
+    after: Option<String>,
+    before: Option<String>,
+    first: Option<i32>,
+    last: Option<i32>,
+) -> Result<PageRequestDto<AuthorCursorDto>, PresentationalError> {
+    Ok(PageRequestDto {
+        first,
+        after: decode_cursor::<AuthorCursor>(after)?
+            .map(AuthorCursorDto::try_from)
+            .transpose()?,
+        last,
+        before: decode_cursor::<AuthorCursor>(before)?
+            .map(AuthorCursorDto::try_from)
+            .transpose()?,
+    })
+}
+
+pub fn history_page_request(
+    after: Option<String>,
+    before: Option<String>,
+    first: Option<i32>,
+    last: Option<i32>,
+) -> Result<PageRequestDto<HistoryCursorDto>, PresentationalError> {
+    Ok(PageRequestDto {
+        first,
+        after: decode_cursor::<HistoryCursor>(after)?
+            .map(HistoryCursorDto::try_from)
+            .transpose()?,
+        last,
+        before: decode_cursor::<HistoryCursor>(before)?
+            .map(HistoryCursorDto::try_from)
+            .transpose()?,
+    })
+}
+
+pub fn book_connection(page: PageDto<BookDto, BookCursorDto>) -> BookConnection {
+    into_connection(page, BookCursor::from, Book::from)
+}
+
+pub fn author_connection(page: PageDto<AuthorDto, AuthorCursorDto>) -> AuthorConnection {
+    into_connection(page, AuthorCursor::from, Author::from)
+}
+
+pub fn book_event_connection(page: PageDto<BookEventDto, HistoryCursorDto>) -> BookEventConnection {
+    into_connection(page, HistoryCursor::from, BookEventEntry::from)
+}
+
+pub fn author_event_connection(
+    page: PageDto<AuthorEventDto, HistoryCursorDto>,
+) -> AuthorEventConnection {
+    into_connection(page, HistoryCursor::from, AuthorEventEntry::from)
+}
+
+pub fn event_set_connection(page: PageDto<EventSetDto, HistoryCursorDto>) -> EventSetConnection {
+    into_connection(page, HistoryCursor::from, EventSetEntry::from)
+}
+
+fn decode_cursor<C>(cursor: Option<String>) -> Result<Option<C>, PresentationalError>
+where
+    C: Serialize + DeserializeOwned + Send + Sync,
+{
+    cursor
+        .map(|cursor| {
+            OpaqueCursor::<C>::decode_cursor(&cursor)
+                .map(|cursor| cursor.0)
+                .map_err(|_| invalid_cursor())
+        })
+        .transpose()
+}
+
+fn into_connection<T, D, C, N>(
+    page: PageDto<T, D>,
+    cursor: impl Fn(D) -> C,
+    node: impl Fn(T) -> N,
+) -> Connection<OpaqueCursor<C>, N, ConnectionFields>
+where
+    C: Serialize + DeserializeOwned + Send + Sync,
+    N: async_graphql::OutputType,
+{
+    let mut connection = Connection::with_additional_fields(
+        page.has_previous_page,
+        page.has_next_page,
+        ConnectionFields {
+            total_count: page.total_count,
+        },
+    );
+    connection.edges = page
+        .edges
+        .into_iter()
+        .map(|edge| Edge::new(OpaqueCursor(cursor(edge.cursor)), node(edge.node)))
+        .collect();
+    connection
+}
+
+fn invalid_cursor() -> PresentationalError {
+    PresentationalError::Validation("Invalid cursor.".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use async_graphql::connection::{CursorType, OpaqueCursor};
+    use time::macros::datetime;
+
+    use crate::{
+        common::sort::{AuthorSortValue, BookSortValue},
+        presentation::error::PresentationalError,
+        use_case::dto::{author::AuthorCursorDto, book::BookCursorDto, history::HistoryCursorDto},
+    };
+
+    use super::{
+        AuthorCursor, BookCursor, HistoryCursor, author_page_request, book_page_request,
+        history_page_request,
+    };
+
+    #[test]
+    fn book_cursor_round_trips_through_page_request() {
+        let cursor = BookCursorDto {
+            value: BookSortValue::UpdatedAt(datetime!(2022-05-05 01:02:03.456789 UTC)),
+            id: "675bc8d9-3155-42fb-87b0-0a82cb162848".to_string(),
+        };
+        let encoded = OpaqueCursor(BookCursor::from(cursor.clone())).encode_cursor();
+
+        let page = book_page_request(Some(encoded), None, Some(10), None).unwrap();
+
+        assert_eq!(page.after, Some(cursor));
+    }
+
+    #[test]
+    fn author_cursor_round_trips_through_page_request() {
+        let cursor = AuthorCursorDto {
+            value: AuthorSortValue::Yomi("なつめそうせき".to_string()),
+            id: "006099b4-6c42-4ec4-8645-f6bd5b63eddc".to_string(),
+        };
+        let encoded = OpaqueCursor(AuthorCursor::from(cursor.clone())).encode_cursor();
+
+        let page = author_page_request(None, Some(encoded), None, Some(10)).unwrap();
+
+        assert_eq!(page.before, Some(cursor));
+    }
+
+    #[test]
+    fn history_cursor_round_trips_through_page_request() {
+        let cursor = HistoryCursorDto {
+            at: datetime!(2026-10-17 09:30:00.000123 UTC),
+            id: "42".to_string(),
+        };
+        let encoded = OpaqueCursor(HistoryCursor::from(cursor.clone())).encode_cursor();
+
+        let page = history_page_request(Some(encoded), None, Some(5), None).unwrap();
+
+        assert_eq!(page.after, Some(cursor));
+    }
+
+    #[test]
+    fn malformed_cursor_is_a_validation_error() {
+        let result = book_page_request(None, Some("not-a-cursor".to_string()), None, Some(1));
+
+        assert!(matches!(result, Err(PresentationalError::Validation(_))));
+    }
+}
@@ -3,6 +3,7 @@ use std::collections::HashMap;
 use async_graphql::dataloader::Loader;
 
 use crate::{
+    common::sort::BookOrder,
     presentation::{error::PresentationalError, extractor::claims::Claims},
     use_case::traits::query::QueryUseCase,
 };
@@ -28,28 +29,41 @@ impl<QUC> BooksByAuthorLoader<QUC> {
     }
 }
 
-impl<QUC> Loader<String> for BooksByAuthorLoader<QUC>
+// Keys pair an author id with the order of that author's books.
+impl<QUC> Loader<(String, BookOrder)> for BooksByAuthorLoader<QUC>
 where
     QUC: QueryUseCase,
 {
     type Value = Vec<Book>;
     type Error = PresentationalError;
 
-    async fn load(&self, keys: &[String]) -> Result<HashMap<String, Self::Value>, Self::Error> {
-        let books_by_author = self
-            .query_use_case
-            .find_books_by_author_ids_as_hash_map(&self.claims.sub, keys)
-            .await?;
+    async fn load(
+        &self,
+        keys: &[(String, BookOrder)],
+    ) -> Result<HashMap<(String, BookOrder), Self::Value>, Self::Error> {
+        let mut author_ids_by_order: HashMap<BookOrder, Vec<String>> = HashMap::new();
+        for (author_id, order) in keys {
+            author_ids_by_order
+                .entry(*order)
+                .or_default()
+                .push(author_id.clone());
+        }
 
-        Ok(books_by_author
-            .into_iter()
-            .map(|(author_id, books)| {
+        let mut books = HashMap::new();
+        for (order, author_ids) in author_ids_by_order {
+            let books_by_author = self
+                .query_use_case
+                .find_books_by_author_ids_as_hash_map(&self.claims.sub, &author_ids, order)
+                .await?;
+            books.extend(books_by_author.into_iter().map(|(author_id, books)| {
                 (
-                    author_id,
+                    (author_id, order),
                     books.into_iter().map(Book::from).collect::<Vec<_>>(),
                 )
-            })
-            .collect())
+            }));
+        }
+
+        Ok(books)
     }
 }
 
@@ -92,7 +106,10 @@ mod tests {
     use time::OffsetDateTime;
 
     use crate::{
-        common::types::{BookFormat, BookStore},
+        common::{
+            sort::{BookOrder, BookSortKey, SortDirection},
+            types::{BookFormat, BookStore},
+        },
         presentation::extractor::claims::Claims,
         use_case::{dto::book::BookDto, traits::query::MockQueryUseCase},
     };
@@ -103,13 +120,21 @@ mod tests {
     async fn books_by_author_loader_batches_keys_and_maps_books() {
         let author_id1 = "006099b4-6c42-4ec4-8645-f6bd5b63eddc".to_string();
         let author_id2 = "93090e87-b7a1-403c-974c-d74d881e83b9".to_string();
-        let expected_keys = vec![author_id1.clone(), author_id2.clone()];
+        let expected_author_ids = vec![author_id1.clone(), author_id2.clone()];
+        let keys = vec![
+            (author_id1.clone(), BookOrder::default()),
+            (author_id2.clone(), BookOrder::default()),
+        ];
         let mut query_use_case = MockQueryUseCase::new();
         query_use_case
             .expect_find_books_by_author_ids_as_hash_map()
-            .with(predicate::eq("user1"), predicate::eq(expected_keys.clone()))
+            .with(
+                predicate::eq("user1"),
+                predicate::eq(expected_author_ids),
+                predicate::eq(BookOrder::default()),
+            )
             .times(1)
-            .returning(move |_, _| {
+            .returning(move |_, _, _| {
                 Ok(HashMap::from([
                     (
                         author_id1.clone(),
@@ -138,10 +163,41 @@ mod tests {
             query_use_case,
         );
 
-        let result = loader.load(&expected_keys).await.unwrap();
+        let result = loader.load(&keys).await.unwrap();
+
+        assert_eq!(result[&keys[0]].len(), 1);
+        assert_eq!(result[&keys[0]][0].title, "Book 1");
+        assert!(result[&keys[1]].is_empty());
+    }
+
+    #[tokio::test]
+    async fn books_by_author_loader_queries_each_order_separately() {
+        let author_id = "006099b4-6c42-4ec4-8645-f6bd5b63eddc".to_string();
+        let by_title = BookOrder {
+            key: BookSortKey::Title,
+            direction: SortDirection::Desc,
+        };
+        let mut query_use_case = MockQueryUseCase::new();
+        query_use_case
+            .expect_find_books_by_author_ids_as_hash_map()
+            .withf(|_, author_ids, _| author_ids.len() == 1)
+            .times(2)
+            .returning(|_, author_ids, _| Ok(HashMap::from([(author_ids[0].clone(), Vec::new())])));
+        let loader = BooksByAuthorLoader::new(
+            Claims {
+                sub: "user1".to_string(),
+                _permissions: None,
+            },
+            query_use_case,
+        );
+        let keys = vec![
+            (author_id.clone(), BookOrder::default()),
+            (author_id.clone(), by_title),
+        ];
+
+        let result = loader.load(&keys).await.unwrap();
 
-        assert_eq!(result[&expected_keys[0]].len(), 1);
-        assert_eq!(result[&expected_keys[0]][0].title, "Book 1");
-        assert!(result[&expected_keys[1]].is_empty());
+        assert!(result.contains_key(&keys[0]));
+        assert!(result.contains_key(&keys[1]));
     }
 }
@@ -1,17 +1,24 @@
 use std::sync::Arc;
 
-use async_graphql::{Context, ID, Object};
+use async_graphql::{Context, ErrorExtensions, ID, Object};
 
 use crate::{
     presentation::{error::PresentationalError, extractor::claims::Claims},
-    use_case::traits::mutation::MutationUseCase,
+    use_case::{
+        dto::{
+            book::ImportOptionsDto, change::ChangeOperationDto, import_job::ImportJobOptionsDto,
+        },
+        traits::mutation::MutationUseCase,
+    },
 };
 
 use super::object::{
-    Author, AuthorMutationPayload, Book, BookMutationPayload, CreateAuthorInput, CreateBookInput,
-    DeleteAuthorPayload, DeleteBookPayload, ImportBookInput, ImportBooksPayload,
-    MergeAuthorPayload, RestoreAuthorPayload, RestoreBookPayload, UpdateAuthorInput,
-    UpdateBookInput, User,
+    ApplyChangesPayload, Author, AuthorMutationPayload, BookMutationPayload, BookPatchInput,
+    BookSelection, BulkUpdateBooksPayload, ChangeOperation, CreateAuthorInput, CreateBookInput,
+    DeleteAuthorPayload, DeleteAuthorsPayload, DeleteBookPayload, DeleteBooksPayload,
+    ImportBookInput, ImportBooksPayload, ImportJob, ImportMode, MergeAuthorPayload,
+    PatchAuthorInput, PatchBookInput, PurgeTrashPayload, RestoreAuthorPayload, RestoreBookPayload,
+    RevertEventSetPayload, UpdateAuthorInput, UpdateBookInput, User,
 };
 
 pub struct Mutation<MUC> {
@@ -53,16 +60,43 @@ where
         ))
     }
 
+    /// Updates a book. With `expectedEventId`, the id of the book's
+    /// latest event the edit is based on, a concurrent change fails the
+    /// update with a `CONFLICT` error whose extensions hold the `current`
+    /// book and its `currentEventId`.
     async fn update_book(
         &self,
         ctx: &Context<'_>,
         book_data: UpdateBookInput,
-    ) -> Result<BookMutationPayload, PresentationalError> {
+    ) -> async_graphql::Result<BookMutationPayload> {
         let claims = get_claims(ctx)?;
         let book = self
             .mutation_use_case
-            .update_book(&claims.sub, book_data.into())
-            .await?;
+            .update_book(&claims.sub, book_data.try_into()?)
+            .await
+            .map_err(|err| PresentationalError::from(err).extend())?;
+
+        Ok(BookMutationPayload::new(
+            book.value.into(),
+            ID(book.event_set_id),
+            ID(book.event_id.to_string()),
+        ))
+    }
+
+    /// Changes only the given fields of a book, recorded as an ordinary
+    /// update. A patch that changes nothing records nothing, and returns the
+    /// book with its latest event. Conflicts are reported as for `updateBook`.
+    async fn patch_book(
+        &self,
+        ctx: &Context<'_>,
+        book_data: PatchBookInput,
+    ) -> async_graphql::Result<BookMutationPayload> {
+        let claims = get_claims(ctx)?;
+        let book = self
+            .mutation_use_case
+            .patch_book(&claims.sub, book_data.try_into()?)
+            .await
+            .map_err(|err| PresentationalError::from(err).extend())?;
 
         Ok(BookMutationPayload::new(
             book.value.into(),
@@ -71,6 +105,23 @@ where
         ))
     }
 
+    /// Applies `patch` to the selected books as one event set, recording an
+    /// update event for each book it changes. At most 1000 books can be
+    /// selected.
+    async fn bulk_update_books(
+        &self,
+        ctx: &Context<'_>,
+        selection: BookSelection,
+        patch: BookPatchInput,
+    ) -> Result<BulkUpdateBooksPayload, PresentationalError> {
+        let claims = get_claims(ctx)?;
+        let result = self
+            .mutation_use_case
+            .bulk_update_books(&claims.sub, selection.try_into()?, patch.into())
+            .await?;
+        Ok(result.into())
+    }
+
     async fn delete_book(
         &self,
         ctx: &Context<'_>,
@@ -88,6 +139,26 @@ where
         })
     }
 
+    /// Deletes the listed books as one event set, recording a delete event
+    /// for each. Nothing is deleted if any of them does not exist. At most
+    /// 1000 books can be listed.
+    async fn delete_books(
+        &self,
+        ctx: &Context<'_>,
+        book_ids: Vec<ID>,
+    ) -> Result<DeleteBooksPayload, PresentationalError> {
+        let claims = get_claims(ctx)?;
+        let result = self
+            .mutation_use_case
+            .delete_books(&claims.sub, book_ids.into_iter().map(|id| id.0).collect())
+            .await?;
+
+        Ok(DeleteBooksPayload {
+            book_ids: result.value.into_iter().map(ID).collect(),
+            event_set_id: ID(result.event_set_id),
+        })
+    }
+
     async fn create_author(
         &self,
         ctx: &Context<'_>,
@@ -105,16 +176,41 @@ where
         ))
     }
 
+    /// Updates an author. With `expectedEventId`, the id of the author's
+    /// latest event the edit is based on, a concurrent change fails the
+    /// update with a `CONFLICT` error whose extensions hold the `current`
+    /// author and its `currentEventId`.
     async fn update_author(
         &self,
         ctx: &Context<'_>,
         author_data: UpdateAuthorInput,
-    ) -> Result<AuthorMutationPayload, PresentationalError> {
+    ) -> async_graphql::Result<AuthorMutationPayload> {
         let claims = get_claims(ctx)?;
         let author = self
             .mutation_use_case
-            .update_author(&claims.sub, author_data.into())
-            .await?;
+            .update_author(&claims.sub, author_data.try_into()?)
+            .await
+            .map_err(|err| PresentationalError::from(err).extend())?;
+        Ok(AuthorMutationPayload::new(
+            author.value.into(),
+            ID(author.event_set_id),
+            ID(author.event_id.to_string()),
+        ))
+    }
+
+    /// Changes only the given fields of an author, recorded as an ordinary
+    /// update. Conflicts are reported as for `updateAuthor`.
+    async fn patch_author(
+        &self,
+        ctx: &Context<'_>,
+        author_data: PatchAuthorInput,
+    ) -> async_graphql::Result<AuthorMutationPayload> {
+        let claims = get_claims(ctx)?;
+        let author = self
+            .mutation_use_case
+            .patch_author(&claims.sub, author_data.try_into()?)
+            .await
+            .map_err(|err| PresentationalError::from(err).extend())?;
         Ok(AuthorMutationPayload::new(
             author.value.into(),
             ID(author.event_set_id),
@@ -138,6 +234,26 @@ where
         })
     }
 
+    /// Deletes the listed authors as one event set. If any of them still has
+    /// books, nothing is deleted and the `CONFLICT` error lists every such
+    /// author with its books in `extensions.blockingAuthors`.
+    async fn delete_authors(
+        &self,
+        ctx: &Context<'_>,
+        author_ids: Vec<ID>,
+    ) -> async_graphql::Result<DeleteAuthorsPayload> {
+        let claims = get_claims(ctx)?;
+        let result = self
+            .mutation_use_case
+            .delete_authors(&claims.sub, author_ids.into_iter().map(|id| id.0).collect())
+            .await
+            .map_err(|err| PresentationalError::from(err).extend())?;
+        Ok(DeleteAuthorsPayload {
+            author_ids: result.value.into_iter().map(ID).collect(),
+            event_set_id: ID(result.event_set_id),
+        })
+    }
+
     async fn merge_author(
         &self,
         ctx: &Context<'_>,
@@ -161,10 +277,37 @@ where
         })
     }
 
+    /// Applies every operation in one transaction and event set, in the
+    /// order given. If any of them fails, none is applied, and the error
+    /// names it by its position, as in `operation 2: ...`. Creates can be
+    /// given a `tempId` that later operations use in place of the new id.
+    /// At most 1000 operations can be given.
+    async fn apply_changes(
+        &self,
+        ctx: &Context<'_>,
+        operations: Vec<ChangeOperation>,
+    ) -> async_graphql::Result<ApplyChangesPayload> {
+        let claims = get_claims(ctx)?;
+        let operations = operations
+            .into_iter()
+            .map(ChangeOperationDto::try_from)
+            .collect::<Result<Vec<_>, PresentationalError>>()?;
+        let result = self
+            .mutation_use_case
+            .apply_changes(&claims.sub, operations)
+            .await
+            .map_err(|err| PresentationalError::from(err).extend())?;
+        Ok(result.into())
+    }
+
+    /// Restores a book to the state recorded by a book event. With `cascade`,
+    /// authors the book listed that were deleted since come back in their last
+    /// known state, and authors merged away are replaced by their destination.
     async fn restore_book(
         &self,
         ctx: &Context<'_>,
         event_id: ID,
+        #[graphql(default = false)] cascade: bool,
     ) -> Result<RestoreBookPayload, PresentationalError> {
         let claims = get_claims(ctx)?;
         let eid: i64 = event_id.parse().map_err(|_| {
@@ -172,14 +315,14 @@ where
                 "event_id must be an integer"
             )))
         })?;
-        let book = self
+        let result = self
             .mutation_use_case
-            .restore_book(&claims.sub, eid)
+            .restore_book(&claims.sub, eid, cascade)
             .await?;
-        Ok(RestoreBookPayload {
-            book: book.value.map(Book::from),
-            event_set_id: ID(book.event_set_id),
-        })
+        Ok(RestoreBookPayload::new(
+            result.value,
+            ID(result.event_set_id),
+        ))
     }
 
     async fn restore_author(
@@ -203,21 +346,112 @@ where
         })
     }
 
-    /// Imports multiple books. Creates authors if they do not exist.
+    /// Undoes every book and author change of an event set in a new event set.
+    /// Nothing is applied when an entity was changed again after the set;
+    /// the conflicts are reported instead. A dry run only returns the plan.
+    /// Snapshot sets, which record the library as it was, cannot be reverted.
+    async fn revert_event_set(
+        &self,
+        ctx: &Context<'_>,
+        id: ID,
+        #[graphql(default = false)] dry_run: bool,
+    ) -> Result<RevertEventSetPayload, PresentationalError> {
+        let claims = get_claims(ctx)?;
+        let result = self
+            .mutation_use_case
+            .revert_event_set(&claims.sub, &id, dry_run)
+            .await?;
+        Ok(result.into())
+    }
+
+    /// Permanently removes the history of books and authors deleted more than
+    /// `retentionDays` days ago. They no longer appear in `deletedBooks` or
+    /// `deletedAuthors` and cannot be restored.
+    async fn purge_trash(
+        &self,
+        ctx: &Context<'_>,
+        retention_days: i32,
+    ) -> Result<PurgeTrashPayload, PresentationalError> {
+        let claims = get_claims(ctx)?;
+        let purged = self
+            .mutation_use_case
+            .purge_trash(&claims.sub, retention_days)
+            .await?;
+        Ok(purged.into())
+    }
+
+    /// Imports multiple books. Creates authors if they do not exist. `mode`
+    /// decides whether entries matching an existing book update it, are
+    /// skipped or create another book. One invalid entry fails the whole
+    /// import, unless it is `partial`, which imports the valid entries and
+    /// reports the others in `errors`. A dry run reports what the import would
+    /// do, including every invalid entry, and keeps nothing.
     async fn import_books(
         &self,
         ctx: &Context<'_>,
         books: Vec<ImportBookInput>,
+        #[graphql(default)] mode: ImportMode,
+        #[graphql(default = false)] partial: bool,
+        #[graphql(default = false)] dry_run: bool,
     ) -> Result<ImportBooksPayload, PresentationalError> {
         let claims = get_claims(ctx)?;
-        let books = self
+        let options = ImportOptionsDto {
+            mode: mode.into(),
+            partial,
+            dry_run,
+        };
+        let imported = self
             .mutation_use_case
-            .import_books(&claims.sub, books.into_iter().map(Into::into).collect())
+            .import_books(
+                &claims.sub,
+                books.into_iter().map(Into::into).collect(),
+                options,
+            )
             .await?;
-        Ok(ImportBooksPayload {
-            books: books.value.into_iter().map(Book::from).collect(),
-            event_set_id: ID(books.event_set_id),
-        })
+        Ok(imported.into())
+    }
+
+    /// Imports books in the background, for imports too large for
+    /// `importBooks`. The job writes its entries in chunks, each recorded in
+    /// an event set of its own, and its progress can be followed with
+    /// `importJob`. Unless the job is `partial`, every entry is validated
+    /// before it starts and one invalid entry fails the mutation.
+    async fn start_import(
+        &self,
+        ctx: &Context<'_>,
+        books: Vec<ImportBookInput>,
+        #[graphql(default)] mode: ImportMode,
+        #[graphql(default = false)] partial: bool,
+    ) -> Result<ImportJob, PresentationalError> {
+        let claims = get_claims(ctx)?;
+        let options = ImportJobOptionsDto {
+            mode: mode.into(),
+            partial,
+        };
+        let job = self
+            .mutation_use_case
+            .start_import(
+                &claims.sub,
+                books.into_iter().map(Into::into).collect(),
+                options,
+            )
+            .await?;
+        Ok(job.into())
+    }
+
+    /// Stops an import job before its next chunk. The chunks it already wrote
+    /// are kept. Cancelling a finished job returns it unchanged.
+    async fn cancel_import(
+        &self,
+        ctx: &Context<'_>,
+        id: ID,
+    ) -> Result<ImportJob, PresentationalError> {
+        let claims = get_claims(ctx)?;
+        let job = self
+            .mutation_use_case
+            .cancel_import(&claims.sub, id.as_str())
+            .await?;
+        Ok(job.into())
     }
 }
 
@@ -1,16 +1,47 @@
 use async_graphql::dataloader::DataLoader;
 use async_graphql::{ComplexObject, Context, Enum, Json, Result};
-use async_graphql::{ID, InputObject, SimpleObject};
+use async_graphql::{ID, InputObject, OneofObject, SimpleObject};
 use serde_json::Value;
 use time::OffsetDateTime;
 
+use crate::common::activity::ActivityGranularity as CommonActivityGranularity;
+use crate::common::sort::{
+    AuthorOrder as CommonAuthorOrder, AuthorSortKey, BookOrder as CommonBookOrder, BookSortKey,
+    SortDirection,
+};
 use crate::common::types::{BookFormat as CommonBookFormat, BookStore as CommonBookStore};
 use crate::dependency_injection::QI;
-use crate::use_case::dto::author::{AuthorDto, CreateAuthorDto, UpdateAuthorDto};
-use crate::use_case::dto::book::{BookDto, CreateBookDto, ImportBookEntryDto, UpdateBookDto};
-use crate::use_case::dto::event::{AuthorEventDto, BookEventDto};
-use crate::use_case::dto::event_set::{EventSetDetailDto, EventSetDto};
+use crate::presentation::error::PresentationalError;
+use crate::presentation::extractor::claims::Claims;
+use crate::use_case::dto::activity::ActivityPeriodDto;
+use crate::use_case::dto::author::{AuthorDto, CreateAuthorDto, PatchAuthorDto, UpdateAuthorDto};
+use crate::use_case::dto::book::{
+    AuthorIdsPatchDto, BookDto, BookFilterDto, BookPatchDto, BookSelectionDto, CreateBookDto,
+    ImportBookEntryDto, ImportModeDto, PatchBookDto, UpdateBookDto,
+};
+use crate::use_case::dto::change::{AppliedChangeDto, ApplyChangesResultDto, ChangeOperationDto};
+use crate::use_case::dto::event::{
+    AuthorEventDto, BookEventDto, DeletedAuthorDto, DeletedBookDto, FieldChangeDto, PurgedTrashDto,
+};
+use crate::use_case::dto::event_set::{
+    EventSetDetailDto, EventSetDto, RevertAction as RevertActionDto, RevertChangeDto,
+    RevertConflictDto, RevertConflictReason as RevertConflictReasonDto,
+    RevertEntityType as RevertEntityTypeDto, RevertEventSetResultDto,
+};
+use crate::use_case::dto::history::{
+    EntityDiffDto, HistoryFilterDto, LibraryDiffDto, PointInTimeDto,
+};
+use crate::use_case::dto::import_job::{ImportJobDto, ImportJobStatusDto};
+use crate::use_case::dto::mutation::{
+    AuthorRemapDto, BulkUpdateBooksResultDto, ExpectedVersionDto, ImportBookErrorDto,
+    ImportBookFieldDto, ImportBooksResultDto, RestoredBookDto,
+};
+use crate::use_case::dto::search::SearchResultDto;
+use crate::use_case::dto::stats::{LibraryStatsDto, PriorityBucketDto};
 
+use crate::use_case::traits::query::QueryUseCase;
+
+use super::connection::{BookConnection, book_connection, book_page_request};
 use super::loader::{AuthorLoader, BooksByAuthorLoader};
 
 #[derive(SimpleObject)]
@@ -205,10 +236,15 @@ pub struct UpdateBookInput {
     pub priority: i32,
     pub format: BookFormat,
     pub store: BookStore,
+    /// Id of the book's latest event the edit is based on. The update is
+    /// rejected with a conflict if the book has newer events.
+    pub expected_event_id: Option<ID>,
 }
 
-impl From<UpdateBookInput> for UpdateBookDto {
-    fn from(book_input: UpdateBookInput) -> Self {
+impl TryFrom<UpdateBookInput> for UpdateBookDto {
+    type Error = PresentationalError;
+
+    fn try_from(book_input: UpdateBookInput) -> Result<Self, Self::Error> {
         let UpdateBookInput {
             id,
             title,
@@ -219,9 +255,10 @@ impl From<UpdateBookInput> for UpdateBookDto {
             priority,
             format,
             store,
+            expected_event_id,
         } = book_input;
 
-        UpdateBookDto::new(
+        let mut dto = UpdateBookDto::new(
             id,
             title,
             author_ids,
@@ -231,7 +268,277 @@ impl From<UpdateBookInput> for UpdateBookDto {
             priority,
             format.into(),
             store.into(),
-        )
+        );
+        dto.expected_version = expected_version(expected_event_id)?;
+        Ok(dto)
+    }
+}
+
+/// Changes to some of a book's fields, as in `BookPatchInput`.
+#[derive(InputObject)]
+pub struct PatchBookInput {
+    pub id: String,
+    #[graphql(flatten)]
+    pub patch: BookPatchInput,
+    /// Id of the book's latest event the edit is based on. The patch is
+    /// rejected with a conflict if the book has newer events.
+    pub expected_event_id: Option<ID>,
+}
+
+/// Changes to some of a book's fields. Omitted fields keep their stored
+/// values.
+#[derive(InputObject)]
+pub struct BookPatchInput {
+    pub title: Option<String>,
+    pub author_ids: Option<AuthorIdsPatch>,
+    pub isbn: Option<String>,
+    pub read: Option<bool>,
+    pub owned: Option<bool>,
+    pub priority: Option<i32>,
+    pub format: Option<BookFormat>,
+    pub store: Option<BookStore>,
+}
+
+/// New authors of a book: either the full list, or authors to add to and
+/// remove from the current one.
+#[derive(OneofObject)]
+pub enum AuthorIdsPatch {
+    Set(Vec<String>),
+    Edit(AuthorIdsEdit),
+}
+
+#[derive(InputObject)]
+pub struct AuthorIdsEdit {
+    /// Authors to append, unless the book already has them.
+    #[graphql(default)]
+    pub add: Vec<String>,
+    #[graphql(default)]
+    pub remove: Vec<String>,
+}
+
+impl From<AuthorIdsPatch> for AuthorIdsPatchDto {
+    fn from(patch: AuthorIdsPatch) -> Self {
+        match patch {
+            AuthorIdsPatch::Set(author_ids) => AuthorIdsPatchDto::Replace(author_ids),
+            AuthorIdsPatch::Edit(AuthorIdsEdit { add, remove }) => {
+                AuthorIdsPatchDto::Edit { add, remove }
+            }
+        }
+    }
+}
+
+impl From<BookPatchInput> for BookPatchDto {
+    fn from(patch: BookPatchInput) -> Self {
+        BookPatchDto {
+            title: patch.title,
+            author_ids: patch.author_ids.map(AuthorIdsPatchDto::from),
+            isbn: patch.isbn,
+            read: patch.read,
+            owned: patch.owned,
+            priority: patch.priority,
+            format: patch.format.map(Into::into),
+            store: patch.store.map(Into::into),
+        }
+    }
+}
+
+impl TryFrom<PatchBookInput> for PatchBookDto {
+    type Error = PresentationalError;
+
+    fn try_from(book_input: PatchBookInput) -> Result<Self, Self::Error> {
+        let mut dto = PatchBookDto::new(book_input.id, book_input.patch.into());
+        dto.expected_version = expected_version(book_input.expected_event_id)?;
+        Ok(dto)
+    }
+}
+
+/// Books to change at once: the listed ones, or all that match a filter.
+#[derive(OneofObject)]
+pub enum BookSelection {
+    Ids(Vec<String>),
+    Filter(BookFilter),
+}
+
+impl TryFrom<BookSelection> for BookSelectionDto {
+    type Error = PresentationalError;
+
+    fn try_from(selection: BookSelection) -> Result<Self, Self::Error> {
+        match selection {
+            BookSelection::Ids(book_ids) => Ok(BookSelectionDto::Ids(book_ids)),
+            BookSelection::Filter(filter) => Ok(BookSelectionDto::Filter(filter.try_into()?)),
+        }
+    }
+}
+
+fn expected_version(event_id: Option<ID>) -> Result<ExpectedVersionDto, PresentationalError> {
+    let event_id = event_id
+        .map(|id| {
+            id.parse::<i64>().map_err(|_| {
+                PresentationalError::Validation(format!("Invalid event id: {}.", id.as_str()))
+            })
+        })
+        .transpose()?;
+    Ok(ExpectedVersionDto { event_id })
+}
+
+/// Conditions that books must all satisfy. Omitted fields do not filter.
+#[derive(Default, InputObject)]
+pub struct BookFilter {
+    pub read: Option<bool>,
+    pub owned: Option<bool>,
+    pub format: Option<BookFormat>,
+    pub store: Option<BookStore>,
+    pub priority: Option<PriorityRange>,
+    /// Matches books written by at least one of the given authors.
+    pub author_ids: Option<Vec<String>>,
+    pub created_at: Option<TimestampRange>,
+    pub updated_at: Option<TimestampRange>,
+    /// `true` matches books with a non-empty ISBN, `false` books without one.
+    pub has_isbn: Option<bool>,
+}
+
+/// Inclusive range of priorities.
+#[derive(InputObject)]
+pub struct PriorityRange {
+    pub min: Option<i32>,
+    pub max: Option<i32>,
+}
+
+/// Range of Unix timestamps in seconds. `from` is inclusive and `to` is exclusive.
+#[derive(InputObject)]
+pub struct TimestampRange {
+    pub from: Option<i64>,
+    pub to: Option<i64>,
+}
+
+impl TryFrom<BookFilter> for BookFilterDto {
+    type Error = PresentationalError;
+
+    fn try_from(filter: BookFilter) -> Result<Self, Self::Error> {
+        let (priority_min, priority_max) = filter
+            .priority
+            .map(|range| (range.min, range.max))
+            .unwrap_or_default();
+        let (created_from, created_to) = timestamp_bounds(filter.created_at)?;
+        let (updated_from, updated_to) = timestamp_bounds(filter.updated_at)?;
+
+        Ok(BookFilterDto {
+            read: filter.read,
+            owned: filter.owned,
+            format: filter.format.map(CommonBookFormat::from),
+            store: filter.store.map(CommonBookStore::from),
+            priority_min,
+            priority_max,
+            author_ids: filter.author_ids,
+            created_from,
+            created_to,
+            updated_from,
+            updated_to,
+            has_isbn: filter.has_isbn,
+        })
+    }
+}
+
+type TimestampBounds = (Option<OffsetDateTime>, Option<OffsetDateTime>);
+
+fn timestamp_bounds(range: Option<TimestampRange>) -> Result<TimestampBounds, PresentationalError> {
+    let Some(range) = range else {
+        return Ok((None, None));
+    };
+    Ok((
+        range.from.map(datetime_from_timestamp).transpose()?,
+        range.to.map(datetime_from_timestamp).transpose()?,
+    ))
+}
+
+pub fn datetime_from_timestamp(timestamp: i64) -> Result<OffsetDateTime, PresentationalError> {
+    OffsetDateTime::from_unix_timestamp(timestamp)
+        .map_err(|_| PresentationalError::Validation(format!("Invalid timestamp: {timestamp}.")))
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Enum)]
+pub enum OrderDirection {
+    #[default]
+    Asc,
+    Desc,
+}
+
+impl From<OrderDirection> for SortDirection {
+    fn from(direction: OrderDirection) -> Self {
+        match direction {
+            OrderDirection::Asc => SortDirection::Asc,
+            OrderDirection::Desc => SortDirection::Desc,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Enum)]
+pub enum BookOrderField {
+    Title,
+    Priority,
+    CreatedAt,
+    UpdatedAt,
+    /// Reading of the book's authors, ordered as authors are by `YOMI`. A
+    /// book with several authors sorts by the reading that comes first.
+    AuthorYomi,
+}
+
+/// Order of a book listing. Books sharing the same value are ordered by id.
+#[derive(InputObject)]
+pub struct BookOrder {
+    pub field: BookOrderField,
+    #[graphql(default)]
+    pub direction: OrderDirection,
+}
+
+impl From<BookOrder> for CommonBookOrder {
+    fn from(order: BookOrder) -> Self {
+        let key = match order.field {
+            BookOrderField::Title => BookSortKey::Title,
+            BookOrderField::Priority => BookSortKey::Priority,
+            BookOrderField::CreatedAt => BookSortKey::CreatedAt,
+            BookOrderField::UpdatedAt => BookSortKey::UpdatedAt,
+            BookOrderField::AuthorYomi => BookSortKey::AuthorYomi,
+        };
+        CommonBookOrder {
+            key,
+            direction: order.direction.into(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Enum)]
+pub enum AuthorOrderField {
+    Name,
+    /// `yomi` in gojūon order, katakana and hiragana alike, and half-width
+    /// kana as full-width. A voiced or small kana sorts next to its plain one
+    /// rather than with it, so がくせい comes after かわ. Authors without
+    /// `yomi` sort by `name`.
+    Yomi,
+    CreatedAt,
+    UpdatedAt,
+}
+
+/// Order of an author listing. Authors sharing the same value are ordered by id.
+#[derive(InputObject)]
+pub struct AuthorOrder {
+    pub field: AuthorOrderField,
+    #[graphql(default)]
+    pub direction: OrderDirection,
+}
+
+impl From<AuthorOrder> for CommonAuthorOrder {
+    fn from(order: AuthorOrder) -> Self {
+        let key = match order.field {
+            AuthorOrderField::Name => AuthorSortKey::Name,
+            AuthorOrderField::Yomi => AuthorSortKey::Yomi,
+            AuthorOrderField::CreatedAt => AuthorSortKey::CreatedAt,
+            AuthorOrderField::UpdatedAt => AuthorSortKey::UpdatedAt,
+        };
+        CommonAuthorOrder {
+            key,
+            direction: order.direction.into(),
+        }
     }
 }
 
@@ -247,13 +554,38 @@ pub struct Author {
 
 #[ComplexObject]
 impl Author {
-    async fn books(&self, ctx: &Context<'_>) -> Result<Vec<Book>> {
+    #[graphql(deprecation = "Use `booksConnection` to fetch books page by page.")]
+    async fn books(&self, ctx: &Context<'_>, order_by: Option<BookOrder>) -> Result<Vec<Book>> {
         let loader = ctx.data_unchecked::<DataLoader<BooksByAuthorLoader<QI>>>();
+        let order = order_by.map(CommonBookOrder::from).unwrap_or_default();
         Ok(loader
-            .load_one(self.id.to_string())
+            .load_one((self.id.to_string(), order))
             .await?
             .unwrap_or_default())
     }
+
+    /// Books written by this author as a Relay connection, oldest first unless
+    /// `orderBy` is given.
+    async fn books_connection(
+        &self,
+        ctx: &Context<'_>,
+        order_by: Option<BookOrder>,
+        after: Option<String>,
+        before: Option<String>,
+        first: Option<i32>,
+        last: Option<i32>,
+    ) -> Result<BookConnection> {
+        let claims = ctx.data_unchecked::<Claims>();
+        let query_use_case = ctx.data_unchecked::<QI>();
+        let order = order_by.map(CommonBookOrder::from).unwrap_or_default();
+        let page = book_page_request(after, before, first, last)?;
+        let books = query_use_case
+            .find_books_page_by_author_id(&claims.sub, self.id.as_str(), order, page)
+            .await
+            .map_err(PresentationalError::from)?;
+
+        Ok(book_connection(books))
+    }
 }
 
 impl Author {
@@ -313,15 +645,46 @@ pub struct UpdateAuthorInput {
     pub id: ID,
     pub name: String,
     pub yomi: Option<String>,
+    /// Id of the author's latest event the edit is based on. The update is
+    /// rejected with a conflict if the author has newer events.
+    pub expected_event_id: Option<ID>,
 }
 
-impl From<UpdateAuthorInput> for UpdateAuthorDto {
-    fn from(val: UpdateAuthorInput) -> Self {
-        UpdateAuthorDto {
+impl TryFrom<UpdateAuthorInput> for UpdateAuthorDto {
+    type Error = PresentationalError;
+
+    fn try_from(val: UpdateAuthorInput) -> Result<Self, Self::Error> {
+        Ok(UpdateAuthorDto {
             id: val.id.to_string(),
             name: val.name,
             yomi: val.yomi,
-        }
+            expected_version: expected_version(val.expected_event_id)?,
+        })
+    }
+}
+
+/// Changes to some of an author's fields. Omitted fields keep their stored
+/// values.
+#[derive(InputObject)]
+pub struct PatchAuthorInput {
+    pub id: ID,
+    pub name: Option<String>,
+    pub yomi: Option<String>,
+    /// Id of the author's latest event the edit is based on. The patch is
+    /// rejected with a conflict if the author has newer events.
+    pub expected_event_id: Option<ID>,
+}
+
+impl TryFrom<PatchAuthorInput> for PatchAuthorDto {
+    type Error = PresentationalError;
+
+    fn try_from(val: PatchAuthorInput) -> Result<Self, Self::Error> {
+        Ok(PatchAuthorDto {
+            id: val.id.to_string(),
+            name: val.name,
+            yomi: val.yomi,
+            expected_version: expected_version(val.expected_event_id)?,
+        })
     }
 }
 
@@ -360,6 +723,66 @@ impl From<ImportBookInput> for ImportBookEntryDto {
     }
 }
 
+/// How `importBooks` and `startImport` treat entries that match a book the
+/// user already has. Only books that existed before the import are matched,
+/// or for `startImport`, before the chunk of entries being imported.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Enum)]
+pub enum ImportMode {
+    /// Creates a book for every entry.
+    #[default]
+    CreateOnly,
+    /// Updates the book with the same ISBN, ignoring hyphens. Entries without
+    /// an ISBN are created.
+    UpsertByIsbn,
+    /// Updates the book with the same title and the same set of author names.
+    UpsertByTitleAndAuthors,
+    /// Leaves a matching book as it is. Entries are matched by ISBN when they
+    /// have one, and by title and author names otherwise.
+    SkipExisting,
+}
+
+impl From<ImportMode> for ImportModeDto {
+    fn from(mode: ImportMode) -> Self {
+        match mode {
+            ImportMode::CreateOnly => ImportModeDto::CreateOnly,
+            ImportMode::UpsertByIsbn => ImportModeDto::UpsertByIsbn,
+            ImportMode::UpsertByTitleAndAuthors => ImportModeDto::UpsertByTitleAndAuthors,
+            ImportMode::SkipExisting => ImportModeDto::SkipExisting,
+        }
+    }
+}
+
+impl From<ImportModeDto> for ImportMode {
+    fn from(mode: ImportModeDto) -> Self {
+        match mode {
+            ImportModeDto::CreateOnly => ImportMode::CreateOnly,
+            ImportModeDto::UpsertByIsbn => ImportMode::UpsertByIsbn,
+            ImportModeDto::UpsertByTitleAndAuthors => ImportMode::UpsertByTitleAndAuthors,
+            ImportModeDto::SkipExisting => ImportMode::SkipExisting,
+        }
+    }
+}
+
+/// A field that differs from the previous event of the same entity. Values are
+/// rendered as strings and absent on the side where the field was unset; each
+/// added or removed author id is reported as its own `authorIds` change.
+#[derive(SimpleObject)]
+pub struct FieldChange {
+    pub field: String,
+    pub old_value: Option<String>,
+    pub new_value: Option<String>,
+}
+
+impl From<FieldChangeDto> for FieldChange {
+    fn from(dto: FieldChangeDto) -> Self {
+        Self {
+            field: dto.field,
+            old_value: dto.old_value,
+            new_value: dto.new_value,
+        }
+    }
+}
+
 #[derive(SimpleObject)]
 pub struct BookEventEntry {
     pub event_id: ID,
@@ -378,6 +801,7 @@ pub struct BookEventEntry {
     pub book_updated_at: Option<i64>,
     pub changed_at: i64,
     pub extra: Option<Json<Value>>,
+    pub changes: Vec<FieldChange>,
 }
 
 impl From<BookEventDto> for BookEventEntry {
@@ -399,6 +823,7 @@ impl From<BookEventDto> for BookEventEntry {
             book_updated_at: dto.book_updated_at.map(|t| t.unix_timestamp()),
             changed_at: dto.changed_at.unix_timestamp(),
             extra: dto.extra.map(Json),
+            changes: dto.changes.into_iter().map(FieldChange::from).collect(),
         }
     }
 }
@@ -415,6 +840,7 @@ pub struct AuthorEventEntry {
     pub author_updated_at: Option<i64>,
     pub changed_at: i64,
     pub extra: Option<Json<Value>>,
+    pub changes: Vec<FieldChange>,
 }
 
 impl From<AuthorEventDto> for AuthorEventEntry {
@@ -430,10 +856,558 @@ impl From<AuthorEventDto> for AuthorEventEntry {
             author_updated_at: dto.author_updated_at.map(|t| t.unix_timestamp()),
             changed_at: dto.changed_at.unix_timestamp(),
             extra: dto.extra.map(Json),
+            changes: dto.changes.into_iter().map(FieldChange::from).collect(),
+        }
+    }
+}
+
+/// A book whose latest event deleted it.
+#[derive(SimpleObject)]
+pub struct DeletedBook {
+    /// The latest event still holding the book's state. Pass its `eventId`
+    /// to `restoreBook` to bring the book back.
+    pub last_known: BookEventEntry,
+    /// The event that deleted the book.
+    pub deletion: BookEventEntry,
+}
+
+impl From<DeletedBookDto> for DeletedBook {
+    fn from(dto: DeletedBookDto) -> Self {
+        Self {
+            last_known: dto.last_known.into(),
+            deletion: dto.deletion.into(),
+        }
+    }
+}
+
+/// An author whose latest event deleted it.
+#[derive(SimpleObject)]
+pub struct DeletedAuthor {
+    /// The latest event still holding the author's state. Pass its `eventId`
+    /// to `restoreAuthor` to bring the author back.
+    pub last_known: AuthorEventEntry,
+    /// The event that deleted the author.
+    pub deletion: AuthorEventEntry,
+    /// The author it was merged into, if it was deleted by a merge.
+    pub merged_into_author_id: Option<ID>,
+}
+
+impl From<DeletedAuthorDto> for DeletedAuthor {
+    fn from(dto: DeletedAuthorDto) -> Self {
+        Self {
+            last_known: dto.last_known.into(),
+            deletion: dto.deletion.into(),
+            merged_into_author_id: dto.merged_into_author_id.map(ID),
+        }
+    }
+}
+
+#[derive(SimpleObject)]
+pub struct PurgeTrashPayload {
+    pub purged_book_count: i64,
+    pub purged_author_count: i64,
+    /// Event sets removed because the purge left them empty.
+    pub purged_event_set_count: i64,
+    /// The `purge_trash` event set recording the purge. Null when nothing
+    /// was purged, as nothing was recorded.
+    pub event_set_id: Option<ID>,
+}
+
+impl From<PurgedTrashDto> for PurgeTrashPayload {
+    fn from(dto: PurgedTrashDto) -> Self {
+        Self {
+            purged_book_count: dto.book_count,
+            purged_author_count: dto.author_count,
+            purged_event_set_count: dto.event_set_count,
+            event_set_id: dto.event_set_id.map(ID),
+        }
+    }
+}
+
+#[derive(SimpleObject)]
+pub struct BulkUpdateBooksPayload {
+    /// Books the patch changed. Selected books it left as they were are not
+    /// counted and get no event.
+    pub updated_count: i64,
+    /// Null when no book changed, as nothing was recorded.
+    pub event_set_id: Option<ID>,
+}
+
+impl From<BulkUpdateBooksResultDto> for BulkUpdateBooksPayload {
+    fn from(dto: BulkUpdateBooksResultDto) -> Self {
+        Self {
+            updated_count: dto.updated_count,
+            event_set_id: dto.event_set_id.map(ID),
+        }
+    }
+}
+
+/// One operation of an `applyChanges` batch. Ids given to it, including
+/// `authorIds`, may be the `tempId` of a create earlier in the batch.
+#[derive(OneofObject)]
+pub enum ChangeOperation {
+    CreateBook(CreateBookChange),
+    UpdateBook(UpdateBookInput),
+    DeleteBook(ID),
+    CreateAuthor(CreateAuthorChange),
+    UpdateAuthor(UpdateAuthorInput),
+    DeleteAuthor(ID),
+}
+
+/// A book to create, which later operations can refer to by `tempId`.
+#[derive(InputObject)]
+pub struct CreateBookChange {
+    pub temp_id: Option<String>,
+    #[graphql(flatten)]
+    pub book: CreateBookInput,
+}
+
+/// An author to create, which later operations can refer to by `tempId`.
+#[derive(InputObject)]
+pub struct CreateAuthorChange {
+    pub temp_id: Option<String>,
+    #[graphql(flatten)]
+    pub author: CreateAuthorInput,
+}
+
+impl TryFrom<ChangeOperation> for ChangeOperationDto {
+    type Error = PresentationalError;
+
+    fn try_from(operation: ChangeOperation) -> Result<Self, Self::Error> {
+        let dto = match operation {
+            ChangeOperation::CreateBook(change) => ChangeOperationDto::CreateBook {
+                temp_id: change.temp_id,
+                book: change.book.into(),
+            },
+            ChangeOperation::UpdateBook(book_input) => {
+                ChangeOperationDto::UpdateBook(book_input.try_into()?)
+            }
+            ChangeOperation::DeleteBook(id) => ChangeOperationDto::DeleteBook { id: id.0 },
+            ChangeOperation::CreateAuthor(change) => ChangeOperationDto::CreateAuthor {
+                temp_id: change.temp_id,
+                author: change.author.into(),
+            },
+            ChangeOperation::UpdateAuthor(author_input) => {
+                ChangeOperationDto::UpdateAuthor(author_input.try_into()?)
+            }
+            ChangeOperation::DeleteAuthor(id) => ChangeOperationDto::DeleteAuthor { id: id.0 },
+        };
+        Ok(dto)
+    }
+}
+
+/// The outcome of one `applyChanges` operation. A create or update sets
+/// `book` or `author` to the entity as saved; a delete sets neither.
+#[derive(SimpleObject)]
+pub struct AppliedChange {
+    /// Id of the entity the operation was applied to, generated for a create.
+    pub id: ID,
+    /// The `tempId` the create was given.
+    pub temp_id: Option<String>,
+    pub book: Option<Book>,
+    pub author: Option<Author>,
+}
+
+impl From<AppliedChangeDto> for AppliedChange {
+    fn from(dto: AppliedChangeDto) -> Self {
+        match dto {
+            AppliedChangeDto::Book { temp_id, book } => Self {
+                id: ID(book.id.clone()),
+                temp_id,
+                book: Some(book.into()),
+                author: None,
+            },
+            AppliedChangeDto::Author { temp_id, author } => Self {
+                id: ID(author.id.clone()),
+                temp_id,
+                book: None,
+                author: Some(author.into()),
+            },
+            AppliedChangeDto::DeletedBook { id } | AppliedChangeDto::DeletedAuthor { id } => Self {
+                id: ID(id),
+                temp_id: None,
+                book: None,
+                author: None,
+            },
+        }
+    }
+}
+
+#[derive(SimpleObject)]
+pub struct ApplyChangesPayload {
+    /// One entry per operation, in the order they were given.
+    pub results: Vec<AppliedChange>,
+    pub event_set_id: ID,
+}
+
+impl From<ApplyChangesResultDto> for ApplyChangesPayload {
+    fn from(dto: ApplyChangesResultDto) -> Self {
+        Self {
+            results: dto.value.into_iter().map(AppliedChange::from).collect(),
+            event_set_id: ID(dto.event_set_id),
+        }
+    }
+}
+
+/// Books and authors matching a search, each list best match first.
+#[derive(SimpleObject)]
+pub struct SearchResult {
+    pub books: Vec<Book>,
+    pub authors: Vec<Author>,
+}
+
+impl From<SearchResultDto> for SearchResult {
+    fn from(dto: SearchResultDto) -> Self {
+        Self {
+            books: dto.books.into_iter().map(Book::from).collect(),
+            authors: dto.authors.into_iter().map(Author::from).collect(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Enum)]
+pub enum ActivityGranularity {
+    #[default]
+    Day,
+    /// Weeks starting on Monday.
+    Week,
+    Month,
+}
+
+impl From<ActivityGranularity> for CommonActivityGranularity {
+    fn from(granularity: ActivityGranularity) -> Self {
+        match granularity {
+            ActivityGranularity::Day => CommonActivityGranularity::Day,
+            ActivityGranularity::Week => CommonActivityGranularity::Week,
+            ActivityGranularity::Month => CommonActivityGranularity::Month,
+        }
+    }
+}
+
+/// Book activity within one day, week or month.
+#[derive(SimpleObject)]
+pub struct ActivityPeriod {
+    /// Unix timestamp of the start of the period.
+    pub period_start: i64,
+    /// Books marked as read. Books created as read do not count.
+    pub finished_count: i64,
+    /// Books created.
+    pub added_count: i64,
+    /// Books deleted.
+    pub deleted_count: i64,
+}
+
+impl From<ActivityPeriodDto> for ActivityPeriod {
+    fn from(dto: ActivityPeriodDto) -> Self {
+        Self {
+            period_start: dto.start.unix_timestamp(),
+            finished_count: dto.finished_count,
+            added_count: dto.added_count,
+            deleted_count: dto.deleted_count,
+        }
+    }
+}
+
+/// Aggregates over all of the logged-in user's books.
+#[derive(SimpleObject)]
+pub struct LibraryStats {
+    pub total_books: i64,
+    pub read_count: i64,
+    pub unread_count: i64,
+    pub owned_count: i64,
+    pub not_owned_count: i64,
+    /// Books that are owned but not read yet.
+    pub unread_owned_count: i64,
+    /// Every format, including those without books.
+    pub formats: Vec<FormatCount>,
+    /// Every store, including those without books.
+    pub stores: Vec<StoreCount>,
+    /// Priorities split into buckets of ten, lowest first.
+    pub priority_histogram: Vec<PriorityBucket>,
+    /// Authors with the most books, most books first.
+    pub top_authors: Vec<AuthorBookCount>,
+}
+
+#[derive(SimpleObject)]
+pub struct FormatCount {
+    pub format: BookFormat,
+    pub count: i64,
+}
+
+#[derive(SimpleObject)]
+pub struct StoreCount {
+    pub store: BookStore,
+    pub count: i64,
+}
+
+/// Number of books whose priority lies between `min` and `max`, inclusive.
+#[derive(SimpleObject)]
+pub struct PriorityBucket {
+    pub min: i32,
+    pub max: i32,
+    pub count: i64,
+}
+
+impl From<PriorityBucketDto> for PriorityBucket {
+    fn from(dto: PriorityBucketDto) -> Self {
+        Self {
+            min: dto.min,
+            max: dto.max,
+            count: dto.count,
+        }
+    }
+}
+
+#[derive(SimpleObject)]
+pub struct AuthorBookCount {
+    pub author: Author,
+    pub book_count: i64,
+}
+
+impl From<LibraryStatsDto> for LibraryStats {
+    fn from(dto: LibraryStatsDto) -> Self {
+        Self {
+            total_books: dto.total_count,
+            read_count: dto.read_count,
+            unread_count: dto.total_count - dto.read_count,
+            owned_count: dto.owned_count,
+            not_owned_count: dto.total_count - dto.owned_count,
+            unread_owned_count: dto.unread_owned_count,
+            formats: dto
+                .format_counts
+                .into_iter()
+                .map(|(format, count)| FormatCount {
+                    format: format.into(),
+                    count,
+                })
+                .collect(),
+            stores: dto
+                .store_counts
+                .into_iter()
+                .map(|(store, count)| StoreCount {
+                    store: store.into(),
+                    count,
+                })
+                .collect(),
+            priority_histogram: dto
+                .priority_histogram
+                .into_iter()
+                .map(PriorityBucket::from)
+                .collect(),
+            top_authors: dto
+                .top_authors
+                .into_iter()
+                .map(|(author, book_count)| AuthorBookCount {
+                    author: Author::from(author),
+                    book_count,
+                })
+                .collect(),
+        }
+    }
+}
+
+/// A moment in the change history: a point in time, or the moment an event
+/// set was recorded.
+#[derive(OneofObject)]
+pub enum PointInTime {
+    At(OffsetDateTime),
+    EventSetId(ID),
+}
+
+impl From<PointInTime> for PointInTimeDto {
+    fn from(point: PointInTime) -> Self {
+        match point {
+            PointInTime::At(at) => PointInTimeDto::Timestamp(at),
+            PointInTime::EventSetId(id) => PointInTimeDto::EventSet(id.0),
+        }
+    }
+}
+
+/// A book as it was recorded at some point in the change history.
+#[derive(SimpleObject)]
+pub struct HistoricalBook {
+    pub id: ID,
+    pub title: String,
+    pub author_ids: Vec<ID>,
+    pub isbn: String,
+    pub read: bool,
+    pub owned: bool,
+    pub priority: i32,
+    pub format: BookFormat,
+    pub store: BookStore,
+    pub created_at: i64,
+    pub updated_at: i64,
+}
+
+impl From<BookDto> for HistoricalBook {
+    fn from(dto: BookDto) -> Self {
+        Self {
+            id: ID(dto.id),
+            title: dto.title,
+            author_ids: dto.author_ids.into_iter().map(ID).collect(),
+            isbn: dto.isbn,
+            read: dto.read,
+            owned: dto.owned,
+            priority: dto.priority,
+            format: dto.format.into(),
+            store: dto.store.into(),
+            created_at: dto.created_at.unix_timestamp(),
+            updated_at: dto.updated_at.unix_timestamp(),
         }
     }
 }
 
+/// An author as it was recorded at some point in the change history.
+#[derive(SimpleObject)]
+pub struct HistoricalAuthor {
+    pub id: ID,
+    pub name: String,
+    pub yomi: String,
+    pub created_at: OffsetDateTime,
+    pub updated_at: OffsetDateTime,
+}
+
+impl From<AuthorDto> for HistoricalAuthor {
+    fn from(dto: AuthorDto) -> Self {
+        Self {
+            id: ID(dto.id),
+            name: dto.name,
+            yomi: dto.yomi,
+            created_at: dto.created_at,
+            updated_at: dto.updated_at,
+        }
+    }
+}
+
+#[derive(SimpleObject)]
+pub struct BookChange {
+    pub before: HistoricalBook,
+    pub after: HistoricalBook,
+}
+
+#[derive(SimpleObject)]
+pub struct AuthorChange {
+    pub before: HistoricalAuthor,
+    pub after: HistoricalAuthor,
+}
+
+#[derive(SimpleObject)]
+pub struct BookDiff {
+    /// Books present only at the later point.
+    pub added: Vec<HistoricalBook>,
+    /// Books present only at the earlier point.
+    pub removed: Vec<HistoricalBook>,
+    /// Books present at both points but recorded differently.
+    pub changed: Vec<BookChange>,
+}
+
+impl From<EntityDiffDto<BookDto>> for BookDiff {
+    fn from(dto: EntityDiffDto<BookDto>) -> Self {
+        Self {
+            added: dto.added.into_iter().map(HistoricalBook::from).collect(),
+            removed: dto.removed.into_iter().map(HistoricalBook::from).collect(),
+            changed: dto
+                .changed
+                .into_iter()
+                .map(|(before, after)| BookChange {
+                    before: before.into(),
+                    after: after.into(),
+                })
+                .collect(),
+        }
+    }
+}
+
+#[derive(SimpleObject)]
+pub struct AuthorDiff {
+    /// Authors present only at the later point.
+    pub added: Vec<HistoricalAuthor>,
+    /// Authors present only at the earlier point.
+    pub removed: Vec<HistoricalAuthor>,
+    /// Authors present at both points but recorded differently.
+    pub changed: Vec<AuthorChange>,
+}
+
+impl From<EntityDiffDto<AuthorDto>> for AuthorDiff {
+    fn from(dto: EntityDiffDto<AuthorDto>) -> Self {
+        Self {
+            added: dto.added.into_iter().map(HistoricalAuthor::from).collect(),
+            removed: dto
+                .removed
+                .into_iter()
+                .map(HistoricalAuthor::from)
+                .collect(),
+            changed: dto
+                .changed
+                .into_iter()
+                .map(|(before, after)| AuthorChange {
+                    before: before.into(),
+                    after: after.into(),
+                })
+                .collect(),
+        }
+    }
+}
+
+/// Differences in the library between two points in the change history.
+#[derive(SimpleObject)]
+pub struct LibraryDiff {
+    pub books: BookDiff,
+    pub authors: AuthorDiff,
+}
+
+impl From<LibraryDiffDto> for LibraryDiff {
+    fn from(dto: LibraryDiffDto) -> Self {
+        Self {
+            books: dto.books.into(),
+            authors: dto.authors.into(),
+        }
+    }
+}
+
+/// Conditions that events must all satisfy. Omitted fields do not filter.
+#[derive(Default, InputObject)]
+pub struct EventFilter {
+    /// Matches events with any of the given operations, such as `update`.
+    pub operations: Option<Vec<String>>,
+    pub changed_at: Option<TimestampRange>,
+}
+
+impl TryFrom<EventFilter> for HistoryFilterDto {
+    type Error = PresentationalError;
+
+    fn try_from(filter: EventFilter) -> Result<Self, Self::Error> {
+        let (from, to) = timestamp_bounds(filter.changed_at)?;
+        Ok(HistoryFilterDto {
+            operations: filter.operations,
+            from,
+            to,
+        })
+    }
+}
+
+/// Conditions that event sets must all satisfy. Omitted fields do not filter.
+#[derive(Default, InputObject)]
+pub struct EventSetFilter {
+    /// Matches event sets with any of the given operations, such as
+    /// `import_books`.
+    pub operations: Option<Vec<String>>,
+    pub created_at: Option<TimestampRange>,
+}
+
+impl TryFrom<EventSetFilter> for HistoryFilterDto {
+    type Error = PresentationalError;
+
+    fn try_from(filter: EventSetFilter) -> Result<Self, Self::Error> {
+        let (from, to) = timestamp_bounds(filter.created_at)?;
+        Ok(HistoryFilterDto {
+            operations: filter.operations,
+            from,
+            to,
+        })
+    }
+}
+
 #[derive(SimpleObject)]
 pub struct EventSetEntry {
     pub id: ID,
@@ -526,22 +1500,210 @@ pub struct DeleteBookPayload {
     pub event_set_id: ID,
 }
 
+#[derive(SimpleObject)]
+pub struct DeleteBooksPayload {
+    pub book_ids: Vec<ID>,
+    pub event_set_id: ID,
+}
+
 #[derive(SimpleObject)]
 pub struct DeleteAuthorPayload {
     pub author_id: ID,
     pub event_set_id: ID,
 }
 
+#[derive(SimpleObject)]
+pub struct DeleteAuthorsPayload {
+    pub author_ids: Vec<ID>,
+    pub event_set_id: ID,
+}
+
 #[derive(SimpleObject)]
 pub struct ImportBooksPayload {
+    /// Books the import created. On a dry run, the books it would create:
+    /// their ids are not kept, and their `authors` leave out the authors
+    /// listed in `newAuthorNames`.
+    pub created: Vec<Book>,
+    /// Existing books the import changed.
+    pub updated: Vec<Book>,
+    /// Existing books entries were skipped for, as they were.
+    pub skipped: Vec<Book>,
+    #[graphql(deprecation = "Use `created`, which lists the same books.")]
     pub books: Vec<Book>,
-    pub event_set_id: ID,
+    /// Names of the authors the import created because no author had them.
+    pub new_author_names: Vec<String>,
+    /// Names of existing authors the import used.
+    pub reused_author_names: Vec<String>,
+    /// Entries a dry run or partial import found invalid. Otherwise an
+    /// invalid entry fails the whole import.
+    pub errors: Vec<ImportBookError>,
+    /// Null on dry runs and when every entry was skipped, as nothing was
+    /// recorded.
+    pub event_set_id: Option<ID>,
+}
+
+impl From<ImportBooksResultDto> for ImportBooksPayload {
+    fn from(dto: ImportBooksResultDto) -> Self {
+        let created: Vec<Book> = dto.created.into_iter().map(Book::from).collect();
+        Self {
+            books: created.clone(),
+            created,
+            updated: dto.updated.into_iter().map(Book::from).collect(),
+            skipped: dto.skipped.into_iter().map(Book::from).collect(),
+            new_author_names: dto.new_author_names,
+            reused_author_names: dto.reused_author_names,
+            errors: dto.errors.into_iter().map(ImportBookError::from).collect(),
+            event_set_id: dto.event_set_id.map(ID),
+        }
+    }
+}
+
+#[derive(SimpleObject)]
+pub struct ImportBookError {
+    /// Position of the entry in `books`, starting at 0.
+    pub index: usize,
+    pub field: ImportBookField,
+    pub message: String,
+}
+
+impl From<ImportBookErrorDto> for ImportBookError {
+    fn from(dto: ImportBookErrorDto) -> Self {
+        Self {
+            index: dto.index,
+            field: dto.field.into(),
+            message: dto.message,
+        }
+    }
+}
+
+/// A field of `ImportBookInput`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Enum)]
+pub enum ImportBookField {
+    Title,
+    AuthorNames,
+    Isbn,
+    Priority,
+}
+
+impl From<ImportBookFieldDto> for ImportBookField {
+    fn from(field: ImportBookFieldDto) -> Self {
+        match field {
+            ImportBookFieldDto::Title => ImportBookField::Title,
+            ImportBookFieldDto::AuthorNames => ImportBookField::AuthorNames,
+            ImportBookFieldDto::Isbn => ImportBookField::Isbn,
+            ImportBookFieldDto::Priority => ImportBookField::Priority,
+        }
+    }
+}
+
+/// An import started with `startImport`, run in the background in chunks.
+#[derive(SimpleObject)]
+pub struct ImportJob {
+    pub id: ID,
+    pub status: ImportJobStatus,
+    pub mode: ImportMode,
+    pub partial: bool,
+    /// Number of entries the job was started with.
+    pub total_count: usize,
+    /// Entries handled so far, invalid ones included.
+    pub processed_count: usize,
+    /// Number of entries listed in `failures`.
+    pub failed_count: usize,
+    /// Entries of a partial job that were found invalid.
+    pub failures: Vec<ImportBookError>,
+    /// One event set per chunk that created or updated books, oldest first.
+    pub event_set_ids: Vec<ID>,
+    /// Why the job stopped, when its status is `FAILED`.
+    pub error: Option<String>,
+    pub created_at: OffsetDateTime,
+    pub updated_at: OffsetDateTime,
+}
+
+impl From<ImportJobDto> for ImportJob {
+    fn from(dto: ImportJobDto) -> Self {
+        Self {
+            id: ID(dto.id),
+            status: dto.status.into(),
+            mode: dto.mode.into(),
+            partial: dto.partial,
+            total_count: dto.total_count,
+            processed_count: dto.processed_count,
+            failed_count: dto.failures.len(),
+            failures: dto
+                .failures
+                .into_iter()
+                .map(ImportBookError::from)
+                .collect(),
+            event_set_ids: dto.event_set_ids.into_iter().map(ID).collect(),
+            error: dto.error,
+            created_at: dto.created_at,
+            updated_at: dto.updated_at,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Enum)]
+pub enum ImportJobStatus {
+    /// Waiting for the jobs started before it.
+    Pending,
+    Running,
+    Succeeded,
+    /// Stopped by an error. The chunks written before it are kept.
+    Failed,
+    /// Stopped by `cancelImport`. The chunks written before it are kept.
+    Cancelled,
+}
+
+impl From<ImportJobStatusDto> for ImportJobStatus {
+    fn from(status: ImportJobStatusDto) -> Self {
+        match status {
+            ImportJobStatusDto::Pending => ImportJobStatus::Pending,
+            ImportJobStatusDto::Running => ImportJobStatus::Running,
+            ImportJobStatusDto::Succeeded => ImportJobStatus::Succeeded,
+            ImportJobStatusDto::Failed => ImportJobStatus::Failed,
+            ImportJobStatusDto::Cancelled => ImportJobStatus::Cancelled,
+        }
+    }
 }
 
 #[derive(SimpleObject)]
 pub struct RestoreBookPayload {
     pub book: Option<Book>,
     pub event_set_id: ID,
+    /// Deleted authors restored along with the book.
+    pub restored_authors: Vec<Author>,
+    pub remapped_authors: Vec<AuthorRemap>,
+}
+
+impl RestoreBookPayload {
+    pub fn new(dto: RestoredBookDto, event_set_id: ID) -> Self {
+        Self {
+            book: dto.book.map(Book::from),
+            event_set_id,
+            restored_authors: dto.restored_authors.into_iter().map(Author::from).collect(),
+            remapped_authors: dto
+                .remapped_authors
+                .into_iter()
+                .map(AuthorRemap::from)
+                .collect(),
+        }
+    }
+}
+
+/// An author merged away since the restored state, replaced by `author`.
+#[derive(SimpleObject)]
+pub struct AuthorRemap {
+    pub merged_author_id: ID,
+    pub author: Author,
+}
+
+impl From<AuthorRemapDto> for AuthorRemap {
+    fn from(dto: AuthorRemapDto) -> Self {
+        Self {
+            merged_author_id: ID(dto.merged_author_id),
+            author: dto.author.into(),
+        }
+    }
 }
 
 #[derive(SimpleObject)]
@@ -549,3 +1711,142 @@ pub struct RestoreAuthorPayload {
     pub author: Option<Author>,
     pub event_set_id: ID,
 }
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Enum)]
+pub enum RevertEntityType {
+    Book,
+    Author,
+}
+
+impl From<RevertEntityTypeDto> for RevertEntityType {
+    fn from(entity_type: RevertEntityTypeDto) -> Self {
+        match entity_type {
+            RevertEntityTypeDto::Book => RevertEntityType::Book,
+            RevertEntityTypeDto::Author => RevertEntityType::Author,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Enum)]
+pub enum RevertAction {
+    /// Brings back the state the entity had before the event set.
+    Restore,
+    /// Deletes an entity the event set created.
+    Remove,
+}
+
+impl From<RevertActionDto> for RevertAction {
+    fn from(action: RevertActionDto) -> Self {
+        match action {
+            RevertActionDto::Restore => RevertAction::Restore,
+            RevertActionDto::Remove => RevertAction::Remove,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Enum)]
+pub enum RevertConflictReason {
+    /// The entity was changed again after the event set.
+    ChangedLater,
+    /// The author would be removed while other books still list it.
+    HasAssociatedBooks,
+    /// The book would list authors that no longer exist.
+    MissingAuthors,
+    /// Another author has the name the author would go back to.
+    NameTaken,
+}
+
+/// The change reverting applies to one entity, diffed from its current state.
+#[derive(SimpleObject)]
+pub struct RevertChange {
+    pub entity_type: RevertEntityType,
+    pub entity_id: ID,
+    pub action: RevertAction,
+    pub changes: Vec<FieldChange>,
+}
+
+impl From<RevertChangeDto> for RevertChange {
+    fn from(dto: RevertChangeDto) -> Self {
+        Self {
+            entity_type: dto.entity_type.into(),
+            entity_id: ID(dto.entity_id),
+            action: dto.action.into(),
+            changes: dto.changes.into_iter().map(FieldChange::from).collect(),
+        }
+    }
+}
+
+#[derive(SimpleObject)]
+pub struct RevertConflict {
+    pub entity_type: RevertEntityType,
+    pub entity_id: ID,
+    pub reason: RevertConflictReason,
+    /// For `CHANGED_LATER`, the first event after the reverted set.
+    pub later_event_id: Option<ID>,
+    pub later_event_set_id: Option<ID>,
+    /// The books still listing the author, the authors the book is missing,
+    /// or the author that has the name now.
+    pub related_ids: Vec<ID>,
+}
+
+impl From<RevertConflictDto> for RevertConflict {
+    fn from(dto: RevertConflictDto) -> Self {
+        let (reason, later_event_id, later_event_set_id, related_ids) = match dto.reason {
+            RevertConflictReasonDto::ChangedLater {
+                event_id,
+                event_set_id,
+            } => (
+                RevertConflictReason::ChangedLater,
+                Some(ID(event_id.to_string())),
+                Some(ID(event_set_id)),
+                vec![],
+            ),
+            RevertConflictReasonDto::HasAssociatedBooks { book_ids } => (
+                RevertConflictReason::HasAssociatedBooks,
+                None,
+                None,
+                book_ids,
+            ),
+            RevertConflictReasonDto::MissingAuthors { author_ids } => {
+                (RevertConflictReason::MissingAuthors, None, None, author_ids)
+            }
+            RevertConflictReasonDto::NameTaken { author_id } => {
+                (RevertConflictReason::NameTaken, None, None, vec![author_id])
+            }
+        };
+        Self {
+            entity_type: dto.entity_type.into(),
+            entity_id: ID(dto.entity_id),
+            reason,
+            later_event_id,
+            later_event_set_id,
+            related_ids: related_ids.into_iter().map(ID).collect(),
+        }
+    }
+}
+
+#[derive(SimpleObject)]
+pub struct RevertEventSetPayload {
+    /// Whether the changes were applied. They are not on dry runs or when
+    /// there are conflicts.
+    pub applied: bool,
+    /// The event set recording the revert, once applied.
+    pub event_set_id: Option<ID>,
+    pub changes: Vec<RevertChange>,
+    pub conflicts: Vec<RevertConflict>,
+}
+
+impl From<RevertEventSetResultDto> for RevertEventSetPayload {
+    fn from(dto: RevertEventSetResultDto) -> Self {
+        Self {
+            applied: dto.event_set_id.is_some(),
+            event_set_id: dto.event_set_id.map(ID),
+            changes: dto.changes.into_iter().map(RevertChange::from).collect(),
+            conflicts: dto
+                .conflicts
+                .into_iter()
+                .map(RevertConflict::from)
+                .collect(),
+        }
+    }
+}
@@ -1,14 +1,33 @@
 use std::sync::Arc;
 
 use async_graphql::{Context, ID, Object};
+use time::OffsetDateTime;
 
 use crate::{
+    common::sort::{AuthorOrder as CommonAuthorOrder, BookOrder as CommonBookOrder},
     presentation::{error::PresentationalError, extractor::claims::Claims},
-    use_case::traits::query::QueryUseCase,
+    use_case::{
+        dto::{
+            activity::ActivityRangeDto, history::PointInTimeDto, search::DEFAULT_SEARCH_LIMIT,
+            stats::DEFAULT_TOP_AUTHORS,
+        },
+        traits::query::QueryUseCase,
+    },
 };
 
-use super::object::{
-    Author, AuthorEventEntry, Book, BookEventEntry, EventSetDetail, EventSetEntry, User,
+use super::{
+    connection::{
+        AuthorConnection, AuthorEventConnection, BookConnection, BookEventConnection,
+        EventSetConnection, author_connection, author_event_connection, author_page_request,
+        book_connection, book_event_connection, book_page_request, event_set_connection,
+        history_page_request,
+    },
+    object::{
+        ActivityGranularity, ActivityPeriod, Author, AuthorEventEntry, AuthorOrder, Book,
+        BookEventEntry, BookFilter, BookOrder, DeletedAuthor, DeletedBook, EventFilter,
+        EventSetDetail, EventSetEntry, EventSetFilter, HistoricalAuthor, HistoricalBook, ImportJob,
+        LibraryDiff, LibraryStats, PointInTime, SearchResult, User, datetime_from_timestamp,
+    },
 };
 
 pub struct Query<QUC> {
@@ -42,14 +61,50 @@ where
         Ok(book.map(Book::from))
     }
 
-    async fn books(&self, ctx: &Context<'_>) -> Result<Vec<Book>, PresentationalError> {
+    #[graphql(deprecation = "Use `booksConnection` to fetch books page by page.")]
+    async fn books(
+        &self,
+        ctx: &Context<'_>,
+        filter: Option<BookFilter>,
+        order_by: Option<BookOrder>,
+    ) -> Result<Vec<Book>, PresentationalError> {
         let claims = get_claims(ctx)?;
-        let books = self.query_use_case.find_all_books(&claims.sub).await?;
+        let filter = filter.unwrap_or_default().try_into()?;
+        let order = order_by.map(CommonBookOrder::from).unwrap_or_default();
+        let books = self
+            .query_use_case
+            .find_all_books(&claims.sub, filter, order)
+            .await?;
         let books: Vec<Book> = books.into_iter().map(Book::from).collect();
 
         Ok(books)
     }
 
+    /// Returns the logged-in user's books as a Relay connection.
+    /// Books are ordered by creation time, oldest first, unless `orderBy` is given.
+    #[allow(clippy::too_many_arguments)]
+    async fn books_connection(
+        &self,
+        ctx: &Context<'_>,
+        filter: Option<BookFilter>,
+        order_by: Option<BookOrder>,
+        after: Option<String>,
+        before: Option<String>,
+        first: Option<i32>,
+        last: Option<i32>,
+    ) -> Result<BookConnection, PresentationalError> {
+        let claims = get_claims(ctx)?;
+        let filter = filter.unwrap_or_default().try_into()?;
+        let order = order_by.map(CommonBookOrder::from).unwrap_or_default();
+        let page = book_page_request(after, before, first, last)?;
+        let books = self
+            .query_use_case
+            .find_books_page(&claims.sub, filter, order, page)
+            .await?;
+
+        Ok(book_connection(books))
+    }
+
     async fn author(
         &self,
         ctx: &Context<'_>,
@@ -63,15 +118,155 @@ where
         Ok(author.map(Author::from))
     }
 
-    async fn authors(&self, ctx: &Context<'_>) -> Result<Vec<Author>, PresentationalError> {
+    #[graphql(deprecation = "Use `authorsConnection` to fetch authors page by page.")]
+    async fn authors(
+        &self,
+        ctx: &Context<'_>,
+        order_by: Option<AuthorOrder>,
+    ) -> Result<Vec<Author>, PresentationalError> {
         let claims = get_claims(ctx)?;
-        let authors = self.query_use_case.find_all_authors(&claims.sub).await?;
+        let order = order_by.map(CommonAuthorOrder::from).unwrap_or_default();
+        let authors = self
+            .query_use_case
+            .find_all_authors(&claims.sub, order)
+            .await?;
         let authors: Vec<Author> = authors.into_iter().map(Author::from).collect();
         Ok(authors)
     }
 
+    /// Returns the logged-in user's authors as a Relay connection.
+    /// Authors are ordered by name unless `orderBy` is given.
+    async fn authors_connection(
+        &self,
+        ctx: &Context<'_>,
+        order_by: Option<AuthorOrder>,
+        after: Option<String>,
+        before: Option<String>,
+        first: Option<i32>,
+        last: Option<i32>,
+    ) -> Result<AuthorConnection, PresentationalError> {
+        let claims = get_claims(ctx)?;
+        let order = order_by.map(CommonAuthorOrder::from).unwrap_or_default();
+        let page = author_page_request(after, before, first, last)?;
+        let authors = self
+            .query_use_case
+            .find_authors_page(&claims.sub, order, page)
+            .await?;
+
+        Ok(author_connection(authors))
+    }
+
+    /// Searches the logged-in user's books and authors.
+    /// Matches titles, author names and yomi by substring, ignoring
+    /// full-width/half-width and katakana/hiragana differences. Every
+    /// whitespace-separated term must match.
+    async fn search(
+        &self,
+        ctx: &Context<'_>,
+        query: String,
+        #[graphql(default_with = "DEFAULT_SEARCH_LIMIT")] limit: i32,
+    ) -> Result<SearchResult, PresentationalError> {
+        let claims = get_claims(ctx)?;
+        let result = self
+            .query_use_case
+            .search(&claims.sub, &query, limit)
+            .await?;
+        Ok(SearchResult::from(result))
+    }
+
+    /// Returns counts and breakdowns over the logged-in user's books.
+    async fn library_stats(
+        &self,
+        ctx: &Context<'_>,
+        #[graphql(default_with = "DEFAULT_TOP_AUTHORS")] top_authors: i32,
+    ) -> Result<LibraryStats, PresentationalError> {
+        let claims = get_claims(ctx)?;
+        let stats = self
+            .query_use_case
+            .library_stats(&claims.sub, top_authors)
+            .await?;
+        Ok(LibraryStats::from(stats))
+    }
+
+    /// Returns how many books were finished, added and deleted in each
+    /// period between the Unix timestamps `from` (inclusive) and `to`
+    /// (exclusive), oldest first. Periods follow the calendar at
+    /// `utcOffsetMinutes` and are derived from the change history.
+    async fn reading_activity(
+        &self,
+        ctx: &Context<'_>,
+        from: i64,
+        to: i64,
+        #[graphql(default)] granularity: ActivityGranularity,
+        #[graphql(default)] utc_offset_minutes: i32,
+    ) -> Result<Vec<ActivityPeriod>, PresentationalError> {
+        let claims = get_claims(ctx)?;
+        let range = ActivityRangeDto {
+            from: datetime_from_timestamp(from)?,
+            to: datetime_from_timestamp(to)?,
+            granularity: granularity.into(),
+            utc_offset_minutes,
+        };
+        let periods = self
+            .query_use_case
+            .reading_activity(&claims.sub, range)
+            .await?;
+        Ok(periods.into_iter().map(ActivityPeriod::from).collect())
+    }
+
+    /// Returns the logged-in user's books as they were at `at`, or right
+    /// after the event set `eventSetId` was recorded. Exactly one of the two
+    /// must be given.
+    async fn books_as_of(
+        &self,
+        ctx: &Context<'_>,
+        at: Option<OffsetDateTime>,
+        event_set_id: Option<ID>,
+    ) -> Result<Vec<HistoricalBook>, PresentationalError> {
+        let claims = get_claims(ctx)?;
+        let books = self
+            .query_use_case
+            .find_books_as_of(&claims.sub, point_in_time(at, event_set_id)?)
+            .await?;
+        Ok(books.into_iter().map(HistoricalBook::from).collect())
+    }
+
+    /// Returns the logged-in user's authors as they were at `at`, or right
+    /// after the event set `eventSetId` was recorded. Exactly one of the two
+    /// must be given.
+    async fn authors_as_of(
+        &self,
+        ctx: &Context<'_>,
+        at: Option<OffsetDateTime>,
+        event_set_id: Option<ID>,
+    ) -> Result<Vec<HistoricalAuthor>, PresentationalError> {
+        let claims = get_claims(ctx)?;
+        let authors = self
+            .query_use_case
+            .find_authors_as_of(&claims.sub, point_in_time(at, event_set_id)?)
+            .await?;
+        Ok(authors.into_iter().map(HistoricalAuthor::from).collect())
+    }
+
+    /// Lists the books and authors added, removed and changed between two
+    /// points in the change history.
+    async fn library_diff(
+        &self,
+        ctx: &Context<'_>,
+        from: PointInTime,
+        to: PointInTime,
+    ) -> Result<LibraryDiff, PresentationalError> {
+        let claims = get_claims(ctx)?;
+        let diff = self
+            .query_use_case
+            .diff_library(&claims.sub, from.into(), to.into())
+            .await?;
+        Ok(LibraryDiff::from(diff))
+    }
+
     /// Returns the change history for a book.
     /// Entries are sorted by `changedAt` in descending order (newest first).
+    #[graphql(deprecation = "Use `bookEventsConnection` to fetch the history page by page.")]
     async fn book_events(
         &self,
         ctx: &Context<'_>,
@@ -85,8 +280,32 @@ where
         Ok(entries.into_iter().map(BookEventEntry::from).collect())
     }
 
+    /// Returns the change history for a book as a Relay connection, newest
+    /// first.
+    #[allow(clippy::too_many_arguments)]
+    async fn book_events_connection(
+        &self,
+        ctx: &Context<'_>,
+        book_id: ID,
+        filter: Option<EventFilter>,
+        after: Option<String>,
+        before: Option<String>,
+        first: Option<i32>,
+        last: Option<i32>,
+    ) -> Result<BookEventConnection, PresentationalError> {
+        let claims = get_claims(ctx)?;
+        let filter = filter.unwrap_or_default().try_into()?;
+        let page = history_page_request(after, before, first, last)?;
+        let events = self
+            .query_use_case
+            .find_book_events_page(&claims.sub, book_id.as_str(), filter, page)
+            .await?;
+        Ok(book_event_connection(events))
+    }
+
     /// Returns the change history for an author.
     /// Entries are sorted by `changedAt` in descending order (newest first).
+    #[graphql(deprecation = "Use `authorEventsConnection` to fetch the history page by page.")]
     async fn author_events(
         &self,
         ctx: &Context<'_>,
@@ -100,7 +319,56 @@ where
         Ok(entries.into_iter().map(AuthorEventEntry::from).collect())
     }
 
+    /// Returns the change history for an author as a Relay connection,
+    /// newest first.
+    #[allow(clippy::too_many_arguments)]
+    async fn author_events_connection(
+        &self,
+        ctx: &Context<'_>,
+        author_id: ID,
+        filter: Option<EventFilter>,
+        after: Option<String>,
+        before: Option<String>,
+        first: Option<i32>,
+        last: Option<i32>,
+    ) -> Result<AuthorEventConnection, PresentationalError> {
+        let claims = get_claims(ctx)?;
+        let filter = filter.unwrap_or_default().try_into()?;
+        let page = history_page_request(after, before, first, last)?;
+        let events = self
+            .query_use_case
+            .find_author_events_page(&claims.sub, author_id.as_str(), filter, page)
+            .await?;
+        Ok(author_event_connection(events))
+    }
+
+    /// Returns the books whose latest event deleted them, most recently
+    /// deleted first.
+    async fn deleted_books(
+        &self,
+        ctx: &Context<'_>,
+    ) -> Result<Vec<DeletedBook>, PresentationalError> {
+        let claims = get_claims(ctx)?;
+        let deleted = self.query_use_case.list_deleted_books(&claims.sub).await?;
+        Ok(deleted.into_iter().map(DeletedBook::from).collect())
+    }
+
+    /// Returns the authors whose latest event deleted them, including those
+    /// merged into another author, most recently deleted first.
+    async fn deleted_authors(
+        &self,
+        ctx: &Context<'_>,
+    ) -> Result<Vec<DeletedAuthor>, PresentationalError> {
+        let claims = get_claims(ctx)?;
+        let deleted = self
+            .query_use_case
+            .list_deleted_authors(&claims.sub)
+            .await?;
+        Ok(deleted.into_iter().map(DeletedAuthor::from).collect())
+    }
+
     /// Returns the logged-in user's event sets, newest first.
+    #[graphql(deprecation = "Use `eventSetsConnection` to fetch event sets page by page.")]
     async fn event_sets(
         &self,
         ctx: &Context<'_>,
@@ -110,6 +378,27 @@ where
         Ok(sets.into_iter().map(EventSetEntry::from).collect())
     }
 
+    /// Returns the logged-in user's event sets as a Relay connection, newest
+    /// first.
+    async fn event_sets_connection(
+        &self,
+        ctx: &Context<'_>,
+        filter: Option<EventSetFilter>,
+        after: Option<String>,
+        before: Option<String>,
+        first: Option<i32>,
+        last: Option<i32>,
+    ) -> Result<EventSetConnection, PresentationalError> {
+        let claims = get_claims(ctx)?;
+        let filter = filter.unwrap_or_default().try_into()?;
+        let page = history_page_request(after, before, first, last)?;
+        let sets = self
+            .query_use_case
+            .find_event_sets_page(&claims.sub, filter, page)
+            .await?;
+        Ok(event_set_connection(sets))
+    }
+
     /// Returns a single event set with nested events, or null if not found.
     async fn event_set(
         &self,
@@ -123,9 +412,36 @@ where
             .await?;
         Ok(detail.map(EventSetDetail::from))
     }
+
+    /// Returns an import job started with `startImport`, or null if not found.
+    async fn import_job(
+        &self,
+        ctx: &Context<'_>,
+        id: ID,
+    ) -> Result<Option<ImportJob>, PresentationalError> {
+        let claims = get_claims(ctx)?;
+        let job = self
+            .query_use_case
+            .find_import_job(&claims.sub, id.as_str())
+            .await?;
+        Ok(job.map(ImportJob::from))
+    }
 }
 
 fn get_claims<'a>(ctx: &Context<'a>) -> Result<&'a Claims, PresentationalError> {
     ctx.data::<Claims>()
         .map_err(|err| PresentationalError::OtherError(Arc::new(anyhow::anyhow!(err.message))))
 }
+
+fn point_in_time(
+    at: Option<OffsetDateTime>,
+    event_set_id: Option<ID>,
+) -> Result<PointInTimeDto, PresentationalError> {
+    match (at, event_set_id) {
+        (Some(at), None) => Ok(PointInTimeDto::Timestamp(at)),
+        (None, Some(event_set_id)) => Ok(PointInTimeDto::EventSet(event_set_id.0)),
+        _ => Err(PresentationalError::Validation(
+            "Exactly one of `at` and `eventSetId` must be given.".to_string(),
+        )),
+    }
+}
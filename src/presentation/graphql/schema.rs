@@ -19,18 +19,68 @@ mod tests {
     use mockall::predicate;
 
     use crate::{
+        common::{
+            activity::ActivityGranularity,
+            sort::{BookOrder, BookSortKey, BookSortValue, SortDirection},
+            types::{BookFormat, BookStore},
+        },
         presentation::{
             extractor::claims::Claims,
             graphql::{mutation::Mutation, query::Query},
         },
         use_case::{
-            dto::{author::AuthorDto, mutation::SingleEventMutationResultDto},
+            dto::{
+                activity::{ActivityPeriodDto, ActivityRangeDto},
+                author::AuthorDto,
+                book::{BookCursorDto, BookDto},
+                history::{EntityDiffDto, LibraryDiffDto, PointInTimeDto},
+                mutation::SingleEventMutationResultDto,
+                pagination::{EdgeDto, PageDto},
+                search::{DEFAULT_SEARCH_LIMIT, SearchResultDto},
+                stats::{DEFAULT_TOP_AUTHORS, LibraryStatsDto, PriorityBucketDto},
+            },
             traits::{mutation::MockMutationUseCase, query::MockQueryUseCase},
         },
     };
 
     use super::build_schema;
 
+    fn book_dto(id: &str) -> BookDto {
+        BookDto {
+            id: id.to_string(),
+            title: "title".to_string(),
+            author_ids: vec![],
+            isbn: String::new(),
+            read: false,
+            owned: true,
+            priority: 50,
+            format: BookFormat::Unknown,
+            store: BookStore::Unknown,
+            created_at: time::OffsetDateTime::UNIX_EPOCH,
+            updated_at: time::OffsetDateTime::UNIX_EPOCH,
+        }
+    }
+
+    fn by_title_desc() -> BookOrder {
+        BookOrder {
+            key: BookSortKey::Title,
+            direction: SortDirection::Desc,
+        }
+    }
+
+    fn title_edge(id: &str, title: &str) -> EdgeDto<BookDto, BookCursorDto> {
+        EdgeDto {
+            node: BookDto {
+                title: title.to_string(),
+                ..book_dto(id)
+            },
+            cursor: BookCursorDto {
+                value: BookSortValue::Title(title.to_string()),
+                id: id.to_string(),
+            },
+        }
+    }
+
     #[tokio::test]
     async fn execute_query() {
         let user_id = "user1";
@@ -143,7 +193,426 @@ mod tests {
             Mutation::new(MockMutationUseCase::new()),
         );
 
-        assert!(schema.sdl().contains("\tbooks: [Book!]!"));
+        assert!(
+            schema
+                .sdl()
+                .contains("\tbooks(orderBy: BookOrder): [Book!]!")
+        );
+    }
+
+    #[tokio::test]
+    async fn books_connection_returns_page_info_and_accepts_end_cursor() {
+        let mut mock_query_use_case = MockQueryUseCase::new();
+        mock_query_use_case
+            .expect_find_books_page()
+            .withf(|user_id, _, order, page| {
+                user_id == "user1"
+                    && *order == by_title_desc()
+                    && page.first == Some(1)
+                    && page.after.is_none()
+            })
+            .times(1)
+            .returning(|_, _, _, _| {
+                Ok(PageDto {
+                    edges: vec![title_edge("a1b2c3d4-e5f6-4890-abcd-ef1234567890", "b")],
+                    has_previous_page: false,
+                    has_next_page: true,
+                    total_count: 2,
+                })
+            });
+        mock_query_use_case
+            .expect_find_books_page()
+            .withf(|_, _, order, page| {
+                *order == by_title_desc()
+                    && page.after
+                        == Some(BookCursorDto {
+                            value: BookSortValue::Title("b".to_string()),
+                            id: "a1b2c3d4-e5f6-4890-abcd-ef1234567890".to_string(),
+                        })
+            })
+            .times(1)
+            .returning(|_, _, _, _| {
+                Ok(PageDto {
+                    edges: vec![title_edge("c5a81e57-bc91-40ff-8b57-18cfa7cc7ae8", "a")],
+                    has_previous_page: true,
+                    has_next_page: false,
+                    total_count: 2,
+                })
+            });
+        let schema = build_schema(
+            Query::new(mock_query_use_case),
+            Mutation::new(MockMutationUseCase::new()),
+        );
+        let claims = Claims {
+            sub: "user1".to_string(),
+            _permissions: None,
+        };
+
+        let first = schema
+            .execute(
+                async_graphql::Request::from(
+                    "query { booksConnection(first: 1, orderBy: { field: TITLE, direction: DESC }) { totalCount pageInfo { hasNextPage endCursor } edges { node { id } } } }",
+                )
+                .data(claims.clone()),
+            )
+            .await;
+        let first = serde_json::to_value(first).unwrap();
+        let connection = &first["data"]["booksConnection"];
+        assert_eq!(connection["totalCount"], 2);
+        assert_eq!(connection["pageInfo"]["hasNextPage"], true);
+        assert_eq!(
+            connection["edges"][0]["node"]["id"],
+            "a1b2c3d4-e5f6-4890-abcd-ef1234567890"
+        );
+
+        let end_cursor = connection["pageInfo"]["endCursor"].as_str().unwrap();
+        let second = schema
+            .execute(
+                async_graphql::Request::from(format!(
+                    r#"query {{ booksConnection(first: 1, after: "{end_cursor}", orderBy: {{ field: TITLE, direction: DESC }}) {{ pageInfo {{ hasPreviousPage }} nodes {{ id }} }} }}"#
+                ))
+                .data(claims),
+            )
+            .await;
+        let second = serde_json::to_value(second).unwrap();
+        assert_eq!(
+            second["data"]["booksConnection"]["pageInfo"]["hasPreviousPage"],
+            true
+        );
+        assert_eq!(
+            second["data"]["booksConnection"]["nodes"][0]["id"],
+            "c5a81e57-bc91-40ff-8b57-18cfa7cc7ae8"
+        );
+    }
+
+    #[tokio::test]
+    async fn books_filter_is_converted_to_dto() {
+        let mut mock_query_use_case = MockQueryUseCase::new();
+        mock_query_use_case
+            .expect_find_all_books()
+            .withf(|user_id, filter, _| {
+                user_id == "user1"
+                    && filter.read == Some(false)
+                    && filter.format == Some(BookFormat::EBook)
+                    && filter.priority_min == Some(10)
+                    && filter.priority_max.is_none()
+                    && filter.author_ids
+                        == Some(vec!["d065a358-4fa7-4236-ae19-f6f2f9467c35".to_string()])
+                    && filter.created_from
+                        == Some(time::OffsetDateTime::from_unix_timestamp(1651622400).unwrap())
+                    && filter.created_to.is_none()
+                    && filter.has_isbn == Some(true)
+            })
+            .times(1)
+            .returning(|_, _, _| Ok(vec![book_dto("a1b2c3d4-e5f6-4890-abcd-ef1234567890")]));
+        let schema = build_schema(
+            Query::new(mock_query_use_case),
+            Mutation::new(MockMutationUseCase::new()),
+        );
+        let claims = Claims {
+            sub: "user1".to_string(),
+            _permissions: None,
+        };
+
+        let res = schema
+            .execute(
+                async_graphql::Request::from(
+                    r#"query { books(filter: { read: false, format: E_BOOK, priority: { min: 10 }, authorIds: ["d065a358-4fa7-4236-ae19-f6f2f9467c35"], createdAt: { from: 1651622400 }, hasIsbn: true }) { id } }"#,
+                )
+                .data(claims.clone()),
+            )
+            .await;
+        assert!(res.errors.is_empty(), "{:?}", res.errors);
+
+        let res = schema
+            .execute(
+                async_graphql::Request::from(
+                    "query { books(filter: { updatedAt: { to: 9223372036854775807 } }) { id } }",
+                )
+                .data(claims),
+            )
+            .await;
+        assert_eq!(res.errors.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn search_uses_default_limit_and_returns_both_lists() {
+        let mut mock_query_use_case = MockQueryUseCase::new();
+        mock_query_use_case
+            .expect_search()
+            .with(
+                predicate::eq("user1"),
+                predicate::eq("なつめ"),
+                predicate::eq(DEFAULT_SEARCH_LIMIT),
+            )
+            .times(1)
+            .returning(|_, _, _| {
+                Ok(SearchResultDto {
+                    books: vec![book_dto("a1b2c3d4-e5f6-4890-abcd-ef1234567890")],
+                    authors: vec![],
+                })
+            });
+        let schema = build_schema(
+            Query::new(mock_query_use_case),
+            Mutation::new(MockMutationUseCase::new()),
+        );
+        let claims = Claims {
+            sub: "user1".to_string(),
+            _permissions: None,
+        };
+
+        let res = schema
+            .execute(
+                async_graphql::Request::from(
+                    r#"query { search(query: "なつめ") { books { id } authors { id } } }"#,
+                )
+                .data(claims),
+            )
+            .await;
+
+        assert!(res.errors.is_empty(), "{:?}", res.errors);
+        assert_eq!(
+            res.data.into_json().unwrap(),
+            serde_json::json!({
+                "search": {
+                    "books": [{ "id": "a1b2c3d4-e5f6-4890-abcd-ef1234567890" }],
+                    "authors": [],
+                }
+            })
+        );
+    }
+
+    #[tokio::test]
+    async fn library_stats_derives_complementary_counts() {
+        let mut mock_query_use_case = MockQueryUseCase::new();
+        mock_query_use_case
+            .expect_library_stats()
+            .with(predicate::eq("user1"), predicate::eq(DEFAULT_TOP_AUTHORS))
+            .times(1)
+            .returning(|_, _| {
+                Ok(LibraryStatsDto {
+                    total_count: 5,
+                    read_count: 2,
+                    owned_count: 4,
+                    unread_owned_count: 2,
+                    format_counts: vec![(BookFormat::EBook, 5)],
+                    store_counts: vec![(BookStore::Kindle, 5)],
+                    priority_histogram: vec![PriorityBucketDto {
+                        min: 0,
+                        max: 9,
+                        count: 5,
+                    }],
+                    top_authors: vec![(
+                        AuthorDto {
+                            id: "d065a358-4fa7-4236-ae19-f6f2f9467c35".to_string(),
+                            name: "author1".to_string(),
+                            yomi: "".to_string(),
+                            created_at: time::OffsetDateTime::UNIX_EPOCH,
+                            updated_at: time::OffsetDateTime::UNIX_EPOCH,
+                        },
+                        5,
+                    )],
+                })
+            });
+        let schema = build_schema(
+            Query::new(mock_query_use_case),
+            Mutation::new(MockMutationUseCase::new()),
+        );
+        let claims = Claims {
+            sub: "user1".to_string(),
+            _permissions: None,
+        };
+
+        let res = schema
+            .execute(
+                async_graphql::Request::from(
+                    r#"query {
+                        libraryStats {
+                            totalBooks readCount unreadCount ownedCount notOwnedCount unreadOwnedCount
+                            formats { format count }
+                            stores { store count }
+                            priorityHistogram { min max count }
+                            topAuthors { author { name } bookCount }
+                        }
+                    }"#,
+                )
+                .data(claims),
+            )
+            .await;
+
+        assert!(res.errors.is_empty(), "{:?}", res.errors);
+        assert_eq!(
+            res.data.into_json().unwrap(),
+            serde_json::json!({
+                "libraryStats": {
+                    "totalBooks": 5,
+                    "readCount": 2,
+                    "unreadCount": 3,
+                    "ownedCount": 4,
+                    "notOwnedCount": 1,
+                    "unreadOwnedCount": 2,
+                    "formats": [{ "format": "E_BOOK", "count": 5 }],
+                    "stores": [{ "store": "KINDLE", "count": 5 }],
+                    "priorityHistogram": [{ "min": 0, "max": 9, "count": 5 }],
+                    "topAuthors": [{ "author": { "name": "author1" }, "bookCount": 5 }],
+                }
+            })
+        );
+    }
+
+    #[tokio::test]
+    async fn reading_activity_defaults_to_utc_days() {
+        let from = time::OffsetDateTime::from_unix_timestamp(1772323200).unwrap();
+        let to = time::OffsetDateTime::from_unix_timestamp(1772409600).unwrap();
+        let mut mock_query_use_case = MockQueryUseCase::new();
+        mock_query_use_case
+            .expect_reading_activity()
+            .with(
+                predicate::eq("user1"),
+                predicate::eq(ActivityRangeDto {
+                    from,
+                    to,
+                    granularity: ActivityGranularity::Day,
+                    utc_offset_minutes: 0,
+                }),
+            )
+            .times(1)
+            .returning(move |_, _| {
+                Ok(vec![ActivityPeriodDto {
+                    start: from,
+                    finished_count: 1,
+                    added_count: 2,
+                    deleted_count: 3,
+                }])
+            });
+        let schema = build_schema(
+            Query::new(mock_query_use_case),
+            Mutation::new(MockMutationUseCase::new()),
+        );
+        let claims = Claims {
+            sub: "user1".to_string(),
+            _permissions: None,
+        };
+
+        let res = schema
+            .execute(
+                async_graphql::Request::from(
+                    r#"query {
+                        readingActivity(from: 1772323200, to: 1772409600) {
+                            periodStart finishedCount addedCount deletedCount
+                        }
+                    }"#,
+                )
+                .data(claims),
+            )
+            .await;
+
+        assert!(res.errors.is_empty(), "{:?}", res.errors);
+        assert_eq!(
+            res.data.into_json().unwrap(),
+            serde_json::json!({
+                "readingActivity": [{
+                    "periodStart": 1772323200,
+                    "finishedCount": 1,
+                    "addedCount": 2,
+                    "deletedCount": 3,
+                }]
+            })
+        );
+    }
+
+    #[tokio::test]
+    async fn library_diff_accepts_either_kind_of_point_in_time() {
+        let mut mock_query_use_case = MockQueryUseCase::new();
+        mock_query_use_case
+            .expect_diff_library()
+            .with(
+                predicate::eq("user1"),
+                predicate::eq(PointInTimeDto::Timestamp(
+                    time::OffsetDateTime::from_unix_timestamp(1772323200).unwrap(),
+                )),
+                predicate::eq(PointInTimeDto::EventSet(
+                    "c5a81e57-bc91-40ff-8b57-18cfa7cc7ae8".to_string(),
+                )),
+            )
+            .times(1)
+            .returning(|_, _, _| {
+                Ok(LibraryDiffDto {
+                    books: EntityDiffDto {
+                        added: vec![book_dto("a1b2c3d4-e5f6-4890-abcd-ef1234567890")],
+                        removed: vec![],
+                        changed: vec![],
+                    },
+                    authors: EntityDiffDto {
+                        added: vec![],
+                        removed: vec![],
+                        changed: vec![],
+                    },
+                })
+            });
+        let schema = build_schema(
+            Query::new(mock_query_use_case),
+            Mutation::new(MockMutationUseCase::new()),
+        );
+        let claims = Claims {
+            sub: "user1".to_string(),
+            _permissions: None,
+        };
+
+        let res = schema
+            .execute(
+                async_graphql::Request::from(
+                    r#"query {
+                        libraryDiff(
+                            from: { at: "2026-03-01T00:00:00Z" }
+                            to: { eventSetId: "c5a81e57-bc91-40ff-8b57-18cfa7cc7ae8" }
+                        ) {
+                            books { added { id } removed { id } changed { before { id } } }
+                            authors { added { id } }
+                        }
+                    }"#,
+                )
+                .data(claims.clone()),
+            )
+            .await;
+
+        assert!(res.errors.is_empty(), "{:?}", res.errors);
+        assert_eq!(
+            res.data.into_json().unwrap(),
+            serde_json::json!({
+                "libraryDiff": {
+                    "books": {
+                        "added": [{ "id": "a1b2c3d4-e5f6-4890-abcd-ef1234567890" }],
+                        "removed": [],
+                        "changed": [],
+                    },
+                    "authors": { "added": [] },
+                }
+            })
+        );
+
+        let res = schema
+            .execute(async_graphql::Request::from("query { booksAsOf { id } }").data(claims))
+            .await;
+        assert_eq!(res.errors.len(), 1);
+    }
+
+    #[test]
+    fn list_fields_are_deprecated_in_favor_of_connections() {
+        let sdl = build_schema(
+            Query::new(MockQueryUseCase::new()),
+            Mutation::new(MockMutationUseCase::new()),
+        )
+        .sdl();
+
+        assert!(sdl.contains("type BookConnection {"));
+        assert!(sdl.contains("type AuthorConnection {"));
+        assert!(sdl.contains(
+            "\tbooks(filter: BookFilter, orderBy: BookOrder): [Book!]! @deprecated(reason: \"Use `booksConnection` to fetch books page by page.\")"
+        ));
+        assert!(sdl.contains(
+            "\tauthors(orderBy: AuthorOrder): [Author!]! @deprecated(reason: \"Use `authorsConnection` to fetch authors page by page.\")"
+        ));
     }
 
     #[cfg(feature = "test-with-database")]
@@ -201,15 +670,19 @@ mod tests {
         .execute(&pool)
         .await?;
 
-        let (query_use_case, schema) = dependency_injection(pool);
+        let (query_use_case, schema, _import_books_use_case, _import_job_worker) =
+            dependency_injection(pool);
         let claims = Claims {
             sub: "user1".to_string(),
             _permissions: None,
         };
         let response = schema
             .execute(
-                async_graphql::Request::from("query { authors { id books { id title } } }")
-                    .data(claims.clone())
+                async_graphql::Request::from(
+                    "query { authors { id books { id title } booksConnection(first: 1) { totalCount pageInfo { hasNextPage } } } }",
+                )
+                .data(claims.clone())
+                .data(query_use_case.clone())
                     .data(DataLoader::new(
                         AuthorLoader::new(claims.clone(), query_use_case.clone()),
                         tokio::spawn,
@@ -236,6 +709,16 @@ mod tests {
         assert_eq!(books_for(author2).len(), 1);
         assert!(books_for(author3).is_empty());
 
+        let connection_for = |author_id: &str| {
+            &authors
+                .iter()
+                .find(|author| author["id"] == author_id)
+                .unwrap()["booksConnection"]
+        };
+        assert_eq!(connection_for(author1)["totalCount"], 2);
+        assert_eq!(connection_for(author1)["pageInfo"]["hasNextPage"], true);
+        assert_eq!(connection_for(author3)["totalCount"], 0);
+
         Ok(())
     }
 }
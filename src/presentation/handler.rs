@@ -1,2 +1,4 @@
+pub mod export;
 pub mod graphql;
+pub mod import;
 pub mod user;
@@ -0,0 +1,590 @@
+//! GET /export/* endpoints - Export the library
+//!
+//! This module provides the handlers that write out the user's books with the
+//! names of their authors, either as a CSV file that POST /import/csv reads
+//! back with [`export_column_mapping`], or as JSON whose entries the
+//! `importBooks` mutation takes as they are.
+//!
+//! Books are read a page at a time as the file is sent, so a library of any
+//! size can be exported. POST /import/csv and `importBooks` take at most 1000
+//! books, though: a larger export is imported back with the `startImport`
+//! mutation, which takes the entries of the JSON export.
+
+use std::sync::Arc;
+
+use async_graphql::{InputType, Value};
+use axum::{
+    BoxError, Extension, Json,
+    body::{Body, Bytes},
+    response::{IntoResponse, Response},
+};
+use futures_util::{Stream, StreamExt, TryStreamExt, future, stream};
+use http::{
+    StatusCode,
+    header::{CONTENT_DISPOSITION, CONTENT_TYPE},
+};
+use serde::Serialize;
+use serde_json::json;
+use thiserror::Error;
+
+use crate::{
+    common::sort::BookOrder,
+    presentation::{
+        extractor::claims::Claims,
+        graphql::object::{BookFormat, BookStore},
+        import::csv::ColumnMapping,
+    },
+    use_case::{
+        dto::{
+            book::{BookCursorDto, BookDto, BookFilterDto},
+            pagination::{MAX_PAGE_SIZE, PageRequestDto},
+        },
+        error::UseCaseError,
+        traits::query::QueryUseCase,
+    },
+};
+
+const CSV_HEADER: [&str; 11] = [
+    "ID",
+    "Title",
+    "Authors",
+    "ISBN",
+    "Read",
+    "Owned",
+    "Priority",
+    "Format",
+    "Store",
+    "Created At",
+    "Updated At",
+];
+
+// The CSV importer splits author names on its default separator, `;`, and
+// trims them, unless they are wrapped in double quotes.
+const AUTHOR_SEPARATOR: &str = "; ";
+
+/// The column mapping POST /import/csv reads GET /export/books.csv with.
+pub fn export_column_mapping() -> ColumnMapping {
+    ColumnMapping {
+        title: "Title".to_string(),
+        authors: Some("Authors".to_string()),
+        author_separator: None,
+        isbn: Some("ISBN".to_string()),
+        read: Some("Read".to_string()),
+        owned: Some("Owned".to_string()),
+        priority: Some("Priority".to_string()),
+        format: Some("Format".to_string()),
+        store: Some("Store".to_string()),
+    }
+}
+
+/// A book of GET /export/library.json.
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ExportedBook {
+    pub id: String,
+    /// The book as an `ImportBookInput` of the `importBooks` mutation.
+    pub entry: ExportedEntry,
+    pub created_at: i64,
+    pub updated_at: i64,
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ExportedEntry {
+    pub title: String,
+    pub author_names: Vec<String>,
+    pub isbn: String,
+    pub read: bool,
+    pub owned: bool,
+    pub priority: i32,
+    /// The `BookFormat` GraphQL enum value, e.g. `E_BOOK`.
+    pub format: Value,
+    /// The `BookStore` GraphQL enum value, e.g. `KINDLE`.
+    pub store: Value,
+}
+
+struct LibraryBook {
+    book: BookDto,
+    author_names: Vec<String>,
+}
+
+// A page of the library, and the cursor of the next one if there is one.
+struct LibraryPage {
+    books: Vec<LibraryBook>,
+    next: Option<BookCursorDto>,
+}
+
+enum LibraryPageState {
+    Read(LibraryPage),
+    After(BookCursorDto),
+    Done,
+}
+
+impl From<LibraryBook> for ExportedBook {
+    fn from(LibraryBook { book, author_names }: LibraryBook) -> Self {
+        Self {
+            id: book.id,
+            entry: ExportedEntry {
+                title: book.title,
+                author_names,
+                isbn: book.isbn,
+                read: book.read,
+                owned: book.owned,
+                priority: book.priority,
+                format: BookFormat::from(book.format).to_value(),
+                store: BookStore::from(book.store).to_value(),
+            },
+            created_at: book.created_at.unix_timestamp(),
+            updated_at: book.updated_at.unix_timestamp(),
+        }
+    }
+}
+
+#[derive(Debug, Error)]
+#[error(transparent)]
+pub struct ExportError(#[from] UseCaseError);
+
+impl IntoResponse for ExportError {
+    fn into_response(self) -> Response {
+        let status = match &self.0 {
+            UseCaseError::Validation(_) => StatusCode::BAD_REQUEST,
+            UseCaseError::NotFound { .. } => StatusCode::NOT_FOUND,
+            UseCaseError::Conflict { .. } => StatusCode::CONFLICT,
+            UseCaseError::Other(_) | UseCaseError::Unexpected(_) => {
+                StatusCode::INTERNAL_SERVER_ERROR
+            }
+        };
+        let message = if status.is_server_error() {
+            tracing::error!("Export failed: {self:?}");
+            "Internal server error".to_string()
+        } else {
+            self.to_string()
+        };
+        (status, Json(json!({ "message": message }))).into_response()
+    }
+}
+
+/// Handler for GET /export/books.csv
+///
+/// Writes a CSV file with a row per book, oldest first. Authors are listed by
+/// name, separated by `;`, with names that hold a `;` wrapped in double
+/// quotes, and timestamps are unix times. Importing the file with the mapping
+/// `{"title": "Title", "authors": "Authors", "isbn": "ISBN", "read": "Read",
+/// "owned": "Owned", "priority": "Priority", "format": "Format",
+/// "store": "Store"}` recreates the books.
+pub async fn export_books_csv_handler<QUC>(
+    claims: Claims,
+    Extension(query_use_case): Extension<QUC>,
+) -> Result<Response, ExportError>
+where
+    QUC: QueryUseCase,
+{
+    let books = find_library_books(Arc::new(query_use_case), claims.sub).await?;
+    Ok((
+        [
+            (CONTENT_TYPE, "text/csv; charset=utf-8"),
+            (CONTENT_DISPOSITION, r#"attachment; filename="books.csv""#),
+        ],
+        Body::from_stream(csv_chunks(books)),
+    )
+        .into_response())
+}
+
+/// Handler for GET /export/library.json
+///
+/// Writes `{"books": [...]}`, each book an [`ExportedBook`]. The `entry` of
+/// each book can be passed to the `importBooks` mutation as is.
+pub async fn export_library_json_handler<QUC>(
+    claims: Claims,
+    Extension(query_use_case): Extension<QUC>,
+) -> Result<Response, ExportError>
+where
+    QUC: QueryUseCase,
+{
+    let books = find_library_books(Arc::new(query_use_case), claims.sub).await?;
+    Ok((
+        [
+            (CONTENT_TYPE, "application/json"),
+            (
+                CONTENT_DISPOSITION,
+                r#"attachment; filename="library.json""#,
+            ),
+        ],
+        Body::from_stream(json_chunks(books)),
+    )
+        .into_response())
+}
+
+// Reads the first page before the response starts, so that an error with it
+// gets a status of its own. An error with a later page ends the response.
+async fn find_library_books<QUC>(
+    query_use_case: Arc<QUC>,
+    user_id: String,
+) -> Result<impl Stream<Item = Result<LibraryBook, UseCaseError>> + use<QUC>, UseCaseError>
+where
+    QUC: QueryUseCase,
+{
+    let first = find_library_page(&*query_use_case, &user_id, None).await?;
+    let pages = stream::try_unfold(LibraryPageState::Read(first), move |state| {
+        let query_use_case = Arc::clone(&query_use_case);
+        let user_id = user_id.clone();
+        async move {
+            let page = match state {
+                LibraryPageState::Read(page) => page,
+                LibraryPageState::After(cursor) => {
+                    find_library_page(&*query_use_case, &user_id, Some(cursor)).await?
+                }
+                LibraryPageState::Done => return Ok(None),
+            };
+            let next = match page.next {
+                Some(cursor) => LibraryPageState::After(cursor),
+                None => LibraryPageState::Done,
+            };
+            Ok::<_, UseCaseError>(Some((stream::iter(page.books.into_iter().map(Ok)), next)))
+        }
+    });
+    Ok(pages.try_flatten())
+}
+
+async fn find_library_page<QUC>(
+    query_use_case: &QUC,
+    user_id: &str,
+    after: Option<BookCursorDto>,
+) -> Result<LibraryPage, UseCaseError>
+where
+    QUC: QueryUseCase,
+{
+    let page = query_use_case
+        .find_books_page(
+            user_id,
+            BookFilterDto::default(),
+            BookOrder::default(),
+            PageRequestDto {
+                after,
+                ..PageRequestDto::first(MAX_PAGE_SIZE)
+            },
+        )
+        .await?;
+    let next = page
+        .has_next_page
+        .then(|| page.edges.last().map(|edge| edge.cursor.clone()))
+        .flatten();
+
+    let mut author_ids: Vec<String> = page
+        .edges
+        .iter()
+        .flat_map(|edge| edge.node.author_ids.iter().cloned())
+        .collect();
+    author_ids.sort();
+    author_ids.dedup();
+    let authors = query_use_case
+        .find_author_by_ids_as_hash_map(user_id, &author_ids)
+        .await?;
+    let books = page
+        .edges
+        .into_iter()
+        .map(|edge| LibraryBook {
+            author_names: edge
+                .node
+                .author_ids
+                .iter()
+                .filter_map(|author_id| authors.get(author_id).map(|author| author.name.clone()))
+                .collect(),
+            book: edge.node,
+        })
+        .collect();
+    Ok(LibraryPage { books, next })
+}
+
+// Each book is written as it is sent, rather than the whole file at once.
+fn csv_chunks(
+    books: impl Stream<Item = Result<LibraryBook, UseCaseError>>,
+) -> impl Stream<Item = Result<Bytes, BoxError>> {
+    let header = CSV_HEADER.iter().map(|name| name.to_string()).collect();
+    stream::once(future::ready(Ok(header)))
+        .chain(books.map_ok(csv_record))
+        .map(|record: Result<Vec<String>, UseCaseError>| {
+            let mut writer = csv::Writer::from_writer(vec![]);
+            writer.write_record(&record?)?;
+            let line = writer
+                .into_inner()
+                .map_err(|err| csv::Error::from(err.into_error()))?;
+            Ok(Bytes::from(line))
+        })
+}
+
+fn csv_record(LibraryBook { book, author_names }: LibraryBook) -> Vec<String> {
+    vec![
+        book.id,
+        book.title,
+        author_names
+            .iter()
+            .map(|name| quote_author_name(name))
+            .collect::<Vec<_>>()
+            .join(AUTHOR_SEPARATOR),
+        book.isbn,
+        book.read.to_string(),
+        book.owned.to_string(),
+        book.priority.to_string(),
+        book.format.to_string(),
+        book.store.to_string(),
+        book.created_at.unix_timestamp().to_string(),
+        book.updated_at.unix_timestamp().to_string(),
+    ]
+}
+
+// Wraps a name the importer would otherwise split or trim in double quotes.
+fn quote_author_name(name: &str) -> String {
+    if name.contains(';') || name.starts_with('"') || name.trim() != name {
+        format!("\"{}\"", name.replace('"', "\"\""))
+    } else {
+        name.to_string()
+    }
+}
+
+fn json_chunks(
+    books: impl Stream<Item = Result<LibraryBook, UseCaseError>>,
+) -> impl Stream<Item = Result<Bytes, BoxError>> {
+    let books = books.enumerate().map(|(index, book)| {
+        let mut chunk = if index == 0 { vec![] } else { vec![b','] };
+        serde_json::to_writer(&mut chunk, &ExportedBook::from(book?))?;
+        Ok(Bytes::from(chunk))
+    });
+    stream::once(future::ready(Ok(Bytes::from_static(br#"{"books":["#))))
+        .chain(books)
+        .chain(stream::once(future::ready(Ok(Bytes::from_static(b"]}")))))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{collections::HashMap, sync::Arc};
+
+    use async_graphql::{InputType, Value};
+    use futures_util::{Stream, TryStreamExt, stream};
+    use time::macros::datetime;
+
+    use crate::{
+        common::{
+            sort::BookSortValue,
+            types::{BookFormat, BookStore},
+        },
+        presentation::{graphql::object::ImportBookInput, import::csv::parse_csv},
+        use_case::{
+            dto::{
+                author::AuthorDto,
+                book::{BookCursorDto, BookDto, ImportBookEntryDto},
+                pagination::{EdgeDto, PageDto},
+            },
+            error::UseCaseError,
+            traits::query::MockQueryUseCase,
+        },
+    };
+
+    use super::{LibraryBook, csv_chunks, export_column_mapping, find_library_books, json_chunks};
+
+    fn books(library: Vec<LibraryBook>) -> impl Stream<Item = Result<LibraryBook, UseCaseError>> {
+        stream::iter(library.into_iter().map(Ok))
+    }
+
+    fn library() -> Vec<LibraryBook> {
+        let book = |title: &str, author_names: &[&str]| LibraryBook {
+            book: BookDto {
+                id: format!("id-{title}"),
+                title: title.to_string(),
+                author_ids: vec![],
+                isbn: String::new(),
+                read: false,
+                owned: false,
+                priority: 0,
+                format: BookFormat::Unknown,
+                store: BookStore::Unknown,
+                created_at: datetime!(2026-01-02 03:04:05 UTC),
+                updated_at: datetime!(2026-02-03 04:05:06 UTC),
+            },
+            author_names: author_names.iter().map(|name| name.to_string()).collect(),
+        };
+        let mut dune = book("Dune, \"Deluxe\"", &["Frank Herbert"]);
+        dune.book.isbn = "9780441013593".to_string();
+        dune.book.read = true;
+        dune.book.owned = true;
+        dune.book.priority = 80;
+        dune.book.format = BookFormat::EBook;
+        dune.book.store = BookStore::Kindle;
+        vec![
+            dune,
+            book("The Hobbit", &["Tolkien, J. R. R.", "Someone; Else"]),
+            book("Anonymous", &[]),
+        ]
+    }
+
+    fn entries() -> Vec<ImportBookEntryDto> {
+        library()
+            .into_iter()
+            .map(|LibraryBook { book, author_names }| ImportBookEntryDto {
+                title: book.title,
+                author_names,
+                isbn: book.isbn,
+                read: book.read,
+                owned: book.owned,
+                priority: book.priority,
+                format: book.format,
+                store: book.store,
+            })
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn csv_export_reads_back_with_the_export_mapping() {
+        // Given: The CSV export of a library
+        let data: Vec<u8> = csv_chunks(books(library()))
+            .map_ok(|chunk| chunk.to_vec())
+            .try_concat()
+            .await
+            .unwrap();
+
+        // When: Parsing it the way POST /import/csv does
+        let rows = parse_csv(&data, &export_column_mapping()).unwrap();
+
+        // Then: Every book comes back as it was
+        let imported: Vec<_> = rows.into_iter().map(|row| row.entry).collect();
+        assert_eq!(imported, entries());
+
+        // And: Timestamps are written as unix times
+        let text = String::from_utf8(data).unwrap();
+        assert!(text.starts_with("ID,Title,Authors,ISBN,Read,Owned,Priority"));
+        assert!(text.contains(",1767323045,1770091506"));
+    }
+
+    #[tokio::test]
+    async fn json_export_entries_are_import_book_inputs() {
+        // Given: The JSON export of a library
+        let data: Vec<u8> = json_chunks(books(library()))
+            .map_ok(|chunk| chunk.to_vec())
+            .try_concat()
+            .await
+            .unwrap();
+        let exported: serde_json::Value = serde_json::from_slice(&data).unwrap();
+
+        // When: Reading each entry as an importBooks input
+        let books = exported["books"].as_array().unwrap();
+        let imported: Vec<ImportBookEntryDto> = books
+            .iter()
+            .map(|book| {
+                let entry = Value::from_json(book["entry"].clone()).unwrap();
+                let Ok(input) = ImportBookInput::parse(Some(entry)) else {
+                    panic!("{book} is not an ImportBookInput");
+                };
+                input.into()
+            })
+            .collect();
+
+        // Then: Every book comes back as it was, alongside its id and times
+        assert_eq!(imported, entries());
+        assert_eq!(books[0]["id"], "id-Dune, \"Deluxe\"");
+        assert_eq!(books[0]["entry"]["format"], "E_BOOK");
+        assert_eq!(books[0]["createdAt"], 1767323045);
+    }
+
+    #[tokio::test]
+    async fn json_export_of_an_empty_library_has_no_books() {
+        let data: Vec<u8> = json_chunks(books(vec![]))
+            .map_ok(|chunk| chunk.to_vec())
+            .try_concat()
+            .await
+            .unwrap();
+        assert_eq!(data, br#"{"books":[]}"#);
+    }
+
+    #[tokio::test]
+    async fn find_library_books_reads_every_page_with_its_authors() {
+        // Given: A library of two pages
+        let book = |id: &str, author_ids: &[&str]| EdgeDto {
+            node: BookDto {
+                id: id.to_string(),
+                author_ids: author_ids.iter().map(|id| id.to_string()).collect(),
+                ..library().remove(2).book
+            },
+            cursor: BookCursorDto {
+                value: BookSortValue::CreatedAt(datetime!(2026-01-02 03:04:05 UTC)),
+                id: id.to_string(),
+            },
+        };
+        let author = |id: &str| AuthorDto {
+            id: id.to_string(),
+            name: format!("name-{id}"),
+            yomi: String::new(),
+            created_at: datetime!(2026-01-02 03:04:05 UTC),
+            updated_at: datetime!(2026-01-02 03:04:05 UTC),
+        };
+        let mut query_use_case = MockQueryUseCase::new();
+        query_use_case
+            .expect_find_books_page()
+            .withf(|_, _, _, page| page.after.is_none())
+            .times(1)
+            .returning(move |_, _, _, _| {
+                Ok(PageDto {
+                    edges: vec![book("book-1", &["a", "b"]), book("book-2", &["a"])],
+                    has_previous_page: false,
+                    has_next_page: true,
+                    total_count: 3,
+                })
+            });
+        query_use_case
+            .expect_find_books_page()
+            .withf(|_, _, _, page| {
+                page.after.as_ref().map(|cursor| cursor.id.as_str()) == Some("book-2")
+            })
+            .times(1)
+            .returning(move |_, _, _, _| {
+                Ok(PageDto {
+                    edges: vec![book("book-3", &["c"])],
+                    has_previous_page: true,
+                    has_next_page: false,
+                    total_count: 3,
+                })
+            });
+        query_use_case
+            .expect_find_author_by_ids_as_hash_map()
+            .withf(|_, author_ids| author_ids == ["a", "b"])
+            .times(1)
+            .returning(move |_, _| {
+                Ok(HashMap::from([
+                    ("a".to_string(), author("a")),
+                    ("b".to_string(), author("b")),
+                ]))
+            });
+        query_use_case
+            .expect_find_author_by_ids_as_hash_map()
+            .withf(|_, author_ids| author_ids == ["c"])
+            .times(1)
+            .returning(move |_, _| Ok(HashMap::from([("c".to_string(), author("c"))])));
+
+        // When: Reading the library
+        let library: Vec<LibraryBook> =
+            find_library_books(Arc::new(query_use_case), "user".to_string())
+                .await
+                .unwrap()
+                .try_collect()
+                .await
+                .unwrap();
+
+        // Then: Every book of every page comes with its authors' names
+        let read: Vec<(&str, Vec<&str>)> = library
+            .iter()
+            .map(|LibraryBook { book, author_names }| {
+                (
+                    book.id.as_str(),
+                    author_names.iter().map(String::as_str).collect(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            read,
+            vec![
+                ("book-1", vec!["name-a", "name-b"]),
+                ("book-2", vec!["name-a"]),
+                ("book-3", vec!["name-c"]),
+            ]
+        );
+    }
+}
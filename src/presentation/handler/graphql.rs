@@ -37,7 +37,7 @@ where
         tokio::spawn,
     );
     let books_by_author_loader = DataLoader::new(
-        BooksByAuthorLoader::new(claims.clone(), query_use_case),
+        BooksByAuthorLoader::new(claims.clone(), query_use_case.clone()),
         tokio::spawn,
     );
 
@@ -45,6 +45,7 @@ where
         .execute(
             req.into_inner()
                 .data(claims)
+                .data(query_use_case)
                 .data(author_loader)
                 .data(books_by_author_loader),
         )
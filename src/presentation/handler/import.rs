@@ -0,0 +1,383 @@
+//! POST /import/* endpoints - Import books from files
+//!
+//! This module provides the handlers that read a spreadsheet exported as CSV,
+//! using a column mapping sent along with it, or the export of another
+//! service, and import its rows the way the `importBooks` mutation imports
+//! its entries.
+
+use std::sync::Arc;
+
+use axum::{
+    Extension, Json,
+    body::Bytes,
+    extract::{Multipart, multipart::MultipartError},
+    response::{IntoResponse, Response},
+};
+use http::StatusCode;
+use serde::Serialize;
+use serde_json::json;
+use thiserror::Error;
+
+use crate::{
+    presentation::{
+        extractor::claims::Claims,
+        import::{
+            bookmeter::parse_bookmeter,
+            csv::{ColumnMapping, CsvImportEntry, CsvImportError, parse_csv},
+            goodreads::parse_goodreads,
+        },
+    },
+    use_case::{
+        dto::{
+            book::{BookDto, ImportModeDto, ImportOptionsDto},
+            mutation::{ImportBookFieldDto, ImportBooksResultDto},
+        },
+        error::UseCaseError,
+        traits::book::ImportBooksUseCase,
+    },
+};
+
+/// Response payload for POST /import/*, shaped like the `importBooks`
+/// mutation's payload.
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ImportBooksResponse {
+    pub created: Vec<ImportedBook>,
+    pub updated: Vec<ImportedBook>,
+    pub skipped: Vec<ImportedBook>,
+    pub new_author_names: Vec<String>,
+    pub reused_author_names: Vec<String>,
+    /// Rows a dry run or partial import found invalid.
+    pub errors: Vec<ImportRowError>,
+    pub event_set_id: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ImportedBook {
+    pub id: String,
+    pub title: String,
+    pub author_ids: Vec<String>,
+    pub isbn: String,
+    pub read: bool,
+    pub owned: bool,
+    pub priority: i32,
+    pub format: String,
+    pub store: String,
+    pub created_at: i64,
+    pub updated_at: i64,
+}
+
+impl From<BookDto> for ImportedBook {
+    fn from(book: BookDto) -> Self {
+        Self {
+            id: book.id,
+            title: book.title,
+            author_ids: book.author_ids,
+            isbn: book.isbn,
+            read: book.read,
+            owned: book.owned,
+            priority: book.priority,
+            format: book.format.to_string(),
+            store: book.store.to_string(),
+            created_at: book.created_at.unix_timestamp(),
+            updated_at: book.updated_at.unix_timestamp(),
+        }
+    }
+}
+
+/// Why a row could not be imported.
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ImportRowError {
+    /// Position of the row among the imported rows, starting at 0.
+    pub index: usize,
+    /// Line of the file the row starts on, the header being line 1.
+    pub line: u64,
+    /// The field as named by the `ImportBookField` GraphQL enum.
+    pub field: &'static str,
+    pub message: String,
+}
+
+#[derive(Debug, Error)]
+pub enum ImportError {
+    #[error("{0}")]
+    BadRequest(String),
+    #[error(transparent)]
+    UseCase(#[from] UseCaseError),
+}
+
+impl From<MultipartError> for ImportError {
+    fn from(err: MultipartError) -> Self {
+        ImportError::BadRequest(err.body_text())
+    }
+}
+
+impl From<CsvImportError> for ImportError {
+    fn from(err: CsvImportError) -> Self {
+        ImportError::BadRequest(err.to_string())
+    }
+}
+
+impl IntoResponse for ImportError {
+    fn into_response(self) -> Response {
+        let status = match &self {
+            ImportError::BadRequest(_) | ImportError::UseCase(UseCaseError::Validation(_)) => {
+                StatusCode::BAD_REQUEST
+            }
+            ImportError::UseCase(UseCaseError::NotFound { .. }) => StatusCode::NOT_FOUND,
+            ImportError::UseCase(UseCaseError::Conflict { .. }) => StatusCode::CONFLICT,
+            ImportError::UseCase(UseCaseError::Other(_) | UseCaseError::Unexpected(_)) => {
+                StatusCode::INTERNAL_SERVER_ERROR
+            }
+        };
+        let message = if status.is_server_error() {
+            tracing::error!("File import failed: {self:?}");
+            "Internal server error".to_string()
+        } else {
+            self.to_string()
+        };
+        (status, Json(json!({ "message": message }))).into_response()
+    }
+}
+
+/// Handler for POST /import/csv
+///
+/// Takes a multipart form with these parts:
+/// - `file`: the CSV file, UTF-8 encoded, with a header row.
+/// - `mapping`: a JSON [`ColumnMapping`], e.g.
+///   `{"title": "Title", "authors": "Author", "authorSeparator": "/"}`.
+/// - `mode`, `partial` and `dryRun` (optional): the options of the
+///   `importBooks` mutation, written the same way, e.g. `UPSERT_BY_ISBN` and
+///   `true`.
+///
+/// All rows are imported in one event set, and are limited to as many as
+/// `importBooks` takes.
+pub async fn import_csv_handler<IBUC>(
+    claims: Claims,
+    Extension(import_books_use_case): Extension<Arc<IBUC>>,
+    multipart: Multipart,
+) -> Result<Json<ImportBooksResponse>, ImportError>
+where
+    IBUC: ImportBooksUseCase,
+{
+    let form = read_form(multipart, true).await?;
+    let mapping = form
+        .mapping
+        .ok_or_else(|| ImportError::BadRequest("mapping is required".to_string()))?;
+    let rows = parse_csv(&form.file, &mapping)?;
+    import_rows(import_books_use_case.as_ref(), &claims, rows, form.options).await
+}
+
+/// Handler for POST /import/goodreads
+///
+/// Imports a Goodreads library export, taking the same form as
+/// POST /import/csv without the `mapping`.
+pub async fn import_goodreads_handler<IBUC>(
+    claims: Claims,
+    Extension(import_books_use_case): Extension<Arc<IBUC>>,
+    multipart: Multipart,
+) -> Result<Json<ImportBooksResponse>, ImportError>
+where
+    IBUC: ImportBooksUseCase,
+{
+    let form = read_form(multipart, false).await?;
+    let rows = parse_goodreads(&form.file)?;
+    import_rows(import_books_use_case.as_ref(), &claims, rows, form.options).await
+}
+
+/// Handler for POST /import/bookmeter
+///
+/// Imports a 読書メーター (Bookmeter) export, taking the same form as
+/// POST /import/csv without the `mapping`.
+pub async fn import_bookmeter_handler<IBUC>(
+    claims: Claims,
+    Extension(import_books_use_case): Extension<Arc<IBUC>>,
+    multipart: Multipart,
+) -> Result<Json<ImportBooksResponse>, ImportError>
+where
+    IBUC: ImportBooksUseCase,
+{
+    let form = read_form(multipart, false).await?;
+    let rows = parse_bookmeter(&form.file)?;
+    import_rows(import_books_use_case.as_ref(), &claims, rows, form.options).await
+}
+
+struct ImportForm {
+    file: Bytes,
+    mapping: Option<ColumnMapping>,
+    options: ImportOptionsDto,
+}
+
+async fn read_form(
+    mut multipart: Multipart,
+    takes_mapping: bool,
+) -> Result<ImportForm, ImportError> {
+    let mut file = None;
+    let mut mapping = None;
+    let mut options = ImportOptionsDto::default();
+    while let Some(field) = multipart.next_field().await? {
+        let name = field.name().unwrap_or_default().to_string();
+        match name.as_str() {
+            "file" => file = Some(field.bytes().await?),
+            "mapping" if takes_mapping => {
+                let text = field.text().await?;
+                mapping = Some(
+                    serde_json::from_str::<ColumnMapping>(&text)
+                        .map_err(|e| ImportError::BadRequest(format!("invalid mapping: {e}")))?,
+                );
+            }
+            "mode" => options.mode = parse_mode(&field.text().await?)?,
+            "partial" => options.partial = parse_option(&name, &field.text().await?)?,
+            "dryRun" => options.dry_run = parse_option(&name, &field.text().await?)?,
+            _ => {
+                return Err(ImportError::BadRequest(format!(
+                    r#"unexpected form field "{name}""#
+                )));
+            }
+        }
+    }
+    let file = file.ok_or_else(|| ImportError::BadRequest("file is required".to_string()))?;
+    Ok(ImportForm {
+        file,
+        mapping,
+        options,
+    })
+}
+
+async fn import_rows<IBUC>(
+    import_books_use_case: &IBUC,
+    claims: &Claims,
+    rows: Vec<CsvImportEntry>,
+    options: ImportOptionsDto,
+) -> Result<Json<ImportBooksResponse>, ImportError>
+where
+    IBUC: ImportBooksUseCase,
+{
+    let lines: Vec<u64> = rows.iter().map(|row| row.line).collect();
+    let entries = rows
+        .into_iter()
+        .map(|CsvImportEntry { entry, .. }| entry)
+        .collect();
+    let imported = import_books_use_case
+        .import(&claims.sub, entries, options)
+        .await?;
+    Ok(Json(to_response(imported, &lines)))
+}
+
+fn parse_mode(value: &str) -> Result<ImportModeDto, ImportError> {
+    match value.trim() {
+        "CREATE_ONLY" => Ok(ImportModeDto::CreateOnly),
+        "UPSERT_BY_ISBN" => Ok(ImportModeDto::UpsertByIsbn),
+        "UPSERT_BY_TITLE_AND_AUTHORS" => Ok(ImportModeDto::UpsertByTitleAndAuthors),
+        "SKIP_EXISTING" => Ok(ImportModeDto::SkipExisting),
+        other => Err(ImportError::BadRequest(format!(
+            r#""{other}" is not an import mode"#
+        ))),
+    }
+}
+
+fn parse_option(name: &str, value: &str) -> Result<bool, ImportError> {
+    match value.trim() {
+        "true" => Ok(true),
+        "false" => Ok(false),
+        other => Err(ImportError::BadRequest(format!(
+            r#"{name} must be true or false, not "{other}""#
+        ))),
+    }
+}
+
+fn to_response(imported: ImportBooksResultDto, lines: &[u64]) -> ImportBooksResponse {
+    let books = |books: Vec<BookDto>| books.into_iter().map(ImportedBook::from).collect();
+    ImportBooksResponse {
+        created: books(imported.created),
+        updated: books(imported.updated),
+        skipped: books(imported.skipped),
+        new_author_names: imported.new_author_names,
+        reused_author_names: imported.reused_author_names,
+        errors: imported
+            .errors
+            .into_iter()
+            .map(|error| ImportRowError {
+                index: error.index,
+                line: lines.get(error.index).copied().unwrap_or_default(),
+                field: field_name(error.field),
+                message: error.message,
+            })
+            .collect(),
+        event_set_id: imported.event_set_id,
+    }
+}
+
+fn field_name(field: ImportBookFieldDto) -> &'static str {
+    match field {
+        ImportBookFieldDto::Title => "TITLE",
+        ImportBookFieldDto::AuthorNames => "AUTHOR_NAMES",
+        ImportBookFieldDto::Isbn => "ISBN",
+        ImportBookFieldDto::Priority => "PRIORITY",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::use_case::dto::{
+        book::ImportModeDto,
+        mutation::{ImportBookErrorDto, ImportBookFieldDto, ImportBooksResultDto},
+    };
+
+    use super::{ImportError, ImportRowError, parse_mode, parse_option, to_response};
+
+    #[test]
+    fn parse_mode_accepts_graphql_enum_values() {
+        assert_eq!(
+            parse_mode("UPSERT_BY_ISBN").unwrap(),
+            ImportModeDto::UpsertByIsbn
+        );
+        assert!(matches!(
+            parse_mode("upsert"),
+            Err(ImportError::BadRequest(_))
+        ));
+    }
+
+    #[test]
+    fn parse_option_accepts_only_true_and_false() {
+        assert!(parse_option("partial", "true").unwrap());
+        assert!(!parse_option("partial", "false").unwrap());
+        assert!(matches!(
+            parse_option("dryRun", "yes"),
+            Err(ImportError::BadRequest(message)) if message.starts_with("dryRun")
+        ));
+    }
+
+    #[test]
+    fn to_response_reports_the_line_of_each_invalid_row() {
+        // Given: An import result with an error for the second row
+        let imported = ImportBooksResultDto {
+            created: vec![],
+            updated: vec![],
+            skipped: vec![],
+            new_author_names: vec![],
+            reused_author_names: vec![],
+            errors: vec![ImportBookErrorDto {
+                index: 1,
+                field: ImportBookFieldDto::Isbn,
+                message: "invalid".to_string(),
+            }],
+            event_set_id: None,
+        };
+
+        // When: Building the response for rows on lines 2 and 5
+        let response = to_response(imported, &[2, 5]);
+
+        // Then: The error names the line the row is on
+        assert_eq!(
+            response.errors,
+            vec![ImportRowError {
+                index: 1,
+                line: 5,
+                field: "ISBN",
+                message: "invalid".to_string(),
+            }]
+        );
+    }
+}
@@ -0,0 +1,5 @@
+pub mod bookmeter;
+pub mod csv;
+pub mod goodreads;
+pub mod isbn;
+pub mod shelf;
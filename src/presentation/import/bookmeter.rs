@@ -0,0 +1,131 @@
+//! Reads a CSV export of a 読書メーター (Bookmeter) library, with the columns
+//! タイトル, 著者, ISBN/ASIN, 読了日 and 本棚.
+
+use crate::{
+    common::types::{BookFormat, BookStore},
+    use_case::dto::book::ImportBookEntryDto,
+};
+
+use super::{
+    csv::{CsvImportEntry, CsvImportError, find_column, read_rows, split_author_names},
+    isbn::normalize_isbn,
+    shelf::Shelf,
+};
+
+/// Reads the books of a Bookmeter export. The 本棚 column, when there is one,
+/// holds the shelf that decides whether a book is read, owned and its
+/// priority; exports of read books only leave it out. A 読了日 marks a book
+/// read too. Amazon ASINs, which Bookmeter gives for Kindle books in place
+/// of an ISBN, make a book a Kindle eBook without an ISBN.
+pub fn parse_bookmeter(data: &[u8]) -> Result<Vec<CsvImportEntry>, CsvImportError> {
+    let (headers, rows) = read_rows(data)?;
+    let title = find_column(&headers, "タイトル")?;
+    let authors = find_column(&headers, "著者")?;
+    let optional = |names: &[&str]| {
+        names
+            .iter()
+            .find_map(|name| find_column(&headers, name).ok())
+    };
+    let code = optional(&["ISBN/ASIN", "ISBN", "ASIN"]);
+    let read_date = optional(&["読了日"]);
+    let shelf = optional(&["本棚"]);
+
+    Ok(rows
+        .into_iter()
+        .map(|row| {
+            let cell = |column: &Option<_>| column.as_ref().map_or("", |column| row.cell(column));
+
+            let shelf = match (&shelf, cell(&shelf)) {
+                (None, _) | (_, "読んだ本") => Shelf::Read,
+                (_, "読んでる本") => Shelf::Reading,
+                (_, "読みたい本") => Shelf::WantToRead,
+                (_, "積読本") => Shelf::Unread,
+                _ => Shelf::Other,
+            };
+            let code = cell(&code);
+            let (isbn, format, store) = if is_asin(code) {
+                (String::new(), BookFormat::EBook, BookStore::Kindle)
+            } else {
+                (
+                    normalize_isbn(code),
+                    BookFormat::Unknown,
+                    BookStore::Unknown,
+                )
+            };
+
+            CsvImportEntry {
+                line: row.line,
+                entry: ImportBookEntryDto {
+                    title: row.cell(&title).to_string(),
+                    // Bookmeter separates authors with full-width commas too.
+                    author_names: split_author_names(&row.cell(&authors).replace('、', ","), ","),
+                    isbn,
+                    read: shelf.is_read() || !cell(&read_date).is_empty(),
+                    owned: shelf.is_owned(),
+                    priority: shelf.priority(),
+                    format,
+                    store,
+                },
+            }
+        })
+        .collect())
+}
+
+// ASINs of Kindle books are ten characters starting with "B", which no
+// ISBN-10 does.
+fn is_asin(code: &str) -> bool {
+    code.len() == 10 && code.starts_with('B') && code.bytes().all(|b| b.is_ascii_alphanumeric())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::common::types::{BookFormat, BookStore};
+
+    use super::parse_bookmeter;
+
+    #[test]
+    fn parse_bookmeter_maps_shelves_and_asins() {
+        // Given: Books on each shelf, one of them a Kindle book
+        let data = "タイトル,著者,ISBN/ASIN,読了日,本棚\n\
+                    ノルウェイの森,村上春樹,4062748681,2023/05/14,読んだ本\n\
+                    コンビニ人間,村田沙耶香,B01J4JW3KM,,読んでる本\n\
+                    共著の本,著者A、著者B,9784062758574,,積読本\n\
+                    気になる本,著者C,,,読みたい本\n";
+
+        // When: Parsing it
+        let entries = parse_bookmeter(data.as_bytes()).unwrap();
+
+        // Then: Shelves give the flags and priority
+        assert_eq!(entries.len(), 4);
+        let read = &entries[0].entry;
+        assert_eq!(read.isbn, "9784062748681");
+        assert!(read.read);
+        assert_eq!(read.priority, 0);
+
+        let kindle = &entries[1].entry;
+        assert_eq!(kindle.isbn, "");
+        assert_eq!(kindle.format, BookFormat::EBook);
+        assert_eq!(kindle.store, BookStore::Kindle);
+        assert!(!kindle.read);
+        assert_eq!(kindle.priority, 80);
+
+        let unread = &entries[2].entry;
+        assert_eq!(unread.author_names, vec!["著者A", "著者B"]);
+        assert!(unread.owned);
+        assert!(!unread.read);
+        assert_eq!(unread.priority, 50);
+
+        let wanted = &entries[3].entry;
+        assert!(!wanted.owned);
+        assert_eq!(wanted.priority, 50);
+    }
+
+    #[test]
+    fn parse_bookmeter_reads_books_without_a_shelf_column_as_read() {
+        let data = "タイトル,著者\nノルウェイの森,村上春樹\n";
+
+        let entries = parse_bookmeter(data.as_bytes()).unwrap();
+
+        assert!(entries[0].entry.read);
+    }
+}
@@ -0,0 +1,430 @@
+//! Turns the rows of a CSV file into import entries, following a column
+//! mapping that tells which column holds which field of a book.
+
+use ::csv::StringRecord;
+use serde::Deserialize;
+use thiserror::Error;
+
+use crate::{
+    common::types::{BookFormat, BookStore},
+    use_case::dto::book::ImportBookEntryDto,
+};
+
+const DEFAULT_AUTHOR_SEPARATOR: &str = ";";
+const UTF8_BOM: &[u8] = b"\xEF\xBB\xBF";
+
+/// Which column of the CSV file holds which field, by header name. Only the
+/// title is required. Fields without a column get the value a blank cell
+/// would: no authors, no ISBN, not read, not owned, priority 0 and an
+/// unknown format and store.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct ColumnMapping {
+    pub title: String,
+    pub authors: Option<String>,
+    /// Separates the names in the authors column. Defaults to `;`, as names
+    /// such as "Tolkien, J. R. R." hold commas. A name holding the separator
+    /// itself can be wrapped in double quotes, with `""` for a quote inside.
+    pub author_separator: Option<String>,
+    pub isbn: Option<String>,
+    pub read: Option<String>,
+    pub owned: Option<String>,
+    pub priority: Option<String>,
+    pub format: Option<String>,
+    pub store: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum CsvImportError {
+    #[error("invalid CSV: {0}")]
+    Csv(String),
+    #[error(r#"column "{0}" is not in the CSV header"#)]
+    MissingColumn(String),
+    #[error("the author separator cannot be empty")]
+    EmptyAuthorSeparator,
+    #[error(r#"line {line}, column "{column}": {message}"#)]
+    Value {
+        line: u64,
+        column: String,
+        message: String,
+    },
+}
+
+/// An entry read from the CSV file, with the line its row starts on.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CsvImportEntry {
+    pub line: u64,
+    pub entry: ImportBookEntryDto,
+}
+
+/// Reads the entries of a CSV file whose first row is a header. Rows whose
+/// cells are all blank are skipped. A cell that cannot be read as its field,
+/// such as "maybe" in the read column, fails the whole file; values that
+/// can be read but are invalid, such as a priority of 200, are left to the
+/// import to report.
+pub fn parse_csv(
+    data: &[u8],
+    mapping: &ColumnMapping,
+) -> Result<Vec<CsvImportEntry>, CsvImportError> {
+    let separator = mapping
+        .author_separator
+        .as_deref()
+        .unwrap_or(DEFAULT_AUTHOR_SEPARATOR);
+    if separator.is_empty() {
+        return Err(CsvImportError::EmptyAuthorSeparator);
+    }
+
+    let (headers, rows) = read_rows(data)?;
+    let columns = MappedColumns::resolve(&headers, mapping)?;
+
+    let mut entries = Vec::new();
+    for row in rows {
+        let entry = ImportBookEntryDto {
+            title: row.cell(&columns.title).to_string(),
+            author_names: columns
+                .authors
+                .as_ref()
+                .map(|column| split_author_names(row.cell(column), separator))
+                .unwrap_or_default(),
+            isbn: columns
+                .isbn
+                .as_ref()
+                .map(|column| row.cell(column).to_string())
+                .unwrap_or_default(),
+            read: row.parse(&columns.read, parse_flag)?.unwrap_or(false),
+            owned: row.parse(&columns.owned, parse_flag)?.unwrap_or(false),
+            priority: row.parse(&columns.priority, parse_priority)?.unwrap_or(0),
+            format: row
+                .parse(&columns.format, parse_format)?
+                .unwrap_or(BookFormat::Unknown),
+            store: row
+                .parse(&columns.store, parse_store)?
+                .unwrap_or(BookStore::Unknown),
+        };
+        entries.push(CsvImportEntry {
+            line: row.line,
+            entry,
+        });
+    }
+    Ok(entries)
+}
+
+// Reads the header of a CSV file and the rows after it, leaving out rows
+// whose cells are all blank.
+pub(super) fn read_rows(data: &[u8]) -> Result<(StringRecord, Vec<Row>), CsvImportError> {
+    let data = data.strip_prefix(UTF8_BOM).unwrap_or(data);
+    let mut reader = ::csv::ReaderBuilder::new().flexible(true).from_reader(data);
+    let headers = reader
+        .headers()
+        .map_err(|e| CsvImportError::Csv(e.to_string()))?
+        .clone();
+
+    let mut rows = Vec::new();
+    for record in reader.records() {
+        let record = record.map_err(|e| CsvImportError::Csv(e.to_string()))?;
+        if record.iter().all(|cell| cell.trim().is_empty()) {
+            continue;
+        }
+        let line = record.position().map_or(0, |position| position.line());
+        rows.push(Row { record, line });
+    }
+    Ok((headers, rows))
+}
+
+pub(super) struct Column {
+    name: String,
+    index: usize,
+}
+
+pub(super) fn find_column(headers: &StringRecord, name: &str) -> Result<Column, CsvImportError> {
+    headers
+        .iter()
+        .position(|header| header.trim() == name.trim())
+        .map(|index| Column {
+            name: name.to_string(),
+            index,
+        })
+        .ok_or_else(|| CsvImportError::MissingColumn(name.to_string()))
+}
+
+struct MappedColumns {
+    title: Column,
+    authors: Option<Column>,
+    isbn: Option<Column>,
+    read: Option<Column>,
+    owned: Option<Column>,
+    priority: Option<Column>,
+    format: Option<Column>,
+    store: Option<Column>,
+}
+
+impl MappedColumns {
+    fn resolve(headers: &StringRecord, mapping: &ColumnMapping) -> Result<Self, CsvImportError> {
+        let find = |name: &str| find_column(headers, name);
+        let find_optional = |name: &Option<String>| name.as_deref().map(find).transpose();
+
+        Ok(Self {
+            title: find(&mapping.title)?,
+            authors: find_optional(&mapping.authors)?,
+            isbn: find_optional(&mapping.isbn)?,
+            read: find_optional(&mapping.read)?,
+            owned: find_optional(&mapping.owned)?,
+            priority: find_optional(&mapping.priority)?,
+            format: find_optional(&mapping.format)?,
+            store: find_optional(&mapping.store)?,
+        })
+    }
+}
+
+pub(super) struct Row {
+    record: StringRecord,
+    pub(super) line: u64,
+}
+
+impl Row {
+    // Short rows are read as if their missing cells were blank.
+    pub(super) fn cell(&self, column: &Column) -> &str {
+        self.record.get(column.index).unwrap_or("").trim()
+    }
+
+    // Blank cells, like unmapped columns, give None.
+    fn parse<T>(
+        &self,
+        column: &Option<Column>,
+        parse: fn(&str) -> Result<T, String>,
+    ) -> Result<Option<T>, CsvImportError> {
+        let Some(column) = column else {
+            return Ok(None);
+        };
+        let cell = self.cell(column);
+        if cell.is_empty() {
+            return Ok(None);
+        }
+        parse(cell)
+            .map(Some)
+            .map_err(|message| CsvImportError::Value {
+                line: self.line,
+                column: column.name.clone(),
+                message,
+            })
+    }
+}
+
+pub(super) fn split_author_names(cell: &str, separator: &str) -> Vec<String> {
+    let mut names = Vec::new();
+    let mut rest = Some(cell);
+    while let Some(cell) = rest {
+        let (name, next) = next_author_name(cell, separator);
+        if !name.is_empty() {
+            names.push(name);
+        }
+        rest = next;
+    }
+    names
+}
+
+// Reads the first name of `cell`, and what follows its separator if anything
+// does. A name wrapped in double quotes, with `""` for a quote inside, can
+// hold the separator and keeps its spaces. Quotes anywhere else are read as
+// they are.
+fn next_author_name<'a>(cell: &'a str, separator: &str) -> (String, Option<&'a str>) {
+    if let Some(quoted) = cell.trim_start().strip_prefix('"') {
+        let mut name = String::new();
+        let mut chars = quoted.char_indices().peekable();
+        while let Some((index, c)) = chars.next() {
+            if c != '"' {
+                name.push(c);
+            } else if chars.next_if(|&(_, c)| c == '"').is_some() {
+                name.push('"');
+            } else {
+                let rest = &quoted[index + 1..];
+                if rest.trim().is_empty() {
+                    return (name, None);
+                }
+                match rest.split_once(separator) {
+                    Some((gap, next)) if gap.trim().is_empty() => return (name, Some(next)),
+                    _ => break,
+                }
+            }
+        }
+    }
+    match cell.split_once(separator) {
+        Some((name, next)) => (name.trim().to_string(), Some(next)),
+        None => (cell.trim().to_string(), None),
+    }
+}
+
+fn parse_flag(cell: &str) -> Result<bool, String> {
+    match cell.to_lowercase().as_str() {
+        "true" | "yes" | "y" | "1" | "x" => Ok(true),
+        "false" | "no" | "n" | "0" => Ok(false),
+        _ => Err(format!(r#""{cell}" is not a yes/no value"#)),
+    }
+}
+
+fn parse_priority(cell: &str) -> Result<i32, String> {
+    cell.parse()
+        .map_err(|_| format!(r#""{cell}" is not a whole number"#))
+}
+
+// Formats and stores are matched regardless of case and underscores, so both
+// the names books are shown with ("eBook") and GraphQL enum values ("E_BOOK")
+// work.
+fn parse_format(cell: &str) -> Result<BookFormat, String> {
+    let name = cell.replace('_', "");
+    [BookFormat::EBook, BookFormat::Printed, BookFormat::Unknown]
+        .into_iter()
+        .find(|format| format.to_string().eq_ignore_ascii_case(&name))
+        .ok_or_else(|| format!(r#""{cell}" is not a format"#))
+}
+
+fn parse_store(cell: &str) -> Result<BookStore, String> {
+    [BookStore::Kindle, BookStore::Unknown]
+        .into_iter()
+        .find(|store| store.to_string().eq_ignore_ascii_case(cell))
+        .ok_or_else(|| format!(r#""{cell}" is not a store"#))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::common::types::{BookFormat, BookStore};
+
+    use super::{ColumnMapping, CsvImportError, parse_csv, split_author_names};
+
+    fn full_mapping() -> ColumnMapping {
+        ColumnMapping {
+            title: "Title".to_string(),
+            authors: Some("Authors".to_string()),
+            author_separator: None,
+            isbn: Some("ISBN".to_string()),
+            read: Some("Read".to_string()),
+            owned: Some("Owned".to_string()),
+            priority: Some("Priority".to_string()),
+            format: Some("Format".to_string()),
+            store: Some("Store".to_string()),
+        }
+    }
+
+    #[test]
+    fn parse_csv_reads_mapped_columns() {
+        // Given: A file with every column mapped, in another order
+        let data = "Store,Title,Authors,ISBN,Priority,Read,Owned,Format\n\
+                    Kindle,Dune,Frank Herbert,9780441013593,80,yes,no,E_BOOK\n\
+                    ,The Hobbit,\"Tolkien, J. R. R.; Someone Else \",,,,1,PRINTED\n";
+
+        // When: Parsing it
+        let entries = parse_csv(data.as_bytes(), &full_mapping()).unwrap();
+
+        // Then: Each row becomes an entry with the line it is on
+        assert_eq!(entries.len(), 2);
+        let dune = &entries[0];
+        assert_eq!(dune.line, 2);
+        assert_eq!(dune.entry.title, "Dune");
+        assert_eq!(dune.entry.author_names, vec!["Frank Herbert"]);
+        assert_eq!(dune.entry.isbn, "9780441013593");
+        assert!(dune.entry.read);
+        assert!(!dune.entry.owned);
+        assert_eq!(dune.entry.priority, 80);
+        assert_eq!(dune.entry.format, BookFormat::EBook);
+        assert_eq!(dune.entry.store, BookStore::Kindle);
+
+        // And: Blank cells get the defaults
+        let hobbit = &entries[1];
+        assert_eq!(hobbit.line, 3);
+        assert_eq!(
+            hobbit.entry.author_names,
+            vec!["Tolkien, J. R. R.", "Someone Else"]
+        );
+        assert_eq!(hobbit.entry.isbn, "");
+        assert!(!hobbit.entry.read);
+        assert!(hobbit.entry.owned);
+        assert_eq!(hobbit.entry.priority, 0);
+        assert_eq!(hobbit.entry.format, BookFormat::Printed);
+        assert_eq!(hobbit.entry.store, BookStore::Unknown);
+    }
+
+    #[test]
+    fn parse_csv_uses_defaults_for_unmapped_columns_and_custom_separator() {
+        // Given: A spreadsheet export with a BOM, a blank row and a short row
+        let data = "\u{FEFF}Name,Writers,Notes\nDune,Frank Herbert|Brian Herbert,x\n,,\nEmma\n";
+        let mapping = ColumnMapping {
+            title: "Name".to_string(),
+            authors: Some("Writers".to_string()),
+            author_separator: Some("|".to_string()),
+            ..Default::default()
+        };
+
+        // When: Parsing it
+        let entries = parse_csv(data.as_bytes(), &mapping).unwrap();
+
+        // Then: The blank row is skipped and the short row has no authors
+        assert_eq!(entries.len(), 2);
+        assert_eq!(
+            entries[0].entry.author_names,
+            vec!["Frank Herbert", "Brian Herbert"]
+        );
+        assert_eq!(entries[0].entry.format, BookFormat::Unknown);
+        assert_eq!(entries[1].line, 4);
+        assert_eq!(entries[1].entry.title, "Emma");
+        assert!(entries[1].entry.author_names.is_empty());
+    }
+
+    #[test]
+    fn parse_csv_rejects_a_mapped_column_missing_from_the_header() {
+        let data = "Title\nDune\n";
+
+        let result = parse_csv(data.as_bytes(), &full_mapping());
+
+        assert_eq!(
+            result,
+            Err(CsvImportError::MissingColumn("Authors".to_string()))
+        );
+    }
+
+    #[test]
+    fn parse_csv_reports_the_line_and_column_of_an_unreadable_cell() {
+        let data = "Title,Read\nDune,yes\nEmma,maybe\n";
+        let mapping = ColumnMapping {
+            title: "Title".to_string(),
+            read: Some("Read".to_string()),
+            ..Default::default()
+        };
+
+        let result = parse_csv(data.as_bytes(), &mapping);
+
+        assert_eq!(
+            result,
+            Err(CsvImportError::Value {
+                line: 3,
+                column: "Read".to_string(),
+                message: r#""maybe" is not a yes/no value"#.to_string(),
+            })
+        );
+    }
+
+    #[test]
+    fn parse_csv_rejects_an_empty_author_separator() {
+        let mapping = ColumnMapping {
+            title: "Title".to_string(),
+            author_separator: Some(String::new()),
+            ..Default::default()
+        };
+
+        let result = parse_csv(b"Title\nDune\n", &mapping);
+
+        assert_eq!(result, Err(CsvImportError::EmptyAuthorSeparator));
+    }
+
+    #[test]
+    fn split_author_names_reads_quoted_names_whole() {
+        assert_eq!(
+            split_author_names(r#""Smith; Jones"; Doe ; "Say ""Hi"""; "#, ";"),
+            vec!["Smith; Jones", "Doe", r#"Say "Hi""#]
+        );
+        // Quotes that do not wrap a whole name are part of it
+        assert_eq!(
+            split_author_names(r#""Weird Al" Yankovic; O"Brien"#, ";"),
+            vec![r#""Weird Al" Yankovic"#, r#"O"Brien"#]
+        );
+    }
+}
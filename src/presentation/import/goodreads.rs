@@ -0,0 +1,175 @@
+//! Reads the CSV file Goodreads exports a library as, from "My Books" >
+//! "Import and export".
+
+use crate::{
+    common::types::{BookFormat, BookStore},
+    use_case::dto::book::ImportBookEntryDto,
+};
+
+use super::{
+    csv::{CsvImportEntry, CsvImportError, find_column, read_rows, split_author_names},
+    isbn::normalize_isbn,
+    shelf::Shelf,
+};
+
+/// Reads the books of a Goodreads library export. The exclusive shelf decides
+/// whether a book is read and its priority, and a read date marks it read
+/// too. Books with owned copies or on an "owned" shelf are owned. The
+/// binding gives the format, Kindle editions being eBooks from Kindle.
+pub fn parse_goodreads(data: &[u8]) -> Result<Vec<CsvImportEntry>, CsvImportError> {
+    let (headers, rows) = read_rows(data)?;
+    let title = find_column(&headers, "Title")?;
+    let author = find_column(&headers, "Author")?;
+    let optional = |name| find_column(&headers, name).ok();
+    let additional_authors = optional("Additional Authors");
+    let isbn = optional("ISBN");
+    let isbn13 = optional("ISBN13");
+    let binding = optional("Binding");
+    let date_read = optional("Date Read");
+    let exclusive_shelf = optional("Exclusive Shelf");
+    let bookshelves = optional("Bookshelves");
+    let owned_copies = optional("Owned Copies");
+
+    Ok(rows
+        .into_iter()
+        .map(|row| {
+            let cell = |column: &Option<_>| column.as_ref().map_or("", |column| row.cell(column));
+
+            let shelf = match cell(&exclusive_shelf) {
+                "read" => Shelf::Read,
+                "currently-reading" => Shelf::Reading,
+                "to-read" => Shelf::WantToRead,
+                _ => Shelf::Other,
+            };
+            let on_owned_shelf = cell(&bookshelves)
+                .split(',')
+                .any(|shelf| shelf.trim() == "owned");
+            let has_copies = cell(&owned_copies)
+                .parse::<u32>()
+                .is_ok_and(|copies| copies > 0);
+
+            // "Author" holds a single name, which may itself contain a comma
+            // ("Martin Luther King, Jr."); only the additional ones are a list.
+            let mut author_names: Vec<String> = [row.cell(&author).trim()]
+                .into_iter()
+                .filter(|name| !name.is_empty())
+                .map(ToString::to_string)
+                .collect();
+            author_names.extend(split_author_names(cell(&additional_authors), ","));
+            // Goodreads pads some names with double spaces ("J.R.R.  Tolkien").
+            let author_names = author_names
+                .iter()
+                .map(|name| name.split_whitespace().collect::<Vec<_>>().join(" "))
+                .collect();
+
+            let isbn = match normalize_isbn(cell(&isbn13)) {
+                isbn13 if isbn13.is_empty() => normalize_isbn(cell(&isbn)),
+                isbn13 => isbn13,
+            };
+            let (format, store) = format_and_store(cell(&binding));
+
+            CsvImportEntry {
+                line: row.line,
+                entry: ImportBookEntryDto {
+                    title: row.cell(&title).to_string(),
+                    author_names,
+                    isbn,
+                    read: shelf.is_read() || !cell(&date_read).is_empty(),
+                    owned: shelf.is_owned() || on_owned_shelf || has_copies,
+                    priority: shelf.priority(),
+                    format,
+                    store,
+                },
+            }
+        })
+        .collect())
+}
+
+fn format_and_store(binding: &str) -> (BookFormat, BookStore) {
+    let binding = binding.to_lowercase();
+    if binding.contains("kindle") {
+        (BookFormat::EBook, BookStore::Kindle)
+    } else if binding.contains("ebook") || binding.contains("e-book") {
+        (BookFormat::EBook, BookStore::Unknown)
+    } else if ["paperback", "hardcover", "tankobon", "bunko"]
+        .iter()
+        .any(|printed| binding.contains(printed))
+    {
+        (BookFormat::Printed, BookStore::Unknown)
+    } else {
+        (BookFormat::Unknown, BookStore::Unknown)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::common::types::{BookFormat, BookStore};
+
+    use super::parse_goodreads;
+
+    const HEADER: &str = "Book Id,Title,Author,Author l-f,Additional Authors,ISBN,ISBN13,\
+                          My Rating,Average Rating,Publisher,Binding,Number of Pages,\
+                          Year Published,Original Publication Year,Date Read,Date Added,\
+                          Bookshelves,Bookshelves with positions,Exclusive Shelf,My Review,\
+                          Spoiler,Private Notes,Read Count,Owned Copies";
+
+    #[test]
+    fn parse_goodreads_maps_shelves_isbns_and_bindings() {
+        // Given: A read Kindle book, a to-read owned paperback with only an
+        // ISBN-10, a book being read, and a book whose author's name holds a
+        // comma
+        let data = format!(
+            "{HEADER}\n\
+             1,Dune,Frank Herbert,\"Herbert, Frank\",Brian Herbert,\"=\"\"0441013597\"\"\",\"=\"\"9780441013593\"\"\",5,4.27,Ace,Kindle Edition,604,2005,1965,2023/05/14,2023/01/02,,,read,,,,1,0\n\
+             2,The Hobbit,J.R.R.  Tolkien,\"Tolkien, J.R.R.\",,\"=\"\"054792822X\"\"\",\"=\"\"\"\"\",0,4.28,HMH,Paperback,300,2012,1937,,2023/01/03,\"owned, fantasy\",,to-read,,,,0,0\n\
+             3,Emma,Jane Austen,\"Austen, Jane\",,\"=\"\"\"\"\",\"=\"\"\"\"\",0,4.0,Penguin,Hardcover,474,2003,1815,,2023/01/04,,,currently-reading,,,,0,1\n\
+             4,Strength to Love,\"Martin Luther King, Jr.\",\"King, Martin Luther, Jr.\",\"Coretta Scott King, Andrew Young\",\"=\"\"\"\"\",\"=\"\"\"\"\",0,4.5,Fortress,Paperback,192,2010,1963,,2023/01/05,,,to-read,,,,0,0\n"
+        );
+
+        // When: Parsing it
+        let entries = parse_goodreads(data.as_bytes()).unwrap();
+
+        // Then: Shelves give the flags and priority, and ISBNs become ISBN-13s
+        assert_eq!(entries.len(), 4);
+        let dune = &entries[0].entry;
+        assert_eq!(dune.author_names, vec!["Frank Herbert", "Brian Herbert"]);
+        assert_eq!(dune.isbn, "9780441013593");
+        assert!(dune.read);
+        assert!(!dune.owned);
+        assert_eq!(dune.priority, 0);
+        assert_eq!(dune.format, BookFormat::EBook);
+        assert_eq!(dune.store, BookStore::Kindle);
+
+        let hobbit = &entries[1].entry;
+        assert_eq!(hobbit.author_names, vec!["J.R.R. Tolkien"]);
+        assert_eq!(hobbit.isbn, "9780547928227");
+        assert!(!hobbit.read);
+        assert!(hobbit.owned);
+        assert_eq!(hobbit.priority, 50);
+        assert_eq!(hobbit.format, BookFormat::Printed);
+
+        let emma = &entries[2].entry;
+        assert_eq!(emma.isbn, "");
+        assert!(!emma.read);
+        assert!(emma.owned);
+        assert_eq!(emma.priority, 80);
+        assert_eq!(entries[2].line, 4);
+
+        let strength = &entries[3].entry;
+        assert_eq!(
+            strength.author_names,
+            vec![
+                "Martin Luther King, Jr.",
+                "Coretta Scott King",
+                "Andrew Young"
+            ]
+        );
+    }
+
+    #[test]
+    fn parse_goodreads_requires_title_and_author_columns() {
+        let result = parse_goodreads(b"Title,ISBN\nDune,\n");
+
+        assert!(result.is_err());
+    }
+}
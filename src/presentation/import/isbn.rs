@@ -0,0 +1,64 @@
+//! ISBNs as exported by other services, which write them as ISBN-10 or wrap
+//! them for spreadsheets, turned into the ISBN-13 books are stored with.
+
+/// Returns the ISBN-13 for `raw`, converting an ISBN-10. Hyphens, spaces and
+/// the `="…"` wrapping spreadsheets are given are dropped. Anything that is
+/// not an ISBN-10 is returned cleaned up, for the import to validate.
+pub fn normalize_isbn(raw: &str) -> String {
+    let cleaned: String = raw
+        .trim()
+        .trim_start_matches('=')
+        .trim_matches('"')
+        .chars()
+        .filter(|c| *c != '-' && !c.is_whitespace())
+        .collect();
+    isbn10_to_isbn13(&cleaned).unwrap_or(cleaned)
+}
+
+// ISBN-10s get the 978 prefix, and the check digit is worked out again as
+// ISBN-13s weigh their digits differently.
+fn isbn10_to_isbn13(isbn10: &str) -> Option<String> {
+    if isbn10.len() != 10 {
+        return None;
+    }
+    let (body, check) = isbn10.split_at_checked(9)?;
+    if !body.bytes().all(|b| b.is_ascii_digit())
+        || !check
+            .bytes()
+            .all(|b| b.is_ascii_digit() || b == b'X' || b == b'x')
+    {
+        return None;
+    }
+
+    let digits = format!("978{body}");
+    let sum: u32 = digits
+        .chars()
+        .filter_map(|c| c.to_digit(10))
+        .enumerate()
+        .map(|(i, digit)| if i % 2 == 0 { digit } else { digit * 3 })
+        .sum();
+    Some(format!("{digits}{}", (10 - sum % 10) % 10))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::normalize_isbn;
+
+    #[test]
+    fn normalize_isbn_converts_isbn10() {
+        assert_eq!(normalize_isbn("0441013597"), "9780441013593");
+        assert_eq!(normalize_isbn("4-06-275857-X"), "9784062758574");
+    }
+
+    #[test]
+    fn normalize_isbn_unwraps_spreadsheet_values() {
+        assert_eq!(normalize_isbn(r#"="9780441013593""#), "9780441013593");
+        assert_eq!(normalize_isbn(r#"="""#), "");
+    }
+
+    #[test]
+    fn normalize_isbn_leaves_other_values_for_validation() {
+        assert_eq!(normalize_isbn("978-4-06-275857-4"), "9784062758574");
+        assert_eq!(normalize_isbn("B00ABCDEFG"), "B00ABCDEFG");
+    }
+}
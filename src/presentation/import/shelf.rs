@@ -0,0 +1,36 @@
+//! The reading states other services sort books into, and how a book on
+//! each is imported.
+
+const READING_PRIORITY: i32 = 80;
+const WANT_TO_READ_PRIORITY: i32 = 50;
+
+/// Where a service had a book. Books still to be read get a priority, higher
+/// for the ones being read, so they come first among unread books.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Shelf {
+    Read,
+    Reading,
+    WantToRead,
+    // Bought but not read yet, which Bookmeter calls 積読.
+    Unread,
+    // Any other shelf, e.g. a custom one.
+    Other,
+}
+
+impl Shelf {
+    pub fn is_read(&self) -> bool {
+        *self == Shelf::Read
+    }
+
+    pub fn is_owned(&self) -> bool {
+        *self == Shelf::Unread
+    }
+
+    pub fn priority(&self) -> i32 {
+        match self {
+            Shelf::Reading => READING_PRIORITY,
+            Shelf::WantToRead | Shelf::Unread => WANT_TO_READ_PRIORITY,
+            Shelf::Read | Shelf::Other => 0,
+        }
+    }
+}
@@ -1,6 +1,13 @@
+pub mod activity;
 pub mod author;
 pub mod book;
+pub mod change;
 pub mod event;
 pub mod event_set;
+pub mod history;
+pub mod import_job;
 pub mod mutation;
+pub mod pagination;
+pub mod search;
+pub mod stats;
 pub mod user;
@@ -0,0 +1,44 @@
+use time::OffsetDateTime;
+
+use crate::{
+    common::activity::ActivityGranularity,
+    domain::{
+        error::DomainError,
+        repository::activity::{ActivityPeriod, ActivityRange},
+    },
+};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ActivityRangeDto {
+    pub from: OffsetDateTime,
+    pub to: OffsetDateTime,
+    pub granularity: ActivityGranularity,
+    pub utc_offset_minutes: i32,
+}
+
+impl TryFrom<ActivityRangeDto> for ActivityRange {
+    type Error = DomainError;
+
+    fn try_from(dto: ActivityRangeDto) -> Result<Self, Self::Error> {
+        ActivityRange::new(dto.from, dto.to, dto.granularity, dto.utc_offset_minutes)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ActivityPeriodDto {
+    pub start: OffsetDateTime,
+    pub finished_count: i64,
+    pub added_count: i64,
+    pub deleted_count: i64,
+}
+
+impl From<ActivityPeriod> for ActivityPeriodDto {
+    fn from(period: ActivityPeriod) -> Self {
+        Self {
+            start: period.start,
+            finished_count: period.finished_count,
+            added_count: period.added_count,
+            deleted_count: period.deleted_count,
+        }
+    }
+}
@@ -1,4 +1,12 @@
-use crate::domain::entity::author::{Author, DestructureAuthor};
+use crate::{
+    common::sort::AuthorSortValue,
+    domain::{
+        entity::author::{Author, AuthorId, DestructureAuthor},
+        error::DomainError,
+        repository::author_repository::AuthorCursor,
+    },
+    use_case::dto::mutation::ExpectedVersionDto,
+};
 use time::OffsetDateTime;
 
 #[derive(Debug, PartialEq, Eq)]
@@ -29,6 +37,32 @@ impl From<Author> for AuthorDto {
     }
 }
 
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AuthorCursorDto {
+    pub value: AuthorSortValue,
+    pub id: String,
+}
+
+impl From<AuthorCursor> for AuthorCursorDto {
+    fn from(cursor: AuthorCursor) -> Self {
+        Self {
+            value: cursor.value,
+            id: cursor.id.to_string(),
+        }
+    }
+}
+
+impl TryFrom<AuthorCursorDto> for AuthorCursor {
+    type Error = DomainError;
+
+    fn try_from(cursor: AuthorCursorDto) -> Result<Self, Self::Error> {
+        Ok(AuthorCursor {
+            value: cursor.value,
+            id: AuthorId::try_from(cursor.id.as_str())?,
+        })
+    }
+}
+
 pub struct CreateAuthorDto {
     pub name: String,
     pub yomi: Option<String>,
@@ -44,6 +78,15 @@ pub struct UpdateAuthorDto {
     pub id: String,
     pub name: String,
     pub yomi: Option<String>,
+    pub expected_version: ExpectedVersionDto,
+}
+
+// Fields left as None keep their stored values.
+pub struct PatchAuthorDto {
+    pub id: String,
+    pub name: Option<String>,
+    pub yomi: Option<String>,
+    pub expected_version: ExpectedVersionDto,
 }
 
 pub struct MergeAuthorInputDto {
@@ -57,6 +100,18 @@ impl UpdateAuthorDto {
             id,
             name,
             yomi: None,
+            expected_version: ExpectedVersionDto::default(),
+        }
+    }
+}
+
+impl PatchAuthorDto {
+    pub fn new(id: String) -> Self {
+        Self {
+            id,
+            name: None,
+            yomi: None,
+            expected_version: ExpectedVersionDto::default(),
         }
     }
 }
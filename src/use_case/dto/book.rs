@@ -2,15 +2,23 @@ use time::OffsetDateTime;
 use uuid::Uuid;
 
 use crate::{
-    common::types::{BookFormat, BookStore},
+    common::{
+        sort::BookSortValue,
+        types::{BookFormat, BookStore},
+    },
     domain::{
         entity::{
             author::AuthorId,
-            book::{Book, BookId, BookTitle, DestructureBook, Isbn, OwnedFlag, Priority, ReadFlag},
+            book::{
+                AuthorIdsPatch, Book, BookId, BookPatch, BookTitle, DestructureBook, Isbn,
+                OwnedFlag, Priority, ReadFlag,
+            },
+            import_job::{ImportEntry, ImportMode},
         },
         error::DomainError,
+        repository::book_repository::{BookCursor, BookFilter, BookSelection},
     },
-    use_case::error::UseCaseError,
+    use_case::{dto::mutation::ExpectedVersionDto, error::UseCaseError},
 };
 
 #[derive(Debug, Clone)]
@@ -63,6 +71,86 @@ impl From<Book> for BookDto {
     }
 }
 
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BookCursorDto {
+    pub value: BookSortValue,
+    pub id: String,
+}
+
+impl From<BookCursor> for BookCursorDto {
+    fn from(cursor: BookCursor) -> Self {
+        Self {
+            value: cursor.value,
+            id: cursor.id.to_string(),
+        }
+    }
+}
+
+impl TryFrom<BookCursorDto> for BookCursor {
+    type Error = DomainError;
+
+    fn try_from(cursor: BookCursorDto) -> Result<Self, Self::Error> {
+        Ok(BookCursor {
+            value: cursor.value,
+            id: BookId::try_from(cursor.id.as_str())?,
+        })
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct BookFilterDto {
+    pub read: Option<bool>,
+    pub owned: Option<bool>,
+    pub format: Option<BookFormat>,
+    pub store: Option<BookStore>,
+    pub priority_min: Option<i32>,
+    pub priority_max: Option<i32>,
+    pub author_ids: Option<Vec<String>>,
+    pub created_from: Option<OffsetDateTime>,
+    pub created_to: Option<OffsetDateTime>,
+    pub updated_from: Option<OffsetDateTime>,
+    pub updated_to: Option<OffsetDateTime>,
+    pub has_isbn: Option<bool>,
+}
+
+impl TryFrom<BookFilterDto> for BookFilter {
+    type Error = DomainError;
+
+    fn try_from(filter: BookFilterDto) -> Result<Self, Self::Error> {
+        if let (Some(min), Some(max)) = (filter.priority_min, filter.priority_max)
+            && min > max
+        {
+            return Err(DomainError::Validation(format!(
+                "priority min ({min}) must not be greater than max ({max})."
+            )));
+        }
+        let author_ids = filter
+            .author_ids
+            .map(|author_ids| {
+                author_ids
+                    .iter()
+                    .map(|author_id| AuthorId::try_from(author_id.as_str()))
+                    .collect::<Result<Vec<_>, _>>()
+            })
+            .transpose()?;
+
+        Ok(BookFilter {
+            read: filter.read,
+            owned: filter.owned,
+            format: filter.format,
+            store: filter.store,
+            priority_min: filter.priority_min,
+            priority_max: filter.priority_max,
+            author_ids,
+            created_from: filter.created_from,
+            created_to: filter.created_to,
+            updated_from: filter.updated_from,
+            updated_to: filter.updated_to,
+            has_isbn: filter.has_isbn,
+        })
+    }
+}
+
 pub struct TimeInfo {
     pub created_at: OffsetDateTime,
     pub updated_at: OffsetDateTime,
@@ -156,9 +244,96 @@ pub struct UpdateBookDto {
     pub priority: i32,
     pub format: BookFormat,
     pub store: BookStore,
+    pub expected_version: ExpectedVersionDto,
 }
 
 #[derive(Debug, Clone)]
+pub struct PatchBookDto {
+    pub id: String,
+    pub patch: BookPatchDto,
+    pub expected_version: ExpectedVersionDto,
+}
+
+// Fields left as None keep their stored values.
+#[derive(Debug, Clone, Default)]
+pub struct BookPatchDto {
+    pub title: Option<String>,
+    pub author_ids: Option<AuthorIdsPatchDto>,
+    pub isbn: Option<String>,
+    pub read: Option<bool>,
+    pub owned: Option<bool>,
+    pub priority: Option<i32>,
+    pub format: Option<BookFormat>,
+    pub store: Option<BookStore>,
+}
+
+#[derive(Debug, Clone)]
+pub enum AuthorIdsPatchDto {
+    Replace(Vec<String>),
+    Edit {
+        add: Vec<String>,
+        remove: Vec<String>,
+    },
+}
+
+impl TryFrom<BookPatchDto> for BookPatch {
+    type Error = DomainError;
+
+    fn try_from(patch: BookPatchDto) -> Result<Self, Self::Error> {
+        let author_ids = match patch.author_ids {
+            Some(AuthorIdsPatchDto::Replace(author_ids)) => {
+                Some(AuthorIdsPatch::Replace(parse_author_ids(author_ids)?))
+            }
+            Some(AuthorIdsPatchDto::Edit { add, remove }) => Some(AuthorIdsPatch::Edit {
+                add: parse_author_ids(add)?,
+                remove: parse_author_ids(remove)?,
+            }),
+            None => None,
+        };
+        Ok(BookPatch {
+            title: patch.title.map(BookTitle::new).transpose()?,
+            author_ids,
+            isbn: patch.isbn.map(Isbn::new).transpose()?,
+            read: patch.read.map(ReadFlag::new),
+            owned: patch.owned.map(OwnedFlag::new),
+            priority: patch.priority.map(Priority::new).transpose()?,
+            format: patch.format,
+            store: patch.store,
+        })
+    }
+}
+
+fn parse_author_ids(author_ids: Vec<String>) -> Result<Vec<AuthorId>, DomainError> {
+    author_ids
+        .into_iter()
+        .map(|author_id| AuthorId::try_from(author_id.as_str()))
+        .collect()
+}
+
+#[derive(Debug, Clone)]
+pub enum BookSelectionDto {
+    Ids(Vec<String>),
+    Filter(BookFilterDto),
+}
+
+impl TryFrom<BookSelectionDto> for BookSelection {
+    type Error = DomainError;
+
+    fn try_from(selection: BookSelectionDto) -> Result<Self, Self::Error> {
+        match selection {
+            BookSelectionDto::Ids(book_ids) => {
+                let book_ids: Result<Vec<BookId>, DomainError> = book_ids
+                    .iter()
+                    .map(|book_id| BookId::try_from(book_id.as_str()))
+                    .collect();
+                Ok(BookSelection::Ids(book_ids?))
+            }
+            BookSelectionDto::Filter(filter) => Ok(BookSelection::Filter(filter.try_into()?)),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct ImportBookEntryDto {
     pub title: String,
     pub author_names: Vec<String>,
@@ -170,6 +345,71 @@ pub struct ImportBookEntryDto {
     pub store: BookStore,
 }
 
+impl From<ImportBookEntryDto> for ImportEntry {
+    fn from(entry: ImportBookEntryDto) -> Self {
+        ImportEntry {
+            title: entry.title,
+            author_names: entry.author_names,
+            isbn: entry.isbn,
+            read: entry.read,
+            owned: entry.owned,
+            priority: entry.priority,
+            format: entry.format,
+            store: entry.store,
+        }
+    }
+}
+
+// How an import treats entries that match a book the user already has.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ImportModeDto {
+    // Every entry becomes a new book.
+    #[default]
+    CreateOnly,
+    // Entries update the book with the same ISBN. Entries without one are
+    // created.
+    UpsertByIsbn,
+    // Entries update the book with the same title and set of author names.
+    UpsertByTitleAndAuthors,
+    // Entries matching a book by ISBN, or by title and authors when they have
+    // no ISBN, are skipped.
+    SkipExisting,
+}
+
+impl From<ImportModeDto> for ImportMode {
+    fn from(mode: ImportModeDto) -> Self {
+        match mode {
+            ImportModeDto::CreateOnly => ImportMode::CreateOnly,
+            ImportModeDto::UpsertByIsbn => ImportMode::UpsertByIsbn,
+            ImportModeDto::UpsertByTitleAndAuthors => ImportMode::UpsertByTitleAndAuthors,
+            ImportModeDto::SkipExisting => ImportMode::SkipExisting,
+        }
+    }
+}
+
+impl From<ImportMode> for ImportModeDto {
+    fn from(mode: ImportMode) -> Self {
+        match mode {
+            ImportMode::CreateOnly => ImportModeDto::CreateOnly,
+            ImportMode::UpsertByIsbn => ImportModeDto::UpsertByIsbn,
+            ImportMode::UpsertByTitleAndAuthors => ImportModeDto::UpsertByTitleAndAuthors,
+            ImportMode::SkipExisting => ImportModeDto::SkipExisting,
+        }
+    }
+}
+
+// How an import runs. By default every entry is validated before anything is
+// written and one invalid entry fails the whole import.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ImportOptionsDto {
+    pub mode: ImportModeDto,
+    // Imports the valid entries and reports the invalid ones.
+    pub partial: bool,
+    // Reports what the import would do, including the invalid entries, and
+    // keeps nothing.
+    pub dry_run: bool,
+}
+
 impl UpdateBookDto {
     #[allow(clippy::too_many_arguments)]
     pub fn new(
@@ -193,6 +433,17 @@ impl UpdateBookDto {
             priority,
             format,
             store,
+            expected_version: ExpectedVersionDto::default(),
+        }
+    }
+}
+
+impl PatchBookDto {
+    pub fn new(id: String, patch: BookPatchDto) -> Self {
+        Self {
+            id,
+            patch,
+            expected_version: ExpectedVersionDto::default(),
         }
     }
 }
@@ -204,13 +455,17 @@ mod tests {
 
     use crate::{
         common::types::{BookFormat, BookStore},
-        domain::entity::{
-            author::AuthorId,
-            book::{Book, BookId, BookTitle, Isbn, OwnedFlag, Priority, ReadFlag},
+        domain::{
+            entity::{
+                author::AuthorId,
+                book::{Book, BookId, BookTitle, Isbn, OwnedFlag, Priority, ReadFlag},
+            },
+            error::DomainError,
+            repository::book_repository::BookFilter,
         },
     };
 
-    use super::{BookDto, CreateBookDto, TimeInfo};
+    use super::{BookDto, BookFilterDto, CreateBookDto, TimeInfo};
 
     #[test]
     fn book_dto_from_book_maps_all_fields() {
@@ -325,4 +580,52 @@ mod tests {
         // Then
         assert!(result.is_err());
     }
+
+    #[test]
+    fn book_filter_try_from_dto_parses_author_ids() {
+        // Given
+        let author_id_str = "006099b4-6c42-4ec4-8645-f6bd5b63eddc";
+        let dto = BookFilterDto {
+            read: Some(true),
+            priority_min: Some(10),
+            priority_max: Some(10),
+            author_ids: Some(vec![author_id_str.to_string()]),
+            ..Default::default()
+        };
+
+        // When
+        let filter = BookFilter::try_from(dto).unwrap();
+
+        // Then
+        assert_eq!(filter.read, Some(true));
+        assert_eq!(
+            filter.author_ids,
+            Some(vec![AuthorId::try_from(author_id_str).unwrap()])
+        );
+    }
+
+    #[test]
+    fn book_filter_try_from_dto_rejects_inverted_priority_range() {
+        let dto = BookFilterDto {
+            priority_min: Some(80),
+            priority_max: Some(20),
+            ..Default::default()
+        };
+
+        let result = BookFilter::try_from(dto);
+
+        assert!(matches!(result, Err(DomainError::Validation(_))));
+    }
+
+    #[test]
+    fn book_filter_try_from_dto_rejects_invalid_author_id() {
+        let dto = BookFilterDto {
+            author_ids: Some(vec!["not-a-uuid".to_string()]),
+            ..Default::default()
+        };
+
+        let result = BookFilter::try_from(dto);
+
+        assert!(matches!(result, Err(DomainError::Validation(_))));
+    }
 }
@@ -0,0 +1,49 @@
+use super::{
+    author::{AuthorDto, CreateAuthorDto, UpdateAuthorDto},
+    book::{BookDto, CreateBookDto, UpdateBookDto},
+    mutation::MutationResultDto,
+};
+
+/// One operation of a batch applied in a single transaction. A create may name
+/// a `temp_id`, which later operations of the batch can use in place of the
+/// id of the entity it creates.
+pub enum ChangeOperationDto {
+    CreateBook {
+        temp_id: Option<String>,
+        book: CreateBookDto,
+    },
+    UpdateBook(UpdateBookDto),
+    DeleteBook {
+        id: String,
+    },
+    CreateAuthor {
+        temp_id: Option<String>,
+        author: CreateAuthorDto,
+    },
+    UpdateAuthor(UpdateAuthorDto),
+    DeleteAuthor {
+        id: String,
+    },
+}
+
+/// The outcome of one operation, in the order the operations were given.
+#[derive(Debug)]
+pub enum AppliedChangeDto {
+    /// A created or updated book. `temp_id` is set for a create that named one.
+    Book {
+        temp_id: Option<String>,
+        book: BookDto,
+    },
+    Author {
+        temp_id: Option<String>,
+        author: AuthorDto,
+    },
+    DeletedBook {
+        id: String,
+    },
+    DeletedAuthor {
+        id: String,
+    },
+}
+
+pub type ApplyChangesResultDto = MutationResultDto<Vec<AppliedChangeDto>>;
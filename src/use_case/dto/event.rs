@@ -3,9 +3,29 @@ use time::OffsetDateTime;
 
 use crate::{
     common::types::{BookFormat, BookStore},
-    domain::entity::event::{AuthorEvent, BookEvent},
+    domain::{
+        entity::event::{AuthorEvent, BookEvent, FieldChange},
+        repository::{history::DeletedEntity, trash_repository::PurgedTrash},
+    },
 };
 
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FieldChangeDto {
+    pub field: String,
+    pub old_value: Option<String>,
+    pub new_value: Option<String>,
+}
+
+impl From<FieldChange> for FieldChangeDto {
+    fn from(change: FieldChange) -> Self {
+        Self {
+            field: change.field.to_string(),
+            old_value: change.old_value,
+            new_value: change.new_value,
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct BookEventDto {
     pub event_id: i64,
@@ -24,10 +44,17 @@ pub struct BookEventDto {
     pub book_updated_at: Option<OffsetDateTime>,
     pub changed_at: OffsetDateTime,
     pub extra: Option<Value>,
+    pub changes: Vec<FieldChangeDto>,
 }
 
-impl From<BookEvent> for BookEventDto {
-    fn from(e: BookEvent) -> Self {
+impl BookEventDto {
+    // `previous` is the preceding event of the same book, if any.
+    pub fn new(e: BookEvent, previous: Option<&BookEvent>) -> Self {
+        let changes = e
+            .changes_since(previous)
+            .into_iter()
+            .map(FieldChangeDto::from)
+            .collect();
         Self {
             event_id: e.event_id,
             event_set_id: e.event_set_id.to_string(),
@@ -45,8 +72,21 @@ impl From<BookEvent> for BookEventDto {
             book_updated_at: e.book_updated_at,
             changed_at: e.changed_at,
             extra: e.extra,
+            changes,
         }
     }
+
+    // Converts the whole history of one book, given newest first.
+    pub fn from_history(events: Vec<BookEvent>) -> Vec<Self> {
+        let previous: Vec<Option<BookEvent>> = (0..events.len())
+            .map(|i| events.get(i + 1).cloned())
+            .collect();
+        events
+            .into_iter()
+            .zip(previous)
+            .map(|(event, previous)| Self::new(event, previous.as_ref()))
+            .collect()
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -61,10 +101,17 @@ pub struct AuthorEventDto {
     pub author_updated_at: Option<OffsetDateTime>,
     pub changed_at: OffsetDateTime,
     pub extra: Option<Value>,
+    pub changes: Vec<FieldChangeDto>,
 }
 
-impl From<AuthorEvent> for AuthorEventDto {
-    fn from(e: AuthorEvent) -> Self {
+impl AuthorEventDto {
+    // `previous` is the preceding event of the same author that has state.
+    pub fn new(e: AuthorEvent, previous: Option<&AuthorEvent>) -> Self {
+        let changes = e
+            .changes_since(previous)
+            .into_iter()
+            .map(FieldChangeDto::from)
+            .collect();
         Self {
             event_id: e.event_id,
             event_set_id: e.event_set_id.to_string(),
@@ -76,6 +123,78 @@ impl From<AuthorEvent> for AuthorEventDto {
             author_updated_at: e.author_updated_at,
             changed_at: e.changed_at,
             extra: e.extra,
+            changes,
+        }
+    }
+
+    // Converts the whole history of one author, given newest first.
+    pub fn from_history(events: Vec<AuthorEvent>) -> Vec<Self> {
+        let previous: Vec<Option<AuthorEvent>> = (0..events.len())
+            .map(|i| events[i + 1..].iter().find(|e| e.has_state()).cloned())
+            .collect();
+        events
+            .into_iter()
+            .zip(previous)
+            .map(|(event, previous)| Self::new(event, previous.as_ref()))
+            .collect()
+    }
+}
+
+// A deleted book: its latest event with state, which restoreBook takes, and
+// the event that deleted it.
+#[derive(Debug, Clone)]
+pub struct DeletedBookDto {
+    pub last_known: BookEventDto,
+    pub deletion: BookEventDto,
+}
+
+impl DeletedBookDto {
+    // `previous` is the event preceding the last known one, if any.
+    pub fn new(entity: DeletedEntity<BookEvent>, previous: Option<&BookEvent>) -> Self {
+        let deletion = BookEventDto::new(entity.deletion, Some(&entity.last_known));
+        Self {
+            last_known: BookEventDto::new(entity.last_known, previous),
+            deletion,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct DeletedAuthorDto {
+    pub last_known: AuthorEventDto,
+    pub deletion: AuthorEventDto,
+    // Set when the author was deleted by merging it into another one.
+    pub merged_into_author_id: Option<String>,
+}
+
+impl DeletedAuthorDto {
+    // `previous` is the stateful event preceding the last known one, if any.
+    pub fn new(entity: DeletedEntity<AuthorEvent>, previous: Option<&AuthorEvent>) -> Self {
+        let merged_into_author_id = entity.deletion.merge_destination().map(|a| a.to_string());
+        let deletion = AuthorEventDto::new(entity.deletion, Some(&entity.last_known));
+        Self {
+            last_known: AuthorEventDto::new(entity.last_known, previous),
+            deletion,
+            merged_into_author_id,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PurgedTrashDto {
+    pub book_count: i64,
+    pub author_count: i64,
+    pub event_set_count: i64,
+    pub event_set_id: Option<String>,
+}
+
+impl From<PurgedTrash> for PurgedTrashDto {
+    fn from(purged: PurgedTrash) -> Self {
+        Self {
+            book_count: purged.book_count,
+            author_count: purged.author_count,
+            event_set_count: purged.event_set_count,
+            event_set_id: None,
         }
     }
 }
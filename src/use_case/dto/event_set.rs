@@ -2,7 +2,7 @@ use time::OffsetDateTime;
 
 use crate::{
     domain::entity::event_set::EventSet,
-    use_case::dto::event::{AuthorEventDto, BookEventDto},
+    use_case::dto::event::{AuthorEventDto, BookEventDto, FieldChangeDto},
 };
 
 #[derive(Debug, Clone)]
@@ -46,3 +46,55 @@ impl EventSetDetailDto {
         }
     }
 }
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RevertEntityType {
+    Book,
+    Author,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RevertAction {
+    // Brings back the state the entity had before the event set.
+    Restore,
+    // Deletes an entity the event set created.
+    Remove,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RevertChangeDto {
+    pub entity_type: RevertEntityType,
+    pub entity_id: String,
+    pub action: RevertAction,
+    // From the current state to the reverted one.
+    pub changes: Vec<FieldChangeDto>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RevertConflictReason {
+    // The entity has an event after the event set.
+    ChangedLater { event_id: i64, event_set_id: String },
+    // The author would be removed while books outside the revert still list it.
+    HasAssociatedBooks { book_ids: Vec<String> },
+    // The restored book would list authors that no longer exist.
+    MissingAuthors { author_ids: Vec<String> },
+    // Another author has the name the author would go back to.
+    NameTaken { author_id: String },
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RevertConflictDto {
+    pub entity_type: RevertEntityType,
+    pub entity_id: String,
+    pub reason: RevertConflictReason,
+}
+
+// The planned inverse of an event set. `event_set_id` is set once the changes
+// have been applied, which happens only outside dry runs and without
+// conflicts.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RevertEventSetResultDto {
+    pub event_set_id: Option<String>,
+    pub changes: Vec<RevertChangeDto>,
+    pub conflicts: Vec<RevertConflictDto>,
+}
@@ -0,0 +1,110 @@
+use time::OffsetDateTime;
+
+use crate::domain::{
+    entity::event_set::EventSetId,
+    error::DomainError,
+    repository::history::{EventCursor, EventSetCursor, HistoryFilter},
+};
+
+use super::{author::AuthorDto, book::BookDto};
+
+// A moment in the change history: a timestamp, or the moment an event set
+// was recorded.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PointInTimeDto {
+    Timestamp(OffsetDateTime),
+    EventSet(String),
+}
+
+// Entities present only at the later point, only at the earlier point, and
+// present at both but different, as (before, after) pairs.
+#[derive(Debug)]
+pub struct EntityDiffDto<T> {
+    pub added: Vec<T>,
+    pub removed: Vec<T>,
+    pub changed: Vec<(T, T)>,
+}
+
+#[derive(Debug)]
+pub struct LibraryDiffDto {
+    pub books: EntityDiffDto<BookDto>,
+    pub authors: EntityDiffDto<AuthorDto>,
+}
+
+// Narrows a history listing to operations, given by their stored names, and
+// to a time window where `from` is inclusive and `to` is exclusive.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct HistoryFilterDto {
+    pub operations: Option<Vec<String>>,
+    pub from: Option<OffsetDateTime>,
+    pub to: Option<OffsetDateTime>,
+}
+
+impl HistoryFilterDto {
+    pub fn try_into_filter<O>(self) -> Result<HistoryFilter<O>, DomainError>
+    where
+        O: for<'a> TryFrom<&'a str, Error = String>,
+    {
+        let operations = self
+            .operations
+            .map(|operations| {
+                operations
+                    .iter()
+                    .map(|operation| O::try_from(operation))
+                    .collect::<Result<Vec<_>, _>>()
+                    .map_err(DomainError::Validation)
+            })
+            .transpose()?;
+
+        Ok(HistoryFilter {
+            operations,
+            from: self.from,
+            to: self.to,
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HistoryCursorDto {
+    pub at: OffsetDateTime,
+    pub id: String,
+}
+
+impl From<EventCursor> for HistoryCursorDto {
+    fn from(cursor: EventCursor) -> Self {
+        Self {
+            at: cursor.at,
+            id: cursor.id.to_string(),
+        }
+    }
+}
+
+impl TryFrom<HistoryCursorDto> for EventCursor {
+    type Error = DomainError;
+
+    fn try_from(cursor: HistoryCursorDto) -> Result<Self, Self::Error> {
+        let id = cursor
+            .id
+            .parse()
+            .map_err(|_| DomainError::Validation(format!("Invalid event id: {}.", cursor.id)))?;
+        Ok(EventCursor { at: cursor.at, id })
+    }
+}
+
+impl From<EventSetCursor> for HistoryCursorDto {
+    fn from(cursor: EventSetCursor) -> Self {
+        Self {
+            at: cursor.at,
+            id: cursor.id.to_string(),
+        }
+    }
+}
+
+impl TryFrom<HistoryCursorDto> for EventSetCursor {
+    type Error = DomainError;
+
+    fn try_from(cursor: HistoryCursorDto) -> Result<Self, Self::Error> {
+        let id = EventSetId::try_from(cursor.id.as_str()).map_err(DomainError::Validation)?;
+        Ok(EventSetCursor { at: cursor.at, id })
+    }
+}
@@ -0,0 +1,79 @@
+use time::OffsetDateTime;
+
+use crate::{
+    domain::entity::import_job::{ImportJob, ImportJobStatus},
+    use_case::dto::{book::ImportModeDto, mutation::ImportBookErrorDto},
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImportJobStatusDto {
+    Pending,
+    Running,
+    Succeeded,
+    Failed,
+    Cancelled,
+}
+
+impl From<ImportJobStatus> for ImportJobStatusDto {
+    fn from(status: ImportJobStatus) -> Self {
+        match status {
+            ImportJobStatus::Pending => ImportJobStatusDto::Pending,
+            ImportJobStatus::Running => ImportJobStatusDto::Running,
+            ImportJobStatus::Succeeded => ImportJobStatusDto::Succeeded,
+            ImportJobStatus::Failed => ImportJobStatusDto::Failed,
+            ImportJobStatus::Cancelled => ImportJobStatusDto::Cancelled,
+        }
+    }
+}
+
+// How a background import runs. Unlike `ImportOptionsDto` it has no dry run,
+// since a job always keeps what it writes.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ImportJobOptionsDto {
+    pub mode: ImportModeDto,
+    // Imports the valid entries and reports the invalid ones. Otherwise every
+    // entry is validated when the job is started.
+    pub partial: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ImportJobDto {
+    pub id: String,
+    pub status: ImportJobStatusDto,
+    pub mode: ImportModeDto,
+    pub partial: bool,
+    pub total_count: usize,
+    pub processed_count: usize,
+    pub failures: Vec<ImportBookErrorDto>,
+    // One event set per chunk that wrote anything, oldest first.
+    pub event_set_ids: Vec<String>,
+    pub error: Option<String>,
+    pub created_at: OffsetDateTime,
+    pub updated_at: OffsetDateTime,
+}
+
+impl From<ImportJob> for ImportJobDto {
+    fn from(job: ImportJob) -> Self {
+        Self {
+            id: job.id.to_string(),
+            status: job.status.into(),
+            mode: job.mode.into(),
+            partial: job.partial,
+            total_count: job.total_count,
+            processed_count: job.processed_count,
+            failures: job
+                .failures
+                .into_iter()
+                .map(|failure| ImportBookErrorDto {
+                    index: failure.index,
+                    field: failure.field.into(),
+                    message: failure.message,
+                })
+                .collect(),
+            event_set_ids: job.event_set_ids.iter().map(ToString::to_string).collect(),
+            error: job.error,
+            created_at: job.created_at,
+            updated_at: job.updated_at,
+        }
+    }
+}
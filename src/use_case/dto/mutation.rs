@@ -1,5 +1,5 @@
 use super::{author::AuthorDto, book::BookDto};
-use crate::domain::entity::event::EventId;
+use crate::domain::entity::{event::EventId, import_job::ImportField};
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct MutationResultDto<T> {
@@ -50,10 +50,149 @@ impl<T> std::ops::Deref for SingleEventMutationResultDto<T> {
     }
 }
 
+/// A restored book and the authors that had to be resolved for it. `book` is
+/// None when the restored state is a deletion.
+#[derive(Debug)]
+pub struct RestoredBookDto {
+    pub book: Option<BookDto>,
+    pub restored_authors: Vec<AuthorDto>,
+    pub remapped_authors: Vec<AuthorRemapDto>,
+}
+
+impl RestoredBookDto {
+    pub fn new(book: Option<BookDto>) -> Self {
+        Self {
+            book,
+            restored_authors: vec![],
+            remapped_authors: vec![],
+        }
+    }
+}
+
+/// An author merged away since the restored state, replaced by `author`.
+#[derive(Debug)]
+pub struct AuthorRemapDto {
+    pub merged_author_id: String,
+    pub author: AuthorDto,
+}
+
+/// The version of an entity an update was based on: the id of its latest
+/// event, which every write changes. When set, it must still be the latest
+/// for the update to go through.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ExpectedVersionDto {
+    pub event_id: Option<i64>,
+}
+
+impl ExpectedVersionDto {
+    pub fn is_set(&self) -> bool {
+        self.event_id.is_some()
+    }
+
+    pub fn matches(&self, latest_event_id: Option<i64>) -> bool {
+        self.event_id
+            .is_none_or(|expected| Some(expected) == latest_event_id)
+    }
+}
+
+/// What is stored for an entity an update conflicted with, along with its
+/// latest event, so that the client can merge and retry. A bulk deletion of
+/// authors instead reports every author that still has books.
+#[derive(Debug)]
+pub enum CurrentStateDto {
+    Book {
+        book: BookDto,
+        event_id: Option<i64>,
+    },
+    Author {
+        author: AuthorDto,
+        event_id: Option<i64>,
+    },
+    AuthorsWithBooks(Vec<AuthorWithBooksDto>),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AuthorWithBooksDto {
+    pub author_id: String,
+    pub book_ids: Vec<String>,
+}
+
+/// Outcome of a bulk update. `event_set_id` is None when no book changed,
+/// since nothing is recorded then.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BulkUpdateBooksResultDto {
+    pub updated_count: i64,
+    pub event_set_id: Option<String>,
+}
+
+/// Outcome of an import: the books it created, the existing books it updated
+/// and the existing books entries were skipped for, along with the names of
+/// the authors it created and of those it found. `event_set_id` is None when
+/// nothing was recorded, as on dry runs or when every entry was skipped.
+#[derive(Debug)]
+pub struct ImportBooksResultDto {
+    pub created: Vec<BookDto>,
+    pub updated: Vec<BookDto>,
+    pub skipped: Vec<BookDto>,
+    pub new_author_names: Vec<String>,
+    pub reused_author_names: Vec<String>,
+    /// Entries a dry run or partial import found invalid. Otherwise an
+    /// invalid entry fails the whole import instead.
+    pub errors: Vec<ImportBookErrorDto>,
+    pub event_set_id: Option<String>,
+}
+
+/// Why the entry at `index` of an import could not be imported.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ImportBookErrorDto {
+    pub index: usize,
+    pub field: ImportBookFieldDto,
+    pub message: String,
+}
+
+/// The field of an import entry that failed validation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImportBookFieldDto {
+    Title,
+    AuthorNames,
+    Isbn,
+    Priority,
+}
+
+impl From<ImportField> for ImportBookFieldDto {
+    fn from(field: ImportField) -> Self {
+        match field {
+            ImportField::Title => ImportBookFieldDto::Title,
+            ImportField::AuthorNames => ImportBookFieldDto::AuthorNames,
+            ImportField::Isbn => ImportBookFieldDto::Isbn,
+            ImportField::Priority => ImportBookFieldDto::Priority,
+        }
+    }
+}
+
 pub type BookMutationResultDto = SingleEventMutationResultDto<BookDto>;
 pub type AuthorMutationResultDto = SingleEventMutationResultDto<AuthorDto>;
 pub type DeleteBookResultDto = MutationResultDto<String>;
 pub type DeleteAuthorResultDto = MutationResultDto<String>;
-pub type ImportBooksResultDto = MutationResultDto<Vec<BookDto>>;
-pub type RestoreBookResultDto = MutationResultDto<Option<BookDto>>;
+pub type DeleteBooksResultDto = MutationResultDto<Vec<String>>;
+pub type DeleteAuthorsResultDto = MutationResultDto<Vec<String>>;
+pub type RestoreBookResultDto = MutationResultDto<RestoredBookDto>;
 pub type RestoreAuthorResultDto = MutationResultDto<Option<AuthorDto>>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn expected_version_matches_only_the_latest_event() {
+        assert!(ExpectedVersionDto::default().matches(None));
+        for (expected, latest, matches) in
+            [(7, Some(7), true), (6, Some(7), false), (7, None, false)]
+        {
+            let version = ExpectedVersionDto {
+                event_id: Some(expected),
+            };
+            assert_eq!(version.matches(latest), matches, "{version:?} {latest:?}");
+        }
+    }
+}
@@ -0,0 +1,164 @@
+use crate::{
+    domain::{
+        error::DomainError,
+        repository::pagination::{Page, PageDirection, PageRequest},
+    },
+    use_case::error::UseCaseError,
+};
+
+pub const DEFAULT_PAGE_SIZE: i32 = 50;
+pub const MAX_PAGE_SIZE: i32 = 100;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PageRequestDto<C> {
+    pub first: Option<i32>,
+    pub after: Option<C>,
+    pub last: Option<i32>,
+    pub before: Option<C>,
+}
+
+impl<C> PageRequestDto<C> {
+    pub fn first(first: i32) -> Self {
+        Self {
+            first: Some(first),
+            after: None,
+            last: None,
+            before: None,
+        }
+    }
+
+    pub fn try_into_page_request<D>(
+        self,
+        convert_cursor: impl Fn(C) -> Result<D, DomainError>,
+    ) -> Result<PageRequest<D>, UseCaseError> {
+        let (limit, direction) = match (self.first, self.last) {
+            (Some(_), Some(_)) => {
+                return Err(UseCaseError::Validation(
+                    "first and last cannot be specified together.".to_string(),
+                ));
+            }
+            (Some(first), None) => (first, PageDirection::Forward),
+            (None, Some(last)) => (last, PageDirection::Backward),
+            (None, None) => (DEFAULT_PAGE_SIZE, PageDirection::Forward),
+        };
+        if !(0..=MAX_PAGE_SIZE).contains(&limit) {
+            return Err(UseCaseError::Validation(format!(
+                "page size must be between 0 and {MAX_PAGE_SIZE}, but was {limit}."
+            )));
+        }
+
+        Ok(PageRequest {
+            after: self.after.map(&convert_cursor).transpose()?,
+            before: self.before.map(&convert_cursor).transpose()?,
+            limit: limit as usize,
+            direction,
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EdgeDto<T, C> {
+    pub node: T,
+    pub cursor: C,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PageDto<T, C> {
+    pub edges: Vec<EdgeDto<T, C>>,
+    pub has_previous_page: bool,
+    pub has_next_page: bool,
+    pub total_count: i64,
+}
+
+impl<T, C> PageDto<T, C> {
+    // Converts a page whose nodes need more than `From` to become DTOs.
+    pub fn map_from<U, D>(page: Page<U, D>, mut node: impl FnMut(U) -> T) -> Self
+    where
+        C: From<D>,
+    {
+        Self {
+            edges: page
+                .edges
+                .into_iter()
+                .map(|edge| EdgeDto {
+                    node: node(edge.node),
+                    cursor: C::from(edge.cursor),
+                })
+                .collect(),
+            has_previous_page: page.has_previous_page,
+            has_next_page: page.has_next_page,
+            total_count: page.total_count,
+        }
+    }
+}
+
+impl<T, C, U, D> From<Page<U, D>> for PageDto<T, C>
+where
+    T: From<U>,
+    C: From<D>,
+{
+    fn from(page: Page<U, D>) -> Self {
+        Self::map_from(page, T::from)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{domain::repository::pagination::PageDirection, use_case::error::UseCaseError};
+
+    use super::{DEFAULT_PAGE_SIZE, PageRequestDto};
+
+    #[test]
+    fn defaults_to_forward_page_when_no_size_is_given() {
+        let dto: PageRequestDto<i32> = PageRequestDto {
+            first: None,
+            after: Some(1),
+            last: None,
+            before: None,
+        };
+
+        let request = dto.try_into_page_request(Ok).unwrap();
+
+        assert_eq!(request.limit, DEFAULT_PAGE_SIZE as usize);
+        assert_eq!(request.direction, PageDirection::Forward);
+        assert_eq!(request.after, Some(1));
+    }
+
+    #[test]
+    fn last_makes_a_backward_page() {
+        let dto: PageRequestDto<i32> = PageRequestDto {
+            first: None,
+            after: None,
+            last: Some(10),
+            before: Some(3),
+        };
+
+        let request = dto.try_into_page_request(Ok).unwrap();
+
+        assert_eq!(request.limit, 10);
+        assert_eq!(request.direction, PageDirection::Backward);
+        assert_eq!(request.before, Some(3));
+    }
+
+    #[test]
+    fn rejects_first_and_last_together() {
+        let dto: PageRequestDto<i32> = PageRequestDto {
+            first: Some(1),
+            after: None,
+            last: Some(1),
+            before: None,
+        };
+
+        let result = dto.try_into_page_request(Ok);
+
+        assert!(matches!(result, Err(UseCaseError::Validation(_))));
+    }
+
+    #[test]
+    fn rejects_out_of_range_page_sizes() {
+        for size in [-1, 101] {
+            let result = PageRequestDto::<i32>::first(size).try_into_page_request(Ok);
+            assert!(matches!(result, Err(UseCaseError::Validation(_))));
+        }
+    }
+}
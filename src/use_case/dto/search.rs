@@ -0,0 +1,11 @@
+use super::{author::AuthorDto, book::BookDto};
+
+pub const DEFAULT_SEARCH_LIMIT: i32 = 20;
+pub const MAX_SEARCH_LIMIT: i32 = 100;
+
+// Books and authors matching a search query, each best match first.
+#[derive(Debug)]
+pub struct SearchResultDto {
+    pub books: Vec<BookDto>,
+    pub authors: Vec<AuthorDto>,
+}
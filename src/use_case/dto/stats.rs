@@ -0,0 +1,61 @@
+use crate::{
+    common::types::{BookFormat, BookStore},
+    domain::repository::book_repository::{LibraryStats, PriorityBucket},
+};
+
+use super::author::AuthorDto;
+
+pub const DEFAULT_TOP_AUTHORS: i32 = 10;
+pub const MAX_TOP_AUTHORS: i32 = 100;
+
+#[derive(Debug)]
+pub struct LibraryStatsDto {
+    pub total_count: i64,
+    pub read_count: i64,
+    pub owned_count: i64,
+    pub unread_owned_count: i64,
+    pub format_counts: Vec<(BookFormat, i64)>,
+    pub store_counts: Vec<(BookStore, i64)>,
+    pub priority_histogram: Vec<PriorityBucketDto>,
+    pub top_authors: Vec<(AuthorDto, i64)>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PriorityBucketDto {
+    pub min: i32,
+    pub max: i32,
+    pub count: i64,
+}
+
+impl From<PriorityBucket> for PriorityBucketDto {
+    fn from(bucket: PriorityBucket) -> Self {
+        Self {
+            min: bucket.min,
+            max: bucket.max,
+            count: bucket.count,
+        }
+    }
+}
+
+impl From<LibraryStats> for LibraryStatsDto {
+    fn from(stats: LibraryStats) -> Self {
+        Self {
+            total_count: stats.total_count,
+            read_count: stats.read_count,
+            owned_count: stats.owned_count,
+            unread_owned_count: stats.unread_owned_count,
+            format_counts: stats.format_counts,
+            store_counts: stats.store_counts,
+            priority_histogram: stats
+                .priority_histogram
+                .into_iter()
+                .map(PriorityBucketDto::from)
+                .collect(),
+            top_authors: stats
+                .top_authors
+                .into_iter()
+                .map(|(author, count)| (AuthorDto::from(author), count))
+                .collect(),
+        }
+    }
+}
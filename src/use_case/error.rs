@@ -1,6 +1,6 @@
 use thiserror::Error;
 
-use crate::domain::error::DomainError;
+use crate::{domain::error::DomainError, use_case::dto::mutation::CurrentStateDto};
 
 #[derive(Debug, Error)]
 pub enum UseCaseError {
@@ -12,8 +12,12 @@ pub enum UseCaseError {
         entity_id: String,
         user_id: String,
     },
-    #[error("{0}")]
-    Conflict(String),
+    #[error("{message}")]
+    Conflict {
+        message: String,
+        // The stored state, when the conflict is with a concurrent change.
+        current: Option<Box<CurrentStateDto>>,
+    },
     #[error(transparent)]
     Other(anyhow::Error),
     #[error("{0}")]
@@ -33,7 +37,10 @@ impl From<DomainError> for UseCaseError {
                 entity_id,
                 user_id,
             },
-            DomainError::HasAssociatedBooks { .. } => UseCaseError::Conflict(err.to_string()),
+            DomainError::HasAssociatedBooks { .. } => UseCaseError::Conflict {
+                message: err.to_string(),
+                current: None,
+            },
             DomainError::InfrastructureError(_) => UseCaseError::Other(anyhow::Error::new(err)),
             DomainError::Unexpected(message) => UseCaseError::Unexpected(message),
         }
@@ -71,7 +78,7 @@ mod tests {
             user_id: "user1".to_string(),
         };
         let use_case_err = UseCaseError::from(domain_err);
-        assert!(matches!(use_case_err, UseCaseError::Conflict(_)));
+        assert!(matches!(use_case_err, UseCaseError::Conflict { .. }));
     }
 
     #[test]
@@ -1,3 +1,5 @@
+use std::collections::HashSet;
+
 use async_trait::async_trait;
 use time::OffsetDateTime;
 use uuid::Uuid;
@@ -6,10 +8,11 @@ use crate::{
     domain::{
         entity::{
             author::{Author, AuthorId, AuthorName, AuthorUpdate, validate_author_yomi},
-            book::BookUpdate,
+            book::{BookId, BookUpdate},
             event::{EventSetOperation, NewAuthorEvent},
             user::UserId,
         },
+        error::DomainError,
         repository::{
             author_event_repository::AuthorEventRepository,
             author_repository::{AuthorRepository, DeleteAuthorEventExtra},
@@ -19,10 +22,13 @@ use crate::{
     },
     use_case::{
         dto::{
-            author::{AuthorDto, CreateAuthorDto, MergeAuthorInputDto, UpdateAuthorDto},
+            author::{
+                AuthorDto, CreateAuthorDto, MergeAuthorInputDto, PatchAuthorDto, UpdateAuthorDto,
+            },
             mutation::{
-                AuthorMutationResultDto, DeleteAuthorResultDto, MutationResultDto,
-                SingleEventMutationResultDto,
+                AuthorMutationResultDto, AuthorWithBooksDto, CurrentStateDto,
+                DeleteAuthorResultDto, DeleteAuthorsResultDto, ExpectedVersionDto,
+                MutationResultDto, SingleEventMutationResultDto,
             },
         },
         error::UseCaseError,
@@ -32,6 +38,8 @@ use crate::{
     },
 };
 
+const MAX_AUTHOR_BATCH: usize = 1000;
+
 pub struct CreateAuthorInteractor<AR, TM> {
     author_repository: AR,
     transaction_manager: TM,
@@ -238,48 +246,33 @@ impl<AR, TM> UpdateAuthorInteractor<AR, TM> {
     }
 }
 
-#[async_trait]
-impl<AR, TM> UpdateAuthorUseCase for UpdateAuthorInteractor<AR, TM>
+impl<AR, TM> UpdateAuthorInteractor<AR, TM>
 where
     TM: TransactionManager,
     AR: AuthorRepository<Transaction = TM::Transaction>,
 {
-    async fn update(
+    // Applies `change` to the stored author and records it as one update event.
+    async fn save_change(
         &self,
-        user_id: &str,
-        author_data: UpdateAuthorDto,
+        user_id: UserId,
+        author_id: AuthorId,
+        expected_version: ExpectedVersionDto,
+        change: impl FnOnce(&mut Author, OffsetDateTime) + Send,
     ) -> Result<AuthorMutationResultDto, UseCaseError> {
-        let user_id = UserId::new(user_id.to_string())?;
-        let author_id = AuthorId::try_from(author_data.id.as_str())?;
-        let author_name = AuthorName::new(author_data.name)?;
-        let yomi = author_data.yomi.map(validate_author_yomi).transpose()?;
-
         let mut tx = self
             .transaction_manager
             .begin(&user_id, EventSetOperation::UpdateAuthor)
             .await?;
-        let author = self
-            .author_repository
-            .find_by_id_with_tx(&mut tx, &user_id, &author_id)
-            .await?;
-        let mut author = match author {
-            Some(author) => author,
-            None => {
-                return Err(UseCaseError::NotFound {
-                    entity_type: "author",
-                    entity_id: author_data.id,
-                    user_id: user_id.into_string(),
-                });
-            }
-        };
+        let mut author = find_author_for_update(
+            &self.author_repository,
+            &mut tx,
+            &user_id,
+            &author_id,
+            expected_version,
+        )
+        .await?;
 
-        author.update(
-            AuthorUpdate {
-                name: author_name,
-                yomi,
-            },
-            OffsetDateTime::now_utc(),
-        );
+        change(&mut author, OffsetDateTime::now_utc());
 
         let event_id = self.author_repository.update(&mut tx, &author).await?;
         let event_set_id = tx.event_set_id().hyphenated().to_string();
@@ -293,6 +286,102 @@ where
     }
 }
 
+#[async_trait]
+impl<AR, TM> UpdateAuthorUseCase for UpdateAuthorInteractor<AR, TM>
+where
+    TM: TransactionManager,
+    AR: AuthorRepository<Transaction = TM::Transaction>,
+{
+    async fn update(
+        &self,
+        user_id: &str,
+        author_data: UpdateAuthorDto,
+    ) -> Result<AuthorMutationResultDto, UseCaseError> {
+        let user_id = UserId::new(user_id.to_string())?;
+        let (author_id, update, expected_version) = validate_author_update(author_data)?;
+
+        self.save_change(user_id, author_id, expected_version, |author, now| {
+            author.update(update, now)
+        })
+        .await
+    }
+
+    async fn patch(
+        &self,
+        user_id: &str,
+        author_data: PatchAuthorDto,
+    ) -> Result<AuthorMutationResultDto, UseCaseError> {
+        let user_id = UserId::new(user_id.to_string())?;
+        let author_id = AuthorId::try_from(author_data.id.as_str())?;
+        let name = author_data.name.map(AuthorName::new).transpose()?;
+        let yomi = author_data.yomi.map(validate_author_yomi).transpose()?;
+
+        self.save_change(
+            user_id,
+            author_id,
+            author_data.expected_version,
+            |author, now| {
+                let update = AuthorUpdate {
+                    name: name.unwrap_or_else(|| author.name().clone()),
+                    yomi,
+                };
+                author.update(update, now)
+            },
+        )
+        .await
+    }
+}
+
+// Splits an update into the author it targets, the validated new state and
+// the version the caller based it on.
+pub(super) fn validate_author_update(
+    author_data: UpdateAuthorDto,
+) -> Result<(AuthorId, AuthorUpdate, ExpectedVersionDto), UseCaseError> {
+    let author_id = AuthorId::try_from(author_data.id.as_str())?;
+    let update = AuthorUpdate {
+        name: AuthorName::new(author_data.name)?,
+        yomi: author_data.yomi.map(validate_author_yomi).transpose()?,
+    };
+    Ok((author_id, update, author_data.expected_version))
+}
+
+// Loads an author about to be changed within `tx`, failing if it does not
+// exist or no longer is at `expected_version`.
+pub(super) async fn find_author_for_update<AR: AuthorRepository>(
+    author_repository: &AR,
+    tx: &mut AR::Transaction,
+    user_id: &UserId,
+    author_id: &AuthorId,
+    expected_version: ExpectedVersionDto,
+) -> Result<Author, UseCaseError> {
+    let Some(author) = author_repository
+        .find_by_id_with_tx(tx, user_id, author_id)
+        .await?
+    else {
+        return Err(UseCaseError::NotFound {
+            entity_type: "author",
+            entity_id: author_id.to_string(),
+            user_id: user_id.as_str().to_string(),
+        });
+    };
+    // The row is locked by now, so the check holds until commit.
+    if expected_version.is_set() {
+        let latest_event_id = author_repository
+            .find_latest_event_id_with_tx(tx, user_id, author_id)
+            .await?;
+        if !expected_version.matches(latest_event_id) {
+            return Err(UseCaseError::Conflict {
+                message: format!("author {author_id} has changed since the expected version."),
+                current: Some(Box::new(CurrentStateDto::Author {
+                    author: author.into(),
+                    event_id: latest_event_id,
+                })),
+            });
+        }
+    }
+    Ok(author)
+}
+
 pub struct DeleteAuthorInteractor<AR, TM> {
     author_repository: AR,
     transaction_manager: TM,
@@ -334,6 +423,84 @@ where
 
         Ok(MutationResultDto::new(author_id_value, event_set_id))
     }
+
+    async fn delete_many(
+        &self,
+        user_id: &str,
+        author_ids: Vec<String>,
+    ) -> Result<DeleteAuthorsResultDto, UseCaseError> {
+        let user_id = UserId::new(user_id.to_string())?;
+        let mut author_ids = author_ids
+            .iter()
+            .map(|author_id| AuthorId::try_from(author_id.as_str()))
+            .collect::<Result<Vec<AuthorId>, DomainError>>()?;
+        let mut seen = HashSet::new();
+        author_ids.retain(|author_id| seen.insert(author_id.clone()));
+        if author_ids.is_empty() {
+            return Err(UseCaseError::Validation(
+                "author ids cannot be empty".to_string(),
+            ));
+        }
+        if author_ids.len() > MAX_AUTHOR_BATCH {
+            return Err(UseCaseError::Validation(format!(
+                "author ids cannot exceed {MAX_AUTHOR_BATCH}"
+            )));
+        }
+
+        let mut tx = self
+            .transaction_manager
+            .begin(&user_id, EventSetOperation::DeleteAuthors)
+            .await?;
+        let locked = self
+            .author_repository
+            .lock_with_book_ids(&mut tx, &user_id, &author_ids)
+            .await?;
+        if let Some(missing) = author_ids
+            .iter()
+            .find(|author_id| !locked.iter().any(|(locked_id, _)| locked_id == *author_id))
+        {
+            return Err(UseCaseError::NotFound {
+                entity_type: "author",
+                entity_id: missing.to_string(),
+                user_id: user_id.into_string(),
+            });
+        }
+        // Every author that still has books is reported, not just the first.
+        let blocking: Vec<AuthorWithBooksDto> = locked
+            .into_iter()
+            .filter(|(_, book_ids)| !book_ids.is_empty())
+            .map(|(author_id, book_ids)| AuthorWithBooksDto {
+                author_id: author_id.to_string(),
+                book_ids: book_ids.iter().map(BookId::to_string).collect(),
+            })
+            .collect();
+        if !blocking.is_empty() {
+            let blocking_ids: Vec<&str> = blocking
+                .iter()
+                .map(|author| author.author_id.as_str())
+                .collect();
+            return Err(UseCaseError::Conflict {
+                message: format!(
+                    "authors have associated books and cannot be deleted: {}.",
+                    blocking_ids.join(", ")
+                ),
+                current: Some(Box::new(CurrentStateDto::AuthorsWithBooks(blocking))),
+            });
+        }
+
+        for author_id in &author_ids {
+            self.author_repository
+                .delete(&mut tx, author_id, None)
+                .await?;
+        }
+        let event_set_id = tx.event_set_id().hyphenated().to_string();
+        self.transaction_manager.commit(tx).await?;
+
+        Ok(MutationResultDto::new(
+            author_ids.iter().map(AuthorId::to_string).collect(),
+            event_set_id,
+        ))
+    }
 }
 
 #[cfg(test)]
@@ -351,7 +518,7 @@ mod tests {
             entity::{
                 author::{Author, AuthorId, AuthorName},
                 book::{Book, BookId, BookTitle, Isbn, OwnedFlag, Priority, ReadFlag},
-                event::EventOperation,
+                event::{EventOperation, EventSetOperation},
             },
             error::DomainError,
             repository::{
@@ -362,7 +529,10 @@ mod tests {
             },
         },
         use_case::{
-            dto::author::{CreateAuthorDto, UpdateAuthorDto},
+            dto::{
+                author::{CreateAuthorDto, PatchAuthorDto, UpdateAuthorDto},
+                mutation::{CurrentStateDto, ExpectedVersionDto},
+            },
             error::UseCaseError,
             interactor::author::{
                 CreateAuthorInteractor, DeleteAuthorInteractor, MergeAuthorInteractor,
@@ -540,6 +710,94 @@ mod tests {
         assert_eq!(updated.event_id.value(), 404);
     }
 
+    #[tokio::test]
+    async fn update_author_rejects_a_stale_expected_event() {
+        // Given: the author was updated after the client read it
+        let author_id_str = "006099b4-6c42-4ec4-8645-f6bd5b63eddc";
+        let updated_at = OffsetDateTime::from_unix_timestamp(1_700_000_000).unwrap();
+        let existing_author = Author::new_with_timestamps(
+            AuthorId::try_from(author_id_str).unwrap(),
+            AuthorName::new("Current Name".to_string()).unwrap(),
+            "".to_string(),
+            updated_at,
+            updated_at,
+        )
+        .unwrap();
+
+        let mut author_repository = MockAuthorRepository::new();
+        author_repository
+            .expect_find_by_id_with_tx()
+            .return_once(move |_, _, _| Ok(Some(existing_author)));
+        author_repository
+            .expect_find_latest_event_id_with_tx()
+            .returning(|_, _, _| Ok(Some(5)));
+        author_repository.expect_update().times(0);
+
+        let mut tm = MockTransactionManager::new();
+        tm.expect_begin().returning(|_, _| Ok(()));
+        tm.expect_commit().times(0);
+        let interactor = UpdateAuthorInteractor::new(author_repository, tm);
+        let mut author_data =
+            UpdateAuthorDto::new(author_id_str.to_string(), "New Name".to_string());
+        author_data.expected_version = ExpectedVersionDto { event_id: Some(4) };
+
+        // When
+        let result = interactor.update("user1", author_data).await;
+
+        // Then
+        let Err(UseCaseError::Conflict {
+            current: Some(current),
+            ..
+        }) = result
+        else {
+            panic!("expected a conflict with the current state");
+        };
+        let CurrentStateDto::Author { author, event_id } = *current else {
+            panic!("expected the current author");
+        };
+        assert_eq!(author.name, "Current Name");
+        assert_eq!(event_id, Some(5));
+    }
+
+    #[tokio::test]
+    async fn patch_author_keeps_the_name_when_only_yomi_is_given() {
+        // Given
+        let author_id_str = "006099b4-6c42-4ec4-8645-f6bd5b63eddc";
+        let existing_author = Author::new(
+            AuthorId::try_from(author_id_str).unwrap(),
+            AuthorName::new("Stored Name".to_string()).unwrap(),
+            OffsetDateTime::UNIX_EPOCH,
+        )
+        .unwrap();
+
+        let mut author_repository = MockAuthorRepository::new();
+        author_repository
+            .expect_find_by_id_with_tx()
+            .return_once(move |_, _, _| Ok(Some(existing_author)));
+        author_repository
+            .expect_update()
+            .withf(|_, author| author.name().as_str() == "Stored Name" && author.yomi() == "よみ")
+            .returning(|_, _| Ok(405.into()));
+
+        let mut tm = MockTransactionManager::new();
+        tm.expect_begin().returning(|_, _| Ok(()));
+        tm.expect_commit().returning(|_| Ok(()));
+        let interactor = UpdateAuthorInteractor::new(author_repository, tm);
+        let author_data = PatchAuthorDto {
+            yomi: Some("よみ".to_string()),
+            ..PatchAuthorDto::new(author_id_str.to_string())
+        };
+
+        // When
+        let result = interactor.patch("user1", author_data).await;
+
+        // Then
+        let result = result.unwrap();
+        assert_eq!(result.name, "Stored Name");
+        assert_eq!(result.yomi, "よみ");
+        assert_eq!(result.event_id.value(), 405);
+    }
+
     #[tokio::test]
     async fn update_author_commit_failure_returns_no_result() {
         let author_id_str = "006099b4-6c42-4ec4-8645-f6bd5b63eddc";
@@ -783,7 +1041,7 @@ mod tests {
         let result = interactor.delete("user1", author_id_str).await;
 
         // Then
-        assert!(matches!(result, Err(UseCaseError::Conflict(_))));
+        assert!(matches!(result, Err(UseCaseError::Conflict { .. })));
     }
 
     #[tokio::test]
@@ -815,6 +1073,121 @@ mod tests {
         assert!(matches!(result, Err(UseCaseError::Validation(_))));
     }
 
+    #[tokio::test]
+    async fn delete_many_deletes_each_author_in_one_event_set() {
+        // Given
+        let first_id = "006099b4-6c42-4ec4-8645-f6bd5b63eddc";
+        let second_id = "7b1f6a0e-3c2d-4e5f-8a9b-0c1d2e3f4a5b";
+
+        let mut author_repository = MockAuthorRepository::new();
+        author_repository
+            .expect_lock_with_book_ids()
+            .withf(|_, _, author_ids| author_ids.len() == 2)
+            .returning(|_, _, author_ids| {
+                Ok(author_ids
+                    .iter()
+                    .map(|author_id| (author_id.clone(), Vec::new()))
+                    .collect())
+            });
+        author_repository
+            .expect_delete()
+            .times(2)
+            .returning(|_, _, _| Ok(()));
+
+        let mut transaction_manager = MockTransactionManager::new();
+        transaction_manager
+            .expect_begin()
+            .withf(|_, operation| *operation == EventSetOperation::DeleteAuthors)
+            .times(1)
+            .returning(|_, _| Ok(()));
+        transaction_manager
+            .expect_commit()
+            .times(1)
+            .returning(|_| Ok(()));
+
+        let interactor = DeleteAuthorInteractor::new(author_repository, transaction_manager);
+
+        // When
+        let result = interactor
+            .delete_many(
+                "user1",
+                vec![
+                    first_id.to_string(),
+                    second_id.to_string(),
+                    first_id.to_string(),
+                ],
+            )
+            .await;
+
+        // Then
+        assert_eq!(result.unwrap().value, vec![first_id, second_id]);
+    }
+
+    #[tokio::test]
+    async fn delete_many_reports_every_blocking_author() {
+        // Given
+        let first_id = "006099b4-6c42-4ec4-8645-f6bd5b63eddc";
+        let second_id = "7b1f6a0e-3c2d-4e5f-8a9b-0c1d2e3f4a5b";
+        let book_id = "a1b2c3d4-e5f6-4890-abcd-ef1234567890";
+
+        let mut author_repository = MockAuthorRepository::new();
+        author_repository
+            .expect_lock_with_book_ids()
+            .returning(move |_, _, author_ids| {
+                Ok(author_ids
+                    .iter()
+                    .map(|author_id| (author_id.clone(), vec![BookId::try_from(book_id).unwrap()]))
+                    .collect())
+            });
+        author_repository.expect_delete().never();
+
+        let mut transaction_manager = MockTransactionManager::new();
+        transaction_manager.expect_begin().returning(|_, _| Ok(()));
+        transaction_manager.expect_commit().never();
+
+        let interactor = DeleteAuthorInteractor::new(author_repository, transaction_manager);
+
+        // When
+        let result = interactor
+            .delete_many("user1", vec![first_id.to_string(), second_id.to_string()])
+            .await;
+
+        // Then
+        let Err(UseCaseError::Conflict {
+            current: Some(current),
+            ..
+        }) = result
+        else {
+            panic!("expected a conflict, got {result:?}");
+        };
+        let CurrentStateDto::AuthorsWithBooks(blocking) = *current else {
+            panic!("expected the blocking authors, got {current:?}");
+        };
+        let blocking_ids: Vec<&str> = blocking
+            .iter()
+            .map(|author| author.author_id.as_str())
+            .collect();
+        assert_eq!(blocking_ids, vec![first_id, second_id]);
+        assert!(
+            blocking
+                .iter()
+                .all(|author| author.book_ids == vec![book_id])
+        );
+    }
+
+    #[tokio::test]
+    async fn delete_many_rejects_an_empty_list() {
+        // Given
+        let interactor =
+            DeleteAuthorInteractor::new(MockAuthorRepository::new(), MockTransactionManager::new());
+
+        // When
+        let result = interactor.delete_many("user1", Vec::new()).await;
+
+        // Then
+        assert!(matches!(result, Err(UseCaseError::Validation(_))));
+    }
+
     #[tokio::test]
     async fn merge_author_rejects_identical_ids_before_transaction() {
         let id = "006099b4-6c42-4ec4-8645-f6bd5b63eddc";
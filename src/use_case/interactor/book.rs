@@ -1,4 +1,4 @@
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeSet, HashMap, HashSet};
 
 use async_trait::async_trait;
 use time::OffsetDateTime;
@@ -8,38 +8,46 @@ use crate::{
     common::types::{BookFormat, BookStore},
     domain::{
         entity::{
-            author::{AuthorId, AuthorName},
-            book::{Book, BookId, BookTitle, BookUpdate, Isbn, OwnedFlag, Priority, ReadFlag},
+            author::{Author, AuthorId, AuthorName, validate_author_yomi},
+            book::{
+                Book, BookId, BookPatch, BookTitle, BookUpdate, Isbn, OwnedFlag, Priority, ReadFlag,
+            },
             event::EventSetOperation,
+            import_job::{ImportEntry, ImportField, ImportMode},
             user::UserId,
         },
         error::DomainError,
         repository::{
             author_repository::AuthorRepository,
-            book_repository::BookRepository,
+            book_repository::{BookFilter, BookRepository, BookSelection},
             transaction::{TransactionEventSet, TransactionManager},
         },
     },
     use_case::{
         dto::{
-            book::{BookDto, CreateBookDto, ImportBookEntryDto, TimeInfo, UpdateBookDto},
+            book::{
+                BookDto, BookPatchDto, BookSelectionDto, CreateBookDto, ImportBookEntryDto,
+                ImportOptionsDto, PatchBookDto, TimeInfo, UpdateBookDto,
+            },
             mutation::{
-                BookMutationResultDto, DeleteBookResultDto, ImportBooksResultDto,
-                MutationResultDto, SingleEventMutationResultDto,
+                BookMutationResultDto, BulkUpdateBooksResultDto, CurrentStateDto,
+                DeleteBookResultDto, DeleteBooksResultDto, ExpectedVersionDto, ImportBookErrorDto,
+                ImportBooksResultDto, MutationResultDto, SingleEventMutationResultDto,
             },
         },
         error::UseCaseError,
         traits::book::{
-            CreateBookUseCase, DeleteBookUseCase, ImportBooksUseCase, UpdateBookUseCase,
+            BulkUpdateBooksUseCase, CreateBookUseCase, DeleteBookUseCase, ImportBooksUseCase,
+            UpdateBookUseCase,
         },
     },
 };
 
 const MAX_BOOK_BATCH: usize = 1000;
 
-// Validated input for one book in a bulk import. Built from ImportBookEntryDto
+// Validated input for one book in a bulk import. Built from ImportEntry
 // before the transaction opens, so validation failures never start one.
-struct ImportBookInput {
+pub(super) struct ImportBookInput {
     book_id: BookId,
     title: BookTitle,
     author_names: Vec<AuthorName>,
@@ -53,6 +61,37 @@ struct ImportBookInput {
     updated_at: OffsetDateTime,
 }
 
+// Validates one import entry. A failure names the field it concerns.
+pub(super) fn validate_import_entry(
+    book_id: BookId,
+    entry: ImportEntry,
+    now: OffsetDateTime,
+) -> Result<ImportBookInput, (ImportField, DomainError)> {
+    let title = BookTitle::new(entry.title).map_err(|err| (ImportField::Title, err))?;
+    let author_names: Result<Vec<AuthorName>, DomainError> = entry
+        .author_names
+        .into_iter()
+        .map(AuthorName::new)
+        .collect();
+    let author_names = author_names.map_err(|err| (ImportField::AuthorNames, err))?;
+    let isbn = Isbn::new(entry.isbn).map_err(|err| (ImportField::Isbn, err))?;
+    let priority = Priority::new(entry.priority).map_err(|err| (ImportField::Priority, err))?;
+
+    Ok(ImportBookInput {
+        book_id,
+        title,
+        author_names,
+        isbn,
+        read: ReadFlag::new(entry.read),
+        owned: OwnedFlag::new(entry.owned),
+        priority,
+        format: entry.format,
+        store: entry.store,
+        created_at: now,
+        updated_at: now,
+    })
+}
+
 pub struct CreateBookInteractor<BR, TM> {
     book_repository: BR,
     transaction_manager: TM,
@@ -114,6 +153,62 @@ impl<BR, TM> UpdateBookInteractor<BR, TM> {
     }
 }
 
+impl<BR, TM> UpdateBookInteractor<BR, TM>
+where
+    TM: TransactionManager,
+    BR: BookRepository<Transaction = TM::Transaction>,
+{
+    // Applies the update `change` makes of the stored book and records it as
+    // one update event. When it makes none, the book is returned as it is with
+    // its latest event, and nothing is written.
+    async fn save_change(
+        &self,
+        user_id: UserId,
+        book_id: BookId,
+        expected_version: ExpectedVersionDto,
+        change: impl FnOnce(&Book) -> Option<BookUpdate> + Send,
+    ) -> Result<BookMutationResultDto, UseCaseError> {
+        let mut tx = self
+            .transaction_manager
+            .begin(&user_id, EventSetOperation::UpdateBook)
+            .await?;
+        let mut book = find_book_for_update(
+            &self.book_repository,
+            &mut tx,
+            &user_id,
+            &book_id,
+            expected_version,
+        )
+        .await?;
+
+        let Some(update) = change(&book) else {
+            let (event_id, event_set_id) = self
+                .book_repository
+                .find_latest_event_with_tx(&mut tx, &user_id, &book_id)
+                .await?
+                .ok_or_else(|| {
+                    UseCaseError::Unexpected(format!("book {book_id} has no events."))
+                })?;
+            return Ok(SingleEventMutationResultDto::new(
+                book.into(),
+                event_set_id.to_string(),
+                event_id,
+            ));
+        };
+        book.update(update, OffsetDateTime::now_utc());
+
+        let event_id = self.book_repository.update(&mut tx, &book).await?;
+        let event_set_id = tx.event_set_id().hyphenated().to_string();
+        self.transaction_manager.commit(tx).await?;
+
+        Ok(SingleEventMutationResultDto::new(
+            book.into(),
+            event_set_id,
+            event_id,
+        ))
+    }
+}
+
 #[async_trait]
 impl<BR, TM> UpdateBookUseCase for UpdateBookInteractor<BR, TM>
 where
@@ -126,70 +221,196 @@ where
         book_data: UpdateBookDto,
     ) -> Result<BookMutationResultDto, UseCaseError> {
         let user_id = UserId::new(user_id.to_string())?;
-        let UpdateBookDto {
+        let (book_id, update, expected_version) = validate_book_update(book_data)?;
+
+        self.save_change(user_id, book_id, expected_version, |_| Some(update))
+            .await
+    }
+
+    async fn patch(
+        &self,
+        user_id: &str,
+        book_data: PatchBookDto,
+    ) -> Result<BookMutationResultDto, UseCaseError> {
+        let user_id = UserId::new(user_id.to_string())?;
+        let PatchBookDto {
             id,
-            title,
-            author_ids,
-            isbn,
-            read,
-            owned,
-            priority,
-            format,
-            store,
+            patch,
+            expected_version,
         } = book_data;
 
         let book_id = BookId::try_from(id.as_str())?;
-        let title = BookTitle::new(title)?;
-        let author_ids: Result<Vec<AuthorId>, DomainError> = author_ids
-            .into_iter()
-            .map(|author_id| AuthorId::try_from(author_id.as_str()))
-            .collect();
-        let author_ids = author_ids?;
-        let isbn = Isbn::new(isbn)?;
-        let read = ReadFlag::new(read);
-        let owned = OwnedFlag::new(owned);
-        let priority = Priority::new(priority)?;
+        let patch = BookPatch::try_from(patch)?;
+
+        self.save_change(user_id, book_id, expected_version, |book| {
+            let update = book.update_from_patch(patch);
+            book.is_changed_by(&update).then_some(update)
+        })
+        .await
+    }
+}
+
+// Splits an update into the book it targets, the validated new state and the
+// version the caller based it on.
+pub(super) fn validate_book_update(
+    book_data: UpdateBookDto,
+) -> Result<(BookId, BookUpdate, ExpectedVersionDto), UseCaseError> {
+    let UpdateBookDto {
+        id,
+        title,
+        author_ids,
+        isbn,
+        read,
+        owned,
+        priority,
+        format,
+        store,
+        expected_version,
+    } = book_data;
+
+    let book_id = BookId::try_from(id.as_str())?;
+    let author_ids: Result<Vec<AuthorId>, DomainError> = author_ids
+        .into_iter()
+        .map(|author_id| AuthorId::try_from(author_id.as_str()))
+        .collect();
+    let update = BookUpdate {
+        title: BookTitle::new(title)?,
+        author_ids: author_ids?,
+        isbn: Isbn::new(isbn)?,
+        read: ReadFlag::new(read),
+        owned: OwnedFlag::new(owned),
+        priority: Priority::new(priority)?,
+        format,
+        store,
+    };
+    Ok((book_id, update, expected_version))
+}
+
+// Loads a book about to be changed within `tx`, failing if it does not exist
+// or no longer is at `expected_version`.
+pub(super) async fn find_book_for_update<BR: BookRepository>(
+    book_repository: &BR,
+    tx: &mut BR::Transaction,
+    user_id: &UserId,
+    book_id: &BookId,
+    expected_version: ExpectedVersionDto,
+) -> Result<Book, UseCaseError> {
+    let Some(book) = book_repository
+        .find_by_id_with_tx(tx, user_id, book_id)
+        .await?
+    else {
+        return Err(UseCaseError::NotFound {
+            entity_type: "book",
+            entity_id: book_id.to_string(),
+            user_id: user_id.as_str().to_string(),
+        });
+    };
+    // The row is locked by now, so the check holds until commit.
+    if expected_version.is_set() {
+        let latest_event_id = book_repository
+            .find_latest_event_id_with_tx(tx, user_id, book_id)
+            .await?;
+        if !expected_version.matches(latest_event_id) {
+            return Err(UseCaseError::Conflict {
+                message: format!("book {book_id} has changed since the expected version."),
+                current: Some(Box::new(CurrentStateDto::Book {
+                    book: book.into(),
+                    event_id: latest_event_id,
+                })),
+            });
+        }
+    }
+    Ok(book)
+}
+
+pub struct BulkUpdateBooksInteractor<BR, TM> {
+    book_repository: BR,
+    transaction_manager: TM,
+}
+
+impl<BR, TM> BulkUpdateBooksInteractor<BR, TM> {
+    pub fn new(book_repository: BR, transaction_manager: TM) -> Self {
+        Self {
+            book_repository,
+            transaction_manager,
+        }
+    }
+}
+
+#[async_trait]
+impl<BR, TM> BulkUpdateBooksUseCase for BulkUpdateBooksInteractor<BR, TM>
+where
+    TM: TransactionManager,
+    BR: BookRepository<Transaction = TM::Transaction>,
+{
+    async fn bulk_update(
+        &self,
+        user_id: &str,
+        selection: BookSelectionDto,
+        patch: BookPatchDto,
+    ) -> Result<BulkUpdateBooksResultDto, UseCaseError> {
+        let user_id = UserId::new(user_id.to_string())?;
+        let selection = BookSelection::try_from(selection)?;
+        if let BookSelection::Ids(book_ids) = &selection {
+            if book_ids.is_empty() {
+                return Err(UseCaseError::Validation(
+                    "book ids cannot be empty".to_string(),
+                ));
+            }
+            if book_ids.len() > MAX_BOOK_BATCH {
+                return Err(UseCaseError::Validation(format!(
+                    "book ids cannot exceed {MAX_BOOK_BATCH}"
+                )));
+            }
+        }
+        let patch = BookPatch::try_from(patch)?;
+        if patch == BookPatch::default() {
+            return Err(UseCaseError::Validation(
+                "patch must change at least one field".to_string(),
+            ));
+        }
 
         let mut tx = self
             .transaction_manager
-            .begin(&user_id, EventSetOperation::UpdateBook)
+            .begin(&user_id, EventSetOperation::BulkUpdateBooks)
             .await?;
-        let book = self
+        let books = self
             .book_repository
-            .find_by_id_with_tx(&mut tx, &user_id, &book_id)
+            .find_selection_with_tx(&mut tx, &user_id, &selection)
             .await?;
-        let mut book = match book {
-            Some(book) => book,
-            None => {
-                return Err(UseCaseError::NotFound {
-                    entity_type: "book",
-                    entity_id: id,
-                    user_id: user_id.into_string(),
-                });
-            }
-        };
+        if books.len() > MAX_BOOK_BATCH {
+            return Err(UseCaseError::Validation(format!(
+                "bulk update cannot change more than {MAX_BOOK_BATCH} books, but {} match",
+                books.len()
+            )));
+        }
 
-        let update = BookUpdate {
-            title,
-            author_ids,
-            isbn,
-            read,
-            owned,
-            priority,
-            format,
-            store,
-        };
-        book.update(update, OffsetDateTime::now_utc());
+        // Books the patch leaves as they are get no event.
+        let now = OffsetDateTime::now_utc();
+        let mut updated_count = 0;
+        for mut book in books {
+            let update = book.update_from_patch(patch.clone());
+            if !book.is_changed_by(&update) {
+                continue;
+            }
+            book.update(update, now);
+            self.book_repository.update(&mut tx, &book).await?;
+            updated_count += 1;
+        }
+        if updated_count == 0 {
+            return Ok(BulkUpdateBooksResultDto {
+                updated_count,
+                event_set_id: None,
+            });
+        }
 
-        let event_id = self.book_repository.update(&mut tx, &book).await?;
         let event_set_id = tx.event_set_id().hyphenated().to_string();
         self.transaction_manager.commit(tx).await?;
 
-        Ok(SingleEventMutationResultDto::new(
-            book.into(),
-            event_set_id,
-            event_id,
-        ))
+        Ok(BulkUpdateBooksResultDto {
+            updated_count,
+            event_set_id: Some(event_set_id),
+        })
     }
 }
 
@@ -232,12 +453,337 @@ where
 
         Ok(MutationResultDto::new(book_id_value, event_set_id))
     }
+
+    async fn delete_many(
+        &self,
+        user_id: &str,
+        book_ids: Vec<String>,
+    ) -> Result<DeleteBooksResultDto, UseCaseError> {
+        let user_id = UserId::new(user_id.to_string())?;
+        let mut book_ids = book_ids
+            .iter()
+            .map(|book_id| BookId::try_from(book_id.as_str()))
+            .collect::<Result<Vec<BookId>, DomainError>>()?;
+        let mut seen = HashSet::new();
+        book_ids.retain(|book_id| seen.insert(book_id.to_uuid()));
+        if book_ids.is_empty() {
+            return Err(UseCaseError::Validation(
+                "book ids cannot be empty".to_string(),
+            ));
+        }
+        if book_ids.len() > MAX_BOOK_BATCH {
+            return Err(UseCaseError::Validation(format!(
+                "book ids cannot exceed {MAX_BOOK_BATCH}"
+            )));
+        }
+
+        // Any missing book fails the whole deletion.
+        let mut tx = self
+            .transaction_manager
+            .begin(&user_id, EventSetOperation::DeleteBooks)
+            .await?;
+        for book_id in &book_ids {
+            self.book_repository.delete(&mut tx, book_id).await?;
+        }
+        let event_set_id = tx.event_set_id().hyphenated().to_string();
+        self.transaction_manager.commit(tx).await?;
+
+        Ok(MutationResultDto::new(
+            book_ids.iter().map(BookId::to_string).collect(),
+            event_set_id,
+        ))
+    }
+}
+
+// The books an import matches its entries against, indexed by the keys its
+// mode compares: the user's books as they were before it, and those it
+// created since.
+struct ImportMatcher {
+    mode: ImportMode,
+    books: Vec<Book>,
+    by_isbn: HashMap<String, usize>,
+    by_title_and_authors: HashMap<(String, BTreeSet<String>), usize>,
+}
+
+impl ImportMatcher {
+    fn new(mode: ImportMode, books: Vec<Book>, author_names: &HashMap<AuthorId, Author>) -> Self {
+        let mut by_isbn = HashMap::new();
+        let mut by_title_and_authors = HashMap::new();
+        // The first book with a key wins, so books are matched in id order.
+        for (index, book) in books.iter().enumerate() {
+            if !book.isbn().as_str().is_empty() {
+                by_isbn.entry(book.isbn().digits()).or_insert(index);
+            }
+            let names = book
+                .author_ids()
+                .iter()
+                .filter_map(|author_id| author_names.get(author_id))
+                .map(|author| author.name().as_str().to_owned())
+                .collect();
+            by_title_and_authors
+                .entry((book.title().as_str().to_owned(), names))
+                .or_insert(index);
+        }
+        Self {
+            mode,
+            books,
+            by_isbn,
+            by_title_and_authors,
+        }
+    }
+
+    // Makes a book the import created matchable by the entries after it, so
+    // that an entry repeated within the import does not create it twice.
+    fn register(&mut self, book: Book, author_names: BTreeSet<String>) {
+        let index = self.books.len();
+        if !book.isbn().as_str().is_empty() {
+            self.by_isbn.entry(book.isbn().digits()).or_insert(index);
+        }
+        self.by_title_and_authors
+            .entry((book.title().as_str().to_owned(), author_names))
+            .or_insert(index);
+        self.books.push(book);
+    }
+
+    fn find(&self, input: &ImportBookInput) -> Option<usize> {
+        let by_isbn = || {
+            if input.isbn.as_str().is_empty() {
+                return None;
+            }
+            self.by_isbn.get(&input.isbn.digits()).copied()
+        };
+        let by_title_and_authors = || {
+            let names = input
+                .author_names
+                .iter()
+                .map(|name| name.as_str().to_owned())
+                .collect();
+            self.by_title_and_authors
+                .get(&(input.title.as_str().to_owned(), names))
+                .copied()
+        };
+        match self.mode {
+            ImportMode::CreateOnly => None,
+            ImportMode::UpsertByIsbn => by_isbn(),
+            ImportMode::UpsertByTitleAndAuthors => by_title_and_authors(),
+            ImportMode::SkipExisting if input.isbn.as_str().is_empty() => by_title_and_authors(),
+            ImportMode::SkipExisting => by_isbn(),
+        }
+    }
+}
+
+// The authors an import resolved by name, each name once, split by whether
+// resolving it created the author.
+#[derive(Default)]
+struct ImportedAuthors {
+    ids: HashMap<String, AuthorId>,
+    created: Vec<String>,
+    reused: Vec<String>,
+}
+
+// Loads and locks the user's books for matching, along with the names of
+// their authors when the mode compares them.
+async fn load_import_matcher<BR, AR>(
+    book_repository: &BR,
+    author_repository: &AR,
+    tx: &mut BR::Transaction,
+    user_id: &UserId,
+    mode: ImportMode,
+) -> Result<ImportMatcher, UseCaseError>
+where
+    BR: BookRepository,
+    AR: AuthorRepository<Transaction = BR::Transaction>,
+{
+    let filter = BookFilter {
+        has_isbn: (mode == ImportMode::UpsertByIsbn).then_some(true),
+        ..Default::default()
+    };
+    let books = book_repository
+        .find_selection_with_tx(tx, user_id, &BookSelection::Filter(filter))
+        .await?;
+    let authors = if mode == ImportMode::UpsertByIsbn {
+        HashMap::new()
+    } else {
+        let mut author_ids: Vec<AuthorId> = books
+            .iter()
+            .flat_map(|book| book.author_ids().iter().cloned())
+            .collect();
+        let mut seen = HashSet::new();
+        author_ids.retain(|author_id| seen.insert(author_id.clone()));
+        author_repository
+            .find_by_ids_as_hash_map_with_tx(tx, user_id, &author_ids)
+            .await?
+    };
+    Ok(ImportMatcher::new(mode, books, &authors))
+}
+
+// Resolves the entry's author names to ids, creating authors that do not
+// exist yet with their yomi from `author_yomi`. Names resolved before are
+// taken from `authors`.
+async fn resolve_author_ids<AR: AuthorRepository>(
+    author_repository: &AR,
+    tx: &mut AR::Transaction,
+    authors: &mut ImportedAuthors,
+    author_names: &[AuthorName],
+    author_yomi: &HashMap<String, String>,
+    now: OffsetDateTime,
+) -> Result<Vec<AuthorId>, UseCaseError> {
+    // Duplicate names within one book are dropped, keeping first-seen
+    // order, since book_author has a primary key on (user_id, book_id,
+    // author_id) and a duplicated id would abort the whole import.
+    let mut seen_names: HashSet<&str> = HashSet::new();
+    let mut author_ids = Vec::with_capacity(author_names.len());
+    for author_name in author_names {
+        if !seen_names.insert(author_name.as_str()) {
+            continue;
+        }
+        let author_id = match authors.ids.get(author_name.as_str()) {
+            Some(author_id) => author_id.clone(),
+            None => {
+                let yomi = author_yomi
+                    .get(author_name.as_str())
+                    .map_or("", String::as_str);
+                let resolved = author_repository
+                    .find_or_create_by_name(tx, author_name, yomi, now)
+                    .await?;
+                let name = author_name.as_str().to_owned();
+                authors.ids.insert(name.clone(), resolved.id.clone());
+                if resolved.created {
+                    authors.created.push(name);
+                } else {
+                    authors.reused.push(name);
+                }
+                resolved.id
+            }
+        };
+        author_ids.push(author_id);
+    }
+    Ok(author_ids)
+}
+
+// Writes validated entries in `tx`, creating, updating or skipping each as
+// `mode` says. The result leaves `errors` and `event_set_id` to the caller.
+// Unless the mode creates every entry, they are matched against the library
+// as loaded in `tx`, so an import written over several transactions also
+// matches what was added between them.
+#[allow(clippy::too_many_arguments)]
+pub(super) async fn write_import_entries<BR, AR>(
+    book_repository: &BR,
+    author_repository: &AR,
+    tx: &mut BR::Transaction,
+    user_id: &UserId,
+    inputs: Vec<ImportBookInput>,
+    mode: ImportMode,
+    author_yomi: &HashMap<String, String>,
+    now: OffsetDateTime,
+) -> Result<ImportBooksResultDto, UseCaseError>
+where
+    BR: BookRepository,
+    AR: AuthorRepository<Transaction = BR::Transaction>,
+{
+    let mut matcher = if mode == ImportMode::CreateOnly {
+        None
+    } else {
+        Some(load_import_matcher(book_repository, author_repository, tx, user_id, mode).await?)
+    };
+
+    // Authors are resolved by name within the shared transaction, each
+    // unique name once, and only for entries that are written.
+    let mut authors = ImportedAuthors::default();
+    let mut created = Vec::with_capacity(inputs.len());
+    let mut updated = Vec::new();
+    let mut skipped = Vec::new();
+    for input in inputs {
+        let matched = matcher.as_mut().and_then(|matcher| {
+            let index = matcher.find(&input)?;
+            Some(&mut matcher.books[index])
+        });
+        match matched {
+            Some(book) if mode == ImportMode::SkipExisting => {
+                skipped.push(BookDto::from(book.clone()));
+            }
+            Some(book) => {
+                let author_ids = resolve_author_ids(
+                    author_repository,
+                    tx,
+                    &mut authors,
+                    &input.author_names,
+                    author_yomi,
+                    now,
+                )
+                .await?;
+                let update = BookUpdate {
+                    title: input.title,
+                    author_ids,
+                    isbn: input.isbn,
+                    read: input.read,
+                    owned: input.owned,
+                    priority: input.priority,
+                    format: input.format,
+                    store: input.store,
+                };
+                // A matched book the entry leaves as it is gets no event.
+                if !book.is_changed_by(&update) {
+                    skipped.push(BookDto::from(book.clone()));
+                    continue;
+                }
+                book.update(update, now);
+                book_repository.update(tx, book).await?;
+                updated.push(BookDto::from(book.clone()));
+            }
+            None => {
+                let author_names: BTreeSet<String> = input
+                    .author_names
+                    .iter()
+                    .map(|name| name.as_str().to_owned())
+                    .collect();
+                let author_ids = resolve_author_ids(
+                    author_repository,
+                    tx,
+                    &mut authors,
+                    &input.author_names,
+                    author_yomi,
+                    now,
+                )
+                .await?;
+                let book = Book::new(
+                    input.book_id,
+                    input.title,
+                    author_ids,
+                    input.isbn,
+                    input.read,
+                    input.owned,
+                    input.priority,
+                    input.format,
+                    input.store,
+                    input.created_at,
+                    input.updated_at,
+                )?;
+
+                let _event_id = book_repository.create(tx, &book).await?;
+                created.push(BookDto::from(book.clone()));
+                if let Some(matcher) = matcher.as_mut() {
+                    matcher.register(book, author_names);
+                }
+            }
+        }
+    }
+    Ok(ImportBooksResultDto {
+        created,
+        updated,
+        skipped,
+        new_author_names: authors.created,
+        reused_author_names: authors.reused,
+        errors: Vec::new(),
+        event_set_id: None,
+    })
 }
 
 pub struct ImportBooksInteractor<BR, AR, TM> {
     book_repository: BR,
     author_repository: AR,
     transaction_manager: TM,
+    max_books: usize,
 }
 
 impl<BR, AR, TM> ImportBooksInteractor<BR, AR, TM> {
@@ -246,8 +792,16 @@ impl<BR, AR, TM> ImportBooksInteractor<BR, AR, TM> {
             book_repository,
             author_repository,
             transaction_manager,
+            max_books: MAX_BOOK_BATCH,
         }
     }
+
+    // Raises the limit on books per import, which otherwise keeps requests to
+    // MAX_BOOK_BATCH books, for imports that are not made through the API.
+    pub fn with_max_books(mut self, max_books: usize) -> Self {
+        self.max_books = max_books;
+        self
+    }
 }
 
 #[async_trait]
@@ -261,6 +815,18 @@ where
         &self,
         user_id: &str,
         books: Vec<ImportBookEntryDto>,
+        options: ImportOptionsDto,
+    ) -> Result<ImportBooksResultDto, UseCaseError> {
+        self.import_with_author_yomi(user_id, books, HashMap::new(), options)
+            .await
+    }
+
+    async fn import_with_author_yomi(
+        &self,
+        user_id: &str,
+        books: Vec<ImportBookEntryDto>,
+        author_yomi: HashMap<String, String>,
+        options: ImportOptionsDto,
     ) -> Result<ImportBooksResultDto, UseCaseError> {
         if books.is_empty() {
             return Err(UseCaseError::Validation(
@@ -268,118 +834,85 @@ where
             ));
         }
 
-        if books.len() > MAX_BOOK_BATCH {
+        if books.len() > self.max_books {
             return Err(UseCaseError::Validation(format!(
-                "books cannot exceed {MAX_BOOK_BATCH}"
+                "books cannot exceed {}",
+                self.max_books
             )));
         }
 
         let user_id = UserId::new(user_id.to_string())?;
         let now = OffsetDateTime::now_utc();
-
-        // Validation and DTO mapping happen BEFORE begin, so a validation
-        // failure never opens a transaction.
-        let inputs: Result<Vec<ImportBookInput>, UseCaseError> = books
+        let author_yomi = author_yomi
             .into_iter()
-            .map(|dto| {
-                let title = BookTitle::new(dto.title)?;
-                let author_names: Result<Vec<AuthorName>, DomainError> =
-                    dto.author_names.into_iter().map(AuthorName::new).collect();
-                let author_names = author_names?;
-                let isbn = Isbn::new(dto.isbn)?;
-                let priority = Priority::new(dto.priority)?;
-
-                Ok(ImportBookInput {
-                    book_id: BookId::new(Uuid::new_v4())?,
-                    title,
-                    author_names,
-                    isbn,
-                    read: ReadFlag::new(dto.read),
-                    owned: OwnedFlag::new(dto.owned),
-                    priority,
-                    format: dto.format,
-                    store: dto.store,
-                    created_at: now,
-                    updated_at: now,
-                })
-            })
-            .collect();
-        let inputs = inputs?;
-
-        let mut tx = self
-            .transaction_manager
-            .begin(&user_id, EventSetOperation::ImportBooks)
-            .await?;
+            .map(|(name, yomi)| Ok((name, validate_author_yomi(yomi)?)))
+            .collect::<Result<HashMap<_, _>, DomainError>>()?;
 
-        // Resolve every unique author name to an id within the shared
-        // transaction. Deduplication (formerly inside the import repository)
-        // lives here as a name -> AuthorId map.
-        let mut name_to_id: HashMap<String, AuthorId> = HashMap::new();
-        for input in &inputs {
-            for author_name in &input.author_names {
-                let key = author_name.as_str().to_owned();
-                if name_to_id.contains_key(&key) {
+        // Validation and DTO mapping happen BEFORE begin, so a validation
+        // failure never opens a transaction. Dry runs and partial imports
+        // report invalid entries and go on with the others instead.
+        let report_invalid = options.dry_run || options.partial;
+        let mut inputs = Vec::with_capacity(books.len());
+        let mut errors = Vec::new();
+        for (index, dto) in books.into_iter().enumerate() {
+            let book_id = BookId::new(Uuid::new_v4())?;
+            let (field, err) = match validate_import_entry(book_id, dto.into(), now) {
+                Ok(input) => {
+                    inputs.push(input);
                     continue;
                 }
-                let author_id = self
-                    .author_repository
-                    .find_or_create_by_name(&mut tx, author_name, now)
-                    .await?;
-                name_to_id.insert(key, author_id);
+                Err(invalid) => invalid,
+            };
+            match UseCaseError::from(err) {
+                UseCaseError::Validation(message) if report_invalid => {
+                    errors.push(ImportBookErrorDto {
+                        index,
+                        field: field.into(),
+                        message,
+                    });
+                }
+                UseCaseError::Validation(message) => {
+                    return Err(UseCaseError::Validation(format!("book {index}: {message}")));
+                }
+                err => return Err(err),
             }
         }
 
-        let mut result_books = Vec::with_capacity(inputs.len());
-        for input in inputs {
-            // Drop duplicate author names within one book (keeping first-seen
-            // order) — book_author has a primary key on (user_id, book_id,
-            // author_id), so a duplicated id would abort the whole import.
-            let mut seen_names: HashSet<&str> = HashSet::new();
-            let author_ids: Vec<AuthorId> = input
-                .author_names
-                .iter()
-                .filter(|name| seen_names.insert(name.as_str()))
-                .map(|name| {
-                    name_to_id.get(name.as_str()).cloned().ok_or_else(|| {
-                        DomainError::Unexpected(format!(
-                            "author name '{}' not found in name_to_id map",
-                            name.as_str()
-                        ))
-                    })
-                })
-                .collect::<Result<Vec<_>, _>>()?;
-
-            let book = Book::new(
-                input.book_id,
-                input.title,
-                author_ids,
-                input.isbn,
-                input.read,
-                input.owned,
-                input.priority,
-                input.format,
-                input.store,
-                input.created_at,
-                input.updated_at,
-            )?;
-
-            let _event_id = self.book_repository.create(&mut tx, &book).await?;
-            result_books.push(book);
+        let mut tx = self
+            .transaction_manager
+            .begin(&user_id, EventSetOperation::ImportBooks)
+            .await?;
+        let mut result = write_import_entries(
+            &self.book_repository,
+            &self.author_repository,
+            &mut tx,
+            &user_id,
+            inputs,
+            options.mode.into(),
+            &author_yomi,
+            now,
+        )
+        .await?;
+        result.errors = errors;
+        // Dropping the transaction rolls back what a dry run wrote.
+        if options.dry_run || (result.created.is_empty() && result.updated.is_empty()) {
+            return Ok(result);
         }
 
         let event_set_id = tx.event_set_id().hyphenated().to_string();
         self.transaction_manager.commit(tx).await?;
+        result.event_set_id = Some(event_set_id);
 
-        Ok(MutationResultDto::new(
-            result_books.into_iter().map(BookDto::from).collect(),
-            event_set_id,
-        ))
+        Ok(result)
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use std::sync::{Arc, Mutex};
+    use std::{
+        collections::HashMap,
+        sync::{Arc, Mutex},
+    };
 
     use mockall::predicate::always;
     use time::OffsetDateTime;
@@ -392,24 +925,41 @@ mod tests {
         },
         domain::{
             entity::{
-                author::AuthorId,
-                book::{Book, BookId, BookTitle, Isbn, OwnedFlag, Priority, ReadFlag},
+                author::{Author, AuthorId, AuthorName},
+                book::{
+                    Book, BookId, BookPatch, BookTitle, BookUpdate, Isbn, OwnedFlag, Priority,
+                    ReadFlag,
+                },
+                event::EventSetOperation,
+                event_set::EventSetId,
             },
             error::DomainError,
             repository::{
-                author_repository::MockAuthorRepository, book_repository::MockBookRepository,
+                author_repository::{MockAuthorRepository, ResolvedAuthor},
+                book_repository::{BookSelection, MockBookRepository},
                 transaction::MockTransactionManager,
             },
         },
         use_case::{
-            dto::book::{CreateBookDto, ImportBookEntryDto, UpdateBookDto},
+            dto::{
+                book::{
+                    AuthorIdsPatchDto, BookFilterDto, BookPatchDto, BookSelectionDto,
+                    CreateBookDto, ImportBookEntryDto, ImportModeDto, ImportOptionsDto,
+                    PatchBookDto, UpdateBookDto,
+                },
+                mutation::{
+                    BulkUpdateBooksResultDto, CurrentStateDto, ExpectedVersionDto,
+                    ImportBookFieldDto,
+                },
+            },
             error::UseCaseError,
             interactor::book::{
-                CreateBookInteractor, DeleteBookInteractor, ImportBooksInteractor,
-                UpdateBookInteractor,
+                BulkUpdateBooksInteractor, CreateBookInteractor, DeleteBookInteractor,
+                ImportBooksInteractor, UpdateBookInteractor,
             },
             traits::book::{
-                CreateBookUseCase, DeleteBookUseCase, ImportBooksUseCase, UpdateBookUseCase,
+                BulkUpdateBooksUseCase, CreateBookUseCase, DeleteBookUseCase, ImportBooksUseCase,
+                UpdateBookUseCase,
             },
         },
     };
@@ -729,30 +1279,345 @@ mod tests {
     }
 
     #[tokio::test]
-    async fn delete_book_success() {
-        // Given
+    async fn update_book_rejects_a_stale_event_id_with_the_current_state() {
+        // Given: the book has an event newer than the one the client saw
         let book_uuid = Uuid::new_v4();
-        let book_id_str = book_uuid.hyphenated().to_string();
-
+        let book = make_book(book_uuid);
         let mut book_repository = MockBookRepository::new();
         book_repository
-            .expect_delete()
-            .with(always(), always())
-            .returning(|_, _| Ok(()));
+            .expect_find_by_id_with_tx()
+            .return_once(move |_, _, _| Ok(Some(book)));
+        book_repository
+            .expect_find_latest_event_id_with_tx()
+            .returning(|_, _, _| Ok(Some(12)));
+        book_repository.expect_update().times(0);
 
-        let interactor = DeleteBookInteractor::new(book_repository, make_transaction_manager());
+        let interactor =
+            UpdateBookInteractor::new(book_repository, make_begin_only_transaction_manager());
+        let mut book_data = UpdateBookDto::new(
+            book_uuid.hyphenated().to_string(),
+            "Updated Book".to_string(),
+            vec![],
+            "".to_string(),
+            true,
+            false,
+            70,
+            BookFormat::Unknown,
+            BookStore::Unknown,
+        );
+        book_data.expected_version = ExpectedVersionDto { event_id: Some(11) };
 
         // When
-        let result = interactor.delete("user1", &book_id_str).await;
+        let result = interactor.update("user1", book_data).await;
 
-        // Then
-        assert!(result.is_ok());
+        // Then: the stored book comes back with the conflict
+        let Err(UseCaseError::Conflict {
+            current: Some(current),
+            ..
+        }) = result
+        else {
+            panic!("expected a conflict with the current state");
+        };
+        let CurrentStateDto::Book { book, event_id } = *current else {
+            panic!("expected the current book");
+        };
+        assert_eq!(book.title, "Test Book");
+        assert_eq!(event_id, Some(12));
     }
 
     #[tokio::test]
-    async fn delete_book_fails_with_invalid_book_id() {
+    async fn update_book_applies_when_the_expected_version_matches() {
         // Given
-        let book_repository = MockBookRepository::new();
+        let book_uuid = Uuid::new_v4();
+        let book = make_book(book_uuid);
+        let mut book_repository = MockBookRepository::new();
+        book_repository
+            .expect_find_by_id_with_tx()
+            .return_once(move |_, _, _| Ok(Some(book)));
+        book_repository
+            .expect_find_latest_event_id_with_tx()
+            .returning(|_, _, _| Ok(Some(12)));
+        book_repository
+            .expect_update()
+            .returning(|_, _| Ok(13.into()));
+
+        let interactor = UpdateBookInteractor::new(book_repository, make_transaction_manager());
+        let mut book_data = UpdateBookDto::new(
+            book_uuid.hyphenated().to_string(),
+            "Updated Book".to_string(),
+            vec![],
+            "".to_string(),
+            true,
+            false,
+            70,
+            BookFormat::Unknown,
+            BookStore::Unknown,
+        );
+        book_data.expected_version = ExpectedVersionDto { event_id: Some(12) };
+
+        // When
+        let result = interactor.update("user1", book_data).await;
+
+        // Then
+        assert_eq!(result.unwrap().event_id.value(), 13);
+    }
+
+    #[tokio::test]
+    async fn patch_book_changes_only_the_given_fields() {
+        // Given
+        let book_uuid = Uuid::new_v4();
+        let kept_author_id = AuthorId::new(Uuid::new_v4());
+        let added_author_id = AuthorId::new(Uuid::new_v4());
+        let mut book = make_book(book_uuid);
+        book.update(
+            BookUpdate {
+                title: BookTitle::new("Stored Title".to_string()).unwrap(),
+                author_ids: vec![kept_author_id.clone()],
+                isbn: Isbn::new("9784062758574".to_string()).unwrap(),
+                read: ReadFlag::new(false),
+                owned: OwnedFlag::new(true),
+                priority: Priority::new(30).unwrap(),
+                format: BookFormat::Printed,
+                store: BookStore::Kindle,
+            },
+            OffsetDateTime::now_utc(),
+        );
+        let mut book_repository = MockBookRepository::new();
+        book_repository
+            .expect_find_by_id_with_tx()
+            .return_once(move |_, _, _| Ok(Some(book)));
+        let expected_author_ids = vec![kept_author_id, added_author_id.clone()];
+        book_repository
+            .expect_update()
+            .withf(move |_, book| {
+                book.read().to_bool()
+                    && book.title().as_str() == "Stored Title"
+                    && book.isbn().as_str() == "9784062758574"
+                    && book.priority().to_i32() == 30
+                    && book.store() == &BookStore::Kindle
+                    && book.author_ids() == &expected_author_ids
+            })
+            .returning(|_, _| Ok(14.into()));
+
+        let interactor = UpdateBookInteractor::new(book_repository, make_transaction_manager());
+        let book_data = PatchBookDto::new(
+            book_uuid.hyphenated().to_string(),
+            BookPatchDto {
+                read: Some(true),
+                author_ids: Some(AuthorIdsPatchDto::Edit {
+                    add: vec![added_author_id.to_uuid().hyphenated().to_string()],
+                    remove: vec![],
+                }),
+                ..Default::default()
+            },
+        );
+
+        // When
+        let result = interactor.patch("user1", book_data).await;
+
+        // Then
+        let result = result.unwrap();
+        assert!(result.read);
+        assert_eq!(result.title, "Stored Title");
+        assert_eq!(result.event_id.value(), 14);
+    }
+
+    #[tokio::test]
+    async fn patch_book_that_changes_nothing_returns_the_book_without_writing() {
+        // Given: A patch that sets the book's read flag to what it is already
+        let book_uuid = Uuid::new_v4();
+        let book = make_book(book_uuid);
+        let event_set_id = EventSetId::new();
+        let mut book_repository = MockBookRepository::new();
+        book_repository
+            .expect_find_by_id_with_tx()
+            .return_once(move |_, _, _| Ok(Some(book)));
+        let latest_event_set_id = event_set_id.clone();
+        book_repository
+            .expect_find_latest_event_with_tx()
+            .return_once(move |_, _, _| Ok(Some((7.into(), latest_event_set_id))));
+        book_repository.expect_update().never();
+
+        let interactor =
+            UpdateBookInteractor::new(book_repository, make_begin_only_transaction_manager());
+        let book_data = PatchBookDto::new(
+            book_uuid.hyphenated().to_string(),
+            BookPatchDto {
+                read: Some(false),
+                ..Default::default()
+            },
+        );
+
+        // When
+        let result = interactor.patch("user1", book_data).await;
+
+        // Then: The book comes back at its latest event, and nothing is committed
+        let result = result.unwrap();
+        assert!(!result.read);
+        assert_eq!(result.event_id.value(), 7);
+        assert_eq!(result.event_set_id, event_set_id.to_string());
+    }
+
+    #[tokio::test]
+    async fn patch_book_rejects_an_invalid_field_before_the_transaction() {
+        // Given
+        let mut tm = MockTransactionManager::new();
+        tm.expect_begin().times(0);
+        let interactor = UpdateBookInteractor::new(MockBookRepository::new(), tm);
+        let book_data = PatchBookDto::new(
+            Uuid::new_v4().hyphenated().to_string(),
+            BookPatchDto {
+                priority: Some(101),
+                ..Default::default()
+            },
+        );
+
+        // When
+        let result = interactor.patch("user1", book_data).await;
+
+        // Then
+        assert!(matches!(result, Err(UseCaseError::Validation(_))));
+    }
+
+    #[tokio::test]
+    async fn bulk_update_books_records_an_event_per_changed_book() {
+        // Given: one of the two selected books is already owned
+        let owned_book = make_book(Uuid::new_v4());
+        let mut unowned_book = make_book(Uuid::new_v4());
+        unowned_book.patch(
+            BookPatch {
+                owned: Some(OwnedFlag::new(false)),
+                ..Default::default()
+            },
+            OffsetDateTime::now_utc(),
+        );
+        let unowned_book_id = unowned_book.id().clone();
+        let mut book_repository = MockBookRepository::new();
+        book_repository
+            .expect_find_selection_with_tx()
+            .withf(|_, _, selection| matches!(selection, BookSelection::Filter(_)))
+            .return_once(move |_, _, _| Ok(vec![owned_book, unowned_book]));
+        book_repository
+            .expect_update()
+            .times(1)
+            .withf(move |_, book| book.id() == &unowned_book_id && book.owned().to_bool())
+            .returning(|_, _| Ok(21.into()));
+        let mut tm = MockTransactionManager::new();
+        tm.expect_begin()
+            .withf(|_, operation| *operation == EventSetOperation::BulkUpdateBooks)
+            .returning(|_, _| Ok(()));
+        tm.expect_commit().times(1).returning(|_| Ok(()));
+
+        let interactor = BulkUpdateBooksInteractor::new(book_repository, tm);
+        let patch = BookPatchDto {
+            owned: Some(true),
+            ..Default::default()
+        };
+
+        // When
+        let result = interactor
+            .bulk_update(
+                "user1",
+                BookSelectionDto::Filter(BookFilterDto {
+                    store: Some(BookStore::Kindle),
+                    ..Default::default()
+                }),
+                patch,
+            )
+            .await;
+
+        // Then
+        let result = result.unwrap();
+        assert_eq!(result.updated_count, 1);
+        assert!(result.event_set_id.is_some());
+    }
+
+    #[tokio::test]
+    async fn bulk_update_books_without_changes_commits_nothing() {
+        // Given
+        let book = make_book(Uuid::new_v4());
+        let book_id = book.id().to_string();
+        let mut book_repository = MockBookRepository::new();
+        book_repository
+            .expect_find_selection_with_tx()
+            .return_once(move |_, _, _| Ok(vec![book]));
+        book_repository.expect_update().times(0);
+
+        let interactor =
+            BulkUpdateBooksInteractor::new(book_repository, make_begin_only_transaction_manager());
+        let patch = BookPatchDto {
+            priority: Some(50),
+            ..Default::default()
+        };
+
+        // When
+        let result = interactor
+            .bulk_update("user1", BookSelectionDto::Ids(vec![book_id]), patch)
+            .await;
+
+        // Then
+        assert_eq!(
+            result.unwrap(),
+            BulkUpdateBooksResultDto {
+                updated_count: 0,
+                event_set_id: None,
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn bulk_update_books_rejects_an_empty_patch_or_selection() {
+        for (selection, patch) in [
+            (
+                BookSelectionDto::Ids(vec![Uuid::new_v4().hyphenated().to_string()]),
+                BookPatchDto::default(),
+            ),
+            (
+                BookSelectionDto::Ids(vec![]),
+                BookPatchDto {
+                    read: Some(true),
+                    ..Default::default()
+                },
+            ),
+        ] {
+            // Given
+            let mut tm = MockTransactionManager::new();
+            tm.expect_begin().times(0);
+            let interactor = BulkUpdateBooksInteractor::new(MockBookRepository::new(), tm);
+
+            // When
+            let result = interactor.bulk_update("user1", selection, patch).await;
+
+            // Then
+            assert!(matches!(result, Err(UseCaseError::Validation(_))));
+        }
+    }
+
+    #[tokio::test]
+    async fn delete_book_success() {
+        // Given
+        let book_uuid = Uuid::new_v4();
+        let book_id_str = book_uuid.hyphenated().to_string();
+
+        let mut book_repository = MockBookRepository::new();
+        book_repository
+            .expect_delete()
+            .with(always(), always())
+            .returning(|_, _| Ok(()));
+
+        let interactor = DeleteBookInteractor::new(book_repository, make_transaction_manager());
+
+        // When
+        let result = interactor.delete("user1", &book_id_str).await;
+
+        // Then
+        assert!(result.is_ok());
+    }
+
+    #[tokio::test]
+    async fn delete_book_fails_with_invalid_book_id() {
+        // Given
+        let book_repository = MockBookRepository::new();
         let interactor = DeleteBookInteractor::new(book_repository, MockTransactionManager::new());
 
         // When
@@ -762,6 +1627,83 @@ mod tests {
         assert!(matches!(result, Err(UseCaseError::Validation(_))));
     }
 
+    #[tokio::test]
+    async fn delete_many_deletes_each_book_in_one_event_set() {
+        // Given
+        let first_id = Uuid::new_v4().hyphenated().to_string();
+        let second_id = Uuid::new_v4().hyphenated().to_string();
+
+        let mut book_repository = MockBookRepository::new();
+        book_repository
+            .expect_delete()
+            .times(2)
+            .returning(|_, _| Ok(()));
+
+        let mut transaction_manager = MockTransactionManager::new();
+        transaction_manager
+            .expect_begin()
+            .withf(|_, operation| *operation == EventSetOperation::DeleteBooks)
+            .times(1)
+            .returning(|_, _| Ok(()));
+        transaction_manager
+            .expect_commit()
+            .times(1)
+            .returning(|_| Ok(()));
+
+        let interactor = DeleteBookInteractor::new(book_repository, transaction_manager);
+
+        // When: the repeated id is deleted only once.
+        let result = interactor
+            .delete_many(
+                "user1",
+                vec![first_id.clone(), second_id.clone(), first_id.clone()],
+            )
+            .await;
+
+        // Then
+        assert_eq!(result.unwrap().value, vec![first_id, second_id]);
+    }
+
+    #[tokio::test]
+    async fn delete_many_fails_as_a_whole_when_a_book_is_missing() {
+        // Given
+        let mut book_repository = MockBookRepository::new();
+        book_repository.expect_delete().returning(|_, book_id| {
+            Err(DomainError::NotFound {
+                entity_type: "book",
+                entity_id: book_id.to_string(),
+                user_id: "user1".to_string(),
+            })
+        });
+
+        let mut transaction_manager = MockTransactionManager::new();
+        transaction_manager.expect_begin().returning(|_, _| Ok(()));
+        transaction_manager.expect_commit().never();
+
+        let interactor = DeleteBookInteractor::new(book_repository, transaction_manager);
+
+        // When
+        let result = interactor
+            .delete_many("user1", vec![Uuid::new_v4().hyphenated().to_string()])
+            .await;
+
+        // Then
+        assert!(matches!(result, Err(UseCaseError::NotFound { .. })));
+    }
+
+    #[tokio::test]
+    async fn delete_many_rejects_an_empty_list() {
+        // Given
+        let interactor =
+            DeleteBookInteractor::new(MockBookRepository::new(), MockTransactionManager::new());
+
+        // When
+        let result = interactor.delete_many("user1", Vec::new()).await;
+
+        // Then
+        assert!(matches!(result, Err(UseCaseError::Validation(_))));
+    }
+
     fn import_entry(title: &str, author_names: Vec<&str>) -> ImportBookEntryDto {
         ImportBookEntryDto {
             title: title.to_string(),
@@ -785,7 +1727,9 @@ mod tests {
         );
 
         // When
-        let result = interactor.import("user1", vec![]).await;
+        let result = interactor
+            .import("user1", vec![], ImportOptionsDto::default())
+            .await;
 
         // Then
         assert!(
@@ -813,7 +1757,9 @@ mod tests {
         let books = vec![import_entry("Book", vec![]); super::MAX_BOOK_BATCH];
 
         // When
-        let result = interactor.import("user1", books).await;
+        let result = interactor
+            .import("user1", books, ImportOptionsDto::default())
+            .await;
 
         // Then
         assert!(result.is_ok());
@@ -830,7 +1776,9 @@ mod tests {
         let books = vec![import_entry("Book", vec![]); super::MAX_BOOK_BATCH + 1];
 
         // When
-        let result = interactor.import("user1", books).await;
+        let result = interactor
+            .import("user1", books, ImportOptionsDto::default())
+            .await;
 
         // Then
         assert!(
@@ -851,9 +1799,12 @@ mod tests {
         author_repository
             .expect_find_or_create_by_name()
             .times(2)
-            .returning(move |_, _, created_at| {
+            .returning(move |_, _, _, created_at| {
                 captured_author_times.lock().unwrap().push(created_at);
-                Ok(AuthorId::new(author_uuid))
+                Ok(ResolvedAuthor {
+                    id: AuthorId::new(author_uuid),
+                    created: true,
+                })
             });
 
         let mut book_repository = MockBookRepository::new();
@@ -873,159 +1824,665 @@ mod tests {
         ];
 
         // When
-        let result = interactor.import("user1", books).await;
+        let result = interactor
+            .import("user1", books, ImportOptionsDto::default())
+            .await;
 
         // Then
         assert!(result.is_ok());
         let dtos = result.unwrap();
-        assert_eq!(dtos.len(), 2);
-        assert_eq!(dtos[0].created_at, dtos[0].updated_at);
-        assert_eq!(dtos[1].created_at, dtos[1].updated_at);
-        assert_eq!(dtos[0].created_at, dtos[1].created_at);
+        assert_eq!(dtos.created.len(), 2);
+        assert_eq!(dtos.created[0].created_at, dtos.created[0].updated_at);
+        assert_eq!(dtos.created[1].created_at, dtos.created[1].updated_at);
+        assert_eq!(dtos.created[0].created_at, dtos.created[1].created_at);
         let author_times = author_times.lock().unwrap();
         assert_eq!(author_times[0], author_times[1]);
         assert_eq!(
-            normalize_timestamp_for_persistence(author_times[0]),
-            dtos[0].created_at
+            normalize_timestamp_for_persistence(author_times[0]),
+            dtos.created[0].created_at
+        );
+    }
+
+    #[tokio::test]
+    async fn import_books_deduplicates_authors_within_one_book() {
+        // Given: one book listing the same author twice. The name is resolved
+        // once and the created book carries a single author id, since
+        // book_author cannot hold duplicate (book_id, author_id) pairs.
+        let author_uuid = Uuid::new_v4();
+        let mut author_repository = MockAuthorRepository::new();
+        author_repository
+            .expect_find_or_create_by_name()
+            .times(1)
+            .returning(move |_, _, _, _| {
+                Ok(ResolvedAuthor {
+                    id: AuthorId::new(author_uuid),
+                    created: true,
+                })
+            });
+
+        let mut book_repository = MockBookRepository::new();
+        book_repository
+            .expect_create()
+            .withf(|_, book| book.author_ids().len() == 1)
+            .times(1)
+            .returning(|_, _| Ok(1.into()));
+
+        let interactor = ImportBooksInteractor::new(
+            book_repository,
+            author_repository,
+            make_transaction_manager(),
+        );
+        let books = vec![import_entry("Book", vec!["Author A", "Author A"])];
+
+        // When
+        let result = interactor
+            .import("user1", books, ImportOptionsDto::default())
+            .await;
+
+        // Then
+        assert!(result.is_ok());
+    }
+
+    #[tokio::test]
+    async fn import_with_author_yomi_creates_authors_with_their_yomi() {
+        // Given: yomi for one of two new authors
+        let mut author_repository = MockAuthorRepository::new();
+        author_repository
+            .expect_find_or_create_by_name()
+            .withf(|_, name, yomi, _| name.as_str() == "村上春樹" && yomi == "むらかみ はるき")
+            .times(1)
+            .returning(|_, _, _, _| {
+                Ok(ResolvedAuthor {
+                    id: AuthorId::new(Uuid::new_v4()),
+                    created: true,
+                })
+            });
+        author_repository
+            .expect_find_or_create_by_name()
+            .withf(|_, name, yomi, _| name.as_str() == "Author B" && yomi.is_empty())
+            .times(1)
+            .returning(|_, _, _, _| {
+                Ok(ResolvedAuthor {
+                    id: AuthorId::new(Uuid::new_v4()),
+                    created: true,
+                })
+            });
+
+        let mut book_repository = MockBookRepository::new();
+        book_repository
+            .expect_create()
+            .times(1)
+            .returning(|_, _| Ok(1.into()));
+
+        let interactor = ImportBooksInteractor::new(
+            book_repository,
+            author_repository,
+            make_transaction_manager(),
+        );
+        let books = vec![import_entry("Book", vec!["村上春樹", "Author B"])];
+        let author_yomi = HashMap::from([("村上春樹".to_string(), "むらかみ はるき".to_string())]);
+
+        // When
+        let result = interactor
+            .import_with_author_yomi("user1", books, author_yomi, ImportOptionsDto::default())
+            .await;
+
+        // Then
+        assert!(result.is_ok());
+    }
+
+    #[tokio::test]
+    async fn import_with_author_yomi_rejects_invalid_yomi_before_begin() {
+        // Given: a yomi that is not in hiragana
+        let mut tm = MockTransactionManager::new();
+        tm.expect_begin().times(0);
+        let interactor =
+            ImportBooksInteractor::new(MockBookRepository::new(), MockAuthorRepository::new(), tm);
+        let books = vec![import_entry("Book", vec!["村上春樹"])];
+        let author_yomi = HashMap::from([("村上春樹".to_string(), "Murakami".to_string())]);
+
+        // When
+        let result = interactor
+            .import_with_author_yomi("user1", books, author_yomi, ImportOptionsDto::default())
+            .await;
+
+        // Then
+        assert!(matches!(result, Err(UseCaseError::Validation(_))));
+    }
+
+    #[tokio::test]
+    async fn import_books_does_not_commit_when_create_fails_mid_transaction() {
+        // Given: the failure happens AFTER begin (author already resolved,
+        // book creation fails). The transaction must not be committed; the
+        // dropped transaction rolls back.
+        let author_uuid = Uuid::new_v4();
+        let mut author_repository = MockAuthorRepository::new();
+        author_repository
+            .expect_find_or_create_by_name()
+            .times(1)
+            .returning(move |_, _, _, _| {
+                Ok(ResolvedAuthor {
+                    id: AuthorId::new(author_uuid),
+                    created: true,
+                })
+            });
+
+        let mut book_repository = MockBookRepository::new();
+        book_repository
+            .expect_create()
+            .returning(|_, _| Err(DomainError::Unexpected(String::from("db error"))));
+
+        let mut tm = MockTransactionManager::new();
+        tm.expect_begin().times(1).returning(|_, _| Ok(()));
+        tm.expect_commit().times(0);
+
+        let interactor = ImportBooksInteractor::new(book_repository, author_repository, tm);
+        let books = vec![import_entry("Book", vec!["Author A"])];
+
+        // When
+        let result = interactor
+            .import("user1", books, ImportOptionsDto::default())
+            .await;
+
+        // Then
+        assert!(matches!(result, Err(UseCaseError::Unexpected(_))));
+    }
+
+    #[tokio::test]
+    async fn import_books_propagates_repository_error() {
+        // Given: book creation fails inside the transaction.
+        let mut book_repository = MockBookRepository::new();
+        book_repository
+            .expect_create()
+            .returning(|_, _| Err(DomainError::Unexpected(String::from("db error"))));
+
+        let interactor = ImportBooksInteractor::new(
+            book_repository,
+            MockAuthorRepository::new(),
+            make_transaction_manager(),
+        );
+        let books = vec![import_entry("Book", vec![])];
+
+        // When
+        let result = interactor
+            .import("user1", books, ImportOptionsDto::default())
+            .await;
+
+        // Then
+        assert!(matches!(result, Err(UseCaseError::Unexpected(_))));
+    }
+
+    #[tokio::test]
+    async fn import_books_invalid_title_returns_error() {
+        // Given
+        let interactor = ImportBooksInteractor::new(
+            MockBookRepository::new(),
+            MockAuthorRepository::new(),
+            MockTransactionManager::new(),
+        );
+        let books = vec![import_entry("", vec![])];
+
+        // When
+        let result = interactor
+            .import("user1", books, ImportOptionsDto::default())
+            .await;
+
+        // Then
+        assert!(matches!(result, Err(UseCaseError::Validation(_))));
+    }
+
+    #[tokio::test]
+    async fn import_books_invalid_isbn_returns_error() {
+        // Given
+        let mut entry = import_entry("Valid Title", vec![]);
+        entry.isbn = "1".to_string();
+        let interactor = ImportBooksInteractor::new(
+            MockBookRepository::new(),
+            MockAuthorRepository::new(),
+            MockTransactionManager::new(),
+        );
+
+        // When
+        let result = interactor
+            .import("user1", vec![entry], ImportOptionsDto::default())
+            .await;
+
+        // Then
+        assert!(matches!(result, Err(UseCaseError::Validation(_))));
+    }
+
+    #[tokio::test]
+    async fn import_books_invalid_author_name_returns_error() {
+        // Given
+        let interactor = ImportBooksInteractor::new(
+            MockBookRepository::new(),
+            MockAuthorRepository::new(),
+            MockTransactionManager::new(),
+        );
+        let books = vec![import_entry("Valid Title", vec![""])];
+
+        // When
+        let result = interactor
+            .import("user1", books, ImportOptionsDto::default())
+            .await;
+
+        // Then
+        assert!(matches!(result, Err(UseCaseError::Validation(_))));
+    }
+
+    #[tokio::test]
+    async fn import_books_names_the_invalid_entry() {
+        // Given
+        let mut invalid = import_entry("Valid Title", vec![]);
+        invalid.isbn = "1".to_string();
+        let interactor = ImportBooksInteractor::new(
+            MockBookRepository::new(),
+            MockAuthorRepository::new(),
+            MockTransactionManager::new(),
+        );
+        let books = vec![import_entry("Valid Title", vec![]), invalid];
+
+        // When
+        let result = interactor
+            .import("user1", books, ImportOptionsDto::default())
+            .await;
+
+        // Then
+        assert!(
+            matches!(result, Err(UseCaseError::Validation(ref msg)) if msg.starts_with("book 1: ")),
+            "expected the error to name the entry, got {:?}",
+            result
+        );
+    }
+
+    #[tokio::test]
+    async fn import_books_dry_run_reports_invalid_entries_and_rolls_back() {
+        // Given: one invalid entry among two valid ones, whose authors are
+        // one new and one existing. The transaction is begun and never
+        // committed.
+        let mut author_repository = MockAuthorRepository::new();
+        author_repository
+            .expect_find_or_create_by_name()
+            .times(2)
+            .returning(|_, name, _, _| {
+                Ok(ResolvedAuthor {
+                    id: AuthorId::new(Uuid::new_v4()),
+                    created: name.as_str() == "New Author",
+                })
+            });
+        let mut book_repository = MockBookRepository::new();
+        book_repository
+            .expect_create()
+            .times(2)
+            .returning(|_, _| Ok(1.into()));
+
+        let interactor = ImportBooksInteractor::new(
+            book_repository,
+            author_repository,
+            make_begin_only_transaction_manager(),
+        );
+        let books = vec![
+            import_entry("First", vec!["New Author"]),
+            import_entry("", vec!["Never Resolved"]),
+            import_entry("Third", vec!["Existing Author"]),
+        ];
+
+        // When
+        let result = interactor
+            .import(
+                "user1",
+                books,
+                ImportOptionsDto {
+                    dry_run: true,
+                    ..Default::default()
+                },
+            )
+            .await
+            .unwrap();
+
+        // Then
+        assert_eq!(result.created.len(), 2);
+        assert_eq!(result.new_author_names, vec!["New Author".to_string()]);
+        assert_eq!(
+            result.reused_author_names,
+            vec!["Existing Author".to_string()]
         );
+        assert_eq!(result.errors.len(), 1);
+        assert_eq!(result.errors[0].index, 1);
+        assert_eq!(result.errors[0].field, ImportBookFieldDto::Title);
+        assert_eq!(result.event_set_id, None);
     }
 
     #[tokio::test]
-    async fn import_books_deduplicates_authors_within_one_book() {
-        // Given: one book listing the same author twice. The name is resolved
-        // once and the created book carries a single author id, since
-        // book_author cannot hold duplicate (book_id, author_id) pairs.
-        let author_uuid = Uuid::new_v4();
-        let mut author_repository = MockAuthorRepository::new();
-        author_repository
-            .expect_find_or_create_by_name()
-            .times(1)
-            .returning(move |_, _, _| Ok(AuthorId::new(author_uuid)));
-
+    async fn import_books_partial_commits_valid_entries_and_reports_the_others() {
+        // Given
         let mut book_repository = MockBookRepository::new();
         book_repository
             .expect_create()
-            .withf(|_, book| book.author_ids().len() == 1)
-            .times(1)
+            .times(2)
             .returning(|_, _| Ok(1.into()));
+        let mut invalid = import_entry("Invalid", vec![]);
+        invalid.priority = 101;
+        let books = vec![
+            import_entry("First", vec![]),
+            invalid,
+            import_entry("Third", vec![]),
+        ];
 
         let interactor = ImportBooksInteractor::new(
             book_repository,
-            author_repository,
+            MockAuthorRepository::new(),
             make_transaction_manager(),
         );
-        let books = vec![import_entry("Book", vec!["Author A", "Author A"])];
 
         // When
-        let result = interactor.import("user1", books).await;
+        let result = interactor
+            .import(
+                "user1",
+                books,
+                ImportOptionsDto {
+                    partial: true,
+                    ..Default::default()
+                },
+            )
+            .await
+            .unwrap();
 
         // Then
-        assert!(result.is_ok());
+        assert_eq!(result.created.len(), 2);
+        assert_eq!(result.errors.len(), 1);
+        assert_eq!(result.errors[0].index, 1);
+        assert_eq!(result.errors[0].field, ImportBookFieldDto::Priority);
+        assert!(result.event_set_id.is_some());
     }
 
-    #[tokio::test]
-    async fn import_books_does_not_commit_when_create_fails_mid_transaction() {
-        // Given: the failure happens AFTER begin (author already resolved,
-        // book creation fails). The transaction must not be committed; the
-        // dropped transaction rolls back.
-        let author_uuid = Uuid::new_v4();
-        let mut author_repository = MockAuthorRepository::new();
-        author_repository
-            .expect_find_or_create_by_name()
-            .times(1)
-            .returning(move |_, _, _| Ok(AuthorId::new(author_uuid)));
+    fn make_book_with_isbn(uuid: Uuid, isbn: &str) -> Book {
+        let mut book = make_book(uuid);
+        let update = BookUpdate {
+            isbn: Isbn::new(isbn.to_string()).unwrap(),
+            ..book.update_from_patch(BookPatch::default())
+        };
+        book.update(update, OffsetDateTime::now_utc());
+        book
+    }
 
+    #[tokio::test]
+    async fn import_books_upsert_by_isbn_updates_the_matching_book() {
+        // Given: an existing book whose ISBN differs from the entry's only in
+        // hyphens, and an entry without an ISBN, which is created.
+        let existing_uuid = Uuid::new_v4();
         let mut book_repository = MockBookRepository::new();
+        book_repository
+            .expect_find_selection_with_tx()
+            .times(1)
+            .returning(move |_, _, _| {
+                Ok(vec![make_book_with_isbn(existing_uuid, "9784062758574")])
+            });
+        book_repository
+            .expect_update()
+            .withf(move |_, book| {
+                book.id().to_uuid() == existing_uuid && book.title().as_str() == "Renamed"
+            })
+            .times(1)
+            .returning(|_, _| Ok(1.into()));
         book_repository
             .expect_create()
-            .returning(|_, _| Err(DomainError::Unexpected(String::from("db error"))));
-
-        let mut tm = MockTransactionManager::new();
-        tm.expect_begin().times(1).returning(|_, _| Ok(()));
-        tm.expect_commit().times(0);
+            .withf(|_, book| book.title().as_str() == "New Book")
+            .times(1)
+            .returning(|_, _| Ok(2.into()));
 
-        let interactor = ImportBooksInteractor::new(book_repository, author_repository, tm);
-        let books = vec![import_entry("Book", vec!["Author A"])];
+        let interactor = ImportBooksInteractor::new(
+            book_repository,
+            MockAuthorRepository::new(),
+            make_transaction_manager(),
+        );
+        let mut matching = import_entry("Renamed", vec![]);
+        matching.isbn = "978-4062758574".to_string();
+        let books = vec![matching, import_entry("New Book", vec![])];
 
         // When
-        let result = interactor.import("user1", books).await;
+        let result = interactor
+            .import(
+                "user1",
+                books,
+                ImportOptionsDto {
+                    mode: ImportModeDto::UpsertByIsbn,
+                    ..Default::default()
+                },
+            )
+            .await
+            .unwrap();
 
         // Then
-        assert!(matches!(result, Err(UseCaseError::Unexpected(_))));
+        assert_eq!(result.created.len(), 1);
+        assert_eq!(result.updated.len(), 1);
+        assert_eq!(result.updated[0].id, existing_uuid.to_string());
+        assert!(result.skipped.is_empty());
+        assert!(result.event_set_id.is_some());
     }
 
     #[tokio::test]
-    async fn import_books_propagates_repository_error() {
-        // Given: book creation fails inside the transaction.
+    async fn import_books_upsert_by_isbn_creates_a_repeated_entry_once() {
+        // Given: no existing book, and two entries sharing an ISBN
         let mut book_repository = MockBookRepository::new();
+        book_repository
+            .expect_find_selection_with_tx()
+            .times(1)
+            .returning(|_, _, _| Ok(vec![]));
         book_repository
             .expect_create()
-            .returning(|_, _| Err(DomainError::Unexpected(String::from("db error"))));
+            .times(1)
+            .returning(|_, _| Ok(1.into()));
+        book_repository
+            .expect_update()
+            .times(1)
+            .returning(|_, _| Ok(2.into()));
 
         let interactor = ImportBooksInteractor::new(
             book_repository,
             MockAuthorRepository::new(),
             make_transaction_manager(),
         );
-        let books = vec![import_entry("Book", vec![])];
+        let mut first = import_entry("First", vec![]);
+        first.isbn = "978-4062758574".to_string();
+        let mut second = import_entry("Second", vec![]);
+        second.isbn = "9784062758574".to_string();
 
         // When
-        let result = interactor.import("user1", books).await;
-
-        // Then
-        assert!(matches!(result, Err(UseCaseError::Unexpected(_))));
+        let result = interactor
+            .import(
+                "user1",
+                vec![first, second],
+                ImportOptionsDto {
+                    mode: ImportModeDto::UpsertByIsbn,
+                    ..Default::default()
+                },
+            )
+            .await
+            .unwrap();
+
+        // Then: the second entry updates the book the first one created
+        assert_eq!(result.created.len(), 1);
+        assert_eq!(result.updated.len(), 1);
+        assert_eq!(result.updated[0].id, result.created[0].id);
+        assert_eq!(result.updated[0].title, "Second");
     }
 
     #[tokio::test]
-    async fn import_books_invalid_title_returns_error() {
-        // Given
+    async fn import_books_skip_existing_skips_a_repeated_entry() {
+        // Given: no existing book, and the same entry twice without an ISBN
+        let mut book_repository = MockBookRepository::new();
+        book_repository
+            .expect_find_selection_with_tx()
+            .times(1)
+            .returning(|_, _, _| Ok(vec![]));
+        book_repository
+            .expect_create()
+            .times(1)
+            .returning(|_, _| Ok(1.into()));
+        let mut author_repository = MockAuthorRepository::new();
+        author_repository
+            .expect_find_by_ids_as_hash_map_with_tx()
+            .returning(|_, _, _| Ok(HashMap::new()));
+        author_repository
+            .expect_find_or_create_by_name()
+            .times(1)
+            .returning(|_, _, _, _| {
+                Ok(ResolvedAuthor {
+                    id: AuthorId::new(Uuid::new_v4()),
+                    created: true,
+                })
+            });
+
         let interactor = ImportBooksInteractor::new(
-            MockBookRepository::new(),
-            MockAuthorRepository::new(),
-            MockTransactionManager::new(),
+            book_repository,
+            author_repository,
+            make_transaction_manager(),
         );
-        let books = vec![import_entry("", vec![])];
+        let entry = || import_entry("Same Book", vec!["Author A"]);
 
         // When
-        let result = interactor.import("user1", books).await;
+        let result = interactor
+            .import(
+                "user1",
+                vec![entry(), entry()],
+                ImportOptionsDto {
+                    mode: ImportModeDto::SkipExisting,
+                    ..Default::default()
+                },
+            )
+            .await
+            .unwrap();
 
         // Then
-        assert!(matches!(result, Err(UseCaseError::Validation(_))));
+        assert_eq!(result.created.len(), 1);
+        assert_eq!(result.skipped.len(), 1);
+        assert_eq!(result.skipped[0].id, result.created[0].id);
     }
 
     #[tokio::test]
-    async fn import_books_invalid_isbn_returns_error() {
-        // Given
-        let mut entry = import_entry("Valid Title", vec![]);
-        entry.isbn = "1".to_string();
+    async fn import_books_upsert_by_title_and_authors_skips_an_unchanged_book() {
+        // Given: the entry matches an existing book by title and author names
+        // and changes nothing, so it is skipped without an event or a commit.
+        let existing_uuid = Uuid::new_v4();
+        let author_id = AuthorId::new(Uuid::new_v4());
+        let existing_author_id = author_id.clone();
+        let mut book_repository = MockBookRepository::new();
+        book_repository
+            .expect_find_selection_with_tx()
+            .times(1)
+            .returning(move |_, _, _| {
+                let mut book = make_book(existing_uuid);
+                let update = BookUpdate {
+                    author_ids: vec![existing_author_id.clone()],
+                    ..book.update_from_patch(BookPatch::default())
+                };
+                book.update(update, OffsetDateTime::now_utc());
+                Ok(vec![book])
+            });
+        book_repository.expect_update().times(0);
+        book_repository.expect_create().times(0);
+
+        let found_author_id = author_id.clone();
+        let mut author_repository = MockAuthorRepository::new();
+        author_repository
+            .expect_find_by_ids_as_hash_map_with_tx()
+            .times(1)
+            .returning(move |_, _, _| {
+                let author = Author::new(
+                    found_author_id.clone(),
+                    AuthorName::new("Author A".to_string()).unwrap(),
+                    OffsetDateTime::now_utc(),
+                )
+                .unwrap();
+                Ok(HashMap::from([(found_author_id.clone(), author)]))
+            });
+        let resolved_author_id = author_id.clone();
+        author_repository
+            .expect_find_or_create_by_name()
+            .times(1)
+            .returning(move |_, _, _, _| {
+                Ok(ResolvedAuthor {
+                    id: resolved_author_id.clone(),
+                    created: false,
+                })
+            });
+
         let interactor = ImportBooksInteractor::new(
-            MockBookRepository::new(),
-            MockAuthorRepository::new(),
-            MockTransactionManager::new(),
+            book_repository,
+            author_repository,
+            make_begin_only_transaction_manager(),
         );
+        let mut entry = import_entry("Test Book", vec!["Author A"]);
+        entry.owned = true;
 
         // When
-        let result = interactor.import("user1", vec![entry]).await;
+        let result = interactor
+            .import(
+                "user1",
+                vec![entry],
+                ImportOptionsDto {
+                    mode: ImportModeDto::UpsertByTitleAndAuthors,
+                    ..Default::default()
+                },
+            )
+            .await
+            .unwrap();
 
         // Then
-        assert!(matches!(result, Err(UseCaseError::Validation(_))));
+        assert!(result.created.is_empty());
+        assert!(result.updated.is_empty());
+        assert_eq!(result.skipped.len(), 1);
+        assert_eq!(result.skipped[0].id, existing_uuid.to_string());
+        assert_eq!(result.event_set_id, None);
     }
 
     #[tokio::test]
-    async fn import_books_invalid_author_name_returns_error() {
-        // Given
+    async fn import_books_skip_existing_leaves_matching_books_and_their_authors_alone() {
+        // Given: the entry matches by ISBN, so neither the book nor its
+        // authors are written.
+        let existing_uuid = Uuid::new_v4();
+        let mut book_repository = MockBookRepository::new();
+        book_repository
+            .expect_find_selection_with_tx()
+            .times(1)
+            .returning(move |_, _, _| {
+                Ok(vec![make_book_with_isbn(existing_uuid, "9784062758574")])
+            });
+        book_repository.expect_update().times(0);
+        book_repository.expect_create().times(0);
+        let mut author_repository = MockAuthorRepository::new();
+        author_repository
+            .expect_find_by_ids_as_hash_map_with_tx()
+            .returning(|_, _, _| Ok(HashMap::new()));
+        author_repository.expect_find_or_create_by_name().times(0);
+
         let interactor = ImportBooksInteractor::new(
-            MockBookRepository::new(),
-            MockAuthorRepository::new(),
-            MockTransactionManager::new(),
+            book_repository,
+            author_repository,
+            make_begin_only_transaction_manager(),
         );
-        let books = vec![import_entry("Valid Title", vec![""])];
+        let mut entry = import_entry("Another Title", vec!["New Author"]);
+        entry.isbn = "9784062758574".to_string();
 
         // When
-        let result = interactor.import("user1", books).await;
+        let result = interactor
+            .import(
+                "user1",
+                vec![entry],
+                ImportOptionsDto {
+                    mode: ImportModeDto::SkipExisting,
+                    ..Default::default()
+                },
+            )
+            .await
+            .unwrap();
 
         // Then
-        assert!(matches!(result, Err(UseCaseError::Validation(_))));
+        assert!(result.created.is_empty());
+        assert_eq!(result.skipped.len(), 1);
+        assert_eq!(result.skipped[0].title, "Test Book");
+        assert_eq!(result.event_set_id, None);
     }
 }
 
@@ -1049,7 +2506,8 @@ mod import_integration_tests {
             transaction::PgTransactionManager, user_repository::PgUserRepository,
         },
         use_case::{
-            dto::book::ImportBookEntryDto, interactor::book::ImportBooksInteractor,
+            dto::book::{ImportBookEntryDto, ImportModeDto, ImportOptionsDto},
+            interactor::book::ImportBooksInteractor,
             traits::book::ImportBooksUseCase,
         },
     };
@@ -1094,6 +2552,7 @@ mod import_integration_tests {
             .import(
                 user_id.as_str(),
                 vec![entry("Seed", vec!["Existing Author"])],
+                ImportOptionsDto::default(),
             )
             .await?;
 
@@ -1104,9 +2563,10 @@ mod import_integration_tests {
                     entry("Book One", vec!["Existing Author"]),
                     entry("Book Two", vec!["New Author"]),
                 ],
+                ImportOptionsDto::default(),
             )
             .await?;
-        assert_eq!(result.len(), 2);
+        assert_eq!(result.created.len(), 2);
 
         // Exactly two authors exist (Existing Author reused, New Author added).
         let author_rows: Vec<(String,)> =
@@ -1145,9 +2605,10 @@ mod import_integration_tests {
                     entry("Book One", vec!["Shared Author"]),
                     entry("Book Two", vec!["Shared Author"]),
                 ],
+                ImportOptionsDto::default(),
             )
             .await?;
-        assert_eq!(result.len(), 2);
+        assert_eq!(result.created.len(), 2);
 
         let (author_count,): (i64,) =
             sqlx::query_as("SELECT COUNT(*) FROM author WHERE user_id = $1")
@@ -1174,9 +2635,10 @@ mod import_integration_tests {
             .import(
                 user_id.as_str(),
                 vec![entry("Imported Book", vec!["Author A"])],
+                ImportOptionsDto::default(),
             )
             .await?;
-        assert_eq!(result.len(), 1);
+        assert_eq!(result.created.len(), 1);
 
         // event_set has the import_books row.
         let (es_op,): (String,) = sqlx::query_as(
@@ -1250,6 +2712,7 @@ mod import_integration_tests {
                     // Empty title fails domain validation, before any tx opens.
                     entry("", vec!["Author B"]),
                 ],
+                ImportOptionsDto::default(),
             )
             .await;
         assert!(result.is_err(), "import should fail on the invalid entry");
@@ -1277,6 +2740,181 @@ mod import_integration_tests {
         Ok(())
     }
 
+    #[sqlx::test]
+    async fn import_upsert_records_update_events_for_matched_books(
+        pool: PgPool,
+    ) -> anyhow::Result<()> {
+        let user_id = prepare_user(&pool, "user1").await?;
+
+        let mut seeded = entry("Seed", vec!["Author A"]);
+        seeded.isbn = "9784062758574".to_string();
+        let seed = interactor(&pool)
+            .import(user_id.as_str(), vec![seeded], ImportOptionsDto::default())
+            .await?;
+
+        let mut matching = entry("Renamed Seed", vec!["Author A"]);
+        matching.isbn = "978-4-06-275857-4".to_string();
+        let result = interactor(&pool)
+            .import(
+                user_id.as_str(),
+                vec![matching, entry("Fresh Book", vec!["Author B"])],
+                ImportOptionsDto {
+                    mode: ImportModeDto::UpsertByIsbn,
+                    ..Default::default()
+                },
+            )
+            .await?;
+        assert_eq!(result.created.len(), 1);
+        assert_eq!(result.updated.len(), 1);
+        assert_eq!(result.updated[0].id, seed.created[0].id);
+        assert_eq!(result.updated[0].title, "Renamed Seed");
+
+        let event_set_id = uuid::Uuid::parse_str(
+            result
+                .event_set_id
+                .as_deref()
+                .expect("the import should record an event set"),
+        )?;
+        let operations: Vec<(String,)> = sqlx::query_as(
+            "SELECT operation FROM book_event WHERE event_set_id = $1 ORDER BY operation",
+        )
+        .bind(event_set_id)
+        .fetch_all(&pool)
+        .await?;
+        assert_eq!(
+            operations,
+            vec![("create".to_string(),), ("update".to_string(),)]
+        );
+
+        let (book_count,): (i64,) = sqlx::query_as("SELECT COUNT(*) FROM book WHERE user_id = $1")
+            .bind(user_id.as_str())
+            .fetch_one(&pool)
+            .await?;
+        assert_eq!(book_count, 2);
+
+        Ok(())
+    }
+
+    #[sqlx::test]
+    async fn import_skipping_every_entry_records_no_event_set(pool: PgPool) -> anyhow::Result<()> {
+        let user_id = prepare_user(&pool, "user1").await?;
+
+        interactor(&pool)
+            .import(
+                user_id.as_str(),
+                vec![entry("Seed", vec!["Author A"])],
+                ImportOptionsDto::default(),
+            )
+            .await?;
+        let result = interactor(&pool)
+            .import(
+                user_id.as_str(),
+                vec![entry("Seed", vec!["Author A"])],
+                ImportOptionsDto {
+                    mode: ImportModeDto::SkipExisting,
+                    ..Default::default()
+                },
+            )
+            .await?;
+        assert_eq!(result.skipped.len(), 1);
+        assert_eq!(result.event_set_id, None);
+
+        let (event_set_count,): (i64,) =
+            sqlx::query_as("SELECT COUNT(*) FROM event_set WHERE user_id = $1")
+                .bind(user_id.as_str())
+                .fetch_one(&pool)
+                .await?;
+        assert_eq!(event_set_count, 1);
+
+        Ok(())
+    }
+
+    #[sqlx::test]
+    async fn import_dry_run_persists_nothing(pool: PgPool) -> anyhow::Result<()> {
+        let user_id = prepare_user(&pool, "user1").await?;
+
+        interactor(&pool)
+            .import(
+                user_id.as_str(),
+                vec![entry("Seed", vec!["Existing Author"])],
+                ImportOptionsDto::default(),
+            )
+            .await?;
+        let result = interactor(&pool)
+            .import(
+                user_id.as_str(),
+                vec![
+                    entry("Dry Book", vec!["Existing Author", "New Author"]),
+                    entry("", vec!["Other Author"]),
+                ],
+                ImportOptionsDto {
+                    dry_run: true,
+                    ..Default::default()
+                },
+            )
+            .await?;
+        assert_eq!(result.created.len(), 1);
+        assert_eq!(result.new_author_names, vec!["New Author".to_string()]);
+        assert_eq!(
+            result.reused_author_names,
+            vec!["Existing Author".to_string()]
+        );
+        assert_eq!(result.errors.len(), 1);
+        assert_eq!(result.errors[0].index, 1);
+        assert_eq!(result.event_set_id, None);
+
+        let (book_count,): (i64,) = sqlx::query_as("SELECT COUNT(*) FROM book WHERE user_id = $1")
+            .bind(user_id.as_str())
+            .fetch_one(&pool)
+            .await?;
+        assert_eq!(book_count, 1);
+        let (author_count,): (i64,) =
+            sqlx::query_as("SELECT COUNT(*) FROM author WHERE user_id = $1")
+                .bind(user_id.as_str())
+                .fetch_one(&pool)
+                .await?;
+        assert_eq!(author_count, 1);
+        let (event_set_count,): (i64,) =
+            sqlx::query_as("SELECT COUNT(*) FROM event_set WHERE user_id = $1")
+                .bind(user_id.as_str())
+                .fetch_one(&pool)
+                .await?;
+        assert_eq!(event_set_count, 1);
+
+        Ok(())
+    }
+
+    #[sqlx::test]
+    async fn import_partial_keeps_the_valid_entries(pool: PgPool) -> anyhow::Result<()> {
+        let user_id = prepare_user(&pool, "user1").await?;
+
+        let result = interactor(&pool)
+            .import(
+                user_id.as_str(),
+                vec![
+                    entry("Kept Book", vec!["Kept Author"]),
+                    entry("Dropped Book", vec![""]),
+                ],
+                ImportOptionsDto {
+                    partial: true,
+                    ..Default::default()
+                },
+            )
+            .await?;
+        assert_eq!(result.created.len(), 1);
+        assert_eq!(result.errors.len(), 1);
+        assert_eq!(result.errors[0].index, 1);
+        assert!(result.event_set_id.is_some());
+
+        let titles: Vec<(String,)> = sqlx::query_as("SELECT title FROM book WHERE user_id = $1")
+            .bind(user_id.as_str())
+            .fetch_all(&pool)
+            .await?;
+        assert_eq!(titles, vec![("Kept Book".to_string(),)]);
+
+        Ok(())
+    }
+
     #[sqlx::test]
     async fn import_empty_author_names(pool: PgPool) -> anyhow::Result<()> {
         let user_id = prepare_user(&pool, "user1").await?;
@@ -1285,9 +2923,10 @@ mod import_integration_tests {
             .import(
                 user_id.as_str(),
                 vec![entry("Book With No Authors", vec![])],
+                ImportOptionsDto::default(),
             )
             .await?;
-        assert_eq!(result.len(), 1);
+        assert_eq!(result.created.len(), 1);
 
         let (book_count,): (i64,) = sqlx::query_as("SELECT COUNT(*) FROM book WHERE user_id = $1")
             .bind(user_id.as_str())
@@ -0,0 +1,589 @@
+use std::collections::{HashMap, HashSet};
+
+use async_trait::async_trait;
+use time::OffsetDateTime;
+use uuid::Uuid;
+
+use crate::{
+    domain::{
+        entity::{
+            author::{Author, AuthorId, AuthorName, AuthorUpdate, validate_author_yomi},
+            book::{Book, BookId, BookUpdate},
+            event::EventSetOperation,
+            user::UserId,
+        },
+        repository::{
+            author_repository::AuthorRepository,
+            book_repository::BookRepository,
+            transaction::{TransactionEventSet, TransactionManager},
+        },
+    },
+    use_case::{
+        dto::{
+            book::TimeInfo,
+            change::{AppliedChangeDto, ApplyChangesResultDto, ChangeOperationDto},
+            mutation::{ExpectedVersionDto, MutationResultDto},
+        },
+        error::UseCaseError,
+        interactor::{
+            author::{find_author_for_update, validate_author_update},
+            book::{find_book_for_update, validate_book_update},
+        },
+        traits::change::ApplyChangesUseCase,
+    },
+};
+
+const MAX_CHANGE_BATCH: usize = 1000;
+
+// An operation with its temp ids resolved and its input validated. The whole
+// batch is planned before the transaction opens, so validation failures never
+// start one.
+enum PlannedChange {
+    CreateBook {
+        temp_id: Option<String>,
+        book: Book,
+    },
+    UpdateBook {
+        book_id: BookId,
+        update: BookUpdate,
+        expected_version: ExpectedVersionDto,
+    },
+    DeleteBook(BookId),
+    CreateAuthor {
+        temp_id: Option<String>,
+        author: Author,
+    },
+    UpdateAuthor {
+        author_id: AuthorId,
+        update: AuthorUpdate,
+        expected_version: ExpectedVersionDto,
+    },
+    DeleteAuthor(AuthorId),
+}
+
+// The ids generated for the temp ids of the creates planned so far.
+struct TempIds {
+    assigned: HashMap<String, (&'static str, Uuid)>,
+    // Every temp id in the batch, so that a reference made before its create
+    // is reported as such rather than as a malformed id.
+    declared: HashSet<String>,
+}
+
+impl TempIds {
+    fn new(operations: &[ChangeOperationDto]) -> Result<Self, UseCaseError> {
+        let mut declared = HashSet::new();
+        for operation in operations {
+            let temp_id = match operation {
+                ChangeOperationDto::CreateBook { temp_id, .. }
+                | ChangeOperationDto::CreateAuthor { temp_id, .. } => temp_id,
+                _ => continue,
+            };
+            let Some(temp_id) = temp_id else {
+                continue;
+            };
+            if temp_id.is_empty() {
+                return Err(UseCaseError::Validation(
+                    "temp ids cannot be empty".to_string(),
+                ));
+            }
+            if !declared.insert(temp_id.clone()) {
+                return Err(UseCaseError::Validation(format!(
+                    "temp id {temp_id} is given to more than one create"
+                )));
+            }
+        }
+        Ok(Self {
+            assigned: HashMap::new(),
+            declared,
+        })
+    }
+
+    fn assign(&mut self, temp_id: Option<&String>, entity_type: &'static str) -> Uuid {
+        let uuid = Uuid::new_v4();
+        if let Some(temp_id) = temp_id {
+            self.assigned.insert(temp_id.clone(), (entity_type, uuid));
+        }
+        uuid
+    }
+
+    // Replaces a temp id with the id generated for it. Anything else is taken
+    // to be the id of a stored entity.
+    fn resolve(&self, id: String, entity_type: &'static str) -> Result<String, UseCaseError> {
+        match self.assigned.get(&id) {
+            Some((assigned_type, uuid)) if *assigned_type == entity_type => {
+                Ok(uuid.hyphenated().to_string())
+            }
+            Some((assigned_type, _)) => Err(UseCaseError::Validation(format!(
+                "temp id {id} names {assigned_type}, not {entity_type}"
+            ))),
+            None if self.declared.contains(&id) => Err(UseCaseError::Validation(format!(
+                "temp id {id} is used before the operation that creates it"
+            ))),
+            None => Ok(id),
+        }
+    }
+
+    fn resolve_all(
+        &self,
+        ids: Vec<String>,
+        entity_type: &'static str,
+    ) -> Result<Vec<String>, UseCaseError> {
+        ids.into_iter()
+            .map(|id| self.resolve(id, entity_type))
+            .collect()
+    }
+}
+
+fn plan_change(
+    operation: ChangeOperationDto,
+    temp_ids: &mut TempIds,
+    now: OffsetDateTime,
+) -> Result<PlannedChange, UseCaseError> {
+    let planned = match operation {
+        ChangeOperationDto::CreateBook { temp_id, mut book } => {
+            book.author_ids = temp_ids.resolve_all(book.author_ids, "author")?;
+            let uuid = temp_ids.assign(temp_id.as_ref(), "book");
+            PlannedChange::CreateBook {
+                temp_id,
+                book: Book::try_from((uuid, book, TimeInfo::new(now, now)))?,
+            }
+        }
+        ChangeOperationDto::UpdateBook(mut book_data) => {
+            book_data.id = temp_ids.resolve(book_data.id, "book")?;
+            book_data.author_ids = temp_ids.resolve_all(book_data.author_ids, "author")?;
+            let (book_id, update, expected_version) = validate_book_update(book_data)?;
+            PlannedChange::UpdateBook {
+                book_id,
+                update,
+                expected_version,
+            }
+        }
+        ChangeOperationDto::DeleteBook { id } => {
+            let id = temp_ids.resolve(id, "book")?;
+            PlannedChange::DeleteBook(BookId::try_from(id.as_str())?)
+        }
+        ChangeOperationDto::CreateAuthor { temp_id, author } => {
+            let name = AuthorName::new(author.name)?;
+            let yomi = validate_author_yomi(author.yomi.unwrap_or_default())?;
+            let uuid = temp_ids.assign(temp_id.as_ref(), "author");
+            PlannedChange::CreateAuthor {
+                temp_id,
+                author: Author::new_with_yomi(AuthorId::new(uuid), name, yomi, now)?,
+            }
+        }
+        ChangeOperationDto::UpdateAuthor(mut author_data) => {
+            author_data.id = temp_ids.resolve(author_data.id, "author")?;
+            let (author_id, update, expected_version) = validate_author_update(author_data)?;
+            PlannedChange::UpdateAuthor {
+                author_id,
+                update,
+                expected_version,
+            }
+        }
+        ChangeOperationDto::DeleteAuthor { id } => {
+            let id = temp_ids.resolve(id, "author")?;
+            PlannedChange::DeleteAuthor(AuthorId::try_from(id.as_str())?)
+        }
+    };
+    Ok(planned)
+}
+
+pub struct ApplyChangesInteractor<BR, AR, TM> {
+    book_repository: BR,
+    author_repository: AR,
+    transaction_manager: TM,
+}
+
+impl<BR, AR, TM> ApplyChangesInteractor<BR, AR, TM> {
+    pub fn new(book_repository: BR, author_repository: AR, transaction_manager: TM) -> Self {
+        Self {
+            book_repository,
+            author_repository,
+            transaction_manager,
+        }
+    }
+}
+
+#[async_trait]
+impl<BR, AR, TM> ApplyChangesUseCase for ApplyChangesInteractor<BR, AR, TM>
+where
+    TM: TransactionManager,
+    BR: BookRepository<Transaction = TM::Transaction>,
+    AR: AuthorRepository<Transaction = TM::Transaction>,
+{
+    async fn apply(
+        &self,
+        user_id: &str,
+        operations: Vec<ChangeOperationDto>,
+    ) -> Result<ApplyChangesResultDto, UseCaseError> {
+        let user_id = UserId::new(user_id.to_string())?;
+        if operations.is_empty() {
+            return Err(UseCaseError::Validation(
+                "operations cannot be empty".to_string(),
+            ));
+        }
+        if operations.len() > MAX_CHANGE_BATCH {
+            return Err(UseCaseError::Validation(format!(
+                "operations cannot exceed {MAX_CHANGE_BATCH}"
+            )));
+        }
+
+        let now = OffsetDateTime::now_utc();
+        let mut temp_ids = TempIds::new(&operations)?;
+        let plan = operations
+            .into_iter()
+            .enumerate()
+            .map(|(index, operation)| {
+                plan_change(operation, &mut temp_ids, now).map_err(|err| in_operation(index, err))
+            })
+            .collect::<Result<Vec<PlannedChange>, UseCaseError>>()?;
+
+        let mut tx = self
+            .transaction_manager
+            .begin(&user_id, EventSetOperation::ApplyChanges)
+            .await?;
+        let mut applied = Vec::with_capacity(plan.len());
+        for (index, change) in plan.into_iter().enumerate() {
+            let applied_change = self
+                .apply_change(&mut tx, &user_id, change, now)
+                .await
+                .map_err(|err| in_operation(index, err))?;
+            applied.push(applied_change);
+        }
+        let event_set_id = tx.event_set_id().hyphenated().to_string();
+        self.transaction_manager.commit(tx).await?;
+
+        Ok(MutationResultDto::new(applied, event_set_id))
+    }
+}
+
+impl<BR, AR, TM> ApplyChangesInteractor<BR, AR, TM>
+where
+    TM: TransactionManager,
+    BR: BookRepository<Transaction = TM::Transaction>,
+    AR: AuthorRepository<Transaction = TM::Transaction>,
+{
+    async fn apply_change(
+        &self,
+        tx: &mut TM::Transaction,
+        user_id: &UserId,
+        change: PlannedChange,
+        now: OffsetDateTime,
+    ) -> Result<AppliedChangeDto, UseCaseError> {
+        Ok(match change {
+            PlannedChange::CreateBook { temp_id, book } => {
+                self.book_repository.create(tx, &book).await?;
+                AppliedChangeDto::Book {
+                    temp_id,
+                    book: book.into(),
+                }
+            }
+            PlannedChange::UpdateBook {
+                book_id,
+                update,
+                expected_version,
+            } => {
+                let mut book = find_book_for_update(
+                    &self.book_repository,
+                    tx,
+                    user_id,
+                    &book_id,
+                    expected_version,
+                )
+                .await?;
+                book.update(update, now);
+                self.book_repository.update(tx, &book).await?;
+                AppliedChangeDto::Book {
+                    temp_id: None,
+                    book: book.into(),
+                }
+            }
+            PlannedChange::DeleteBook(book_id) => {
+                self.book_repository.delete(tx, &book_id).await?;
+                AppliedChangeDto::DeletedBook {
+                    id: book_id.to_string(),
+                }
+            }
+            PlannedChange::CreateAuthor { temp_id, author } => {
+                self.author_repository.create(tx, &author).await?;
+                AppliedChangeDto::Author {
+                    temp_id,
+                    author: author.into(),
+                }
+            }
+            PlannedChange::UpdateAuthor {
+                author_id,
+                update,
+                expected_version,
+            } => {
+                let mut author = find_author_for_update(
+                    &self.author_repository,
+                    tx,
+                    user_id,
+                    &author_id,
+                    expected_version,
+                )
+                .await?;
+                author.update(update, now);
+                self.author_repository.update(tx, &author).await?;
+                AppliedChangeDto::Author {
+                    temp_id: None,
+                    author: author.into(),
+                }
+            }
+            PlannedChange::DeleteAuthor(author_id) => {
+                self.author_repository.delete(tx, &author_id, None).await?;
+                AppliedChangeDto::DeletedAuthor {
+                    id: author_id.to_string(),
+                }
+            }
+        })
+    }
+}
+
+// Tags an error with the operation it came from. A book or author that is not
+// found is an invalid operation, as a temp id that names nothing is.
+fn in_operation(index: usize, err: UseCaseError) -> UseCaseError {
+    match err {
+        UseCaseError::Validation(message) => {
+            UseCaseError::Validation(format!("operation {index}: {message}"))
+        }
+        UseCaseError::NotFound { .. } => {
+            UseCaseError::Validation(format!("operation {index}: {err}"))
+        }
+        UseCaseError::Conflict { message, current } => UseCaseError::Conflict {
+            message: format!("operation {index}: {message}"),
+            current,
+        },
+        err => err,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use mockall::predicate::always;
+
+    use crate::{
+        common::types::{BookFormat, BookStore},
+        domain::{
+            entity::event::EventSetOperation,
+            error::DomainError,
+            repository::{
+                author_repository::MockAuthorRepository, book_repository::MockBookRepository,
+                transaction::MockTransactionManager,
+            },
+        },
+        use_case::{
+            dto::{
+                author::CreateAuthorDto,
+                book::CreateBookDto,
+                change::{AppliedChangeDto, ChangeOperationDto},
+            },
+            error::UseCaseError,
+            interactor::change::ApplyChangesInteractor,
+            traits::change::ApplyChangesUseCase,
+        },
+    };
+
+    fn create_author(temp_id: &str) -> ChangeOperationDto {
+        ChangeOperationDto::CreateAuthor {
+            temp_id: Some(temp_id.to_string()),
+            author: CreateAuthorDto::new("New Author".to_string()),
+        }
+    }
+
+    fn create_book(temp_id: &str, author_ids: Vec<&str>) -> ChangeOperationDto {
+        ChangeOperationDto::CreateBook {
+            temp_id: Some(temp_id.to_string()),
+            book: CreateBookDto::new(
+                "New Book".to_string(),
+                author_ids.into_iter().map(str::to_string).collect(),
+                "".to_string(),
+                false,
+                false,
+                50,
+                BookFormat::Unknown,
+                BookStore::Unknown,
+            ),
+        }
+    }
+
+    #[tokio::test]
+    async fn apply_creates_a_book_by_the_author_created_before_it() {
+        // Given
+        let mut author_repository = MockAuthorRepository::new();
+        author_repository
+            .expect_create()
+            .times(1)
+            .returning(|_, _| Ok(1.into()));
+        let mut book_repository = MockBookRepository::new();
+        book_repository
+            .expect_create()
+            .times(1)
+            .returning(|_, _| Ok(2.into()));
+
+        let mut transaction_manager = MockTransactionManager::new();
+        transaction_manager
+            .expect_begin()
+            .withf(|_, operation| *operation == EventSetOperation::ApplyChanges)
+            .times(1)
+            .returning(|_, _| Ok(()));
+        transaction_manager
+            .expect_commit()
+            .times(1)
+            .returning(|_| Ok(()));
+
+        let interactor =
+            ApplyChangesInteractor::new(book_repository, author_repository, transaction_manager);
+
+        // When
+        let result = interactor
+            .apply(
+                "user1",
+                vec![
+                    create_author("author-1"),
+                    create_book("book-1", vec!["author-1"]),
+                ],
+            )
+            .await
+            .unwrap();
+
+        // Then: the book lists the id generated for the author.
+        let [
+            AppliedChangeDto::Author {
+                temp_id: author_temp_id,
+                author,
+            },
+            AppliedChangeDto::Book {
+                temp_id: book_temp_id,
+                book,
+            },
+        ] = result.value.as_slice()
+        else {
+            panic!("expected an author and a book, got {:?}", result.value);
+        };
+        assert_eq!(author_temp_id.as_deref(), Some("author-1"));
+        assert_eq!(book_temp_id.as_deref(), Some("book-1"));
+        assert_eq!(book.author_ids, vec![author.id.clone()]);
+    }
+
+    #[tokio::test]
+    async fn apply_rejects_a_temp_id_used_before_its_create() {
+        // Given: validation fails before any transaction, so bare mocks.
+        let interactor = ApplyChangesInteractor::new(
+            MockBookRepository::new(),
+            MockAuthorRepository::new(),
+            MockTransactionManager::new(),
+        );
+
+        // When
+        let result = interactor
+            .apply(
+                "user1",
+                vec![
+                    create_book("book-1", vec!["author-1"]),
+                    create_author("author-1"),
+                ],
+            )
+            .await;
+
+        // Then
+        assert!(
+            matches!(&result, Err(UseCaseError::Validation(message)) if message.starts_with("operation 0:")),
+            "expected a validation error for operation 0, got {result:?}"
+        );
+    }
+
+    #[tokio::test]
+    async fn apply_rejects_a_temp_id_of_the_wrong_entity_type() {
+        // Given
+        let interactor = ApplyChangesInteractor::new(
+            MockBookRepository::new(),
+            MockAuthorRepository::new(),
+            MockTransactionManager::new(),
+        );
+
+        // When
+        let result = interactor
+            .apply(
+                "user1",
+                vec![
+                    create_book("book-1", vec![]),
+                    ChangeOperationDto::DeleteAuthor {
+                        id: "book-1".to_string(),
+                    },
+                ],
+            )
+            .await;
+
+        // Then
+        assert!(matches!(result, Err(UseCaseError::Validation(_))));
+    }
+
+    #[tokio::test]
+    async fn apply_rejects_a_temp_id_given_twice() {
+        // Given
+        let interactor = ApplyChangesInteractor::new(
+            MockBookRepository::new(),
+            MockAuthorRepository::new(),
+            MockTransactionManager::new(),
+        );
+
+        // When
+        let result = interactor
+            .apply(
+                "user1",
+                vec![create_author("same"), create_book("same", vec![])],
+            )
+            .await;
+
+        // Then
+        assert!(matches!(result, Err(UseCaseError::Validation(_))));
+    }
+
+    #[tokio::test]
+    async fn apply_commits_nothing_when_an_operation_fails() {
+        // Given
+        let mut author_repository = MockAuthorRepository::new();
+        author_repository
+            .expect_create()
+            .returning(|_, _| Ok(1.into()));
+        let mut book_repository = MockBookRepository::new();
+        book_repository
+            .expect_delete()
+            .with(always(), always())
+            .returning(|_, book_id| {
+                Err(DomainError::NotFound {
+                    entity_type: "book",
+                    entity_id: book_id.to_string(),
+                    user_id: "user1".to_string(),
+                })
+            });
+
+        let mut transaction_manager = MockTransactionManager::new();
+        transaction_manager.expect_begin().returning(|_, _| Ok(()));
+        transaction_manager.expect_commit().never();
+
+        let interactor =
+            ApplyChangesInteractor::new(book_repository, author_repository, transaction_manager);
+
+        // When
+        let result = interactor
+            .apply(
+                "user1",
+                vec![
+                    create_author("author-1"),
+                    ChangeOperationDto::DeleteBook {
+                        id: "a1b2c3d4-e5f6-4890-abcd-ef1234567890".to_string(),
+                    },
+                ],
+            )
+            .await;
+
+        // Then: The missing book is reported with the operation that named it
+        assert!(
+            matches!(&result, Err(UseCaseError::Validation(message)) if message.starts_with("operation 1: book was not found")),
+            "expected a validation error for operation 1, got {result:?}"
+        );
+    }
+}
@@ -1,33 +1,50 @@
+use std::{
+    collections::{HashMap, HashSet},
+    hash::Hash,
+};
+
 use async_trait::async_trait;
-use time::OffsetDateTime;
+use time::{Duration, OffsetDateTime};
+use uuid::Uuid;
 
 use crate::{
+    common::sort::BookOrder,
     domain::{
         entity::{
             author::{Author, AuthorId, AuthorName},
             book::{Book, BookId},
-            event::{EventOperation, EventSetOperation},
+            event::{AuthorEvent, BookEvent, EventOperation, EventSetOperation, FieldChange},
+            event_set::EventSetId,
             user::UserId,
         },
         repository::{
             author_event_repository::AuthorEventRepository,
             author_repository::AuthorRepository,
             book_event_repository::BookEventRepository,
-            book_repository::BookRepository,
+            book_repository::{BookRepository, BookSelection},
+            event_set_repository::EventSetRepository,
             transaction::{TransactionEventSet, TransactionManager},
+            trash_repository::{PurgedTrash, TrashRepository},
         },
     },
     use_case::{
         dto::{
             author::AuthorDto,
             book::BookDto,
-            event::{AuthorEventDto, BookEventDto},
-            mutation::{MutationResultDto, RestoreAuthorResultDto, RestoreBookResultDto},
+            event::{AuthorEventDto, BookEventDto, FieldChangeDto, PurgedTrashDto},
+            event_set::{
+                RevertAction, RevertChangeDto, RevertConflictDto, RevertConflictReason,
+                RevertEntityType, RevertEventSetResultDto,
+            },
+            mutation::{
+                AuthorRemapDto, MutationResultDto, RestoreAuthorResultDto, RestoreBookResultDto,
+                RestoredBookDto,
+            },
         },
         error::UseCaseError,
         traits::event::{
-            ListAuthorEventsUseCase, ListBookEventsUseCase, RestoreAuthorUseCase,
-            RestoreBookUseCase,
+            ListAuthorEventsUseCase, ListBookEventsUseCase, PurgeTrashUseCase,
+            RestoreAuthorUseCase, RestoreBookUseCase, RevertEventSetUseCase,
         },
     },
 };
@@ -56,7 +73,7 @@ where
             .book_event_repository
             .find_by_book(&user_id, &book_id)
             .await?;
-        Ok(entries.into_iter().map(BookEventDto::from).collect())
+        Ok(BookEventDto::from_history(entries))
     }
 }
 
@@ -88,40 +105,53 @@ where
             .author_event_repository
             .find_by_author(&user_id, &author_id)
             .await?;
-        Ok(entries.into_iter().map(AuthorEventDto::from).collect())
+        Ok(AuthorEventDto::from_history(entries))
     }
 }
 
-pub struct RestoreBookInteractor<BR, BER, TM> {
+pub struct RestoreBookInteractor<BR, AR, BER, AER, TM> {
     book_repository: BR,
+    author_repository: AR,
     book_event_repository: BER,
+    author_event_repository: AER,
     transaction_manager: TM,
 }
 
-impl<BR, BER, TM> RestoreBookInteractor<BR, BER, TM> {
-    pub fn new(book_repository: BR, book_event_repository: BER, transaction_manager: TM) -> Self {
+impl<BR, AR, BER, AER, TM> RestoreBookInteractor<BR, AR, BER, AER, TM> {
+    pub fn new(
+        book_repository: BR,
+        author_repository: AR,
+        book_event_repository: BER,
+        author_event_repository: AER,
+        transaction_manager: TM,
+    ) -> Self {
         Self {
             book_repository,
+            author_repository,
             book_event_repository,
+            author_event_repository,
             transaction_manager,
         }
     }
 }
 
 #[async_trait]
-impl<BR, BER, TM> RestoreBookUseCase for RestoreBookInteractor<BR, BER, TM>
+impl<BR, AR, BER, AER, TM> RestoreBookUseCase for RestoreBookInteractor<BR, AR, BER, AER, TM>
 where
     TM: TransactionManager,
     BR: BookRepository<Transaction = TM::Transaction>,
+    AR: AuthorRepository<Transaction = TM::Transaction>,
     BER: BookEventRepository,
+    AER: AuthorEventRepository,
 {
     async fn restore(
         &self,
         user_id: &str,
         event_id: i64,
+        cascade: bool,
     ) -> Result<RestoreBookResultDto, UseCaseError> {
         let user_id = UserId::new(user_id.to_string())?;
-        let event = self
+        let mut event = self
             .book_event_repository
             .find_by_event_id(&user_id, event_id)
             .await?
@@ -137,51 +167,26 @@ where
             | EventOperation::Restore
             | EventOperation::Snapshot => {
                 let restored_at = OffsetDateTime::now_utc();
-                let created_at = event.book_created_at.ok_or_else(|| {
-                    UseCaseError::Validation("book_event book_created_at is null".to_string())
-                })?;
-                event.book_updated_at.ok_or_else(|| {
-                    UseCaseError::Validation("book_event book_updated_at is null".to_string())
-                })?;
-                let book = Book::new(
-                    event.book_id,
-                    event.title.ok_or_else(|| {
-                        UseCaseError::Validation("book_event title is null".to_string())
-                    })?,
-                    event.author_ids,
-                    event.isbn.ok_or_else(|| {
-                        UseCaseError::Validation("book_event isbn is null".to_string())
-                    })?,
-                    event.read.ok_or_else(|| {
-                        UseCaseError::Validation("book_event read is null".to_string())
-                    })?,
-                    event.owned.ok_or_else(|| {
-                        UseCaseError::Validation("book_event owned is null".to_string())
-                    })?,
-                    event.priority.ok_or_else(|| {
-                        UseCaseError::Validation("book_event priority is null".to_string())
-                    })?,
-                    event.format.ok_or_else(|| {
-                        UseCaseError::Validation("book_event format is null".to_string())
-                    })?,
-                    event.store.ok_or_else(|| {
-                        UseCaseError::Validation("book_event store is null".to_string())
-                    })?,
-                    created_at,
-                    restored_at,
-                )?;
-
-                let dto = BookDto::from(book.clone());
+                let mut restored = RestoredBookDto::new(None);
                 let mut tx = self
                     .transaction_manager
                     .begin(&user_id, EventSetOperation::RestoreBook)
                     .await?;
+                if cascade {
+                    let author_ids = std::mem::take(&mut event.author_ids);
+                    event.author_ids = self
+                        .resolve_authors(&mut tx, &user_id, author_ids, restored_at, &mut restored)
+                        .await?;
+                }
+                let book = book_from_event(event, restored_at)?;
+
+                restored.book = Some(BookDto::from(book.clone()));
                 self.book_repository
                     .restore(&mut tx, event_id, Some(book))
                     .await?;
                 let event_set_id = tx.event_set_id().hyphenated().to_string();
                 self.transaction_manager.commit(tx).await?;
-                Ok(MutationResultDto::new(Some(dto), event_set_id))
+                Ok(MutationResultDto::new(restored, event_set_id))
             }
             EventOperation::Delete => {
                 let mut tx = self
@@ -193,7 +198,10 @@ where
                     .await?;
                 let event_set_id = tx.event_set_id().hyphenated().to_string();
                 self.transaction_manager.commit(tx).await?;
-                Ok(MutationResultDto::new(None, event_set_id))
+                Ok(MutationResultDto::new(
+                    RestoredBookDto::new(None),
+                    event_set_id,
+                ))
             }
             EventOperation::MergeAsDestination => Err(UseCaseError::Validation(
                 "merge_as_destination events cannot be restored".to_string(),
@@ -202,6 +210,102 @@ where
     }
 }
 
+impl<BR, AR, BER, AER, TM> RestoreBookInteractor<BR, AR, BER, AER, TM>
+where
+    TM: TransactionManager,
+    AR: AuthorRepository<Transaction = TM::Transaction>,
+    AER: AuthorEventRepository,
+{
+    // Makes every author in `author_ids` exist again and returns the ids the
+    // book should list. Deleted authors come back in their last known state;
+    // authors merged away are replaced by where the merges lead.
+    async fn resolve_authors(
+        &self,
+        tx: &mut TM::Transaction,
+        user_id: &UserId,
+        author_ids: Vec<AuthorId>,
+        restored_at: OffsetDateTime,
+        restored: &mut RestoredBookDto,
+    ) -> Result<Vec<AuthorId>, UseCaseError> {
+        let mut resolved_ids: Vec<AuthorId> = Vec::new();
+        for author_id in author_ids {
+            let mut current = author_id.clone();
+            let mut visited = Vec::new();
+            let author = loop {
+                if let Some(author) = self
+                    .author_repository
+                    .find_by_id_with_tx(tx, user_id, &current)
+                    .await?
+                {
+                    break author;
+                }
+                visited.push(current.clone());
+
+                let not_found = || UseCaseError::NotFound {
+                    entity_type: "author_event",
+                    entity_id: current.to_string(),
+                    user_id: user_id.as_str().to_string(),
+                };
+                let history = self
+                    .author_event_repository
+                    .find_by_author(user_id, &current)
+                    .await?;
+                let mut stateful = history.into_iter().filter(AuthorEvent::has_state);
+                let latest = stateful.next().ok_or_else(not_found)?;
+                if let Some(destination) = latest.merge_destination() {
+                    if visited.contains(&destination) {
+                        return Err(UseCaseError::Unexpected(format!(
+                            "author merges loop back to {destination}"
+                        )));
+                    }
+                    current = destination;
+                    continue;
+                }
+
+                let last_known = std::iter::once(latest)
+                    .chain(stateful)
+                    .find(AuthorEvent::is_present)
+                    .ok_or_else(not_found)?;
+                let source_event_id = last_known.event_id;
+                let author = author_from_event(last_known, restored_at)?;
+                // Names are unique, and another author may have taken this
+                // one since it was deleted.
+                if let Some(holder) = self
+                    .author_repository
+                    .find_by_name_with_tx(tx, user_id, author.name())
+                    .await?
+                {
+                    return Err(UseCaseError::Conflict {
+                        message: format!(
+                            "author {} cannot be restored as \"{}\": author {} has that name now.",
+                            author.id(),
+                            author.name().as_str(),
+                            holder.id()
+                        ),
+                        current: None,
+                    });
+                }
+                self.author_repository
+                    .restore(tx, source_event_id, Some(author.clone()))
+                    .await?;
+                restored.restored_authors.push(author.clone().into());
+                break author;
+            };
+
+            if author.id() != &author_id {
+                restored.remapped_authors.push(AuthorRemapDto {
+                    merged_author_id: author_id.to_string(),
+                    author: author.clone().into(),
+                });
+            }
+            if !resolved_ids.contains(author.id()) {
+                resolved_ids.push(author.id().clone());
+            }
+        }
+        Ok(resolved_ids)
+    }
+}
+
 pub struct RestoreAuthorInteractor<AR, AER, TM> {
     author_repository: AR,
     author_event_repository: AER,
@@ -250,27 +354,7 @@ where
             | EventOperation::Update
             | EventOperation::Restore
             | EventOperation::Snapshot => {
-                let restored_at = OffsetDateTime::now_utc();
-                let name = event.name.ok_or_else(|| {
-                    UseCaseError::Validation("author_event name is null".to_string())
-                })?;
-                let yomi = event.yomi.ok_or_else(|| {
-                    UseCaseError::Validation("author_event yomi is null".to_string())
-                })?;
-                let author_name = AuthorName::new(name)?;
-                let created_at = event.author_created_at.ok_or_else(|| {
-                    UseCaseError::Validation("author_event author_created_at is null".to_string())
-                })?;
-                event.author_updated_at.ok_or_else(|| {
-                    UseCaseError::Validation("author_event author_updated_at is null".to_string())
-                })?;
-                let author = Author::new_with_timestamps(
-                    event.author_id,
-                    author_name,
-                    yomi,
-                    created_at,
-                    restored_at,
-                )?;
+                let author = author_from_event(event, OffsetDateTime::now_utc())?;
 
                 let dto = AuthorDto::from(author.clone());
                 let mut tx = self
@@ -303,6 +387,678 @@ where
     }
 }
 
+pub struct RevertEventSetInteractor<BR, AR, BER, AER, ESR, TM> {
+    book_repository: BR,
+    author_repository: AR,
+    book_event_repository: BER,
+    author_event_repository: AER,
+    event_set_repository: ESR,
+    transaction_manager: TM,
+}
+
+impl<BR, AR, BER, AER, ESR, TM> RevertEventSetInteractor<BR, AR, BER, AER, ESR, TM> {
+    pub fn new(
+        book_repository: BR,
+        author_repository: AR,
+        book_event_repository: BER,
+        author_event_repository: AER,
+        event_set_repository: ESR,
+        transaction_manager: TM,
+    ) -> Self {
+        Self {
+            book_repository,
+            author_repository,
+            book_event_repository,
+            author_event_repository,
+            event_set_repository,
+            transaction_manager,
+        }
+    }
+}
+
+// What reverting an event set would write, and what keeps it from being
+// written.
+struct RevertPlan {
+    books: Vec<PlannedRevert<BookId, Book>>,
+    authors: Vec<PlannedRevert<AuthorId, Author>>,
+    conflicts: Vec<RevertConflictDto>,
+}
+
+impl RevertPlan {
+    fn is_empty(&self) -> bool {
+        self.books.is_empty() && self.authors.is_empty()
+    }
+
+    fn into_result(self, event_set_id: Option<String>) -> RevertEventSetResultDto {
+        RevertEventSetResultDto {
+            event_set_id,
+            changes: changes_of(&self.books, &self.authors),
+            conflicts: self.conflicts,
+        }
+    }
+}
+
+fn changes_of(
+    books: &[PlannedRevert<BookId, Book>],
+    authors: &[PlannedRevert<AuthorId, Author>],
+) -> Vec<RevertChangeDto> {
+    books
+        .iter()
+        .map(|planned| planned.change.clone())
+        .chain(authors.iter().map(|planned| planned.change.clone()))
+        .collect()
+}
+
+// The inverse of one entity's events in the reverted set.
+struct PlannedRevert<I, T> {
+    id: I,
+    change: RevertChangeDto,
+    // Recorded as the source of the restore event.
+    source_event_id: i64,
+    // The state to restore; None removes the entity.
+    entity: Option<T>,
+}
+
+#[async_trait]
+impl<BR, AR, BER, AER, ESR, TM> RevertEventSetUseCase
+    for RevertEventSetInteractor<BR, AR, BER, AER, ESR, TM>
+where
+    TM: TransactionManager,
+    BR: BookRepository<Transaction = TM::Transaction>,
+    AR: AuthorRepository<Transaction = TM::Transaction>,
+    BER: BookEventRepository,
+    AER: AuthorEventRepository,
+    ESR: EventSetRepository,
+{
+    async fn revert(
+        &self,
+        user_id: &str,
+        event_set_id: &str,
+        dry_run: bool,
+    ) -> Result<RevertEventSetResultDto, UseCaseError> {
+        let user_id = UserId::new(user_id.to_string())?;
+        let event_set_id = EventSetId::try_from(event_set_id).map_err(UseCaseError::Validation)?;
+        let event_set = self
+            .event_set_repository
+            .find_by_id(&user_id, &event_set_id)
+            .await?
+            .ok_or_else(|| UseCaseError::NotFound {
+                entity_type: "event_set",
+                entity_id: event_set_id.to_string(),
+                user_id: user_id.as_str().to_string(),
+            })?;
+        // A snapshot records the library as it already was. With nothing
+        // before it, reverting it would remove every book and author.
+        if event_set.operation == EventSetOperation::SnapshotAll {
+            return Err(UseCaseError::Validation(format!(
+                "event set {event_set_id} is a snapshot and cannot be reverted."
+            )));
+        }
+
+        // Dropping the transaction, as dry runs and plans with conflicts do,
+        // leaves everything as it was.
+        let restored_at = OffsetDateTime::now_utc();
+        let mut tx = self
+            .transaction_manager
+            .begin(&user_id, EventSetOperation::RevertEventSet)
+            .await?;
+        let plan = self
+            .plan(&mut tx, &user_id, &event_set_id, restored_at)
+            .await?;
+        if dry_run || !plan.conflicts.is_empty() || plan.is_empty() {
+            return Ok(plan.into_result(None));
+        }
+
+        // The plan was made before its rows were locked. Once they are,
+        // planning again catches any change made in between.
+        self.lock_planned(&mut tx, &user_id, &plan).await?;
+        let plan = self
+            .plan(&mut tx, &user_id, &event_set_id, restored_at)
+            .await?;
+        if !plan.conflicts.is_empty() || plan.is_empty() {
+            return Ok(plan.into_result(None));
+        }
+        let RevertPlan {
+            books,
+            authors,
+            conflicts,
+        } = plan;
+        let changes = changes_of(&books, &authors);
+
+        // Authors come back before the books listing them and are removed
+        // after the books that listed them.
+        let (restored_authors, removed_authors): (Vec<_>, Vec<_>) = authors
+            .into_iter()
+            .partition(|planned| planned.entity.is_some());
+        for planned in restored_authors {
+            self.author_repository
+                .restore(&mut tx, planned.source_event_id, planned.entity)
+                .await?;
+        }
+        for planned in books {
+            self.book_repository
+                .restore(&mut tx, planned.source_event_id, planned.entity)
+                .await?;
+        }
+        for planned in removed_authors {
+            self.author_repository
+                .restore(&mut tx, planned.source_event_id, None)
+                .await?;
+        }
+        let revert_event_set_id = tx.event_set_id().hyphenated().to_string();
+        self.transaction_manager.commit(tx).await?;
+
+        Ok(RevertEventSetResultDto {
+            event_set_id: Some(revert_event_set_id),
+            changes,
+            conflicts,
+        })
+    }
+}
+
+impl<BR, AR, BER, AER, ESR, TM> RevertEventSetInteractor<BR, AR, BER, AER, ESR, TM>
+where
+    TM: TransactionManager,
+    BR: BookRepository<Transaction = TM::Transaction>,
+    AR: AuthorRepository<Transaction = TM::Transaction>,
+    BER: BookEventRepository,
+    AER: AuthorEventRepository,
+{
+    async fn plan(
+        &self,
+        tx: &mut TM::Transaction,
+        user_id: &UserId,
+        event_set_id: &EventSetId,
+        restored_at: OffsetDateTime,
+    ) -> Result<RevertPlan, UseCaseError> {
+        let mut conflicts = Vec::new();
+        let books = self
+            .plan_books(user_id, event_set_id, restored_at, &mut conflicts)
+            .await?;
+        let authors = self
+            .plan_authors(user_id, event_set_id, restored_at, &mut conflicts)
+            .await?;
+        self.check_author_links(user_id, &books, &authors, &mut conflicts)
+            .await?;
+        self.check_author_names(tx, user_id, &authors, &mut conflicts)
+            .await?;
+        Ok(RevertPlan {
+            books,
+            authors,
+            conflicts,
+        })
+    }
+
+    // Locks the planned books and authors, and the authors restored books
+    // list, so that nothing else changes them or links books to them until
+    // `tx` ends. Deleted entities have no row to lock.
+    async fn lock_planned(
+        &self,
+        tx: &mut TM::Transaction,
+        user_id: &UserId,
+        plan: &RevertPlan,
+    ) -> Result<(), UseCaseError> {
+        let book_ids: Vec<BookId> = plan
+            .books
+            .iter()
+            .map(|planned| planned.id.clone())
+            .collect();
+        if !book_ids.is_empty() {
+            self.book_repository
+                .find_selection_with_tx(tx, user_id, &BookSelection::Ids(book_ids))
+                .await?;
+        }
+        let mut author_ids: Vec<AuthorId> = plan
+            .authors
+            .iter()
+            .map(|planned| planned.id.clone())
+            .chain(
+                plan.books
+                    .iter()
+                    .filter_map(|planned| planned.entity.as_ref())
+                    .flat_map(|book| book.author_ids().iter().cloned()),
+            )
+            .collect();
+        author_ids.sort_by_key(AuthorId::to_uuid);
+        author_ids.dedup();
+        if !author_ids.is_empty() {
+            self.author_repository
+                .lock_with_book_ids(tx, user_id, &author_ids)
+                .await?;
+        }
+        Ok(())
+    }
+
+    // Each book goes back to its state before its earliest event in the set.
+    async fn plan_books(
+        &self,
+        user_id: &UserId,
+        event_set_id: &EventSetId,
+        restored_at: OffsetDateTime,
+        conflicts: &mut Vec<RevertConflictDto>,
+    ) -> Result<Vec<PlannedRevert<BookId, Book>>, UseCaseError> {
+        let mut events = self
+            .book_event_repository
+            .find_by_event_set(user_id, event_set_id)
+            .await?;
+        // Snapshots restate a book without changing it.
+        events.retain(|event| event.operation != EventOperation::Snapshot);
+        let spans = first_and_last_by_entity(
+            events,
+            |e| (e.changed_at, e.event_id),
+            |e| e.book_id.to_uuid(),
+        );
+        let first_ids: Vec<i64> = spans.iter().map(|(first, _)| first.event_id).collect();
+        let last_ids: Vec<i64> = spans.iter().map(|(_, last)| last.event_id).collect();
+        let mut previous = self
+            .book_event_repository
+            .find_previous(user_id, &first_ids)
+            .await?;
+        let later = self
+            .book_event_repository
+            .find_next(user_id, &last_ids)
+            .await?;
+
+        let mut planned = Vec::new();
+        for (first, last) in spans {
+            let target = previous
+                .remove(&first.event_id)
+                .filter(BookEvent::is_present);
+            let changes: Vec<FieldChangeDto> = match &target {
+                Some(target) => target.changes_since(Some(&last)),
+                None => last
+                    .changes_since(None)
+                    .into_iter()
+                    .map(FieldChange::reversed)
+                    .collect(),
+            }
+            .into_iter()
+            .map(FieldChangeDto::from)
+            .collect();
+            if changes.is_empty() {
+                continue;
+            }
+
+            let entity_id = last.book_id.to_string();
+            if let Some(later) = later.get(&last.event_id) {
+                conflicts.push(RevertConflictDto {
+                    entity_type: RevertEntityType::Book,
+                    entity_id: entity_id.clone(),
+                    reason: RevertConflictReason::ChangedLater {
+                        event_id: later.event_id,
+                        event_set_id: later.event_set_id.to_string(),
+                    },
+                });
+            }
+            planned.push(PlannedRevert {
+                change: RevertChangeDto {
+                    entity_type: RevertEntityType::Book,
+                    entity_id,
+                    action: revert_action(target.is_some()),
+                    changes,
+                },
+                source_event_id: target.as_ref().map_or(last.event_id, |t| t.event_id),
+                entity: target
+                    .map(|target| book_from_event(target, restored_at))
+                    .transpose()?,
+                id: last.book_id,
+            });
+        }
+        Ok(planned)
+    }
+
+    // Each author goes back to its state before its earliest stateful event in
+    // the set. Merge markers carry no state and snapshots change nothing, so
+    // both are left alone.
+    async fn plan_authors(
+        &self,
+        user_id: &UserId,
+        event_set_id: &EventSetId,
+        restored_at: OffsetDateTime,
+        conflicts: &mut Vec<RevertConflictDto>,
+    ) -> Result<Vec<PlannedRevert<AuthorId, Author>>, UseCaseError> {
+        let mut events = self
+            .author_event_repository
+            .find_by_event_set(user_id, event_set_id)
+            .await?;
+        events.retain(|event| event.has_state() && event.operation != EventOperation::Snapshot);
+        let spans = first_and_last_by_entity(
+            events,
+            |e| (e.changed_at, e.event_id),
+            |e| e.author_id.clone(),
+        );
+        let first_ids: Vec<i64> = spans.iter().map(|(first, _)| first.event_id).collect();
+        let last_ids: Vec<i64> = spans.iter().map(|(_, last)| last.event_id).collect();
+        let mut previous = self
+            .author_event_repository
+            .find_previous(user_id, &first_ids)
+            .await?;
+        let later = self
+            .author_event_repository
+            .find_next(user_id, &last_ids)
+            .await?;
+
+        let mut planned = Vec::new();
+        for (first, last) in spans {
+            let target = previous
+                .remove(&first.event_id)
+                .filter(AuthorEvent::is_present);
+            let changes: Vec<FieldChangeDto> = match &target {
+                Some(target) => target.changes_since(Some(&last)),
+                None => last
+                    .changes_since(None)
+                    .into_iter()
+                    .map(FieldChange::reversed)
+                    .collect(),
+            }
+            .into_iter()
+            .map(FieldChangeDto::from)
+            .collect();
+            if changes.is_empty() {
+                continue;
+            }
+
+            let entity_id = last.author_id.to_string();
+            if let Some(later) = later.get(&last.event_id) {
+                conflicts.push(RevertConflictDto {
+                    entity_type: RevertEntityType::Author,
+                    entity_id: entity_id.clone(),
+                    reason: RevertConflictReason::ChangedLater {
+                        event_id: later.event_id,
+                        event_set_id: later.event_set_id.to_string(),
+                    },
+                });
+            }
+            planned.push(PlannedRevert {
+                change: RevertChangeDto {
+                    entity_type: RevertEntityType::Author,
+                    entity_id,
+                    action: revert_action(target.is_some()),
+                    changes,
+                },
+                source_event_id: target.as_ref().map_or(last.event_id, |t| t.event_id),
+                entity: target
+                    .map(|target| author_from_event(target, restored_at))
+                    .transpose()?,
+                id: last.author_id,
+            });
+        }
+        Ok(planned)
+    }
+
+    // Restored books must only list authors that exist after the revert, and
+    // removed authors must not be listed by books that keep them.
+    async fn check_author_links(
+        &self,
+        user_id: &UserId,
+        books: &[PlannedRevert<BookId, Book>],
+        authors: &[PlannedRevert<AuthorId, Author>],
+        conflicts: &mut Vec<RevertConflictDto>,
+    ) -> Result<(), UseCaseError> {
+        let restored: HashSet<&AuthorId> = authors
+            .iter()
+            .filter(|planned| planned.entity.is_some())
+            .map(|planned| &planned.id)
+            .collect();
+        let removed: Vec<AuthorId> = authors
+            .iter()
+            .filter(|planned| planned.entity.is_none())
+            .map(|planned| planned.id.clone())
+            .collect();
+
+        let mut unresolved: Vec<AuthorId> = books
+            .iter()
+            .filter_map(|planned| planned.entity.as_ref())
+            .flat_map(|book| book.author_ids())
+            .filter(|id| !restored.contains(id) && !removed.contains(id))
+            .cloned()
+            .collect();
+        unresolved.sort_by_key(|id| id.to_string());
+        unresolved.dedup();
+        let existing = if unresolved.is_empty() {
+            HashMap::new()
+        } else {
+            self.author_repository
+                .find_by_ids_as_hash_map(user_id, &unresolved)
+                .await?
+        };
+        for planned in books {
+            let Some(book) = &planned.entity else {
+                continue;
+            };
+            let missing: Vec<String> = book
+                .author_ids()
+                .iter()
+                .filter(|id| !restored.contains(id) && !existing.contains_key(*id))
+                .map(ToString::to_string)
+                .collect();
+            if !missing.is_empty() {
+                conflicts.push(RevertConflictDto {
+                    entity_type: RevertEntityType::Book,
+                    entity_id: planned.change.entity_id.clone(),
+                    reason: RevertConflictReason::MissingAuthors {
+                        author_ids: missing,
+                    },
+                });
+            }
+        }
+
+        if removed.is_empty() {
+            return Ok(());
+        }
+        let books_by_author = self
+            .book_repository
+            .find_by_author_ids_as_hash_map(user_id, &removed, &BookOrder::default())
+            .await?;
+        let reverted_books: HashMap<Uuid, Option<&Book>> = books
+            .iter()
+            .map(|planned| (planned.id.to_uuid(), planned.entity.as_ref()))
+            .collect();
+        for author_id in &removed {
+            let book_ids: Vec<String> = books_by_author
+                .get(author_id)
+                .into_iter()
+                .flatten()
+                .filter(|book| match reverted_books.get(&book.id().to_uuid()) {
+                    Some(reverted) => {
+                        reverted.is_some_and(|book| book.author_ids().contains(author_id))
+                    }
+                    None => true,
+                })
+                .map(|book| book.id().to_string())
+                .collect();
+            if !book_ids.is_empty() {
+                conflicts.push(RevertConflictDto {
+                    entity_type: RevertEntityType::Author,
+                    entity_id: author_id.to_string(),
+                    reason: RevertConflictReason::HasAssociatedBooks { book_ids },
+                });
+            }
+        }
+        Ok(())
+    }
+
+    // Names are unique, and another author may have taken the name an author
+    // goes back to since. The name is locked until `tx` ends.
+    async fn check_author_names(
+        &self,
+        tx: &mut TM::Transaction,
+        user_id: &UserId,
+        authors: &[PlannedRevert<AuthorId, Author>],
+        conflicts: &mut Vec<RevertConflictDto>,
+    ) -> Result<(), UseCaseError> {
+        for planned in authors {
+            let Some(author) = &planned.entity else {
+                continue;
+            };
+            let holder = self
+                .author_repository
+                .find_by_name_with_tx(tx, user_id, author.name())
+                .await?;
+            if let Some(holder) = holder.filter(|holder| holder.id() != &planned.id) {
+                conflicts.push(RevertConflictDto {
+                    entity_type: RevertEntityType::Author,
+                    entity_id: planned.change.entity_id.clone(),
+                    reason: RevertConflictReason::NameTaken {
+                        author_id: holder.id().to_string(),
+                    },
+                });
+            }
+        }
+        Ok(())
+    }
+}
+
+pub struct PurgeTrashInteractor<TR, TM> {
+    trash_repository: TR,
+    transaction_manager: TM,
+}
+
+impl<TR, TM> PurgeTrashInteractor<TR, TM> {
+    pub fn new(trash_repository: TR, transaction_manager: TM) -> Self {
+        Self {
+            trash_repository,
+            transaction_manager,
+        }
+    }
+}
+
+#[async_trait]
+impl<TR, TM> PurgeTrashUseCase for PurgeTrashInteractor<TR, TM>
+where
+    TM: TransactionManager,
+    TR: TrashRepository<Transaction = TM::Transaction>,
+{
+    async fn purge(
+        &self,
+        user_id: &str,
+        retention_days: i32,
+    ) -> Result<PurgedTrashDto, UseCaseError> {
+        let user_id = UserId::new(user_id.to_string())?;
+        if retention_days < 0 {
+            return Err(UseCaseError::Validation(format!(
+                "retention days must not be negative, got {retention_days}."
+            )));
+        }
+        let deleted_before = OffsetDateTime::now_utc() - Duration::days(retention_days.into());
+        let mut tx = self
+            .transaction_manager
+            .begin(&user_id, EventSetOperation::PurgeTrash)
+            .await?;
+        let purged = self.trash_repository.purge(&mut tx, deleted_before).await?;
+        // A purge that found nothing leaves no event set behind; dropping the
+        // transaction rolls it back.
+        if purged == PurgedTrash::default() {
+            return Ok(PurgedTrashDto::from(purged));
+        }
+        let event_set_id = tx.event_set_id().hyphenated().to_string();
+        self.transaction_manager.commit(tx).await?;
+        Ok(PurgedTrashDto {
+            event_set_id: Some(event_set_id),
+            ..PurgedTrashDto::from(purged)
+        })
+    }
+}
+
+fn revert_action(restores_state: bool) -> RevertAction {
+    if restores_state {
+        RevertAction::Restore
+    } else {
+        RevertAction::Remove
+    }
+}
+
+// Pairs the earliest and latest of each entity's events, in the order the
+// entities were first changed.
+fn first_and_last_by_entity<E, O, K>(
+    mut events: Vec<E>,
+    order: impl Fn(&E) -> O,
+    entity: impl Fn(&E) -> K,
+) -> Vec<(E, E)>
+where
+    E: Clone,
+    O: Ord,
+    K: Eq + Hash,
+{
+    events.sort_by_key(&order);
+    let mut spans: Vec<(E, E)> = Vec::new();
+    let mut positions: HashMap<K, usize> = HashMap::new();
+    for event in events {
+        match positions.get(&entity(&event)) {
+            Some(&position) => spans[position].1 = event,
+            None => {
+                positions.insert(entity(&event), spans.len());
+                spans.push((event.clone(), event));
+            }
+        }
+    }
+    spans
+}
+
+// Rebuilds the book recorded by a stateful event, as restored at `restored_at`.
+fn book_from_event(event: BookEvent, restored_at: OffsetDateTime) -> Result<Book, UseCaseError> {
+    let created_at = event.book_created_at.ok_or_else(|| {
+        UseCaseError::Validation("book_event book_created_at is null".to_string())
+    })?;
+    event.book_updated_at.ok_or_else(|| {
+        UseCaseError::Validation("book_event book_updated_at is null".to_string())
+    })?;
+    Ok(Book::new(
+        event.book_id,
+        event
+            .title
+            .ok_or_else(|| UseCaseError::Validation("book_event title is null".to_string()))?,
+        event.author_ids,
+        event
+            .isbn
+            .ok_or_else(|| UseCaseError::Validation("book_event isbn is null".to_string()))?,
+        event
+            .read
+            .ok_or_else(|| UseCaseError::Validation("book_event read is null".to_string()))?,
+        event
+            .owned
+            .ok_or_else(|| UseCaseError::Validation("book_event owned is null".to_string()))?,
+        event
+            .priority
+            .ok_or_else(|| UseCaseError::Validation("book_event priority is null".to_string()))?,
+        event
+            .format
+            .ok_or_else(|| UseCaseError::Validation("book_event format is null".to_string()))?,
+        event
+            .store
+            .ok_or_else(|| UseCaseError::Validation("book_event store is null".to_string()))?,
+        created_at,
+        restored_at,
+    )?)
+}
+
+// Rebuilds the author recorded by a stateful event, as restored at `restored_at`.
+fn author_from_event(
+    event: AuthorEvent,
+    restored_at: OffsetDateTime,
+) -> Result<Author, UseCaseError> {
+    let name = event
+        .name
+        .ok_or_else(|| UseCaseError::Validation("author_event name is null".to_string()))?;
+    let yomi = event
+        .yomi
+        .ok_or_else(|| UseCaseError::Validation("author_event yomi is null".to_string()))?;
+    let author_name = AuthorName::new(name)?;
+    let created_at = event.author_created_at.ok_or_else(|| {
+        UseCaseError::Validation("author_event author_created_at is null".to_string())
+    })?;
+    event.author_updated_at.ok_or_else(|| {
+        UseCaseError::Validation("author_event author_updated_at is null".to_string())
+    })?;
+    Ok(Author::new_with_timestamps(
+        event.author_id,
+        author_name,
+        yomi,
+        created_at,
+        restored_at,
+    )?)
+}
+
 #[cfg(test)]
 mod tests {
     use mockall::predicate::{always, eq};
@@ -319,20 +1075,21 @@ mod tests {
                 author::AuthorId,
                 book::{BookId, BookTitle, Isbn, OwnedFlag, Priority, ReadFlag},
                 event::{AuthorEvent, BookEvent, EventOperation},
-                event_set::EventSetId,
+                event_set::{EventSet, EventSetId},
             },
             repository::{
                 author_event_repository::MockAuthorEventRepository,
                 author_repository::MockAuthorRepository,
                 book_event_repository::MockBookEventRepository,
-                book_repository::MockBookRepository, transaction::MockTransactionManager,
+                book_repository::MockBookRepository, event_set_repository::MockEventSetRepository,
+                transaction::MockTransactionManager, trash_repository::MockTrashRepository,
             },
         },
         use_case::{
             error::UseCaseError,
             traits::event::{
-                ListAuthorEventsUseCase, ListBookEventsUseCase, RestoreAuthorUseCase,
-                RestoreBookUseCase,
+                ListAuthorEventsUseCase, ListBookEventsUseCase, PurgeTrashUseCase,
+                RestoreAuthorUseCase, RestoreBookUseCase, RevertEventSetUseCase,
             },
         },
     };
@@ -348,6 +1105,15 @@ mod tests {
         tm
     }
 
+    // A MockTransactionManager for paths that begin a transaction and drop it
+    // without committing.
+    fn make_rollback_transaction_manager() -> MockTransactionManager {
+        let mut tm = MockTransactionManager::new();
+        tm.expect_begin().times(1).returning(|_, _| Ok(()));
+        tm.expect_commit().never();
+        tm
+    }
+
     fn make_book_event(book_id: Uuid) -> BookEvent {
         BookEvent {
             event_id: 1,
@@ -485,8 +1251,14 @@ mod tests {
             .returning(|_, _| Ok(None));
 
         let book_repo = MockBookRepository::new();
-        let interactor = RestoreBookInteractor::new(book_repo, repo, MockTransactionManager::new());
-        let result = interactor.restore("user1", 999).await;
+        let interactor = RestoreBookInteractor::new(
+            book_repo,
+            MockAuthorRepository::new(),
+            repo,
+            MockAuthorEventRepository::new(),
+            MockTransactionManager::new(),
+        );
+        let result = interactor.restore("user1", 999, false).await;
 
         assert!(matches!(result, Err(UseCaseError::NotFound { .. })));
     }
@@ -508,14 +1280,19 @@ mod tests {
             .with(always(), eq(1i64), always())
             .returning(|_, _, _| Ok(()));
 
-        let interactor =
-            RestoreBookInteractor::new(book_repo, history_repo, make_transaction_manager());
+        let interactor = RestoreBookInteractor::new(
+            book_repo,
+            MockAuthorRepository::new(),
+            history_repo,
+            MockAuthorEventRepository::new(),
+            make_transaction_manager(),
+        );
         let before = normalize_timestamp_for_persistence(OffsetDateTime::now_utc());
-        let result = interactor.restore("user1", 1).await;
+        let result = interactor.restore("user1", 1, false).await;
         let after = normalize_timestamp_for_persistence(OffsetDateTime::now_utc());
 
         assert!(result.is_ok());
-        let restored = result.unwrap().value.unwrap();
+        let restored = result.unwrap().value.book.unwrap();
         assert_eq!(restored.title, "Old Title");
         assert_eq!(restored.created_at, OffsetDateTime::UNIX_EPOCH);
         assert!(restored.updated_at >= before);
@@ -539,12 +1316,17 @@ mod tests {
             .with(always(), eq(10i64), always())
             .returning(|_, _, _| Ok(()));
 
-        let interactor =
-            RestoreBookInteractor::new(book_repo, history_repo, make_transaction_manager());
-        let result = interactor.restore("user1", 10).await;
+        let interactor = RestoreBookInteractor::new(
+            book_repo,
+            MockAuthorRepository::new(),
+            history_repo,
+            MockAuthorEventRepository::new(),
+            make_transaction_manager(),
+        );
+        let result = interactor.restore("user1", 10, false).await;
 
         assert!(result.is_ok());
-        assert!(result.unwrap().is_none());
+        assert!(result.unwrap().book.is_none());
     }
 
     #[tokio::test]
@@ -565,12 +1347,218 @@ mod tests {
             .with(always(), eq(1i64), always())
             .returning(|_, _, _| Ok(()));
 
-        let interactor =
-            RestoreBookInteractor::new(book_repo, history_repo, make_transaction_manager());
-        let result = interactor.restore("user1", 1).await;
+        let interactor = RestoreBookInteractor::new(
+            book_repo,
+            MockAuthorRepository::new(),
+            history_repo,
+            MockAuthorEventRepository::new(),
+            make_transaction_manager(),
+        );
+        let result = interactor.restore("user1", 1, false).await;
 
         assert!(result.is_ok());
-        assert!(result.unwrap().is_some());
+        assert!(result.unwrap().book.is_some());
+    }
+
+    #[tokio::test]
+    async fn restore_book_cascade_restores_deleted_and_remaps_merged_authors() {
+        // Given: the book listed a since-deleted author, one merged into
+        // another author, and that other author.
+        let deleted_id = AuthorId::new(Uuid::new_v4());
+        let merged_id = AuthorId::new(Uuid::new_v4());
+        let destination = Author::new(
+            AuthorId::new(Uuid::new_v4()),
+            AuthorName::new("Destination".to_string()).unwrap(),
+            OffsetDateTime::UNIX_EPOCH,
+        )
+        .unwrap();
+        let mut event = make_book_event(Uuid::new_v4());
+        event.author_ids = vec![
+            deleted_id.clone(),
+            merged_id.clone(),
+            destination.id().clone(),
+        ];
+
+        let mut history_repo = MockBookEventRepository::new();
+        history_repo
+            .expect_find_by_event_id()
+            .returning(move |_, _| Ok(Some(event.clone())));
+
+        let mut author_repo = MockAuthorRepository::new();
+        let existing = destination.clone();
+        author_repo
+            .expect_find_by_id_with_tx()
+            .returning(move |_, _, id| Ok(Some(existing.clone()).filter(|a| a.id() == id)));
+        author_repo
+            .expect_find_by_name_with_tx()
+            .returning(|_, _, _| Ok(None));
+        let restored_id = deleted_id.clone();
+        author_repo
+            .expect_restore()
+            .withf(move |_, event_id, author| {
+                *event_id == 50
+                    && author
+                        .as_ref()
+                        .is_some_and(|a| a.id() == &restored_id && a.name().as_str() == "Old Name")
+            })
+            .times(1)
+            .returning(|_, _, _| Ok(()));
+
+        let mut author_history_repo = MockAuthorEventRepository::new();
+        let (deleted, merged, destination_id) = (
+            deleted_id.clone(),
+            merged_id.clone(),
+            destination.id().clone(),
+        );
+        author_history_repo
+            .expect_find_by_author()
+            .returning(move |_, id| {
+                let mut created = make_author_event(id.to_uuid());
+                created.event_id = if id == &deleted { 50 } else { 60 };
+                let mut removed = make_author_delete_event(id.to_uuid());
+                if id == &merged {
+                    removed.extra = Some(serde_json::json!({
+                        "type": "merge",
+                        "version": 1,
+                        "destination_author_id": destination_id.to_string(),
+                    }));
+                }
+                Ok(vec![removed, created])
+            });
+
+        let mut book_repo = MockBookRepository::new();
+        let expected_ids = vec![deleted_id.clone(), destination.id().clone()];
+        book_repo
+            .expect_restore()
+            .withf(move |_, event_id, book| {
+                *event_id == 1
+                    && book
+                        .as_ref()
+                        .is_some_and(|b| b.author_ids() == &expected_ids)
+            })
+            .times(1)
+            .returning(|_, _, _| Ok(()));
+
+        let interactor = RestoreBookInteractor::new(
+            book_repo,
+            author_repo,
+            history_repo,
+            author_history_repo,
+            make_transaction_manager(),
+        );
+
+        // When
+        let result = interactor.restore("user1", 1, true).await.unwrap();
+
+        // Then
+        let restored_authors: Vec<&str> = result
+            .restored_authors
+            .iter()
+            .map(|a| a.id.as_str())
+            .collect();
+        assert_eq!(restored_authors, vec![deleted_id.to_string()]);
+        let [remap] = result.remapped_authors.as_slice() else {
+            panic!("expected one remap: {:?}", result.remapped_authors);
+        };
+        assert_eq!(remap.merged_author_id, merged_id.to_string());
+        assert_eq!(remap.author.id, destination.id().to_string());
+        assert_eq!(result.book.as_ref().unwrap().author_ids.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn restore_book_cascade_conflicts_when_the_author_name_was_taken() {
+        // Given: the book's deleted author had a name another author has now
+        let deleted_id = AuthorId::new(Uuid::new_v4());
+        let mut event = make_book_event(Uuid::new_v4());
+        event.author_ids = vec![deleted_id.clone()];
+
+        let mut history_repo = MockBookEventRepository::new();
+        history_repo
+            .expect_find_by_event_id()
+            .returning(move |_, _| Ok(Some(event.clone())));
+        let holder = Author::new(
+            AuthorId::new(Uuid::new_v4()),
+            AuthorName::new("Old Name".to_string()).unwrap(),
+            OffsetDateTime::UNIX_EPOCH,
+        )
+        .unwrap();
+        let holder_id = holder.id().to_string();
+        let mut author_repo = MockAuthorRepository::new();
+        author_repo
+            .expect_find_by_id_with_tx()
+            .returning(|_, _, _| Ok(None));
+        author_repo
+            .expect_find_by_name_with_tx()
+            .withf(|_, _, name| name.as_str() == "Old Name")
+            .returning(move |_, _, _| Ok(Some(holder.clone())));
+        author_repo.expect_restore().times(0);
+        let mut author_history_repo = MockAuthorEventRepository::new();
+        author_history_repo
+            .expect_find_by_author()
+            .returning(|_, id| Ok(vec![make_author_event(id.to_uuid())]));
+        let mut book_repo = MockBookRepository::new();
+        book_repo.expect_restore().times(0);
+        let mut tm = MockTransactionManager::new();
+        tm.expect_begin().returning(|_, _| Ok(()));
+
+        let interactor = RestoreBookInteractor::new(
+            book_repo,
+            author_repo,
+            history_repo,
+            author_history_repo,
+            tm,
+        );
+
+        // When
+        let result = interactor.restore("user1", 1, true).await;
+
+        // Then: nothing is written and the error names both authors
+        let Err(UseCaseError::Conflict { message, .. }) = result else {
+            panic!("expected a conflict, got {result:?}");
+        };
+        assert!(message.contains(&deleted_id.to_string()));
+        assert!(message.contains(&holder_id));
+        assert!(message.contains("Old Name"));
+    }
+
+    #[tokio::test]
+    async fn restore_book_cascade_fails_for_authors_without_history() {
+        // Given
+        let unknown_id = AuthorId::new(Uuid::new_v4());
+        let mut event = make_book_event(Uuid::new_v4());
+        event.author_ids = vec![unknown_id.clone()];
+
+        let mut history_repo = MockBookEventRepository::new();
+        history_repo
+            .expect_find_by_event_id()
+            .returning(move |_, _| Ok(Some(event.clone())));
+        let mut author_repo = MockAuthorRepository::new();
+        author_repo
+            .expect_find_by_id_with_tx()
+            .returning(|_, _, _| Ok(None));
+        let mut author_history_repo = MockAuthorEventRepository::new();
+        author_history_repo
+            .expect_find_by_author()
+            .returning(|_, _| Ok(vec![]));
+        let mut tm = MockTransactionManager::new();
+        tm.expect_begin().returning(|_, _| Ok(()));
+
+        let interactor = RestoreBookInteractor::new(
+            MockBookRepository::new(),
+            author_repo,
+            history_repo,
+            author_history_repo,
+            tm,
+        );
+
+        // When
+        let result = interactor.restore("user1", 1, true).await;
+
+        // Then
+        assert!(matches!(
+            result,
+            Err(UseCaseError::NotFound { entity_id, .. }) if entity_id == unknown_id.to_string()
+        ));
     }
 
     #[tokio::test]
@@ -706,4 +1694,611 @@ mod tests {
         assert!(result.is_ok());
         assert!(result.unwrap().is_some());
     }
+
+    type TestRevertInteractor = RevertEventSetInteractor<
+        MockBookRepository,
+        MockAuthorRepository,
+        MockBookEventRepository,
+        MockAuthorEventRepository,
+        MockEventSetRepository,
+        MockTransactionManager,
+    >;
+
+    fn make_event_set_repository(event_set_id: &EventSetId) -> MockEventSetRepository {
+        make_event_set_repository_for(event_set_id, EventSetOperation::ImportBooks)
+    }
+
+    fn make_event_set_repository_for(
+        event_set_id: &EventSetId,
+        operation: EventSetOperation,
+    ) -> MockEventSetRepository {
+        let event_set_id = event_set_id.clone();
+        let mut repo = MockEventSetRepository::new();
+        repo.expect_find_by_id().returning(move |user_id, id| {
+            Ok(Some(EventSet {
+                id: id.clone(),
+                user_id: user_id.clone(),
+                operation: operation.clone(),
+                created_at: OffsetDateTime::UNIX_EPOCH,
+            })
+            .filter(|event_set| event_set.id == event_set_id))
+        });
+        repo
+    }
+
+    // An event set that renamed a book and added a new author to it:
+    // - book event 31 follows event 1 ("Old Title", no authors)
+    // - author event 32 created the author
+    struct RevertFixture {
+        event_set_id: EventSetId,
+        author_id: AuthorId,
+        book_event: BookEvent,
+        previous_book_event: BookEvent,
+        author_event: AuthorEvent,
+    }
+
+    impl RevertFixture {
+        fn new() -> Self {
+            let event_set_id = EventSetId::new();
+            let book_uuid = Uuid::new_v4();
+            let author_id = AuthorId::new(Uuid::new_v4());
+
+            let mut book_event = make_book_event(book_uuid);
+            book_event.event_id = 31;
+            book_event.event_set_id = event_set_id.clone();
+            book_event.title = Some(BookTitle::new("New Title".to_string()).unwrap());
+            book_event.author_ids = vec![author_id.clone()];
+            let previous_book_event = make_book_event(book_uuid);
+
+            let mut author_event = make_author_event(author_id.to_uuid());
+            author_event.event_id = 32;
+            author_event.event_set_id = event_set_id.clone();
+            author_event.operation = EventOperation::Create;
+            author_event.name = Some("New Author".to_string());
+
+            Self {
+                event_set_id,
+                author_id,
+                book_event,
+                previous_book_event,
+                author_event,
+            }
+        }
+
+        fn book_event_repository(&self, later: HashMap<i64, BookEvent>) -> MockBookEventRepository {
+            let mut repo = MockBookEventRepository::new();
+            let book_event = self.book_event.clone();
+            repo.expect_find_by_event_set()
+                .returning(move |_, _| Ok(vec![book_event.clone()]));
+            let previous = HashMap::from([(31, self.previous_book_event.clone())]);
+            repo.expect_find_previous()
+                .withf(|_, ids| ids == [31])
+                .returning(move |_, _| Ok(previous.clone()));
+            repo.expect_find_next()
+                .withf(|_, ids| ids == [31])
+                .returning(move |_, _| Ok(later.clone()));
+            repo
+        }
+
+        fn author_event_repository(&self) -> MockAuthorEventRepository {
+            let mut repo = MockAuthorEventRepository::new();
+            let author_event = self.author_event.clone();
+            repo.expect_find_by_event_set()
+                .returning(move |_, _| Ok(vec![author_event.clone()]));
+            repo.expect_find_previous()
+                .withf(|_, ids| ids == [32])
+                .returning(|_, _| Ok(HashMap::new()));
+            repo.expect_find_next()
+                .withf(|_, ids| ids == [32])
+                .returning(|_, _| Ok(HashMap::new()));
+            repo
+        }
+
+        // The book as it is now, still listing the new author.
+        fn book_repository(&self) -> MockBookRepository {
+            let mut repo = MockBookRepository::new();
+            let author_id = self.author_id.clone();
+            let book = book_from_event(self.book_event.clone(), OffsetDateTime::now_utc()).unwrap();
+            repo.expect_find_by_author_ids_as_hash_map()
+                .withf(move |_, ids, _| ids == std::slice::from_ref(&author_id))
+                .returning(move |_, ids, _| {
+                    Ok(HashMap::from([(ids[0].clone(), vec![book.clone()])]))
+                });
+            let book_id = self.book_event.book_id.clone();
+            repo.expect_find_selection_with_tx()
+                .withf(move |_, _, selection| {
+                    matches!(selection, BookSelection::Ids(ids) if ids == std::slice::from_ref(&book_id))
+                })
+                .returning(|_, _, _| Ok(vec![]));
+            repo
+        }
+
+        // Locks the new author, which is both reverted and listed by the book.
+        fn author_repository(&self) -> MockAuthorRepository {
+            let mut repo = MockAuthorRepository::new();
+            let author_id = self.author_id.clone();
+            repo.expect_lock_with_book_ids()
+                .withf(move |_, _, ids| ids == std::slice::from_ref(&author_id))
+                .returning(|_, _, _| Ok(vec![]));
+            repo
+        }
+
+        fn interactor(
+            &self,
+            book_repository: MockBookRepository,
+            author_repository: MockAuthorRepository,
+            later: HashMap<i64, BookEvent>,
+            transaction_manager: MockTransactionManager,
+        ) -> TestRevertInteractor {
+            RevertEventSetInteractor::new(
+                book_repository,
+                author_repository,
+                self.book_event_repository(later),
+                self.author_event_repository(),
+                make_event_set_repository(&self.event_set_id),
+                transaction_manager,
+            )
+        }
+    }
+
+    #[tokio::test]
+    async fn revert_event_set_restores_books_before_removing_created_authors() {
+        // Given
+        let fixture = RevertFixture::new();
+        let mut sequence = mockall::Sequence::new();
+        let mut book_repo = fixture.book_repository();
+        book_repo
+            .expect_restore()
+            .withf(|_, event_id, book| {
+                *event_id == 1
+                    && book.as_ref().is_some_and(|book| {
+                        book.title().as_str() == "Old Title" && book.author_ids().is_empty()
+                    })
+            })
+            .times(1)
+            .in_sequence(&mut sequence)
+            .returning(|_, _, _| Ok(()));
+        let mut author_repo = fixture.author_repository();
+        author_repo
+            .expect_restore()
+            .withf(|_, event_id, author| *event_id == 32 && author.is_none())
+            .times(1)
+            .in_sequence(&mut sequence)
+            .returning(|_, _, _| Ok(()));
+        let mut tm = MockTransactionManager::new();
+        tm.expect_begin()
+            .withf(|_, operation| *operation == EventSetOperation::RevertEventSet)
+            .times(1)
+            .returning(|_, _| Ok(()));
+        tm.expect_commit().times(1).returning(|_| Ok(()));
+        let interactor = fixture.interactor(book_repo, author_repo, HashMap::new(), tm);
+
+        // When
+        let result = interactor
+            .revert("user1", &fixture.event_set_id.to_string(), false)
+            .await
+            .unwrap();
+
+        // Then
+        assert_eq!(
+            result.event_set_id,
+            Some(Uuid::nil().hyphenated().to_string())
+        );
+        assert!(result.conflicts.is_empty());
+        let [book_change, author_change] = result.changes.as_slice() else {
+            panic!("unexpected changes: {:?}", result.changes);
+        };
+        assert_eq!(book_change.entity_type, RevertEntityType::Book);
+        assert_eq!(book_change.action, RevertAction::Restore);
+        assert_eq!(
+            book_change.changes,
+            vec![
+                FieldChangeDto {
+                    field: "title".to_string(),
+                    old_value: Some("New Title".to_string()),
+                    new_value: Some("Old Title".to_string()),
+                },
+                FieldChangeDto {
+                    field: "authorIds".to_string(),
+                    old_value: Some(fixture.author_id.to_string()),
+                    new_value: None,
+                },
+            ]
+        );
+        assert_eq!(author_change.entity_id, fixture.author_id.to_string());
+        assert_eq!(author_change.action, RevertAction::Remove);
+        assert_eq!(
+            author_change.changes[0],
+            FieldChangeDto {
+                field: "name".to_string(),
+                old_value: Some("New Author".to_string()),
+                new_value: None,
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn revert_event_set_reports_later_changes_without_applying() {
+        // Given
+        let fixture = RevertFixture::new();
+        let mut later_event = make_book_event(fixture.book_event.book_id.to_uuid());
+        later_event.event_id = 40;
+        let later_event_set_id = later_event.event_set_id.to_string();
+        let interactor = fixture.interactor(
+            fixture.book_repository(),
+            MockAuthorRepository::new(),
+            HashMap::from([(31, later_event)]),
+            make_rollback_transaction_manager(),
+        );
+
+        // When
+        let result = interactor
+            .revert("user1", &fixture.event_set_id.to_string(), false)
+            .await
+            .unwrap();
+
+        // Then
+        assert_eq!(result.event_set_id, None);
+        assert_eq!(result.changes.len(), 2);
+        assert_eq!(
+            result.conflicts,
+            vec![RevertConflictDto {
+                entity_type: RevertEntityType::Book,
+                entity_id: fixture.book_event.book_id.to_string(),
+                reason: RevertConflictReason::ChangedLater {
+                    event_id: 40,
+                    event_set_id: later_event_set_id,
+                },
+            }]
+        );
+    }
+
+    #[tokio::test]
+    async fn revert_event_set_reports_a_name_taken_since_without_applying() {
+        // Given: the set deleted an author, and another author has taken its
+        // name since
+        let event_set_id = EventSetId::new();
+        let deleted_id = AuthorId::new(Uuid::new_v4());
+        let mut delete_event = make_author_delete_event(deleted_id.to_uuid());
+        delete_event.event_set_id = event_set_id.clone();
+        let previous = HashMap::from([(20, make_author_event(deleted_id.to_uuid()))]);
+        let holder = Author::new(
+            AuthorId::new(Uuid::new_v4()),
+            AuthorName::new("Old Name".to_string()).unwrap(),
+            OffsetDateTime::UNIX_EPOCH,
+        )
+        .unwrap();
+        let holder_id = holder.id().to_string();
+
+        let mut book_event_repo = MockBookEventRepository::new();
+        book_event_repo
+            .expect_find_by_event_set()
+            .returning(|_, _| Ok(vec![]));
+        book_event_repo
+            .expect_find_previous()
+            .returning(|_, _| Ok(HashMap::new()));
+        book_event_repo
+            .expect_find_next()
+            .returning(|_, _| Ok(HashMap::new()));
+        let mut author_event_repo = MockAuthorEventRepository::new();
+        author_event_repo
+            .expect_find_by_event_set()
+            .returning(move |_, _| Ok(vec![delete_event.clone()]));
+        author_event_repo
+            .expect_find_previous()
+            .withf(|_, ids| ids == [20])
+            .returning(move |_, _| Ok(previous.clone()));
+        author_event_repo
+            .expect_find_next()
+            .withf(|_, ids| ids == [20])
+            .returning(|_, _| Ok(HashMap::new()));
+        let mut author_repo = MockAuthorRepository::new();
+        author_repo
+            .expect_find_by_name_with_tx()
+            .withf(|_, _, name| name.as_str() == "Old Name")
+            .returning(move |_, _, _| Ok(Some(holder.clone())));
+        author_repo.expect_restore().never();
+        let interactor = RevertEventSetInteractor::new(
+            MockBookRepository::new(),
+            author_repo,
+            book_event_repo,
+            author_event_repo,
+            make_event_set_repository(&event_set_id),
+            make_rollback_transaction_manager(),
+        );
+
+        // When
+        let result = interactor
+            .revert("user1", &event_set_id.to_string(), false)
+            .await
+            .unwrap();
+
+        // Then
+        assert_eq!(result.event_set_id, None);
+        assert_eq!(result.changes.len(), 1);
+        assert_eq!(
+            result.conflicts,
+            vec![RevertConflictDto {
+                entity_type: RevertEntityType::Author,
+                entity_id: deleted_id.to_string(),
+                reason: RevertConflictReason::NameTaken {
+                    author_id: holder_id,
+                },
+            }]
+        );
+    }
+
+    #[tokio::test]
+    async fn revert_event_set_checks_again_after_locking() {
+        // Given: the book is changed after the revert is planned, before the
+        // transaction locks it
+        let fixture = RevertFixture::new();
+        let mut later_event = make_book_event(fixture.book_event.book_id.to_uuid());
+        later_event.event_id = 40;
+        let mut book_event_repo = MockBookEventRepository::new();
+        let book_event = fixture.book_event.clone();
+        book_event_repo
+            .expect_find_by_event_set()
+            .returning(move |_, _| Ok(vec![book_event.clone()]));
+        let previous = HashMap::from([(31, fixture.previous_book_event.clone())]);
+        book_event_repo
+            .expect_find_previous()
+            .returning(move |_, _| Ok(previous.clone()));
+        book_event_repo
+            .expect_find_next()
+            .times(1)
+            .returning(|_, _| Ok(HashMap::new()));
+        book_event_repo
+            .expect_find_next()
+            .times(1)
+            .returning(move |_, _| Ok(HashMap::from([(31, later_event.clone())])));
+        let mut tm = MockTransactionManager::new();
+        tm.expect_begin().times(1).returning(|_, _| Ok(()));
+        tm.expect_commit().never();
+        let interactor = RevertEventSetInteractor::new(
+            fixture.book_repository(),
+            fixture.author_repository(),
+            book_event_repo,
+            fixture.author_event_repository(),
+            make_event_set_repository(&fixture.event_set_id),
+            tm,
+        );
+
+        // When
+        let result = interactor
+            .revert("user1", &fixture.event_set_id.to_string(), false)
+            .await
+            .unwrap();
+
+        // Then: the conflict is reported and nothing is restored
+        assert_eq!(result.event_set_id, None);
+        assert!(matches!(
+            result.conflicts.as_slice(),
+            [RevertConflictDto {
+                reason: RevertConflictReason::ChangedLater { event_id: 40, .. },
+                ..
+            }]
+        ));
+    }
+
+    #[tokio::test]
+    async fn revert_event_set_dry_run_reports_authors_still_listed_elsewhere() {
+        // Given: another book, outside the event set, lists the new author.
+        let fixture = RevertFixture::new();
+        let mut other_book_event = make_book_event(Uuid::new_v4());
+        other_book_event.author_ids = vec![fixture.author_id.clone()];
+        let other_book = book_from_event(other_book_event, OffsetDateTime::now_utc()).unwrap();
+        let other_book_id = other_book.id().to_string();
+        let mut book_repo = MockBookRepository::new();
+        book_repo
+            .expect_find_by_author_ids_as_hash_map()
+            .returning(move |_, ids, _| {
+                Ok(HashMap::from([(ids[0].clone(), vec![other_book.clone()])]))
+            });
+        let interactor = fixture.interactor(
+            book_repo,
+            MockAuthorRepository::new(),
+            HashMap::new(),
+            make_rollback_transaction_manager(),
+        );
+
+        // When
+        let result = interactor
+            .revert("user1", &fixture.event_set_id.to_string(), true)
+            .await
+            .unwrap();
+
+        // Then
+        assert_eq!(result.event_set_id, None);
+        assert_eq!(result.changes.len(), 2);
+        assert_eq!(
+            result.conflicts,
+            vec![RevertConflictDto {
+                entity_type: RevertEntityType::Author,
+                entity_id: fixture.author_id.to_string(),
+                reason: RevertConflictReason::HasAssociatedBooks {
+                    book_ids: vec![other_book_id],
+                },
+            }]
+        );
+    }
+
+    #[tokio::test]
+    async fn revert_event_set_rejects_unknown_and_malformed_ids() {
+        // Given
+        let interactor = RevertEventSetInteractor::new(
+            MockBookRepository::new(),
+            MockAuthorRepository::new(),
+            MockBookEventRepository::new(),
+            MockAuthorEventRepository::new(),
+            make_event_set_repository(&EventSetId::new()),
+            MockTransactionManager::new(),
+        );
+
+        // When
+        let unknown = interactor
+            .revert("user1", &EventSetId::new().to_string(), true)
+            .await;
+        let malformed = interactor.revert("user1", "not-a-uuid", true).await;
+
+        // Then
+        assert!(matches!(
+            unknown,
+            Err(UseCaseError::NotFound {
+                entity_type: "event_set",
+                ..
+            })
+        ));
+        assert!(matches!(malformed, Err(UseCaseError::Validation(_))));
+    }
+
+    #[tokio::test]
+    async fn revert_event_set_rejects_snapshot_sets() {
+        // Given
+        let event_set_id = EventSetId::new();
+        let interactor = RevertEventSetInteractor::new(
+            MockBookRepository::new(),
+            MockAuthorRepository::new(),
+            MockBookEventRepository::new(),
+            MockAuthorEventRepository::new(),
+            make_event_set_repository_for(&event_set_id, EventSetOperation::SnapshotAll),
+            MockTransactionManager::new(),
+        );
+
+        // When
+        let result = interactor
+            .revert("user1", &event_set_id.to_string(), false)
+            .await;
+
+        // Then
+        assert!(matches!(result, Err(UseCaseError::Validation(_))));
+    }
+
+    #[tokio::test]
+    async fn revert_event_set_leaves_snapshot_events_alone() {
+        // Given: a set holding only snapshots of a book and an author with
+        // no earlier events
+        let event_set_id = EventSetId::new();
+        let mut book_event = make_book_event(Uuid::new_v4());
+        book_event.event_set_id = event_set_id.clone();
+        book_event.operation = EventOperation::Snapshot;
+        let mut author_event = make_author_event(Uuid::new_v4());
+        author_event.event_set_id = event_set_id.clone();
+        author_event.operation = EventOperation::Snapshot;
+        let mut book_event_repo = MockBookEventRepository::new();
+        book_event_repo
+            .expect_find_by_event_set()
+            .returning(move |_, _| Ok(vec![book_event.clone()]));
+        book_event_repo
+            .expect_find_previous()
+            .withf(|_, ids| ids.is_empty())
+            .returning(|_, _| Ok(HashMap::new()));
+        book_event_repo
+            .expect_find_next()
+            .withf(|_, ids| ids.is_empty())
+            .returning(|_, _| Ok(HashMap::new()));
+        let mut author_event_repo = MockAuthorEventRepository::new();
+        author_event_repo
+            .expect_find_by_event_set()
+            .returning(move |_, _| Ok(vec![author_event.clone()]));
+        author_event_repo
+            .expect_find_previous()
+            .withf(|_, ids| ids.is_empty())
+            .returning(|_, _| Ok(HashMap::new()));
+        author_event_repo
+            .expect_find_next()
+            .withf(|_, ids| ids.is_empty())
+            .returning(|_, _| Ok(HashMap::new()));
+        let interactor = RevertEventSetInteractor::new(
+            MockBookRepository::new(),
+            MockAuthorRepository::new(),
+            book_event_repo,
+            author_event_repo,
+            make_event_set_repository(&event_set_id),
+            make_rollback_transaction_manager(),
+        );
+
+        // When
+        let result = interactor
+            .revert("user1", &event_set_id.to_string(), false)
+            .await
+            .unwrap();
+
+        // Then: nothing is removed and no event set is recorded
+        assert_eq!(result.event_set_id, None);
+        assert!(result.changes.is_empty());
+        assert!(result.conflicts.is_empty());
+    }
+
+    #[tokio::test]
+    async fn purge_trash_cuts_off_at_the_retention_window() {
+        // Given
+        let mut trash_repository = MockTrashRepository::new();
+        trash_repository
+            .expect_purge()
+            .withf(|_, deleted_before| {
+                let age = OffsetDateTime::now_utc() - *deleted_before;
+                age >= Duration::days(30) && age < Duration::days(30) + Duration::minutes(1)
+            })
+            .returning(|_, _| {
+                Ok(PurgedTrash {
+                    book_count: 2,
+                    author_count: 1,
+                    event_set_count: 3,
+                })
+            });
+        let mut tm = MockTransactionManager::new();
+        tm.expect_begin()
+            .withf(|_, operation| *operation == EventSetOperation::PurgeTrash)
+            .times(1)
+            .returning(|_, _| Ok(()));
+        tm.expect_commit().times(1).returning(|_| Ok(()));
+        let interactor = PurgeTrashInteractor::new(trash_repository, tm);
+
+        // When
+        let purged = interactor.purge("user1", 30).await.unwrap();
+
+        // Then
+        assert_eq!(
+            purged,
+            PurgedTrashDto {
+                book_count: 2,
+                author_count: 1,
+                event_set_count: 3,
+                event_set_id: Some(Uuid::nil().hyphenated().to_string()),
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn purge_trash_records_nothing_when_nothing_is_purged() {
+        // Given: commit must not be reached
+        let mut trash_repository = MockTrashRepository::new();
+        trash_repository
+            .expect_purge()
+            .returning(|_, _| Ok(PurgedTrash::default()));
+        let mut tm = MockTransactionManager::new();
+        tm.expect_begin().times(1).returning(|_, _| Ok(()));
+        let interactor = PurgeTrashInteractor::new(trash_repository, tm);
+
+        // When
+        let purged = interactor.purge("user1", 30).await.unwrap();
+
+        // Then
+        assert_eq!(purged.event_set_id, None);
+        assert_eq!(purged.book_count, 0);
+    }
+
+    #[tokio::test]
+    async fn purge_trash_rejects_a_negative_retention() {
+        // Given: the repository must not be reached
+        let interactor =
+            PurgeTrashInteractor::new(MockTrashRepository::new(), MockTransactionManager::new());
+
+        // When
+        let result = interactor.purge("user1", -1).await;
+
+        // Then
+        assert!(matches!(result, Err(UseCaseError::Validation(_))));
+    }
 }
@@ -0,0 +1,915 @@
+use std::{collections::HashMap, sync::Arc};
+
+use async_trait::async_trait;
+use time::{Duration, OffsetDateTime};
+use tokio::{
+    sync::mpsc,
+    task::{self, JoinSet},
+};
+use uuid::Uuid;
+
+use crate::{
+    domain::{
+        entity::{
+            book::BookId,
+            event::EventSetOperation,
+            event_set::EventSetId,
+            import_job::{ImportEntry, ImportJob, ImportJobFailure, ImportJobId, ImportJobStatus},
+            user::UserId,
+        },
+        error::DomainError,
+        repository::{
+            author_repository::AuthorRepository,
+            book_repository::BookRepository,
+            import_job_repository::ImportJobRepository,
+            transaction::{TransactionEventSet, TransactionManager},
+        },
+    },
+    use_case::{
+        dto::{
+            book::ImportBookEntryDto,
+            import_job::{ImportJobDto, ImportJobOptionsDto},
+        },
+        error::UseCaseError,
+        interactor::book::{validate_import_entry, write_import_entries},
+        traits::import_job::ImportJobUseCase,
+    },
+};
+
+const MAX_IMPORT_JOB_ENTRIES: usize = 100_000;
+// Entries written per transaction, and so per event set.
+const IMPORT_JOB_CHUNK_SIZE: usize = 500;
+// Jobs a worker runs at once. Each holds a pooled connection while it writes
+// a chunk, so this stays well below the pool size.
+const MAX_RUNNING_IMPORT_JOBS: usize = 2;
+// A user's jobs lock the same library, so running more of them at once would
+// only have them wait for each other.
+const MAX_RUNNING_IMPORT_JOBS_PER_USER: usize = 1;
+// A running job whose claim was not renewed for this long is taken to be
+// left by a stopped server, and another worker takes it over.
+const IMPORT_JOB_LEASE: Duration = Duration::minutes(1);
+// How often a worker renews the claims on its running jobs, well within
+// IMPORT_JOB_LEASE so that a slow chunk does not lose its job.
+const IMPORT_JOB_RENEWAL_INTERVAL: std::time::Duration = std::time::Duration::from_secs(20);
+// How often a worker looks for jobs besides being woken up.
+const IMPORT_JOB_POLL_INTERVAL: std::time::Duration = std::time::Duration::from_secs(15);
+
+pub struct ImportJobInteractor<IJR> {
+    import_job_repository: IJR,
+    queue: mpsc::UnboundedSender<()>,
+}
+
+impl<IJR> ImportJobInteractor<IJR> {
+    pub fn new(import_job_repository: IJR, queue: mpsc::UnboundedSender<()>) -> Self {
+        Self {
+            import_job_repository,
+            queue,
+        }
+    }
+}
+
+#[async_trait]
+impl<IJR> ImportJobUseCase for ImportJobInteractor<IJR>
+where
+    IJR: ImportJobRepository,
+{
+    async fn start(
+        &self,
+        user_id: &str,
+        books: Vec<ImportBookEntryDto>,
+        options: ImportJobOptionsDto,
+    ) -> Result<ImportJobDto, UseCaseError> {
+        if books.is_empty() {
+            return Err(UseCaseError::Validation(
+                "books cannot be empty".to_string(),
+            ));
+        }
+
+        if books.len() > MAX_IMPORT_JOB_ENTRIES {
+            return Err(UseCaseError::Validation(format!(
+                "books cannot exceed {MAX_IMPORT_JOB_ENTRIES}"
+            )));
+        }
+
+        let user_id = UserId::new(user_id.to_string())?;
+        let now = OffsetDateTime::now_utc();
+        let entries: Vec<ImportEntry> = books.into_iter().map(ImportEntry::from).collect();
+
+        // Without `partial` one invalid entry rejects the whole import, so
+        // it is rejected here rather than after some chunks were written.
+        if !options.partial {
+            for (index, entry) in entries.iter().enumerate() {
+                let book_id = BookId::new(Uuid::new_v4())?;
+                let Err((_, err)) = validate_import_entry(book_id, entry.clone(), now) else {
+                    continue;
+                };
+                return match UseCaseError::from(err) {
+                    UseCaseError::Validation(message) => {
+                        Err(UseCaseError::Validation(format!("book {index}: {message}")))
+                    }
+                    err => Err(err),
+                };
+            }
+        }
+
+        let job = ImportJob::new(
+            user_id,
+            options.mode.into(),
+            options.partial,
+            entries.len(),
+            now,
+        );
+        self.import_job_repository.create(&job, &entries).await?;
+        // A send only fails once the worker has stopped. The job is stored as
+        // pending then, and the next worker to look claims it.
+        let _ = self.queue.send(());
+
+        Ok(job.into())
+    }
+
+    async fn cancel(&self, user_id: &str, job_id: &str) -> Result<ImportJobDto, UseCaseError> {
+        let user_id = UserId::new(user_id.to_string())?;
+        let import_job_id = ImportJobId::try_from(job_id)
+            .map_err(|e| UseCaseError::from(DomainError::Unexpected(e)))?;
+        let job = self
+            .import_job_repository
+            .cancel(&user_id, &import_job_id, OffsetDateTime::now_utc())
+            .await?;
+
+        job.map(ImportJobDto::from)
+            .ok_or_else(|| UseCaseError::NotFound {
+                entity_type: "import job",
+                entity_id: job_id.to_string(),
+                user_id: user_id.into_string(),
+            })
+    }
+}
+
+// Runs import jobs in the background, claiming them from the database so
+// that several servers can share them. Jobs queued by ImportJobInteractor
+// only wake the worker up.
+pub struct ImportJobWorker<IJR, BR, AR, TM> {
+    runner: Arc<ImportJobRunner<IJR, BR, AR, TM>>,
+    queue: mpsc::UnboundedReceiver<()>,
+}
+
+impl<IJR, BR, AR, TM> ImportJobWorker<IJR, BR, AR, TM> {
+    pub fn new(
+        import_job_repository: IJR,
+        book_repository: BR,
+        author_repository: AR,
+        transaction_manager: TM,
+        queue: mpsc::UnboundedReceiver<()>,
+    ) -> Self {
+        Self {
+            runner: Arc::new(ImportJobRunner {
+                worker_id: Uuid::new_v4(),
+                import_job_repository,
+                book_repository,
+                author_repository,
+                transaction_manager,
+            }),
+            queue,
+        }
+    }
+}
+
+impl<IJR, BR, AR, TM> ImportJobWorker<IJR, BR, AR, TM>
+where
+    TM: TransactionManager,
+    BR: BookRepository<Transaction = TM::Transaction>,
+    AR: AuthorRepository<Transaction = TM::Transaction>,
+    IJR: ImportJobRepository<Transaction = TM::Transaction>,
+{
+    // Runs jobs, oldest first, until every sender is gone and no job is left
+    // to claim. Besides pending jobs, that includes running jobs a stopped
+    // server left, which are looked for every IMPORT_JOB_POLL_INTERVAL.
+    pub async fn run(mut self) {
+        let mut running = JoinSet::new();
+        let mut running_user_ids: HashMap<task::Id, UserId> = HashMap::new();
+        let mut queue_open = true;
+        let mut poll = tokio::time::interval(IMPORT_JOB_POLL_INTERVAL);
+        loop {
+            while running.len() < MAX_RUNNING_IMPORT_JOBS {
+                let Some((job, entries)) = self.claim(&running_user_ids).await else {
+                    break;
+                };
+                let user_id = job.user_id.clone();
+                let runner = Arc::clone(&self.runner);
+                let handle = running.spawn(async move { runner.run_job(job, entries).await });
+                running_user_ids.insert(handle.id(), user_id);
+            }
+            if !queue_open && running.is_empty() {
+                return;
+            }
+            tokio::select! {
+                queued = self.queue.recv(), if queue_open => queue_open = queued.is_some(),
+                Some(finished) = running.join_next_with_id() => {
+                    let task_id = match finished {
+                        Ok((task_id, ())) => task_id,
+                        Err(err) => {
+                            tracing::error!("import job task failed: {err}");
+                            err.id()
+                        }
+                    };
+                    running_user_ids.remove(&task_id);
+                }
+                _ = poll.tick() => {}
+            }
+        }
+    }
+
+    // Claims a job of a user below MAX_RUNNING_IMPORT_JOBS_PER_USER.
+    async fn claim(
+        &self,
+        running_user_ids: &HashMap<task::Id, UserId>,
+    ) -> Option<(ImportJob, Vec<ImportEntry>)> {
+        let mut busy_user_ids: Vec<UserId> = Vec::new();
+        for user_id in running_user_ids.values() {
+            let running = running_user_ids
+                .values()
+                .filter(|other| *other == user_id)
+                .count();
+            if running >= MAX_RUNNING_IMPORT_JOBS_PER_USER && !busy_user_ids.contains(user_id) {
+                busy_user_ids.push(user_id.clone());
+            }
+        }
+        let now = OffsetDateTime::now_utc();
+        match self
+            .runner
+            .import_job_repository
+            .claim(
+                self.runner.worker_id,
+                &busy_user_ids,
+                now - IMPORT_JOB_LEASE,
+                now,
+            )
+            .await
+        {
+            Ok(claimed) => claimed,
+            Err(err) => {
+                tracing::error!("failed to claim an import job: {err}");
+                None
+            }
+        }
+    }
+}
+
+struct ImportJobRunner<IJR, BR, AR, TM> {
+    worker_id: Uuid,
+    import_job_repository: IJR,
+    book_repository: BR,
+    author_repository: AR,
+    transaction_manager: TM,
+}
+
+impl<IJR, BR, AR, TM> ImportJobRunner<IJR, BR, AR, TM>
+where
+    TM: TransactionManager,
+    BR: BookRepository<Transaction = TM::Transaction>,
+    AR: AuthorRepository<Transaction = TM::Transaction>,
+    IJR: ImportJobRepository<Transaction = TM::Transaction>,
+{
+    // Runs a claimed job from where it stopped, renewing the claim while it
+    // runs. An error ends the job as failed, with the chunks before it kept.
+    async fn run_job(&self, mut job: ImportJob, entries: Vec<ImportEntry>) {
+        let claimed = job.clone();
+        let result = tokio::select! {
+            result = self.process(&mut job, entries) => result,
+            () = self.renew_claim(&claimed) => unreachable!("renewing a claim never ends"),
+        };
+        let Err(err) = result else {
+            return;
+        };
+        job.fail(err.to_string(), OffsetDateTime::now_utc());
+        if let Err(err) = self.import_job_repository.save(&job).await {
+            tracing::error!(
+                "failed to record the failure of import job {}: {err}",
+                job.id
+            );
+        }
+    }
+
+    // Renews the claim on `job` every IMPORT_JOB_RENEWAL_INTERVAL. Once the
+    // job is finished or taken over it stops renewing, leaving `process` to
+    // find out when it saves, and never returns.
+    async fn renew_claim(&self, job: &ImportJob) {
+        let mut renewal = tokio::time::interval_at(
+            tokio::time::Instant::now() + IMPORT_JOB_RENEWAL_INTERVAL,
+            IMPORT_JOB_RENEWAL_INTERVAL,
+        );
+        loop {
+            renewal.tick().await;
+            match self
+                .import_job_repository
+                .renew(job, OffsetDateTime::now_utc())
+                .await
+            {
+                Ok(true) => {}
+                Ok(false) => break,
+                Err(err) => tracing::error!("failed to renew import job {}: {err}", job.id),
+            }
+        }
+        std::future::pending().await
+    }
+
+    async fn process(
+        &self,
+        job: &mut ImportJob,
+        entries: Vec<ImportEntry>,
+    ) -> Result<(), UseCaseError> {
+        let mut remaining = entries.into_iter().enumerate().skip(job.processed_count);
+        loop {
+            let chunk: Vec<(usize, ImportEntry)> =
+                remaining.by_ref().take(IMPORT_JOB_CHUNK_SIZE).collect();
+            if chunk.is_empty() {
+                break;
+            }
+            if !self.process_chunk(job, chunk).await? {
+                return Ok(());
+            }
+        }
+
+        job.set_status(ImportJobStatus::Succeeded, OffsetDateTime::now_utc());
+        self.import_job_repository.save(job).await?;
+        Ok(())
+    }
+
+    // Writes a chunk and saves the job's progress in one transaction, so a
+    // restart resumes after the last chunk that was kept. Returns false when
+    // the job was cancelled or taken over, in which case the chunk is rolled
+    // back. The library is matched against as it is in the chunk's
+    // transaction, including books and authors added since the job started.
+    async fn process_chunk(
+        &self,
+        job: &mut ImportJob,
+        chunk: Vec<(usize, ImportEntry)>,
+    ) -> Result<bool, UseCaseError> {
+        let now = OffsetDateTime::now_utc();
+        let processed = chunk.len();
+        let mut inputs = Vec::with_capacity(processed);
+        let mut failures = Vec::new();
+        for (index, entry) in chunk {
+            let book_id = BookId::new(Uuid::new_v4())?;
+            match validate_import_entry(book_id, entry, now) {
+                Ok(input) => inputs.push(input),
+                Err((field, err)) => failures.push(ImportJobFailure {
+                    index,
+                    field,
+                    message: err.to_string(),
+                }),
+            }
+        }
+
+        let mut tx = self
+            .transaction_manager
+            .begin(&job.user_id, EventSetOperation::ImportBooks)
+            .await?;
+        let result = write_import_entries(
+            &self.book_repository,
+            &self.author_repository,
+            &mut tx,
+            &job.user_id,
+            inputs,
+            job.mode,
+            &HashMap::new(),
+            now,
+        )
+        .await?;
+        let wrote = !result.created.is_empty() || !result.updated.is_empty();
+        let event_set_id = wrote.then(|| EventSetId::from(tx.event_set_id()));
+        job.record_chunk(processed, failures, event_set_id, now);
+
+        // A chunk that wrote nothing leaves no event set behind.
+        if !wrote {
+            drop(tx);
+            return Ok(self.import_job_repository.save(job).await?);
+        }
+        if !self
+            .import_job_repository
+            .save_with_tx(&mut tx, job)
+            .await?
+        {
+            return Ok(false);
+        }
+        self.transaction_manager.commit(tx).await?;
+        Ok(true)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use time::OffsetDateTime;
+    use tokio::sync::mpsc;
+
+    use crate::{
+        common::types::{BookFormat, BookStore},
+        domain::{
+            entity::{
+                author::AuthorId,
+                import_job::{
+                    ImportEntry, ImportField, ImportJob, ImportJobId, ImportJobStatus, ImportMode,
+                },
+                user::UserId,
+            },
+            error::DomainError,
+            repository::{
+                author_repository::{MockAuthorRepository, ResolvedAuthor},
+                book_repository::MockBookRepository,
+                import_job_repository::MockImportJobRepository,
+                transaction::MockTransactionManager,
+            },
+        },
+        use_case::{
+            dto::{
+                book::{ImportBookEntryDto, ImportModeDto},
+                import_job::{ImportJobOptionsDto, ImportJobStatusDto},
+            },
+            error::UseCaseError,
+            traits::import_job::ImportJobUseCase,
+        },
+    };
+
+    use super::{IMPORT_JOB_CHUNK_SIZE, ImportJobInteractor, ImportJobRunner, ImportJobWorker};
+
+    fn entry_dto(title: &str) -> ImportBookEntryDto {
+        ImportBookEntryDto {
+            title: title.to_string(),
+            author_names: vec!["author1".to_string()],
+            isbn: "".to_string(),
+            read: false,
+            owned: true,
+            priority: 50,
+            format: BookFormat::EBook,
+            store: BookStore::Kindle,
+        }
+    }
+
+    fn entry(title: &str) -> ImportEntry {
+        entry_dto(title).into()
+    }
+
+    fn make_job(total_count: usize) -> ImportJob {
+        ImportJob::new(
+            UserId::new("user1".to_string()).unwrap(),
+            ImportMode::CreateOnly,
+            true,
+            total_count,
+            OffsetDateTime::now_utc(),
+        )
+    }
+
+    fn make_author_repository() -> MockAuthorRepository {
+        let mut author_repository = MockAuthorRepository::new();
+        author_repository
+            .expect_find_or_create_by_name()
+            .returning(|_, _, _, _| {
+                Ok(ResolvedAuthor {
+                    id: AuthorId::new(uuid::Uuid::new_v4()),
+                    created: true,
+                })
+            });
+        author_repository
+    }
+
+    fn make_runner(
+        import_job_repository: MockImportJobRepository,
+        book_repository: MockBookRepository,
+        transaction_manager: MockTransactionManager,
+    ) -> ImportJobRunner<
+        MockImportJobRepository,
+        MockBookRepository,
+        MockAuthorRepository,
+        MockTransactionManager,
+    > {
+        ImportJobRunner {
+            worker_id: uuid::Uuid::new_v4(),
+            import_job_repository,
+            book_repository,
+            author_repository: make_author_repository(),
+            transaction_manager,
+        }
+    }
+
+    #[tokio::test]
+    async fn start_stores_a_pending_job_and_queues_it() {
+        let mut import_job_repository = MockImportJobRepository::new();
+        import_job_repository
+            .expect_create()
+            .withf(|job, entries| {
+                job.status == ImportJobStatus::Pending
+                    && job.mode == ImportMode::SkipExisting
+                    && job.total_count == 2
+                    && entries.len() == 2
+            })
+            .times(1)
+            .returning(|_, _| Ok(()));
+        let (sender, mut receiver) = mpsc::unbounded_channel();
+        let interactor = ImportJobInteractor::new(import_job_repository, sender);
+
+        let job = interactor
+            .start(
+                "user1",
+                vec![entry_dto("title1"), entry_dto("title2")],
+                ImportJobOptionsDto {
+                    mode: ImportModeDto::SkipExisting,
+                    partial: false,
+                },
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(job.status, ImportJobStatusDto::Pending);
+        assert_eq!(job.total_count, 2);
+        assert!(receiver.try_recv().is_ok());
+    }
+
+    #[tokio::test]
+    async fn start_rejects_an_invalid_entry_unless_partial() {
+        let mut import_job_repository = MockImportJobRepository::new();
+        import_job_repository.expect_create().times(0);
+        let (sender, _receiver) = mpsc::unbounded_channel();
+        let interactor = ImportJobInteractor::new(import_job_repository, sender);
+
+        let result = interactor
+            .start(
+                "user1",
+                vec![entry_dto("title1"), entry_dto("")],
+                ImportJobOptionsDto::default(),
+            )
+            .await;
+
+        match result {
+            Err(UseCaseError::Validation(message)) => assert!(message.starts_with("book 1: ")),
+            other => panic!("expected a validation error, got {other:?}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn cancel_returns_not_found_for_an_unknown_job() {
+        let mut import_job_repository = MockImportJobRepository::new();
+        import_job_repository
+            .expect_cancel()
+            .returning(|_, _, _| Ok(None));
+        let (sender, _receiver) = mpsc::unbounded_channel();
+        let interactor = ImportJobInteractor::new(import_job_repository, sender);
+
+        let result = interactor
+            .cancel("user1", &ImportJobId::new().to_string())
+            .await;
+
+        assert!(matches!(result, Err(UseCaseError::NotFound { .. })));
+    }
+
+    #[tokio::test]
+    async fn run_job_writes_each_chunk_in_its_own_event_set() {
+        let entries: Vec<ImportEntry> = (0..IMPORT_JOB_CHUNK_SIZE + 1)
+            .map(|n| entry(&format!("title{n}")))
+            .collect();
+        let job = make_job(entries.len());
+        let mut import_job_repository = MockImportJobRepository::new();
+        import_job_repository
+            .expect_save_with_tx()
+            .times(2)
+            .returning(|_, _| Ok(true));
+        import_job_repository
+            .expect_save()
+            .withf(|job| job.status == ImportJobStatus::Succeeded)
+            .times(1)
+            .returning(|_| Ok(true));
+        let mut book_repository = MockBookRepository::new();
+        book_repository
+            .expect_create()
+            .times(IMPORT_JOB_CHUNK_SIZE + 1)
+            .returning(|_, _| Ok(101.into()));
+        let mut transaction_manager = MockTransactionManager::new();
+        transaction_manager
+            .expect_begin()
+            .times(2)
+            .returning(|_, _| Ok(()));
+        transaction_manager
+            .expect_commit()
+            .times(2)
+            .returning(|_| Ok(()));
+
+        make_runner(import_job_repository, book_repository, transaction_manager)
+            .run_job(job, entries)
+            .await;
+    }
+
+    #[tokio::test]
+    async fn run_job_loads_the_library_again_for_each_chunk() {
+        let entries: Vec<ImportEntry> = (0..IMPORT_JOB_CHUNK_SIZE + 1)
+            .map(|n| entry(&format!("title{n}")))
+            .collect();
+        let mut job = make_job(entries.len());
+        job.mode = ImportMode::SkipExisting;
+        let mut import_job_repository = MockImportJobRepository::new();
+        import_job_repository
+            .expect_save_with_tx()
+            .times(2)
+            .returning(|_, _| Ok(true));
+        import_job_repository
+            .expect_save()
+            .times(1)
+            .returning(|_| Ok(true));
+        let mut book_repository = MockBookRepository::new();
+        book_repository
+            .expect_find_selection_with_tx()
+            .times(2)
+            .returning(|_, _, _| Ok(vec![]));
+        book_repository
+            .expect_create()
+            .times(IMPORT_JOB_CHUNK_SIZE + 1)
+            .returning(|_, _| Ok(101.into()));
+        let mut transaction_manager = MockTransactionManager::new();
+        transaction_manager.expect_begin().returning(|_, _| Ok(()));
+        transaction_manager.expect_commit().returning(|_| Ok(()));
+        let mut runner = make_runner(import_job_repository, book_repository, transaction_manager);
+        runner
+            .author_repository
+            .expect_find_by_ids_as_hash_map_with_tx()
+            .times(2)
+            .returning(|_, _, _| Ok(HashMap::new()));
+
+        runner.run_job(job, entries).await;
+    }
+
+    #[tokio::test]
+    async fn run_job_records_invalid_entries_of_a_partial_job() {
+        let job = make_job(2);
+        let mut import_job_repository = MockImportJobRepository::new();
+        import_job_repository
+            .expect_save_with_tx()
+            .withf(|_, job| {
+                job.processed_count == 2
+                    && job.failures.len() == 1
+                    && job.failures[0].index == 0
+                    && job.failures[0].field == ImportField::Title
+                    && job.event_set_ids.len() == 1
+            })
+            .times(1)
+            .returning(|_, _| Ok(true));
+        import_job_repository
+            .expect_save()
+            .times(1)
+            .returning(|_| Ok(true));
+        let mut book_repository = MockBookRepository::new();
+        book_repository
+            .expect_create()
+            .times(1)
+            .returning(|_, _| Ok(101.into()));
+        let mut transaction_manager = MockTransactionManager::new();
+        transaction_manager.expect_begin().returning(|_, _| Ok(()));
+        transaction_manager
+            .expect_commit()
+            .times(1)
+            .returning(|_| Ok(()));
+
+        make_runner(import_job_repository, book_repository, transaction_manager)
+            .run_job(job, vec![entry(""), entry("title1")])
+            .await;
+    }
+
+    #[tokio::test]
+    async fn run_job_stops_without_committing_once_the_job_is_cancelled() {
+        let job = make_job(1);
+        let mut import_job_repository = MockImportJobRepository::new();
+        import_job_repository.expect_save().times(0);
+        import_job_repository
+            .expect_save_with_tx()
+            .times(1)
+            .returning(|_, _| Ok(false));
+        let mut book_repository = MockBookRepository::new();
+        book_repository
+            .expect_create()
+            .returning(|_, _| Ok(101.into()));
+        let mut transaction_manager = MockTransactionManager::new();
+        transaction_manager.expect_begin().returning(|_, _| Ok(()));
+        transaction_manager.expect_commit().times(0);
+
+        make_runner(import_job_repository, book_repository, transaction_manager)
+            .run_job(job, vec![entry("title1")])
+            .await;
+    }
+
+    #[tokio::test]
+    async fn run_job_marks_the_job_failed_when_a_chunk_fails() {
+        let job = make_job(1);
+        let mut import_job_repository = MockImportJobRepository::new();
+        import_job_repository
+            .expect_save()
+            .withf(|job| job.status == ImportJobStatus::Failed && job.error.is_some())
+            .times(1)
+            .returning(|_| Ok(true));
+        let mut transaction_manager = MockTransactionManager::new();
+        transaction_manager
+            .expect_begin()
+            .returning(|_, _| Err(DomainError::Unexpected("connection lost".to_string())));
+
+        make_runner(
+            import_job_repository,
+            MockBookRepository::new(),
+            transaction_manager,
+        )
+        .run_job(job, vec![entry("title1")])
+        .await;
+    }
+
+    #[tokio::test]
+    async fn run_leaves_a_users_next_job_until_their_running_one_ends() {
+        // Given: user1 has a job to claim, and nothing else is pending
+        let job = make_job(1);
+        let mut import_job_repository = MockImportJobRepository::new();
+        import_job_repository
+            .expect_claim()
+            .withf(|_, excluded_user_ids, _, _| excluded_user_ids.is_empty())
+            .times(1)
+            .returning(move |_, _, _, _| Ok(Some((job.clone(), vec![entry("title1")]))));
+        // While the job runs, user1's other jobs are not claimed
+        import_job_repository
+            .expect_claim()
+            .withf(|_, excluded_user_ids, _, _| {
+                excluded_user_ids.len() == 1 && excluded_user_ids[0].as_str() == "user1"
+            })
+            .times(1..)
+            .returning(|_, _, _, _| Ok(None));
+        import_job_repository
+            .expect_claim()
+            .withf(|_, excluded_user_ids, _, _| excluded_user_ids.is_empty())
+            .returning(|_, _, _, _| Ok(None));
+        import_job_repository
+            .expect_save_with_tx()
+            .times(1)
+            .returning(|_, _| Ok(true));
+        import_job_repository
+            .expect_save()
+            .times(1)
+            .returning(|_| Ok(true));
+        let mut book_repository = MockBookRepository::new();
+        book_repository
+            .expect_create()
+            .times(1)
+            .returning(|_, _| Ok(101.into()));
+        let mut transaction_manager = MockTransactionManager::new();
+        transaction_manager.expect_begin().returning(|_, _| Ok(()));
+        transaction_manager.expect_commit().returning(|_| Ok(()));
+
+        // When the worker runs with no more jobs queued, it stops once the
+        // claimed job is done
+        let (_, queue) = mpsc::unbounded_channel();
+        ImportJobWorker::new(
+            import_job_repository,
+            book_repository,
+            make_author_repository(),
+            transaction_manager,
+            queue,
+        )
+        .run()
+        .await;
+    }
+}
+
+// Integration tests for resuming jobs against a real database. Requires a
+// PostgreSQL database (feature `test-with-database`).
+#[cfg(all(test, feature = "test-with-database"))]
+mod import_job_integration_tests {
+    use sqlx::PgPool;
+    use tokio::sync::mpsc;
+
+    use crate::{
+        common::types::{BookFormat, BookStore},
+        domain::{
+            entity::{
+                import_job::{ImportJobId, ImportJobStatus},
+                user::{User, UserId},
+            },
+            repository::{
+                import_job_repository::ImportJobRepository, user_repository::UserRepository,
+            },
+        },
+        infrastructure::{
+            author_repository::PgAuthorRepository, book_repository::PgBookRepository,
+            import_job_repository::PgImportJobRepository, transaction::PgTransactionManager,
+            user_repository::PgUserRepository,
+        },
+        use_case::{
+            dto::{book::ImportBookEntryDto, import_job::ImportJobOptionsDto},
+            interactor::import_job::{ImportJobInteractor, ImportJobWorker},
+            traits::import_job::ImportJobUseCase,
+        },
+    };
+
+    async fn prepare_user(pool: &PgPool, id: &str) -> anyhow::Result<UserId> {
+        let user_repository = PgUserRepository::new(pool.clone());
+        let user_id = UserId::new(id.to_string())?;
+        user_repository.create(&User::new(user_id.clone())).await?;
+        Ok(user_id)
+    }
+
+    fn entry(title: &str) -> ImportBookEntryDto {
+        ImportBookEntryDto {
+            title: title.to_string(),
+            author_names: vec!["Job Author".to_string()],
+            isbn: "".to_string(),
+            read: false,
+            owned: false,
+            priority: 50,
+            format: BookFormat::EBook,
+            store: BookStore::Kindle,
+        }
+    }
+
+    // Starts a job whose queue nobody reads, as if the server stopped right
+    // after the job was stored.
+    async fn start_unqueued(
+        pool: &PgPool,
+        user_id: &UserId,
+        books: Vec<ImportBookEntryDto>,
+    ) -> anyhow::Result<ImportJobId> {
+        let (sender, _) = mpsc::unbounded_channel();
+        let interactor = ImportJobInteractor::new(PgImportJobRepository::new(pool.clone()), sender);
+        let job = interactor
+            .start(user_id.as_str(), books, ImportJobOptionsDto::default())
+            .await?;
+        ImportJobId::try_from(job.id.as_str()).map_err(anyhow::Error::msg)
+    }
+
+    // Runs a worker over the unfinished jobs and lets it stop.
+    async fn resume(pool: &PgPool) {
+        let (_, receiver) = mpsc::unbounded_channel();
+        ImportJobWorker::new(
+            PgImportJobRepository::new(pool.clone()),
+            PgBookRepository::new(pool.clone()),
+            PgAuthorRepository::new(pool.clone()),
+            PgTransactionManager::new(pool.clone()),
+            receiver,
+        )
+        .run()
+        .await;
+    }
+
+    async fn book_titles(pool: &PgPool, user_id: &UserId) -> anyhow::Result<Vec<String>> {
+        Ok(
+            sqlx::query_scalar("SELECT title FROM book WHERE user_id = $1 ORDER BY title")
+                .bind(user_id.as_str())
+                .fetch_all(pool)
+                .await?,
+        )
+    }
+
+    #[sqlx::test]
+    async fn worker_resumes_a_pending_job(pool: PgPool) -> anyhow::Result<()> {
+        let user_id = prepare_user(&pool, "user1").await?;
+        let job_id =
+            start_unqueued(&pool, &user_id, vec![entry("Book A"), entry("Book B")]).await?;
+
+        resume(&pool).await;
+
+        let job = PgImportJobRepository::new(pool.clone())
+            .find_by_id(&user_id, &job_id)
+            .await?
+            .unwrap();
+        assert_eq!(job.status, ImportJobStatus::Succeeded);
+        assert_eq!(job.processed_count, 2);
+        assert_eq!(job.event_set_ids.len(), 1);
+        assert_eq!(
+            book_titles(&pool, &user_id).await?,
+            vec!["Book A", "Book B"]
+        );
+        Ok(())
+    }
+
+    #[sqlx::test]
+    async fn worker_resumes_a_running_job_after_its_last_kept_chunk(
+        pool: PgPool,
+    ) -> anyhow::Result<()> {
+        let user_id = prepare_user(&pool, "user1").await?;
+        let job_id =
+            start_unqueued(&pool, &user_id, vec![entry("Book A"), entry("Book B")]).await?;
+        // As left by a server stopped an hour ago after keeping a chunk of
+        // one entry.
+        sqlx::query(
+            "UPDATE import_job
+             SET status = 'running', processed_count = 1, claimed_by = gen_random_uuid(),
+                 updated_at = now() - interval '1 hour'
+             WHERE id = $1",
+        )
+        .bind(job_id.to_uuid())
+        .execute(&pool)
+        .await?;
+
+        resume(&pool).await;
+
+        let job = PgImportJobRepository::new(pool.clone())
+            .find_by_id(&user_id, &job_id)
+            .await?
+            .unwrap();
+        assert_eq!(job.status, ImportJobStatus::Succeeded);
+        assert_eq!(job.processed_count, 2);
+        assert_eq!(book_titles(&pool, &user_id).await?, vec!["Book B"]);
+        Ok(())
+    }
+}
@@ -2,11 +2,21 @@ use async_trait::async_trait;
 
 use crate::use_case::{
     dto::{
-        author::{AuthorDto, CreateAuthorDto, MergeAuthorInputDto, UpdateAuthorDto},
-        book::{CreateBookDto, ImportBookEntryDto, UpdateBookDto},
+        author::{
+            AuthorDto, CreateAuthorDto, MergeAuthorInputDto, PatchAuthorDto, UpdateAuthorDto,
+        },
+        book::{
+            BookPatchDto, BookSelectionDto, CreateBookDto, ImportBookEntryDto, ImportOptionsDto,
+            PatchBookDto, UpdateBookDto,
+        },
+        change::{ApplyChangesResultDto, ChangeOperationDto},
+        event::PurgedTrashDto,
+        event_set::RevertEventSetResultDto,
+        import_job::{ImportJobDto, ImportJobOptionsDto},
         mutation::{
-            AuthorMutationResultDto, BookMutationResultDto, DeleteAuthorResultDto,
-            DeleteBookResultDto, ImportBooksResultDto, MutationResultDto, RestoreAuthorResultDto,
+            AuthorMutationResultDto, BookMutationResultDto, BulkUpdateBooksResultDto,
+            DeleteAuthorResultDto, DeleteAuthorsResultDto, DeleteBookResultDto,
+            DeleteBooksResultDto, ImportBooksResultDto, MutationResultDto, RestoreAuthorResultDto,
             RestoreBookResultDto,
         },
         user::UserDto,
@@ -16,14 +26,38 @@ use crate::use_case::{
         author::{
             CreateAuthorUseCase, DeleteAuthorUseCase, MergeAuthorUseCase, UpdateAuthorUseCase,
         },
-        book::{CreateBookUseCase, DeleteBookUseCase, ImportBooksUseCase, UpdateBookUseCase},
-        event::{RestoreAuthorUseCase, RestoreBookUseCase},
+        book::{
+            BulkUpdateBooksUseCase, CreateBookUseCase, DeleteBookUseCase, ImportBooksUseCase,
+            UpdateBookUseCase,
+        },
+        change::ApplyChangesUseCase,
+        event::{
+            PurgeTrashUseCase, RestoreAuthorUseCase, RestoreBookUseCase, RevertEventSetUseCase,
+        },
+        import_job::ImportJobUseCase,
         mutation::MutationUseCase,
         user::RegisterUserUseCase,
     },
 };
 
-pub struct MutationInteractor<RUUC, CBUC, UBUC, DBUC, CAUC, UAUC, DAUC, MAUC, RBUC, RAUC, IBUC> {
+pub struct MutationInteractor<
+    RUUC,
+    CBUC,
+    UBUC,
+    DBUC,
+    CAUC,
+    UAUC,
+    DAUC,
+    MAUC,
+    RBUC,
+    RAUC,
+    REUC,
+    PTUC,
+    IBUC,
+    BUBUC,
+    ACUC,
+    IJUC,
+> {
     register_user_use_case: RUUC,
     create_book_use_case: CBUC,
     update_book_use_case: UBUC,
@@ -34,11 +68,50 @@ pub struct MutationInteractor<RUUC, CBUC, UBUC, DBUC, CAUC, UAUC, DAUC, MAUC, RB
     merge_author_use_case: MAUC,
     restore_book_use_case: RBUC,
     restore_author_use_case: RAUC,
+    revert_event_set_use_case: REUC,
+    purge_trash_use_case: PTUC,
     import_books_use_case: IBUC,
+    bulk_update_books_use_case: BUBUC,
+    apply_changes_use_case: ACUC,
+    import_job_use_case: IJUC,
 }
 
-impl<RUUC, CBUC, UBUC, DBUC, CAUC, UAUC, DAUC, MAUC, RBUC, RAUC, IBUC>
-    MutationInteractor<RUUC, CBUC, UBUC, DBUC, CAUC, UAUC, DAUC, MAUC, RBUC, RAUC, IBUC>
+impl<
+    RUUC,
+    CBUC,
+    UBUC,
+    DBUC,
+    CAUC,
+    UAUC,
+    DAUC,
+    MAUC,
+    RBUC,
+    RAUC,
+    REUC,
+    PTUC,
+    IBUC,
+    BUBUC,
+    ACUC,
+    IJUC,
+>
+    MutationInteractor<
+        RUUC,
+        CBUC,
+        UBUC,
+        DBUC,
+        CAUC,
+        UAUC,
+        DAUC,
+        MAUC,
+        RBUC,
+        RAUC,
+        REUC,
+        PTUC,
+        IBUC,
+        BUBUC,
+        ACUC,
+        IJUC,
+    >
 {
     // This constructor takes many arguments because MutationInteractor composes all
     // mutation use cases via dependency injection. Splitting it would reduce clarity
@@ -55,7 +128,12 @@ impl<RUUC, CBUC, UBUC, DBUC, CAUC, UAUC, DAUC, MAUC, RBUC, RAUC, IBUC>
         merge_author_use_case: MAUC,
         restore_book_use_case: RBUC,
         restore_author_use_case: RAUC,
+        revert_event_set_use_case: REUC,
+        purge_trash_use_case: PTUC,
         import_books_use_case: IBUC,
+        bulk_update_books_use_case: BUBUC,
+        apply_changes_use_case: ACUC,
+        import_job_use_case: IJUC,
     ) -> Self {
         Self {
             register_user_use_case,
@@ -68,14 +146,53 @@ impl<RUUC, CBUC, UBUC, DBUC, CAUC, UAUC, DAUC, MAUC, RBUC, RAUC, IBUC>
             merge_author_use_case,
             restore_book_use_case,
             restore_author_use_case,
+            revert_event_set_use_case,
+            purge_trash_use_case,
             import_books_use_case,
+            bulk_update_books_use_case,
+            apply_changes_use_case,
+            import_job_use_case,
         }
     }
 }
 
 #[async_trait]
-impl<RUUC, CBUC, UBUC, DBUC, CAUC, UAUC, DAUC, MAUC, RBUC, RAUC, IBUC> MutationUseCase
-    for MutationInteractor<RUUC, CBUC, UBUC, DBUC, CAUC, UAUC, DAUC, MAUC, RBUC, RAUC, IBUC>
+impl<
+    RUUC,
+    CBUC,
+    UBUC,
+    DBUC,
+    CAUC,
+    UAUC,
+    DAUC,
+    MAUC,
+    RBUC,
+    RAUC,
+    REUC,
+    PTUC,
+    IBUC,
+    BUBUC,
+    ACUC,
+    IJUC,
+> MutationUseCase
+    for MutationInteractor<
+        RUUC,
+        CBUC,
+        UBUC,
+        DBUC,
+        CAUC,
+        UAUC,
+        DAUC,
+        MAUC,
+        RBUC,
+        RAUC,
+        REUC,
+        PTUC,
+        IBUC,
+        BUBUC,
+        ACUC,
+        IJUC,
+    >
 where
     RUUC: RegisterUserUseCase,
     CBUC: CreateBookUseCase,
@@ -87,7 +204,12 @@ where
     MAUC: MergeAuthorUseCase,
     RBUC: RestoreBookUseCase,
     RAUC: RestoreAuthorUseCase,
+    REUC: RevertEventSetUseCase,
+    PTUC: PurgeTrashUseCase,
     IBUC: ImportBooksUseCase,
+    BUBUC: BulkUpdateBooksUseCase,
+    ACUC: ApplyChangesUseCase,
+    IJUC: ImportJobUseCase,
 {
     async fn register_user(&self, user_id: &str) -> Result<UserDto, UseCaseError> {
         let user = self.register_user_use_case.register_user(user_id).await?;
@@ -112,6 +234,28 @@ where
         Ok(book)
     }
 
+    async fn patch_book(
+        &self,
+        user_id: &str,
+        book_data: PatchBookDto,
+    ) -> Result<BookMutationResultDto, UseCaseError> {
+        let book = self.update_book_use_case.patch(user_id, book_data).await?;
+        Ok(book)
+    }
+
+    async fn bulk_update_books(
+        &self,
+        user_id: &str,
+        selection: BookSelectionDto,
+        patch: BookPatchDto,
+    ) -> Result<BulkUpdateBooksResultDto, UseCaseError> {
+        let result = self
+            .bulk_update_books_use_case
+            .bulk_update(user_id, selection, patch)
+            .await?;
+        Ok(result)
+    }
+
     async fn delete_book(
         &self,
         user_id: &str,
@@ -121,6 +265,18 @@ where
         Ok(result)
     }
 
+    async fn delete_books(
+        &self,
+        user_id: &str,
+        book_ids: Vec<String>,
+    ) -> Result<DeleteBooksResultDto, UseCaseError> {
+        let result = self
+            .delete_book_use_case
+            .delete_many(user_id, book_ids)
+            .await?;
+        Ok(result)
+    }
+
     async fn create_author(
         &self,
         user_id: &str,
@@ -145,6 +301,18 @@ where
         Ok(author)
     }
 
+    async fn patch_author(
+        &self,
+        user_id: &str,
+        author_data: PatchAuthorDto,
+    ) -> Result<AuthorMutationResultDto, UseCaseError> {
+        let author = self
+            .update_author_use_case
+            .patch(user_id, author_data)
+            .await?;
+        Ok(author)
+    }
+
     async fn delete_author(
         &self,
         user_id: &str,
@@ -157,6 +325,18 @@ where
         Ok(result)
     }
 
+    async fn delete_authors(
+        &self,
+        user_id: &str,
+        author_ids: Vec<String>,
+    ) -> Result<DeleteAuthorsResultDto, UseCaseError> {
+        let result = self
+            .delete_author_use_case
+            .delete_many(user_id, author_ids)
+            .await?;
+        Ok(result)
+    }
+
     async fn merge_author(
         &self,
         user_id: &str,
@@ -169,8 +349,11 @@ where
         &self,
         user_id: &str,
         event_id: i64,
+        cascade: bool,
     ) -> Result<RestoreBookResultDto, UseCaseError> {
-        self.restore_book_use_case.restore(user_id, event_id).await
+        self.restore_book_use_case
+            .restore(user_id, event_id, cascade)
+            .await
     }
 
     async fn restore_author(
@@ -183,12 +366,63 @@ where
             .await
     }
 
+    async fn revert_event_set(
+        &self,
+        user_id: &str,
+        event_set_id: &str,
+        dry_run: bool,
+    ) -> Result<RevertEventSetResultDto, UseCaseError> {
+        self.revert_event_set_use_case
+            .revert(user_id, event_set_id, dry_run)
+            .await
+    }
+
+    async fn purge_trash(
+        &self,
+        user_id: &str,
+        retention_days: i32,
+    ) -> Result<PurgedTrashDto, UseCaseError> {
+        self.purge_trash_use_case
+            .purge(user_id, retention_days)
+            .await
+    }
+
     async fn import_books(
         &self,
         user_id: &str,
         books: Vec<ImportBookEntryDto>,
+        options: ImportOptionsDto,
     ) -> Result<ImportBooksResultDto, UseCaseError> {
-        self.import_books_use_case.import(user_id, books).await
+        self.import_books_use_case
+            .import(user_id, books, options)
+            .await
+    }
+
+    async fn apply_changes(
+        &self,
+        user_id: &str,
+        operations: Vec<ChangeOperationDto>,
+    ) -> Result<ApplyChangesResultDto, UseCaseError> {
+        self.apply_changes_use_case.apply(user_id, operations).await
+    }
+
+    async fn start_import(
+        &self,
+        user_id: &str,
+        books: Vec<ImportBookEntryDto>,
+        options: ImportJobOptionsDto,
+    ) -> Result<ImportJobDto, UseCaseError> {
+        self.import_job_use_case
+            .start(user_id, books, options)
+            .await
+    }
+
+    async fn cancel_import(
+        &self,
+        user_id: &str,
+        job_id: &str,
+    ) -> Result<ImportJobDto, UseCaseError> {
+        self.import_job_use_case.cancel(user_id, job_id).await
     }
 }
 
@@ -197,12 +431,22 @@ mod tests {
     use mockall::predicate::{always, eq};
 
     use crate::common::types::{BookFormat, BookStore};
-    use crate::use_case::dto::mutation::{MutationResultDto, SingleEventMutationResultDto};
+    use crate::use_case::dto::mutation::{
+        BulkUpdateBooksResultDto, ImportBooksResultDto, MutationResultDto, RestoredBookDto,
+        SingleEventMutationResultDto,
+    };
     use crate::use_case::error::UseCaseError;
     use crate::use_case::{
         dto::{
             author::{AuthorDto, CreateAuthorDto, UpdateAuthorDto},
-            book::{BookDto, CreateBookDto, ImportBookEntryDto, UpdateBookDto},
+            book::{
+                BookDto, BookPatchDto, BookSelectionDto, CreateBookDto, ImportBookEntryDto,
+                ImportModeDto, ImportOptionsDto, PatchBookDto, UpdateBookDto,
+            },
+            change::{AppliedChangeDto, ChangeOperationDto},
+            event::PurgedTrashDto,
+            event_set::RevertEventSetResultDto,
+            import_job::{ImportJobDto, ImportJobOptionsDto, ImportJobStatusDto},
             user::UserDto,
         },
         interactor::mutation::MutationInteractor,
@@ -212,10 +456,15 @@ mod tests {
                 MockUpdateAuthorUseCase,
             },
             book::{
-                MockCreateBookUseCase, MockDeleteBookUseCase, MockImportBooksUseCase,
-                MockUpdateBookUseCase,
+                MockBulkUpdateBooksUseCase, MockCreateBookUseCase, MockDeleteBookUseCase,
+                MockImportBooksUseCase, MockUpdateBookUseCase,
+            },
+            change::MockApplyChangesUseCase,
+            event::{
+                MockPurgeTrashUseCase, MockRestoreAuthorUseCase, MockRestoreBookUseCase,
+                MockRevertEventSetUseCase,
             },
-            event::{MockRestoreAuthorUseCase, MockRestoreBookUseCase},
+            import_job::MockImportJobUseCase,
             mutation::MutationUseCase,
             user::MockRegisterUserUseCase,
         },
@@ -234,7 +483,12 @@ mod tests {
         MockMergeAuthorUseCase,
         MockRestoreBookUseCase,
         MockRestoreAuthorUseCase,
+        MockRevertEventSetUseCase,
+        MockPurgeTrashUseCase,
         MockImportBooksUseCase,
+        MockBulkUpdateBooksUseCase,
+        MockApplyChangesUseCase,
+        MockImportJobUseCase,
     >;
 
     struct InteractorBuilder {
@@ -248,7 +502,12 @@ mod tests {
         merge_author: MockMergeAuthorUseCase,
         restore_book: MockRestoreBookUseCase,
         restore_author: MockRestoreAuthorUseCase,
+        revert_event_set: MockRevertEventSetUseCase,
+        purge_trash: MockPurgeTrashUseCase,
         import_books: MockImportBooksUseCase,
+        bulk_update_books: MockBulkUpdateBooksUseCase,
+        apply_changes: MockApplyChangesUseCase,
+        import_job: MockImportJobUseCase,
     }
 
     impl InteractorBuilder {
@@ -264,7 +523,12 @@ mod tests {
                 merge_author: MockMergeAuthorUseCase::new(),
                 restore_book: MockRestoreBookUseCase::new(),
                 restore_author: MockRestoreAuthorUseCase::new(),
+                revert_event_set: MockRevertEventSetUseCase::new(),
+                purge_trash: MockPurgeTrashUseCase::new(),
                 import_books: MockImportBooksUseCase::new(),
+                bulk_update_books: MockBulkUpdateBooksUseCase::new(),
+                apply_changes: MockApplyChangesUseCase::new(),
+                import_job: MockImportJobUseCase::new(),
             }
         }
 
@@ -318,11 +582,36 @@ mod tests {
             self
         }
 
+        fn with_revert_event_set(mut self, mock: MockRevertEventSetUseCase) -> Self {
+            self.revert_event_set = mock;
+            self
+        }
+
+        fn with_purge_trash(mut self, mock: MockPurgeTrashUseCase) -> Self {
+            self.purge_trash = mock;
+            self
+        }
+
         fn with_import_books(mut self, mock: MockImportBooksUseCase) -> Self {
             self.import_books = mock;
             self
         }
 
+        fn with_bulk_update_books(mut self, mock: MockBulkUpdateBooksUseCase) -> Self {
+            self.bulk_update_books = mock;
+            self
+        }
+
+        fn with_apply_changes(mut self, mock: MockApplyChangesUseCase) -> Self {
+            self.apply_changes = mock;
+            self
+        }
+
+        fn with_import_job(mut self, mock: MockImportJobUseCase) -> Self {
+            self.import_job = mock;
+            self
+        }
+
         fn build(self) -> DefaultInteractor {
             MutationInteractor::new(
                 self.register_user,
@@ -335,7 +624,12 @@ mod tests {
                 self.merge_author,
                 self.restore_book,
                 self.restore_author,
+                self.revert_event_set,
+                self.purge_trash,
                 self.import_books,
+                self.bulk_update_books,
+                self.apply_changes,
+                self.import_job,
             )
         }
     }
@@ -462,6 +756,117 @@ mod tests {
         assert_eq!(result.event_id.value(), 102);
     }
 
+    #[tokio::test]
+    async fn patch_book_delegates_to_sub_use_case() {
+        // Given
+        let book_id = Uuid::new_v4().hyphenated().to_string();
+        let expected_id = book_id.clone();
+
+        let mut mock_update_book = MockUpdateBookUseCase::new();
+        mock_update_book.expect_update().times(0);
+        mock_update_book
+            .expect_patch()
+            .withf(|_, data| data.patch.read == Some(true) && data.patch.title.is_none())
+            .returning(move |_, _| {
+                Ok(SingleEventMutationResultDto::new(
+                    make_book_dto(&book_id),
+                    "event-set".to_string(),
+                    105.into(),
+                ))
+            });
+
+        let interactor = InteractorBuilder::new()
+            .with_update_book(mock_update_book)
+            .build();
+
+        let book_data = PatchBookDto::new(
+            expected_id.clone(),
+            BookPatchDto {
+                read: Some(true),
+                ..Default::default()
+            },
+        );
+
+        // When
+        let result = interactor.patch_book("user1", book_data).await;
+
+        // Then
+        let result = result.unwrap();
+        assert_eq!(result.id, expected_id);
+        assert_eq!(result.event_id.value(), 105);
+    }
+
+    #[tokio::test]
+    async fn bulk_update_books_delegates_to_sub_use_case() {
+        // Given
+        let mut mock_bulk_update_books = MockBulkUpdateBooksUseCase::new();
+        mock_bulk_update_books
+            .expect_bulk_update()
+            .withf(|_, selection, patch| {
+                matches!(selection, BookSelectionDto::Ids(ids) if ids.len() == 2)
+                    && patch.owned == Some(true)
+            })
+            .returning(|_, _, _| {
+                Ok(BulkUpdateBooksResultDto {
+                    updated_count: 2,
+                    event_set_id: Some("event-set".to_string()),
+                })
+            });
+
+        let interactor = InteractorBuilder::new()
+            .with_bulk_update_books(mock_bulk_update_books)
+            .build();
+
+        // When
+        let result = interactor
+            .bulk_update_books(
+                "user1",
+                BookSelectionDto::Ids(vec!["a".to_string(), "b".to_string()]),
+                BookPatchDto {
+                    owned: Some(true),
+                    ..Default::default()
+                },
+            )
+            .await;
+
+        // Then
+        assert_eq!(result.unwrap().updated_count, 2);
+    }
+
+    #[tokio::test]
+    async fn apply_changes_delegates_to_sub_use_case() {
+        // Given
+        let mut mock_apply_changes = MockApplyChangesUseCase::new();
+        mock_apply_changes
+            .expect_apply()
+            .withf(|_, operations| operations.len() == 1)
+            .returning(|_, _| {
+                Ok(MutationResultDto::new(
+                    vec![AppliedChangeDto::DeletedBook {
+                        id: "deleted-id".to_string(),
+                    }],
+                    "event-set".to_string(),
+                ))
+            });
+
+        let interactor = InteractorBuilder::new()
+            .with_apply_changes(mock_apply_changes)
+            .build();
+
+        // When
+        let result = interactor
+            .apply_changes(
+                "user1",
+                vec![ChangeOperationDto::DeleteBook {
+                    id: "deleted-id".to_string(),
+                }],
+            )
+            .await;
+
+        // Then
+        assert_eq!(result.unwrap().event_set_id, "event-set");
+    }
+
     #[tokio::test]
     async fn delete_book_delegates_to_sub_use_case() {
         // Given
@@ -489,6 +894,28 @@ mod tests {
         assert!(result.is_ok());
     }
 
+    #[tokio::test]
+    async fn delete_books_delegates_to_sub_use_case() {
+        // Given
+        let mut mock_delete_book = MockDeleteBookUseCase::new();
+        mock_delete_book
+            .expect_delete_many()
+            .withf(|_, book_ids| book_ids.len() == 2)
+            .returning(|_, book_ids| Ok(MutationResultDto::new(book_ids, "event-set".to_string())));
+
+        let interactor = InteractorBuilder::new()
+            .with_delete_book(mock_delete_book)
+            .build();
+
+        // When
+        let result = interactor
+            .delete_books("user1", vec!["a".to_string(), "b".to_string()])
+            .await;
+
+        // Then
+        assert_eq!(result.unwrap().value, vec!["a", "b"]);
+    }
+
     #[tokio::test]
     async fn create_author_delegates_to_sub_use_case() {
         // Given
@@ -591,6 +1018,30 @@ mod tests {
         assert!(result.is_ok());
     }
 
+    #[tokio::test]
+    async fn delete_authors_delegates_to_sub_use_case() {
+        // Given
+        let mut mock_delete_author = MockDeleteAuthorUseCase::new();
+        mock_delete_author
+            .expect_delete_many()
+            .withf(|_, author_ids| author_ids.len() == 2)
+            .returning(|_, author_ids| {
+                Ok(MutationResultDto::new(author_ids, "event-set".to_string()))
+            });
+
+        let interactor = InteractorBuilder::new()
+            .with_delete_author(mock_delete_author)
+            .build();
+
+        // When
+        let result = interactor
+            .delete_authors("user1", vec!["a".to_string(), "b".to_string()])
+            .await;
+
+        // Then
+        assert_eq!(result.unwrap().value, vec!["a", "b"]);
+    }
+
     #[tokio::test]
     async fn merge_author_delegates_to_sub_use_case() {
         let mut mock_merge_author = MockMergeAuthorUseCase::new();
@@ -638,10 +1089,10 @@ mod tests {
         let mut mock_restore_book = MockRestoreBookUseCase::new();
         mock_restore_book
             .expect_restore()
-            .with(always(), always())
-            .returning(move |_, _| {
+            .with(always(), always(), always())
+            .returning(move |_, _, _| {
                 Ok(MutationResultDto::new(
-                    Some(make_book_dto(&book_id)),
+                    RestoredBookDto::new(Some(make_book_dto(&book_id))),
                     "event-set".to_string(),
                 ))
             });
@@ -651,11 +1102,11 @@ mod tests {
             .build();
 
         // When
-        let result = interactor.restore_book("user1", 42).await;
+        let result = interactor.restore_book("user1", 42, false).await;
 
         // Then
         assert!(result.is_ok());
-        assert_eq!(result.unwrap().value.unwrap().id, expected_id);
+        assert_eq!(result.unwrap().value.book.unwrap().id, expected_id);
     }
 
     #[tokio::test]
@@ -664,19 +1115,24 @@ mod tests {
         let mut mock_restore_book = MockRestoreBookUseCase::new();
         mock_restore_book
             .expect_restore()
-            .with(always(), always())
-            .returning(|_, _| Ok(MutationResultDto::new(None, "event-set".to_string())));
+            .with(always(), always(), always())
+            .returning(|_, _, _| {
+                Ok(MutationResultDto::new(
+                    RestoredBookDto::new(None),
+                    "event-set".to_string(),
+                ))
+            });
 
         let interactor = InteractorBuilder::new()
             .with_restore_book(mock_restore_book)
             .build();
 
         // When
-        let result = interactor.restore_book("user1", 42).await;
+        let result = interactor.restore_book("user1", 42, false).await;
 
         // Then
         assert!(result.is_ok());
-        assert!(result.unwrap().is_none());
+        assert!(result.unwrap().book.is_none());
     }
 
     #[tokio::test]
@@ -711,6 +1167,61 @@ mod tests {
         assert_eq!(result.unwrap().value.unwrap().name, "Test Author");
     }
 
+    #[tokio::test]
+    async fn revert_event_set_delegates_to_sub_use_case() {
+        // Given
+        let mut mock_revert_event_set = MockRevertEventSetUseCase::new();
+        mock_revert_event_set
+            .expect_revert()
+            .with(always(), eq("event-set"), eq(true))
+            .returning(|_, _, _| {
+                Ok(RevertEventSetResultDto {
+                    event_set_id: None,
+                    changes: vec![],
+                    conflicts: vec![],
+                })
+            });
+
+        let interactor = InteractorBuilder::new()
+            .with_revert_event_set(mock_revert_event_set)
+            .build();
+
+        // When
+        let result = interactor
+            .revert_event_set("user1", "event-set", true)
+            .await;
+
+        // Then
+        assert_eq!(result.unwrap().event_set_id, None);
+    }
+
+    #[tokio::test]
+    async fn purge_trash_delegates_to_sub_use_case() {
+        // Given
+        let mut mock_purge_trash = MockPurgeTrashUseCase::new();
+        mock_purge_trash
+            .expect_purge()
+            .with(eq("user1"), eq(30))
+            .returning(|_, _| {
+                Ok(PurgedTrashDto {
+                    book_count: 2,
+                    author_count: 1,
+                    event_set_count: 3,
+                    event_set_id: None,
+                })
+            });
+
+        let interactor = InteractorBuilder::new()
+            .with_purge_trash(mock_purge_trash)
+            .build();
+
+        // When
+        let result = interactor.purge_trash("user1", 30).await;
+
+        // Then
+        assert_eq!(result.unwrap().book_count, 2);
+    }
+
     #[tokio::test]
     async fn restore_author_delete_event_returns_none() {
         // Given
@@ -738,15 +1249,20 @@ mod tests {
         let mut mock_restore_book = MockRestoreBookUseCase::new();
         mock_restore_book
             .expect_restore()
-            .with(eq("user1"), eq(42_i64))
-            .returning(|_, _| Ok(MutationResultDto::new(None, "event-set".to_string())));
+            .with(eq("user1"), eq(42_i64), eq(true))
+            .returning(|_, _, _| {
+                Ok(MutationResultDto::new(
+                    RestoredBookDto::new(None),
+                    "event-set".to_string(),
+                ))
+            });
 
         let interactor = InteractorBuilder::new()
             .with_restore_book(mock_restore_book)
             .build();
 
         // When
-        let result = interactor.restore_book("user1", 42).await;
+        let result = interactor.restore_book("user1", 42, true).await;
 
         // Then
         assert!(result.is_ok());
@@ -758,8 +1274,8 @@ mod tests {
         let mut mock_restore_book = MockRestoreBookUseCase::new();
         mock_restore_book
             .expect_restore()
-            .with(always(), always())
-            .returning(|_, _| {
+            .with(always(), always(), always())
+            .returning(|_, _, _| {
                 Err(UseCaseError::NotFound {
                     entity_type: "Book",
                     entity_id: "999".to_string(),
@@ -772,7 +1288,7 @@ mod tests {
             .build();
 
         // When
-        let result = interactor.restore_book("user1", 999).await;
+        let result = interactor.restore_book("user1", 999, false).await;
 
         // Then
         assert!(matches!(result, Err(UseCaseError::NotFound { .. })));
@@ -829,16 +1345,26 @@ mod tests {
         // Given
         let book_id = Uuid::new_v4().hyphenated().to_string();
         let expected_dto = make_book_dto(&book_id);
+        let options = ImportOptionsDto {
+            mode: ImportModeDto::UpsertByIsbn,
+            partial: false,
+            dry_run: true,
+        };
 
         let mut mock_import_books = MockImportBooksUseCase::new();
         mock_import_books
             .expect_import()
-            .with(eq("user1"), always())
-            .returning(move |_, _| {
-                Ok(MutationResultDto::new(
-                    vec![make_book_dto(&book_id)],
-                    "event-set".to_string(),
-                ))
+            .with(eq("user1"), always(), eq(options))
+            .returning(move |_, _, _| {
+                Ok(ImportBooksResultDto {
+                    created: vec![make_book_dto(&book_id)],
+                    updated: vec![],
+                    skipped: vec![],
+                    new_author_names: vec!["Author".to_string()],
+                    reused_author_names: vec![],
+                    errors: vec![],
+                    event_set_id: None,
+                })
             });
 
         let interactor = InteractorBuilder::new()
@@ -857,10 +1383,80 @@ mod tests {
         }];
 
         // When
-        let result = interactor.import_books("user1", books).await;
+        let result = interactor.import_books("user1", books, options).await;
 
         // Then
-        assert!(result.is_ok());
-        assert_eq!(result.unwrap()[0].id, expected_dto.id);
+        assert_eq!(result.unwrap().created[0].id, expected_dto.id);
+    }
+
+    fn make_import_job_dto(id: &str, status: ImportJobStatusDto) -> ImportJobDto {
+        ImportJobDto {
+            id: id.to_string(),
+            status,
+            mode: ImportModeDto::CreateOnly,
+            partial: true,
+            total_count: 1,
+            processed_count: 0,
+            failures: vec![],
+            event_set_ids: vec![],
+            error: None,
+            created_at: OffsetDateTime::now_utc(),
+            updated_at: OffsetDateTime::now_utc(),
+        }
+    }
+
+    #[tokio::test]
+    async fn start_import_delegates_to_sub_use_case() {
+        // Given
+        let options = ImportJobOptionsDto {
+            mode: ImportModeDto::CreateOnly,
+            partial: true,
+        };
+        let mut mock_import_job = MockImportJobUseCase::new();
+        mock_import_job
+            .expect_start()
+            .with(eq("user1"), always(), eq(options))
+            .returning(|_, _, _| Ok(make_import_job_dto("job-id", ImportJobStatusDto::Pending)));
+
+        let interactor = InteractorBuilder::new()
+            .with_import_job(mock_import_job)
+            .build();
+
+        let books = vec![ImportBookEntryDto {
+            title: "Imported Book".to_string(),
+            author_names: vec!["Author".to_string()],
+            isbn: "".to_string(),
+            read: false,
+            owned: false,
+            priority: 50,
+            format: BookFormat::Unknown,
+            store: BookStore::Unknown,
+        }];
+
+        // When
+        let result = interactor.start_import("user1", books, options).await;
+
+        // Then
+        assert_eq!(result.unwrap().id, "job-id");
+    }
+
+    #[tokio::test]
+    async fn cancel_import_delegates_to_sub_use_case() {
+        // Given
+        let mut mock_import_job = MockImportJobUseCase::new();
+        mock_import_job
+            .expect_cancel()
+            .with(eq("user1"), eq("job-id"))
+            .returning(|_, job_id| Ok(make_import_job_dto(job_id, ImportJobStatusDto::Cancelled)));
+
+        let interactor = InteractorBuilder::new()
+            .with_import_job(mock_import_job)
+            .build();
+
+        // When
+        let result = interactor.cancel_import("user1", "job-id").await;
+
+        // Then
+        assert_eq!(result.unwrap().status, ImportJobStatusDto::Cancelled);
     }
 }
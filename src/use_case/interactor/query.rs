@@ -1,23 +1,47 @@
-use std::collections::HashMap;
+use std::{collections::HashMap, hash::Hash};
 
 use async_trait::async_trait;
+use time::OffsetDateTime;
 
 use crate::{
+    common::sort::{AuthorOrder, BookOrder},
     domain::{
-        entity::{author::AuthorId, book::BookId, event_set::EventSetId, user::UserId},
+        entity::{
+            author::AuthorId,
+            book::BookId,
+            event::{EventOperation, EventSetOperation},
+            event_set::EventSetId,
+            import_job::ImportJobId,
+            user::UserId,
+        },
         error::DomainError,
         repository::{
-            author_event_repository::AuthorEventRepository, author_repository::AuthorRepository,
-            book_event_repository::BookEventRepository, book_repository::BookRepository,
-            event_set_repository::EventSetRepository, user_repository::UserRepository,
+            activity::ActivityRange,
+            author_event_repository::AuthorEventRepository,
+            author_repository::{AuthorCursor, AuthorRepository},
+            book_event_repository::BookEventRepository,
+            book_repository::{BookCursor, BookFilter, BookRepository},
+            event_set_repository::EventSetRepository,
+            history::{EventCursor, EventSetCursor},
+            import_job_repository::ImportJobRepository,
+            search::SearchQuery,
+            user_repository::UserRepository,
         },
     },
     use_case::{
         dto::{
-            author::AuthorDto,
-            book::BookDto,
-            event::{AuthorEventDto, BookEventDto},
+            activity::{ActivityPeriodDto, ActivityRangeDto},
+            author::{AuthorCursorDto, AuthorDto},
+            book::{BookCursorDto, BookDto, BookFilterDto},
+            event::{AuthorEventDto, BookEventDto, DeletedAuthorDto, DeletedBookDto},
             event_set::{EventSetDetailDto, EventSetDto},
+            history::{
+                EntityDiffDto, HistoryCursorDto, HistoryFilterDto, LibraryDiffDto, PointInTimeDto,
+            },
+            import_job::ImportJobDto,
+            pagination::{PageDto, PageRequestDto},
+            search::{MAX_SEARCH_LIMIT, SearchResultDto},
+            stats::{LibraryStatsDto, MAX_TOP_AUTHORS},
             user::UserDto,
         },
         error::UseCaseError,
@@ -26,17 +50,83 @@ use crate::{
 };
 
 #[derive(Debug, Clone)]
-pub struct QueryInteractor<UR, BR, AR, BER, AER, ESR> {
+pub struct QueryInteractor<UR, BR, AR, BER, AER, ESR, IJR> {
     pub user_repository: UR,
     pub book_repository: BR,
     pub author_repository: AR,
     pub book_event_repository: BER,
     pub author_event_repository: AER,
     pub event_set_repository: ESR,
+    pub import_job_repository: IJR,
+}
+
+impl<UR, BR, AR, BER, AER, ESR, IJR> QueryInteractor<UR, BR, AR, BER, AER, ESR, IJR>
+where
+    ESR: EventSetRepository,
+{
+    async fn resolve_point_in_time(
+        &self,
+        user_id: &UserId,
+        at: PointInTimeDto,
+    ) -> Result<OffsetDateTime, UseCaseError> {
+        match at {
+            PointInTimeDto::Timestamp(at) => Ok(at),
+            PointInTimeDto::EventSet(event_set_id_text) => {
+                let event_set_id = EventSetId::try_from(event_set_id_text.as_str())
+                    .map_err(|e| UseCaseError::from(DomainError::Unexpected(e)))?;
+                let event_set = self
+                    .event_set_repository
+                    .find_by_id(user_id, &event_set_id)
+                    .await?
+                    .ok_or_else(|| UseCaseError::NotFound {
+                        entity_type: "event_set",
+                        entity_id: event_set_id_text,
+                        user_id: user_id.as_str().to_string(),
+                    })?;
+                // Every event of a set shares the set's transaction timestamp.
+                Ok(event_set.created_at)
+            }
+        }
+    }
+}
+
+// Pairs up entities by key, converting them to DTOs. Added and changed
+// entities follow the order of `after`, removed ones the order of `before`.
+fn diff_entities<E, D, K>(before: Vec<E>, after: Vec<E>, key: impl Fn(&E) -> K) -> EntityDiffDto<D>
+where
+    E: PartialEq,
+    D: From<E>,
+    K: Clone + Eq + Hash,
+{
+    let before_keys: Vec<K> = before.iter().map(&key).collect();
+    let mut before: HashMap<K, E> = before_keys.iter().cloned().zip(before).collect();
+    let mut added = Vec::new();
+    let mut changed = Vec::new();
+    for entity in after {
+        match before.remove(&key(&entity)) {
+            None => added.push(D::from(entity)),
+            Some(previous) if previous != entity => {
+                changed.push((D::from(previous), D::from(entity)))
+            }
+            Some(_) => {}
+        }
+    }
+    let removed = before_keys
+        .iter()
+        .filter_map(|k| before.remove(k))
+        .map(D::from)
+        .collect();
+
+    EntityDiffDto {
+        added,
+        removed,
+        changed,
+    }
 }
 
 #[async_trait]
-impl<UR, BR, AR, BER, AER, ESR> QueryUseCase for QueryInteractor<UR, BR, AR, BER, AER, ESR>
+impl<UR, BR, AR, BER, AER, ESR, IJR> QueryUseCase
+    for QueryInteractor<UR, BR, AR, BER, AER, ESR, IJR>
 where
     UR: UserRepository,
     BR: BookRepository,
@@ -44,6 +134,7 @@ where
     BER: BookEventRepository,
     AER: AuthorEventRepository,
     ESR: EventSetRepository,
+    IJR: ImportJobRepository,
 {
     async fn find_user_by_id(&self, raw_user_id: &str) -> Result<Option<UserDto>, UseCaseError> {
         let user_id = UserId::new(raw_user_id.to_string())?;
@@ -64,17 +155,61 @@ where
         Ok(book)
     }
 
-    async fn find_all_books(&self, user_id: &str) -> Result<Vec<BookDto>, UseCaseError> {
+    async fn find_all_books(
+        &self,
+        user_id: &str,
+        filter: BookFilterDto,
+        order: BookOrder,
+    ) -> Result<Vec<BookDto>, UseCaseError> {
         let user_id = UserId::new(user_id.to_string())?;
-        let books = self.book_repository.find_all(&user_id).await?;
+        let filter = BookFilter::try_from(filter)?;
+        let books = self
+            .book_repository
+            .find_all(&user_id, &filter, &order)
+            .await?;
         let books: Vec<BookDto> = books.into_iter().map(BookDto::from).collect();
         Ok(books)
     }
 
+    async fn find_books_page(
+        &self,
+        user_id: &str,
+        filter: BookFilterDto,
+        order: BookOrder,
+        page: PageRequestDto<BookCursorDto>,
+    ) -> Result<PageDto<BookDto, BookCursorDto>, UseCaseError> {
+        let user_id = UserId::new(user_id.to_string())?;
+        let filter = BookFilter::try_from(filter)?;
+        let page = page.try_into_page_request(BookCursor::try_from)?;
+        let books = self
+            .book_repository
+            .find_page(&user_id, &filter, &order, &page)
+            .await?;
+        Ok(books.into())
+    }
+
+    async fn find_books_page_by_author_id(
+        &self,
+        user_id: &str,
+        author_id: &str,
+        order: BookOrder,
+        page: PageRequestDto<BookCursorDto>,
+    ) -> Result<PageDto<BookDto, BookCursorDto>, UseCaseError> {
+        let user_id = UserId::new(user_id.to_string())?;
+        let author_id = AuthorId::try_from(author_id)?;
+        let page = page.try_into_page_request(BookCursor::try_from)?;
+        let books = self
+            .book_repository
+            .find_page_by_author_id(&user_id, &author_id, &order, &page)
+            .await?;
+        Ok(books.into())
+    }
+
     async fn find_books_by_author_ids_as_hash_map(
         &self,
         user_id: &str,
         author_ids: &[String],
+        order: BookOrder,
     ) -> Result<HashMap<String, Vec<BookDto>>, UseCaseError> {
         let user_id = UserId::new(user_id.to_string())?;
         let author_ids: Vec<AuthorId> = author_ids
@@ -83,7 +218,7 @@ where
             .collect::<Result<_, DomainError>>()?;
         let books_by_author = self
             .book_repository
-            .find_by_author_ids_as_hash_map(&user_id, &author_ids)
+            .find_by_author_ids_as_hash_map(&user_id, &author_ids, &order)
             .await?;
 
         Ok(books_by_author
@@ -114,13 +249,32 @@ where
         Ok(author.map(AuthorDto::from))
     }
 
-    async fn find_all_authors(&self, user_id: &str) -> Result<Vec<AuthorDto>, UseCaseError> {
+    async fn find_all_authors(
+        &self,
+        user_id: &str,
+        order: AuthorOrder,
+    ) -> Result<Vec<AuthorDto>, UseCaseError> {
         let user_id = UserId::new(user_id.to_string())?;
-        let authors = self.author_repository.find_all(&user_id).await?;
+        let authors = self.author_repository.find_all(&user_id, &order).await?;
         let authors: Vec<AuthorDto> = authors.into_iter().map(AuthorDto::from).collect();
         Ok(authors)
     }
 
+    async fn find_authors_page(
+        &self,
+        user_id: &str,
+        order: AuthorOrder,
+        page: PageRequestDto<AuthorCursorDto>,
+    ) -> Result<PageDto<AuthorDto, AuthorCursorDto>, UseCaseError> {
+        let user_id = UserId::new(user_id.to_string())?;
+        let page = page.try_into_page_request(AuthorCursor::try_from)?;
+        let authors = self
+            .author_repository
+            .find_page(&user_id, &order, &page)
+            .await?;
+        Ok(authors.into())
+    }
+
     async fn find_author_by_ids_as_hash_map(
         &self,
         user_id: &str,
@@ -143,6 +297,126 @@ where
         Ok(authors_map)
     }
 
+    async fn search(
+        &self,
+        user_id: &str,
+        query: &str,
+        limit: i32,
+    ) -> Result<SearchResultDto, UseCaseError> {
+        let user_id = UserId::new(user_id.to_string())?;
+        let query = SearchQuery::new(query)?;
+        if !(1..=MAX_SEARCH_LIMIT).contains(&limit) {
+            return Err(UseCaseError::Validation(format!(
+                "search limit must be between 1 and {MAX_SEARCH_LIMIT}, but was {limit}."
+            )));
+        }
+        let limit = limit as usize;
+        let books = self.book_repository.search(&user_id, &query, limit).await?;
+        let authors = self
+            .author_repository
+            .search(&user_id, &query, limit)
+            .await?;
+
+        Ok(SearchResultDto {
+            books: books.into_iter().map(BookDto::from).collect(),
+            authors: authors.into_iter().map(AuthorDto::from).collect(),
+        })
+    }
+
+    async fn library_stats(
+        &self,
+        user_id: &str,
+        top_authors: i32,
+    ) -> Result<LibraryStatsDto, UseCaseError> {
+        let user_id = UserId::new(user_id.to_string())?;
+        if !(0..=MAX_TOP_AUTHORS).contains(&top_authors) {
+            return Err(UseCaseError::Validation(format!(
+                "topAuthors must be between 0 and {MAX_TOP_AUTHORS}, but was {top_authors}."
+            )));
+        }
+        let stats = self
+            .book_repository
+            .library_stats(&user_id, top_authors as usize)
+            .await?;
+
+        Ok(LibraryStatsDto::from(stats))
+    }
+
+    async fn reading_activity(
+        &self,
+        user_id: &str,
+        range: ActivityRangeDto,
+    ) -> Result<Vec<ActivityPeriodDto>, UseCaseError> {
+        let user_id = UserId::new(user_id.to_string())?;
+        let range = ActivityRange::try_from(range)?;
+        let periods = self
+            .book_event_repository
+            .reading_activity(&user_id, &range)
+            .await?;
+
+        Ok(periods.into_iter().map(ActivityPeriodDto::from).collect())
+    }
+
+    async fn find_books_as_of(
+        &self,
+        user_id: &str,
+        at: PointInTimeDto,
+    ) -> Result<Vec<BookDto>, UseCaseError> {
+        let user_id = UserId::new(user_id.to_string())?;
+        let at = self.resolve_point_in_time(&user_id, at).await?;
+        let books = self
+            .book_event_repository
+            .find_books_as_of(&user_id, at)
+            .await?;
+        Ok(books.into_iter().map(BookDto::from).collect())
+    }
+
+    async fn find_authors_as_of(
+        &self,
+        user_id: &str,
+        at: PointInTimeDto,
+    ) -> Result<Vec<AuthorDto>, UseCaseError> {
+        let user_id = UserId::new(user_id.to_string())?;
+        let at = self.resolve_point_in_time(&user_id, at).await?;
+        let authors = self
+            .author_event_repository
+            .find_authors_as_of(&user_id, at)
+            .await?;
+        Ok(authors.into_iter().map(AuthorDto::from).collect())
+    }
+
+    async fn diff_library(
+        &self,
+        user_id: &str,
+        from: PointInTimeDto,
+        to: PointInTimeDto,
+    ) -> Result<LibraryDiffDto, UseCaseError> {
+        let user_id = UserId::new(user_id.to_string())?;
+        let from = self.resolve_point_in_time(&user_id, from).await?;
+        let to = self.resolve_point_in_time(&user_id, to).await?;
+        let books_before = self
+            .book_event_repository
+            .find_books_as_of(&user_id, from)
+            .await?;
+        let books_after = self
+            .book_event_repository
+            .find_books_as_of(&user_id, to)
+            .await?;
+        let authors_before = self
+            .author_event_repository
+            .find_authors_as_of(&user_id, from)
+            .await?;
+        let authors_after = self
+            .author_event_repository
+            .find_authors_as_of(&user_id, to)
+            .await?;
+
+        Ok(LibraryDiffDto {
+            books: diff_entities(books_before, books_after, |book| book.id().to_uuid()),
+            authors: diff_entities(authors_before, authors_after, |author| author.id().clone()),
+        })
+    }
+
     async fn list_book_events(
         &self,
         user_id: &str,
@@ -154,7 +428,35 @@ where
             .book_event_repository
             .find_by_book(&user_id, &book_id)
             .await?;
-        Ok(entries.into_iter().map(BookEventDto::from).collect())
+        Ok(BookEventDto::from_history(entries))
+    }
+
+    async fn find_book_events_page(
+        &self,
+        user_id: &str,
+        book_id: &str,
+        filter: HistoryFilterDto,
+        page: PageRequestDto<HistoryCursorDto>,
+    ) -> Result<PageDto<BookEventDto, HistoryCursorDto>, UseCaseError> {
+        let user_id = UserId::new(user_id.to_string())?;
+        let book_id = BookId::try_from(book_id)?;
+        let filter = filter.try_into_filter::<EventOperation>()?;
+        let page = page.try_into_page_request(EventCursor::try_from)?;
+        let events = self
+            .book_event_repository
+            .find_page_by_book(&user_id, &book_id, &filter, &page)
+            .await?;
+        // Neighbours within a filtered page are not necessarily consecutive,
+        // so each event is diffed against its actual predecessor.
+        let event_ids: Vec<i64> = events.nodes().map(|e| e.event_id).collect();
+        let previous = self
+            .book_event_repository
+            .find_previous(&user_id, &event_ids)
+            .await?;
+        Ok(PageDto::map_from(events, |e| {
+            let previous = previous.get(&e.event_id);
+            BookEventDto::new(e, previous)
+        }))
     }
 
     async fn list_author_events(
@@ -168,7 +470,70 @@ where
             .author_event_repository
             .find_by_author(&user_id, &author_id)
             .await?;
-        Ok(entries.into_iter().map(AuthorEventDto::from).collect())
+        Ok(AuthorEventDto::from_history(entries))
+    }
+
+    async fn find_author_events_page(
+        &self,
+        user_id: &str,
+        author_id: &str,
+        filter: HistoryFilterDto,
+        page: PageRequestDto<HistoryCursorDto>,
+    ) -> Result<PageDto<AuthorEventDto, HistoryCursorDto>, UseCaseError> {
+        let user_id = UserId::new(user_id.to_string())?;
+        let author_id = AuthorId::try_from(author_id)?;
+        let filter = filter.try_into_filter::<EventOperation>()?;
+        let page = page.try_into_page_request(EventCursor::try_from)?;
+        let events = self
+            .author_event_repository
+            .find_page_by_author(&user_id, &author_id, &filter, &page)
+            .await?;
+        let event_ids: Vec<i64> = events.nodes().map(|e| e.event_id).collect();
+        let previous = self
+            .author_event_repository
+            .find_previous(&user_id, &event_ids)
+            .await?;
+        Ok(PageDto::map_from(events, |e| {
+            let previous = previous.get(&e.event_id);
+            AuthorEventDto::new(e, previous)
+        }))
+    }
+
+    async fn list_deleted_books(&self, user_id: &str) -> Result<Vec<DeletedBookDto>, UseCaseError> {
+        let user_id = UserId::new(user_id.to_string())?;
+        let deleted = self.book_event_repository.find_deleted(&user_id).await?;
+        let event_ids: Vec<i64> = deleted.iter().map(|d| d.last_known.event_id).collect();
+        let previous = self
+            .book_event_repository
+            .find_previous(&user_id, &event_ids)
+            .await?;
+        Ok(deleted
+            .into_iter()
+            .map(|d| {
+                let previous = previous.get(&d.last_known.event_id);
+                DeletedBookDto::new(d, previous)
+            })
+            .collect())
+    }
+
+    async fn list_deleted_authors(
+        &self,
+        user_id: &str,
+    ) -> Result<Vec<DeletedAuthorDto>, UseCaseError> {
+        let user_id = UserId::new(user_id.to_string())?;
+        let deleted = self.author_event_repository.find_deleted(&user_id).await?;
+        let event_ids: Vec<i64> = deleted.iter().map(|d| d.last_known.event_id).collect();
+        let previous = self
+            .author_event_repository
+            .find_previous(&user_id, &event_ids)
+            .await?;
+        Ok(deleted
+            .into_iter()
+            .map(|d| {
+                let previous = previous.get(&d.last_known.event_id);
+                DeletedAuthorDto::new(d, previous)
+            })
+            .collect())
     }
 
     async fn list_event_sets(&self, user_id: &str) -> Result<Vec<EventSetDto>, UseCaseError> {
@@ -177,6 +542,22 @@ where
         Ok(sets.into_iter().map(EventSetDto::from).collect())
     }
 
+    async fn find_event_sets_page(
+        &self,
+        user_id: &str,
+        filter: HistoryFilterDto,
+        page: PageRequestDto<HistoryCursorDto>,
+    ) -> Result<PageDto<EventSetDto, HistoryCursorDto>, UseCaseError> {
+        let user_id = UserId::new(user_id.to_string())?;
+        let filter = filter.try_into_filter::<EventSetOperation>()?;
+        let page = page.try_into_page_request(EventSetCursor::try_from)?;
+        let sets = self
+            .event_set_repository
+            .find_page(&user_id, &filter, &page)
+            .await?;
+        Ok(sets.into())
+    }
+
     async fn find_event_set(
         &self,
         user_id: &str,
@@ -200,11 +581,29 @@ where
             .author_event_repository
             .find_by_event_set(&user_id, &event_set_id)
             .await?;
-        let book_events: Vec<BookEventDto> =
-            book_events.into_iter().map(BookEventDto::from).collect();
+        let event_ids: Vec<i64> = book_events.iter().map(|e| e.event_id).collect();
+        let previous_book_events = self
+            .book_event_repository
+            .find_previous(&user_id, &event_ids)
+            .await?;
+        let event_ids: Vec<i64> = author_events.iter().map(|e| e.event_id).collect();
+        let previous_author_events = self
+            .author_event_repository
+            .find_previous(&user_id, &event_ids)
+            .await?;
+        let book_events: Vec<BookEventDto> = book_events
+            .into_iter()
+            .map(|e| {
+                let previous = previous_book_events.get(&e.event_id);
+                BookEventDto::new(e, previous)
+            })
+            .collect();
         let author_events: Vec<AuthorEventDto> = author_events
             .into_iter()
-            .map(AuthorEventDto::from)
+            .map(|e| {
+                let previous = previous_author_events.get(&e.event_id);
+                AuthorEventDto::new(e, previous)
+            })
             .collect();
         Ok(Some(EventSetDetailDto::new(
             event_set,
@@ -212,6 +611,21 @@ where
             author_events,
         )))
     }
+
+    async fn find_import_job(
+        &self,
+        user_id: &str,
+        job_id: &str,
+    ) -> Result<Option<ImportJobDto>, UseCaseError> {
+        let user_id = UserId::new(user_id.to_string())?;
+        let job_id = ImportJobId::try_from(job_id)
+            .map_err(|e| UseCaseError::from(DomainError::Unexpected(e)))?;
+        let job = self
+            .import_job_repository
+            .find_by_id(&user_id, &job_id)
+            .await?;
+        Ok(job.map(ImportJobDto::from))
+    }
 }
 
 #[cfg(test)]
@@ -223,25 +637,53 @@ mod tests {
     use uuid::Uuid;
 
     use crate::{
-        common::types::{BookFormat, BookStore},
+        common::{
+            activity::ActivityGranularity,
+            sort::{
+                AuthorOrder, AuthorSortKey, AuthorSortValue, BookOrder, BookSortKey, BookSortValue,
+                SortDirection,
+            },
+            types::{BookFormat, BookStore},
+        },
         domain::{
             entity::{
                 author::{Author, AuthorId, AuthorName},
-                book::{Book, BookId, BookTitle, Isbn, OwnedFlag, Priority, ReadFlag},
+                book::{Book, BookId, BookTitle, BookUpdate, Isbn, OwnedFlag, Priority, ReadFlag},
                 event::{AuthorEvent, BookEvent, EventOperation, EventSetOperation},
                 event_set::{EventSet, EventSetId},
+                import_job::{ImportJob, ImportMode},
                 user::{User, UserId},
             },
             repository::{
+                activity::{ActivityPeriod, ActivityRange},
                 author_event_repository::MockAuthorEventRepository,
-                author_repository::MockAuthorRepository,
+                author_repository::{AuthorCursor, MockAuthorRepository},
                 book_event_repository::MockBookEventRepository,
-                book_repository::MockBookRepository, event_set_repository::MockEventSetRepository,
+                book_repository::{
+                    BookCursor, BookFilter, LibraryStats, MockBookRepository, PriorityBucket,
+                },
+                event_set_repository::MockEventSetRepository,
+                history::{DeletedEntity, EventCursor, EventSetCursor},
+                import_job_repository::MockImportJobRepository,
+                pagination::{Edge, Page, PageDirection},
+                search::SearchQuery,
                 user_repository::MockUserRepository,
             },
         },
         use_case::{
-            dto::author::AuthorDto, interactor::query::QueryInteractor, traits::query::QueryUseCase,
+            dto::{
+                activity::ActivityRangeDto,
+                author::{AuthorCursorDto, AuthorDto},
+                book::{BookCursorDto, BookDto, BookFilterDto, ImportModeDto},
+                history::{HistoryCursorDto, HistoryFilterDto, PointInTimeDto},
+                import_job::ImportJobStatusDto,
+                pagination::PageRequestDto,
+                search::MAX_SEARCH_LIMIT,
+                stats::MAX_TOP_AUTHORS,
+            },
+            error::UseCaseError,
+            interactor::query::QueryInteractor,
+            traits::query::QueryUseCase,
         },
     };
 
@@ -297,6 +739,7 @@ mod tests {
             book_event_repository: MockBookEventRepository::new(),
             author_event_repository: MockAuthorEventRepository::new(),
             event_set_repository: MockEventSetRepository::new(),
+            import_job_repository: MockImportJobRepository::new(),
         };
 
         // When
@@ -317,8 +760,10 @@ mod tests {
 
         book_repository
             .expect_find_all()
-            .withf(move |uid| uid.as_str() == expected_user_id)
-            .returning(|_| Ok(vec![]));
+            .withf(move |uid, _, order| {
+                uid.as_str() == expected_user_id && *order == BookOrder::default()
+            })
+            .returning(|_, _, _| Ok(vec![]));
 
         let query_interactor = QueryInteractor {
             user_repository,
@@ -327,135 +772,387 @@ mod tests {
             book_event_repository: MockBookEventRepository::new(),
             author_event_repository: MockAuthorEventRepository::new(),
             event_set_repository: MockEventSetRepository::new(),
+            import_job_repository: MockImportJobRepository::new(),
         };
 
         // When
-        let result = query_interactor.find_all_books("user1").await;
+        let result = query_interactor
+            .find_all_books("user1", BookFilterDto::default(), BookOrder::default())
+            .await;
 
         // Then
         assert!(result.is_ok());
     }
 
     #[tokio::test]
-    async fn find_books_by_author_ids_batches_ids_and_maps_books() {
-        let user_repository = MockUserRepository::new();
+    async fn find_books_page_converts_cursor_and_maps_page() {
+        // Given
         let mut book_repository = MockBookRepository::new();
-        let author_repository = MockAuthorRepository::new();
-        let author_id1 = "006099b4-6c42-4ec4-8645-f6bd5b63eddc";
-        let author_id2 = "93090e87-b7a1-403c-974c-d74d881e83b9";
         let book = make_book("a1b2c3d4-e5f6-4890-abcd-ef1234567890");
-        let expected_book_id = book.id().to_string();
+        let cursor_book_id = "c5a81e57-bc91-40ff-8b57-18cfa7cc7ae8";
+        let by_priority = BookOrder {
+            key: BookSortKey::Priority,
+            direction: SortDirection::Desc,
+        };
 
         book_repository
-            .expect_find_by_author_ids_as_hash_map()
-            .withf(move |user_id, author_ids| {
-                user_id.as_str() == "user1"
-                    && author_ids.len() == 2
-                    && author_ids[0].to_string() == author_id1
-                    && author_ids[1].to_string() == author_id2
+            .expect_find_page()
+            .withf(move |uid, filter, order, page| {
+                uid.as_str() == "user1"
+                    && *filter == BookFilter::default()
+                    && order.key == BookSortKey::Priority
+                    && page.limit == 2
+                    && page.direction == PageDirection::Forward
+                    && page.after.as_ref().map(|cursor| cursor.id.to_string())
+                        == Some(cursor_book_id.to_string())
+                    && page.after.as_ref().map(|cursor| &cursor.value)
+                        == Some(&BookSortValue::Priority(80))
+                    && page.before.is_none()
             })
             .times(1)
-            .returning(move |_, author_ids| {
-                Ok(HashMap::from([
-                    (author_ids[0].clone(), vec![book.clone()]),
-                    (author_ids[1].clone(), Vec::new()),
-                ]))
+            .returning(move |_, _, _, _| {
+                Ok(Page {
+                    edges: vec![Edge {
+                        cursor: BookCursor {
+                            value: BookSortValue::Priority(50),
+                            id: book.id().clone(),
+                        },
+                        node: book.clone(),
+                    }],
+                    has_previous_page: true,
+                    has_next_page: false,
+                    total_count: 2,
+                })
             });
 
         let query_interactor = QueryInteractor {
-            user_repository,
+            user_repository: MockUserRepository::new(),
             book_repository,
-            author_repository,
+            author_repository: MockAuthorRepository::new(),
             book_event_repository: MockBookEventRepository::new(),
             author_event_repository: MockAuthorEventRepository::new(),
             event_set_repository: MockEventSetRepository::new(),
+            import_job_repository: MockImportJobRepository::new(),
+        };
+        let page = PageRequestDto {
+            first: Some(2),
+            after: Some(BookCursorDto {
+                value: BookSortValue::Priority(80),
+                id: cursor_book_id.to_string(),
+            }),
+            last: None,
+            before: None,
         };
 
+        // When
         let result = query_interactor
-            .find_books_by_author_ids_as_hash_map(
-                "user1",
-                &[author_id1.to_string(), author_id2.to_string()],
-            )
+            .find_books_page("user1", BookFilterDto::default(), by_priority, page)
             .await
             .unwrap();
 
-        assert_eq!(result[author_id1].len(), 1);
-        assert_eq!(result[author_id1][0].id, expected_book_id);
-        assert!(result[author_id2].is_empty());
+        // Then
+        assert_eq!(result.edges.len(), 1);
+        assert_eq!(
+            result.edges[0].node.id,
+            "a1b2c3d4-e5f6-4890-abcd-ef1234567890"
+        );
+        assert_eq!(result.edges[0].cursor.value, BookSortValue::Priority(50));
+        assert!(result.has_previous_page);
+        assert!(!result.has_next_page);
+        assert_eq!(result.total_count, 2);
     }
 
     #[tokio::test]
-    async fn find_books_by_author_ids_rejects_invalid_id_without_calling_repository() {
-        let user_repository = MockUserRepository::new();
+    async fn find_books_page_rejects_invalid_cursor_without_calling_repository() {
         let mut book_repository = MockBookRepository::new();
-        let author_repository = MockAuthorRepository::new();
-        book_repository
-            .expect_find_by_author_ids_as_hash_map()
-            .times(0)
-            .returning(|_, _| Ok(HashMap::new()));
+        book_repository.expect_find_page().times(0);
         let query_interactor = QueryInteractor {
-            user_repository,
+            user_repository: MockUserRepository::new(),
             book_repository,
-            author_repository,
+            author_repository: MockAuthorRepository::new(),
             book_event_repository: MockBookEventRepository::new(),
             author_event_repository: MockAuthorEventRepository::new(),
             event_set_repository: MockEventSetRepository::new(),
+            import_job_repository: MockImportJobRepository::new(),
+        };
+        let page = PageRequestDto {
+            first: None,
+            after: None,
+            last: Some(10),
+            before: Some(BookCursorDto {
+                value: BookSortValue::CreatedAt(OffsetDateTime::UNIX_EPOCH),
+                id: "invalid-book-id".to_string(),
+            }),
         };
 
         let result = query_interactor
-            .find_books_by_author_ids_as_hash_map("user1", &["invalid-author-id".to_string()])
+            .find_books_page(
+                "user1",
+                BookFilterDto::default(),
+                BookOrder::default(),
+                page,
+            )
             .await;
 
-        assert!(result.is_err());
+        assert!(matches!(result, Err(UseCaseError::Validation(_))));
     }
 
     #[tokio::test]
-    async fn find_author_by_id_passes_correct_user_id_to_repository() {
+    async fn find_all_books_passes_converted_filter_to_repository() {
         // Given
-        let user_repository = MockUserRepository::new();
-        let book_repository = MockBookRepository::new();
-        let mut author_repository = MockAuthorRepository::new();
-
-        let expected_user_id = "user1";
-        let author_id_str = "006099b4-6c42-4ec4-8645-f6bd5b63eddc";
-
-        author_repository
-            .expect_find_by_id()
-            .withf(move |uid, _| uid.as_str() == expected_user_id)
-            .returning(|_, _| Ok(None));
-
+        let mut book_repository = MockBookRepository::new();
+        let author_id = "006099b4-6c42-4ec4-8645-f6bd5b63eddc";
+        book_repository
+            .expect_find_all()
+            .withf(move |_, filter, _| {
+                filter.read == Some(false)
+                    && filter.format == Some(BookFormat::EBook)
+                    && filter.priority_min == Some(50)
+                    && filter.author_ids.as_ref().map(|ids| ids[0].to_string())
+                        == Some(author_id.to_string())
+            })
+            .times(1)
+            .returning(|_, _, _| Ok(vec![]));
         let query_interactor = QueryInteractor {
-            user_repository,
+            user_repository: MockUserRepository::new(),
             book_repository,
-            author_repository,
+            author_repository: MockAuthorRepository::new(),
             book_event_repository: MockBookEventRepository::new(),
             author_event_repository: MockAuthorEventRepository::new(),
             event_set_repository: MockEventSetRepository::new(),
+            import_job_repository: MockImportJobRepository::new(),
+        };
+        let filter = BookFilterDto {
+            read: Some(false),
+            format: Some(BookFormat::EBook),
+            priority_min: Some(50),
+            author_ids: Some(vec![author_id.to_string()]),
+            ..Default::default()
         };
 
         // When
         let result = query_interactor
-            .find_author_by_id("user1", author_id_str)
+            .find_all_books("user1", filter, BookOrder::default())
             .await;
 
         // Then
         assert!(result.is_ok());
-        assert!(result.unwrap().is_none());
     }
 
     #[tokio::test]
-    async fn find_all_authors_passes_correct_user_id_to_repository() {
-        // Given
-        let user_repository = MockUserRepository::new();
-        let book_repository = MockBookRepository::new();
-        let mut author_repository = MockAuthorRepository::new();
-
-        let expected_user_id = "user1";
-
-        author_repository
-            .expect_find_all()
-            .withf(move |uid| uid.as_str() == expected_user_id)
-            .returning(|_| Ok(vec![]));
+    async fn find_books_page_rejects_invalid_filter_without_calling_repository() {
+        let mut book_repository = MockBookRepository::new();
+        book_repository.expect_find_page().times(0);
+        let query_interactor = QueryInteractor {
+            user_repository: MockUserRepository::new(),
+            book_repository,
+            author_repository: MockAuthorRepository::new(),
+            book_event_repository: MockBookEventRepository::new(),
+            author_event_repository: MockAuthorEventRepository::new(),
+            event_set_repository: MockEventSetRepository::new(),
+            import_job_repository: MockImportJobRepository::new(),
+        };
+        let filter = BookFilterDto {
+            priority_min: Some(90),
+            priority_max: Some(10),
+            ..Default::default()
+        };
+
+        let result = query_interactor
+            .find_books_page(
+                "user1",
+                filter,
+                BookOrder::default(),
+                PageRequestDto::first(10),
+            )
+            .await;
+
+        assert!(matches!(result, Err(UseCaseError::Validation(_))));
+    }
+
+    #[tokio::test]
+    async fn find_books_page_by_author_id_passes_author_scope_to_repository() {
+        let mut book_repository = MockBookRepository::new();
+        let author_id = "006099b4-6c42-4ec4-8645-f6bd5b63eddc";
+        book_repository
+            .expect_find_page_by_author_id()
+            .withf(move |uid, aid, order, page| {
+                uid.as_str() == "user1"
+                    && aid.to_string() == author_id
+                    && order.key == BookSortKey::Title
+                    && page.direction == PageDirection::Backward
+                    && page.limit == 5
+            })
+            .times(1)
+            .returning(|_, _, _, _| {
+                Ok(Page {
+                    edges: vec![],
+                    has_previous_page: false,
+                    has_next_page: false,
+                    total_count: 0,
+                })
+            });
+        let query_interactor = QueryInteractor {
+            user_repository: MockUserRepository::new(),
+            book_repository,
+            author_repository: MockAuthorRepository::new(),
+            book_event_repository: MockBookEventRepository::new(),
+            author_event_repository: MockAuthorEventRepository::new(),
+            event_set_repository: MockEventSetRepository::new(),
+            import_job_repository: MockImportJobRepository::new(),
+        };
+        let page = PageRequestDto {
+            first: None,
+            after: None,
+            last: Some(5),
+            before: None,
+        };
+
+        let result = query_interactor
+            .find_books_page_by_author_id(
+                "user1",
+                author_id,
+                BookOrder {
+                    key: BookSortKey::Title,
+                    direction: SortDirection::Asc,
+                },
+                page,
+            )
+            .await
+            .unwrap();
+
+        assert!(result.edges.is_empty());
+    }
+
+    #[tokio::test]
+    async fn find_books_by_author_ids_batches_ids_and_maps_books() {
+        let user_repository = MockUserRepository::new();
+        let mut book_repository = MockBookRepository::new();
+        let author_repository = MockAuthorRepository::new();
+        let author_id1 = "006099b4-6c42-4ec4-8645-f6bd5b63eddc";
+        let author_id2 = "93090e87-b7a1-403c-974c-d74d881e83b9";
+        let book = make_book("a1b2c3d4-e5f6-4890-abcd-ef1234567890");
+        let expected_book_id = book.id().to_string();
+
+        book_repository
+            .expect_find_by_author_ids_as_hash_map()
+            .withf(move |user_id, author_ids, order| {
+                user_id.as_str() == "user1"
+                    && author_ids.len() == 2
+                    && author_ids[0].to_string() == author_id1
+                    && author_ids[1].to_string() == author_id2
+                    && *order == BookOrder::default()
+            })
+            .times(1)
+            .returning(move |_, author_ids, _| {
+                Ok(HashMap::from([
+                    (author_ids[0].clone(), vec![book.clone()]),
+                    (author_ids[1].clone(), Vec::new()),
+                ]))
+            });
+
+        let query_interactor = QueryInteractor {
+            user_repository,
+            book_repository,
+            author_repository,
+            book_event_repository: MockBookEventRepository::new(),
+            author_event_repository: MockAuthorEventRepository::new(),
+            event_set_repository: MockEventSetRepository::new(),
+            import_job_repository: MockImportJobRepository::new(),
+        };
+
+        let result = query_interactor
+            .find_books_by_author_ids_as_hash_map(
+                "user1",
+                &[author_id1.to_string(), author_id2.to_string()],
+                BookOrder::default(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(result[author_id1].len(), 1);
+        assert_eq!(result[author_id1][0].id, expected_book_id);
+        assert!(result[author_id2].is_empty());
+    }
+
+    #[tokio::test]
+    async fn find_books_by_author_ids_rejects_invalid_id_without_calling_repository() {
+        let user_repository = MockUserRepository::new();
+        let mut book_repository = MockBookRepository::new();
+        let author_repository = MockAuthorRepository::new();
+        book_repository
+            .expect_find_by_author_ids_as_hash_map()
+            .times(0)
+            .returning(|_, _, _| Ok(HashMap::new()));
+        let query_interactor = QueryInteractor {
+            user_repository,
+            book_repository,
+            author_repository,
+            book_event_repository: MockBookEventRepository::new(),
+            author_event_repository: MockAuthorEventRepository::new(),
+            event_set_repository: MockEventSetRepository::new(),
+            import_job_repository: MockImportJobRepository::new(),
+        };
+
+        let result = query_interactor
+            .find_books_by_author_ids_as_hash_map(
+                "user1",
+                &["invalid-author-id".to_string()],
+                BookOrder::default(),
+            )
+            .await;
+
+        assert!(result.is_err());
+    }
+
+    #[tokio::test]
+    async fn find_author_by_id_passes_correct_user_id_to_repository() {
+        // Given
+        let user_repository = MockUserRepository::new();
+        let book_repository = MockBookRepository::new();
+        let mut author_repository = MockAuthorRepository::new();
+
+        let expected_user_id = "user1";
+        let author_id_str = "006099b4-6c42-4ec4-8645-f6bd5b63eddc";
+
+        author_repository
+            .expect_find_by_id()
+            .withf(move |uid, _| uid.as_str() == expected_user_id)
+            .returning(|_, _| Ok(None));
+
+        let query_interactor = QueryInteractor {
+            user_repository,
+            book_repository,
+            author_repository,
+            book_event_repository: MockBookEventRepository::new(),
+            author_event_repository: MockAuthorEventRepository::new(),
+            event_set_repository: MockEventSetRepository::new(),
+            import_job_repository: MockImportJobRepository::new(),
+        };
+
+        // When
+        let result = query_interactor
+            .find_author_by_id("user1", author_id_str)
+            .await;
+
+        // Then
+        assert!(result.is_ok());
+        assert!(result.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn find_all_authors_passes_correct_user_id_to_repository() {
+        // Given
+        let user_repository = MockUserRepository::new();
+        let book_repository = MockBookRepository::new();
+        let mut author_repository = MockAuthorRepository::new();
+
+        let expected_user_id = "user1";
+
+        author_repository
+            .expect_find_all()
+            .withf(move |uid, _| uid.as_str() == expected_user_id)
+            .returning(|_, _| Ok(vec![]));
 
         let query_interactor = QueryInteractor {
             user_repository,
@@ -464,15 +1161,86 @@ mod tests {
             book_event_repository: MockBookEventRepository::new(),
             author_event_repository: MockAuthorEventRepository::new(),
             event_set_repository: MockEventSetRepository::new(),
+            import_job_repository: MockImportJobRepository::new(),
         };
 
         // When
-        let result = query_interactor.find_all_authors("user1").await;
+        let result = query_interactor
+            .find_all_authors("user1", AuthorOrder::default())
+            .await;
 
         // Then
         assert!(result.is_ok());
     }
 
+    #[tokio::test]
+    async fn find_authors_page_converts_cursor_and_maps_page() {
+        let mut author_repository = MockAuthorRepository::new();
+        let author = make_author("006099b4-6c42-4ec4-8645-f6bd5b63eddc", "author1");
+        author_repository
+            .expect_find_page()
+            .withf(|uid, order, page| {
+                uid.as_str() == "user1"
+                    && order.key == AuthorSortKey::Yomi
+                    && page.limit == 1
+                    && page.after.as_ref().map(|cursor| &cursor.value)
+                        == Some(&AuthorSortValue::Yomi("あ".to_string()))
+            })
+            .times(1)
+            .returning(move |_, _, _| {
+                Ok(Page {
+                    edges: vec![Edge {
+                        cursor: AuthorCursor {
+                            value: AuthorSortValue::Yomi("い".to_string()),
+                            id: author.id().clone(),
+                        },
+                        node: author.clone(),
+                    }],
+                    has_previous_page: true,
+                    has_next_page: true,
+                    total_count: 3,
+                })
+            });
+        let query_interactor = QueryInteractor {
+            user_repository: MockUserRepository::new(),
+            book_repository: MockBookRepository::new(),
+            author_repository,
+            book_event_repository: MockBookEventRepository::new(),
+            author_event_repository: MockAuthorEventRepository::new(),
+            event_set_repository: MockEventSetRepository::new(),
+            import_job_repository: MockImportJobRepository::new(),
+        };
+        let page = PageRequestDto {
+            first: Some(1),
+            after: Some(AuthorCursorDto {
+                value: AuthorSortValue::Yomi("あ".to_string()),
+                id: "93090e87-b7a1-403c-974c-d74d881e83b9".to_string(),
+            }),
+            last: None,
+            before: None,
+        };
+
+        let result = query_interactor
+            .find_authors_page(
+                "user1",
+                AuthorOrder {
+                    key: AuthorSortKey::Yomi,
+                    direction: SortDirection::Asc,
+                },
+                page,
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(result.edges.len(), 1);
+        assert_eq!(result.edges[0].node.name, "author1");
+        assert_eq!(
+            result.edges[0].cursor.value,
+            AuthorSortValue::Yomi("い".to_string())
+        );
+        assert_eq!(result.total_count, 3);
+    }
+
     #[tokio::test]
     async fn find_author_by_ids_as_hash_map_passes_correct_user_id_to_repository() {
         // Given
@@ -494,6 +1262,7 @@ mod tests {
             book_event_repository: MockBookEventRepository::new(),
             author_event_repository: MockAuthorEventRepository::new(),
             event_set_repository: MockEventSetRepository::new(),
+            import_job_repository: MockImportJobRepository::new(),
         };
 
         // When
@@ -530,6 +1299,7 @@ mod tests {
             book_event_repository: MockBookEventRepository::new(),
             author_event_repository: MockAuthorEventRepository::new(),
             event_set_repository: MockEventSetRepository::new(),
+            import_job_repository: MockImportJobRepository::new(),
         };
 
         let actual = query_interactor
@@ -571,6 +1341,7 @@ mod tests {
             book_event_repository: MockBookEventRepository::new(),
             author_event_repository: MockAuthorEventRepository::new(),
             event_set_repository: MockEventSetRepository::new(),
+            import_job_repository: MockImportJobRepository::new(),
         };
 
         // When
@@ -600,6 +1371,7 @@ mod tests {
             book_event_repository: MockBookEventRepository::new(),
             author_event_repository: MockAuthorEventRepository::new(),
             event_set_repository: MockEventSetRepository::new(),
+            import_job_repository: MockImportJobRepository::new(),
         };
 
         // When
@@ -631,6 +1403,7 @@ mod tests {
             book_event_repository: MockBookEventRepository::new(),
             author_event_repository: MockAuthorEventRepository::new(),
             event_set_repository: MockEventSetRepository::new(),
+            import_job_repository: MockImportJobRepository::new(),
         };
 
         // When
@@ -663,6 +1436,7 @@ mod tests {
             book_event_repository: MockBookEventRepository::new(),
             author_event_repository: MockAuthorEventRepository::new(),
             event_set_repository: MockEventSetRepository::new(),
+            import_job_repository: MockImportJobRepository::new(),
         };
 
         // When
@@ -686,8 +1460,8 @@ mod tests {
 
         book_repository
             .expect_find_all()
-            .with(always())
-            .returning(move |_| Ok(vec![book.clone()]));
+            .with(always(), always(), always())
+            .returning(move |_, _, _| Ok(vec![book.clone()]));
 
         let query_interactor = QueryInteractor {
             user_repository,
@@ -696,10 +1470,14 @@ mod tests {
             book_event_repository: MockBookEventRepository::new(),
             author_event_repository: MockAuthorEventRepository::new(),
             event_set_repository: MockEventSetRepository::new(),
+            import_job_repository: MockImportJobRepository::new(),
         };
 
         // When
-        let actual = query_interactor.find_all_books("user1").await.unwrap();
+        let actual = query_interactor
+            .find_all_books("user1", BookFilterDto::default(), BookOrder::default())
+            .await
+            .unwrap();
 
         // Then
         assert_eq!(actual.len(), 1);
@@ -717,8 +1495,8 @@ mod tests {
 
         author_repository
             .expect_find_all()
-            .with(always())
-            .returning(move |_| Ok(vec![author.clone()]));
+            .with(always(), always())
+            .returning(move |_, _| Ok(vec![author.clone()]));
 
         let query_interactor = QueryInteractor {
             user_repository,
@@ -727,10 +1505,14 @@ mod tests {
             book_event_repository: MockBookEventRepository::new(),
             author_event_repository: MockAuthorEventRepository::new(),
             event_set_repository: MockEventSetRepository::new(),
+            import_job_repository: MockImportJobRepository::new(),
         };
 
         // When
-        let actual = query_interactor.find_all_authors("user1").await.unwrap();
+        let actual = query_interactor
+            .find_all_authors("user1", AuthorOrder::default())
+            .await
+            .unwrap();
 
         // Then
         assert_eq!(actual.len(), 1);
@@ -773,6 +1555,7 @@ mod tests {
             book_event_repository: MockBookEventRepository::new(),
             author_event_repository: MockAuthorEventRepository::new(),
             event_set_repository: MockEventSetRepository::new(),
+            import_job_repository: MockImportJobRepository::new(),
         };
 
         // When
@@ -859,6 +1642,7 @@ mod tests {
             book_event_repository,
             author_event_repository: MockAuthorEventRepository::new(),
             event_set_repository: MockEventSetRepository::new(),
+            import_job_repository: MockImportJobRepository::new(),
         };
 
         let result = query_interactor
@@ -889,6 +1673,7 @@ mod tests {
             book_event_repository,
             author_event_repository: MockAuthorEventRepository::new(),
             event_set_repository: MockEventSetRepository::new(),
+            import_job_repository: MockImportJobRepository::new(),
         };
 
         let result = query_interactor
@@ -899,6 +1684,64 @@ mod tests {
         assert!(result.unwrap().is_empty());
     }
 
+    #[tokio::test]
+    async fn list_book_events_diffs_each_event_against_the_older_one() {
+        let book_uuid = Uuid::new_v4();
+        let book_id_str = book_uuid.hyphenated().to_string();
+        let older = make_book_event(book_uuid);
+        let newer = BookEvent {
+            event_id: 3,
+            priority: Some(Priority::new(80).unwrap()),
+            read: Some(ReadFlag::new(true)),
+            ..make_book_event(book_uuid)
+        };
+
+        // Given: the repository returns the history newest first
+        let mut book_event_repository = MockBookEventRepository::new();
+        book_event_repository
+            .expect_find_by_book()
+            .with(always(), always())
+            .returning(move |_, _| Ok(vec![newer.clone(), older.clone()]));
+
+        let query_interactor = QueryInteractor {
+            user_repository: MockUserRepository::new(),
+            book_repository: MockBookRepository::new(),
+            author_repository: MockAuthorRepository::new(),
+            book_event_repository,
+            author_event_repository: MockAuthorEventRepository::new(),
+            event_set_repository: MockEventSetRepository::new(),
+            import_job_repository: MockImportJobRepository::new(),
+        };
+
+        // When
+        let list = query_interactor
+            .list_book_events("user1", &book_id_str)
+            .await
+            .unwrap();
+
+        // Then: the newer event reports only what changed since the older one
+        let changes: Vec<_> = list[0]
+            .changes
+            .iter()
+            .map(|c| {
+                (
+                    c.field.as_str(),
+                    c.old_value.as_deref(),
+                    c.new_value.as_deref(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            changes,
+            vec![
+                ("read", Some("false"), Some("true")),
+                ("priority", Some("50"), Some("80")),
+            ]
+        );
+        // and the oldest event is diffed against nothing
+        assert!(list[1].changes.iter().all(|c| c.old_value.is_none()));
+    }
+
     #[tokio::test]
     async fn list_book_events_invalid_book_id_returns_error() {
         let query_interactor = QueryInteractor {
@@ -908,6 +1751,7 @@ mod tests {
             book_event_repository: MockBookEventRepository::new(),
             author_event_repository: MockAuthorEventRepository::new(),
             event_set_repository: MockEventSetRepository::new(),
+            import_job_repository: MockImportJobRepository::new(),
         };
 
         let result = query_interactor
@@ -936,6 +1780,7 @@ mod tests {
             book_event_repository: MockBookEventRepository::new(),
             author_event_repository,
             event_set_repository: MockEventSetRepository::new(),
+            import_job_repository: MockImportJobRepository::new(),
         };
 
         let result = query_interactor
@@ -966,6 +1811,7 @@ mod tests {
             book_event_repository: MockBookEventRepository::new(),
             author_event_repository,
             event_set_repository: MockEventSetRepository::new(),
+            import_job_repository: MockImportJobRepository::new(),
         };
 
         let result = query_interactor
@@ -985,6 +1831,7 @@ mod tests {
             book_event_repository: MockBookEventRepository::new(),
             author_event_repository: MockAuthorEventRepository::new(),
             event_set_repository: MockEventSetRepository::new(),
+            import_job_repository: MockImportJobRepository::new(),
         };
 
         let result = query_interactor
@@ -1009,6 +1856,7 @@ mod tests {
             book_event_repository: MockBookEventRepository::new(),
             author_event_repository: MockAuthorEventRepository::new(),
             event_set_repository,
+            import_job_repository: MockImportJobRepository::new(),
         };
 
         let result = query_interactor.list_event_sets("user1").await;
@@ -1020,25 +1868,201 @@ mod tests {
     }
 
     #[tokio::test]
-    async fn list_event_sets_returns_empty() {
-        let mut event_set_repository = MockEventSetRepository::new();
-        event_set_repository
-            .expect_find_all()
-            .with(always())
-            .returning(|_| Ok(vec![]));
+    async fn find_book_events_page_diffs_against_the_actual_predecessor() {
+        let book_uuid = Uuid::new_v4();
+        let older = make_book_event(book_uuid);
+        let newer = BookEvent {
+            event_id: 5,
+            priority: Some(Priority::new(80).unwrap()),
+            ..make_book_event(book_uuid)
+        };
+        let changed_at = newer.changed_at;
+
+        // Given: a page holding only the newer event, filtered to updates
+        let mut book_event_repository = MockBookEventRepository::new();
+        book_event_repository
+            .expect_find_page_by_book()
+            .withf(|_, _, filter, page| {
+                filter.operations == Some(vec![EventOperation::Update])
+                    && page.limit == 1
+                    && page.after.as_ref().map(|cursor| cursor.id) == Some(9)
+            })
+            .returning(move |_, _, _, _| {
+                Ok(Page {
+                    edges: vec![Edge {
+                        cursor: EventCursor {
+                            at: changed_at,
+                            id: 5,
+                        },
+                        node: newer.clone(),
+                    }],
+                    has_previous_page: true,
+                    has_next_page: true,
+                    total_count: 4,
+                })
+            });
+        book_event_repository
+            .expect_find_previous()
+            .with(always(), eq(vec![5]))
+            .returning(move |_, _| Ok(HashMap::from([(5, older.clone())])));
 
         let query_interactor = QueryInteractor {
             user_repository: MockUserRepository::new(),
             book_repository: MockBookRepository::new(),
             author_repository: MockAuthorRepository::new(),
-            book_event_repository: MockBookEventRepository::new(),
+            book_event_repository,
             author_event_repository: MockAuthorEventRepository::new(),
-            event_set_repository,
+            event_set_repository: MockEventSetRepository::new(),
+            import_job_repository: MockImportJobRepository::new(),
+        };
+        let filter = HistoryFilterDto {
+            operations: Some(vec!["update".to_string()]),
+            ..HistoryFilterDto::default()
+        };
+        let page = PageRequestDto {
+            first: Some(1),
+            after: Some(HistoryCursorDto {
+                at: changed_at,
+                id: "9".to_string(),
+            }),
+            last: None,
+            before: None,
         };
 
-        let result = query_interactor.list_event_sets("user1").await;
-
-        assert!(result.is_ok());
+        // When
+        let result = query_interactor
+            .find_book_events_page("user1", &book_uuid.to_string(), filter, page)
+            .await
+            .unwrap();
+
+        // Then
+        assert_eq!(result.total_count, 4);
+        assert_eq!(result.edges[0].cursor.id, "5");
+        let changes = &result.edges[0].node.changes;
+        assert_eq!(changes.len(), 1);
+        assert_eq!(changes[0].field, "priority");
+        assert_eq!(changes[0].old_value.as_deref(), Some("50"));
+    }
+
+    #[tokio::test]
+    async fn find_book_events_page_rejects_unknown_operations_and_cursors() {
+        let query_interactor = QueryInteractor {
+            user_repository: MockUserRepository::new(),
+            book_repository: MockBookRepository::new(),
+            author_repository: MockAuthorRepository::new(),
+            book_event_repository: MockBookEventRepository::new(),
+            author_event_repository: MockAuthorEventRepository::new(),
+            event_set_repository: MockEventSetRepository::new(),
+            import_job_repository: MockImportJobRepository::new(),
+        };
+        let book_id = Uuid::new_v4().to_string();
+        let unknown_operation = HistoryFilterDto {
+            operations: Some(vec!["rename".to_string()]),
+            ..HistoryFilterDto::default()
+        };
+        let bad_cursor = PageRequestDto {
+            first: None,
+            after: Some(HistoryCursorDto {
+                at: OffsetDateTime::now_utc(),
+                id: "not-an-event-id".to_string(),
+            }),
+            last: None,
+            before: None,
+        };
+
+        for (filter, page) in [
+            (unknown_operation, PageRequestDto::first(10)),
+            (HistoryFilterDto::default(), bad_cursor),
+        ] {
+            let result = query_interactor
+                .find_book_events_page("user1", &book_id, filter, page)
+                .await;
+
+            assert!(matches!(result, Err(UseCaseError::Validation(_))));
+        }
+    }
+
+    #[tokio::test]
+    async fn find_event_sets_page_parses_filter_and_maps_cursors() {
+        let event_set = make_event_set();
+        let cursor = EventSetCursor {
+            at: event_set.created_at,
+            id: event_set.id.clone(),
+        };
+        let expected_cursor = HistoryCursorDto::from(cursor.clone());
+
+        let mut event_set_repository = MockEventSetRepository::new();
+        event_set_repository
+            .expect_find_page()
+            .withf(|_, filter, page| {
+                filter.operations == Some(vec![EventSetOperation::ImportBooks])
+                    && filter.from.is_some()
+                    && page.direction == PageDirection::Backward
+            })
+            .returning(move |_, _, _| {
+                Ok(Page {
+                    edges: vec![Edge {
+                        cursor: cursor.clone(),
+                        node: make_event_set(),
+                    }],
+                    has_previous_page: false,
+                    has_next_page: false,
+                    total_count: 1,
+                })
+            });
+
+        let query_interactor = QueryInteractor {
+            user_repository: MockUserRepository::new(),
+            book_repository: MockBookRepository::new(),
+            author_repository: MockAuthorRepository::new(),
+            book_event_repository: MockBookEventRepository::new(),
+            author_event_repository: MockAuthorEventRepository::new(),
+            event_set_repository,
+            import_job_repository: MockImportJobRepository::new(),
+        };
+        let filter = HistoryFilterDto {
+            operations: Some(vec!["import_books".to_string()]),
+            from: Some(OffsetDateTime::UNIX_EPOCH),
+            to: None,
+        };
+        let page = PageRequestDto {
+            first: None,
+            after: None,
+            last: Some(10),
+            before: None,
+        };
+
+        let result = query_interactor
+            .find_event_sets_page("user1", filter, page)
+            .await
+            .unwrap();
+
+        assert_eq!(result.edges.len(), 1);
+        assert_eq!(result.edges[0].cursor, expected_cursor);
+        assert_eq!(result.edges[0].node.operation, "create_book");
+    }
+
+    #[tokio::test]
+    async fn list_event_sets_returns_empty() {
+        let mut event_set_repository = MockEventSetRepository::new();
+        event_set_repository
+            .expect_find_all()
+            .with(always())
+            .returning(|_| Ok(vec![]));
+
+        let query_interactor = QueryInteractor {
+            user_repository: MockUserRepository::new(),
+            book_repository: MockBookRepository::new(),
+            author_repository: MockAuthorRepository::new(),
+            book_event_repository: MockBookEventRepository::new(),
+            author_event_repository: MockAuthorEventRepository::new(),
+            event_set_repository,
+            import_job_repository: MockImportJobRepository::new(),
+        };
+
+        let result = query_interactor.list_event_sets("user1").await;
+
+        assert!(result.is_ok());
         assert!(result.unwrap().is_empty());
     }
 
@@ -1063,12 +2087,18 @@ mod tests {
             .expect_find_by_event_set()
             .with(always(), eq(event_set_id.clone()))
             .returning(move |_, _| Ok(vec![book_event.clone()]));
+        book_event_repository
+            .expect_find_previous()
+            .returning(|_, _| Ok(HashMap::new()));
 
         let mut author_event_repository = MockAuthorEventRepository::new();
         author_event_repository
             .expect_find_by_event_set()
             .with(always(), eq(event_set_id.clone()))
             .returning(move |_, _| Ok(vec![author_event.clone()]));
+        author_event_repository
+            .expect_find_previous()
+            .returning(|_, _| Ok(HashMap::new()));
 
         let query_interactor = QueryInteractor {
             user_repository: MockUserRepository::new(),
@@ -1077,6 +2107,7 @@ mod tests {
             book_event_repository,
             author_event_repository,
             event_set_repository,
+            import_job_repository: MockImportJobRepository::new(),
         };
 
         let result = query_interactor
@@ -1092,6 +2123,46 @@ mod tests {
         assert_eq!(detail.author_events.len(), 1);
     }
 
+    #[tokio::test]
+    async fn find_import_job_looks_the_job_up_for_the_user() {
+        let job = ImportJob::new(
+            UserId::new("user1".to_string()).unwrap(),
+            ImportMode::UpsertByIsbn,
+            false,
+            3,
+            OffsetDateTime::now_utc(),
+        );
+        let job_id = job.id.clone();
+        let mut import_job_repository = MockImportJobRepository::new();
+        import_job_repository
+            .expect_find_by_id()
+            .with(
+                eq(UserId::new("user1".to_string()).unwrap()),
+                eq(job_id.clone()),
+            )
+            .returning(move |_, _| Ok(Some(job.clone())));
+
+        let query_interactor = QueryInteractor {
+            user_repository: MockUserRepository::new(),
+            book_repository: MockBookRepository::new(),
+            author_repository: MockAuthorRepository::new(),
+            book_event_repository: MockBookEventRepository::new(),
+            author_event_repository: MockAuthorEventRepository::new(),
+            event_set_repository: MockEventSetRepository::new(),
+            import_job_repository,
+        };
+
+        let job = query_interactor
+            .find_import_job("user1", &job_id.to_string())
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(job.id, job_id.to_string());
+        assert_eq!(job.status, ImportJobStatusDto::Pending);
+        assert_eq!(job.mode, ImportModeDto::UpsertByIsbn);
+        assert_eq!(job.total_count, 3);
+    }
+
     #[tokio::test]
     async fn find_event_set_returns_none_when_not_found() {
         let mut event_set_repository = MockEventSetRepository::new();
@@ -1107,6 +2178,7 @@ mod tests {
             book_event_repository: MockBookEventRepository::new(),
             author_event_repository: MockAuthorEventRepository::new(),
             event_set_repository,
+            import_job_repository: MockImportJobRepository::new(),
         };
 
         let event_set_id = Uuid::new_v4().hyphenated().to_string();
@@ -1127,10 +2199,451 @@ mod tests {
             book_event_repository: MockBookEventRepository::new(),
             author_event_repository: MockAuthorEventRepository::new(),
             event_set_repository: MockEventSetRepository::new(),
+            import_job_repository: MockImportJobRepository::new(),
         };
 
         let result = query_interactor.find_event_set("user1", "not-a-uuid").await;
 
         assert!(result.is_err());
     }
+
+    #[tokio::test]
+    async fn search_passes_normalized_query_to_both_repositories() {
+        // Given
+        let mut book_repository = MockBookRepository::new();
+        let mut author_repository = MockAuthorRepository::new();
+        let book_id = "675bc8d9-3155-42fb-87b0-0a82cb162848";
+        let author_id = "006099b4-6c42-4ec4-8645-f6bd5b63eddc";
+        let expected_query = SearchQuery::new("なつめ").unwrap();
+
+        book_repository
+            .expect_search()
+            .with(
+                eq(UserId::new("user1".to_string()).unwrap()),
+                eq(expected_query.clone()),
+                eq(5),
+            )
+            .times(1)
+            .returning(move |_, _, _| Ok(vec![make_book(book_id)]));
+        author_repository
+            .expect_search()
+            .with(always(), eq(expected_query), eq(5))
+            .times(1)
+            .returning(move |_, _, _| Ok(vec![make_author(author_id, "夏目漱石")]));
+
+        let query_interactor = QueryInteractor {
+            user_repository: MockUserRepository::new(),
+            book_repository,
+            author_repository,
+            book_event_repository: MockBookEventRepository::new(),
+            author_event_repository: MockAuthorEventRepository::new(),
+            event_set_repository: MockEventSetRepository::new(),
+            import_job_repository: MockImportJobRepository::new(),
+        };
+
+        // When
+        let result = query_interactor.search("user1", " ﾅﾂﾒ ", 5).await.unwrap();
+
+        // Then
+        assert_eq!(result.books.len(), 1);
+        assert_eq!(result.books[0].id, book_id);
+        assert_eq!(result.authors.len(), 1);
+        assert_eq!(result.authors[0].id, author_id);
+    }
+
+    #[tokio::test]
+    async fn search_rejects_invalid_input_without_calling_repositories() {
+        // Given
+        let query_interactor = QueryInteractor {
+            user_repository: MockUserRepository::new(),
+            book_repository: MockBookRepository::new(),
+            author_repository: MockAuthorRepository::new(),
+            book_event_repository: MockBookEventRepository::new(),
+            author_event_repository: MockAuthorEventRepository::new(),
+            event_set_repository: MockEventSetRepository::new(),
+            import_job_repository: MockImportJobRepository::new(),
+        };
+
+        for (query, limit) in [
+            ("なつめ", 0),
+            ("なつめ", MAX_SEARCH_LIMIT + 1),
+            ("\u{3000}", 10),
+        ] {
+            // When
+            let result = query_interactor.search("user1", query, limit).await;
+
+            // Then
+            assert!(
+                matches!(result, Err(UseCaseError::Validation(_))),
+                "{query:?} with limit {limit}"
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn library_stats_converts_repository_aggregates() {
+        // Given
+        let mut book_repository = MockBookRepository::new();
+        let author_id = "006099b4-6c42-4ec4-8645-f6bd5b63eddc";
+
+        book_repository
+            .expect_library_stats()
+            .with(eq(UserId::new("user1".to_string()).unwrap()), eq(3))
+            .times(1)
+            .returning(move |_, _| {
+                Ok(LibraryStats {
+                    total_count: 4,
+                    read_count: 1,
+                    owned_count: 3,
+                    unread_owned_count: 2,
+                    format_counts: vec![(BookFormat::EBook, 3), (BookFormat::Printed, 1)],
+                    store_counts: vec![(BookStore::Kindle, 3)],
+                    priority_histogram: vec![PriorityBucket {
+                        min: 0,
+                        max: 9,
+                        count: 4,
+                    }],
+                    top_authors: vec![(make_author(author_id, "夏目漱石"), 4)],
+                })
+            });
+
+        let query_interactor = QueryInteractor {
+            user_repository: MockUserRepository::new(),
+            book_repository,
+            author_repository: MockAuthorRepository::new(),
+            book_event_repository: MockBookEventRepository::new(),
+            author_event_repository: MockAuthorEventRepository::new(),
+            event_set_repository: MockEventSetRepository::new(),
+            import_job_repository: MockImportJobRepository::new(),
+        };
+
+        // When
+        let result = query_interactor.library_stats("user1", 3).await.unwrap();
+
+        // Then
+        assert_eq!(result.total_count, 4);
+        assert_eq!(result.unread_owned_count, 2);
+        assert_eq!(result.format_counts.len(), 2);
+        assert_eq!(result.priority_histogram[0].count, 4);
+        assert_eq!(result.top_authors[0].0.id, author_id);
+        assert_eq!(result.top_authors[0].1, 4);
+    }
+
+    #[tokio::test]
+    async fn library_stats_rejects_out_of_range_top_authors() {
+        // Given
+        let query_interactor = QueryInteractor {
+            user_repository: MockUserRepository::new(),
+            book_repository: MockBookRepository::new(),
+            author_repository: MockAuthorRepository::new(),
+            book_event_repository: MockBookEventRepository::new(),
+            author_event_repository: MockAuthorEventRepository::new(),
+            event_set_repository: MockEventSetRepository::new(),
+            import_job_repository: MockImportJobRepository::new(),
+        };
+
+        for top_authors in [-1, MAX_TOP_AUTHORS + 1] {
+            // When
+            let result = query_interactor.library_stats("user1", top_authors).await;
+
+            // Then
+            assert!(
+                matches!(result, Err(UseCaseError::Validation(_))),
+                "topAuthors {top_authors}"
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn reading_activity_passes_validated_range_to_event_repository() {
+        // Given
+        let mut book_event_repository = MockBookEventRepository::new();
+        let from = OffsetDateTime::from_unix_timestamp(1772323200).unwrap();
+        let to = OffsetDateTime::from_unix_timestamp(1772409600).unwrap();
+        let expected_range = ActivityRange::new(from, to, ActivityGranularity::Week, 540).unwrap();
+
+        book_event_repository
+            .expect_reading_activity()
+            .with(
+                eq(UserId::new("user1".to_string()).unwrap()),
+                eq(expected_range),
+            )
+            .times(1)
+            .returning(move |_, _| {
+                Ok(vec![ActivityPeriod {
+                    start: from,
+                    finished_count: 1,
+                    added_count: 2,
+                    deleted_count: 0,
+                }])
+            });
+
+        let query_interactor = QueryInteractor {
+            user_repository: MockUserRepository::new(),
+            book_repository: MockBookRepository::new(),
+            author_repository: MockAuthorRepository::new(),
+            book_event_repository,
+            author_event_repository: MockAuthorEventRepository::new(),
+            event_set_repository: MockEventSetRepository::new(),
+            import_job_repository: MockImportJobRepository::new(),
+        };
+
+        // When
+        let result = query_interactor
+            .reading_activity(
+                "user1",
+                ActivityRangeDto {
+                    from,
+                    to,
+                    granularity: ActivityGranularity::Week,
+                    utc_offset_minutes: 540,
+                },
+            )
+            .await
+            .unwrap();
+
+        // Then
+        assert_eq!(result.len(), 1);
+        assert_eq!(result[0].start, from);
+        assert_eq!(result[0].finished_count, 1);
+        assert_eq!(result[0].added_count, 2);
+    }
+
+    #[tokio::test]
+    async fn reading_activity_rejects_reversed_range() {
+        // Given
+        let query_interactor = QueryInteractor {
+            user_repository: MockUserRepository::new(),
+            book_repository: MockBookRepository::new(),
+            author_repository: MockAuthorRepository::new(),
+            book_event_repository: MockBookEventRepository::new(),
+            author_event_repository: MockAuthorEventRepository::new(),
+            event_set_repository: MockEventSetRepository::new(),
+            import_job_repository: MockImportJobRepository::new(),
+        };
+        let from = OffsetDateTime::from_unix_timestamp(1772409600).unwrap();
+
+        // When
+        let result = query_interactor
+            .reading_activity(
+                "user1",
+                ActivityRangeDto {
+                    from,
+                    to: OffsetDateTime::UNIX_EPOCH,
+                    granularity: ActivityGranularity::Day,
+                    utc_offset_minutes: 0,
+                },
+            )
+            .await;
+
+        // Then
+        assert!(matches!(result, Err(UseCaseError::Validation(_))));
+    }
+
+    #[tokio::test]
+    async fn diff_library_compares_states_at_both_points() {
+        // Given
+        let from = OffsetDateTime::from_unix_timestamp(1772323200).unwrap();
+        let event_set_id = make_event_set().id;
+        let to = OffsetDateTime::from_unix_timestamp(1772409600).unwrap();
+        let unchanged = make_book("675bc8d9-3155-42fb-87b0-0a82cb162848");
+        let removed = make_book("c5a81e57-bc91-40ff-8b57-18cfa7cc7ae8");
+        let changed_before = make_book("99a1b2c3-d4e5-4f60-8a7b-1c2d3e4f5a6b");
+        let mut changed_after = changed_before.clone();
+        changed_after.update(
+            BookUpdate {
+                title: BookTitle::new("Renamed".to_string()).unwrap(),
+                author_ids: vec![],
+                isbn: changed_before.isbn().clone(),
+                read: ReadFlag::new(true),
+                owned: changed_before.owned().clone(),
+                priority: changed_before.priority().clone(),
+                format: changed_before.format().clone(),
+                store: changed_before.store().clone(),
+            },
+            to,
+        );
+        let added = make_book("d065a358-4fa7-4236-ae19-f6f2f9467c35");
+        let author = make_author("006099b4-6c42-4ec4-8645-f6bd5b63eddc", "author1");
+
+        let mut event_set_repository = MockEventSetRepository::new();
+        event_set_repository
+            .expect_find_by_id()
+            .with(always(), eq(event_set_id.clone()))
+            .times(1)
+            .returning(move |_, event_set_id| {
+                Ok(Some(EventSet {
+                    id: event_set_id.clone(),
+                    user_id: UserId::new("user1".to_string()).unwrap(),
+                    operation: EventSetOperation::UpdateBook,
+                    created_at: to,
+                }))
+            });
+        let mut book_event_repository = MockBookEventRepository::new();
+        let books_before = vec![unchanged.clone(), removed, changed_before];
+        book_event_repository
+            .expect_find_books_as_of()
+            .with(always(), eq(from))
+            .times(1)
+            .returning(move |_, _| Ok(books_before.clone()));
+        let books_after = vec![added, changed_after, unchanged];
+        book_event_repository
+            .expect_find_books_as_of()
+            .with(always(), eq(to))
+            .times(1)
+            .returning(move |_, _| Ok(books_after.clone()));
+        let mut author_event_repository = MockAuthorEventRepository::new();
+        author_event_repository
+            .expect_find_authors_as_of()
+            .with(always(), eq(from))
+            .times(1)
+            .returning(|_, _| Ok(vec![]));
+        author_event_repository
+            .expect_find_authors_as_of()
+            .with(always(), eq(to))
+            .times(1)
+            .returning(move |_, _| Ok(vec![author.clone()]));
+
+        let query_interactor = QueryInteractor {
+            user_repository: MockUserRepository::new(),
+            book_repository: MockBookRepository::new(),
+            author_repository: MockAuthorRepository::new(),
+            book_event_repository,
+            author_event_repository,
+            event_set_repository,
+            import_job_repository: MockImportJobRepository::new(),
+        };
+
+        // When
+        let diff = query_interactor
+            .diff_library(
+                "user1",
+                PointInTimeDto::Timestamp(from),
+                PointInTimeDto::EventSet(event_set_id.to_string()),
+            )
+            .await
+            .unwrap();
+
+        // Then
+        let ids = |books: &[BookDto]| books.iter().map(|b| b.id.clone()).collect::<Vec<_>>();
+        assert_eq!(
+            ids(&diff.books.added),
+            ["d065a358-4fa7-4236-ae19-f6f2f9467c35"]
+        );
+        assert_eq!(
+            ids(&diff.books.removed),
+            ["c5a81e57-bc91-40ff-8b57-18cfa7cc7ae8"]
+        );
+        assert_eq!(diff.books.changed.len(), 1);
+        assert_eq!(diff.books.changed[0].0.title, "Test Book");
+        assert_eq!(diff.books.changed[0].1.title, "Renamed");
+        assert_eq!(diff.authors.added.len(), 1);
+        assert!(diff.authors.removed.is_empty());
+        assert!(diff.authors.changed.is_empty());
+    }
+
+    #[tokio::test]
+    async fn find_books_as_of_unknown_event_set_returns_not_found() {
+        // Given
+        let mut event_set_repository = MockEventSetRepository::new();
+        event_set_repository
+            .expect_find_by_id()
+            .returning(|_, _| Ok(None));
+        let query_interactor = QueryInteractor {
+            user_repository: MockUserRepository::new(),
+            book_repository: MockBookRepository::new(),
+            author_repository: MockAuthorRepository::new(),
+            book_event_repository: MockBookEventRepository::new(),
+            author_event_repository: MockAuthorEventRepository::new(),
+            event_set_repository,
+            import_job_repository: MockImportJobRepository::new(),
+        };
+
+        // When
+        let result = query_interactor
+            .find_books_as_of(
+                "user1",
+                PointInTimeDto::EventSet(Uuid::new_v4().hyphenated().to_string()),
+            )
+            .await;
+
+        // Then
+        assert!(matches!(result, Err(UseCaseError::NotFound { .. })));
+    }
+
+    #[tokio::test]
+    async fn list_deleted_books_diffs_the_last_known_state() {
+        let book_uuid = Uuid::new_v4();
+        let created = BookEvent {
+            event_id: 1,
+            operation: EventOperation::Create,
+            ..make_book_event(book_uuid)
+        };
+        let updated = BookEvent {
+            event_id: 2,
+            title: Some(BookTitle::new("New Title".to_string()).unwrap()),
+            ..make_book_event(book_uuid)
+        };
+        let deletion = BookEvent {
+            event_id: 3,
+            operation: EventOperation::Delete,
+            title: None,
+            isbn: None,
+            read: None,
+            owned: None,
+            priority: None,
+            format: None,
+            store: None,
+            book_created_at: None,
+            book_updated_at: None,
+            ..make_book_event(book_uuid)
+        };
+
+        // Given: a deleted book whose last known event was an update
+        let mut book_event_repository = MockBookEventRepository::new();
+        book_event_repository
+            .expect_find_deleted()
+            .returning(move |_| {
+                Ok(vec![DeletedEntity {
+                    deletion: deletion.clone(),
+                    last_known: updated.clone(),
+                }])
+            });
+        book_event_repository
+            .expect_find_previous()
+            .with(always(), eq(vec![2]))
+            .returning(move |_, _| Ok(HashMap::from([(2, created.clone())])));
+
+        let query_interactor = QueryInteractor {
+            user_repository: MockUserRepository::new(),
+            book_repository: MockBookRepository::new(),
+            author_repository: MockAuthorRepository::new(),
+            book_event_repository,
+            author_event_repository: MockAuthorEventRepository::new(),
+            event_set_repository: MockEventSetRepository::new(),
+            import_job_repository: MockImportJobRepository::new(),
+        };
+
+        // When
+        let deleted = query_interactor.list_deleted_books("user1").await.unwrap();
+
+        // Then: the snapshot shows its own update and the deletion clears it
+        assert_eq!(deleted.len(), 1);
+        assert_eq!(deleted[0].last_known.event_id, 2);
+        let fields: Vec<_> = deleted[0]
+            .last_known
+            .changes
+            .iter()
+            .map(|c| c.field.as_str())
+            .collect();
+        assert_eq!(fields, vec!["title"]);
+        assert_eq!(deleted[0].deletion.event_id, 3);
+        assert!(
+            deleted[0]
+                .deletion
+                .changes
+                .iter()
+                .all(|c| c.new_value.is_none())
+        );
+    }
 }
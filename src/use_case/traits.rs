@@ -1,6 +1,8 @@
 pub mod author;
 pub mod book;
+pub mod change;
 pub mod event;
+pub mod import_job;
 pub mod mutation;
 pub mod query;
 pub mod user;
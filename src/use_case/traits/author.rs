@@ -3,8 +3,13 @@ use mockall::automock;
 
 use crate::use_case::{
     dto::{
-        author::{AuthorDto, CreateAuthorDto, MergeAuthorInputDto, UpdateAuthorDto},
-        mutation::{AuthorMutationResultDto, DeleteAuthorResultDto, MutationResultDto},
+        author::{
+            AuthorDto, CreateAuthorDto, MergeAuthorInputDto, PatchAuthorDto, UpdateAuthorDto,
+        },
+        mutation::{
+            AuthorMutationResultDto, DeleteAuthorResultDto, DeleteAuthorsResultDto,
+            MutationResultDto,
+        },
     },
     error::UseCaseError,
 };
@@ -37,6 +42,12 @@ pub trait UpdateAuthorUseCase: Send + Sync + 'static {
         user_id: &str,
         author_data: UpdateAuthorDto,
     ) -> Result<AuthorMutationResultDto, UseCaseError>;
+
+    async fn patch(
+        &self,
+        user_id: &str,
+        author_data: PatchAuthorDto,
+    ) -> Result<AuthorMutationResultDto, UseCaseError>;
 }
 
 #[automock]
@@ -47,4 +58,10 @@ pub trait DeleteAuthorUseCase: Send + Sync + 'static {
         user_id: &str,
         author_id: &str,
     ) -> Result<DeleteAuthorResultDto, UseCaseError>;
+
+    async fn delete_many(
+        &self,
+        user_id: &str,
+        author_ids: Vec<String>,
+    ) -> Result<DeleteAuthorsResultDto, UseCaseError>;
 }
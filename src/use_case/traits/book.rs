@@ -1,10 +1,18 @@
+use std::collections::HashMap;
+
 use async_trait::async_trait;
 use mockall::automock;
 
 use crate::use_case::{
     dto::{
-        book::{CreateBookDto, ImportBookEntryDto, UpdateBookDto},
-        mutation::{BookMutationResultDto, DeleteBookResultDto, ImportBooksResultDto},
+        book::{
+            BookPatchDto, BookSelectionDto, CreateBookDto, ImportBookEntryDto, ImportOptionsDto,
+            PatchBookDto, UpdateBookDto,
+        },
+        mutation::{
+            BookMutationResultDto, BulkUpdateBooksResultDto, DeleteBookResultDto,
+            DeleteBooksResultDto, ImportBooksResultDto,
+        },
     },
     error::UseCaseError,
 };
@@ -27,6 +35,23 @@ pub trait UpdateBookUseCase: Send + Sync + 'static {
         user_id: &str,
         book_data: UpdateBookDto,
     ) -> Result<BookMutationResultDto, UseCaseError>;
+
+    async fn patch(
+        &self,
+        user_id: &str,
+        book_data: PatchBookDto,
+    ) -> Result<BookMutationResultDto, UseCaseError>;
+}
+
+#[automock]
+#[async_trait]
+pub trait BulkUpdateBooksUseCase: Send + Sync + 'static {
+    async fn bulk_update(
+        &self,
+        user_id: &str,
+        selection: BookSelectionDto,
+        patch: BookPatchDto,
+    ) -> Result<BulkUpdateBooksResultDto, UseCaseError>;
 }
 
 #[automock]
@@ -37,14 +62,33 @@ pub trait DeleteBookUseCase: Send + Sync + 'static {
         user_id: &str,
         book_id: &str,
     ) -> Result<DeleteBookResultDto, UseCaseError>;
+
+    async fn delete_many(
+        &self,
+        user_id: &str,
+        book_ids: Vec<String>,
+    ) -> Result<DeleteBooksResultDto, UseCaseError>;
 }
 
 #[automock]
 #[async_trait]
 pub trait ImportBooksUseCase: Send + Sync + 'static {
+    // Imports the books in one event set. A dry run rolls back everything it
+    // wrote; it and a partial import report invalid entries instead of
+    // failing on the first one.
     async fn import(
         &self,
         user_id: &str,
         books: Vec<ImportBookEntryDto>,
+        options: ImportOptionsDto,
+    ) -> Result<ImportBooksResultDto, UseCaseError>;
+    // Like `import`, creating missing authors with the yomi `author_yomi`
+    // has for their name. Authors that exist already keep their own.
+    async fn import_with_author_yomi(
+        &self,
+        user_id: &str,
+        books: Vec<ImportBookEntryDto>,
+        author_yomi: HashMap<String, String>,
+        options: ImportOptionsDto,
     ) -> Result<ImportBooksResultDto, UseCaseError>;
 }
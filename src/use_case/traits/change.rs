@@ -0,0 +1,19 @@
+use async_trait::async_trait;
+use mockall::automock;
+
+use crate::use_case::{
+    dto::change::{ApplyChangesResultDto, ChangeOperationDto},
+    error::UseCaseError,
+};
+
+#[automock]
+#[async_trait]
+pub trait ApplyChangesUseCase: Send + Sync + 'static {
+    // Runs every operation in one transaction and event set, in order. If
+    // any of them fails, none is applied.
+    async fn apply(
+        &self,
+        user_id: &str,
+        operations: Vec<ChangeOperationDto>,
+    ) -> Result<ApplyChangesResultDto, UseCaseError>;
+}
@@ -3,7 +3,8 @@ use mockall::automock;
 
 use crate::use_case::{
     dto::{
-        event::{AuthorEventDto, BookEventDto},
+        event::{AuthorEventDto, BookEventDto, PurgedTrashDto},
+        event_set::RevertEventSetResultDto,
         mutation::{RestoreAuthorResultDto, RestoreBookResultDto},
     },
     error::UseCaseError,
@@ -28,10 +29,13 @@ pub trait ListAuthorEventsUseCase: Send + Sync + 'static {
 #[automock]
 #[async_trait]
 pub trait RestoreBookUseCase: Send + Sync + 'static {
+    // With `cascade`, authors the book lists that were deleted or merged away
+    // since the event are restored or remapped in the same event set.
     async fn restore(
         &self,
         user_id: &str,
         event_id: i64,
+        cascade: bool,
     ) -> Result<RestoreBookResultDto, UseCaseError>;
 }
 
@@ -44,3 +48,29 @@ pub trait RestoreAuthorUseCase: Send + Sync + 'static {
         event_id: i64,
     ) -> Result<RestoreAuthorResultDto, UseCaseError>;
 }
+
+#[automock]
+#[async_trait]
+pub trait RevertEventSetUseCase: Send + Sync + 'static {
+    // Plans the inverse of every book and author event in the set and applies
+    // it in a new event set, unless `dry_run` is set or the plan conflicts
+    // with later changes.
+    async fn revert(
+        &self,
+        user_id: &str,
+        event_set_id: &str,
+        dry_run: bool,
+    ) -> Result<RevertEventSetResultDto, UseCaseError>;
+}
+
+#[automock]
+#[async_trait]
+pub trait PurgeTrashUseCase: Send + Sync + 'static {
+    // Permanently drops books and authors deleted more than `retention_days`
+    // days ago, so that they no longer show up as deleted or can be restored.
+    async fn purge(
+        &self,
+        user_id: &str,
+        retention_days: i32,
+    ) -> Result<PurgedTrashDto, UseCaseError>;
+}
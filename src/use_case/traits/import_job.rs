@@ -0,0 +1,24 @@
+use async_trait::async_trait;
+use mockall::automock;
+
+use crate::use_case::{
+    dto::{
+        book::ImportBookEntryDto,
+        import_job::{ImportJobDto, ImportJobOptionsDto},
+    },
+    error::UseCaseError,
+};
+
+#[automock]
+#[async_trait]
+pub trait ImportJobUseCase: Send + Sync + 'static {
+    // Stores the entries as a pending job and queues it for the worker.
+    async fn start(
+        &self,
+        user_id: &str,
+        books: Vec<ImportBookEntryDto>,
+        options: ImportJobOptionsDto,
+    ) -> Result<ImportJobDto, UseCaseError>;
+    // Stops a job before its next chunk. The chunks it finished are kept.
+    async fn cancel(&self, user_id: &str, job_id: &str) -> Result<ImportJobDto, UseCaseError>;
+}
@@ -3,11 +3,21 @@ use mockall::automock;
 
 use crate::use_case::{
     dto::{
-        author::{AuthorDto, CreateAuthorDto, MergeAuthorInputDto, UpdateAuthorDto},
-        book::{CreateBookDto, ImportBookEntryDto, UpdateBookDto},
+        author::{
+            AuthorDto, CreateAuthorDto, MergeAuthorInputDto, PatchAuthorDto, UpdateAuthorDto,
+        },
+        book::{
+            BookPatchDto, BookSelectionDto, CreateBookDto, ImportBookEntryDto, ImportOptionsDto,
+            PatchBookDto, UpdateBookDto,
+        },
+        change::{ApplyChangesResultDto, ChangeOperationDto},
+        event::PurgedTrashDto,
+        event_set::RevertEventSetResultDto,
+        import_job::{ImportJobDto, ImportJobOptionsDto},
         mutation::{
-            AuthorMutationResultDto, BookMutationResultDto, DeleteAuthorResultDto,
-            DeleteBookResultDto, ImportBooksResultDto, MutationResultDto, RestoreAuthorResultDto,
+            AuthorMutationResultDto, BookMutationResultDto, BulkUpdateBooksResultDto,
+            DeleteAuthorResultDto, DeleteAuthorsResultDto, DeleteBookResultDto,
+            DeleteBooksResultDto, ImportBooksResultDto, MutationResultDto, RestoreAuthorResultDto,
             RestoreBookResultDto,
         },
         user::UserDto,
@@ -29,11 +39,27 @@ pub trait MutationUseCase: Send + Sync + 'static {
         user_id: &str,
         book_data: UpdateBookDto,
     ) -> Result<BookMutationResultDto, UseCaseError>;
+    async fn patch_book(
+        &self,
+        user_id: &str,
+        book_data: PatchBookDto,
+    ) -> Result<BookMutationResultDto, UseCaseError>;
+    async fn bulk_update_books(
+        &self,
+        user_id: &str,
+        selection: BookSelectionDto,
+        patch: BookPatchDto,
+    ) -> Result<BulkUpdateBooksResultDto, UseCaseError>;
     async fn delete_book(
         &self,
         user_id: &str,
         book_id: &str,
     ) -> Result<DeleteBookResultDto, UseCaseError>;
+    async fn delete_books(
+        &self,
+        user_id: &str,
+        book_ids: Vec<String>,
+    ) -> Result<DeleteBooksResultDto, UseCaseError>;
     async fn create_author(
         &self,
         user_id: &str,
@@ -44,11 +70,21 @@ pub trait MutationUseCase: Send + Sync + 'static {
         user_id: &str,
         author_data: UpdateAuthorDto,
     ) -> Result<AuthorMutationResultDto, UseCaseError>;
+    async fn patch_author(
+        &self,
+        user_id: &str,
+        author_data: PatchAuthorDto,
+    ) -> Result<AuthorMutationResultDto, UseCaseError>;
     async fn delete_author(
         &self,
         user_id: &str,
         author_id: &str,
     ) -> Result<DeleteAuthorResultDto, UseCaseError>;
+    async fn delete_authors(
+        &self,
+        user_id: &str,
+        author_ids: Vec<String>,
+    ) -> Result<DeleteAuthorsResultDto, UseCaseError>;
     async fn merge_author(
         &self,
         user_id: &str,
@@ -58,15 +94,44 @@ pub trait MutationUseCase: Send + Sync + 'static {
         &self,
         user_id: &str,
         event_id: i64,
+        cascade: bool,
     ) -> Result<RestoreBookResultDto, UseCaseError>;
     async fn restore_author(
         &self,
         user_id: &str,
         event_id: i64,
     ) -> Result<RestoreAuthorResultDto, UseCaseError>;
+    async fn revert_event_set(
+        &self,
+        user_id: &str,
+        event_set_id: &str,
+        dry_run: bool,
+    ) -> Result<RevertEventSetResultDto, UseCaseError>;
+    async fn purge_trash(
+        &self,
+        user_id: &str,
+        retention_days: i32,
+    ) -> Result<PurgedTrashDto, UseCaseError>;
     async fn import_books(
         &self,
         user_id: &str,
         books: Vec<ImportBookEntryDto>,
+        options: ImportOptionsDto,
     ) -> Result<ImportBooksResultDto, UseCaseError>;
+    async fn apply_changes(
+        &self,
+        user_id: &str,
+        operations: Vec<ChangeOperationDto>,
+    ) -> Result<ApplyChangesResultDto, UseCaseError>;
+    async fn start_import(
+        &self,
+        user_id: &str,
+        books: Vec<ImportBookEntryDto>,
+        options: ImportJobOptionsDto,
+    ) -> Result<ImportJobDto, UseCaseError>;
+    async fn cancel_import(
+        &self,
+        user_id: &str,
+        job_id: &str,
+    ) -> Result<ImportJobDto, UseCaseError>;
 }
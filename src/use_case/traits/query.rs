@@ -3,15 +3,24 @@ use std::collections::HashMap;
 use async_trait::async_trait;
 use mockall::automock;
 
-use crate::use_case::{
-    dto::{
-        author::AuthorDto,
-        book::BookDto,
-        event::{AuthorEventDto, BookEventDto},
-        event_set::{EventSetDetailDto, EventSetDto},
-        user::UserDto,
+use crate::{
+    common::sort::{AuthorOrder, BookOrder},
+    use_case::{
+        dto::{
+            activity::{ActivityPeriodDto, ActivityRangeDto},
+            author::{AuthorCursorDto, AuthorDto},
+            book::{BookCursorDto, BookDto, BookFilterDto},
+            event::{AuthorEventDto, BookEventDto, DeletedAuthorDto, DeletedBookDto},
+            event_set::{EventSetDetailDto, EventSetDto},
+            history::{HistoryCursorDto, HistoryFilterDto, LibraryDiffDto, PointInTimeDto},
+            import_job::ImportJobDto,
+            pagination::{PageDto, PageRequestDto},
+            search::SearchResultDto,
+            stats::LibraryStatsDto,
+            user::UserDto,
+        },
+        error::UseCaseError,
     },
-    error::UseCaseError,
 };
 
 #[automock]
@@ -23,37 +32,129 @@ pub trait QueryUseCase: Send + Sync + 'static {
         user_id: &str,
         book_id: &str,
     ) -> Result<Option<BookDto>, UseCaseError>;
-    async fn find_all_books(&self, user_id: &str) -> Result<Vec<BookDto>, UseCaseError>;
+    async fn find_all_books(
+        &self,
+        user_id: &str,
+        filter: BookFilterDto,
+        order: BookOrder,
+    ) -> Result<Vec<BookDto>, UseCaseError>;
+    async fn find_books_page(
+        &self,
+        user_id: &str,
+        filter: BookFilterDto,
+        order: BookOrder,
+        page: PageRequestDto<BookCursorDto>,
+    ) -> Result<PageDto<BookDto, BookCursorDto>, UseCaseError>;
+    async fn find_books_page_by_author_id(
+        &self,
+        user_id: &str,
+        author_id: &str,
+        order: BookOrder,
+        page: PageRequestDto<BookCursorDto>,
+    ) -> Result<PageDto<BookDto, BookCursorDto>, UseCaseError>;
     async fn find_books_by_author_ids_as_hash_map(
         &self,
         user_id: &str,
         author_ids: &[String],
+        order: BookOrder,
     ) -> Result<HashMap<String, Vec<BookDto>>, UseCaseError>;
     async fn find_author_by_id(
         &self,
         user_id: &str,
         author_id: &str,
     ) -> Result<Option<AuthorDto>, UseCaseError>;
-    async fn find_all_authors(&self, user_id: &str) -> Result<Vec<AuthorDto>, UseCaseError>;
+    async fn find_all_authors(
+        &self,
+        user_id: &str,
+        order: AuthorOrder,
+    ) -> Result<Vec<AuthorDto>, UseCaseError>;
+    async fn find_authors_page(
+        &self,
+        user_id: &str,
+        order: AuthorOrder,
+        page: PageRequestDto<AuthorCursorDto>,
+    ) -> Result<PageDto<AuthorDto, AuthorCursorDto>, UseCaseError>;
     async fn find_author_by_ids_as_hash_map(
         &self,
         user_id: &str,
         author_ids: &[String],
     ) -> Result<HashMap<String, AuthorDto>, UseCaseError>;
+    async fn search(
+        &self,
+        user_id: &str,
+        query: &str,
+        limit: i32,
+    ) -> Result<SearchResultDto, UseCaseError>;
+    async fn library_stats(
+        &self,
+        user_id: &str,
+        top_authors: i32,
+    ) -> Result<LibraryStatsDto, UseCaseError>;
+    async fn reading_activity(
+        &self,
+        user_id: &str,
+        range: ActivityRangeDto,
+    ) -> Result<Vec<ActivityPeriodDto>, UseCaseError>;
+    async fn find_books_as_of(
+        &self,
+        user_id: &str,
+        at: PointInTimeDto,
+    ) -> Result<Vec<BookDto>, UseCaseError>;
+    async fn find_authors_as_of(
+        &self,
+        user_id: &str,
+        at: PointInTimeDto,
+    ) -> Result<Vec<AuthorDto>, UseCaseError>;
+    async fn diff_library(
+        &self,
+        user_id: &str,
+        from: PointInTimeDto,
+        to: PointInTimeDto,
+    ) -> Result<LibraryDiffDto, UseCaseError>;
     async fn list_book_events(
         &self,
         user_id: &str,
         book_id: &str,
     ) -> Result<Vec<BookEventDto>, UseCaseError>;
+    async fn find_book_events_page(
+        &self,
+        user_id: &str,
+        book_id: &str,
+        filter: HistoryFilterDto,
+        page: PageRequestDto<HistoryCursorDto>,
+    ) -> Result<PageDto<BookEventDto, HistoryCursorDto>, UseCaseError>;
     async fn list_author_events(
         &self,
         user_id: &str,
         author_id: &str,
     ) -> Result<Vec<AuthorEventDto>, UseCaseError>;
+    async fn find_author_events_page(
+        &self,
+        user_id: &str,
+        author_id: &str,
+        filter: HistoryFilterDto,
+        page: PageRequestDto<HistoryCursorDto>,
+    ) -> Result<PageDto<AuthorEventDto, HistoryCursorDto>, UseCaseError>;
+    async fn list_deleted_books(&self, user_id: &str) -> Result<Vec<DeletedBookDto>, UseCaseError>;
+    async fn list_deleted_authors(
+        &self,
+        user_id: &str,
+    ) -> Result<Vec<DeletedAuthorDto>, UseCaseError>;
     async fn list_event_sets(&self, user_id: &str) -> Result<Vec<EventSetDto>, UseCaseError>;
+    async fn find_event_sets_page(
+        &self,
+        user_id: &str,
+        filter: HistoryFilterDto,
+        page: PageRequestDto<HistoryCursorDto>,
+    ) -> Result<PageDto<EventSetDto, HistoryCursorDto>, UseCaseError>;
     async fn find_event_set(
         &self,
         user_id: &str,
         event_set_id: &str,
     ) -> Result<Option<EventSetDetailDto>, UseCaseError>;
+    async fn find_import_job(
+        &self,
+        user_id: &str,
+        job_id: &str,
+    ) -> Result<Option<ImportJobDto>, UseCaseError>;
 }